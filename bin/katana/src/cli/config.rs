@@ -1,18 +1,50 @@
-use anyhow::Result;
-use clap::Args;
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use clap::{Args, Subcommand};
 use katana_chain_spec::rollup::LocalChainConfigDir;
+use katana_cli::file::NodeArgsConfig;
 use katana_primitives::chain::ChainId;
 use starknet::core::utils::parse_cairo_short_string;
 
 #[derive(Debug, Args)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct ConfigArgs {
+    #[command(subcommand)]
+    commands: Option<Commands>,
+
     /// The chain id.
     #[arg(value_parser = ChainId::parse)]
     chain: Option<ChainId>,
 }
 
+#[derive(Debug, Subcommand)]
+enum Commands {
+    #[command(about = "Creates a node configuration file with every option commented out")]
+    Init {
+        #[arg(help = "Path to the configuration file to create")]
+        #[arg(default_value = "katana.toml")]
+        output: PathBuf,
+
+        #[arg(long)]
+        #[arg(help = "Overwrite the file if it already exists")]
+        force: bool,
+    },
+}
+
 impl ConfigArgs {
     pub fn execute(self) -> Result<()> {
+        if let Some(Commands::Init { output, force }) = self.commands {
+            if output.exists() && !force {
+                bail!("{} already exists, use --force to overwrite it", output.display());
+            }
+
+            std::fs::write(&output, NodeArgsConfig::template()?)?;
+            println!("Configuration file created at {}", output.display());
+            println!("Start the node with it using `katana --config {}`", output.display());
+            return Ok(());
+        }
+
         match self.chain {
             Some(chain) => {
                 let path = LocalChainConfigDir::open(&chain)?.config_path();
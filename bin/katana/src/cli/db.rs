@@ -1,4 +1,5 @@
-use std::path::{self};
+use std::fs::File;
+use std::path::{self, PathBuf};
 
 use anyhow::{Context, Result};
 use clap::{Args, Subcommand};
@@ -8,6 +9,8 @@ use comfy_table::Table;
 use katana_db::abstraction::Database;
 use katana_db::mdbx::{DbEnv, DbEnvKind};
 use katana_db::tables::NUM_TABLES;
+use katana_node::snapshot;
+use katana_provider::providers::db::DbProvider;
 
 /// Create a human-readable byte unit string (eg. 16.00 KiB)
 macro_rules! byte_unit {
@@ -36,6 +39,18 @@ pub struct DbArgs {
 enum Commands {
     #[command(about = "Retrieves database statistics")]
     Stats,
+
+    #[command(about = "Exports the chain into a portable snapshot file")]
+    Export {
+        #[arg(help = "Path to the snapshot file to create")]
+        output: PathBuf,
+    },
+
+    #[command(about = "Imports the chain from a snapshot file into a new database")]
+    Import {
+        #[arg(help = "Path to the snapshot file to import")]
+        input: PathBuf,
+    },
 }
 
 impl DbArgs {
@@ -121,6 +136,26 @@ impl DbArgs {
 
                 println!("{table}");
             }
+
+            Commands::Export { output } => {
+                let provider = DbProvider::new(open_db_ro(&self.path)?);
+                let file = File::create(&output).with_context(|| {
+                    format!("Creating snapshot file at path {}", output.display())
+                })?;
+
+                let total = snapshot::export_snapshot(&provider, file)?;
+                println!("Exported {total} blocks to {}", output.display());
+            }
+
+            Commands::Import { input } => {
+                let file = File::open(&input).with_context(|| {
+                    format!("Opening snapshot file at path {}", input.display())
+                })?;
+
+                let path = path::absolute(shellexpand::full(&self.path)?.into_owned())?;
+                let total = snapshot::import_snapshot_at(&path, file)?;
+                println!("Imported {total} blocks into {}", path.display());
+            }
         }
 
         Ok(())
@@ -41,7 +41,7 @@ enum Commands {
     #[command(about = "Initialize chain")]
     Init(Box<init::InitArgs>),
 
-    #[command(about = "Chain configuration utilities")]
+    #[command(about = "Chain and node configuration utilities")]
     Config(config::ConfigArgs),
 
     #[command(about = "Database utilities")]
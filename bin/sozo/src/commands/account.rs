@@ -4,6 +4,7 @@ use anyhow::Result;
 use clap::{Args, Subcommand};
 use scarb::core::Config;
 use sozo_ops::account;
+use sozo_scarbext::WorkspaceExt;
 use starknet::core::types::Felt;
 use starknet::signers::LocalWallet;
 use tracing::trace;
@@ -11,7 +12,6 @@ use tracing::trace;
 use super::options::signer::SignerOptions;
 use super::options::starknet::StarknetOptions;
 use super::options::transaction::TransactionOptions;
-use crate::utils;
 
 #[derive(Debug, Args)]
 pub struct AccountArgs {
@@ -89,7 +89,14 @@ pub enum AccountCommand {
 impl AccountArgs {
     pub fn run(self, config: &Config) -> Result<()> {
         trace!(args = ?self);
-        let env_metadata = utils::load_metadata_from_config(config)?;
+        // the accounts can be managed outside of a project
+        let env_metadata = if config.manifest_path().exists() {
+            let ws = scarb::ops::read_workspace(config.manifest_path(), config)?;
+            ws.load_profile_config()?.env
+        } else {
+            trace!("Manifest path does not exist.");
+            None
+        };
 
         config.tokio_handle().block_on(async {
             match self.command {
@@ -109,7 +116,7 @@ impl AccountArgs {
                     file,
                     no_confirmation,
                 } => {
-                    let provider = starknet.provider(env_metadata.as_ref())?;
+                    let (provider, _) = starknet.provider(env_metadata.as_ref())?;
                     let signer = signer.signer(env_metadata.as_ref(), false)?;
                     let txn_action = transaction.to_txn_action(simulate, estimate_only)?;
                     trace!(
@@ -136,7 +143,7 @@ impl AccountArgs {
                 }
                 AccountCommand::Fetch { starknet, force, output, address } => {
                     trace!(?starknet, force, ?output, ?address, "Executing Fetch command.");
-                    let provider = starknet.provider(env_metadata.as_ref())?;
+                    let (provider, _) = starknet.provider(env_metadata.as_ref())?;
                     account::fetch(provider, force, output, address).await
                 }
             }
@@ -1,7 +1,9 @@
 use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use cainome::cairo_serde::ContractAddress;
 use clap::{Args, Subcommand};
 use colored::Colorize;
@@ -104,6 +106,12 @@ pub struct CommonAuthOptions {
 
     #[command(flatten)]
     transaction: TransactionOptions,
+
+    #[arg(long, value_name = "PATH")]
+    #[arg(help = "Write the calls as JSON into this file instead of sending them, to have them \
+                  signed and sent by another account, like a multisig.")]
+    #[arg(global = true)]
+    export_calls: Option<PathBuf>,
 }
 
 #[derive(Debug, Subcommand)]
@@ -111,7 +119,7 @@ pub enum AuthKind {
     #[command(about = "Grant to a contract the permission to write to a resource.")]
     Writer {
         #[arg(num_args = 1..)]
-        #[arg(required = true)]
+        #[arg(required_unless_present = "file")]
         #[arg(value_name = "resource_tag,contract_tag_or_address")]
         #[arg(help = "A list of resource/contract couples to grant write access to.
 Comma separated values to indicate resource identifier and contract tag or address.\n
@@ -120,12 +128,18 @@ Some examples:
    ns,ns-actions
 ")]
         pairs: Vec<PermissionPair>,
+
+        #[arg(long, value_name = "PATH")]
+        #[arg(help = "A file with one resource/contract couple per line, in addition to the \
+                      couples given as arguments. Empty lines and lines starting with `#` are \
+                      ignored.")]
+        file: Option<PathBuf>,
     },
 
     #[command(about = "Grant to a contract the ownership of a resource.")]
     Owner {
         #[arg(num_args = 1..)]
-        #[arg(required = true)]
+        #[arg(required_unless_present = "file")]
         #[arg(value_name = "resource_tag,contract_tag_or_address")]
         #[arg(help = "A list of resources and owners to grant ownership to.
 Comma separated values to indicate resource identifier and owner address.\n
@@ -134,6 +148,12 @@ Some examples:
    ns,0xbeef
 ")]
         pairs: Vec<PermissionPair>,
+
+        #[arg(long, value_name = "PATH")]
+        #[arg(help = "A file with one resource/owner couple per line, in addition to the \
+                      couples given as arguments. Empty lines and lines starting with `#` are \
+                      ignored.")]
+        file: Option<PathBuf>,
     },
 }
 
@@ -159,11 +179,13 @@ impl AuthArgs {
                     let do_grant = true;
 
                     match kind {
-                        AuthKind::Writer { pairs } => {
+                        AuthKind::Writer { pairs, file } => {
+                            let pairs = with_file_pairs(pairs, file.as_deref())?;
                             update_writers(&contracts, &common, &profile_config, pairs, do_grant)
                                 .await?;
                         }
-                        AuthKind::Owner { pairs } => {
+                        AuthKind::Owner { pairs, file } => {
+                            let pairs = with_file_pairs(pairs, file.as_deref())?;
                             update_owners(&contracts, &common, &profile_config, pairs, do_grant)
                                 .await?;
                         }
@@ -182,11 +204,13 @@ impl AuthArgs {
                     let do_grant = false;
 
                     match kind {
-                        AuthKind::Writer { pairs } => {
+                        AuthKind::Writer { pairs, file } => {
+                            let pairs = with_file_pairs(pairs, file.as_deref())?;
                             update_writers(&contracts, &common, &profile_config, pairs, do_grant)
                                 .await?;
                         }
-                        AuthKind::Owner { pairs } => {
+                        AuthKind::Owner { pairs, file } => {
+                            let pairs = with_file_pairs(pairs, file.as_deref())?;
                             update_owners(&contracts, &common, &profile_config, pairs, do_grant)
                                 .await?;
                         }
@@ -377,6 +401,10 @@ async fn clone_permissions(
         }
     }
 
+    if let Some(path) = &options.export_calls {
+        return utils::export_calls(path, &invoker.calls);
+    }
+
     let res = invoker.multicall().await?;
     println!("{}", res);
 
@@ -574,7 +602,24 @@ async fn update_owners(
     let world = get_world_contract(contracts, options, profile_config).await?;
 
     let mut invoker = Invoker::new(&world.account, options.transaction.clone().try_into()?);
+    let mut seen = HashSet::new();
     for (selector, address) in selectors_addresses {
+        if !seen.insert((selector, address)) {
+            continue;
+        }
+
+        // Only the permissions that are not already in the expected state are updated, so the
+        // same permissions can be applied several times.
+        let is_owner = world.is_owner(&selector, &ContractAddress(address)).call().await?;
+        if is_owner == do_grant {
+            trace!(
+                selector = format!("{:#066x}", selector),
+                address = format!("{:#066x}", address),
+                "Skipping owner permission already up to date."
+            );
+            continue;
+        }
+
         let call = if do_grant {
             trace!(
                 selector = format!("{:#066x}", selector),
@@ -594,6 +639,15 @@ async fn update_owners(
         invoker.add_call(call);
     }
 
+    if invoker.calls.is_empty() {
+        println!("Permissions already up to date, nothing to do.");
+        return Ok(());
+    }
+
+    if let Some(path) = &options.export_calls {
+        return utils::export_calls(path, &invoker.calls);
+    }
+
     let res = invoker.multicall().await?;
     println!("{}", res);
 
@@ -616,7 +670,24 @@ async fn update_writers(
     let world = get_world_contract(contracts, options, profile_config).await?;
 
     let mut invoker = Invoker::new(&world.account, options.transaction.clone().try_into()?);
+    let mut seen = HashSet::new();
     for (selector, address) in selectors_addresses {
+        if !seen.insert((selector, address)) {
+            continue;
+        }
+
+        // Only the permissions that are not already in the expected state are updated, so the
+        // same permissions can be applied several times.
+        let is_writer = world.is_writer(&selector, &ContractAddress(address)).call().await?;
+        if is_writer == do_grant {
+            trace!(
+                selector = format!("{:#066x}", selector),
+                address = format!("{:#066x}", address),
+                "Skipping writer permission already up to date."
+            );
+            continue;
+        }
+
         let call = if do_grant {
             trace!(
                 selector = format!("{:#066x}", selector),
@@ -636,12 +707,51 @@ async fn update_writers(
         invoker.add_call(call);
     }
 
+    if invoker.calls.is_empty() {
+        println!("Permissions already up to date, nothing to do.");
+        return Ok(());
+    }
+
+    if let Some(path) = &options.export_calls {
+        return utils::export_calls(path, &invoker.calls);
+    }
+
     let res = invoker.multicall().await?;
     println!("{}", res);
 
     Ok(())
 }
 
+/// Appends the permission pairs read from `file`, if any, to the pairs given as arguments.
+fn with_file_pairs(
+    mut pairs: Vec<PermissionPair>,
+    file: Option<&Path>,
+) -> Result<Vec<PermissionPair>> {
+    if let Some(file) = file {
+        let content = fs::read_to_string(file)
+            .with_context(|| format!("Failed to read the permissions file {}.", file.display()))?;
+        pairs.extend(parse_permission_pairs(&content)?);
+    }
+
+    Ok(pairs)
+}
+
+/// Parses the permission pairs of a permissions file, one pair per line.
+///
+/// Empty lines and lines starting with `#` are ignored.
+fn parse_permission_pairs(content: &str) -> Result<Vec<PermissionPair>> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| {
+            PermissionPair::from_str(line)
+                .with_context(|| format!("Invalid pair at line {}.", i + 1))
+        })
+        .collect()
+}
+
 /// Gets the world contract from the contracts map and initializes a world contract instance
 /// from the environment.
 async fn get_world_contract(
@@ -742,6 +852,34 @@ mod tests {
         assert!(PermissionPair::from_str("moves,actions,extra").is_err());
     }
 
+    #[test]
+    fn test_parse_permission_pairs() {
+        let content = "
+        # Writers of the namespace.
+        ns,ns-actions
+
+        ns-Moves,0x123
+        ";
+
+        let pairs = parse_permission_pairs(content).unwrap();
+        assert_eq!(
+            pairs,
+            vec![
+                PermissionPair {
+                    resource_tag: "ns".to_string(),
+                    grantee_tag_or_address: "ns-actions".to_string(),
+                },
+                PermissionPair {
+                    resource_tag: "ns-Moves".to_string(),
+                    grantee_tag_or_address: "0x123".to_string(),
+                },
+            ]
+        );
+
+        let error = parse_permission_pairs("ns,ns-actions\nns-Moves").unwrap_err();
+        assert_eq!(error.to_string(), "Invalid pair at line 2.");
+    }
+
     #[test]
     fn test_permission_pair_to_selector_and_address() {
         let mut contracts = HashMap::new();
@@ -751,6 +889,7 @@ mod tests {
                 tag_or_name: "actions".to_string(),
                 address: Felt::from_str("0x456").unwrap(),
                 entrypoints: vec![],
+                abi: vec![],
             },
         );
 
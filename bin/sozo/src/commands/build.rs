@@ -1,6 +1,6 @@
 use std::cmp::Reverse;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Args, Parser};
 use colored::{ColoredString, Colorize};
 use dojo_bindgen::{BuiltinPlugins, PluginManager};
@@ -23,7 +23,7 @@ pub struct BuildArgs {
     pub typescript: bool,
 
     #[arg(long)]
-    #[arg(help = "Generate Typescript bindings.")]
+    #[arg(help = "Generate Typescript bindings with the v2 generator.")]
     pub typescript_v2: bool,
 
     #[arg(long)]
@@ -153,7 +153,10 @@ impl BuildArgs {
 
         // TODO: check about the skip migration as now we process the metadata
         // directly during the compilation to get the data we need from it.
-        config.tokio_handle().block_on(bindgen.generate(None)).expect("Error generating bindings");
+        config
+            .tokio_handle()
+            .block_on(bindgen.generate(None))
+            .context("Failed to generate the bindings.")?;
 
         if self.stats != StatOptions::default() {
             let world = WorldLocal::from_directory(
@@ -64,8 +64,6 @@ impl CallArgs {
 
             let local_manifest = ws.read_manifest_profile()?;
 
-            let calldata = calldata_decoder::decode_calldata(&self.calldata)?;
-
             let contracts: HashMap<String, ContractInfo> = if self.diff || local_manifest.is_none()
             {
                 let (world_diff, _, _) =
@@ -108,6 +106,14 @@ impl CallArgs {
             let contract_address = contract_address
                 .ok_or_else(|| anyhow!("Contract {descriptor} not found in the world diff."))?;
 
+            // the ABI of the contract, if known, is used to serialize typed values
+            let abi = contracts
+                .values()
+                .find(|c| c.address == contract_address)
+                .map_or(&[][..], |c| c.abi.as_slice());
+            let calldata =
+                calldata_decoder::decode_calldata_with_abi(abi, &self.entrypoint, &self.calldata)?;
+
             let block_id = if let Some(block_id) = self.block_id {
                 dojo_utils::parse_block_id(block_id)?
             } else {
@@ -93,6 +93,7 @@ impl DevArgs {
             account: self.account,
             transaction: self.transaction,
             ipfs: IpfsOptions::default(),
+            dry_run: false,
         };
 
         let _ = migrate_args.clone().run(config);
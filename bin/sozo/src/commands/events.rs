@@ -1,16 +1,21 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use clap::Args;
 use colored::Colorize;
+use dojo_types::schema::{Struct, Ty};
 use dojo_world::contracts::abigen::world::{self, Event as WorldEvent};
+use dojo_world::contracts::model::ModelReader;
+use dojo_world::contracts::WorldContractReader;
 use dojo_world::diff::WorldDiff;
 use scarb::core::Config;
+use serde_json::{json, Map, Value};
 use sozo_ops::model;
 use sozo_scarbext::WorkspaceExt;
-use starknet::core::types::{BlockId, BlockTag, EventFilter, Felt};
-use starknet::core::utils::starknet_keccak;
+use starknet::core::types::{BlockId, BlockTag, EmittedEvent, EventFilter, Felt};
+use starknet::core::utils::{get_selector_from_name, starknet_keccak};
 use starknet::macros::felt;
 use starknet::providers::Provider;
 use tracing::trace;
@@ -19,6 +24,9 @@ use super::options::starknet::StarknetOptions;
 use super::options::world::WorldOptions;
 use crate::utils;
 
+/// The interval between two polls of the new blocks when following the events.
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
 #[derive(Debug, Args)]
 pub struct EventsArgs {
     #[arg(help = "List of specific events to be filtered")]
@@ -45,9 +53,14 @@ pub struct EventsArgs {
     pub max_block_range: u64,
 
     #[arg(long)]
-    #[arg(help = "Print values as raw json")]
+    #[arg(help = "Print the events as json, one event per line")]
     pub json: bool,
 
+    #[arg(long)]
+    #[arg(help = "Keep watching for new events once the existing ones are printed")]
+    #[arg(conflicts_with = "to_block")]
+    pub follow: bool,
+
     #[command(flatten)]
     pub world: WorldOptions,
 
@@ -62,10 +75,10 @@ impl EventsArgs {
             let profile_config = ws.load_profile_config()?;
 
             let (world_diff, provider, _) =
-                utils::get_world_diff_and_provider(self.starknet, self.world, &ws).await?;
+                utils::get_world_diff_and_provider(self.starknet.clone(), self.world.clone(), &ws)
+                    .await?;
             let provider = Arc::new(provider);
 
-            let latest_block = provider.block_number().await?;
             let from_block = if let Some(world_block) =
                 profile_config.env.as_ref().and_then(|e| e.world_block)
             {
@@ -73,353 +86,491 @@ impl EventsArgs {
             } else {
                 self.from_block.unwrap_or(0)
             };
-            let to_block = self.to_block.unwrap_or(latest_block);
 
             let chain_id = provider.chain_id().await?;
             // Katana if it's not `SN_SEPOLIA` or `SN_MAIN`.
             let is_katana = chain_id != felt!("0x534e5f5345504f4c4941")
                 && chain_id != felt!("0x534e5f4d41494e");
 
+            let mut decoder = EventDecoder::new(&world_diff);
             let mut current_from = from_block;
-            let mut events = Vec::new();
-
-            while current_from <= to_block {
-                let current_to = std::cmp::min(current_from + self.max_block_range - 1, to_block);
-
-                let filter = EventFilter {
-                    from_block: Some(BlockId::Number(current_from)),
-                    to_block: Some(BlockId::Number(current_to)),
-                    address: Some(world_diff.world_info.address),
-                    keys: self.events.as_ref().map(|e| {
-                        vec![e.iter().map(|event| starknet_keccak(event.as_bytes())).collect()]
-                    }),
+
+            loop {
+                let to_block = match self.to_block {
+                    Some(to_block) => to_block,
+                    None => provider.block_number().await?,
                 };
 
-                trace!(
-                    world_address = format!("{:#066x}", world_diff.world_info.address),
-                    self.chunk_size,
-                    ?filter,
-                    "Fetching remote world events for block range {}-{}.",
-                    current_from,
-                    current_to
-                );
-
-                let mut continuation_token = None;
-                loop {
-                    let page = provider
-                        .get_events(filter.clone(), continuation_token, self.chunk_size)
+                if current_from <= to_block {
+                    let events = self
+                        .fetch_events(
+                            &provider,
+                            world_diff.world_info.address,
+                            current_from,
+                            to_block,
+                            is_katana,
+                        )
                         .await?;
 
-                    if is_katana && page.events.is_empty() {
-                        break;
+                    for event in &events {
+                        match world::Event::try_from(event) {
+                            Ok(ev) => {
+                                trace!(?ev, "Processing world event.");
+                                let decoded = decoder.decode(&ev, event, &provider).await;
+                                decoded.print(
+                                    event.block_number,
+                                    event.transaction_hash,
+                                    self.json,
+                                );
+                            }
+                            Err(e) => {
+                                tracing::error!(
+                                    ?e,
+                                    "Failed to parse remote world event which is supposed to be \
+                                     valid."
+                                );
+                            }
+                        }
                     }
 
-                    events.extend(page.events);
+                    current_from = to_block + 1;
+                }
 
-                    continuation_token = page.continuation_token;
-                    if continuation_token.is_none() {
-                        break;
-                    }
+                if !self.follow {
+                    break;
                 }
 
-                current_from = current_to + 1;
+                tokio::time::sleep(FOLLOW_POLL_INTERVAL).await;
             }
 
+            Ok(())
+        })
+    }
+
+    /// Fetches the events of the world emitted between `from_block` and `to_block`, splitting the
+    /// range into ranges of at most `max_block_range` blocks.
+    async fn fetch_events<P: Provider>(
+        &self,
+        provider: &P,
+        world_address: Felt,
+        from_block: u64,
+        to_block: u64,
+        is_katana: bool,
+    ) -> Result<Vec<EmittedEvent>> {
+        let mut current_from = from_block;
+        let mut events = Vec::new();
+
+        while current_from <= to_block {
+            let current_to = std::cmp::min(current_from + self.max_block_range - 1, to_block);
+
+            let filter = EventFilter {
+                from_block: Some(BlockId::Number(current_from)),
+                to_block: Some(BlockId::Number(current_to)),
+                address: Some(world_address),
+                keys: self.events.as_ref().map(|e| {
+                    vec![e.iter().map(|event| starknet_keccak(event.as_bytes())).collect()]
+                }),
+            };
+
             trace!(
-                events_count = events.len(),
-                world_address = format!("{:#066x}", world_diff.world_info.address),
-                "Fetched events for world."
+                world_address = format!("{:#066x}", world_address),
+                self.chunk_size,
+                ?filter,
+                "Fetching remote world events for block range {}-{}.",
+                current_from,
+                current_to
             );
 
-            for event in &events {
-                match world::Event::try_from(event) {
-                    Ok(ev) => {
-                        trace!(?ev, "Processing world event.");
-                        match_event(
-                            &ev,
-                            &world_diff,
-                            event.block_number,
-                            event.transaction_hash,
-                            &provider,
-                        )
-                        .await?;
-                    }
-                    Err(e) => {
-                        tracing::error!(
-                            ?e,
-                            "Failed to parse remote world event which is supposed to be valid."
-                        );
-                    }
+            let mut continuation_token = None;
+            loop {
+                let page = provider
+                    .get_events(filter.clone(), continuation_token, self.chunk_size)
+                    .await?;
+
+                if is_katana && page.events.is_empty() {
+                    break;
+                }
+
+                events.extend(page.events);
+
+                continuation_token = page.continuation_token;
+                if continuation_token.is_none() {
+                    break;
                 }
             }
 
-            Ok(())
-        })
+            current_from = current_to + 1;
+        }
+
+        trace!(
+            events_count = events.len(),
+            world_address = format!("{:#066x}", world_address),
+            "Fetched events for world."
+        );
+
+        Ok(events)
     }
 }
 
-/// Matches the event and prints it's content.
-async fn match_event<P: Provider + Send + Sync>(
-    event: &WorldEvent,
-    world_diff: &WorldDiff,
-    block_number: Option<u64>,
-    transaction_hash: Felt,
-    provider: P,
-) -> Result<()> {
-    // Get a mapping of all the known selectors and their addresses.
-    let contract_addresses_from_selector = world_diff.get_contracts_addresses();
-    // Do a reverse mapping to retrieve a contract selector from it's address.
-    let contract_selectors_from_address: HashMap<Felt, Felt> =
-        contract_addresses_from_selector.into_iter().map(|(s, a)| (a, s)).collect();
-    // Finally, cache all the known tags by creating them once for each selector.
-    let mut tags = HashMap::new();
-    for (s, r) in world_diff.resources.iter() {
-        tags.insert(s, r.tag());
+/// A world event decoded for printing.
+#[derive(Debug)]
+struct DecodedEvent {
+    /// The name of the event in the world contract, eg `StoreSetRecord`.
+    kind: &'static str,
+    /// The human-readable name of the event.
+    title: &'static str,
+    /// The tag of the resource the event relates to, if any.
+    tag: Option<String>,
+    fields: Vec<(&'static str, Field)>,
+}
+
+/// A field of a decoded event.
+#[derive(Debug)]
+enum Field {
+    Text(String),
+    Felt(Felt),
+    Felts(Vec<Felt>),
+    /// A value decoded with the schema of a model or an event.
+    Record {
+        pretty: String,
+        json: Value,
+    },
+}
+
+impl DecodedEvent {
+    fn new(kind: &'static str, title: &'static str, tag: Option<String>) -> Self {
+        Self { kind, title, tag, fields: vec![] }
     }
 
-    let block_id = if let Some(block_number) = block_number {
-        BlockId::Number(block_number)
-    } else {
-        BlockId::Tag(BlockTag::Pending)
-    };
-
-    let (name, content) = match event {
-        WorldEvent::WorldSpawned(e) => (
-            "World spawned".to_string(),
-            format!("Creator address: {:?}\nWorld class hash: {:#066x}", e.creator, e.class_hash.0),
-        ),
-        WorldEvent::WorldUpgraded(e) => {
-            ("World upgraded".to_string(), format!("World class hash: {:#066x}", e.class_hash.0))
-        }
-        WorldEvent::NamespaceRegistered(e) => {
-            ("Namespace registered".to_string(), format!("Namespace: {}", e.namespace.to_string()?))
-        }
-        WorldEvent::ModelRegistered(e) => (
-            "Model registered".to_string(),
-            format!(
-                "Namespace: {}\nName: {}\nClass hash: {:#066x}\nAddress: {:#066x}",
-                e.namespace.to_string()?,
-                e.name.to_string()?,
-                e.class_hash.0,
-                e.address.0
-            ),
-        ),
-        WorldEvent::EventRegistered(e) => (
-            "Event registered".to_string(),
-            format!(
-                "Namespace: {}\nName: {}\nClass hash: {:#066x}\nAddress: {:#066x}",
-                e.namespace.to_string()?,
-                e.name.to_string()?,
-                e.class_hash.0,
-                e.address.0
-            ),
-        ),
-        WorldEvent::ContractRegistered(e) => (
-            "Contract registered".to_string(),
-            format!(
-                "Namespace: {}\nName: {}\nClass hash: {:#066x}\nAddress: {:#066x}\nSalt: {:#066x}",
-                e.namespace.to_string()?,
-                e.name.to_string()?,
-                e.class_hash.0,
-                e.address.0,
-                e.salt
-            ),
-        ),
-        WorldEvent::ModelUpgraded(e) => {
-            let tag = get_tag(e.selector, &tags);
-            (
-                format!("Model upgraded ({})", tag),
-                format!(
-                    "Selector: {:#066x}\nClass hash: {:#066x}\nAddress: {:#066x}\nPrev address: \
-                     {:#066x}",
-                    e.selector, e.class_hash.0, e.address.0, e.prev_address.0
-                ),
-            )
-        }
-        WorldEvent::EventUpgraded(e) => {
-            let tag = get_tag(e.selector, &tags);
-            (
-                format!("Event upgraded ({})", tag),
-                format!(
-                    "Selector: {:#066x}\nClass hash: {:#066x}\nAddress: {:#066x}\nPrev address: \
-                     {:#066x}",
-                    e.selector, e.class_hash.0, e.address.0, e.prev_address.0
-                ),
-            )
-        }
-        WorldEvent::ContractUpgraded(e) => {
-            let tag = get_tag(e.selector, &tags);
-            (
-                format!("Contract upgraded ({})", tag),
-                format!("Selector: {:#066x}\nClass hash: {:#066x}", e.selector, e.class_hash.0,),
-            )
-        }
-        WorldEvent::ContractInitialized(e) => {
-            let tag = get_tag(e.selector, &tags);
-            (
-                format!("Contract initialized ({})", tag),
-                format!(
-                    "Selector: {:#066x}\nInit calldata: {}",
-                    e.selector,
-                    e.init_calldata
-                        .iter()
-                        .map(|f| format!("{:#066x}", f))
-                        .collect::<Vec<String>>()
-                        .join(", ")
-                ),
-            )
+    fn field(mut self, name: &'static str, field: Field) -> Self {
+        self.fields.push((name, field));
+        self
+    }
+
+    fn record(self, name: &'static str, record: Option<Field>) -> Self {
+        match record {
+            Some(record) => self.field(name, record),
+            None => self,
         }
-        WorldEvent::WriterUpdated(e) => {
-            let tag = get_tag(e.resource, &tags);
-            let grantee =
-                if let Some(selector) = contract_selectors_from_address.get(&e.contract.into()) {
-                    tags.get(selector).unwrap().to_string()
-                } else {
-                    format!("{:#066x}", e.contract.0)
-                };
+    }
 
-            (
-                "Writer updated".to_string(),
-                format!("Target resource: {}\nContract: {}\nValue: {}", tag, grantee, e.value),
-            )
+    fn print(&self, block_number: Option<u64>, transaction_hash: Felt, as_json: bool) {
+        if as_json {
+            println!("{}", self.to_json(block_number, transaction_hash));
+            return;
         }
-        WorldEvent::OwnerUpdated(e) => {
-            let tag = get_tag(e.resource, &tags);
-            let grantee =
-                if let Some(selector) = contract_selectors_from_address.get(&e.contract.into()) {
-                    tags.get(selector).unwrap().to_string()
-                } else {
-                    format!("{:#066x}", e.contract.0)
+
+        let name = match &self.tag {
+            Some(tag) => format!("{} ({})", self.title, tag),
+            None => self.title.to_string(),
+        };
+        let block_str = block_number.map(|n| n.to_string()).unwrap_or("pending".to_string());
+        let ptr = format!("[block:{} / tx:{:#066x}]", block_str, transaction_hash).bright_black();
+
+        let content = self
+            .fields
+            .iter()
+            .map(|(name, field)| match field {
+                Field::Text(text) => format!("{name}: {text}"),
+                Field::Felt(felt) => format!("{name}: {:#066x}", felt),
+                Field::Felts(felts) => format!("{name}: {}", format_felts(felts)),
+                Field::Record { pretty, .. } => format!("{name}:\n{pretty}"),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        println!("> {name} {ptr}\n{content}\n-----\n");
+    }
+
+    fn to_json(&self, block_number: Option<u64>, transaction_hash: Felt) -> Value {
+        let data = self
+            .fields
+            .iter()
+            .map(|(name, field)| {
+                let value = match field {
+                    Field::Text(text) => json!(text),
+                    Field::Felt(felt) => json!(format!("{:#066x}", felt)),
+                    Field::Felts(felts) => {
+                        json!(felts.iter().map(|f| format!("{:#066x}", f)).collect::<Vec<_>>())
+                    }
+                    Field::Record { json, .. } => json.clone(),
                 };
+                (name.to_lowercase().replace(' ', "_"), value)
+            })
+            .collect::<Map<_, _>>();
+
+        json!({
+            "event": self.kind,
+            "tag": self.tag,
+            "block_number": block_number,
+            "transaction_hash": format!("{:#066x}", transaction_hash),
+            "data": data,
+        })
+    }
+}
 
-            (
-                "Owner updated".to_string(),
-                format!("Target resource: {}\nContract: {}\nValue: {}", tag, grantee, e.value),
-            )
+/// Decodes the world events, using the schemas of the models and events registered in the world
+/// to decode the records.
+struct EventDecoder {
+    world_address: Felt,
+    /// The tags of the known resources, by selector.
+    tags: HashMap<Felt, String>,
+    /// The selectors of the known contracts, by address.
+    contract_selectors: HashMap<Felt, Felt>,
+    /// The schemas of the models and events already fetched, `None` if it can't be fetched.
+    schemas: HashMap<Felt, Option<Ty>>,
+}
+
+impl EventDecoder {
+    fn new(world_diff: &WorldDiff) -> Self {
+        // Do a reverse mapping to retrieve a contract selector from it's address.
+        let contract_selectors =
+            world_diff.get_contracts_addresses().into_iter().map(|(s, a)| (a, s)).collect();
+        // Cache all the known tags by creating them once for each selector.
+        let tags = world_diff.resources.iter().map(|(s, r)| (*s, r.tag())).collect();
+
+        Self {
+            world_address: world_diff.world_info.address,
+            tags,
+            contract_selectors,
+            schemas: HashMap::new(),
         }
-        WorldEvent::StoreSetRecord(e) => {
-            let tag = get_tag(e.selector, &tags);
-            let record = if let Ok((record_str, _, _)) = model::model_get(
-                tag.clone(),
-                e.keys.clone(),
-                world_diff.world_info.address,
-                provider,
-                block_id,
-            )
-            .await
-            {
-                record_str
-            } else {
-                "NOT_AVAILABLE".to_string()
-            };
+    }
 
-            (
-                format!("Store set record ({})", tag),
-                format!(
-                    "Selector: {:#066x}\nEntity ID: {:#066x}\nKeys: {}\nValues: {}\nData:\n{}",
-                    e.selector,
-                    e.entity_id,
-                    e.keys
-                        .iter()
-                        .map(|k| format!("{:#066x}", k))
-                        .collect::<Vec<String>>()
-                        .join(", "),
-                    e.values
-                        .iter()
-                        .map(|v| format!("{:#066x}", v))
-                        .collect::<Vec<String>>()
-                        .join(", "),
-                    record
-                ),
+    async fn decode<P: Provider + Send + Sync>(
+        &mut self,
+        event: &WorldEvent,
+        emitted: &EmittedEvent,
+        provider: P,
+    ) -> DecodedEvent {
+        let block_id = if let Some(block_number) = emitted.block_number {
+            BlockId::Number(block_number)
+        } else {
+            BlockId::Tag(BlockTag::Pending)
+        };
+
+        match event {
+            WorldEvent::WorldSpawned(e) => DecodedEvent::new("WorldSpawned", "World spawned", None)
+                .field("Creator address", Field::Felt(e.creator.0))
+                .field("World class hash", Field::Felt(e.class_hash.0)),
+            WorldEvent::WorldUpgraded(e) => {
+                DecodedEvent::new("WorldUpgraded", "World upgraded", None)
+                    .field("World class hash", Field::Felt(e.class_hash.0))
+            }
+            WorldEvent::NamespaceRegistered(e) => {
+                DecodedEvent::new("NamespaceRegistered", "Namespace registered", None)
+                    .field("Namespace", byte_array(&e.namespace))
+            }
+            WorldEvent::ModelRegistered(e) => {
+                DecodedEvent::new("ModelRegistered", "Model registered", None)
+                    .field("Namespace", byte_array(&e.namespace))
+                    .field("Name", byte_array(&e.name))
+                    .field("Class hash", Field::Felt(e.class_hash.0))
+                    .field("Address", Field::Felt(e.address.0))
+            }
+            WorldEvent::EventRegistered(e) => {
+                DecodedEvent::new("EventRegistered", "Event registered", None)
+                    .field("Namespace", byte_array(&e.namespace))
+                    .field("Name", byte_array(&e.name))
+                    .field("Class hash", Field::Felt(e.class_hash.0))
+                    .field("Address", Field::Felt(e.address.0))
+            }
+            WorldEvent::ContractRegistered(e) => {
+                DecodedEvent::new("ContractRegistered", "Contract registered", None)
+                    .field("Namespace", byte_array(&e.namespace))
+                    .field("Name", byte_array(&e.name))
+                    .field("Class hash", Field::Felt(e.class_hash.0))
+                    .field("Address", Field::Felt(e.address.0))
+                    .field("Salt", Field::Felt(e.salt))
+            }
+            WorldEvent::ModelUpgraded(e) => {
+                // The schema may have changed with the new class.
+                self.schemas.remove(&e.selector);
+
+                DecodedEvent::new("ModelUpgraded", "Model upgraded", Some(self.tag(e.selector)))
+                    .field("Selector", Field::Felt(e.selector))
+                    .field("Class hash", Field::Felt(e.class_hash.0))
+                    .field("Address", Field::Felt(e.address.0))
+                    .field("Prev address", Field::Felt(e.prev_address.0))
+            }
+            WorldEvent::EventUpgraded(e) => {
+                self.schemas.remove(&e.selector);
+
+                DecodedEvent::new("EventUpgraded", "Event upgraded", Some(self.tag(e.selector)))
+                    .field("Selector", Field::Felt(e.selector))
+                    .field("Class hash", Field::Felt(e.class_hash.0))
+                    .field("Address", Field::Felt(e.address.0))
+                    .field("Prev address", Field::Felt(e.prev_address.0))
+            }
+            WorldEvent::ContractUpgraded(e) => DecodedEvent::new(
+                "ContractUpgraded",
+                "Contract upgraded",
+                Some(self.tag(e.selector)),
             )
-        }
-        WorldEvent::StoreUpdateRecord(e) => {
-            let tag = get_tag(e.selector, &tags);
-            // TODO: model value impl + print.
-            (
-                format!("Store update record ({})", tag),
-                format!(
-                    "Selector: {:#066x}\nEntity ID: {:#066x}\nValues: {}",
-                    e.selector,
-                    e.entity_id,
-                    e.values
-                        .iter()
-                        .map(|v| format!("{:#066x}", v))
-                        .collect::<Vec<String>>()
-                        .join(", "),
-                ),
+            .field("Selector", Field::Felt(e.selector))
+            .field("Class hash", Field::Felt(e.class_hash.0)),
+            WorldEvent::ContractInitialized(e) => DecodedEvent::new(
+                "ContractInitialized",
+                "Contract initialized",
+                Some(self.tag(e.selector)),
             )
+            .field("Selector", Field::Felt(e.selector))
+            .field("Init calldata", Field::Felts(e.init_calldata.clone())),
+            WorldEvent::WriterUpdated(e) => {
+                DecodedEvent::new("WriterUpdated", "Writer updated", None)
+                    .field("Target resource", Field::Text(self.tag(e.resource)))
+                    .field("Contract", Field::Text(self.contract_tag(e.contract.0)))
+                    .field("Value", Field::Text(e.value.to_string()))
+            }
+            WorldEvent::OwnerUpdated(e) => DecodedEvent::new("OwnerUpdated", "Owner updated", None)
+                .field("Target resource", Field::Text(self.tag(e.resource)))
+                .field("Contract", Field::Text(self.contract_tag(e.contract.0)))
+                .field("Value", Field::Text(e.value.to_string())),
+            WorldEvent::StoreSetRecord(e) => {
+                let schema = self.schema(e.selector, &provider, block_id).await;
+                let felts = [&e.keys[..], &e.values].concat();
+                let record = schema.and_then(|s| decode_record(&s, &felts));
+
+                DecodedEvent::new("StoreSetRecord", "Store set record", Some(self.tag(e.selector)))
+                    .field("Selector", Field::Felt(e.selector))
+                    .field("Entity ID", Field::Felt(e.entity_id))
+                    .field("Keys", Field::Felts(e.keys.clone()))
+                    .field("Values", Field::Felts(e.values.clone()))
+                    .record("Data", record)
+            }
+            WorldEvent::StoreUpdateRecord(e) => {
+                // Only the values are emitted, the keys are not part of the record.
+                let schema = self.schema(e.selector, &provider, block_id).await;
+                let record = schema.as_ref().and_then(Ty::as_struct).and_then(|s| {
+                    let values = Ty::Struct(Struct {
+                        name: s.name.clone(),
+                        children: s.children.iter().filter(|m| !m.key).cloned().collect(),
+                    });
+                    decode_record(&values, &e.values)
+                });
+
+                DecodedEvent::new(
+                    "StoreUpdateRecord",
+                    "Store update record",
+                    Some(self.tag(e.selector)),
+                )
+                .field("Selector", Field::Felt(e.selector))
+                .field("Entity ID", Field::Felt(e.entity_id))
+                .field("Values", Field::Felts(e.values.clone()))
+                .record("Data", record)
+            }
+            WorldEvent::StoreUpdateMember(e) => {
+                let schema = self.schema(e.selector, &provider, block_id).await;
+                let member = schema.as_ref().and_then(Ty::as_struct).and_then(|s| {
+                    s.children.iter().find(|m| {
+                        get_selector_from_name(&m.name).is_ok_and(|sel| sel == e.member_selector)
+                    })
+                });
+
+                let mut decoded = DecodedEvent::new(
+                    "StoreUpdateMember",
+                    "Store update member",
+                    Some(self.tag(e.selector)),
+                )
+                .field("Selector", Field::Felt(e.selector))
+                .field("Entity ID", Field::Felt(e.entity_id))
+                .field("Member selector", Field::Felt(e.member_selector));
+
+                if let Some(member) = member {
+                    decoded = decoded.field("Member", Field::Text(member.name.clone()));
+                }
+
+                decoded
+                    .field("Values", Field::Felts(e.values.clone()))
+                    .record("Data", member.and_then(|m| decode_record(&m.ty, &e.values)))
+            }
+            WorldEvent::StoreDelRecord(e) => {
+                DecodedEvent::new("StoreDelRecord", "Store del record", Some(self.tag(e.selector)))
+                    .field("Selector", Field::Felt(e.selector))
+                    .field("Entity ID", Field::Felt(e.entity_id))
+            }
+            WorldEvent::EventEmitted(e) => {
+                let schema = self.schema(e.selector, &provider, block_id).await;
+                let felts = [&e.keys[..], &e.values].concat();
+                let record = schema.and_then(|s| decode_record(&s, &felts));
+
+                DecodedEvent::new("EventEmitted", "Event emitted", Some(self.tag(e.selector)))
+                    .field("Selector", Field::Felt(e.selector))
+                    .field("Contract", Field::Text(self.contract_tag(e.system_address.0)))
+                    .field("Keys", Field::Felts(e.keys.clone()))
+                    .field("Values", Field::Felts(e.values.clone()))
+                    .record("Data", record)
+            }
+            _ => DecodedEvent::new("Unknown", "Unprocessed event", None)
+                .field("Event", Field::Text(format!("{:?}", event))),
         }
-        WorldEvent::StoreUpdateMember(e) => {
-            let tag = get_tag(e.selector, &tags);
-            // TODO: pretty print of the value.
-            (
-                format!("Store update member ({})", tag),
-                format!(
-                    "Selector: {:#066x}\nEntity ID: {:#066x}\nMember selector: {:#066x}\nValues: \
-                     {}",
-                    e.selector,
-                    e.entity_id,
-                    e.member_selector,
-                    e.values
-                        .iter()
-                        .map(|v| format!("{:#066x}", v))
-                        .collect::<Vec<String>>()
-                        .join(", "),
-                ),
-            )
+    }
+
+    /// Returns the tag for a selector, or the selector itself if it's not found.
+    fn tag(&self, selector: Felt) -> String {
+        self.tags.get(&selector).cloned().unwrap_or(format!("external-{:#066x}", selector))
+    }
+
+    /// Returns the tag of the contract at `address`, or the address itself if it's not found.
+    fn contract_tag(&self, address: Felt) -> String {
+        match self.contract_selectors.get(&address) {
+            Some(selector) => self.tag(*selector),
+            None => format!("{:#066x}", address),
         }
-        WorldEvent::StoreDelRecord(e) => {
-            let tag = get_tag(e.selector, &tags);
-            (
-                format!("Store del record ({})", tag),
-                format!("Selector: {:#066x}\nEntity ID: {:#066x}", e.selector, e.entity_id,),
-            )
+    }
+
+    /// Returns the schema of the model or the event with the given selector, fetching it from the
+    /// world the first time.
+    async fn schema<P: Provider + Send + Sync>(
+        &mut self,
+        selector: Felt,
+        provider: P,
+        block_id: BlockId,
+    ) -> Option<Ty> {
+        if let Some(schema) = self.schemas.get(&selector) {
+            return schema.clone();
         }
-        WorldEvent::EventEmitted(e) => {
-            let tag = get_tag(e.selector, &tags);
-            let contract_tag = if let Some(selector) =
-                contract_selectors_from_address.get(&e.system_address.into())
-            {
-                tags.get(selector).unwrap().to_string()
-            } else {
-                format!("{:#066x}", e.system_address.0)
-            };
 
-            // TODO: for events, we need to pull the schema and print the values accordingly.
-
-            (
-                format!("Event emitted ({})", tag),
-                format!(
-                    "Selector: {:#066x}\nContract: {}\nKeys: {}\nValues: {}",
-                    e.selector,
-                    contract_tag,
-                    e.keys
-                        .iter()
-                        .map(|k| format!("{:#066x}", k))
-                        .collect::<Vec<String>>()
-                        .join(", "),
-                    e.values
-                        .iter()
-                        .map(|v| format!("{:#066x}", v))
-                        .collect::<Vec<String>>()
-                        .join(", "),
-                ),
-            )
+        let schema = match self.tags.get(&selector) {
+            Some(tag) => {
+                let mut world_reader = WorldContractReader::new(self.world_address, provider);
+                world_reader.set_block(block_id);
+
+                match world_reader.model_reader_with_tag(tag).await {
+                    Ok(model) => model.schema().await.ok(),
+                    Err(_) => None,
+                }
+            }
+            None => None,
+        };
+
+        if schema.is_none() {
+            trace!(selector = format!("{:#066x}", selector), "Schema not available.");
         }
-        _ => ("Unprocessed event".to_string(), format!("Event: {:?}", event)),
-    };
 
-    let block_str = block_number.map(|n| n.to_string()).unwrap_or("pending".to_string());
-    let ptr = format!("[block:{} / tx:{:#066x}]", block_str, transaction_hash).bright_black();
+        self.schemas.insert(selector, schema.clone());
+        schema
+    }
+}
+
+/// Decodes the felts of a record with its schema, returning `None` if they don't match the schema.
+fn decode_record(schema: &Ty, felts: &[Felt]) -> Option<Field> {
+    let mut ty = schema.clone();
+    let mut remaining = felts.to_vec();
+    ty.deserialize(&mut remaining).ok()?;
 
-    println!("> {name} {ptr}\n{content}\n-----\n");
+    if !remaining.is_empty() {
+        return None;
+    }
+
+    let json = ty.to_json_value().ok()?;
+    Some(Field::Record { pretty: model::format_deep_record(schema, felts, &[]), json })
+}
 
-    Ok(())
+fn byte_array(value: &cainome::cairo_serde::ByteArray) -> Field {
+    Field::Text(value.to_string().unwrap_or_default())
 }
 
-/// Returns the tag for a selector, or the selector itself if it's not found.
-#[inline]
-fn get_tag(selector: Felt, tags: &HashMap<&Felt, String>) -> String {
-    tags.get(&selector).unwrap_or(&format!("external-{:#066x}", selector)).to_string()
+fn format_felts(felts: &[Felt]) -> String {
+    felts.iter().map(|f| format!("{:#066x}", f)).collect::<Vec<String>>().join(", ")
 }
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use anyhow::{anyhow, bail, Result};
 use clap::Args;
 use dojo_utils::{Invoker, TxnConfig};
@@ -49,6 +51,11 @@ and the move function of the ns-Actions contract, with the calldata [1,2]."))]
                   tags to addresses.")]
     pub diff: bool,
 
+    #[arg(long, value_name = "PATH")]
+    #[arg(help = "Write the calls as JSON into this file instead of sending them, to have them \
+                  signed and sent by another account, like a multisig.")]
+    pub export_calls: Option<PathBuf>,
+
     #[command(flatten)]
     pub starknet: StarknetOptions,
 
@@ -90,13 +97,7 @@ impl ExecuteArgs {
             )
             .await?;
 
-            let account = self
-                .account
-                .account(provider, profile_config.env.as_ref(), &self.starknet, &contracts)
-                .await?;
-
-            let mut invoker = Invoker::new(&account, txn_config);
-
+            let mut calls = vec![];
             let mut arg_iter = self.calls.into_iter();
 
             while let Some(arg) = arg_iter.next() {
@@ -146,15 +147,21 @@ impl ExecuteArgs {
                     )
                 })?;
 
-                let mut calldata = vec![];
+                let mut args = vec![];
                 for arg in &mut arg_iter {
-                    let arg = match arg.as_str() {
+                    match arg.as_str() {
                         "/" | "-" | "\\" => break,
-                        _ => calldata_decoder::decode_single_calldata(&arg)?,
-                    };
-                    calldata.extend(arg);
+                        _ => args.push(arg),
+                    }
                 }
 
+                // the ABI of the contract, if known, is used to serialize typed values
+                let abi = contracts
+                    .values()
+                    .find(|c| c.address == contract_address)
+                    .map_or(&[][..], |c| c.abi.as_slice());
+                let calldata = calldata_decoder::decode_calldata_with_abi(abi, &entrypoint, &args)?;
+
                 trace!(
                     contract=?contract_address,
                     entrypoint=entrypoint,
@@ -162,13 +169,25 @@ impl ExecuteArgs {
                     "Decoded call."
                 );
 
-                invoker.add_call(Call {
+                calls.push(Call {
                     to: contract_address,
                     selector: snutils::get_selector_from_name(&entrypoint)?,
                     calldata,
                 });
             }
 
+            if let Some(path) = &self.export_calls {
+                return utils::export_calls(path, &calls);
+            }
+
+            let account = self
+                .account
+                .account(provider, profile_config.env.as_ref(), &self.starknet, &contracts)
+                .await?;
+
+            let mut invoker = Invoker::new(&account, txn_config);
+            invoker.extend_calls(calls);
+
             let tx_result = invoker.multicall().await?;
 
             #[cfg(feature = "walnut")]
@@ -27,6 +27,7 @@ pub enum KeystoreCommand {
     },
 
     #[clap(about = "Create a keystore file from an existing private key.")]
+    #[clap(visible_alias = "import")]
     FromKey {
         #[clap(long, help = "Overwrite the file if it already exists")]
         force: bool,
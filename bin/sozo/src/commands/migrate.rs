@@ -1,13 +1,14 @@
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use clap::Args;
 use colored::*;
-use dojo_utils::{self, provider as provider_utils, TxnConfig};
+use dojo_utils::{self, provider as provider_utils, FeeConfig, TxnConfig};
 use dojo_world::contracts::WorldContract;
 use dojo_world::services::IpfsService;
 use scarb::core::{Config, Workspace};
-use sozo_ops::migrate::{Migration, MigrationResult};
+use sozo_ops::migrate::{Migration, MigrationPlan, MigrationResult, PlannedWorldChange};
 use sozo_ops::migration_ui::MigrationUi;
 use sozo_scarbext::WorkspaceExt;
 use starknet::core::utils::parse_cairo_short_string;
@@ -40,6 +41,14 @@ pub struct MigrateArgs {
 
     #[command(flatten)]
     pub ipfs: IpfsOptions,
+
+    #[arg(long, help = "Report the changes and the fees of the migration without sending it")]
+    pub dry_run: bool,
+
+    #[arg(long, value_name = "PATH", conflicts_with = "dry_run")]
+    #[arg(help = "Write the calls of the migration as JSON into this file instead of sending \
+                  them, to have them signed and sent by another account, like a multisig.")]
+    pub export_calls: Option<PathBuf>,
 }
 
 impl MigrateArgs {
@@ -51,7 +60,7 @@ impl MigrateArgs {
         ws.profile_check()?;
         ws.ensure_profile_artifacts()?;
 
-        let MigrateArgs { world, starknet, account, ipfs, .. } = self;
+        let MigrateArgs { world, starknet, account, ipfs, dry_run, export_calls, .. } = self;
 
         config.tokio_handle().block_on(async {
             print_banner(&ws, &starknet).await?;
@@ -84,6 +93,24 @@ impl MigrateArgs {
                 is_guest,
             );
 
+            if dry_run {
+                spinner.update_text("Planning migration...");
+                let plan = migration.plan().await.context("Migration planning failed.")?;
+                spinner.stop();
+
+                print_plan(&plan, &txn_config);
+
+                return Ok(());
+            }
+
+            if let Some(path) = export_calls {
+                spinner.update_text("Planning migration...");
+                let plan = migration.plan().await.context("Migration planning failed.")?;
+                spinner.stop();
+
+                return export_plan_calls(&plan, &path);
+            }
+
             let MigrationResult { manifest, has_changes } =
                 migration.migrate(&mut spinner).await.context("Migration failed.")?;
 
@@ -135,6 +162,120 @@ impl MigrateArgs {
     }
 }
 
+#[derive(Debug, Tabled)]
+struct PlanItem {
+    #[tabled(rename = "Change")]
+    change: String,
+    #[tabled(rename = "Target")]
+    target: String,
+    #[tabled(rename = "Details")]
+    details: String,
+}
+
+impl PlanItem {
+    fn new(change: &str, target: impl Into<String>, details: impl Into<String>) -> Self {
+        Self { change: change.to_string(), target: target.into(), details: details.into() }
+    }
+}
+
+/// Prints the changes a dry run of the migration would apply, and its estimated fees.
+fn print_plan(plan: &MigrationPlan, txn_config: &TxnConfig) {
+    println!();
+
+    if plan.is_empty() {
+        println!("{}", "No changes to migrate.".bright_green());
+        return;
+    }
+
+    let mut items = vec![];
+
+    match plan.world {
+        Some(PlannedWorldChange::Deploy) => items.push(PlanItem::new("deploy", "world", "")),
+        Some(PlannedWorldChange::Upgrade) => items.push(PlanItem::new("upgrade", "world", "")),
+        None => {}
+    }
+
+    for class in &plan.classes {
+        items.push(PlanItem::new("declare", &class.label, format!("{:#066x}", class.class_hash)));
+    }
+
+    for resource in &plan.resources {
+        let change = if resource.upgrade { "upgrade" } else { "register" };
+        items.push(PlanItem::new(change, &resource.tag, &resource.resource_type));
+    }
+
+    for permission in &plan.permissions {
+        let change = if permission.owner { "grant owner" } else { "grant writer" };
+        items.push(PlanItem::new(change, &permission.resource, &permission.grantee));
+    }
+
+    for tag in &plan.initializations {
+        items.push(PlanItem::new("init", tag, ""));
+    }
+
+    for instance_name in &plan.external_contracts {
+        items.push(PlanItem::new("deploy", instance_name, "external contract"));
+    }
+
+    println!("{}", Table::new(items).with(Style::psql()));
+    println!();
+
+    let unit = match txn_config.fee_config {
+        FeeConfig::Strk(_) => "FRI",
+        FeeConfig::Eth(_) => "WEI",
+    };
+    let format_fee = |fee: Option<_>| match fee {
+        Some(fee) => format!("{fee} {unit}"),
+        None => "unavailable".to_string(),
+    };
+
+    println!("Calls to invoke: {}", plan.calls.len());
+    println!("Estimated declaration fee: {}", format_fee(plan.declaration_fee));
+    println!("Estimated invoke fee: {}", format_fee(plan.invoke_fee));
+
+    if plan.invoke_fee.is_none() && (plan.world.is_some() || !plan.classes.is_empty()) {
+        println!(
+            "{}",
+            "The invoke fee can't be estimated before the world and the classes are declared."
+                .bright_yellow()
+        );
+    }
+
+    println!();
+    println!("{}", "Dry run, no transaction was sent.".bright_yellow());
+}
+
+/// Exports the calls of the migration, which only have to be signed by the owner of the world.
+///
+/// Declarations can't be exported as calls. Since anyone can declare a class, they must be sent
+/// before the exported calls.
+fn export_plan_calls(plan: &MigrationPlan, path: &Path) -> Result<()> {
+    if plan.world == Some(PlannedWorldChange::Deploy) {
+        bail!("The world must be deployed before the calls of the migration can be exported.");
+    }
+
+    println!();
+    utils::export_calls(path, &plan.calls)?;
+
+    let mut labels = plan.classes.iter().map(|c| c.label.as_str()).collect::<Vec<_>>();
+    if plan.world == Some(PlannedWorldChange::Upgrade) {
+        labels.insert(0, "world");
+    }
+
+    if !labels.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "The following classes must be declared before the calls are sent: {}.",
+                labels.join(", ")
+            )
+            .bright_yellow()
+        );
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Tabled)]
 pub struct Banner {
     pub profile: String,
@@ -1,5 +1,6 @@
 use core::fmt;
 
+use account::AccountArgs;
 use anyhow::Result;
 use auth::AuthArgs;
 use clap::Subcommand;
@@ -8,6 +9,7 @@ use scarb::core::{Config, Package, Workspace};
 use semver::{Version, VersionReq};
 use tracing::info_span;
 
+pub(crate) mod account;
 pub(crate) mod auth;
 pub(crate) mod build;
 pub(crate) mod call;
@@ -18,10 +20,13 @@ pub(crate) mod execute;
 pub(crate) mod hash;
 pub(crate) mod init;
 pub(crate) mod inspect;
+pub(crate) mod keystore;
 pub(crate) mod migrate;
 pub(crate) mod model;
 pub(crate) mod options;
+pub(crate) mod run;
 pub(crate) mod test;
+pub(crate) mod verify;
 
 use build::BuildArgs;
 use call::CallArgs;
@@ -31,18 +36,25 @@ use execute::ExecuteArgs;
 use hash::HashArgs;
 use init::InitArgs;
 use inspect::InspectArgs;
+use keystore::KeystoreArgs;
 use migrate::MigrateArgs;
 use model::ModelArgs;
+use run::RunArgs;
 #[cfg(feature = "walnut")]
 use sozo_walnut::walnut::WalnutArgs;
 use test::TestArgs;
+use verify::VerifyArgs;
 
 pub(crate) const LOG_TARGET: &str = "sozo::cli";
 
 #[derive(Debug, Subcommand)]
 pub enum Commands {
+    #[command(about = "Create, deploy or fetch the config of an OpenZeppelin account")]
+    Account(Box<AccountArgs>),
     #[command(about = "Grant or revoke a contract permission to write to a resource")]
     Auth(Box<AuthArgs>),
+    #[command(about = "Manage the encrypted keystores holding the private keys of the accounts")]
+    Keystore(Box<KeystoreArgs>),
     #[command(about = "Build the world, generating the necessary artifacts for deployment")]
     Build(Box<BuildArgs>),
     #[command(about = "Build and migrate the world every time a file changes")]
@@ -52,8 +64,14 @@ pub enum Commands {
     Migrate(Box<MigrateArgs>),
     #[command(about = "Execute one or several systems with the given calldata.")]
     Execute(Box<ExecuteArgs>),
+    #[command(about = "Run a script of calls against the deployed world, to initialize it")]
+    Run(Box<RunArgs>),
     #[command(about = "Inspect the world")]
     Inspect(Box<InspectArgs>),
+    #[command(
+        about = "Verify that the deployed world matches the local build artifacts and manifest"
+    )]
+    Verify(Box<VerifyArgs>),
     #[command(about = "Clean the build directory")]
     Clean(Box<CleanArgs>),
     #[command(about = "Call a contract")]
@@ -76,11 +94,14 @@ pub enum Commands {
 impl fmt::Display for Commands {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Commands::Account(_) => write!(f, "Account"),
             Commands::Auth(_) => write!(f, "Auth"),
+            Commands::Keystore(_) => write!(f, "Keystore"),
             Commands::Build(_) => write!(f, "Build"),
             Commands::Clean(_) => write!(f, "Clean"),
             Commands::Dev(_) => write!(f, "Dev"),
             Commands::Execute(_) => write!(f, "Execute"),
+            Commands::Run(_) => write!(f, "Run"),
             Commands::Inspect(_) => write!(f, "Inspect"),
             Commands::Migrate(_) => write!(f, "Migrate"),
             Commands::Call(_) => write!(f, "Call"),
@@ -89,6 +110,7 @@ impl fmt::Display for Commands {
             Commands::Init(_) => write!(f, "Init"),
             Commands::Model(_) => write!(f, "Model"),
             Commands::Events(_) => write!(f, "Events"),
+            Commands::Verify(_) => write!(f, "Verify"),
             #[cfg(feature = "walnut")]
             Commands::Walnut(_) => write!(f, "WalnutVerify"),
         }
@@ -104,11 +126,14 @@ pub fn run(command: Commands, config: &Config) -> Result<()> {
     // useful to write tests for each command.
 
     match command {
+        Commands::Account(args) => args.run(config),
         Commands::Auth(args) => args.run(config),
+        Commands::Keystore(args) => args.run(config),
         Commands::Build(args) => args.run(config),
         Commands::Dev(args) => args.run(config),
         Commands::Migrate(args) => args.run(config),
         Commands::Execute(args) => args.run(config),
+        Commands::Run(args) => args.run(config),
         Commands::Inspect(args) => args.run(config),
         Commands::Clean(args) => args.run(config),
         Commands::Call(args) => args.run(config),
@@ -117,6 +142,7 @@ pub fn run(command: Commands, config: &Config) -> Result<()> {
         Commands::Init(args) => args.run(config),
         Commands::Model(args) => args.run(config),
         Commands::Events(args) => args.run(config),
+        Commands::Verify(args) => args.run(config),
         #[cfg(feature = "walnut")]
         Commands::Walnut(args) => args.run(config),
     }
@@ -123,6 +123,10 @@ hashes, called 'hash' in the following documentation.
         #[command(flatten)]
         starknet: StarknetOptions,
 
+        #[arg(short = 'j', long = "json")]
+        #[arg(help_heading = "Display options")]
+        to_json: bool,
+
         #[arg(short, long)]
         #[arg(help = "Block number at which to retrieve the model data (pending block by default)")]
         block: Option<u64>,
@@ -202,24 +206,30 @@ impl ModelArgs {
                     .await?;
                     Ok(())
                 }
-                ModelCommand::Get { tag_or_name, keys, block, starknet, world } => {
+                ModelCommand::Get { tag_or_name, keys, to_json, block, starknet, world } => {
                     let tag = tag_or_name.ensure_namespace(&default_ns);
                     let block_id =
                         block.map(BlockId::Number).unwrap_or(BlockId::Tag(BlockTag::Pending));
+                    let keys = parse_keys(&keys)?;
 
                     let (world_diff, provider, _) =
                         utils::get_world_diff_and_provider(starknet, world, &ws).await?;
 
-                    let (record, _, _) = model::model_get(
+                    let (record, mut entity, values) = model::model_get(
                         tag.to_string(),
-                        parse_keys(&keys)?,
+                        keys.clone(),
                         world_diff.world_info.address,
                         &provider,
                         block_id,
                     )
                     .await?;
 
-                    println!("{}", record);
+                    if to_json {
+                        entity.deserialize(&mut [keys, values].concat())?;
+                        println!("{}", serde_json::to_string_pretty(&entity.to_json_value()?)?);
+                    } else {
+                        println!("{}", record);
+                    }
 
                     Ok(())
                 }
@@ -322,6 +332,16 @@ mod tests {
             panic!("Expected Get command");
         }
 
+        // Test parsing with json output
+        let args = TestCommand::parse_from(["model", "get", "Account", "0x1", "--json"]);
+
+        if let ModelCommand::Get { keys, to_json, .. } = args.command {
+            assert_eq!(parse_keys(&keys).unwrap(), vec![Felt::ONE]);
+            assert!(to_json);
+        } else {
+            panic!("Expected Get command");
+        }
+
         // Test parsing with int prefix
         let args = TestCommand::parse_from(["model", "get", "Account", "int:-123456789"]);
 
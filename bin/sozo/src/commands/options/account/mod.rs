@@ -9,7 +9,7 @@ use dojo_world::contracts::ContractInfo;
 use starknet::accounts::{ExecutionEncoding, SingleOwnerAccount};
 use starknet::core::types::{BlockId, BlockTag, Felt};
 use starknet::providers::Provider;
-use starknet::signers::LocalWallet;
+use starknet::signers::{LocalWallet, Signer};
 use tracing::trace;
 use url::Url;
 
@@ -99,6 +99,12 @@ impl AccountOptions {
             return Ok(SozoAccount::Controller(account));
         }
 
+        #[cfg(feature = "ledger")]
+        if let Some(signer) = self.signer.ledger_signer().await? {
+            let account = self.single_owner_account(provider, signer, env_metadata).await?;
+            return Ok(SozoAccount::Ledger(account));
+        }
+
         let account = self.std_account(provider, env_metadata).await?;
         Ok(SozoAccount::Standard(account))
     }
@@ -112,9 +118,23 @@ impl AccountOptions {
         P: Provider,
         P: Send + Sync,
     {
-        let account_address = self.account_address(env_metadata)?;
-
         let signer = self.signer.signer(env_metadata, false)?;
+        self.single_owner_account(provider, signer, env_metadata).await
+    }
+
+    /// Creates a [`SingleOwnerAccount`] signing with the given signer.
+    async fn single_owner_account<P, S>(
+        &self,
+        provider: P,
+        signer: S,
+        env_metadata: Option<&Environment>,
+    ) -> Result<SingleOwnerAccount<P, S>>
+    where
+        P: Provider,
+        P: Send + Sync,
+        S: Signer + Send + Sync,
+    {
+        let account_address = self.account_address(env_metadata)?;
 
         trace!("Fetching chain id...");
         let chain_id = provider.chain_id().await?;
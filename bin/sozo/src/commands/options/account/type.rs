@@ -10,6 +10,8 @@ use starknet::core::types::contract::legacy::LegacyContractClass;
 use starknet::core::types::{BlockId, Call, Felt, FlattenedSierraClass};
 use starknet::providers::Provider;
 use starknet::signers::{local_wallet, LocalWallet, SignerInteractivityContext};
+#[cfg(feature = "ledger")]
+use starknet::signers::{LedgerError, LedgerSigner};
 
 #[cfg(feature = "controller")]
 use super::controller::ControllerSessionAccount;
@@ -22,6 +24,10 @@ pub enum SozoAccountSignError {
     #[cfg(feature = "controller")]
     #[error(transparent)]
     Controller(#[from] slot::account_sdk::signers::SignError),
+
+    #[cfg(feature = "ledger")]
+    #[error(transparent)]
+    Ledger(#[from] single_owner::SignError<LedgerError>),
 }
 
 /// To unify the account types, we define a wrapper type that implements the
@@ -39,6 +45,9 @@ where
 
     #[cfg(feature = "controller")]
     Controller(ControllerSessionAccount<P>),
+
+    #[cfg(feature = "ledger")]
+    Ledger(SingleOwnerAccount<P, LedgerSigner>),
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
@@ -55,6 +64,8 @@ where
             Self::Standard(account) => account.is_signer_interactive(context),
             #[cfg(feature = "controller")]
             Self::Controller(account) => account.is_signer_interactive(context),
+            #[cfg(feature = "ledger")]
+            Self::Ledger(account) => account.is_signer_interactive(context),
         }
     }
 
@@ -63,6 +74,8 @@ where
             Self::Standard(account) => account.address(),
             #[cfg(feature = "controller")]
             Self::Controller(account) => account.address(),
+            #[cfg(feature = "ledger")]
+            Self::Ledger(account) => account.address(),
         }
     }
 
@@ -71,6 +84,8 @@ where
             Self::Standard(account) => account.chain_id(),
             #[cfg(feature = "controller")]
             Self::Controller(account) => account.chain_id(),
+            #[cfg(feature = "ledger")]
+            Self::Ledger(account) => account.chain_id(),
         }
     }
 
@@ -126,6 +141,8 @@ where
             Self::Standard(account) => account.sign_execution_v1(execution, query_only).await?,
             #[cfg(feature = "controller")]
             Self::Controller(account) => account.sign_execution_v1(execution, query_only).await?,
+            #[cfg(feature = "ledger")]
+            Self::Ledger(account) => account.sign_execution_v1(execution, query_only).await?,
         };
         Ok(result)
     }
@@ -139,6 +156,8 @@ where
             Self::Standard(account) => account.sign_execution_v3(execution, query_only).await?,
             #[cfg(feature = "controller")]
             Self::Controller(account) => account.sign_execution_v3(execution, query_only).await?,
+            #[cfg(feature = "ledger")]
+            Self::Ledger(account) => account.sign_execution_v3(execution, query_only).await?,
         };
         Ok(result)
     }
@@ -158,6 +177,11 @@ where
                 let result = account.sign_legacy_declaration(declaration, query_only).await?;
                 Ok(result)
             }
+            #[cfg(feature = "ledger")]
+            Self::Ledger(account) => {
+                let result = account.sign_legacy_declaration(declaration, query_only).await?;
+                Ok(result)
+            }
         }
     }
 
@@ -173,6 +197,8 @@ where
             Self::Controller(account) => {
                 account.sign_declaration_v2(declaration, query_only).await?
             }
+            #[cfg(feature = "ledger")]
+            Self::Ledger(account) => account.sign_declaration_v2(declaration, query_only).await?,
         };
         Ok(result)
     }
@@ -189,6 +215,8 @@ where
             Self::Controller(account) => {
                 account.sign_declaration_v3(declaration, query_only).await?
             }
+            #[cfg(feature = "ledger")]
+            Self::Ledger(account) => account.sign_declaration_v3(declaration, query_only).await?,
         };
         Ok(result)
     }
@@ -204,6 +232,8 @@ where
             Self::Standard(account) => account.encode_calls(calls),
             #[cfg(feature = "controller")]
             Self::Controller(account) => account.encode_calls(calls),
+            #[cfg(feature = "ledger")]
+            Self::Ledger(account) => account.encode_calls(calls),
         }
     }
 }
@@ -220,6 +250,8 @@ where
             Self::Standard(account) => account.provider(),
             #[cfg(feature = "controller")]
             Self::Controller(account) => account.provider(),
+            #[cfg(feature = "ledger")]
+            Self::Ledger(account) => account.provider(),
         }
     }
 
@@ -228,6 +260,8 @@ where
             Self::Standard(account) => account.block_id(),
             #[cfg(feature = "controller")]
             Self::Controller(account) => account.block_id(),
+            #[cfg(feature = "ledger")]
+            Self::Ledger(account) => account.block_id(),
         }
     }
 }
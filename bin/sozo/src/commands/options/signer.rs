@@ -11,6 +11,8 @@ use dojo_world::config::Environment;
 use resolve_path::PathResolveExt;
 use starknet::core::types::Felt;
 use starknet::signers::{LocalWallet, SigningKey};
+#[cfg(feature = "ledger")]
+use starknet::signers::{DerivationPath, LedgerSigner};
 use tracing::trace;
 
 #[derive(Debug, Args, Clone)]
@@ -43,6 +45,16 @@ pub struct SignerOptions {
     #[arg(help = "The keystore password. Used with --keystore.")]
     #[arg(global = true)]
     pub keystore_password: Option<String>,
+
+    #[arg(long = "ledger-path")]
+    #[arg(value_name = "DERIVATION_PATH")]
+    #[arg(help_heading = "Signer options - LEDGER")]
+    #[arg(help = "Sign with the Ledger key at the given EIP-2645 derivation path, eg \
+                  m/2645'/1195502025'/1470455285'/0'/0'/0.")]
+    #[arg(global = true)]
+    #[arg(group = "signer")]
+    #[cfg(feature = "ledger")]
+    pub ledger_path: Option<String>,
 }
 
 impl SignerOptions {
@@ -79,6 +91,22 @@ impl SignerOptions {
         Ok(LocalWallet::from_signing_key(private_key))
     }
 
+    /// Connects to the Ledger if a derivation path is specified.
+    /// If the derivation path is not set, it returns `None`.
+    #[cfg(feature = "ledger")]
+    pub async fn ledger_signer(&self) -> Result<Option<LedgerSigner>> {
+        if let Some(path) = &self.ledger_path {
+            let path = DerivationPath::from_str(path)
+                .map_err(|e| anyhow!("Invalid Ledger derivation path: {e}"))?;
+
+            trace!(?path, "Signing using Ledger.");
+            let signer = LedgerSigner::new(path).await?;
+            return Ok(Some(signer));
+        }
+
+        Ok(None)
+    }
+
     /// Retrieves the private key from the CLI keystore.
     /// If the keystore path is not set, it returns `None`.
     pub fn private_key_from_keystore_cli(
@@ -0,0 +1,338 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+
+use anyhow::{anyhow, bail, Context, Result};
+use camino::Utf8PathBuf;
+use clap::Args;
+use dojo_utils::{Invoker, TxnConfig};
+use dojo_world::config::calldata_decoder;
+use dojo_world::contracts::ContractInfo;
+use scarb::core::Config;
+use serde::Deserialize;
+use sozo_ops::resource_descriptor::ResourceDescriptor;
+use sozo_scarbext::WorkspaceExt;
+use starknet::core::types::{Call, Felt};
+use starknet::core::utils as snutils;
+use tracing::trace;
+
+use super::options::account::AccountOptions;
+use super::options::starknet::StarknetOptions;
+use super::options::transaction::TransactionOptions;
+use super::options::world::WorldOptions;
+use crate::utils;
+
+#[derive(Debug, Args)]
+#[command(about = "Run a script of calls against the deployed world.")]
+pub struct RunArgs {
+    #[arg(help = "The path to the script to run, in TOML or JSON.
+
+A script is a list of steps, each step being a call to a contract:
+
+    [vars]
+    max_players = \"4\"
+
+    [[steps]]
+    description = \"Set the game configuration\"
+    contract = \"ns-config\"
+    entrypoint = \"set_config\"
+    calldata = [\"${max_players}\", \"${ns-actions}\"]
+
+The <contract> is the tag of a Dojo contract, the instance name of a Starknet contract, an address \
+                  or 'world'.
+
+The contract and the calldata may contain variables with the ${name} syntax, replaced by the value \
+                  of the variable of the script, or by the address of the contract with this \
+                  tag or instance name.")]
+    pub script: Utf8PathBuf,
+
+    #[arg(long = "var", value_name = "NAME=VALUE")]
+    #[arg(help = "Set a variable of the script, overriding the value defined in the script.")]
+    pub vars: Vec<String>,
+
+    #[arg(long)]
+    #[arg(help = "Send each step in its own transaction, instead of a single multicall.")]
+    pub sequential: bool,
+
+    #[arg(long)]
+    #[arg(help = "If true, sozo will compute the diff of the world from the chain to translate \
+                  tags to addresses.")]
+    pub diff: bool,
+
+    #[command(flatten)]
+    pub starknet: StarknetOptions,
+
+    #[command(flatten)]
+    pub account: AccountOptions,
+
+    #[command(flatten)]
+    pub world: WorldOptions,
+
+    #[command(flatten)]
+    pub transaction: TransactionOptions,
+}
+
+/// A script of calls, usually run once after a migration to initialize the world.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Script {
+    /// The variables which can be interpolated in the steps.
+    #[serde(default)]
+    vars: BTreeMap<String, String>,
+    /// The steps, run in order.
+    #[serde(default)]
+    steps: Vec<Step>,
+}
+
+/// A call to a contract.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Step {
+    description: Option<String>,
+    contract: String,
+    entrypoint: String,
+    #[serde(default)]
+    calldata: Vec<String>,
+}
+
+impl Script {
+    /// Loads a script, parsed as JSON for a `.json` file and as TOML otherwise.
+    fn load(path: &Utf8PathBuf) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read the script {path}."))?;
+
+        if path.extension() == Some("json") {
+            serde_json::from_str(&content).with_context(|| format!("Invalid script {path}."))
+        } else {
+            toml::from_str(&content).with_context(|| format!("Invalid script {path}."))
+        }
+    }
+
+    /// Overrides the variables of the script with `NAME=VALUE` pairs.
+    fn override_vars(&mut self, vars: &[String]) -> Result<()> {
+        for var in vars {
+            let (name, value) = var
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Invalid variable `{var}`, expected NAME=VALUE."))?;
+            self.vars.insert(name.trim().to_string(), value.to_string());
+        }
+
+        Ok(())
+    }
+}
+
+impl RunArgs {
+    pub fn run(self, config: &Config) -> Result<()> {
+        trace!(args = ?self);
+
+        let ws = scarb::ops::read_workspace(config.manifest_path(), config)?;
+
+        let profile_config = ws.load_profile_config()?;
+        let default_namespace = profile_config.namespace.default.clone();
+
+        let mut script = Script::load(&self.script)?;
+        script.override_vars(&self.vars)?;
+
+        let txn_config: TxnConfig = self.transaction.try_into()?;
+
+        config.tokio_handle().block_on(async {
+            let (provider, _) = self.starknet.provider(profile_config.env.as_ref())?;
+
+            let contracts = utils::contracts_from_manifest_or_diff(
+                self.account.clone(),
+                self.starknet.clone(),
+                self.world,
+                &ws,
+                self.diff,
+            )
+            .await?;
+
+            let account = self
+                .account
+                .account(provider, profile_config.env.as_ref(), &self.starknet, &contracts)
+                .await?;
+
+            let mut invoker = Invoker::new(&account, txn_config);
+
+            let lookup = |name: &str| {
+                script.vars.get(name).cloned().or_else(|| {
+                    resolve_contract(&contracts, name, &default_namespace)
+                        .map(|address| format!("{:#066x}", address))
+                })
+            };
+
+            for (i, step) in script.steps.iter().enumerate() {
+                let position = i + 1;
+
+                let contract = interpolate(&step.contract, lookup)
+                    .with_context(|| format!("Invalid contract of step {position}."))?;
+                let contract_address = resolve_contract(&contracts, &contract, &default_namespace)
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "Contract `{contract}` of step {position} not found in the manifest."
+                        )
+                    })?;
+
+                let args = step
+                    .calldata
+                    .iter()
+                    .map(|arg| interpolate(arg, lookup))
+                    .collect::<Result<Vec<_>>>()
+                    .with_context(|| format!("Invalid calldata of step {position}."))?;
+
+                // the ABI of the contract, if known, is used to serialize typed values
+                let abi = contracts
+                    .values()
+                    .find(|c| c.address == contract_address)
+                    .map_or(&[][..], |c| c.abi.as_slice());
+                let calldata =
+                    calldata_decoder::decode_calldata_with_abi(abi, &step.entrypoint, &args)
+                        .with_context(|| format!("Invalid calldata of step {position}."))?;
+
+                trace!(
+                    step = position,
+                    contract = ?contract_address,
+                    entrypoint = step.entrypoint,
+                    calldata = ?calldata,
+                    "Decoded step."
+                );
+
+                println!(
+                    "[{position}/{}] {}",
+                    script.steps.len(),
+                    step.description.as_deref().unwrap_or(&step.entrypoint)
+                );
+
+                invoker.add_call(Call {
+                    to: contract_address,
+                    selector: snutils::get_selector_from_name(&step.entrypoint)?,
+                    calldata,
+                });
+            }
+
+            if invoker.calls.is_empty() {
+                println!("No steps to run.");
+                return Ok(());
+            }
+
+            if self.sequential {
+                for tx_result in invoker.invoke_all_sequentially().await? {
+                    println!("{}", tx_result);
+                }
+            } else {
+                println!("{}", invoker.multicall().await?);
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// Returns the address of a contract given its address, its tag, its instance name or `world`.
+///
+/// A tag without namespace is resolved with the default namespace.
+fn resolve_contract(
+    contracts: &HashMap<String, ContractInfo>,
+    name: &str,
+    default_namespace: &str,
+) -> Option<Felt> {
+    if let Some(contract) = contracts.get(name) {
+        return Some(contract.address);
+    }
+
+    match ResourceDescriptor::from_string(name).ok()?.ensure_namespace(default_namespace) {
+        ResourceDescriptor::Address(address) => Some(address),
+        ResourceDescriptor::Tag(tag) => contracts.get(&tag).map(|c| c.address),
+        ResourceDescriptor::Name(_) => None,
+    }
+}
+
+/// Replaces the `${name}` variables of `value` with their value returned by `lookup`.
+fn interpolate<F>(value: &str, lookup: F) -> Result<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut output = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        output.push_str(&rest[..start]);
+
+        let Some(end) = rest[start..].find('}') else {
+            bail!("Unterminated variable in `{value}`.");
+        };
+
+        let name = rest[start + 2..start + end].trim();
+        let Some(replacement) = lookup(name) else {
+            bail!("Unknown variable `{name}`.");
+        };
+
+        output.push_str(&replacement);
+        rest = &rest[start + end + 1..];
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_script() {
+        let script: Script = toml::from_str(
+            r#"
+            [vars]
+            max_players = "4"
+
+            [[steps]]
+            description = "Set the game configuration"
+            contract = "ns-config"
+            entrypoint = "set_config"
+            calldata = ["${max_players}", "${ns-actions}"]
+
+            [[steps]]
+            contract = "world"
+            entrypoint = "uuid"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(script.vars.get("max_players").unwrap(), "4");
+        assert_eq!(script.steps.len(), 2);
+        assert_eq!(script.steps[0].calldata, vec!["${max_players}", "${ns-actions}"]);
+        assert!(script.steps[1].description.is_none());
+        assert!(script.steps[1].calldata.is_empty());
+
+        assert!(toml::from_str::<Script>("[[steps]]\ncontract = \"world\"").is_err());
+    }
+
+    #[test]
+    fn test_override_vars() {
+        let mut script = Script::default();
+        script.vars.insert("count".to_string(), "1".to_string());
+
+        script.override_vars(&["count=2".to_string(), "name=sstr:a=b".to_string()]).unwrap();
+        assert_eq!(script.vars.get("count").unwrap(), "2");
+        assert_eq!(script.vars.get("name").unwrap(), "sstr:a=b");
+
+        assert!(script.override_vars(&["count".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let lookup = |name: &str| match name {
+            "count" => Some("2".to_string()),
+            "ns-actions" => Some("0x1234".to_string()),
+            _ => None,
+        };
+
+        assert_eq!(interpolate("plain", lookup).unwrap(), "plain");
+        assert_eq!(interpolate("${count}", lookup).unwrap(), "2");
+        assert_eq!(interpolate("u256:${ count }0", lookup).unwrap(), "u256:20");
+        assert_eq!(interpolate("${ns-actions},${count}", lookup).unwrap(), "0x1234,2");
+
+        assert!(interpolate("${unknown}", lookup).is_err());
+        assert!(interpolate("${count", lookup).is_err());
+    }
+}
@@ -1,9 +1,13 @@
 //! Compiles and runs tests for a Dojo project using Scarb.
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fs;
 
 use anyhow::{Context, Result};
-use cairo_lang_sierra::program::VersionedProgram;
+use cairo_lang_runner::profiling::ProfilingInfoCollectionConfig;
+use cairo_lang_runner::{RunResultValue, SierraCasmRunner, StarknetState};
+use cairo_lang_sierra::program::{Program, VersionedProgram};
+use cairo_lang_sierra_to_casm::metadata::MetadataComputationConfig;
+use cairo_lang_test_plugin::test_config::{PanicExpectation, TestConfig, TestExpectation};
 use cairo_lang_test_plugin::{TestCompilation, TestCompilationMetadata};
 use cairo_lang_test_runner::{CompiledTestRunner, RunProfilerConfig, TestRunConfig};
 use camino::Utf8PathBuf;
@@ -63,6 +67,10 @@ pub struct TestArgs {
     /// Should we print the resource usage.
     #[arg(long, default_value_t = false)]
     print_resource_usage: bool,
+    /// Write a JSON report with the resources used by each test and the coverage of the package
+    /// functions, to enforce thresholds in CI.
+    #[arg(long, value_name = "PATH")]
+    report: Option<Utf8PathBuf>,
     /// Specify the features to activate.
     #[command(flatten)]
     features: FeaturesSpec,
@@ -125,6 +133,8 @@ impl TestArgs {
 
         let target_dir = Utf8PathBuf::from(ws.target_dir_profile().to_string());
 
+        let mut report = self.report.as_ref().map(|_| TestReport::default());
+
         let mut deduplicator = TargetGroupDeduplicator::default();
         for package in matched {
             println!("testing {} ...", package.name);
@@ -143,24 +153,234 @@ impl TestArgs {
                     continue;
                 }
                 let test_compilation = deserialize_test_compilation(&target_dir, name.clone())?;
+                let gas_enabled = is_gas_enabled(&metadata, &package.id, target);
                 let config = TestRunConfig {
                     filter: self.filter.clone(),
                     include_ignored: self.include_ignored,
                     ignored: self.ignored,
                     run_profiler: RunProfilerConfig::None,
-                    gas_enabled: is_gas_enabled(&metadata, &package.id, target),
+                    gas_enabled,
                     print_resource_usage: self.print_resource_usage,
                 };
                 let runner = CompiledTestRunner::new(test_compilation, config);
                 runner.run(None)?;
                 println!();
+
+                if let Some(report) = &mut report {
+                    // The runner consumes the compilation and doesn't expose the resources of each
+                    // test, so the tests are run again to collect them.
+                    let test_compilation = deserialize_test_compilation(&target_dir, name)?;
+                    let tests = self.selected_tests(&test_compilation.metadata);
+                    report.collect(&package.name, test_compilation, tests, gas_enabled)?;
+                }
+            }
+        }
+
+        if let (Some(path), Some(report)) = (&self.report, report) {
+            fs::write(path, serde_json::to_string_pretty(&report)?)
+                .with_context(|| format!("failed to write test report: {path}"))?;
+            println!("test report written to {path}");
+        }
+
+        Ok(())
+    }
+
+    /// Returns the tests run with the current filters, the same way the tests runner selects them.
+    fn selected_tests(&self, metadata: &TestCompilationMetadata) -> Vec<(String, TestConfig)> {
+        metadata
+            .named_tests
+            .iter()
+            .filter(|(name, _)| name.contains(&self.filter))
+            .filter(|(_, test)| {
+                if self.include_ignored {
+                    true
+                } else if self.ignored {
+                    test.ignored
+                } else {
+                    !test.ignored
+                }
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+/// The resources used by the tests and the coverage of the package functions.
+#[derive(Debug, Default, Serialize)]
+struct TestReport {
+    tests: Vec<TestResources>,
+    coverage: Vec<ModuleCoverage>,
+}
+
+/// The resources used by a single test.
+#[derive(Debug, Serialize)]
+struct TestResources {
+    package: String,
+    name: String,
+    passed: bool,
+    /// The number of Cairo steps.
+    steps: usize,
+    memory_holes: usize,
+    /// The gas consumed, `None` if gas is disabled for the test.
+    gas: Option<u64>,
+}
+
+/// The share of the functions of a module executed by at least one test.
+#[derive(Debug, Serialize)]
+struct ModuleCoverage {
+    package: String,
+    module: String,
+    functions: usize,
+    covered: usize,
+    percentage: f64,
+}
+
+impl TestReport {
+    /// Runs the given tests of a compilation, recording the resources they use and the functions
+    /// of the package they execute.
+    fn collect(
+        &mut self,
+        package: &str,
+        compilation: TestCompilation,
+        tests: Vec<(String, TestConfig)>,
+        gas_enabled: bool,
+    ) -> Result<()> {
+        let TestCompilation { sierra_program, metadata } = compilation;
+        let program = sierra_program.program;
+
+        let runner = SierraCasmRunner::new(
+            program.clone(),
+            gas_enabled.then(|| MetadataComputationConfig {
+                function_set_costs: metadata.function_set_costs,
+                ..Default::default()
+            }),
+            metadata.contracts_info,
+            Some(ProfilingInfoCollectionConfig::default()),
+        )
+        .context("failed to setup the tests runner")?;
+
+        let mut executed = BTreeSet::new();
+
+        for (name, test) in tests {
+            let function = runner.find_function(&name)?;
+            let available_gas = if gas_enabled { test.available_gas } else { None };
+
+            let result = runner
+                .run_function_with_starknet_context(
+                    function,
+                    vec![],
+                    available_gas,
+                    StarknetState::default(),
+                )
+                .with_context(|| format!("failed to run test: {name}"))?;
+
+            if let Some(profiling) = &result.profiling_info {
+                executed.extend(profiling.sierra_statement_weights.iter_sorted().map(|(s, _)| s.0));
             }
+
+            let passed = match (&result.value, &test.expectation) {
+                (RunResultValue::Success(_), TestExpectation::Success) => true,
+                (RunResultValue::Panic(_), TestExpectation::Panics(PanicExpectation::Any)) => true,
+                (
+                    RunResultValue::Panic(data),
+                    TestExpectation::Panics(PanicExpectation::Exact(e)),
+                ) => data == e,
+                _ => false,
+            };
+
+            let gas = available_gas.zip(result.gas_counter).and_then(|(before, after)| {
+                Some((before as u64).saturating_sub(u64::try_from(after).ok()?))
+            });
+
+            self.tests.push(TestResources {
+                package: package.to_string(),
+                name,
+                passed,
+                steps: result.used_resources.basic_resources.n_steps,
+                memory_holes: result.used_resources.basic_resources.n_memory_holes,
+                gas,
+            });
         }
 
+        let test_names = metadata.named_tests.iter().map(|(name, _)| name).collect::<HashSet<_>>();
+        self.coverage.extend(module_coverage(package, &program, &executed, &test_names));
+
         Ok(())
     }
 }
 
+/// Computes the coverage of the modules of `package`, given the indices of the executed sierra
+/// statements. The tests and the `tests` modules are excluded.
+fn module_coverage(
+    package: &str,
+    program: &Program,
+    executed: &BTreeSet<usize>,
+    test_names: &HashSet<&String>,
+) -> Vec<ModuleCoverage> {
+    let entry_points = program.funcs.iter().map(|f| f.entry_point.0).collect::<BTreeSet<_>>();
+    let prefix = format!("{package}::");
+
+    // Module path to the number of functions and the number of covered ones.
+    let mut modules = BTreeMap::<String, (usize, usize)>::new();
+
+    for function in &program.funcs {
+        let Some(name) = function.id.debug_name.as_ref().map(|n| n.to_string()) else {
+            continue;
+        };
+
+        if !name.starts_with(&prefix) || test_names.contains(&name) {
+            continue;
+        }
+
+        let Some(module) = module_path(&name) else {
+            continue;
+        };
+
+        if module.split("::").any(|segment| segment == "tests") {
+            continue;
+        }
+
+        // The statements of a function span from its entry point to the next one.
+        let start = function.entry_point.0;
+        let end = entry_points.range(start + 1..).next().copied().unwrap_or(usize::MAX);
+        let covered = executed.range(start..end).next().is_some();
+
+        let entry = modules.entry(module).or_default();
+        entry.0 += 1;
+        entry.1 += covered as usize;
+    }
+
+    modules
+        .into_iter()
+        .map(|(module, (functions, covered))| ModuleCoverage {
+            package: package.to_string(),
+            module,
+            functions,
+            covered,
+            percentage: covered as f64 * 100.0 / functions as f64,
+        })
+        .collect()
+}
+
+/// Returns the path of the module or impl of a function from its debug name, without the generic
+/// arguments.
+fn module_path(name: &str) -> Option<String> {
+    let mut path = String::new();
+    let mut depth = 0;
+
+    for c in name.chars() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            _ if depth == 0 => path.push(c),
+            _ => {}
+        }
+    }
+
+    let path = path.replace("::::", "::");
+    path.rsplit_once("::").map(|(module, _)| module.to_string())
+}
+
 fn deserialize_test_compilation(target_dir: &Utf8PathBuf, name: String) -> Result<TestCompilation> {
     let file_path = target_dir.join(format!("{}.test.json", name));
     let test_comp_metadata = serde_json::from_str::<TestCompilationMetadata>(
@@ -212,3 +432,25 @@ fn is_gas_enabled(metadata: &Metadata, package_id: &PackageId, target: &TargetMe
 fn find_testable_targets(package: &PackageMetadata) -> Vec<&TargetMetadata> {
     package.targets.iter().filter(|target| target.kind == "test").collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module_path_of_functions() {
+        assert_eq!(
+            module_path("game::systems::actions::actions::ActionsImpl::spawn"),
+            Some("game::systems::actions::actions::ActionsImpl".to_string())
+        );
+        assert_eq!(
+            module_path("game::models::PositionStore::<game::models::Position>::get"),
+            Some("game::models::PositionStore".to_string())
+        );
+        assert_eq!(
+            module_path("core::array::ArrayImpl::<core::felt252>::append"),
+            Some("core::array::ArrayImpl".to_string())
+        );
+        assert_eq!(module_path("game"), None);
+    }
+}
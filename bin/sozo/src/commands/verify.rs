@@ -0,0 +1,252 @@
+use anyhow::{bail, Result};
+use clap::Args;
+use colored::*;
+use dojo_world::diff::{ExternalContractDiff, Manifest, ResourceDiff, WorldDiff, WorldStatus};
+use dojo_world::ResourceType;
+use scarb::core::Config;
+use sozo_scarbext::WorkspaceExt;
+use starknet::core::types::{BlockId, BlockTag, Felt, StarknetError};
+use starknet::providers::{Provider, ProviderError};
+use tabled::settings::object::Cell;
+use tabled::settings::{Color, Style};
+use tabled::{Table, Tabled};
+use tracing::trace;
+
+use super::options::starknet::StarknetOptions;
+use super::options::world::WorldOptions;
+use crate::utils;
+
+#[derive(Debug, Args)]
+pub struct VerifyArgs {
+    #[command(flatten)]
+    world: WorldOptions,
+
+    #[command(flatten)]
+    starknet: StarknetOptions,
+}
+
+impl VerifyArgs {
+    pub fn run(self, config: &Config) -> Result<()> {
+        trace!(args = ?self);
+        let ws = scarb::ops::read_workspace(config.manifest_path(), config)?;
+
+        let VerifyArgs { world, starknet } = self;
+
+        config.tokio_handle().block_on(async {
+            let (world_diff, provider, _) =
+                utils::get_world_diff_and_provider(starknet.clone(), world, &ws).await?;
+            let manifest = ws.read_manifest_profile()?;
+
+            let mut drifts = verify_chain(&world_diff, &provider).await?;
+            match &manifest {
+                Some(manifest) => drifts.extend(verify_manifest(&world_diff, manifest)),
+                None => {
+                    let message = "No manifest found, only the build artifacts are verified.";
+                    println!("{}", message.yellow())
+                }
+            }
+
+            if drifts.is_empty() {
+                println!(
+                    "{}",
+                    format!(
+                        "🎉 The world at {:#066x} matches the local project.",
+                        world_diff.world_info.address
+                    )
+                    .green()
+                );
+                return Ok(());
+            }
+
+            let mut table = Table::new(&drifts);
+            table.with(Style::psql());
+            table.modify(Cell::new(0, 0), Color::FG_BRIGHT_BLACK);
+            println!("{table}\n");
+
+            bail!("{} drift(s) found between the local project and the chain.", drifts.len())
+        })
+    }
+}
+
+/// A difference between the local project and the deployed world.
+#[derive(Debug, Tabled)]
+struct Drift {
+    #[tabled(rename = "Element")]
+    element: String,
+    #[tabled(rename = "Drift")]
+    reason: String,
+    #[tabled(rename = "Local")]
+    local: String,
+    #[tabled(rename = "On-chain")]
+    remote: String,
+}
+
+impl Drift {
+    fn new(element: impl Into<String>, reason: &str, local: Felt, remote: Option<Felt>) -> Self {
+        Self {
+            element: element.into(),
+            reason: reason.to_string(),
+            local: format!("{:#066x}", local),
+            remote: remote.map_or_else(|| "-".to_string(), |r| format!("{:#066x}", r)),
+        }
+    }
+}
+
+/// Compares the class hashes of the build artifacts with the class hashes registered in the
+/// world and the ones of the contracts deployed on chain.
+async fn verify_chain<P>(world_diff: &WorldDiff, provider: &P) -> Result<Vec<Drift>>
+where
+    P: Provider,
+{
+    let mut drifts = vec![];
+    let world = &world_diff.world_info;
+
+    if world.status == WorldStatus::NotDeployed {
+        drifts.push(Drift::new("world", "Not deployed", world.class_hash, None));
+    } else {
+        let remote = class_hash_at(provider, world.address).await?;
+        if remote != Some(world.class_hash) {
+            drifts.push(Drift::new("world", "Class hash differs", world.class_hash, remote));
+        }
+    }
+
+    let mut resources = world_diff.resources.values().collect::<Vec<_>>();
+    resources.sort_by_key(|r| r.tag());
+
+    for resource in resources {
+        let (local, remote) = match resource {
+            ResourceDiff::Created(local) => {
+                drifts.push(Drift::new(local.tag(), "Not registered", local.class_hash(), None));
+                continue;
+            }
+            ResourceDiff::Updated(local, remote) | ResourceDiff::Synced(local, remote) => {
+                (local, remote)
+            }
+        };
+
+        if local.resource_type() == ResourceType::Namespace {
+            continue;
+        }
+
+        if local.class_hash() != remote.current_class_hash() {
+            drifts.push(Drift::new(
+                local.tag(),
+                "Class hash registered in the world differs",
+                local.class_hash(),
+                Some(remote.current_class_hash()),
+            ));
+        }
+
+        if local.resource_type() == ResourceType::Contract {
+            let deployed = class_hash_at(provider, remote.address()).await?;
+            if deployed != Some(local.class_hash()) {
+                drifts.push(Drift::new(
+                    local.tag(),
+                    "Class hash of the deployed contract differs",
+                    local.class_hash(),
+                    deployed,
+                ));
+            }
+        }
+    }
+
+    let mut contracts = world_diff.external_contracts.values().collect::<Vec<_>>();
+    contracts.sort_by_key(|c| c.contract_data().instance_name);
+
+    for contract in contracts {
+        let data = contract.contract_data();
+        match contract {
+            ExternalContractDiff::Created(_) => {
+                drifts.push(Drift::new(data.instance_name, "Not deployed", data.class_hash, None));
+            }
+            ExternalContractDiff::Synced(_) => {
+                let deployed = class_hash_at(provider, data.address).await?;
+                if deployed != Some(data.class_hash) {
+                    drifts.push(Drift::new(
+                        data.instance_name,
+                        "Class hash of the deployed contract differs",
+                        data.class_hash,
+                        deployed,
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(drifts)
+}
+
+/// Compares the addresses recorded in the manifest with the addresses of the deployed world and
+/// contracts, to detect a manifest that is not up to date.
+fn verify_manifest(world_diff: &WorldDiff, manifest: &Manifest) -> Vec<Drift> {
+    let mut drifts = vec![];
+    let world = &world_diff.world_info;
+
+    if manifest.world.address != world.address {
+        drifts.push(Drift::new(
+            "world",
+            "Manifest address differs",
+            manifest.world.address,
+            Some(world.address),
+        ));
+    }
+
+    for contract in &manifest.contracts {
+        let Some(resource) = world_diff.resources.values().find(|r| r.tag() == contract.tag) else {
+            drifts.push(Drift::new(
+                &contract.tag,
+                "Manifest contract not in the build",
+                contract.class_hash,
+                None,
+            ));
+            continue;
+        };
+
+        if let ResourceDiff::Updated(_, remote) | ResourceDiff::Synced(_, remote) = resource {
+            if contract.address != remote.address() {
+                drifts.push(Drift::new(
+                    &contract.tag,
+                    "Manifest address differs",
+                    contract.address,
+                    Some(remote.address()),
+                ));
+            }
+        }
+    }
+
+    for contract in &manifest.external_contracts {
+        let Some(diff) = world_diff.external_contracts.get(&contract.instance_name) else {
+            drifts.push(Drift::new(
+                &contract.instance_name,
+                "Manifest contract not in the build",
+                contract.class_hash,
+                None,
+            ));
+            continue;
+        };
+
+        if contract.address != diff.address() {
+            drifts.push(Drift::new(
+                &contract.instance_name,
+                "Manifest address differs",
+                contract.address,
+                Some(diff.address()),
+            ));
+        }
+    }
+
+    drifts
+}
+
+/// Returns the class hash of the contract deployed at `address`, or `None` if there is no
+/// contract deployed at this address.
+async fn class_hash_at<P>(provider: &P, address: Felt) -> Result<Option<Felt>>
+where
+    P: Provider,
+{
+    match provider.get_class_hash_at(BlockId::Tag(BlockTag::Pending), address).await {
+        Ok(class_hash) => Ok(Some(class_hash)),
+        Err(ProviderError::StarknetError(StarknetError::ContractNotFound)) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
+use std::fs;
 use std::io::{self, Write};
+use std::path::Path;
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -14,10 +16,11 @@ use dojo_world::local::WorldLocal;
 use katana_rpc_api::starknet::RPC_SPEC_VERSION;
 use scarb::core::{TomlManifest, Workspace};
 use semver::Version;
+use serde::Serialize;
 use sozo_ops::migration_ui::MigrationUi;
 use sozo_scarbext::WorkspaceExt;
 use starknet::accounts::{Account, ConnectedAccount};
-use starknet::core::types::Felt;
+use starknet::core::types::{Call, Felt};
 use starknet::core::utils as snutils;
 use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::{JsonRpcClient, Provider};
@@ -47,7 +50,12 @@ Sozo supports some prefixes that you can use to automatically parse some types.
     - u256arr: A dynamic array of u256.
     - farr: A fixed-size array where each item fits on a single felt252.
     - u256farr: A fixed-size array of u256.
-    - no prefix: A cairo felt or any type that fit into one felt.";
+    - no prefix: A cairo felt or any type that fit into one felt.
+When the ABI of the contract is known and a value is given for each parameter of the entrypoint, \
+                                the values without prefix are serialized according to the types \
+                                of the parameters (ex: 100 for a u256, 'hello world' for a \
+                                ByteArray, '{\"x\":1,\"y\":2}' for a struct, Left or \
+                                '{\"Some\":1}' for an enum and '[1,2,3]' for an array).";
 
 /// Computes the world address based on the provided options.
 pub fn get_world_address(
@@ -285,6 +293,33 @@ pub fn prompt_confirm(prompt: &str) -> Result<bool> {
     Ok(input.trim().to_lowercase() == "y")
 }
 
+/// A call exported to be signed and sent by another account.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ExportedCall {
+    pub to: Felt,
+    pub selector: Felt,
+    pub calldata: Vec<Felt>,
+}
+
+impl From<&Call> for ExportedCall {
+    fn from(call: &Call) -> Self {
+        Self { to: call.to, selector: call.selector, calldata: call.calldata.clone() }
+    }
+}
+
+/// Writes the calls as JSON into `path` instead of sending them, so they can be signed and sent
+/// by another account, like the multisig owning a world.
+pub fn export_calls(path: &Path, calls: &[Call]) -> Result<()> {
+    let calls = calls.iter().map(ExportedCall::from).collect::<Vec<_>>();
+
+    fs::write(path, serde_json::to_string_pretty(&calls)?)
+        .with_context(|| format!("Failed to export the calls to {}.", path.display()))?;
+
+    println!("{} call(s) exported to {}, no transaction was sent.", calls.len(), path.display());
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -311,6 +346,16 @@ mod tests {
         assert!(is_compatible_version(node_version, katana_version).unwrap());
     }
 
+    #[test]
+    fn test_exported_call_serialization() {
+        let call = Call { to: Felt::ONE, selector: Felt::TWO, calldata: vec![Felt::THREE] };
+
+        assert_eq!(
+            serde_json::to_value(ExportedCall::from(&call)).unwrap(),
+            serde_json::json!({ "to": "0x1", "selector": "0x2", "calldata": ["0x3"] })
+        );
+    }
+
     #[test]
     fn test_is_compatible_version_invalid_version_string() {
         assert!(is_compatible_version("1.0", "1.0.0").is_err());
@@ -3,12 +3,71 @@
 //! Use a `Cli` struct to parse the CLI arguments
 //! and to have flexibility in the future to add more commands
 //! that may not start Torii directly.
-use clap::Parser;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
 use torii_cli::ToriiArgs;
+use torii_sqlite::snapshot::{self, DATABASE_FILE_NAME};
 
 #[derive(Parser)]
 #[command(name = "torii", author, version, about, long_about = None)]
 pub struct Cli {
+    #[command(subcommand)]
+    pub commands: Option<Commands>,
+
     #[command(flatten)]
     pub args: ToriiArgs,
 }
+
+#[derive(Subcommand)]
+pub enum Commands {
+    #[command(about = "Exports the indexed database into a snapshot file")]
+    Export {
+        #[arg(long, value_name = "PATH")]
+        #[arg(help = "Database directory of the indexer to export")]
+        db_dir: PathBuf,
+
+        #[arg(help = "Path to the snapshot file to create")]
+        output: PathBuf,
+    },
+
+    #[command(about = "Imports a snapshot file into an empty database directory, from which the \
+                       indexer resumes")]
+    Import {
+        #[arg(long, value_name = "PATH")]
+        #[arg(help = "Database directory to import the snapshot into")]
+        db_dir: PathBuf,
+
+        #[arg(help = "Path to the snapshot file to import")]
+        input: PathBuf,
+    },
+}
+
+impl Commands {
+    pub async fn execute(self) -> Result<()> {
+        match self {
+            Commands::Export { db_dir, output } => {
+                let head = snapshot::export(&db_dir.join(DATABASE_FILE_NAME), &output).await?;
+                println!(
+                    "Exported {} contracts indexed up to block {} to {}",
+                    head.contracts,
+                    head.head,
+                    output.display()
+                );
+            }
+
+            Commands::Import { db_dir, input } => {
+                let head = snapshot::import(&input, &db_dir.join(DATABASE_FILE_NAME)).await?;
+                println!(
+                    "Imported {} contracts indexed up to block {} into {}",
+                    head.contracts,
+                    head.head,
+                    db_dir.display()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
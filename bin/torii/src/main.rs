@@ -29,7 +29,12 @@ async fn main() -> anyhow::Result<()> {
     tracing::subscriber::set_global_default(subscriber)
         .expect("Failed to set the global tracing subscriber");
 
-    let args = Cli::parse().args.with_config_file()?;
+    let cli = Cli::parse();
+    if let Some(command) = cli.commands {
+        return command.execute().await;
+    }
+
+    let args = cli.args.with_config_file()?;
     let runner = Runner::new(args);
     runner.run().await?;
     Ok(())
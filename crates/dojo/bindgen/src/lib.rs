@@ -133,8 +133,7 @@ fn gather_dojo_data(
 ) -> BindgenResult<DojoData> {
     let root_dir: Utf8PathBuf = manifest_path.parent().unwrap().into();
 
-    let profile_config =
-        ProfileConfig::from_toml(root_dir.join(format!("dojo_{}.toml", profile_name)))?;
+    let profile_config = ProfileConfig::from_profile(&root_dir, profile_name)?;
     let target_dir = root_dir.join("target").join(profile_name);
 
     let world_local = WorldLocal::from_directory(&target_dir, profile_config)?;
@@ -107,6 +107,12 @@ struct StructWithOption {
     x: Option<u16>,
 }
 
+#[derive(Drop, Introspect)]
+struct StructWithTupleOption {
+    x: Option<(u8, u16)>,
+    y: Array<Option<(u8, u16)>>,
+}
+
 #[derive(Drop, Introspect)]
 struct Generic<T> {
     value: T,
@@ -265,6 +271,48 @@ fn test_layout_of_struct_with_option() {
     assert!(layout == expected);
 }
 
+#[test]
+fn test_layout_of_struct_with_tuple_option() {
+    let layout = Introspect::<StructWithTupleOption>::layout();
+    let option = _enum(
+        array![Option::Some(tuple(array![fixed(array![8]), fixed(array![16])])), Option::None],
+    );
+    let expected = Layout::Struct(
+        array![field(selector!("x"), option), field(selector!("y"), arr(option))].span(),
+    );
+
+    assert!(layout == expected);
+}
+
+#[test]
+fn test_ty_of_struct_with_tuple_option() {
+    let ty = Introspect::<StructWithTupleOption>::ty();
+    let option = Ty::Enum(
+        Enum {
+            name: 'Option<T>',
+            attrs: [].span(),
+            children: [
+                ('Some(T)', Ty::Tuple([Ty::Primitive('u8'), Ty::Primitive('u16')].span())),
+                ('None', Ty::Tuple([].span())),
+            ]
+                .span(),
+        },
+    );
+    let expected = Ty::Struct(
+        Struct {
+            name: 'StructWithTupleOption',
+            attrs: [].span(),
+            children: [
+                Member { name: 'x', attrs: [].span(), ty: option },
+                Member { name: 'y', attrs: [].span(), ty: Ty::Array([option].span()) },
+            ]
+                .span(),
+        },
+    );
+
+    assert!(ty == expected);
+}
+
 #[test]
 fn test_layout_of_packed_struct() {
     let layout = Introspect::<Vec3>::layout();
@@ -1,4 +1,5 @@
-use dojo::model::ModelStorage;
+use dojo::model::{Model, ModelIndex, ModelStorage};
+use dojo::world::IWorldDispatcherTrait;
 
 use crate::tests::helpers::{deploy_world_and_foo, Foo, NotCopiable, EnumOne, WithOptionAndEnums};
 
@@ -135,3 +136,56 @@ fn write_read_option_enums() {
     // using a `DojoOption` converter or customizing the serialization.
     assert_eq!(wo.b, Option::Some(0));
 }
+
+#[test]
+fn write_read_entities_in_one_call() {
+    let (world, foo_selector) = deploy_world_and_foo();
+
+    let bob = 0xb0b.try_into().unwrap();
+    let alice = 0xa11ce.try_into().unwrap();
+
+    let bob_foo = Foo { caller: bob, a: 1, b: 2 };
+    let alice_foo = Foo { caller: alice, a: 3, b: 4 };
+
+    world
+        .dispatcher
+        .set_entities(
+            foo_selector,
+            [
+                ModelIndex::Keys(bob_foo.serialized_keys()),
+                ModelIndex::Keys(alice_foo.serialized_keys()),
+            ]
+                .span(),
+            [bob_foo.serialized_values(), alice_foo.serialized_values()].span(),
+            Model::<Foo>::layout(),
+        );
+
+    let values = world
+        .dispatcher
+        .entities(
+            foo_selector,
+            [ModelIndex::Id(bob_foo.entity_id()), ModelIndex::Id(alice_foo.entity_id())].span(),
+            Model::<Foo>::layout(),
+        );
+
+    assert_eq!(values, [bob_foo.serialized_values(), alice_foo.serialized_values()].span());
+}
+
+#[test]
+#[should_panic(
+    expected: ("Length mismatch: `indexes` and `values` in `set_entities`", 'ENTRYPOINT_FAILED'),
+)]
+fn write_entities_lengths_mismatch() {
+    let (world, foo_selector) = deploy_world_and_foo();
+
+    let foo = Foo { caller: 0xb0b.try_into().unwrap(), a: 1, b: 2 };
+
+    world
+        .dispatcher
+        .set_entities(
+            foo_selector,
+            [ModelIndex::Keys(foo.serialized_keys())].span(),
+            [foo.serialized_values(), foo.serialized_values()].span(),
+            Model::<Foo>::layout(),
+        );
+}
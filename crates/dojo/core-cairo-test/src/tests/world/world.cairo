@@ -107,6 +107,46 @@ fn test_emit() {
     }
 }
 
+#[test]
+#[available_gas(6000000)]
+fn test_emit_macro() {
+    let bob = starknet::contract_address_const::<0xb0b>();
+
+    let namespace_def = NamespaceDef {
+        namespace: "dojo", resources: [TestResource::Event(e_SimpleEvent::TEST_CLASS_HASH),].span(),
+    };
+
+    let mut world = spawn_test_world([namespace_def].span());
+
+    let bob_def = ContractDefTrait::new_address(bob)
+        .with_writer_of([world.resource_selector(@"SimpleEvent")].span());
+    world.sync_perms_and_inits([bob_def].span());
+
+    drop_all_events(world.dispatcher.contract_address);
+
+    starknet::testing::set_contract_address(bob);
+
+    emit!(world, (SimpleEvent { id: 2, data: (3, 4) }, SimpleEvent { id: 5, data: (6, 7) }));
+
+    let event = starknet::testing::pop_log::<WorldEvent>(world.dispatcher.contract_address);
+
+    if let Option::Some(WorldEvent::EventEmitted(event)) = event {
+        assert(event.keys == [2].span(), 'bad keys');
+        assert(event.values == [3, 4].span(), 'bad values');
+    } else {
+        core::panic_with_felt252('no EventEmitted event');
+    }
+
+    let event = starknet::testing::pop_log::<WorldEvent>(world.dispatcher.contract_address);
+
+    if let Option::Some(WorldEvent::EventEmitted(event)) = event {
+        assert(event.keys == [5].span(), 'bad keys');
+        assert(event.values == [6, 7].span(), 'bad values');
+    } else {
+        core::panic_with_felt252('no EventEmitted event');
+    }
+}
+
 #[test]
 fn test_execute_multiple_worlds() {
     let (world1, bar1_contract) = deploy_world_and_bar();
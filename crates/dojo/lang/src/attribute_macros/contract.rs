@@ -11,6 +11,8 @@ use cairo_lang_syntax::node::{ast, Terminal, TypedStablePtr, TypedSyntaxNode};
 use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
 use dojo_types::naming;
 
+use super::parameters::{namespace_impl_infix, parse_namespace_parameter};
+use super::DOJO_CONTRACT_ATTR;
 use crate::aux_data::ContractAuxData;
 
 const CONTRACT_PATCH: &str = include_str!("./patches/contract.patch.cairo");
@@ -39,6 +41,15 @@ impl DojoContract {
 
         let mut contract = DojoContract { diagnostics: vec![], systems: vec![] };
 
+        let parameters = ContractParameters {
+            namespace: parse_namespace_parameter(
+                db,
+                module_ast,
+                DOJO_CONTRACT_ATTR,
+                &mut contract.diagnostics,
+            ),
+        };
+
         for (id, value) in [("name", &name.to_string())] {
             if !naming::is_name_valid(value) {
                 return PluginResult {
@@ -133,6 +144,16 @@ impl DojoContract {
                     CONTRACT_PATCH,
                     &UnorderedHashMap::from([
                         ("name".to_string(), RewriteNode::Text(name.to_string())),
+                        (
+                            "ns_infix".to_string(),
+                            RewriteNode::Text(namespace_impl_infix(
+                                parameters.namespace.as_deref(),
+                            )),
+                        ),
+                        (
+                            "world_default".to_string(),
+                            RewriteNode::Text(world_default_fn(parameters.namespace.as_deref())),
+                        ),
                         ("body".to_string(), RewriteNode::new_modified(body_nodes)),
                     ]),
                 )),
@@ -149,6 +170,7 @@ impl DojoContract {
                     content: code,
                     aux_data: Some(DynGeneratedFileAuxData::new(ContractAuxData {
                         name: name.to_string(),
+                        namespace: parameters.namespace.clone(),
                         systems: contract.systems.clone(),
                     })),
                     code_mappings,
@@ -383,6 +405,21 @@ impl DojoContract {
     }
 }
 
+/// Generates the `world_default` function of a contract which sets its namespace, returning the
+/// world storage of this namespace.
+fn world_default_fn(namespace: Option<&str>) -> String {
+    match namespace {
+        Some(namespace) => format!(
+            "
+        fn world_default(self: @ContractState) -> dojo::world::storage::WorldStorage {{
+            self.world(@\"{namespace}\")
+        }}
+"
+        ),
+        None => String::new(),
+    }
+}
+
 /// Checks if the constructor parameters are valid.
 /// We only allow one parameter for the constructor, which is the contract state,
 /// since `dojo_init` is called by the world after every resource has been deployed.
@@ -407,6 +444,12 @@ mod tests {
         assert!(is_valid_constructor_params(" ref self: ContractState"));
     }
 
+    #[test]
+    fn test_world_default_fn() {
+        assert!(world_default_fn(None).is_empty());
+        assert!(world_default_fn(Some("combat")).contains("self.world(@\"combat\")"));
+    }
+
     #[test]
     fn test_is_valid_constructor_params_not_ok() {
         assert!(!is_valid_constructor_params(""));
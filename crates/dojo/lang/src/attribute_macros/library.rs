@@ -145,6 +145,7 @@ impl DojoLibrary {
                     content: code,
                     aux_data: Some(DynGeneratedFileAuxData::new(ContractAuxData {
                         name: name.to_string(),
+                        namespace: None,
                         systems: library.systems.clone(),
                     })),
                     code_mappings,
@@ -9,6 +9,7 @@ pub mod event;
 pub mod interface;
 pub mod library;
 pub mod model;
+pub mod parameters;
 
 pub use contract::DojoContract;
 pub use event::DojoEvent;
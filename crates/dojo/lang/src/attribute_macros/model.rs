@@ -16,6 +16,8 @@ use dojo_types::naming;
 use starknet::core::utils::get_selector_from_name;
 
 use super::element::{compute_unique_hash, parse_members, serialize_member_ty};
+use super::parameters::{namespace_impl_infix, parse_namespace_parameter};
+use super::DOJO_MODEL_ATTR;
 use crate::aux_data::{Member, ModelAuxData};
 use crate::derive_macros::{
     extract_derive_attr_names, handle_derive_attrs, DOJO_INTROSPECT_DERIVE, DOJO_PACKED_DERIVE,
@@ -57,6 +59,9 @@ impl DojoModel {
             }
         }
 
+        let namespace =
+            parse_namespace_parameter(db, &struct_ast, DOJO_MODEL_ATTR, &mut diagnostics);
+
         let mut values: Vec<Member> = vec![];
         let mut keys: Vec<Member> = vec![];
         let mut members_values: Vec<RewriteNode> = vec![];
@@ -167,6 +172,10 @@ impl DojoModel {
             MODEL_CODE_PATCH,
             &UnorderedHashMap::from([
                 ("model_type".to_string(), RewriteNode::Text(model_type.clone())),
+                (
+                    "ns_infix".to_string(),
+                    RewriteNode::Text(namespace_impl_infix(namespace.as_deref())),
+                ),
                 ("serialized_keys".to_string(), RewriteNode::new_modified(serialized_keys)),
                 ("serialized_values".to_string(), RewriteNode::new_modified(serialized_values)),
                 ("keys_to_tuple".to_string(), RewriteNode::Text(keys_to_tuple)),
@@ -193,7 +202,7 @@ impl DojoModel {
 
         crate::debug_expand(&format!("MODEL PATCH: {model_type}"), &code);
 
-        let aux_data = ModelAuxData { name: model_type.clone(), members };
+        let aux_data = ModelAuxData { name: model_type.clone(), namespace, members };
 
         PluginResult {
             code: Some(PluginGeneratedFile {
@@ -0,0 +1,94 @@
+//! Parameters of the Dojo attribute macros.
+//!
+//! A resource can set its namespace directly in its attribute, like
+//! `#[dojo::contract(namespace: "combat")]`, instead of relying on the namespace mappings of the
+//! profile config.
+
+use cairo_lang_defs::plugin::PluginDiagnostic;
+use cairo_lang_diagnostics::Severity;
+use cairo_lang_syntax::attribute::structured::{AttributeArgVariant, AttributeStructurize};
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::helpers::QueryAttrs;
+use cairo_lang_syntax::node::{ast, TypedStablePtr, TypedSyntaxNode};
+use dojo_types::naming;
+
+pub const NAMESPACE_PARAM: &str = "namespace";
+
+/// The marker inserted before the namespace in the name of the implementations embedded in the
+/// ABI, since the ABI is the only information about the resource available from the artifacts.
+pub const NAMESPACE_IMPL_MARKER: &str = "ns__";
+
+/// Parses the `namespace` parameter of the `attr_name` attribute of a Dojo item.
+///
+/// Returns `None` if no namespace is provided, or if the parameters are invalid, in which case a
+/// diagnostic is added.
+pub fn parse_namespace_parameter(
+    db: &dyn SyntaxGroup,
+    item: &impl QueryAttrs,
+    attr_name: &str,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+) -> Option<String> {
+    let attr = item.find_attr(db, attr_name)?;
+    let mut namespace = None;
+
+    for arg in attr.structurize(db).args {
+        let stable_ptr = arg.arg.stable_ptr().untyped();
+
+        let AttributeArgVariant::Named { name, value, .. } = arg.variant else {
+            diagnostics.push(PluginDiagnostic {
+                stable_ptr,
+                message: format!(
+                    "Unexpected argument for {attr_name}, only `{NAMESPACE_PARAM}: \
+                     \"<NAMESPACE>\"` is supported."
+                ),
+                severity: Severity::Error,
+            });
+            continue;
+        };
+
+        if name.name != NAMESPACE_PARAM {
+            diagnostics.push(PluginDiagnostic {
+                stable_ptr,
+                message: format!("Unexpected argument '{}' for {attr_name}.", name.name),
+                severity: Severity::Error,
+            });
+            continue;
+        }
+
+        if namespace.is_some() {
+            diagnostics.push(PluginDiagnostic {
+                stable_ptr,
+                message: format!("Too many '{NAMESPACE_PARAM}' arguments for {attr_name}."),
+                severity: Severity::Error,
+            });
+            continue;
+        }
+
+        let value = match value {
+            ast::Expr::String(s) => s.string_value(db),
+            _ => None,
+        };
+
+        match value {
+            Some(value) if naming::is_name_valid(&value) => namespace = Some(value),
+            _ => diagnostics.push(PluginDiagnostic {
+                stable_ptr,
+                message: format!(
+                    "The '{NAMESPACE_PARAM}' argument of {attr_name} must be a string which can \
+                     only contain characters (a-z/A-Z), digits (0-9) and underscore (_)."
+                ),
+                severity: Severity::Error,
+            }),
+        }
+    }
+
+    namespace
+}
+
+/// Returns the part of the name of the embedded implementations identifying the namespace of the
+/// resource, inserted between the name of the resource and the name of the implementation.
+///
+/// Empty if the resource doesn't set its namespace, which keeps the generated code unchanged.
+pub fn namespace_impl_infix(namespace: Option<&str>) -> String {
+    namespace.map_or_else(String::new, |ns| format!("{NAMESPACE_IMPL_MARKER}{ns}__"))
+}
@@ -15,7 +15,7 @@ pub mod $name$ {
     impl UpgradeableImpl = upgradeable_cpt::UpgradeableImpl<ContractState>;
 
     #[abi(embed_v0)]
-    pub impl $name$__ContractImpl of IContract<ContractState> {}
+    pub impl $name$__$ns_infix$ContractImpl of IContract<ContractState> {}
 
     #[abi(embed_v0)]
     pub impl $name$__DeployedContractImpl of IDeployedResource<ContractState> {
@@ -33,6 +33,7 @@ pub mod $name$ {
         fn world_ns_hash(self: @ContractState, namespace_hash: felt252) -> dojo::world::storage::WorldStorage {
             dojo::world::WorldStorageTrait::new_from_hash(self.world_provider.world_dispatcher(), namespace_hash)
         }
+$world_default$
     }
 
     $body$
@@ -88,7 +88,7 @@ pub mod m_$model_type$ {
     impl $model_type$__DojoStoredModelImpl = dojo::model::component::IStoredModelImpl<ContractState, $model_type$>;
 
     #[abi(embed_v0)]
-    impl $model_type$__DojoModelImpl = dojo::model::component::IModelImpl<ContractState, $model_type$>;
+    impl $model_type$__$ns_infix$DojoModelImpl = dojo::model::component::IModelImpl<ContractState, $model_type$>;
 
     #[abi(per_item)]
     #[generate_trait]
@@ -21,12 +21,16 @@ pub struct Member {
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ModelAuxData {
     pub name: String,
+    /// The namespace set in the model attribute, if any.
+    pub namespace: Option<String>,
     pub members: Vec<Member>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ContractAuxData {
     pub name: String,
+    /// The namespace set in the contract attribute, if any.
+    pub namespace: Option<String>,
     pub systems: Vec<String>,
 }
 
@@ -7,8 +7,8 @@ use cairo_lang_syntax::node::{ids, Terminal, TypedSyntaxNode};
 use starknet::core::utils::get_selector_from_name;
 
 use super::utils::{
-    get_array_item_type, get_tuple_item_types, is_array, is_byte_array, is_tuple,
-    is_unsupported_option_type, primitive_type_introspection,
+    get_array_item_type, get_option_item_type, get_tuple_item_types, is_array, is_byte_array,
+    is_option_of_tuple, is_tuple, primitive_type_introspection,
 };
 
 const CAIRO_DELIMITERS: [char; 7] = ['[', ']', '<', '>', '(', ')', ','];
@@ -132,7 +132,10 @@ pub fn build_array_layout_from_type(
 ) -> String {
     let array_item_type = get_array_item_type(item_type);
 
-    if is_tuple(&array_item_type) {
+    if is_tuple(&array_item_type)
+        || is_array(&array_item_type)
+        || is_option_of_tuple(&array_item_type)
+    {
         format!(
             "dojo::meta::Layout::Array(
                 array![
@@ -141,15 +144,6 @@ pub fn build_array_layout_from_type(
             )",
             build_item_layout_from_type(diagnostics, diagnostic_item, &array_item_type)
         )
-    } else if is_array(&array_item_type) {
-        format!(
-            "dojo::meta::Layout::Array(
-                array![
-                    {}
-                ].span()
-            )",
-            build_array_layout_from_type(diagnostics, diagnostic_item, &array_item_type)
-        )
     } else {
         format!("dojo::meta::introspect::Introspect::<{}>::layout()", item_type)
     }
@@ -177,6 +171,34 @@ pub fn build_tuple_layout_from_type(
     )
 }
 
+/// Build the option layout describing the provided option type, when the option
+/// item type contains a tuple.
+/// item_type could be something like Option<(u8, u32)> for example.
+/// The layout is the same as the one of the `Introspect` impl of `Option<T>`.
+pub fn build_option_layout_from_type(
+    diagnostics: &mut Vec<PluginDiagnostic>,
+    diagnostic_item: ids::SyntaxStablePtrId,
+    item_type: &str,
+) -> String {
+    let option_item_type = get_option_item_type(item_type);
+
+    format!(
+        "dojo::meta::Layout::Enum(
+            array![
+                dojo::meta::FieldLayout {{
+                    selector: 0,
+                    layout: {}
+                }},
+                dojo::meta::FieldLayout {{
+                    selector: 1,
+                    layout: dojo::meta::Layout::Fixed(array![].span())
+                }}
+            ].span()
+        )",
+        build_item_layout_from_type(diagnostics, diagnostic_item, &option_item_type)
+    )
+}
+
 /// Build the layout describing the provided type.
 /// item_type could be any type (array, tuple, struct, ...)
 pub fn build_item_layout_from_type(
@@ -188,16 +210,9 @@ pub fn build_item_layout_from_type(
         build_array_layout_from_type(diagnostics, diagnostic_item, item_type)
     } else if is_tuple(item_type) {
         build_tuple_layout_from_type(diagnostics, diagnostic_item, item_type)
+    } else if is_option_of_tuple(item_type) {
+        build_option_layout_from_type(diagnostics, diagnostic_item, item_type)
     } else {
-        // For Option<T>, T cannot be a tuple
-        if is_unsupported_option_type(item_type) {
-            diagnostics.push(PluginDiagnostic {
-                stable_ptr: diagnostic_item,
-                message: "Option<T> cannot be used with tuples. Prefer using a struct.".into(),
-                severity: Severity::Error,
-            });
-        }
-
         // `usize` is forbidden because its size is architecture-dependent
         if item_type == "usize" {
             diagnostics.push(PluginDiagnostic {
@@ -4,7 +4,8 @@ use cairo_lang_syntax::node::helpers::QueryAttrs;
 use cairo_lang_syntax::node::TypedSyntaxNode;
 
 use super::utils::{
-    get_tuple_item_types, is_array, is_byte_array, is_tuple, primitive_type_introspection,
+    get_tuple_item_types, is_array, is_byte_array, is_option_of_tuple, is_tuple,
+    primitive_type_introspection,
 };
 
 pub fn compute_struct_layout_size(
@@ -177,7 +178,7 @@ pub fn get_field_size_from_type_clause(
 }
 
 pub fn compute_item_size_from_type(item_type: &String) -> Vec<String> {
-    if is_array(item_type) || is_byte_array(item_type) {
+    if is_array(item_type) || is_byte_array(item_type) || is_option_of_tuple(item_type) {
         vec!["Option::None".to_string()]
     } else if is_tuple(item_type) {
         compute_tuple_size_from_type(item_type)
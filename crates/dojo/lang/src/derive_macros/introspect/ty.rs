@@ -5,7 +5,10 @@ use cairo_lang_syntax::node::db::SyntaxGroup;
 use cairo_lang_syntax::node::helpers::QueryAttrs;
 use cairo_lang_syntax::node::{Terminal, TypedSyntaxNode};
 
-use super::utils::{get_array_item_type, get_tuple_item_types, is_array, is_byte_array, is_tuple};
+use super::utils::{
+    get_array_item_type, get_option_item_type, get_tuple_item_types, is_array, is_byte_array,
+    is_option_of_tuple, is_tuple,
+};
 
 pub fn build_struct_ty(db: &dyn SyntaxGroup, name: &String, struct_ast: &ItemStruct) -> String {
     let members_ty = struct_ast
@@ -111,11 +114,32 @@ pub fn build_item_ty_from_type(item_type: &String) -> String {
         "dojo::meta::introspect::Ty::ByteArray".to_string()
     } else if is_tuple(item_type) {
         build_tuple_ty_from_type(item_type)
+    } else if is_option_of_tuple(item_type) {
+        build_option_ty_from_type(item_type)
     } else {
         format!("dojo::meta::introspect::Introspect::<{}>::ty()", item_type)
     }
 }
 
+/// Build the ty of an option whose item type contains a tuple,
+/// the same way as the `Introspect` impl of `Option<T>`.
+pub fn build_option_ty_from_type(item_type: &str) -> String {
+    let option_item_type = get_option_item_type(item_type);
+    format!(
+        "dojo::meta::introspect::Ty::Enum(
+            dojo::meta::introspect::Enum {{
+                name: 'Option<T>',
+                attrs: array![].span(),
+                children: array![
+                ('Some(T)', {}),
+                ('None', dojo::meta::introspect::Ty::Tuple(array![].span()))
+                ].span()
+            }}
+        )",
+        build_item_ty_from_type(&option_item_type)
+    )
+}
+
 pub fn build_tuple_ty_from_type(item_type: &str) -> String {
     let tuple_items = get_tuple_item_types(item_type)
         .iter()
@@ -21,10 +21,15 @@ pub fn primitive_type_introspection() -> HashMap<String, TypeIntrospection> {
     ])
 }
 
-/// Check if the provided type is an unsupported `Option<T>`,
-/// because tuples are not supported with Option.
-pub fn is_unsupported_option_type(ty: &str) -> bool {
-    ty.starts_with("Option<(")
+pub fn is_option(ty: &str) -> bool {
+    ty.starts_with("Option<")
+}
+
+/// Check if the provided type is an `Option<T>` where `T` contains a tuple.
+/// As tuples don't implement `Introspect`, the layout and the ty of such an option
+/// are built inline, instead of using the `Introspect` impl of `Option<T>`.
+pub fn is_option_of_tuple(ty: &str) -> bool {
+    is_option(ty) && get_option_item_type(ty).contains('(')
 }
 
 pub fn is_byte_array(ty: &str) -> bool {
@@ -47,6 +52,10 @@ pub fn get_array_item_type(ty: &str) -> String {
     }
 }
 
+pub fn get_option_item_type(ty: &str) -> String {
+    ty.trim().strip_prefix("Option<").unwrap().strip_suffix('>').unwrap().to_string()
+}
+
 /// split a tuple in array of items (nested tuples are not splitted).
 /// example (u8, (u16, u32), u128) -> ["u8", "(u16, u32)", "u128"]
 pub fn get_tuple_item_types(ty: &str) -> Vec<String> {
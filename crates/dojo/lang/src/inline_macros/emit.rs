@@ -31,7 +31,7 @@ impl InlineMacroExprPlugin for EmitMacro {
 
         let args = arg_list.arguments(db).elements(db);
 
-        if args.len() < 2 || args.len() > 3 {
+        if args.len() != 2 {
             return InlinePluginResult {
                 code: None,
                 diagnostics: vec![PluginDiagnostic {
@@ -44,13 +44,13 @@ impl InlineMacroExprPlugin for EmitMacro {
 
         let world = &args[0];
 
-        let ast::ArgClause::Unnamed(models) = args[1].arg_clause(db) else {
+        let ast::ArgClause::Unnamed(events) = args[1].arg_clause(db) else {
             return unsupported_arg_diagnostic(db, syntax);
         };
 
         let mut bundle = vec![];
 
-        match models.value(db) {
+        match events.value(db) {
             ast::Expr::Parenthesized(parens) => {
                 let syntax_node = parens.expr(db).as_syntax_node();
                 bundle.push(syntax_node.get_text(db));
@@ -81,21 +81,29 @@ impl InlineMacroExprPlugin for EmitMacro {
             return InlinePluginResult {
                 code: None,
                 diagnostics: vec![PluginDiagnostic {
-                    message: "Invalid arguments: No models provided.".to_string(),
+                    message: "Invalid arguments: No events provided.".to_string(),
                     stable_ptr: arg_list.arguments(db).stable_ptr().untyped(),
                     severity: Severity::Error,
                 }],
             };
         }
 
+        // The world storage is copied, so any world expression can be used,
+        // and not only a mutable variable.
+        builder.add_str(&format!(
+            "
+            let mut __world_storage__ = {};
+            ",
+            world.as_syntax_node().get_text(db),
+        ));
+
         for event in bundle {
             builder.add_str(&format!(
                 "
                 let __event_instance__ = {};
-                dojo::event::Event::emit(@__event_instance__, {});
+                dojo::event::EventStorage::emit_event(ref __world_storage__, @__event_instance__);
                 ",
                 event,
-                world.as_syntax_node().get_text(db),
             ));
         }
 
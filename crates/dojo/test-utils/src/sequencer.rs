@@ -105,6 +105,10 @@ impl TestSequencer {
         &self.account
     }
 
+    pub fn handle(&self) -> &LaunchedNode {
+        &self.handle
+    }
+
     pub fn stop(self) -> Result<(), Error> {
         self.handle.rpc.stop()
     }
@@ -115,7 +119,7 @@ impl TestSequencer {
 }
 
 pub fn get_default_test_config(sequencing: SequencingConfig) -> Config {
-    let dev = DevConfig { fee: false, account_validation: true, fixed_gas_prices: None };
+    let dev = DevConfig { fee: false, account_validation: true, ..Default::default() };
     let mut chain =
         katana_chain_spec::dev::ChainSpec { id: ChainId::SEPOLIA, ..Default::default() };
     chain.genesis.sequencer_address = *DEFAULT_SEQUENCER_ADDRESS;
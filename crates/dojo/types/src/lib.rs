@@ -12,6 +12,7 @@ pub mod primitive_conversion;
 pub mod schema;
 pub mod storage;
 pub mod system;
+pub mod type_path;
 
 /// Represents the metadata of a World
 #[derive(Debug, Clone, Serialize, Default)]
@@ -0,0 +1,62 @@
+//! Parsing of the fully qualified names of the Cairo types, as found in the ABIs of the contracts,
+//! eg `core::array::Array::<(core::felt252, core::bool)>`.
+
+/// Returns the generic argument of `r#type` if it is the type `name`, eg `u8` for
+/// `core::array::Array::<u8>`.
+pub fn generic_argument<'a>(r#type: &'a str, name: &str) -> Option<&'a str> {
+    r#type.strip_prefix(name)?.strip_prefix("::<")?.strip_suffix('>')
+}
+
+/// Splits the members of a tuple type, ignoring the commas of the nested types.
+pub fn split_tuple(members: &str) -> Vec<&str> {
+    let mut split = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+
+    for (i, c) in members.char_indices() {
+        match c {
+            '(' | '<' | '[' => depth += 1,
+            ')' | '>' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                split.push(members[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    let last = members[start..].trim();
+    if !last.is_empty() {
+        split.push(last);
+    }
+
+    split
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generic_argument() {
+        assert_eq!(generic_argument("core::array::Array::<u8>", "core::array::Array"), Some("u8"));
+        assert_eq!(
+            generic_argument("core::option::Option::<(u8, u16)>", "core::option::Option"),
+            Some("(u8, u16)")
+        );
+        assert_eq!(generic_argument("core::array::Span::<u8>", "core::array::Array"), None);
+        assert_eq!(generic_argument("core::array::Array", "core::array::Array"), None);
+    }
+
+    #[test]
+    fn test_split_tuple() {
+        assert_eq!(
+            split_tuple("core::felt252, (core::integer::u8, core::bool), Array::<u8>"),
+            vec!["core::felt252", "(core::integer::u8, core::bool)", "Array::<u8>"]
+        );
+        assert_eq!(
+            split_tuple("Array::<(u8, u16)>, [u8; 2]"),
+            vec!["Array::<(u8, u16)>", "[u8; 2]"]
+        );
+        assert!(split_tuple("").is_empty());
+    }
+}
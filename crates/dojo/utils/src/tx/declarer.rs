@@ -4,12 +4,13 @@
 //! the declaration to avoid declaring several times the same contract.
 //! Also, checking onchain if the class is declared is less expensive that trying to declare.
 //!
-//! Declare transactions can't be multicalled. The only way to do so is by having multiple accounts.
+//! Declare transactions can't be multicalled. They can be sent concurrently by having multiple
+//! accounts, or by sending several declarations from the same account with consecutive nonces.
 
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use starknet::accounts::ConnectedAccount;
+use starknet::accounts::{AccountError, ConnectedAccount};
 use starknet::core::types::{
     BlockId, BlockTag, DeclareTransactionResult, Felt, FlattenedSierraClass, StarknetError,
 };
@@ -82,6 +83,71 @@ where
         Ok(results)
     }
 
+    /// Declares all the classes registered in the declarer with a single account, sending up to
+    /// `max_concurrency` declarations before waiting for them to be accepted.
+    ///
+    /// Since the declarations are sent before the previous ones are accepted, the nonce of the
+    /// account is managed locally.
+    ///
+    /// The order of the declarations is not guaranteed.
+    pub async fn declare_all_concurrently(
+        self,
+        max_concurrency: usize,
+    ) -> Result<Vec<TransactionResult>, TransactionError<A::SignError>> {
+        if max_concurrency <= 1 {
+            return self.declare_all().await;
+        }
+
+        let mut results = vec![];
+        let mut pending = vec![];
+
+        for (_, labeled_class) in self.classes {
+            let class_hash = labeled_class.class.class_hash();
+
+            if is_declared(&labeled_class.label, class_hash, self.account.provider()).await? {
+                results.push(TransactionResult::Noop);
+            } else {
+                pending.push(labeled_class);
+            }
+        }
+
+        let mut nonce = self.account.get_nonce().await?;
+
+        while !pending.is_empty() {
+            let batch = pending.drain(..max_concurrency.min(pending.len())).collect::<Vec<_>>();
+            let mut transaction_hashes = vec![];
+
+            for labeled_class in batch {
+                let sent = Self::send_declaration(
+                    labeled_class,
+                    &self.account,
+                    &self.txn_config,
+                    Some(nonce),
+                )
+                .await?;
+
+                match sent {
+                    Some(transaction_hash) => {
+                        transaction_hashes.push(transaction_hash);
+                        nonce += Felt::ONE;
+                    }
+                    None => results.push(TransactionResult::Noop),
+                }
+            }
+
+            let batch_results = futures::future::try_join_all(
+                transaction_hashes
+                    .into_iter()
+                    .map(|h| Self::wait(h, &self.account, &self.txn_config)),
+            )
+            .await?;
+
+            results.extend(batch_results);
+        }
+
+        Ok(results)
+    }
+
     /// Declares a class.
     pub async fn declare(
         labeled_class: LabeledClass,
@@ -94,6 +160,22 @@ where
             return Ok(TransactionResult::Noop);
         }
 
+        match Self::send_declaration(labeled_class, account, txn_config, None).await? {
+            Some(transaction_hash) => Self::wait(transaction_hash, account, txn_config).await,
+            None => Ok(TransactionResult::Noop),
+        }
+    }
+
+    /// Sends the declaration of a class, with the given nonce or the current nonce of the account.
+    ///
+    /// Returns the hash of the transaction, or `None` if the class is already declared.
+    async fn send_declaration(
+        labeled_class: LabeledClass,
+        account: &A,
+        txn_config: &TxnConfig,
+        nonce: Option<Felt>,
+    ) -> Result<Option<Felt>, TransactionError<A::SignError>> {
+        let class_hash = labeled_class.class.class_hash();
         let casm_class_hash = labeled_class.casm_class_hash;
 
         trace!(
@@ -103,20 +185,42 @@ where
             "Declaring class."
         );
 
-        let DeclareTransactionResult { transaction_hash, class_hash } = match txn_config.fee_config
-        {
+        let result = match txn_config.fee_config {
             FeeConfig::Strk(_) => {
-                account
-                    .declare_v3(Arc::new(labeled_class.class), casm_class_hash)
-                    .send_with_cfg(txn_config)
-                    .await?
+                let declaration =
+                    account.declare_v3(Arc::new(labeled_class.class), casm_class_hash);
+                let declaration = match nonce {
+                    Some(nonce) => declaration.nonce(nonce),
+                    None => declaration,
+                };
+                declaration.send_with_cfg(txn_config).await
             }
             FeeConfig::Eth(_) => {
-                account
-                    .declare_v2(Arc::new(labeled_class.class), casm_class_hash)
-                    .send_with_cfg(txn_config)
-                    .await?
+                let declaration =
+                    account.declare_v2(Arc::new(labeled_class.class), casm_class_hash);
+                let declaration = match nonce {
+                    Some(nonce) => declaration.nonce(nonce),
+                    None => declaration,
+                };
+                declaration.send_with_cfg(txn_config).await
+            }
+        };
+
+        let DeclareTransactionResult { transaction_hash, class_hash } = match result {
+            Ok(result) => result,
+            // The class may have been declared since it was checked, by an other account declaring
+            // the same classes or by the pending transaction of an interrupted migration.
+            Err(AccountError::Provider(ProviderError::StarknetError(
+                StarknetError::ClassAlreadyDeclared,
+            ))) => {
+                trace!(
+                    label = labeled_class.label,
+                    class_hash = format!("{:#066x}", class_hash),
+                    "Class declared concurrently."
+                );
+                return Ok(None);
             }
+            Err(e) => return Err(e.into()),
         };
 
         trace!(
@@ -127,6 +231,15 @@ where
             "Declared class."
         );
 
+        Ok(Some(transaction_hash))
+    }
+
+    /// Waits for the declaration transaction to be accepted, if required by the configuration.
+    async fn wait(
+        transaction_hash: Felt,
+        account: &A,
+        txn_config: &TxnConfig,
+    ) -> Result<TransactionResult, TransactionError<A::SignError>> {
         if txn_config.wait {
             let receipt = TransactionWaiter::new(transaction_hash, &account.provider()).await?;
 
@@ -86,16 +86,47 @@ where
             return Ok(TransactionResult::Noop);
         }
 
-        trace!(?self.calls, "Invoke contract multicall.");
+        self.invoke_calls(self.calls.clone()).await
+    }
+
+    /// Invokes the calls in batches of at most `max_calls` calls, one transaction per batch.
+    ///
+    /// The order of the calls is the same as the order of the calls added to the invoker, and a
+    /// batch is only sent once the previous one has been sent.
+    pub async fn multicall_batched(
+        &self,
+        max_calls: usize,
+    ) -> Result<Vec<TransactionResult>, TransactionError<A::SignError>> {
+        let mut results = vec![];
+
+        for calls in self.calls.chunks(max_calls.max(1)) {
+            results.push(self.invoke_calls(calls.to_vec()).await?);
+        }
+
+        Ok(results)
+    }
+
+    /// Returns the number of transactions required to invoke all the calls in batches of at most
+    /// `max_calls` calls.
+    pub fn batches_count(&self, max_calls: usize) -> usize {
+        self.calls.len().div_ceil(max_calls.max(1))
+    }
+
+    /// Invokes the given calls in one single transaction.
+    async fn invoke_calls(
+        &self,
+        calls: Vec<Call>,
+    ) -> Result<TransactionResult, TransactionError<A::SignError>> {
+        trace!(?calls, "Invoke contract multicall.");
 
         let tx = match self.txn_config.fee_config {
             FeeConfig::Strk(config) => {
                 trace!(?config, "Invoking with STRK.");
-                self.account.execute_v3(self.calls.clone()).send_with_cfg(&self.txn_config).await?
+                self.account.execute_v3(calls).send_with_cfg(&self.txn_config).await?
             }
             FeeConfig::Eth(config) => {
                 trace!(?config, "Invoking with ETH.");
-                self.account.execute_v1(self.calls.clone()).send_with_cfg(&self.txn_config).await?
+                self.account.execute_v1(calls).send_with_cfg(&self.txn_config).await?
             }
         };
 
@@ -1,6 +1,11 @@
+use std::collections::HashMap;
+
 use anyhow::{self, Result};
 use cainome::cairo_serde::{ByteArray, CairoSerde};
+use dojo_types::type_path::{generic_argument, split_tuple};
 use num_bigint::BigUint;
+use serde_json::Value;
+use starknet::core::types::contract::{AbiEntry, AbiNamedMember};
 use starknet::core::types::{Felt, FromStrError};
 use starknet::core::utils::cairo_short_string_to_felt;
 
@@ -27,6 +32,11 @@ pub type DecoderResult<T, E = CalldataDecoderError> = Result<T, E>;
 
 const ARRAY_ITEM_DELIMITER: char = ',';
 const ITEM_PREFIX_DELIMITER: char = ':';
+const ITEM_PREFIXES: [&str; 8] =
+    ["u256", "str", "sstr", "int", "arr", "u256arr", "farr", "u256farr"];
+
+const U256_TYPE: &str = "core::integer::u256";
+const BYTE_ARRAY_TYPE: &str = "core::byte_array::ByteArray";
 
 /// A trait for decoding calldata into a vector of Felts.
 trait CalldataDecoder {
@@ -241,6 +251,261 @@ pub fn decode_single_calldata(item: &str) -> DecoderResult<Vec<Felt>> {
     Ok(felts)
 }
 
+/// Decodes the calldata of `function` from typed values, one per input of the function, which are
+/// serialized according to the types of the inputs found in `abi`:
+///
+/// - integers, `felt252`, `ContractAddress`, `ClassHash`...: a decimal or hexadecimal value. A
+///   `felt252` can also be a short string.
+/// - `u256`: a decimal or hexadecimal value.
+/// - `bool`: `true` or `false`.
+/// - `ByteArray`: the string itself.
+/// - arrays, spans, tuples and fixed-size arrays: a JSON array, eg `[1,2,3]`.
+/// - structs: a JSON object with the members of the struct, eg `{"x":1,"y":2}`.
+/// - enums: the name of the variant if it has no data, eg `Left`, or a JSON object with the variant
+///   and its data, eg `{"Some":1}`.
+///
+/// A value with one of the prefixes supported by [`decode_single_calldata`] is decoded without
+/// using its type.
+///
+/// If the function is not in the ABI, or if the number of values doesn't match the number of
+/// inputs of the function, the calldata is decoded with [`decode_calldata`] instead.
+pub fn decode_calldata_with_abi(
+    abi: &[AbiEntry],
+    function: &str,
+    input: &Vec<String>,
+) -> DecoderResult<Vec<Felt>> {
+    let types = AbiTypes::new(abi);
+
+    let inputs = match types.functions.get(function) {
+        Some(inputs) if inputs.len() == input.len() => inputs,
+        _ => return decode_calldata(input),
+    };
+
+    let mut calldata = vec![];
+    for (member, item) in inputs.iter().zip(input) {
+        calldata.extend(types.decode_item(&member.r#type, item).map_err(|e| {
+            CalldataDecoderError::ParseError(format!("Invalid value for `{}`: {e}", member.name))
+        })?);
+    }
+
+    Ok(calldata)
+}
+
+/// The functions and the types of an ABI.
+struct AbiTypes<'a> {
+    functions: HashMap<&'a str, &'a [AbiNamedMember]>,
+    structs: HashMap<&'a str, &'a [AbiNamedMember]>,
+    enums: HashMap<&'a str, &'a [AbiNamedMember]>,
+}
+
+impl<'a> AbiTypes<'a> {
+    fn new(abi: &'a [AbiEntry]) -> Self {
+        let mut types =
+            Self { functions: HashMap::new(), structs: HashMap::new(), enums: HashMap::new() };
+        types.add_entries(abi);
+        types
+    }
+
+    fn add_entries(&mut self, entries: &'a [AbiEntry]) {
+        for entry in entries {
+            match entry {
+                AbiEntry::Function(f) => {
+                    self.functions.insert(&f.name, &f.inputs);
+                }
+                AbiEntry::Interface(i) => self.add_entries(&i.items),
+                AbiEntry::Struct(s) => {
+                    self.structs.insert(&s.name, &s.members);
+                }
+                AbiEntry::Enum(e) => {
+                    self.enums.insert(&e.name, &e.variants);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Decodes a single typed value given on the command line.
+    fn decode_item(&self, r#type: &str, item: &str) -> DecoderResult<Vec<Felt>> {
+        let item = item.trim();
+
+        if item
+            .split_once(ITEM_PREFIX_DELIMITER)
+            .is_some_and(|(prefix, _)| ITEM_PREFIXES.contains(&prefix))
+        {
+            return decode_single_calldata(item);
+        }
+
+        // only the values of the composite types are JSON, the others are used as they are
+        let is_primitive = r#type == U256_TYPE
+            || r#type == BYTE_ARRAY_TYPE
+            || !(r#type.starts_with('(')
+                || r#type.starts_with('[')
+                || r#type.contains('<')
+                || self.structs.contains_key(r#type)
+                || self.enums.contains_key(r#type));
+
+        let value = if is_primitive {
+            Value::String(item.to_string())
+        } else {
+            serde_json::from_str(item).unwrap_or_else(|_| Value::String(item.to_string()))
+        };
+
+        let mut felts = vec![];
+        self.decode_value(r#type, &value, &mut felts)?;
+        Ok(felts)
+    }
+
+    fn decode_value(
+        &self,
+        r#type: &str,
+        value: &Value,
+        felts: &mut Vec<Felt>,
+    ) -> DecoderResult<()> {
+        if let Some(inner) = generic_argument(r#type, "core::array::Array")
+            .or_else(|| generic_argument(r#type, "core::array::Span"))
+        {
+            let items = json_array(value)?;
+            felts.push(items.len().into());
+            for item in items {
+                self.decode_value(inner, item, felts)?;
+            }
+            return Ok(());
+        }
+
+        if let Some(inner) = generic_argument(r#type, "core::option::Option") {
+            match value {
+                Value::Null => felts.push(Felt::ONE),
+                Value::String(variant) if variant == "None" => felts.push(Felt::ONE),
+                Value::Object(map) if map.len() == 1 && map.contains_key("Some") => {
+                    felts.push(Felt::ZERO);
+                    self.decode_value(inner, &map["Some"], felts)?;
+                }
+                value => {
+                    felts.push(Felt::ZERO);
+                    self.decode_value(inner, value, felts)?;
+                }
+            }
+            return Ok(());
+        }
+
+        if let Some(members) = r#type.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+            let members = split_tuple(members);
+            let items = json_array(value)?;
+            if items.len() != members.len() {
+                return Err(parse_error(format!("Expected a tuple of {} items", members.len())));
+            }
+            for (member, item) in members.into_iter().zip(items) {
+                self.decode_value(member, item, felts)?;
+            }
+            return Ok(());
+        }
+
+        if let Some((inner, size)) = r#type
+            .strip_prefix('[')
+            .and_then(|t| t.strip_suffix(']'))
+            .and_then(|t| t.rsplit_once(';'))
+        {
+            let size = size.trim().parse::<usize>()?;
+            let items = json_array(value)?;
+            if items.len() != size {
+                return Err(parse_error(format!("Expected an array of {size} items")));
+            }
+            for item in items {
+                self.decode_value(inner.trim(), item, felts)?;
+            }
+            return Ok(());
+        }
+
+        if r#type == U256_TYPE {
+            felts.extend(U256CalldataDecoder.decode(&json_scalar(value)?)?);
+            return Ok(());
+        }
+
+        if let Some(members) = self.structs.get(r#type) {
+            match value {
+                Value::Object(map) => {
+                    for member in members.iter() {
+                        let value = map.get(&member.name).ok_or_else(|| {
+                            parse_error(format!("Missing member `{}`", member.name))
+                        })?;
+                        self.decode_value(&member.r#type, value, felts)?;
+                    }
+                }
+                Value::Array(items) if items.len() == members.len() => {
+                    for (member, item) in members.iter().zip(items) {
+                        self.decode_value(&member.r#type, item, felts)?;
+                    }
+                }
+                _ => return Err(parse_error(format!("Expected a JSON object for `{}`", r#type))),
+            }
+            return Ok(());
+        }
+
+        if let Some(variants) = self.enums.get(r#type) {
+            let (name, data) = match value {
+                Value::String(name) => (name, None),
+                Value::Object(map) if map.len() == 1 => {
+                    let (name, data) = map.iter().next().expect("map has one entry");
+                    (name, Some(data))
+                }
+                _ => return Err(parse_error(format!("Expected a variant of `{}`", r#type))),
+            };
+
+            let index = variants
+                .iter()
+                .position(|v| v.name == *name)
+                .ok_or_else(|| parse_error(format!("Unknown variant `{name}` of `{}`", r#type)))?;
+            felts.push(index.into());
+
+            let variant = &variants[index];
+            match data {
+                Some(data) => self.decode_value(&variant.r#type, data, felts)?,
+                None if variant.r#type == "()" => {}
+                None => return Err(parse_error(format!("Variant `{name}` requires data"))),
+            }
+            return Ok(());
+        }
+
+        decode_primitive(r#type, &json_scalar(value)?, felts)
+    }
+}
+
+fn decode_primitive(r#type: &str, input: &str, felts: &mut Vec<Felt>) -> DecoderResult<()> {
+    let decoded = match r#type.rsplit("::").next().unwrap_or(r#type) {
+        "bool" => match input {
+            "true" => vec![Felt::ONE],
+            "false" => vec![Felt::ZERO],
+            _ => return Err(parse_error("Expected `true` or `false`".to_string())),
+        },
+        "ByteArray" => StrCalldataDecoder.decode(input)?,
+        "i8" | "i16" | "i32" | "i64" | "i128" => SignedIntegerCalldataDecoder.decode(input)?,
+        "felt252" => DefaultCalldataDecoder
+            .decode(input)
+            .or_else(|_| ShortStrCalldataDecoder.decode(input))?,
+        _ => DefaultCalldataDecoder.decode(input)?,
+    };
+
+    felts.extend(decoded);
+    Ok(())
+}
+
+fn json_array(value: &Value) -> DecoderResult<&Vec<Value>> {
+    value.as_array().ok_or_else(|| parse_error("Expected a JSON array".to_string()))
+}
+
+fn json_scalar(value: &Value) -> DecoderResult<String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Bool(b) => Ok(b.to_string()),
+        _ => Err(parse_error(format!("Unexpected value `{value}`"))),
+    }
+}
+
+fn parse_error(message: String) -> CalldataDecoderError {
+    CalldataDecoderError::ParseError(message)
+}
+
 #[cfg(test)]
 mod tests {
     use starknet::core::utils::cairo_short_string_to_felt;
@@ -502,4 +767,109 @@ mod tests {
         let result = decoder.decode(input);
         assert!(result.is_err());
     }
+
+    fn test_abi() -> Vec<AbiEntry> {
+        serde_json::from_value(serde_json::json!([
+            {
+                "type": "struct",
+                "name": "ns::Vec2",
+                "members": [
+                    { "name": "x", "type": "core::integer::u32" },
+                    { "name": "y", "type": "core::integer::u32" }
+                ]
+            },
+            {
+                "type": "enum",
+                "name": "ns::Direction",
+                "variants": [
+                    { "name": "Left", "type": "()" },
+                    { "name": "Right", "type": "()" },
+                    { "name": "Jump", "type": "core::integer::u8" }
+                ]
+            },
+            {
+                "type": "interface",
+                "name": "ns::IActions",
+                "items": [
+                    {
+                        "type": "function",
+                        "name": "move",
+                        "inputs": [
+                            { "name": "direction", "type": "ns::Direction" },
+                            { "name": "position", "type": "ns::Vec2" },
+                            { "name": "amount", "type": "core::integer::u256" },
+                            { "name": "name", "type": "core::byte_array::ByteArray" },
+                            { "name": "items", "type": "core::array::Array::<core::felt252>" },
+                            { "name": "offset", "type": "core::integer::i32" }
+                        ],
+                        "outputs": [],
+                        "state_mutability": "external"
+                    }
+                ]
+            }
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decode_calldata_with_abi() {
+        let input = vec_of_strings![
+            r#"{"Jump":3}"#,
+            r#"{"x":1,"y":"0x2"}"#,
+            "340282366920938463463374607431768211456",
+            "hello world",
+            r#"[1,"0x2","hi"]"#,
+            "-5"
+        ];
+        let expected = vec![
+            // direction
+            Felt::TWO,
+            Felt::THREE,
+            // position
+            Felt::ONE,
+            Felt::TWO,
+            // amount
+            Felt::ZERO,
+            Felt::ONE,
+            // name
+            Felt::ZERO,
+            cairo_short_string_to_felt("hello world").unwrap(),
+            11_u128.into(),
+            // items
+            Felt::THREE,
+            Felt::ONE,
+            Felt::TWO,
+            cairo_short_string_to_felt("hi").unwrap(),
+            // offset
+            Felt::from(-5_i128),
+        ];
+
+        let result = decode_calldata_with_abi(&test_abi(), "move", &input).unwrap();
+        assert_eq!(result, expected);
+
+        let mut invalid = input.clone();
+        invalid[4] = "[1,2".to_string();
+        assert!(decode_calldata_with_abi(&test_abi(), "move", &invalid).is_err());
+
+        let mut invalid = input;
+        invalid[0] = "Up".to_string();
+        assert!(decode_calldata_with_abi(&test_abi(), "move", &invalid).is_err());
+    }
+
+    #[test]
+    fn test_decode_calldata_with_abi_fallback() {
+        // prefixed values are decoded without their type
+        let input = vec_of_strings!["Left", "[1,2]", "u256:1", "str:a", "arr:1,2", "int:-1"];
+        let result = decode_calldata_with_abi(&test_abi(), "move", &input).unwrap();
+        assert_eq!(result[..3], [Felt::ZERO, Felt::ONE, Felt::TWO]);
+        assert_eq!(result[3..5], [Felt::ONE, Felt::ZERO]);
+
+        // the raw felts are still supported when the values don't match the inputs
+        let input = vec_of_strings!["0x1", "2"];
+        let result = decode_calldata_with_abi(&test_abi(), "move", &input).unwrap();
+        assert_eq!(result, vec![Felt::ONE, Felt::TWO]);
+
+        let result = decode_calldata_with_abi(&test_abi(), "unknown", &input).unwrap();
+        assert_eq!(result, vec![Felt::ONE, Felt::TWO]);
+    }
 }
@@ -7,6 +7,13 @@ pub struct MigrationConfig {
     pub skip_contracts: Option<Vec<String>>,
     /// Disable multicall.
     pub disable_multicall: Option<bool>,
+    /// The maximum number of calls sent in a single multicall transaction. The calls of each step
+    /// of the migration are split into as many transactions as required.
+    /// By default, all the calls of a step are sent in a single transaction.
+    pub max_calls_per_multicall: Option<usize>,
+    /// The maximum number of declarations sent by an account before waiting for them to be
+    /// accepted. By default, an account waits for each declaration before sending the next one.
+    pub max_concurrent_declarations: Option<usize>,
     /// Determine the contract initialization order.
     /// Expecting tags.
     pub order_inits: Option<Vec<String>>,
@@ -13,6 +13,9 @@ use super::namespace_config::NamespaceConfig;
 use super::resource_config::ResourceConfig;
 use super::world_config::WorldConfig;
 
+/// The top-level key of a profile configuration naming the profile it inherits from.
+const INHERITS_KEY: &str = "inherits";
+
 /// External contract configuration for the Profile config.
 #[derive(Debug, Clone, Deserialize)]
 pub struct ExternalContractConfig {
@@ -66,6 +69,18 @@ impl ProfileConfig {
         Ok(config)
     }
 
+    /// Loads the configuration of `profile` from the `dojo_<profile>.toml` file in `dir`.
+    ///
+    /// A profile can inherit from another profile with the top-level `inherits = "<profile>"` key,
+    /// to only define the settings that differ, like the RPC url, the account or the world
+    /// address. Its configuration is merged over the one of the inherited profile: tables are
+    /// merged recursively, and any other value, arrays included, replaces the inherited one.
+    pub fn from_profile<P: AsRef<Path>>(dir: P, profile: &str) -> Result<Self> {
+        let table = load_profile_table(dir.as_ref(), profile, &mut vec![])?;
+        let config: ProfileConfig = toml::Value::Table(table).try_into()?;
+        Ok(config)
+    }
+
     /// Returns the local writers for a given tag.
     pub fn get_local_writers(&self, tag: &str) -> HashSet<String> {
         if let Some(writers) = &self.writers {
@@ -145,6 +160,56 @@ impl ProfileConfig {
     }
 }
 
+/// Loads the TOML table of `profile`, merged over the tables of the profiles it inherits from.
+///
+/// `chain` holds the profiles being loaded, to detect inheritance cycles.
+fn load_profile_table(dir: &Path, profile: &str, chain: &mut Vec<String>) -> Result<toml::Table> {
+    if chain.iter().any(|p| p == profile) {
+        bail!("Profile `{}` inherits from itself through `{}`.", profile, chain.join("` -> `"));
+    }
+
+    let path = dir.join(format!("dojo_{}.toml", profile));
+    if !path.exists() {
+        bail!(
+            "Profile configuration file not found for profile `{}`. Expected at {}.",
+            profile,
+            path.display()
+        );
+    }
+
+    let mut table: toml::Table = toml::from_str(&fs::read_to_string(&path)?)?;
+
+    let parent = match table.remove(INHERITS_KEY) {
+        Some(toml::Value::String(parent)) => parent,
+        Some(_) => {
+            bail!("The `{}` key of profile `{}` must be a profile name.", INHERITS_KEY, profile)
+        }
+        None => return Ok(table),
+    };
+
+    chain.push(profile.to_string());
+    let mut base = load_profile_table(dir, &parent, chain)?;
+    chain.pop();
+
+    merge_tables(&mut base, table);
+    Ok(base)
+}
+
+/// Merges `overlay` into `base`, recursively for the tables.
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overlay)) => {
+                merge_tables(base, overlay)
+            }
+            (Some(base), value) => *base = value,
+            (None, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -233,6 +298,8 @@ mod tests {
 
         [migration]
         skip_contracts = [ "module::my-contract" ]
+        max_calls_per_multicall = 50
+        max_concurrent_declarations = 4
 
         [writers]
         "ns1" = ["ns1-actions"]
@@ -251,6 +318,8 @@ mod tests {
 
         let migration = config.migration.unwrap();
         assert_eq!(migration.skip_contracts.unwrap(), vec!["module::my-contract".to_string()]);
+        assert_eq!(migration.max_calls_per_multicall, Some(50));
+        assert_eq!(migration.max_concurrent_declarations, Some(4));
 
         let env = config.env.unwrap();
         assert_eq!(env.rpc_url, Some("https://example.com/rpc".to_string()));
@@ -453,4 +522,85 @@ mod tests {
 
         assert!(config.validate().is_ok());
     }
+
+    #[test]
+    fn test_profile_config_inheritance() {
+        let dir = std::env::temp_dir().join(format!("dojo-profile-config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        fs::write(
+            dir.join("dojo_dev.toml"),
+            r#"
+            [world]
+            name = "test"
+            seed = "abcd"
+
+            [namespace]
+            default = "ns"
+            mappings = { "ns" = ["ns-c1"] }
+
+            [env]
+            rpc_url = "http://localhost:5050"
+            account_address = "0x1"
+
+            [writers]
+            "ns" = ["ns-c1"]
+            "#,
+        )
+        .unwrap();
+
+        fs::write(
+            dir.join("dojo_staging.toml"),
+            r#"
+            inherits = "dev"
+
+            [namespace]
+            mappings = { "ns" = ["ns-c2"] }
+
+            [env]
+            rpc_url = "https://staging.example.com/rpc"
+            world_address = "0x2"
+            "#,
+        )
+        .unwrap();
+
+        fs::write(
+            dir.join("dojo_mainnet.toml"),
+            r#"
+            inherits = "staging"
+
+            [env]
+            rpc_url = "https://mainnet.example.com/rpc"
+            "#,
+        )
+        .unwrap();
+
+        fs::write(dir.join("dojo_loop.toml"), "inherits = \"loop\"").unwrap();
+
+        let config = ProfileConfig::from_profile(&dir, "mainnet").unwrap();
+
+        assert_eq!(config.world.seed, "abcd");
+        assert_eq!(config.namespace.default, "ns");
+        assert_eq!(
+            config.namespace.mappings,
+            Some(HashMap::from([("ns".to_string(), vec!["ns-c2".to_string()])]))
+        );
+        assert_eq!(
+            config.writers,
+            Some(HashMap::from([("ns".to_string(), HashSet::from(["ns-c1".to_string()]))]))
+        );
+
+        let env = config.env.unwrap();
+        assert_eq!(env.rpc_url, Some("https://mainnet.example.com/rpc".to_string()));
+        assert_eq!(env.account_address, Some("0x1".to_string()));
+        assert_eq!(env.world_address, Some("0x2".to_string()));
+
+        let error = ProfileConfig::from_profile(&dir, "loop").unwrap_err();
+        assert!(error.to_string().contains("inherits from itself"));
+
+        let error = ProfileConfig::from_profile(&dir, "unknown").unwrap_err();
+        assert!(error.to_string().contains("not found for profile `unknown`"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
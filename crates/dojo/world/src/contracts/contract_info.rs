@@ -16,6 +16,7 @@
 //! present locally. Only onchain.
 use std::collections::HashMap;
 
+use starknet::core::types::contract::AbiEntry;
 use starknet::core::types::Felt;
 use tracing::trace;
 
@@ -32,6 +33,9 @@ pub struct ContractInfo {
     /// The entrypoints that can be targeted with a transaction.
     /// This only includes `external` functions.
     pub entrypoints: Vec<String>,
+    /// The ABI of the contract, used to serialize the calldata from typed values.
+    /// Empty if the ABI is not known.
+    pub abi: Vec<AbiEntry>,
 }
 
 impl From<&Manifest> for HashMap<String, ContractInfo> {
@@ -45,6 +49,7 @@ impl From<&Manifest> for HashMap<String, ContractInfo> {
                 tag_or_name: "world".to_string(),
                 address: manifest.world.address,
                 entrypoints: manifest.world.entrypoints.clone(),
+                abi: manifest.world.abi.clone(),
             },
         );
 
@@ -55,6 +60,7 @@ impl From<&Manifest> for HashMap<String, ContractInfo> {
                     tag_or_name: c.tag.clone(),
                     address: c.address,
                     entrypoints: c.systems.clone(),
+                    abi: c.abi.clone(),
                 },
             );
         }
@@ -66,6 +72,7 @@ impl From<&Manifest> for HashMap<String, ContractInfo> {
                     tag_or_name: c.instance_name.clone(),
                     address: c.address,
                     entrypoints: vec![],
+                    abi: c.abi.clone(),
                 },
             );
         }
@@ -85,6 +92,7 @@ impl From<&WorldDiff> for HashMap<String, ContractInfo> {
                 tag_or_name: "world".to_string(),
                 address: world_diff.world_info.address,
                 entrypoints: world_diff.world_info.entrypoints.clone(),
+                abi: world_diff.world_info.class.abi.clone(),
             },
         );
 
@@ -101,6 +109,7 @@ impl From<&WorldDiff> for HashMap<String, ContractInfo> {
                             tag_or_name: tag.clone(),
                             address,
                             entrypoints: c.systems.clone(),
+                            abi: c.common.class.abi.clone(),
                         },
                     );
                 }
@@ -111,6 +120,7 @@ impl From<&WorldDiff> for HashMap<String, ContractInfo> {
                             tag_or_name: tag.clone(),
                             address: r.common.address,
                             entrypoints: l.systems.clone(),
+                            abi: l.common.class.abi.clone(),
                         },
                     );
                 }
@@ -121,6 +131,7 @@ impl From<&WorldDiff> for HashMap<String, ContractInfo> {
                             tag_or_name: tag.clone(),
                             address: r.common.address,
                             entrypoints: l.systems.clone(),
+                            abi: l.common.class.abi.clone(),
                         },
                     );
                 }
@@ -130,6 +141,10 @@ impl From<&WorldDiff> for HashMap<String, ContractInfo> {
 
         for contract in world_diff.external_contracts.values() {
             let contract = contract.contract_data();
+            let abi = world_diff
+                .external_contract_classes
+                .get(&contract.contract_name)
+                .map_or(vec![], |c| c.class_data().class.abi);
 
             contracts.insert(
                 contract.instance_name.clone(),
@@ -137,6 +152,7 @@ impl From<&WorldDiff> for HashMap<String, ContractInfo> {
                     tag_or_name: contract.instance_name,
                     address: contract.address,
                     entrypoints: vec![], // Not available for Starknet contracts.
+                    abi,
                 },
             );
         }
@@ -279,7 +295,8 @@ mod tests {
             ContractInfo {
                 tag_or_name: "Instance1".to_string(),
                 address: Felt::from_hex("0x6789").unwrap(),
-                entrypoints: vec![]
+                entrypoints: vec![],
+                abi: vec![],
             }
         );
         assert_eq!(
@@ -287,7 +304,8 @@ mod tests {
             ContractInfo {
                 tag_or_name: "Instance2".to_string(),
                 address: Felt::from_hex("0x1234").unwrap(),
-                entrypoints: vec![]
+                entrypoints: vec![],
+                abi: vec![],
             }
         );
     }
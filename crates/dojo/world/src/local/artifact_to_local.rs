@@ -1,5 +1,6 @@
 //! Converts Scarb artifacts to local resources.
 
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -26,6 +27,10 @@ const LIBRARY_INTF: &str = "dojo::contract::interface::ILibrary";
 const MODEL_INTF: &str = "dojo::model::interface::IModel";
 const EVENT_INTF: &str = "dojo::event::interface::IEvent";
 
+/// The separator of the namespace set in the attribute of a resource, in the name of the
+/// implementation of its interface.
+const NAMESPACE_IMPL_SEPARATOR: &str = "__ns__";
+
 impl WorldLocal {
     pub fn from_directory<P: AsRef<Path>>(dir: P, profile_config: ProfileConfig) -> Result<Self> {
         trace!(
@@ -35,6 +40,8 @@ impl WorldLocal {
         );
         let mut resources = vec![];
         let mut external_contract_classes = HashMap::new();
+        // The namespaces set in the attributes of the resources, which must be registered.
+        let mut attribute_namespaces = HashSet::new();
 
         let mut world_class = None;
         let mut world_class_hash = None;
@@ -95,7 +102,12 @@ impl WorldLocal {
                                 break;
                             }
                             ResourceType::Contract(name) => {
-                                let namespaces = profile_config.namespace.get_namespaces(&name);
+                                let namespaces = resource_namespaces(
+                                    &profile_config,
+                                    i,
+                                    &name,
+                                    &mut attribute_namespaces,
+                                );
 
                                 let systems = systems_from_abi(&abi);
 
@@ -173,7 +185,12 @@ impl WorldLocal {
                                 break;
                             }
                             ResourceType::Model(name) => {
-                                let namespaces = profile_config.namespace.get_namespaces(&name);
+                                let namespaces = resource_namespaces(
+                                    &profile_config,
+                                    i,
+                                    &name,
+                                    &mut attribute_namespaces,
+                                );
 
                                 for ns in namespaces {
                                     trace!(
@@ -330,6 +347,11 @@ impl WorldLocal {
             }
         }
 
+        // Registering a namespace twice is harmless since the resources are indexed by selector.
+        for ns in attribute_namespaces {
+            resources.push(ResourceLocal::Namespace(NamespaceLocal { name: ns }));
+        }
+
         let mut world = match (world_class, world_class_hash, world_casm_class_hash) {
             (Some(class), Some(class_hash), Some(casm_class_hash)) => Self {
                 class,
@@ -410,6 +432,34 @@ fn name_from_impl(impl_name: &str) -> String {
     impl_name.split("__").collect::<Vec<&str>>()[0].to_string()
 }
 
+/// Extracts the namespace set in the attribute of a resource from its `IContract`/`IModel`
+/// implementation, if any.
+///
+/// Dojo lang inserts the namespace between the name of the resource and the name of the
+/// implementation, like `name__ns__namespace__ContractImpl`.
+fn namespace_from_impl(impl_name: &str) -> Option<String> {
+    let (_, rest) = impl_name.split_once(NAMESPACE_IMPL_SEPARATOR)?;
+    let (namespace, _) = rest.rsplit_once("__")?;
+    Some(namespace.to_string())
+}
+
+/// Returns the namespaces of a resource: the namespace set in its attribute if any, which takes
+/// precedence over the namespace mappings of the profile config.
+fn resource_namespaces(
+    profile_config: &ProfileConfig,
+    implem: &AbiImpl,
+    name: &str,
+    attribute_namespaces: &mut HashSet<String>,
+) -> HashSet<String> {
+    match namespace_from_impl(&implem.name) {
+        Some(namespace) => {
+            attribute_namespaces.insert(namespace.clone());
+            HashSet::from([namespace])
+        }
+        None => profile_config.namespace.get_namespaces(name),
+    }
+}
+
 fn systems_from_abi(abi: &[AbiEntry]) -> Vec<String> {
     fn extract_systems_from_abi_entry(entry: &AbiEntry) -> Vec<String> {
         match entry {
@@ -460,6 +510,20 @@ mod tests {
         assert_eq!(name_from_impl("Event__MyEvent"), "Event");
     }
 
+    #[test]
+    fn test_namespace_from_impl() {
+        assert_eq!(namespace_from_impl("contract__ContractImpl"), None);
+        assert_eq!(
+            namespace_from_impl("contract__ns__combat__ContractImpl"),
+            Some("combat".to_string())
+        );
+        assert_eq!(
+            namespace_from_impl("Model__ns__my__ns__DojoModelImpl"),
+            Some("my__ns".to_string())
+        );
+        assert_eq!(name_from_impl("contract__ns__combat__ContractImpl"), "contract");
+    }
+
     #[test]
     fn test_identify_resource_type() {
         assert_eq!(
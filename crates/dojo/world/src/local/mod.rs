@@ -1,9 +1,10 @@
 //! Local resources for the world, gathered from the Scarb generated artifacts.
 //!
-//! When resources are compiled, there's no namespace attached to them.
+//! When resources are compiled, there's usually no namespace attached to them.
 //! However, to be registered and used in the world, they need to be namespaced.
 //! To link a local resource to its world representation, a namespace configuration
-//! is needed.
+//! is needed, unless the resource sets its namespace in its attribute, like
+//! `#[dojo::contract(namespace: "combat")]`.
 //!
 //! Class hashes are cached into the resource to avoid recomputing them when
 //! requesting it.
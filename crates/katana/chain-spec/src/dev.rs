@@ -1,4 +1,6 @@
 use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 use alloy_primitives::U256;
 use katana_primitives::block::{Block, Header};
@@ -14,10 +16,11 @@ use katana_primitives::genesis::constant::{
     DEFAULT_STRK_FEE_TOKEN_ADDRESS, DEFAULT_UDC_ADDRESS, ERC20_DECIMAL_STORAGE_SLOT,
     ERC20_NAME_STORAGE_SLOT, ERC20_SYMBOL_STORAGE_SLOT, ERC20_TOTAL_SUPPLY_STORAGE_SLOT,
 };
+use katana_primitives::genesis::json::GenesisJson;
 use katana_primitives::genesis::Genesis;
 use katana_primitives::state::StateUpdatesWithClasses;
 use katana_primitives::utils::split_u256;
-use katana_primitives::version::CURRENT_STARKNET_VERSION;
+use katana_primitives::version::{ProtocolVersion, CURRENT_STARKNET_VERSION};
 use katana_primitives::Felt;
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
@@ -25,6 +28,9 @@ use starknet::core::utils::cairo_short_string_to_felt;
 
 use crate::SettlementLayer;
 
+/// The default number of decimals of the fee tokens.
+pub const DEFAULT_FEE_TOKEN_DECIMALS: u8 = 18;
+
 #[derive(Debug, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 pub struct ChainSpec {
@@ -37,6 +43,12 @@ pub struct ChainSpec {
     /// The chain fee token contract.
     pub fee_contracts: FeeContracts,
 
+    /// The number of decimals of the fee tokens.
+    pub fee_token_decimals: u8,
+
+    /// The Starknet protocol version of the chain's blocks.
+    pub protocol_version: ProtocolVersion,
+
     pub settlement: Option<SettlementLayer>,
 }
 
@@ -48,7 +60,7 @@ impl ChainSpec {
     pub fn block(&self) -> Block {
         let header = Header {
             state_diff_length: 0,
-            protocol_version: CURRENT_STARKNET_VERSION,
+            protocol_version: self.protocol_version.clone(),
             number: self.genesis.number,
             timestamp: self.genesis.timestamp,
             events_count: 0,
@@ -104,7 +116,7 @@ impl ChainSpec {
         }
 
         //-- Fee tokens
-        add_default_fee_tokens(&mut states, &self.genesis);
+        add_fee_tokens(&mut states, &self.genesis, &self.fee_contracts, self.fee_token_decimals);
         // -- UDC
         add_default_udc(&mut states);
 
@@ -130,6 +142,79 @@ impl Default for ChainSpec {
     }
 }
 
+//////////////////////////////////////////////////////////////
+// 	ChainSpec file
+//////////////////////////////////////////////////////////////
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failed to parse chain spec file: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
+
+    #[error(transparent)]
+    GenesisJson(#[from] katana_primitives::genesis::json::GenesisJsonError),
+}
+
+/// The content of a chain specification file.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct ChainSpecFile {
+    id: ChainId,
+    genesis: Option<PathBuf>,
+    fee_contracts: Option<FeeContracts>,
+    fee_token_decimals: Option<u8>,
+    protocol_version: Option<ProtocolVersion>,
+    settlement: Option<SettlementLayer>,
+}
+
+/// Reads a chain specification from a TOML file.
+///
+/// Only the chain id is required, the other fields default to the ones of [`DEV_UNALLOCATED`]. So
+/// the genesis doesn't include any allocations unless a genesis file is specified:
+///
+/// ```toml
+/// id = "MY_APPCHAIN"
+/// protocol-version = "0.13.4"
+/// fee-token-decimals = 18
+/// # path to a genesis JSON file, relative to the chain spec file
+/// genesis = "genesis.json"
+///
+/// [fee-contracts]
+/// eth = "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7"
+/// strk = "0x4718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d"
+/// ```
+pub fn read(path: impl AsRef<Path>) -> Result<ChainSpec, Error> {
+    let path = path.as_ref();
+    let file: ChainSpecFile = toml::from_str(&fs::read_to_string(path)?)?;
+
+    let mut chain_spec = DEV_UNALLOCATED.clone();
+    chain_spec.id = file.id;
+    chain_spec.settlement = file.settlement;
+
+    if let Some(genesis) = file.genesis {
+        // relative paths are resolved against the directory of the chain spec file
+        let dir = path.parent().unwrap_or(Path::new("."));
+        chain_spec.genesis = Genesis::try_from(GenesisJson::load(dir.join(genesis))?)?;
+    }
+
+    if let Some(contracts) = file.fee_contracts {
+        chain_spec.fee_contracts = contracts;
+    }
+
+    if let Some(decimals) = file.fee_token_decimals {
+        chain_spec.fee_token_decimals = decimals;
+    }
+
+    if let Some(version) = file.protocol_version {
+        chain_spec.protocol_version = version;
+    }
+
+    Ok(chain_spec)
+}
+
 lazy_static! {
     /// The default chain specification in dev mode.
     pub static ref DEV: ChainSpec = {
@@ -155,12 +240,19 @@ lazy_static! {
             id,
             genesis,
             fee_contracts,
+            fee_token_decimals: DEFAULT_FEE_TOKEN_DECIMALS,
+            protocol_version: CURRENT_STARKNET_VERSION,
             settlement: None,
         }
     };
 }
 
-fn add_default_fee_tokens(states: &mut StateUpdatesWithClasses, genesis: &Genesis) {
+fn add_fee_tokens(
+    states: &mut StateUpdatesWithClasses,
+    genesis: &Genesis,
+    contracts: &FeeContracts,
+    decimals: u8,
+) {
     // declare erc20 token contract
     states
         .classes
@@ -172,8 +264,8 @@ fn add_default_fee_tokens(states: &mut StateUpdatesWithClasses, genesis: &Genesi
         states,
         "Ether",
         "ETH",
-        18,
-        DEFAULT_ETH_FEE_TOKEN_ADDRESS,
+        decimals,
+        contracts.eth,
         DEFAULT_LEGACY_ERC20_CLASS_HASH,
         &genesis.allocations,
     );
@@ -183,8 +275,8 @@ fn add_default_fee_tokens(states: &mut StateUpdatesWithClasses, genesis: &Genesi
         states,
         "Starknet Token",
         "STRK",
-        18,
-        DEFAULT_STRK_FEE_TOKEN_ADDRESS,
+        decimals,
+        contracts.strk,
         DEFAULT_LEGACY_ERC20_CLASS_HASH,
         &genesis.allocations,
     );
@@ -276,7 +368,6 @@ mod tests {
         DEFAULT_LEGACY_ERC20_CLASS, DEFAULT_LEGACY_ERC20_COMPILED_CLASS_HASH,
         DEFAULT_LEGACY_UDC_CLASS, DEFAULT_LEGACY_UDC_COMPILED_CLASS_HASH,
     };
-    use katana_primitives::version::CURRENT_STARKNET_VERSION;
     use starknet::macros::felt;
 
     use super::*;
@@ -350,6 +441,8 @@ mod tests {
                 eth: DEFAULT_ETH_FEE_TOKEN_ADDRESS,
                 strk: DEFAULT_STRK_FEE_TOKEN_ADDRESS,
             },
+            fee_token_decimals: DEFAULT_FEE_TOKEN_DECIMALS,
+            protocol_version: CURRENT_STARKNET_VERSION,
             settlement: None,
         };
 
@@ -646,4 +739,52 @@ mod tests {
             "STRK total supply must be calculated from allocations balances correctly"
         );
     }
+
+    #[test]
+    fn read_chain_spec_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("chain.toml");
+
+        fs::write(
+            &path,
+            r#"
+            id = "MY_APPCHAIN"
+            protocol-version = "0.13.1"
+            fee-token-decimals = 6
+
+            [fee-contracts]
+            eth = "0x111"
+            strk = "0x222"
+            "#,
+        )
+        .unwrap();
+
+        let chain_spec = read(&path).unwrap();
+        assert_eq!(chain_spec.id, ChainId::parse("MY_APPCHAIN").unwrap());
+        assert_eq!(chain_spec.protocol_version, ProtocolVersion::new([0, 13, 1, 0]));
+        assert_eq!(chain_spec.fee_contracts.eth, address!("0x111"));
+        assert_eq!(chain_spec.fee_contracts.strk, address!("0x222"));
+        assert!(chain_spec.genesis.allocations.is_empty());
+        assert_eq!(chain_spec.block().header.protocol_version, chain_spec.protocol_version);
+
+        // the fee tokens are deployed at the configured addresses, with the configured decimals
+        let states = chain_spec.state_updates();
+        for address in [address!("0x111"), address!("0x222")] {
+            let storage = states.state_updates.storage_updates.get(&address).unwrap();
+            assert_eq!(storage.get(&ERC20_DECIMAL_STORAGE_SLOT), Some(&Felt::from(6)));
+        }
+        assert!(!states
+            .state_updates
+            .deployed_contracts
+            .contains_key(&DEFAULT_ETH_FEE_TOKEN_ADDRESS));
+
+        // only the chain id is required
+        fs::write(&path, r#"id = "MY_APPCHAIN""#).unwrap();
+        let chain_spec = read(&path).unwrap();
+        assert_eq!(chain_spec.fee_contracts, DEV_UNALLOCATED.fee_contracts);
+        assert_eq!(chain_spec.fee_token_decimals, DEFAULT_FEE_TOKEN_DECIMALS);
+
+        fs::write(&path, r#"protocol-version = "0.13.1""#).unwrap();
+        assert!(read(&path).is_err());
+    }
 }
@@ -1,6 +1,7 @@
 use katana_primitives::block::BlockNumber;
 use katana_primitives::chain::ChainId;
 use katana_primitives::genesis::Genesis;
+use katana_primitives::version::{ProtocolVersion, CURRENT_STARKNET_VERSION};
 use katana_primitives::{eth, ContractAddress};
 use serde::{Deserialize, Serialize};
 use url::Url;
@@ -38,6 +39,14 @@ impl ChainSpec {
         }
     }
 
+    /// Returns the Starknet protocol version of the chain's blocks.
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        match self {
+            Self::Dev(spec) => spec.protocol_version.clone(),
+            Self::Rollup(_) => CURRENT_STARKNET_VERSION,
+        }
+    }
+
     pub fn settlement(&self) -> Option<&SettlementLayer> {
         match self {
             Self::Dev(spec) => spec.settlement.as_ref(),
@@ -1,13 +1,14 @@
 //! Katana node CLI options and configuration.
 
 use std::net::SocketAddr;
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use alloy_primitives::U256;
 use anyhow::{Context, Result};
 use clap::Parser;
-use katana_chain_spec::rollup::ChainConfigDir;
 use katana_chain_spec::ChainSpec;
 use katana_core::constants::DEFAULT_SEQUENCER_ADDRESS;
 use katana_explorer::Explorer;
@@ -15,8 +16,10 @@ use katana_messaging::MessagingConfig;
 use katana_node::config::db::DbConfig;
 use katana_node::config::dev::{DevConfig, FixedL1GasPriceConfig};
 use katana_node::config::execution::ExecutionConfig;
+use katana_node::config::follower::FollowerConfig;
 use katana_node::config::fork::ForkingConfig;
 use katana_node::config::metrics::MetricsConfig;
+use katana_node::config::pruning::PruningConfig;
 use katana_node::config::rpc::{RpcConfig, RpcModuleKind, RpcModulesList};
 #[cfg(not(feature = "server"))]
 use katana_node::config::rpc::{DEFAULT_RPC_ADDR, DEFAULT_RPC_PORT};
@@ -36,7 +39,9 @@ use url::Url;
 
 use crate::file::NodeArgsConfig;
 use crate::options::*;
-use crate::utils::{self, parse_chain_config_dir, parse_seed, LogFormat};
+use crate::utils::{
+    self, parse_chain_source, parse_seed, ChainSource, ExecutionBackend, LogFormat,
+};
 
 pub(crate) const LOG_TARGET: &str = "katana::cli";
 
@@ -47,10 +52,14 @@ pub struct NodeArgs {
     #[arg(long)]
     pub silent: bool,
 
-    /// Path to the chain configuration file.
-    #[arg(long, hide = true)]
-    #[arg(value_parser = parse_chain_config_dir)]
-    pub chain: Option<ChainConfigDir>,
+    /// The chain specification to use.
+    ///
+    /// Either a path to a chain specification TOML file - which defines the chain id, fee tokens,
+    /// protocol version and genesis of the chain - or a chain configuration directory created with
+    /// `katana init`.
+    #[arg(long, value_name = "PATH")]
+    #[arg(value_parser = parse_chain_source)]
+    pub chain: Option<ChainSource>,
 
     /// Disable auto and interval mining, and mine on demand instead via an endpoint.
     #[arg(long)]
@@ -64,18 +73,66 @@ pub struct NodeArgs {
 
     #[arg(long = "sequencing.block-max-cairo-steps")]
     #[arg(value_name = "TOTAL")]
+    #[arg(conflicts_with = "unlimited_resources")]
     pub block_cairo_steps_limit: Option<u64>,
 
+    /// The maximum number of transactions in a block.
+    ///
+    /// The block is closed as soon as it has reached this number of transactions, and the
+    /// remaining transactions are included in the next block.
+    #[arg(long = "sequencing.block-max-txs")]
+    #[arg(value_name = "TOTAL")]
+    pub block_max_txs: Option<u64>,
+
+    /// The maximum number of threads used to execute the transactions of a block.
+    ///
+    /// With more than one thread, independent transactions are executed in parallel.
+    #[arg(long = "sequencing.concurrency")]
+    #[arg(value_name = "THREADS")]
+    pub concurrency: Option<NonZeroUsize>,
+
+    /// Record the SNOS inputs of the produced blocks, ie the state accessed by each block.
+    ///
+    /// They are served by the `saya` RPC module, so that the proving pipeline doesn't have to
+    /// re-execute the blocks. Running SNOS to produce the Cairo PIEs is left to the prover.
+    #[arg(long = "sequencing.provable")]
+    pub provable: bool,
+
+    /// The backend used to run the Sierra classes.
+    ///
+    /// The `native` backend compiles the classes to native code using Cairo Native, which
+    /// requires Katana to be built with the `native` feature.
+    #[arg(long = "execution")]
+    #[arg(value_name = "BACKEND")]
+    #[arg(default_value_t = ExecutionBackend::Vm)]
+    pub execution: ExecutionBackend,
+
     /// Directory path of the database to initialize from.
     ///
     /// The path must either be an empty directory or a directory which already contains a
-    /// previously initialized Katana database.
+    /// previously initialized Katana database. In the latter case, the chain is resumed from the
+    /// latest block in the database.
     #[arg(long)]
     #[arg(value_name = "PATH")]
     pub db_dir: Option<PathBuf>,
 
-    /// Configuration file
+    /// Restore the chain state from a file previously created with `--dump-state`.
+    ///
+    /// The chain must be using the same genesis as the one the state was dumped from.
+    #[arg(long)]
+    #[arg(value_name = "PATH")]
+    pub load_state: Option<PathBuf>,
+
+    /// Dump the chain state to a file when the node is shut down.
     #[arg(long)]
+    #[arg(value_name = "PATH")]
+    pub dump_state: Option<PathBuf>,
+
+    /// Path to a configuration file, eg one created with `katana config init`.
+    ///
+    /// The options provided on the command line take precedence over the ones in the file.
+    #[arg(long)]
+    #[arg(value_name = "PATH")]
     config: Option<PathBuf>,
 
     /// Configure the messaging with an other chain.
@@ -90,7 +147,7 @@ pub struct NodeArgs {
 
     #[arg(long = "l1.provider", value_name = "URL", alias = "l1-provider")]
     #[arg(help = "The Ethereum RPC provider to sample the gas prices from to enable the gas \
-                  price oracle.")]
+                  price oracle. Ignored if any of the fixed `--gpo.*` prices are set.")]
     pub l1_provider_url: Option<Url>,
 
     #[command(flatten)]
@@ -119,6 +176,12 @@ pub struct NodeArgs {
     #[command(flatten)]
     pub development: DevOptions,
 
+    #[command(flatten)]
+    pub follower: FollowerOptions,
+
+    #[command(flatten)]
+    pub pruning: PruningOptions,
+
     #[cfg(feature = "slot")]
     #[command(flatten)]
     pub slot: SlotOptions,
@@ -209,8 +272,10 @@ impl NodeArgs {
         let (chain, cs_messaging) = self.chain_spec()?;
         let metrics = self.metrics_config();
         let forking = self.forking_config()?;
+        let follower = self.follower_config();
         let execution = self.execution_config();
         let sequencing = self.sequencer_config();
+        let pruning = self.pruning_config();
 
         // the `katana init` will automatically generate a messaging config. so if katana is run
         // with `--chain` then the `--messaging` flag is not required. this is temporary and
@@ -228,15 +293,29 @@ impl NodeArgs {
                 chain,
                 metrics,
                 forking,
+                follower,
                 paymaster: cartridge,
                 execution,
                 messaging,
                 sequencing,
+                pruning,
             })
         }
 
         #[cfg(not(feature = "cartridge"))]
-        Ok(Config { metrics, db, dev, rpc, chain, execution, sequencing, messaging, forking })
+        Ok(Config {
+            metrics,
+            db,
+            dev,
+            rpc,
+            chain,
+            execution,
+            sequencing,
+            messaging,
+            forking,
+            follower,
+            pruning,
+        })
     }
 
     fn sequencer_config(&self) -> SequencingConfig {
@@ -244,6 +323,9 @@ impl NodeArgs {
             block_time: self.block_time,
             no_mining: self.no_mining,
             block_cairo_steps_limit: self.block_cairo_steps_limit,
+            block_max_txs: self.block_max_txs,
+            concurrency: self.concurrency,
+            provable: self.provable,
         }
     }
 
@@ -309,14 +391,34 @@ impl NodeArgs {
         #[cfg(not(feature = "server"))]
         let (cors_origins, http_port, http_addr) = (vec![], DEFAULT_RPC_PORT, DEFAULT_RPC_ADDR);
 
+        #[cfg(feature = "server")]
+        let (path_prefix, ws_addr, ws_port, ipc_path) = (
+            self.server.http_path_prefix.clone(),
+            self.server.ws_addr,
+            self.server.ws_port,
+            self.server.ipc_path.clone(),
+        );
+
+        #[cfg(not(feature = "server"))]
+        let (path_prefix, ws_addr, ws_port, ipc_path) = (None, None, None, None);
+
         Ok(RpcConfig {
             apis: modules,
             port: http_port,
             addr: http_addr,
             max_connections: self.rpc.max_connections,
             cors_origins,
-            max_request_body_size: None,
-            max_response_body_size: None,
+            path_prefix,
+            ws_addr,
+            ws_port,
+            ipc_path,
+            max_request_body_size: self.rpc.max_request_body_size,
+            max_response_body_size: self.rpc.max_response_body_size,
+            max_concurrent_requests: self.rpc.max_concurrent_requests,
+            rate_limit: self.rpc.rate_limit,
+            max_batch_size: self.rpc.max_batch_size,
+            batch_concurrency: self.rpc.batch_concurrency,
+            api_key: self.rpc.api_key.clone(),
             max_event_page_size: Some(self.rpc.max_event_page_size),
             max_proof_keys: Some(self.rpc.max_proof_keys),
             max_call_gas: Some(self.rpc.max_call_gas),
@@ -324,34 +426,43 @@ impl NodeArgs {
     }
 
     fn chain_spec(&self) -> Result<(Arc<ChainSpec>, Option<MessagingConfig>)> {
-        if let Some(path) = &self.chain {
-            let mut cs = katana_chain_spec::rollup::read(path)?;
+        if let Some(ChainSource::ConfigDir(dir)) = &self.chain {
+            let mut cs = katana_chain_spec::rollup::read(dir)?;
             cs.genesis.sequencer_address = *DEFAULT_SEQUENCER_ADDRESS;
             let messaging_config = MessagingConfig::from_chain_spec(&cs);
             Ok((Arc::new(ChainSpec::Rollup(cs)), Some(messaging_config)))
         }
-        // exclusively for development mode
+        // development mode, or a custom chain spec
         else {
-            let mut chain_spec = katana_chain_spec::dev::DEV_UNALLOCATED.clone();
+            let mut chain_spec = if let Some(ChainSource::File(path)) = &self.chain {
+                katana_chain_spec::dev::read(path)
+                    .with_context(|| format!("failed to read chain spec at {}", path.display()))?
+            } else {
+                katana_chain_spec::dev::DEV_UNALLOCATED.clone()
+            };
 
             if let Some(id) = self.starknet.environment.chain_id {
                 chain_spec.id = id;
             }
 
+            // The genesis file - either given directly or through the chain spec file - fully
+            // describes the genesis state, so the dev accounts are only generated when no genesis
+            // file is provided.
             if let Some(genesis) = &self.starknet.genesis {
                 chain_spec.genesis = genesis.clone();
-            } else {
+            } else if chain_spec.genesis.allocations.is_empty() {
                 chain_spec.genesis.sequencer_address = *DEFAULT_SEQUENCER_ADDRESS;
-            }
 
-            // Generate dev accounts.
-            // If `cartridge` is enabled, the first account will be the paymaster.
-            let accounts = DevAllocationsGenerator::new(self.development.total_accounts)
-                .with_seed(parse_seed(&self.development.seed))
-                .with_balance(U256::from(DEFAULT_PREFUNDED_ACCOUNT_BALANCE))
-                .generate();
+                // Generate dev accounts.
+                // If `cartridge` is enabled, the first account will be the paymaster.
+                let accounts = DevAllocationsGenerator::new(self.development.total_accounts)
+                    .with_seed(parse_seed(&self.development.seed))
+                    .with_balance(U256::from(DEFAULT_PREFUNDED_ACCOUNT_BALANCE))
+                    .generate();
 
-            chain_spec.genesis.extend_allocations(accounts.into_iter().map(|(k, v)| (k, v.into())));
+                let accounts = accounts.into_iter().map(|(k, v)| (k, v.into()));
+                chain_spec.genesis.extend_allocations(accounts);
+            }
 
             #[cfg(feature = "slot")]
             if self.slot.controller {
@@ -387,17 +498,27 @@ impl NodeArgs {
 
         DevConfig {
             fixed_gas_prices,
+            l1_provider_url: self.l1_provider_url.clone(),
             fee: !self.development.no_fee,
             account_validation: !self.development.no_account_validation,
+            deterministic: self.development.deterministic,
         }
     }
 
     fn execution_config(&self) -> ExecutionConfig {
-        ExecutionConfig {
-            invocation_max_steps: self.starknet.environment.invoke_max_steps,
-            validation_max_steps: self.starknet.environment.validate_max_steps,
-            ..Default::default()
+        let env = &self.starknet.environment;
+        let mut config = ExecutionConfig {
+            invocation_max_steps: env.invoke_max_steps,
+            validation_max_steps: env.validate_max_steps,
+            max_recursion_depth: env.max_recursion_depth,
+            native: self.execution == ExecutionBackend::Native,
+        };
+
+        if env.unlimited_resources {
+            config.remove_step_limits();
         }
+
+        config
     }
 
     fn forking_config(&self) -> Result<Option<ForkingConfig>> {
@@ -409,8 +530,31 @@ impl NodeArgs {
         Ok(None)
     }
 
+    fn follower_config(&self) -> Option<FollowerConfig> {
+        let url = self.follower.primary.clone()?;
+        let mut cfg = FollowerConfig::new(url);
+
+        if let Some(interval) = self.follower.poll_interval {
+            cfg.poll_interval = Duration::from_millis(interval);
+        }
+
+        Some(cfg)
+    }
+
     fn db_config(&self) -> DbConfig {
-        DbConfig { dir: self.db_dir.clone() }
+        DbConfig {
+            dir: self.db_dir.clone(),
+            load_state: self.load_state.clone(),
+            dump_state: self.dump_state.clone(),
+        }
+    }
+
+    fn pruning_config(&self) -> PruningConfig {
+        PruningConfig {
+            receipts: self.pruning.receipts,
+            traces: self.pruning.traces,
+            state_diffs: self.pruning.state_diffs,
+        }
     }
 
     fn metrics_config(&self) -> Option<MetricsConfig> {
@@ -455,10 +599,38 @@ impl NodeArgs {
             self.block_time = config.block_time;
         }
 
+        if self.block_cairo_steps_limit.is_none() {
+            self.block_cairo_steps_limit = config.block_cairo_steps_limit;
+        }
+
+        if self.block_max_txs.is_none() {
+            self.block_max_txs = config.block_max_txs;
+        }
+
+        if self.concurrency.is_none() {
+            self.concurrency = config.concurrency;
+        }
+
+        if !self.provable {
+            self.provable = config.provable.unwrap_or_default();
+        }
+
+        if self.execution == ExecutionBackend::default() {
+            self.execution = config.execution.unwrap_or_default();
+        }
+
         if self.db_dir.is_none() {
             self.db_dir = config.db_dir;
         }
 
+        if self.load_state.is_none() {
+            self.load_state = config.load_state;
+        }
+
+        if self.dump_state.is_none() {
+            self.dump_state = config.dump_state;
+        }
+
         if self.logging == LoggingOptions::default() {
             if let Some(logging) = config.logging {
                 self.logging = logging;
@@ -496,6 +668,18 @@ impl NodeArgs {
             }
         }
 
+        if self.follower == FollowerOptions::default() {
+            if let Some(follower) = config.follower {
+                self.follower = follower;
+            }
+        }
+
+        if self.pruning == PruningOptions::default() {
+            if let Some(pruning) = config.pruning {
+                self.pruning = pruning;
+            }
+        }
+
         #[cfg(feature = "cartridge")]
         {
             self.cartridge.merge(config.cartridge.as_ref());
@@ -507,6 +691,7 @@ impl NodeArgs {
 
 #[cfg(test)]
 mod test {
+    use std::net::{IpAddr, Ipv4Addr};
     use std::str::FromStr;
 
     use assert_matches::assert_matches;
@@ -515,8 +700,10 @@ mod test {
         DEFAULT_STRK_L1_GAS_PRICE,
     };
     use katana_node::config::execution::{
-        DEFAULT_INVOCATION_MAX_STEPS, DEFAULT_VALIDATION_MAX_STEPS,
+        DEFAULT_INVOCATION_MAX_STEPS, DEFAULT_VALIDATION_MAX_STEPS, MAX_RECURSION_DEPTH,
     };
+    use katana_node::config::follower::DEFAULT_FOLLOWER_POLL_INTERVAL;
+    use katana_primitives::block::BlockHashOrNumber;
     use katana_primitives::chain::ChainId;
     use katana_primitives::{address, felt, ContractAddress, Felt};
     use katana_rpc::cors::HeaderValue;
@@ -533,6 +720,7 @@ mod test {
         assert!(config.forking.is_none());
         assert_eq!(config.execution.invocation_max_steps, DEFAULT_INVOCATION_MAX_STEPS);
         assert_eq!(config.execution.validation_max_steps, DEFAULT_VALIDATION_MAX_STEPS);
+        assert!(!config.execution.native);
         assert_eq!(config.db.dir, None);
         assert_eq!(config.chain.id(), ChainId::parse("KATANA").unwrap());
         assert_eq!(config.chain.genesis().sequencer_address, *DEFAULT_SEQUENCER_ADDRESS);
@@ -553,6 +741,13 @@ mod test {
             "100",
             "--db-dir",
             "/path/to/db",
+            "--sequencing.block-max-cairo-steps",
+            "1000",
+            "--sequencing.block-max-txs",
+            "10",
+            "--sequencing.provable",
+            "--execution",
+            "native",
         ]);
         let config = args.config().unwrap();
 
@@ -561,10 +756,151 @@ mod test {
         assert_eq!(config.execution.invocation_max_steps, 200);
         assert_eq!(config.execution.validation_max_steps, 100);
         assert_eq!(config.db.dir, Some(PathBuf::from("/path/to/db")));
+        assert_eq!(config.sequencing.block_limits().cairo_steps, 1000);
+        assert_eq!(config.sequencing.block_limits().transactions, 10);
+        assert!(config.sequencing.provable);
+        assert!(config.execution.native);
         assert_eq!(config.chain.id(), ChainId::GOERLI);
         assert_eq!(config.chain.genesis().sequencer_address, *DEFAULT_SEQUENCER_ADDRESS);
     }
 
+    #[test]
+    fn l1_provider_gas_prices() {
+        let config = NodeArgs::parse_from(["katana"]).config().unwrap();
+        assert!(config.dev.l1_provider_url.is_none());
+
+        let url = "http://localhost:8545";
+        let config = NodeArgs::parse_from(["katana", "--l1.provider", url]).config().unwrap();
+        assert_eq!(config.dev.l1_provider_url, Some(Url::parse(url).unwrap()));
+        assert!(config.dev.fixed_gas_prices.is_none());
+    }
+
+    #[test]
+    fn deterministic_mode() {
+        let config = NodeArgs::parse_from(["katana", "--dev"]).config().unwrap();
+        assert!(!config.dev.deterministic);
+
+        let args = ["katana", "--dev", "--deterministic", "--seed", "42"];
+        let config = NodeArgs::parse_from(args).config().unwrap();
+        assert!(config.dev.deterministic);
+
+        // same seed, same accounts
+        let other = NodeArgs::parse_from(args).config().unwrap();
+        let accounts = config.chain.genesis().accounts().map(|(a, _)| *a).collect::<Vec<_>>();
+        let others = other.chain.genesis().accounts().map(|(a, _)| *a).collect::<Vec<_>>();
+        assert_eq!(accounts, others);
+
+        // the block timestamps can't depend on the L1
+        let args = ["katana", "--dev", "--dev.deterministic", "--l1.provider", "http://localhost"];
+        assert!(NodeArgs::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn pruning_options() {
+        let config = NodeArgs::parse_from(["katana"]).config().unwrap();
+        assert!(!config.pruning.is_enabled());
+
+        let args = [
+            "katana",
+            "--prune.receipts",
+            "100",
+            "--prune.traces",
+            "10",
+            "--prune.state-diffs",
+            "50",
+        ];
+        let config = NodeArgs::parse_from(args).config().unwrap();
+        let expected =
+            PruningConfig { receipts: Some(100), traces: Some(10), state_diffs: Some(50) };
+        assert_eq!(config.pruning, expected);
+
+        // keeping zero blocks is not allowed
+        assert!(NodeArgs::try_parse_from(["katana", "--prune.receipts", "0"]).is_err());
+
+        // the forked state isn't stored locally, so it can't be pruned
+        let args = ["katana", "--fork.provider", "http://localhost:5050", "--prune.traces", "10"];
+        assert!(NodeArgs::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn follower_options() {
+        let config = NodeArgs::parse_from(["katana"]).config().unwrap();
+        assert!(config.follower.is_none());
+
+        let args = ["katana", "--follower.primary", "http://localhost:5050"];
+        let config = NodeArgs::parse_from(args).config().unwrap();
+        assert_matches!(config.follower, Some(cfg) => {
+            assert_eq!(cfg.primary_url, Url::parse("http://localhost:5050").unwrap());
+            assert_eq!(cfg.poll_interval, DEFAULT_FOLLOWER_POLL_INTERVAL);
+        });
+
+        let args = [
+            "katana",
+            "--follower.primary",
+            "http://localhost:5050",
+            "--follower.poll-interval",
+            "200",
+        ];
+        let config = NodeArgs::parse_from(args).config().unwrap();
+        assert_matches!(config.follower, Some(cfg) => {
+            assert_eq!(cfg.poll_interval, Duration::from_millis(200));
+        });
+
+        // can't follow a node and fork a network at the same time
+        let args = [
+            "katana",
+            "--follower.primary",
+            "http://localhost:5050",
+            "--fork.provider",
+            "http://localhost:6060",
+        ];
+        assert!(NodeArgs::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn rpc_limits() {
+        let config = NodeArgs::parse_from(["katana"]).config().unwrap();
+        assert_eq!(config.rpc.max_request_body_size, None);
+        assert_eq!(config.rpc.max_concurrent_requests, None);
+        assert_eq!(config.rpc.rate_limit, None);
+        assert_eq!(config.rpc.max_batch_size, None);
+        assert_eq!(config.rpc.batch_concurrency, None);
+
+        let args = [
+            "katana",
+            "--rpc.max-request-body-size",
+            "1000",
+            "--rpc.max-response-body-size",
+            "2000",
+            "--rpc.max-concurrent-requests",
+            "50",
+            "--rpc.rate-limit",
+            "10",
+            "--rpc.max-batch-size",
+            "20",
+            "--rpc.batch-concurrency",
+            "4",
+        ];
+        let config = NodeArgs::parse_from(args).config().unwrap();
+        assert_eq!(config.rpc.max_request_body_size, Some(1000));
+        assert_eq!(config.rpc.max_response_body_size, Some(2000));
+        assert_eq!(config.rpc.max_concurrent_requests, Some(50));
+        assert_eq!(config.rpc.rate_limit, Some(10));
+        assert_eq!(config.rpc.max_batch_size, Some(20));
+        assert_eq!(config.rpc.batch_concurrency, Some(4));
+
+        assert!(NodeArgs::try_parse_from(["katana", "--rpc.rate-limit", "0"]).is_err());
+    }
+
+    #[test]
+    fn rpc_api_key() {
+        let config = NodeArgs::parse_from(["katana"]).config().unwrap();
+        assert_eq!(config.rpc.api_key, None);
+
+        let config = NodeArgs::parse_from(["katana", "--rpc.api-key", "secret"]).config().unwrap();
+        assert_eq!(config.rpc.api_key.as_deref(), Some("secret"));
+    }
+
     #[test]
     fn custom_fixed_gas_prices() {
         let config = NodeArgs::parse_from(["katana"]).config().unwrap();
@@ -680,6 +1016,59 @@ mod test {
         })
     }
 
+    #[test]
+    fn chain_spec_file() {
+        let config =
+            NodeArgs::parse_from(["katana", "--chain", "./test-data/chain.toml"]).config().unwrap();
+
+        assert_matches!(config.chain.as_ref(), ChainSpec::Dev(cs) => {
+            assert_eq!(cs.id, ChainId::parse("MY_APPCHAIN").unwrap());
+            assert_eq!(cs.fee_contracts.eth, address!("0x111"));
+            assert_eq!(cs.fee_contracts.strk, address!("0x222"));
+            assert_eq!(cs.protocol_version.to_string(), "0.13.1");
+            // the genesis is read from the file referenced by the chain spec
+            assert_eq!(cs.genesis.parent_hash, felt!("0x999"));
+            assert_eq!(cs.genesis.sequencer_address, address!("0x100"));
+        });
+
+        // the chain id is defined by the chain spec
+        let args = ["katana", "--chain", "./test-data/chain.toml", "--chain-id", "SN_SEPOLIA"];
+        assert!(NodeArgs::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn genesis_replaces_dev_accounts() {
+        let config = NodeArgs::parse_from(["katana"]).config().unwrap();
+        let total_accounts = DevOptions::default().total_accounts as usize;
+        assert_eq!(config.chain.genesis().accounts().count(), total_accounts);
+
+        let config = NodeArgs::parse_from(["katana", "--genesis", "./test-data/genesis.json"])
+            .config()
+            .unwrap();
+
+        // only the accounts declared in the genesis file should be allocated
+        assert_eq!(config.chain.genesis().accounts().count(), 1);
+    }
+
+    #[test]
+    fn unlimited_resources() {
+        let config = NodeArgs::parse_from(["katana"]).config().unwrap();
+        assert_eq!(config.execution.max_recursion_depth, MAX_RECURSION_DEPTH);
+
+        let args = ["katana", "--unlimited-resources", "--max-recursion-depth", "5000"];
+        let config = NodeArgs::parse_from(args).config().unwrap();
+        assert_eq!(config.execution.invocation_max_steps, u32::MAX);
+        assert_eq!(config.execution.validation_max_steps, u32::MAX);
+        assert_eq!(config.execution.max_recursion_depth, 5000);
+        assert_eq!(config.sequencing.block_cairo_steps_limit, None);
+
+        // the limits can't be both removed and set
+        let args = ["katana", "--unlimited-resources", "--invoke-max-steps", "100"];
+        assert!(NodeArgs::try_parse_from(args).is_err());
+        let args = ["katana", "--unlimited-resources", "--sequencing.block-max-cairo-steps", "100"];
+        assert!(NodeArgs::try_parse_from(args).is_err());
+    }
+
     #[test]
     fn config_from_file_and_cli() {
         // CLI args must take precedence over the config file.
@@ -739,6 +1128,63 @@ chain_id.Named = "Mainnet"
         assert_eq!(config.chain.id(), ChainId::Id(Felt::from_str("0x123").unwrap()));
     }
 
+    #[test]
+    fn state_dump_options() {
+        let config = NodeArgs::parse_from(["katana"]).config().unwrap();
+        assert_eq!(config.db.load_state, None);
+        assert_eq!(config.db.dump_state, None);
+
+        let config = NodeArgs::parse_from([
+            "katana",
+            "--load-state",
+            "/path/to/input.json",
+            "--dump-state",
+            "/path/to/output.json",
+        ])
+        .config()
+        .unwrap();
+
+        assert_eq!(config.db.load_state, Some(PathBuf::from("/path/to/input.json")));
+        assert_eq!(config.db.dump_state, Some(PathBuf::from("/path/to/output.json")));
+    }
+
+    #[test]
+    fn forking_options() {
+        let config = NodeArgs::parse_from([
+            "katana",
+            "--fork.provider",
+            "http://localhost:5050/",
+            "--fork.block",
+            "1337",
+        ])
+        .config()
+        .unwrap();
+
+        assert_matches!(config.forking, Some(cfg) => {
+            assert_eq!(cfg.url, Url::parse("http://localhost:5050/").unwrap());
+            assert_eq!(cfg.block, Some(BlockHashOrNumber::Num(1337)));
+        });
+
+        // The dashed aliases must resolve to the same options.
+        let config = NodeArgs::parse_from([
+            "katana",
+            "--fork-rpc-url",
+            "http://localhost:5050/",
+            "--fork-block",
+            "0x1337",
+        ])
+        .config()
+        .unwrap();
+
+        assert_matches!(config.forking, Some(cfg) => {
+            assert_eq!(cfg.url, Url::parse("http://localhost:5050/").unwrap());
+            assert_eq!(cfg.block, Some(BlockHashOrNumber::Hash(felt!("0x1337"))));
+        });
+
+        // The fork block can't be specified without the provider.
+        assert!(NodeArgs::try_parse_from(["katana", "--fork-block", "1"]).is_err());
+    }
+
     #[test]
     #[cfg(feature = "server")]
     fn parse_cors_origins() {
@@ -758,6 +1204,34 @@ chain_id.Named = "Mainnet"
         assert!(cors_origins.contains(&HeaderValue::from_static("https://example.com")));
     }
 
+    #[test]
+    #[cfg(feature = "server")]
+    fn server_options() {
+        use katana_node::config::rpc::DEFAULT_RPC_ADDR;
+
+        let config = NodeArgs::parse_from(["katana"]).config().unwrap();
+        assert_eq!(config.rpc.path_prefix, None);
+        assert_eq!(config.rpc.ws_socket_addr(), None);
+        assert_eq!(config.rpc.ipc_path, None);
+
+        let args = ["katana", "--http.path-prefix", "/katana", "--ws.port", "5051"];
+        let config = NodeArgs::parse_from(args).config().unwrap();
+        assert_eq!(config.rpc.path_prefix.as_deref(), Some("/katana"));
+        assert_eq!(config.rpc.ws_socket_addr(), Some(SocketAddr::new(DEFAULT_RPC_ADDR, 5051)));
+
+        let args = ["katana", "--ws.addr", "0.0.0.0", "--ws.port", "5051"];
+        let config = NodeArgs::parse_from(args).config().unwrap();
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 5051);
+        assert_eq!(config.rpc.ws_socket_addr(), Some(addr));
+
+        // the WebSocket interface can't be specified without the port
+        assert!(NodeArgs::try_parse_from(["katana", "--ws.addr", "0.0.0.0"]).is_err());
+
+        let args = ["katana", "--ipc.path", "/tmp/katana.ipc"];
+        let config = NodeArgs::parse_from(args).config().unwrap();
+        assert_eq!(config.rpc.ipc_path, Some(PathBuf::from("/tmp/katana.ipc")));
+    }
+
     #[test]
     fn http_modules() {
         // If the `--rpc.api` isn't specified, only starknet module will be exposed.
@@ -815,4 +1289,37 @@ chain_id.Named = "Mainnet"
         // Verify cartridge module is not enabled by default
         assert!(!config.rpc.apis.contains(&RpcModuleKind::Cartridge));
     }
+
+    #[test]
+    fn config_file_template() {
+        let template = NodeArgsConfig::template().unwrap();
+
+        // everything is commented out
+        let config: NodeArgsConfig = toml::from_str(&template).unwrap();
+        assert!(config.development.is_none());
+
+        // uncomment the sections and the options that have a default value
+        let uncommented = template
+            .lines()
+            .map(|line| match line.strip_prefix("# ") {
+                Some(rest) if rest.starts_with('[') && rest.ends_with(']') => rest,
+                Some(rest) if !rest.ends_with('>') => match rest.split_once(" = ") {
+                    Some((key, _)) if key.chars().all(|c| c.is_ascii_lowercase() || c == '_') => {
+                        rest
+                    }
+                    _ => line,
+                },
+                _ => line,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let config: NodeArgsConfig = toml::from_str(&uncommented).unwrap();
+        assert_eq!(config.no_mining, Some(false));
+        assert_eq!(config.provable, Some(false));
+        assert_eq!(config.development, Some(DevOptions::default()));
+        assert_eq!(config.rpc, Some(RpcOptions::default()));
+        assert_eq!(config.starknet, Some(StarknetOptions::default()));
+        assert_eq!(config.gpo, Some(GasPriceOracleOptions::default()));
+    }
 }
@@ -1,10 +1,14 @@
+use std::fmt::Write;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 
 use anyhow::Result;
+use clap::{Arg, Args, Command, CommandFactory};
 use katana_messaging::MessagingConfig;
 use serde::{Deserialize, Serialize};
 
 use crate::options::*;
+use crate::utils::ExecutionBackend;
 use crate::NodeArgs;
 
 /// Node arguments configuration file.
@@ -13,12 +17,20 @@ pub struct NodeArgsConfig {
     pub no_mining: Option<bool>,
     pub block_time: Option<u64>,
     pub block_cairo_steps_limit: Option<u64>,
+    pub block_max_txs: Option<u64>,
+    pub concurrency: Option<NonZeroUsize>,
+    pub provable: Option<bool>,
+    pub execution: Option<ExecutionBackend>,
     pub db_dir: Option<PathBuf>,
+    pub load_state: Option<PathBuf>,
+    pub dump_state: Option<PathBuf>,
     pub messaging: Option<MessagingConfig>,
     pub logging: Option<LoggingOptions>,
     pub starknet: Option<StarknetOptions>,
     pub gpo: Option<GasPriceOracleOptions>,
     pub forking: Option<ForkingOptions>,
+    pub follower: Option<FollowerOptions>,
+    pub pruning: Option<PruningOptions>,
     #[serde(rename = "dev")]
     pub development: Option<DevOptions>,
     pub rpc: Option<RpcOptions>,
@@ -30,11 +42,166 @@ pub struct NodeArgsConfig {
     pub cartridge: Option<CartridgeOptions>,
 }
 
+/// The top level options of [`NodeArgsConfig`], ie those that aren't part of a section.
+const TOP_LEVEL_OPTIONS: [&str; 10] = [
+    "no_mining",
+    "block_time",
+    "block_cairo_steps_limit",
+    "block_max_txs",
+    "concurrency",
+    "provable",
+    "execution",
+    "db_dir",
+    "load_state",
+    "dump_state",
+];
+
+const TEMPLATE_HEADER: &str = "\
+# Katana node configuration file.
+#
+# Every option is commented out and set to its default value, or to a placeholder if it doesn't
+# have one. Load the file with `katana --config <PATH>`. Options provided on the command line take
+# precedence over the ones in this file.
+";
+
 impl NodeArgsConfig {
     pub fn read(path: impl AsRef<Path>) -> Result<Self> {
         let file = std::fs::read_to_string(path)?;
         Ok(toml::from_str(&file)?)
     }
+
+    /// Returns the content of a configuration file in which every option of the node is commented
+    /// out, along with its description and default value.
+    pub fn template() -> Result<String> {
+        let node = NodeArgs::command();
+        let mut file = String::from(TEMPLATE_HEADER);
+
+        let top_level = TOP_LEVEL_OPTIONS
+            .iter()
+            .filter_map(|id| node.get_arguments().find(|arg| arg.get_id() == *id))
+            .map(TemplateOption::from)
+            .collect::<Vec<_>>();
+        let defaults = NodeArgsConfig {
+            no_mining: Some(false),
+            provable: Some(false),
+            execution: Some(ExecutionBackend::default()),
+            ..Default::default()
+        };
+        write_section(&mut file, None, &top_level, &defaults)?;
+
+        // The messaging is configured with a JSON file on the command line, but it's a section of
+        // its own in the configuration file.
+        let messaging = MessagingConfig::default();
+        let keys = toml::Value::try_from(&messaging)?;
+        let keys = keys.as_table().into_iter().flat_map(|table| table.keys());
+        let messaging_options =
+            keys.map(|key| TemplateOption::new(key, "Messaging options")).collect::<Vec<_>>();
+        write_section(&mut file, Some("messaging"), &messaging_options, &messaging)?;
+
+        // The environment options are flattened in the starknet options on the command line but
+        // have their own section in the file.
+        let env = options::<EnvironmentOptions>();
+        let starknet = options::<StarknetOptions>()
+            .into_iter()
+            .filter(|opt| env.iter().all(|env| env.key != opt.key))
+            .collect::<Vec<_>>();
+        write_section(&mut file, Some("starknet"), &starknet, &StarknetOptions::default())?;
+        write_section(&mut file, Some("starknet.env"), &env, &EnvironmentOptions::default())?;
+
+        write_options::<LoggingOptions>(&mut file, "logging")?;
+        write_options::<GasPriceOracleOptions>(&mut file, "gpo")?;
+        write_options::<ForkingOptions>(&mut file, "forking")?;
+        write_options::<FollowerOptions>(&mut file, "follower")?;
+        write_options::<PruningOptions>(&mut file, "pruning")?;
+        write_options::<DevOptions>(&mut file, "dev")?;
+        write_options::<RpcOptions>(&mut file, "rpc")?;
+        #[cfg(feature = "server")]
+        {
+            write_options::<ServerOptions>(&mut file, "server")?;
+            write_options::<MetricsOptions>(&mut file, "metrics")?;
+        }
+        #[cfg(feature = "cartridge")]
+        write_options::<CartridgeOptions>(&mut file, "cartridge")?;
+
+        Ok(file)
+    }
+}
+
+/// An option of the configuration file template.
+struct TemplateOption {
+    key: String,
+    heading: Option<String>,
+    help: Option<String>,
+    value_name: Option<String>,
+}
+
+impl TemplateOption {
+    fn new(key: &str, heading: &str) -> Self {
+        let heading = Some(heading.to_string());
+        Self { key: key.to_string(), heading, help: None, value_name: None }
+    }
+}
+
+impl From<&Arg> for TemplateOption {
+    fn from(arg: &Arg) -> Self {
+        Self {
+            key: arg.get_id().to_string(),
+            heading: arg.get_help_heading().map(ToString::to_string),
+            help: arg.get_long_help().or(arg.get_help()).map(ToString::to_string),
+            value_name: arg
+                .get_value_names()
+                .and_then(|names| names.first())
+                .map(|n| n.to_string()),
+        }
+    }
+}
+
+/// Returns the options defined by `T`, excluding the hidden ones.
+fn options<T: Args>() -> Vec<TemplateOption> {
+    let command = T::augment_args(Command::new(""));
+    let args = command.get_arguments().filter(|arg| !arg.is_hide_set());
+    args.map(TemplateOption::from).collect()
+}
+
+fn write_options<T: Args + Default + Serialize>(file: &mut String, section: &str) -> Result<()> {
+    write_section(file, Some(section), &options::<T>(), &T::default())
+}
+
+/// Writes the commented out `options` of a section of the configuration file, using `defaults` for
+/// their values. The options without a default value are written with a placeholder.
+fn write_section(
+    file: &mut String,
+    section: Option<&str>,
+    options: &[TemplateOption],
+    defaults: &impl Serialize,
+) -> Result<()> {
+    let defaults = toml::Value::try_from(defaults)?;
+
+    if let Some(section) = section {
+        writeln!(file)?;
+        if let Some(heading) = options.first().and_then(|opt| opt.heading.as_ref()) {
+            writeln!(file, "# {heading}")?;
+        }
+        writeln!(file, "# [{section}]")?;
+    }
+
+    for option in options {
+        writeln!(file)?;
+        for line in option.help.iter().flat_map(|help| help.lines()) {
+            writeln!(file, "{}", format!("# {line}").trim_end())?;
+        }
+
+        let key = &option.key;
+        match defaults.get(key) {
+            Some(value) => writeln!(file, "# {key} = {value}")?,
+            None => {
+                let placeholder = option.value_name.as_deref().unwrap_or("VALUE");
+                writeln!(file, "# {key} = <{placeholder}>")?
+            }
+        }
+    }
+
+    Ok(())
 }
 
 impl TryFrom<NodeArgs> for NodeArgsConfig {
@@ -48,7 +215,13 @@ impl TryFrom<NodeArgs> for NodeArgsConfig {
             no_mining: if args.no_mining { Some(true) } else { None },
             block_time: args.block_time,
             block_cairo_steps_limit: args.block_cairo_steps_limit,
+            block_max_txs: args.block_max_txs,
+            concurrency: args.concurrency,
+            provable: if args.provable { Some(true) } else { None },
+            execution: (args.execution != ExecutionBackend::default()).then_some(args.execution),
             db_dir: args.db_dir,
+            load_state: args.load_state,
+            dump_state: args.dump_state,
             messaging: args.messaging,
             ..Default::default()
         };
@@ -63,6 +236,10 @@ impl TryFrom<NodeArgs> for NodeArgsConfig {
             if args.gpo == GasPriceOracleOptions::default() { None } else { Some(args.gpo) };
         node_config.forking =
             if args.forking == ForkingOptions::default() { None } else { Some(args.forking) };
+        node_config.follower =
+            if args.follower == FollowerOptions::default() { None } else { Some(args.follower) };
+        node_config.pruning =
+            if args.pruning == PruningOptions::default() { None } else { Some(args.pruning) };
         node_config.development =
             if args.development == DevOptions::default() { None } else { Some(args.development) };
         node_config.rpc = if args.rpc == RpcOptions::default() { None } else { Some(args.rpc) };
@@ -8,9 +8,13 @@
 //! Currently, the merge is made at the top level of the commands.
 
 use std::net::{IpAddr, Ipv4Addr};
+#[cfg(feature = "server")]
+use std::path::PathBuf;
 
 use clap::Args;
-use katana_node::config::execution::{DEFAULT_INVOCATION_MAX_STEPS, DEFAULT_VALIDATION_MAX_STEPS};
+use katana_node::config::execution::{
+    DEFAULT_INVOCATION_MAX_STEPS, DEFAULT_VALIDATION_MAX_STEPS, MAX_RECURSION_DEPTH,
+};
 #[cfg(feature = "server")]
 use katana_node::config::metrics::{DEFAULT_METRICS_ADDR, DEFAULT_METRICS_PORT};
 use katana_node::config::rpc::{
@@ -97,6 +101,29 @@ pub struct ServerOptions {
         deserialize_with = "deserialize_cors_origins"
     )]
     pub http_cors_origins: Vec<HeaderValue>,
+
+    /// Path prefix under which the HTTP-RPC server is served, eg `/katana` when the node is behind
+    /// a reverse proxy forwarding the requests with their full path.
+    #[arg(long = "http.path-prefix", value_name = "PATH")]
+    pub http_path_prefix: Option<String>,
+
+    /// WebSocket-RPC server listening interface. Defaults to the HTTP-RPC server interface.
+    #[arg(long = "ws.addr", value_name = "ADDRESS")]
+    #[arg(requires = "ws_port")]
+    pub ws_addr: Option<IpAddr>,
+
+    /// WebSocket-RPC server listening port.
+    ///
+    /// If not set, the WebSocket connections are served on the HTTP-RPC server port.
+    #[arg(long = "ws.port", value_name = "PORT")]
+    pub ws_port: Option<u16>,
+
+    /// Path of the Unix domain socket on which the RPC methods are also served.
+    ///
+    /// Lets the services running on the same host talk to the node without going through the
+    /// network.
+    #[arg(long = "ipc.path", value_name = "PATH")]
+    pub ipc_path: Option<PathBuf>,
 }
 
 #[cfg(feature = "server")]
@@ -106,6 +133,10 @@ impl Default for ServerOptions {
             http_addr: DEFAULT_RPC_ADDR,
             http_port: DEFAULT_RPC_PORT,
             http_cors_origins: Vec::new(),
+            http_path_prefix: None,
+            ws_addr: None,
+            ws_port: None,
+            ipc_path: None,
         }
     }
 }
@@ -123,6 +154,18 @@ impl ServerOptions {
             if self.http_cors_origins.is_empty() {
                 self.http_cors_origins = other.http_cors_origins.clone();
             }
+            if self.http_path_prefix.is_none() {
+                self.http_path_prefix = other.http_path_prefix.clone();
+            }
+            if self.ws_addr.is_none() {
+                self.ws_addr = other.ws_addr;
+            }
+            if self.ws_port.is_none() {
+                self.ws_port = other.ws_port;
+            }
+            if self.ipc_path.is_none() {
+                self.ipc_path = other.ipc_path.clone();
+            }
         }
     }
 }
@@ -148,6 +191,37 @@ pub struct RpcOptions {
     #[arg(long = "rpc.max-response-body-size", value_name = "SIZE")]
     pub max_response_body_size: Option<u32>,
 
+    /// Maximum number of requests processed concurrently, across all connections.
+    #[arg(long = "rpc.max-concurrent-requests", value_name = "MAX")]
+    #[arg(value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_concurrent_requests: Option<u32>,
+
+    /// Maximum number of requests per second allowed from a single client IP address.
+    ///
+    /// The client address is read from the `X-Forwarded-For` or `X-Real-IP` headers set by a
+    /// reverse proxy. Requests without these headers all share the same limit.
+    #[arg(long = "rpc.rate-limit", value_name = "REQUESTS")]
+    #[arg(value_parser = clap::value_parser!(u32).range(1..))]
+    pub rate_limit: Option<u32>,
+
+    /// Maximum number of calls of a batch request. Larger batches are rejected.
+    #[arg(long = "rpc.max-batch-size", value_name = "MAX")]
+    #[arg(value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_batch_size: Option<u32>,
+
+    /// Maximum number of calls of a batch request executed concurrently. Default is 16.
+    #[arg(long = "rpc.batch-concurrency", value_name = "MAX")]
+    #[arg(value_parser = clap::value_parser!(u32).range(1..))]
+    pub batch_concurrency: Option<u32>,
+
+    /// API key required for calling the state-mutating methods (ie adding transactions and the
+    /// `dev` methods), passed as a bearer token in the `Authorization` header.
+    ///
+    /// All the other methods remain open. WebSocket connections always require the API key.
+    #[arg(long = "rpc.api-key", value_name = "KEY", env = "KATANA_RPC_API_KEY")]
+    #[arg(hide_env_values = true)]
+    pub api_key: Option<String>,
+
     /// Maximum page size for event queries.
     #[arg(long = "rpc.max-event-page-size", value_name = "SIZE")]
     #[arg(default_value_t = DEFAULT_RPC_MAX_EVENT_PAGE_SIZE)]
@@ -176,6 +250,11 @@ impl Default for RpcOptions {
             max_connections: None,
             max_request_body_size: None,
             max_response_body_size: None,
+            max_concurrent_requests: None,
+            rate_limit: None,
+            max_batch_size: None,
+            batch_concurrency: None,
+            api_key: None,
             max_call_gas: DEFAULT_RPC_MAX_CALL_GAS,
         }
     }
@@ -196,6 +275,21 @@ impl RpcOptions {
             if self.max_response_body_size.is_none() {
                 self.max_response_body_size = other.max_response_body_size;
             }
+            if self.max_concurrent_requests.is_none() {
+                self.max_concurrent_requests = other.max_concurrent_requests;
+            }
+            if self.rate_limit.is_none() {
+                self.rate_limit = other.rate_limit;
+            }
+            if self.max_batch_size.is_none() {
+                self.max_batch_size = other.max_batch_size;
+            }
+            if self.batch_concurrency.is_none() {
+                self.batch_concurrency = other.batch_concurrency;
+            }
+            if self.api_key.is_none() {
+                self.api_key = other.api_key.clone();
+            }
             if self.max_event_page_size == DEFAULT_RPC_MAX_EVENT_PAGE_SIZE {
                 self.max_event_page_size = other.max_event_page_size;
             }
@@ -216,6 +310,10 @@ pub struct StarknetOptions {
     #[serde(rename = "env")]
     pub environment: EnvironmentOptions,
 
+    /// Path to a genesis JSON file.
+    ///
+    /// The file can be used to declare classes, deploy contracts and accounts, allocate token
+    /// balances and set storage values at genesis. When provided, no dev accounts are generated.
     #[arg(long)]
     #[arg(value_parser = parse_genesis)]
     #[arg(conflicts_with_all(["seed", "total_accounts", "chain"]))]
@@ -258,6 +356,22 @@ pub struct EnvironmentOptions {
     #[arg(default_value_t = DEFAULT_INVOCATION_MAX_STEPS)]
     #[serde(default = "default_invoke_max_steps")]
     pub invoke_max_steps: u32,
+
+    /// The maximum depth of nested contract calls.
+    #[arg(long)]
+    #[arg(default_value_t = MAX_RECURSION_DEPTH)]
+    #[serde(default = "default_max_recursion_depth")]
+    pub max_recursion_depth: usize,
+
+    /// Remove the Cairo step limits of the transactions.
+    ///
+    /// The maximum number of steps of the account validation and execution logic are set to their
+    /// maximum value, such that transactions that are too expensive for a regular sequencer can
+    /// still be executed. Useful for stress testing contracts.
+    #[arg(long)]
+    #[arg(conflicts_with_all(["validate_max_steps", "invoke_max_steps"]))]
+    #[serde(default)]
+    pub unlimited_resources: bool,
 }
 
 impl Default for EnvironmentOptions {
@@ -265,6 +379,8 @@ impl Default for EnvironmentOptions {
         EnvironmentOptions {
             validate_max_steps: DEFAULT_VALIDATION_MAX_STEPS,
             invoke_max_steps: DEFAULT_INVOCATION_MAX_STEPS,
+            max_recursion_depth: MAX_RECURSION_DEPTH,
+            unlimited_resources: false,
             chain_id: None,
         }
     }
@@ -280,6 +396,14 @@ impl EnvironmentOptions {
             if self.invoke_max_steps == DEFAULT_INVOCATION_MAX_STEPS {
                 self.invoke_max_steps = other.invoke_max_steps;
             }
+
+            if self.max_recursion_depth == MAX_RECURSION_DEPTH {
+                self.max_recursion_depth = other.max_recursion_depth;
+            }
+
+            if !self.unlimited_resources {
+                self.unlimited_resources = other.unlimited_resources;
+            }
         }
     }
 }
@@ -295,7 +419,7 @@ pub struct DevOptions {
 
     /// Specify the seed for randomness of accounts to be predeployed.
     #[arg(requires = "dev")]
-    #[arg(long = "dev.seed", default_value = DEFAULT_DEV_SEED)]
+    #[arg(long = "dev.seed", alias = "seed", default_value = DEFAULT_DEV_SEED)]
     #[serde(default = "default_seed")]
     pub seed: String,
 
@@ -319,6 +443,16 @@ pub struct DevOptions {
     #[arg(long = "dev.no-account-validation")]
     #[serde(default)]
     pub no_account_validation: bool,
+
+    /// Run the node in deterministic mode.
+    ///
+    /// Block timestamps start from the genesis timestamp and increase by a constant amount for
+    /// every block instead of following the current time. Combined with `--dev.seed`, running the
+    /// same transactions always produces the same blocks and state roots.
+    #[arg(requires = "dev", conflicts_with = "l1_provider_url")]
+    #[arg(long = "dev.deterministic", alias = "deterministic")]
+    #[serde(default)]
+    pub deterministic: bool,
 }
 
 impl Default for DevOptions {
@@ -329,6 +463,7 @@ impl Default for DevOptions {
             total_accounts: DEFAULT_DEV_ACCOUNTS,
             no_fee: false,
             no_account_validation: false,
+            deterministic: false,
         }
     }
 }
@@ -355,6 +490,10 @@ impl DevOptions {
             if !self.no_account_validation {
                 self.no_account_validation = other.no_account_validation;
             }
+
+            if !self.deterministic {
+                self.deterministic = other.deterministic;
+            }
         }
     }
 }
@@ -367,15 +506,65 @@ pub struct ForkingOptions {
     /// This will operate Katana in forked mode. Continuing from the tip of the forked network, or
     /// at a specific block if `fork.block` is provided.
     #[arg(long = "fork.provider", value_name = "URL", conflicts_with = "genesis")]
+    #[arg(alias = "fork-rpc-url")]
     pub fork_provider: Option<Url>,
 
     /// Fork the network at a specific block id, can either be a hash (0x-prefixed) or a block
     /// number.
     #[arg(long = "fork.block", value_name = "BLOCK", requires = "fork_provider")]
+    #[arg(alias = "fork-block")]
     #[arg(value_parser = parse_block_hash_or_number)]
     pub fork_block: Option<BlockHashOrNumber>,
 }
 
+#[derive(Debug, Args, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[command(next_help_heading = "Follower options")]
+pub struct FollowerOptions {
+    /// The RPC URL of the primary node to follow.
+    ///
+    /// This will operate Katana in follower mode, where blocks are replicated from the primary
+    /// node instead of being produced locally, and transactions can't be submitted. The primary
+    /// must have the `replication` RPC module enabled and use the same chain spec.
+    #[arg(long = "follower.primary", value_name = "URL")]
+    #[arg(conflicts_with = "fork_provider")]
+    pub primary: Option<Url>,
+
+    /// The interval in milliseconds at which the primary node is polled for new blocks.
+    #[arg(long = "follower.poll-interval", value_name = "MILLISECONDS")]
+    #[arg(requires = "primary")]
+    pub poll_interval: Option<u64>,
+}
+
+#[derive(Debug, Args, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[command(next_help_heading = "Pruning options")]
+pub struct PruningOptions {
+    /// Only keep the transaction receipts of the latest N blocks.
+    ///
+    /// The receipts of the older blocks are removed as new blocks are mined.
+    #[arg(long = "prune.receipts", value_name = "BLOCKS")]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    #[arg(conflicts_with = "fork_provider")]
+    pub receipts: Option<u64>,
+
+    /// Only keep the transaction execution traces of the latest N blocks.
+    ///
+    /// The traces of the older blocks are removed as new blocks are mined.
+    #[arg(long = "prune.traces", value_name = "BLOCKS")]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    #[arg(conflicts_with = "fork_provider")]
+    pub traces: Option<u64>,
+
+    /// Only keep the state diffs of the latest N blocks.
+    ///
+    /// The state diffs of the older blocks are removed as new blocks are mined, except for the
+    /// ones needed to know the latest state. The historical state of the pruned blocks can no
+    /// longer be queried.
+    #[arg(long = "prune.state-diffs", value_name = "BLOCKS")]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    #[arg(conflicts_with = "fork_provider")]
+    pub state_diffs: Option<u64>,
+}
+
 #[derive(Debug, Args, Clone, Serialize, Deserialize, Default, PartialEq)]
 #[command(next_help_heading = "Logging options")]
 pub struct LoggingOptions {
@@ -542,6 +731,10 @@ fn default_invoke_max_steps() -> u32 {
     DEFAULT_INVOCATION_MAX_STEPS
 }
 
+fn default_max_recursion_depth() -> usize {
+    MAX_RECURSION_DEPTH
+}
+
 #[cfg(feature = "server")]
 fn default_http_addr() -> IpAddr {
     DEFAULT_RPC_ADDR
@@ -82,6 +82,38 @@ impl Display for LogFormat {
     }
 }
 
+/// The backend used to run the Sierra classes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, Default)]
+pub enum ExecutionBackend {
+    /// Run the classes on the Cairo VM.
+    #[default]
+    Vm,
+    /// Run the classes as native code compiled with Cairo Native.
+    Native,
+}
+
+impl ValueEnum for ExecutionBackend {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[Self::Vm, Self::Native]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        match self {
+            Self::Vm => Some(PossibleValue::new("vm")),
+            Self::Native => Some(PossibleValue::new("native")),
+        }
+    }
+}
+
+impl Display for ExecutionBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Vm => write!(f, "vm"),
+            Self::Native => write!(f, "native"),
+        }
+    }
+}
+
 pub fn print_intro(args: &NodeArgs, chain: &ChainSpec) {
     let mut accounts = chain.genesis().accounts().peekable();
     let account_class_hash = accounts.peek().map(|e| e.1.class_hash());
@@ -238,6 +270,27 @@ where
         .map_err(serde::de::Error::custom)
 }
 
+/// Where the chain specification given with `--chain` is read from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ChainSource {
+    /// A rollup chain configuration directory, as created by `katana init`.
+    ConfigDir(ChainConfigDir),
+    /// A chain specification TOML file.
+    File(PathBuf),
+}
+
+/// Parses the value of `--chain`, which is either a path to a chain specification file, a path to
+/// a chain configuration directory or the id of a locally initialized chain.
+pub fn parse_chain_source(value: &str) -> Result<ChainSource> {
+    let path = PathBuf::from(shellexpand::tilde(value).as_ref());
+
+    if path.is_file() {
+        Ok(ChainSource::File(path))
+    } else {
+        Ok(ChainSource::ConfigDir(parse_chain_config_dir(value)?))
+    }
+}
+
 // Chain IDs can be arbitrary ASCII strings, making them indistinguishable from filesystem paths.
 // To handle this ambiguity, we first try parsing single-component inputs as paths, then as chain
 // IDs. Multi-component inputs are always treated as paths.
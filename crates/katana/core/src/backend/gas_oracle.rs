@@ -84,6 +84,16 @@ impl GasOracle {
         }
     }
 
+    /// Seeds a sampled oracle with the given prices, so that it has sensible prices to report
+    /// until it has collected its first sample. Has no effect on a fixed oracle.
+    pub fn seed(&self, gas_prices: GasPrices, data_gas_prices: GasPrices) {
+        if let GasOracle::Sampled(sampled) = self {
+            let mut prices = sampled.prices.lock();
+            prices.gas_prices = gas_prices;
+            prices.data_gas_prices = data_gas_prices;
+        }
+    }
+
     pub fn run_worker(&self, task_spawner: TaskSpawner) {
         match self {
             Self::Fixed(..) => {}
@@ -166,7 +176,6 @@ impl GasOracleWorker {
     }
 
     pub async fn run(&mut self) -> anyhow::Result<()> {
-        let provider = ProviderBuilder::new().on_http(self.l1_provider_url.clone());
         // every 60 seconds, Starknet samples the base price of gas and data gas on L1
         let mut interval = tokio::time::interval(INTERVAL);
         interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
@@ -175,24 +184,10 @@ impl GasOracleWorker {
             // Wait for the interval to tick
             interval.tick().await;
 
-            {
-                // Attempt to get the gas price from L1
-                let last_block_number = provider.get_block_number().await?;
-
-                let fee_history = provider
-                    .get_fee_history(1, BlockNumberOrTag::Number(last_block_number), &[])
-                    .await?;
-
-                let mut prices = self.prices.lock();
-
-                if let Err(error) = update_gas_price(
-                    &mut prices,
-                    &mut self.gas_price_buffer,
-                    &mut self.data_gas_price_buffer,
-                    fee_history,
-                ) {
-                    error!(target: "gas_oracle", %error, "Error updating gas prices.");
-                }
+            // A failed sample (eg the L1 provider is temporarily unreachable) must not bring the
+            // node down, so we keep on using the last known prices until the next tick.
+            if let Err(error) = self.update_once().await {
+                error!(target: "gas_oracle", %error, "Error updating gas prices.");
             }
         }
     }
@@ -293,6 +288,47 @@ mod tests {
         assert_eq!(buffer.average(), expected_avg);
     }
 
+    #[test]
+    fn test_update_gas_price_from_fee_history() {
+        let mut prices = SampledPrices::default();
+        let mut gas_price_buffer = GasPriceBuffer::new();
+        let mut data_gas_price_buffer = GasPriceBuffer::new();
+
+        for (base_fee, blob_fee) in [(10 * ONE_GWEI, 100), (20 * ONE_GWEI, 300)] {
+            let fee_history = FeeHistory {
+                base_fee_per_gas: vec![ONE_GWEI, base_fee],
+                base_fee_per_blob_gas: vec![1, blob_fee],
+                ..Default::default()
+            };
+
+            update_gas_price(
+                &mut prices,
+                &mut gas_price_buffer,
+                &mut data_gas_price_buffer,
+                fee_history,
+            )
+            .unwrap();
+        }
+
+        // only the latest base fee of each fee history is sampled
+        let expected_gas_price = 15 * ONE_GWEI + ONE_GWEI;
+        assert_eq!(
+            prices.gas_prices,
+            GasPrices { eth: expected_gas_price, strk: expected_gas_price }
+        );
+        assert_eq!(prices.data_gas_prices, GasPrices { eth: 200, strk: 200 });
+
+        // a fee history without any base fee is rejected, and the prices are left untouched
+        let result = update_gas_price(
+            &mut prices,
+            &mut gas_price_buffer,
+            &mut data_gas_price_buffer,
+            FeeHistory::default(),
+        );
+        assert!(result.is_err());
+        assert_eq!(prices.data_gas_prices, GasPrices { eth: 200, strk: 200 });
+    }
+
     #[tokio::test]
     #[ignore = "Requires external assumption"]
     async fn test_gas_oracle() {
@@ -1,5 +1,6 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{btree_map, BTreeMap, HashMap};
 use std::sync::Arc;
+use std::time::Instant;
 
 use anyhow::{anyhow, Context};
 use gas_oracle::GasOracle;
@@ -10,16 +11,19 @@ use katana_primitives::block::{
     SealedBlockWithStatus,
 };
 use katana_primitives::class::{ClassHash, CompiledClassHash};
+use katana_primitives::contract::GenericContractInfo;
 use katana_primitives::da::L1DataAvailabilityMode;
 use katana_primitives::env::BlockEnv;
 use katana_primitives::receipt::{Event, Receipt, ReceiptWithTxHash};
 use katana_primitives::state::{compute_state_diff_hash, StateUpdates, StateUpdatesWithClasses};
-use katana_primitives::trace::TxExecInfo;
+use katana_primitives::trace::{BlockExecutionArtifacts, TxExecInfo};
 use katana_primitives::transaction::{TxHash, TxWithHash};
-use katana_primitives::version::CURRENT_STARKNET_VERSION;
 use katana_primitives::{address, ContractAddress, Felt};
 use katana_provider::providers::in_memory::state::EmptyStateProvider;
-use katana_provider::traits::block::{BlockHashProvider, BlockWriter};
+use katana_provider::traits::block::{
+    BlockArtifactsWriter, BlockHashProvider, BlockNumberProvider, BlockWriter, HeaderProvider,
+};
+use katana_provider::traits::state::StateFactoryProvider;
 use katana_provider::traits::trie::TrieWriter;
 use katana_trie::bonsai::databases::HashMapDb;
 use katana_trie::{
@@ -30,15 +34,18 @@ use parking_lot::RwLock;
 use rayon::prelude::*;
 use starknet::macros::short_string;
 use starknet_types_core::hash::{self, StarkHash};
+use tokio::sync::broadcast;
 use tracing::info;
 
 pub mod contract;
 pub mod gas_oracle;
+pub mod notification;
 pub mod storage;
 
+use self::notification::{BlockNotifier, ExecutedTxNotifier, NewBlock};
 use self::storage::Blockchain;
 use crate::env::BlockContextGenerator;
-use crate::service::block_producer::{BlockProductionError, MinedBlockOutcome};
+use crate::service::block_producer::{BlockProductionError, MinedBlockOutcome, TxWithOutcome};
 use crate::utils::get_current_timestamp;
 
 pub(crate) const LOG_TARGET: &str = "katana::core::backend";
@@ -54,6 +61,12 @@ pub struct Backend<EF> {
     pub executor_factory: Arc<EF>,
 
     pub gas_oracle: GasOracle,
+    /// Notifies subscribers about newly mined blocks.
+    pub block_notifier: BlockNotifier,
+    /// Notifies subscribers about newly executed transactions.
+    pub executed_tx_notifier: ExecutedTxNotifier,
+    /// Whether the SNOS inputs of the mined blocks, ie the state they access, are recorded.
+    pub provable: bool,
 }
 
 impl<EF> Backend<EF> {
@@ -69,16 +82,64 @@ impl<EF> Backend<EF> {
             gas_oracle,
             executor_factory: Arc::new(executor_factory),
             block_context_generator: RwLock::new(BlockContextGenerator::default()),
+            block_notifier: BlockNotifier::default(),
+            executed_tx_notifier: ExecutedTxNotifier::default(),
+            provable: false,
         }
     }
+
+    /// Subscribes to the transactions executed by the block producer, along with their receipts,
+    /// traces and state changes.
+    ///
+    /// This allows projects embedding the node to react to the execution of transactions without
+    /// going through the RPC server. See [`ExecutedTxNotifier`] for more details.
+    pub fn subscribe_executed_txs(&self) -> broadcast::Receiver<Arc<TxWithOutcome>> {
+        self.executed_tx_notifier.subscribe()
+    }
 }
 
 impl<EF: ExecutorFactory> Backend<EF> {
     pub fn init_genesis(&self) -> anyhow::Result<()> {
         match self.chain_spec.as_ref() {
-            ChainSpec::Dev(cs) => self.init_dev_genesis(cs),
-            ChainSpec::Rollup(cs) => self.init_rollup_genesis(cs),
+            ChainSpec::Dev(cs) => self.init_dev_genesis(cs)?,
+            ChainSpec::Rollup(cs) => self.init_rollup_genesis(cs)?,
         }
+
+        self.resume_from_latest_block()
+    }
+
+    /// Restores the in-memory context of a chain that is resumed from an existing database, based
+    /// on its latest sealed block. This is a no-op if the chain only has its genesis block.
+    fn resume_from_latest_block(&self) -> anyhow::Result<()> {
+        let provider = self.blockchain.provider();
+
+        let latest_num = provider.latest_number()?;
+        if latest_num == self.chain_spec.genesis().number {
+            return Ok(());
+        }
+
+        let header = provider
+            .header(latest_num.into())?
+            .with_context(|| format!("missing header for latest block {latest_num}"))?;
+
+        // Until the oracle has sampled the settlement layer, keep on using the prices of the
+        // latest block instead of reporting zero prices.
+        self.gas_oracle.seed(header.l1_gas_prices, header.l1_data_gas_prices);
+
+        // The new blocks must never be older than the latest block, even if the clock has moved
+        // backward (or was moved forward using the dev api) since the chain was last running.
+        // This doesn't apply to fixed block times, as the timestamps only depend on the parent.
+        let mut context_gen = self.block_context_generator.write();
+        let now = get_current_timestamp().as_secs() as i64;
+        let offset = header.timestamp as i64 - now;
+        if offset > 0 && context_gen.fixed_block_time.is_none() {
+            context_gen.block_timestamp_offset = offset;
+        }
+        drop(context_gen);
+
+        info!(target: LOG_TARGET, block = %latest_num, "Resuming chain from existing database.");
+
+        Ok(())
     }
 
     // TODO: add test for this function
@@ -87,6 +148,8 @@ impl<EF: ExecutorFactory> Backend<EF> {
         block_env: &BlockEnv,
         mut execution_output: ExecutionOutput,
     ) -> Result<MinedBlockOutcome, BlockProductionError> {
+        let started_at = Instant::now();
+
         let mut traces = Vec::with_capacity(execution_output.transactions.len());
         let mut receipts = Vec::with_capacity(execution_output.transactions.len());
         let mut transactions = Vec::with_capacity(execution_output.transactions.len());
@@ -107,7 +170,7 @@ impl<EF: ExecutorFactory> Backend<EF> {
         let partial_header = PartialHeader {
             number: block_env.number,
             timestamp: block_env.timestamp,
-            protocol_version: CURRENT_STARKNET_VERSION,
+            protocol_version: self.chain_spec.protocol_version(),
             l1_da_mode: L1DataAvailabilityMode::Calldata,
             sequencer_address: block_env.sequencer_address,
             l1_gas_prices: block_env.l1_gas_prices.clone(),
@@ -127,13 +190,44 @@ impl<EF: ExecutorFactory> Backend<EF> {
         let block = SealedBlockWithStatus { block, status: FinalityStatus::AcceptedOnL2 };
         let block_number = block.block.header.number;
 
+        // only pay for the clones if someone is actually listening
+        let notification = self.block_notifier.has_subscribers().then(|| NewBlock {
+            hash: block.block.hash,
+            header: block.block.header.clone(),
+            receipts: receipts.clone(),
+        });
+
         // TODO: maybe should change the arguments for insert_block_with_states_and_receipts to
         // accept ReceiptWithTxHash instead to avoid this conversion.
         let receipts = receipts.into_iter().map(|r| r.receipt).collect::<Vec<_>>();
-        self.store_block(block, execution_output.states, receipts, traces)?;
+
+        // the artifacts are read from the state of the parent block, so before storing the block
+        let artifacts = if self.provable {
+            let states = &execution_output.states.state_updates;
+            Some(self.collect_execution_artifacts(&traces, states)?)
+        } else {
+            None
+        };
+
+        let states = execution_output.states;
+        if let Some(artifacts) = artifacts {
+            // stored along with the block, so that a provable block never lacks its artifacts
+            provider.insert_block_with_artifacts(block, states, receipts, traces, artifacts)?;
+        } else {
+            self.store_block(block, states, receipts, traces)?;
+        }
+
+        if let Some(notification) = notification {
+            self.block_notifier.notify(notification);
+        }
 
         info!(target: LOG_TARGET, %block_number, %tx_count, "Block mined.");
-        Ok(MinedBlockOutcome { block_number, txs: tx_hashes, stats: execution_output.stats })
+        Ok(MinedBlockOutcome {
+            block_number,
+            txs: tx_hashes,
+            stats: execution_output.stats,
+            duration: started_at.elapsed(),
+        })
     }
 
     fn store_block(
@@ -149,11 +243,70 @@ impl<EF: ExecutorFactory> Backend<EF> {
         Ok(())
     }
 
+    /// Collects the SNOS inputs of a block: the state accessed by its transactions, as it was
+    /// before the block.
+    ///
+    /// Must be called before the block is stored, since the state is read from the latest state.
+    fn collect_execution_artifacts(
+        &self,
+        traces: &[TxExecInfo],
+        state_updates: &StateUpdates,
+    ) -> katana_provider::ProviderResult<BlockExecutionArtifacts> {
+        let state = self.blockchain.provider().latest()?;
+        let mut artifacts = BlockExecutionArtifacts::default();
+
+        for call in traces.iter().flat_map(|trace| trace.calls()) {
+            let address = call.contract_address;
+
+            if let btree_map::Entry::Vacant(entry) = artifacts.initial_contracts.entry(address) {
+                // the contracts deployed by the block don't exist in the parent state
+                if let Some(class_hash) = state.class_hash_of_contract(address)? {
+                    let nonce = state.nonce(address)?.unwrap_or_default();
+                    entry.insert(GenericContractInfo { nonce, class_hash });
+                }
+            }
+
+            let storage = artifacts.initial_storage.entry(address).or_default();
+            for key in &call.accessed_storage_keys {
+                if let btree_map::Entry::Vacant(entry) = storage.entry(*key) {
+                    entry.insert(state.storage(address, *key)?.unwrap_or_default());
+                }
+            }
+
+            let class_hash = call.class_hash.or_else(|| {
+                let initial = artifacts.initial_contracts.get(&address).map(|c| c.class_hash);
+                initial.or_else(|| state_updates.deployed_contracts.get(&address).copied())
+            });
+
+            let Some(class_hash) = class_hash else { continue };
+            if let btree_map::Entry::Vacant(entry) = artifacts.executed_classes.entry(class_hash) {
+                // legacy classes don't have a compiled class hash
+                let compiled_hash = match state.compiled_class_hash_of_class_hash(class_hash)? {
+                    Some(hash) => hash,
+                    None => {
+                        state_updates.declared_classes.get(&class_hash).copied().unwrap_or_default()
+                    }
+                };
+
+                entry.insert(compiled_hash);
+            }
+        }
+
+        artifacts.initial_storage.retain(|_, storage| !storage.is_empty());
+        Ok(artifacts)
+    }
+
     pub fn update_block_env(&self, block_env: &mut BlockEnv) {
         let mut context_gen = self.block_context_generator.write();
         let current_timestamp_secs = get_current_timestamp().as_secs() as i64;
 
-        let timestamp = if context_gen.next_block_start_time == 0 {
+        let timestamp = if let Some(block_time) = context_gen.fixed_block_time {
+            // without a clock to be relative to, the explicit offset only applies to this block
+            let timestamp = fixed_block_timestamp(&context_gen, block_env.timestamp, block_time);
+            context_gen.block_timestamp_offset = 0;
+            context_gen.next_block_start_time = 0;
+            timestamp
+        } else if context_gen.next_block_start_time == 0 {
             (current_timestamp_secs + context_gen.block_timestamp_offset) as u64
         } else {
             let timestamp = context_gen.next_block_start_time;
@@ -169,6 +322,27 @@ impl<EF: ExecutorFactory> Backend<EF> {
         self.update_block_gas_prices(block_env);
     }
 
+    /// Same as [`Backend::update_block_env`] but doesn't consume the timestamp that was explicitly
+    /// set for the next block. Used to preview the environment of the next block (eg when
+    /// executing against the `pending` block) without affecting the block that is actually mined.
+    pub fn preview_block_env(&self, block_env: &mut BlockEnv) {
+        let context_gen = self.block_context_generator.read();
+
+        let timestamp = if let Some(block_time) = context_gen.fixed_block_time {
+            fixed_block_timestamp(&context_gen, block_env.timestamp, block_time)
+        } else if context_gen.next_block_start_time == 0 {
+            let current_timestamp_secs = get_current_timestamp().as_secs() as i64;
+            (current_timestamp_secs + context_gen.block_timestamp_offset) as u64
+        } else {
+            context_gen.next_block_start_time
+        };
+
+        block_env.number += 1;
+        block_env.timestamp = timestamp;
+
+        self.update_block_gas_prices(block_env);
+    }
+
     /// Updates the gas prices in the block environment.
     pub fn update_block_gas_prices(&self, block_env: &mut BlockEnv) {
         block_env.l1_gas_prices = self.gas_oracle.current_gas_prices();
@@ -492,6 +666,20 @@ impl<'a, P: TrieWriter> UncommittedBlock<'a, P> {
     }
 }
 
+/// Returns the timestamp of the block following a block with `parent_timestamp`, when the blocks
+/// are `block_time` seconds apart. A timestamp explicitly set for the next block takes precedence.
+fn fixed_block_timestamp(
+    context_gen: &BlockContextGenerator,
+    parent_timestamp: u64,
+    block_time: u64,
+) -> u64 {
+    if context_gen.next_block_start_time != 0 {
+        context_gen.next_block_start_time
+    } else {
+        (parent_timestamp + block_time).saturating_add_signed(context_gen.block_timestamp_offset)
+    }
+}
+
 // TODO: create a dedicated struct for this contract.
 // https://docs.starknet.io/architecture-and-concepts/network-architecture/starknet-state/#address_0x1
 fn update_block_hash_registry_contract(
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use katana_primitives::block::{BlockHash, Header};
+use katana_primitives::receipt::ReceiptWithTxHash;
+use tokio::sync::broadcast;
+
+use crate::service::block_producer::TxWithOutcome;
+
+/// The maximum number of block notifications that can be buffered for a subscriber before it
+/// starts lagging behind and missing notifications.
+const BLOCK_NOTIFICATION_CAPACITY: usize = 128;
+
+/// The maximum number of executed transactions that can be buffered for a subscriber before it
+/// starts lagging behind and missing notifications.
+const EXECUTED_TX_NOTIFICATION_CAPACITY: usize = 2048;
+
+/// A newly mined block.
+#[derive(Debug, Clone)]
+pub struct NewBlock {
+    pub hash: BlockHash,
+    pub header: Header,
+    /// The receipts of all the transactions included in the block, in order.
+    pub receipts: Vec<ReceiptWithTxHash>,
+}
+
+/// Broadcasts every newly mined block to all of its subscribers.
+#[derive(Debug, Clone)]
+pub struct BlockNotifier {
+    sender: broadcast::Sender<Arc<NewBlock>>,
+}
+
+impl BlockNotifier {
+    /// Subscribes to the newly mined blocks. Only the blocks mined after the subscription are
+    /// received.
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<NewBlock>> {
+        self.sender.subscribe()
+    }
+
+    /// Returns `true` if there is at least one active subscriber.
+    pub fn has_subscribers(&self) -> bool {
+        self.sender.receiver_count() > 0
+    }
+
+    /// Notifies all the subscribers of a new block. Only meant to be called once the block has been
+    /// stored.
+    pub fn notify(&self, block: NewBlock) {
+        // an error only means there are no active subscribers
+        let _ = self.sender.send(Arc::new(block));
+    }
+}
+
+impl Default for BlockNotifier {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(BLOCK_NOTIFICATION_CAPACITY);
+        Self { sender }
+    }
+}
+
+/// Broadcasts every transaction executed by the block producer to all of its subscribers.
+///
+/// Only the transactions that are included in a block - including the reverted ones - are
+/// broadcasted. On _interval_ mining, they are broadcasted as soon as they are executed, ie
+/// before the block that includes them is mined.
+#[derive(Debug, Clone)]
+pub struct ExecutedTxNotifier {
+    sender: broadcast::Sender<Arc<TxWithOutcome>>,
+}
+
+impl ExecutedTxNotifier {
+    /// Subscribes to the executed transactions. Only the transactions executed after the
+    /// subscription are received.
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<TxWithOutcome>> {
+        self.sender.subscribe()
+    }
+
+    pub(crate) fn notify(&self, txs: &[TxWithOutcome]) {
+        // avoid cloning the transactions if nobody is listening
+        if self.sender.receiver_count() == 0 {
+            return;
+        }
+
+        for tx in txs {
+            let _ = self.sender.send(Arc::new(tx.clone()));
+        }
+    }
+}
+
+impl Default for ExecutedTxNotifier {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(EXECUTED_TX_NOTIFICATION_CAPACITY);
+        Self { sender }
+    }
+}
@@ -9,9 +9,12 @@ use katana_primitives::da::L1DataAvailabilityMode;
 use katana_primitives::hash::{self, StarkHash};
 use katana_provider::providers::db::DbProvider;
 use katana_provider::providers::fork::ForkedProvider;
-use katana_provider::traits::block::{BlockProvider, BlockWriter};
+use katana_provider::traits::block::{
+    BlockArtifactsProvider, BlockArtifactsWriter, BlockProvider, BlockStatusWriter, BlockWriter,
+};
 use katana_provider::traits::contract::{ContractClassWriter, ContractClassWriterExt};
 use katana_provider::traits::env::BlockEnvProvider;
+use katana_provider::traits::event::EventProvider;
 use katana_provider::traits::stage::StageCheckpointProvider;
 use katana_provider::traits::state::{StateFactoryProvider, StateWriter};
 use katana_provider::traits::state_update::StateUpdateProvider;
@@ -33,11 +36,15 @@ use url::Url;
 pub trait Database:
     BlockProvider
     + BlockWriter
+    + BlockStatusWriter
+    + BlockArtifactsProvider
+    + BlockArtifactsWriter
     + TransactionProvider
     + TransactionStatusProvider
     + TransactionTraceProvider
     + TransactionsProviderExt
     + ReceiptProvider
+    + EventProvider
     + StateUpdateProvider
     + StateWriter
     + ContractClassWriter
@@ -56,11 +63,15 @@ pub trait Database:
 impl<T> Database for T where
     T: BlockProvider
         + BlockWriter
+        + BlockStatusWriter
+        + BlockArtifactsProvider
+        + BlockArtifactsWriter
         + TransactionProvider
         + TransactionStatusProvider
         + TransactionTraceProvider
         + TransactionsProviderExt
         + ReceiptProvider
+        + EventProvider
         + StateUpdateProvider
         + StateWriter
         + ContractClassWriter
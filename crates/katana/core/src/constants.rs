@@ -10,6 +10,9 @@ pub const DEFAULT_STRK_L1_GAS_PRICE: u128 = 20 * u128::pow(10, 9); // Given in u
 pub const DEFAULT_ETH_L1_DATA_GAS_PRICE: u128 = u128::pow(10, 6); // Given in units of Wei.
 pub const DEFAULT_STRK_L1_DATA_GAS_PRICE: u128 = u128::pow(10, 6); // Given in units of STRK.
 
+/// The number of seconds between the timestamps of two consecutive blocks in deterministic mode.
+pub const DETERMINISTIC_BLOCK_TIME: u64 = 1;
+
 lazy_static! {
 
     // Predefined contract addresses
@@ -2,4 +2,7 @@
 pub struct BlockContextGenerator {
     pub block_timestamp_offset: i64,
     pub next_block_start_time: u64,
+    /// If set, the timestamp of each block is the timestamp of its parent block plus this number
+    /// of seconds, instead of being derived from the current time.
+    pub fixed_block_time: Option<u64>,
 }
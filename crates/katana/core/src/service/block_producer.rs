@@ -9,7 +9,7 @@
 //!
 //! **********************************************************************************************
 
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -19,16 +19,20 @@ use std::time::Duration;
 use futures::channel::mpsc::{channel, Receiver, Sender};
 use futures::stream::{Stream, StreamExt};
 use futures::FutureExt;
-use katana_executor::{BlockExecutor, ExecutionResult, ExecutionStats, ExecutorFactory};
+use katana_executor::{
+    BlockExecutor, ExecutionOutput, ExecutionResult, ExecutionStats, ExecutorFactory,
+};
 use katana_pool::validation::stateful::TxValidator;
-use katana_primitives::block::{BlockHashOrNumber, ExecutableBlock, PartialHeader};
+use katana_primitives::block::{BlockHashOrNumber, BlockNumber, ExecutableBlock, PartialHeader};
+use katana_primitives::class::ClassHash;
+use katana_primitives::contract::{ContractAddress, Nonce, StorageKey, StorageValue};
 use katana_primitives::da::L1DataAvailabilityMode;
 use katana_primitives::receipt::Receipt;
+use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
 use katana_primitives::trace::TxExecInfo;
 use katana_primitives::transaction::{ExecutableTxWithHash, TxHash, TxWithHash};
-use katana_primitives::version::CURRENT_STARKNET_VERSION;
 use katana_provider::error::ProviderError;
-use katana_provider::traits::block::{BlockHashProvider, BlockNumberProvider};
+use katana_provider::traits::block::{BlockHashProvider, BlockNumberProvider, BlockWriter};
 use katana_provider::traits::env::BlockEnvProvider;
 use katana_provider::traits::state::StateFactoryProvider;
 use katana_tasks::{BlockingTaskPool, BlockingTaskResult};
@@ -55,6 +59,9 @@ pub enum BlockProductionError {
 
     #[error("transaction execution error: {0}")]
     TransactionExecutionError(#[from] katana_executor::ExecutorError),
+
+    #[error("transaction {index} of the batch failed: {reason}")]
+    BatchTransactionFailed { index: usize, reason: String },
 }
 
 impl BlockProductionError {
@@ -72,6 +79,8 @@ pub struct MinedBlockOutcome {
     pub block_number: u64,
     pub txs: Vec<TxHash>,
     pub stats: ExecutionStats,
+    /// The time it took to seal and commit the block.
+    pub duration: Duration,
 }
 
 #[derive(Debug, Clone)]
@@ -79,6 +88,8 @@ pub struct TxWithOutcome {
     pub tx: TxWithHash,
     pub receipt: Receipt,
     pub exec_info: TxExecInfo,
+    /// The state changes made by the transaction.
+    pub states: StateUpdates,
 }
 
 type ServiceFuture<T> = Pin<Box<dyn Future<Output = BlockingTaskResult<T>> + Send + Sync>>;
@@ -110,7 +121,10 @@ impl<EF: ExecutorFactory> BlockProducer<EF> {
     }
 
     /// Creates a new block producer that will only be possible to mine by calling the
-    /// `katana_generateBlock` RPC method.
+    /// `dev_mine` (or `dev_generateBlock`) RPC method.
+    ///
+    /// Transactions are still executed as soon as they are received, and their results are kept in
+    /// the pending block until it is explicitly sealed.
     pub fn on_demand(backend: Arc<Backend<EF>>) -> Self {
         let producer = IntervalBlockProducer::new(backend, None);
         let producer = Arc::new(RwLock::new(BlockProducerMode::Interval(producer)));
@@ -151,13 +165,153 @@ impl<EF: ExecutorFactory> BlockProducer<EF> {
         matches!(*self.producer.read(), BlockProducerMode::Instant(_))
     }
 
-    // Handler for the `katana_generateBlock` RPC method.
-    pub fn force_mine(&self) {
+    /// Handler for the `dev_mine` and `dev_generateBlock` RPC methods.
+    ///
+    /// Returns `None` if the block couldn't be mined because there is already an ongoing mining
+    /// process.
+    pub fn force_mine(&self) -> Result<Option<MinedBlockOutcome>, BlockProductionError> {
         trace!(target: LOG_TARGET, "Scheduling force block mining.");
         let mut mode = self.producer.write();
         match &mut *mode {
             BlockProducerMode::Instant(producer) => producer.force_mine(),
-            BlockProducerMode::Interval(producer) => producer.force_mine(),
+            BlockProducerMode::Interval(producer) => producer.force_mine().map(Some),
+        }
+    }
+
+    /// Handler for the `dev_executeBatch` RPC method.
+    ///
+    /// Executes `transactions` in order and mines them in a block of their own, so that they can't
+    /// be interleaved with transactions from other clients. The block is only mined if all the
+    /// transactions are executed successfully, otherwise none of them are committed. On _interval_
+    /// mining, the pending block is sealed first if it contains any transactions.
+    ///
+    /// Returns `None` if the batch couldn't be executed because a block is currently being
+    /// produced.
+    pub fn execute_batch(
+        &self,
+        transactions: Vec<ExecutableTxWithHash>,
+    ) -> Result<Option<(MinedBlockOutcome, Vec<TxWithOutcome>)>, BlockProductionError> {
+        trace!(target: LOG_TARGET, total = %transactions.len(), "Executing transactions batch.");
+        let mut mode = self.producer.write();
+        match &mut *mode {
+            BlockProducerMode::Instant(producer) => producer.execute_batch(transactions),
+            BlockProducerMode::Interval(producer) => producer.execute_batch(transactions),
+        }
+    }
+
+    /// Applies the storage, nonce and class hash changes in `updates` without going through
+    /// transaction execution.
+    ///
+    /// On _interval_ mining, the changes are applied on the pending state and will be part of the
+    /// state updates of the next mined block. On _instant_ mining, a new block with no
+    /// transactions is mined, with `updates` as its state updates.
+    pub fn override_state(&self, updates: StateUpdates) -> Result<(), BlockProductionError> {
+        let mode = self.producer.read();
+        match &*mode {
+            BlockProducerMode::Instant(producer) => producer.override_state(updates),
+            BlockProducerMode::Interval(producer) => producer.override_state(updates),
+        }
+    }
+
+    /// Sets the value of a contract storage slot without going through transaction execution.
+    ///
+    /// The change is applied the same way as [`BlockProducer::override_state`].
+    pub fn set_storage_at(
+        &self,
+        address: ContractAddress,
+        key: StorageKey,
+        value: StorageValue,
+    ) -> Result<(), BlockProductionError> {
+        let mut updates = StateUpdates::default();
+        updates.storage_updates.insert(address, BTreeMap::from([(key, value)]));
+        self.override_state(updates)
+    }
+
+    /// Sets the nonce of a contract without going through transaction execution.
+    ///
+    /// The change is applied the same way as [`BlockProducer::override_state`].
+    pub fn set_nonce(
+        &self,
+        address: ContractAddress,
+        nonce: Nonce,
+    ) -> Result<(), BlockProductionError> {
+        let mut updates = StateUpdates::default();
+        updates.nonce_updates.insert(address, nonce);
+        self.override_state(updates)
+    }
+
+    /// Sets the class hash of a contract without going through transaction execution.
+    ///
+    /// The change is applied the same way as [`BlockProducer::override_state`].
+    pub fn set_class_hash_at(
+        &self,
+        address: ContractAddress,
+        class_hash: ClassHash,
+    ) -> Result<(), BlockProductionError> {
+        let mut updates = StateUpdates::default();
+        updates.replaced_classes.insert(address, class_hash);
+        self.override_state(updates)
+    }
+
+    /// Handler for the `dev_revertToBlock` RPC method.
+    ///
+    /// Reverts the chain to `block_number`, discarding all the blocks after it as well as the
+    /// pending block, if any. Returns `false` if the chain couldn't be reverted because a block is
+    /// currently being produced.
+    pub fn revert_to(&self, block_number: BlockNumber) -> Result<bool, BlockProductionError> {
+        trace!(target: LOG_TARGET, %block_number, "Reverting chain.");
+        let mut mode = self.producer.write();
+        match &mut *mode {
+            BlockProducerMode::Instant(producer) => producer.revert_to(block_number),
+            BlockProducerMode::Interval(producer) => producer.revert_to(block_number),
+        }
+    }
+
+    /// Returns `true` if there are no queued transactions, and no transactions being executed or
+    /// block being mined.
+    pub fn is_idle(&self) -> bool {
+        let mode = self.producer.read();
+        match &*mode {
+            BlockProducerMode::Instant(pd) => pd.queued.is_empty() && pd.block_mining.is_none(),
+            BlockProducerMode::Interval(pd) => {
+                pd.queued.is_empty()
+                    && pd.ongoing_execution.is_none()
+                    && pd.ongoing_mining.is_none()
+            }
+        }
+    }
+
+    /// Returns the hashes of the transactions that have been executed and are part of the pending
+    /// block.
+    ///
+    /// Always empty on _instant_ mining, as transactions are mined as soon as they are executed.
+    pub fn pending_transaction_hashes(&self) -> Vec<TxHash> {
+        let mode = self.producer.read();
+        match &*mode {
+            BlockProducerMode::Instant(_) => Vec::new(),
+            BlockProducerMode::Interval(pd) => {
+                pd.executor.read().transactions().iter().map(|(tx, _)| tx.hash).collect()
+            }
+        }
+    }
+
+    /// Seals the pending block if it contains any transactions, ie when shutting down the node so
+    /// that the executed transactions aren't lost.
+    ///
+    /// Returns `None` if there was nothing to seal, or if a block is already being mined. This is
+    /// always the case on _instant_ mining.
+    pub fn seal_pending_block(&self) -> Result<Option<MinedBlockOutcome>, BlockProductionError> {
+        let mut mode = self.producer.write();
+        match &mut *mode {
+            BlockProducerMode::Instant(_) => Ok(None),
+            BlockProducerMode::Interval(producer) => {
+                let is_empty = producer.executor.read().transactions().is_empty();
+                if is_empty || producer.ongoing_mining.is_some() {
+                    return Ok(None);
+                }
+
+                producer.force_mine().map(Some)
+            }
         }
     }
 
@@ -176,6 +330,57 @@ impl<EF: ExecutorFactory> Clone for BlockProducer<EF> {
     }
 }
 
+/// Executes a batch of transactions using `executor`, failing at the first transaction that isn't
+/// executed successfully. Reverted transactions are considered failed.
+fn execute_batch_with(
+    executor: &mut dyn BlockExecutor<'_>,
+    transactions: Vec<ExecutableTxWithHash>,
+) -> Result<Vec<TxWithOutcome>, BlockProductionError> {
+    let total = transactions.len();
+    let (total_executed, _) = executor.execute_transactions(transactions)?;
+
+    let txs = executor.transactions();
+    let results = &txs[txs.len().saturating_sub(total_executed)..];
+
+    let mut outcomes = Vec::with_capacity(total);
+    for (index, (tx, res)) in results.iter().enumerate() {
+        match res {
+            ExecutionResult::Failed { error } => {
+                return Err(BlockProductionError::BatchTransactionFailed {
+                    index,
+                    reason: error.to_string(),
+                });
+            }
+
+            ExecutionResult::Success { receipt, trace, states } => {
+                if let Some(reason) = receipt.revert_reason() {
+                    return Err(BlockProductionError::BatchTransactionFailed {
+                        index,
+                        reason: reason.to_string(),
+                    });
+                }
+
+                outcomes.push(TxWithOutcome {
+                    tx: tx.clone(),
+                    receipt: receipt.clone(),
+                    exec_info: trace.clone(),
+                    states: states.clone(),
+                });
+            }
+        }
+    }
+
+    // the whole batch must fit in a single block
+    if total_executed < total {
+        return Err(BlockProductionError::BatchTransactionFailed {
+            index: total_executed,
+            reason: "block limits exhausted".to_string(),
+        });
+    }
+
+    Ok(outcomes)
+}
+
 /// The inner type of [BlockProducer].
 ///
 /// On _interval_ mining, a new block is opened for a fixed amount of interval. Within this
@@ -286,8 +491,29 @@ impl<EF: ExecutorFactory> IntervalBlockProducer<EF> {
         self.executor.clone()
     }
 
+    fn override_state(&self, updates: StateUpdates) -> Result<(), BlockProductionError> {
+        // the validator shares the same state as the executor, so it doesn't need to be updated
+        let mut executor = self.executor.write();
+
+        for (address, storage) in updates.storage_updates {
+            for (key, value) in storage {
+                executor.set_storage_at(address, key, value)?;
+            }
+        }
+
+        for (address, nonce) in updates.nonce_updates {
+            executor.set_nonce(address, nonce)?;
+        }
+
+        for (address, class_hash) in updates.replaced_classes {
+            executor.set_class_hash_at(address, class_hash)?;
+        }
+
+        Ok(())
+    }
+
     /// Force mine a new block. It will only able to mine if there is no ongoing mining process.
-    pub fn force_mine(&mut self) {
+    pub fn force_mine(&mut self) -> Result<MinedBlockOutcome, BlockProductionError> {
         match Self::do_mine(self.permit.clone(), self.executor.clone(), self.backend.clone()) {
             Ok(outcome) => {
                 info!(target: LOG_TARGET, block_number = %outcome.block_number, "Force mined block.");
@@ -306,13 +532,75 @@ impl<EF: ExecutorFactory> IntervalBlockProducer<EF> {
                 // -------------------------------------------
 
                 unsafe { self.permit.raw().unlock() };
+
+                Ok(outcome)
             }
             Err(e) => {
+                // the permit is always acquired by `do_mine`, so it must be released even if the
+                // mining failed, otherwise the producer won't be able to mine any further blocks.
+                unsafe { self.permit.raw().unlock() };
                 error!(target: LOG_TARGET, error = %e, "On force mine.");
+                Err(e)
             }
         }
     }
 
+    fn execute_batch(
+        &mut self,
+        transactions: Vec<ExecutableTxWithHash>,
+    ) -> Result<Option<(MinedBlockOutcome, Vec<TxWithOutcome>)>, BlockProductionError> {
+        if self.ongoing_mining.is_some() || self.ongoing_execution.is_some() {
+            trace!(target: LOG_TARGET, "Unable to execute batch while a block is being produced.");
+            return Ok(None);
+        }
+
+        if !self.executor.read().transactions().is_empty() {
+            self.force_mine()?;
+        }
+
+        // The transactions executed by the pending executor can't be rolled back, so the batch is
+        // first executed on top of the pending state to make sure that all of it succeeds.
+        let (state, block_env) = {
+            let executor = self.executor.read();
+            (executor.state(), executor.block_env())
+        };
+        let mut executor = self.backend.executor_factory.with_state_and_block_env(state, block_env);
+        execute_batch_with(executor.as_mut(), transactions.clone())?;
+
+        let txs = execute_batch_with(self.executor.write().as_mut(), transactions)?;
+        let outcome = self.force_mine()?;
+
+        self.backend.executed_tx_notifier.notify(&txs);
+        self.notify_listener(txs.clone());
+
+        Ok(Some((outcome, txs)))
+    }
+
+    fn revert_to(&mut self, block_number: BlockNumber) -> Result<bool, BlockProductionError> {
+        if self.ongoing_mining.is_some() || self.ongoing_execution.is_some() {
+            trace!(target: LOG_TARGET, "Unable to revert while a block is being produced.");
+            return Ok(false);
+        }
+
+        let _permit = self.permit.lock();
+
+        let provider = self.backend.blockchain.provider();
+        provider.unwind_to(block_number)?;
+
+        // discard the pending block as it was built on top of the reverted blocks
+        self.executor = self.create_new_executor_for_next_block()?;
+        self.timer = None;
+        self.is_block_full = false;
+
+        let state = self.executor.0.read().state();
+        let block_env = provider.block_env_at(block_number.into())?.expect("latest");
+        self.validator.reset(state, block_env);
+
+        info!(target: LOG_TARGET, %block_number, "Reverted chain.");
+
+        Ok(true)
+    }
+
     fn do_mine(
         permit: Arc<Mutex<()>>,
         executor: PendingExecutor,
@@ -349,10 +637,11 @@ impl<EF: ExecutorFactory> IntervalBlockProducer<EF> {
             .skip(total_txs.saturating_sub(total_executed))
             .filter_map(|(tx, res)| match res {
                 ExecutionResult::Failed { .. } => None,
-                ExecutionResult::Success { receipt, trace, .. } => Some(TxWithOutcome {
+                ExecutionResult::Success { receipt, trace, states } => Some(TxWithOutcome {
                     tx: tx.clone(),
                     receipt: receipt.clone(),
                     exec_info: trace.clone(),
+                    states: states.clone(),
                 }),
             })
             .collect::<Vec<TxWithOutcome>>();
@@ -488,6 +777,8 @@ impl<EF: ExecutorFactory> Stream for IntervalBlockProducer<EF> {
                 if let Poll::Ready(executor) = execution.poll_unpin(cx) {
                     match executor {
                         Ok(Ok((txs, leftovers))) => {
+                            pin.backend.executed_tx_notifier.notify(&txs);
+
                             if let Some(leftovers) = leftovers {
                                 pin.is_block_full = true;
 
@@ -616,18 +907,107 @@ impl<EF: ExecutorFactory> InstantBlockProducer<EF> {
         }
     }
 
-    pub fn force_mine(&mut self) {
+    pub fn force_mine(&mut self) -> Result<Option<MinedBlockOutcome>, BlockProductionError> {
         if self.block_mining.is_none() {
             let txs = std::mem::take(&mut self.queued);
-            let _ = Self::do_mine(
+            let (outcome, _) = Self::do_mine(
                 self.validator.clone(),
                 self.permit.clone(),
                 self.backend.clone(),
                 txs,
-            );
+            )?;
+            Ok(Some(outcome))
         } else {
-            trace!(target: LOG_TARGET, "Unable to force mine while a mining process is running.")
+            trace!(target: LOG_TARGET, "Unable to force mine while a mining process is running.");
+            Ok(None)
+        }
+    }
+
+    /// Mines a new block without any transactions, whose state updates are `updates`.
+    fn override_state(&self, updates: StateUpdates) -> Result<(), BlockProductionError> {
+        // prevent the block from being mined in the middle of another one
+        let _permit = self.permit.lock();
+
+        let backend = &self.backend;
+        let provider = backend.blockchain.provider();
+
+        let latest_num = provider.latest_number()?;
+        let mut block_env = provider.block_env_at(latest_num.into())?.expect("latest");
+        backend.update_block_env(&mut block_env);
+
+        let states = StateUpdatesWithClasses { state_updates: updates, ..Default::default() };
+        let output = ExecutionOutput { states, ..Default::default() };
+        let outcome = backend.do_mine_block(&block_env, output)?;
+
+        // the validator holds a snapshot of the latest state, so it must be refreshed to see the
+        // new values
+        let state = provider.latest()?;
+        let block_env = provider.block_env_at(outcome.block_number.into())?.expect("latest");
+        self.validator.update(state, block_env);
+
+        info!(target: LOG_TARGET, block_number = %outcome.block_number, "Mined block with state overrides.");
+
+        Ok(())
+    }
+
+    fn execute_batch(
+        &mut self,
+        transactions: Vec<ExecutableTxWithHash>,
+    ) -> Result<Option<(MinedBlockOutcome, Vec<TxWithOutcome>)>, BlockProductionError> {
+        if self.block_mining.is_some() {
+            trace!(target: LOG_TARGET, "Unable to execute batch while a block is being mined.");
+            return Ok(None);
+        }
+
+        let (outcome, txs) = {
+            let _permit = self.permit.lock();
+
+            let backend = &self.backend;
+            let provider = backend.blockchain.provider();
+
+            let latest_num = provider.latest_number()?;
+            let mut block_env = provider.block_env_at(latest_num.into())?.expect("latest");
+            backend.update_block_env(&mut block_env);
+
+            let state = provider.latest()?;
+            let mut executor =
+                backend.executor_factory.with_state_and_block_env(state, block_env.clone());
+
+            let txs = execute_batch_with(executor.as_mut(), transactions)?;
+            let outcome = backend.do_mine_block(&block_env, executor.take_execution_output()?)?;
+
+            let state = provider.latest()?;
+            let latest_num = provider.latest_number()?;
+            let block_env = provider.block_env_at(latest_num.into())?.expect("latest");
+            self.validator.update(state, block_env);
+
+            (outcome, txs)
+        };
+
+        self.backend.executed_tx_notifier.notify(&txs);
+        self.notify_listener(txs.clone());
+
+        Ok(Some((outcome, txs)))
+    }
+
+    fn revert_to(&mut self, block_number: BlockNumber) -> Result<bool, BlockProductionError> {
+        if self.block_mining.is_some() {
+            trace!(target: LOG_TARGET, "Unable to revert while a block is being mined.");
+            return Ok(false);
         }
+
+        let _permit = self.permit.lock();
+
+        let provider = self.backend.blockchain.provider();
+        provider.unwind_to(block_number)?;
+
+        let state = provider.latest()?;
+        let block_env = provider.block_env_at(block_number.into())?.expect("latest");
+        self.validator.reset(state, block_env);
+
+        info!(target: LOG_TARGET, %block_number, "Reverted chain.");
+
+        Ok(true)
     }
 
     fn do_mine(
@@ -661,7 +1041,7 @@ impl<EF: ExecutorFactory> InstantBlockProducer<EF> {
                 parent_hash,
                 number: block_env.number,
                 timestamp: block_env.timestamp,
-                protocol_version: CURRENT_STARKNET_VERSION,
+                protocol_version: backend.chain_spec.protocol_version(),
                 sequencer_address: block_env.sequencer_address,
                 l1_da_mode: L1DataAvailabilityMode::Calldata,
                 l1_gas_prices: block_env.l1_gas_prices.clone(),
@@ -677,8 +1057,8 @@ impl<EF: ExecutorFactory> InstantBlockProducer<EF> {
             .clone()
             .into_iter()
             .filter_map(|(tx, res)| match res {
-                ExecutionResult::Success { receipt, trace, .. } => {
-                    Some(TxWithOutcome { tx, receipt, exec_info: trace })
+                ExecutionResult::Success { receipt, trace, states } => {
+                    Some(TxWithOutcome { tx, receipt, exec_info: trace, states })
                 }
                 _ => None,
             })
@@ -760,6 +1140,7 @@ impl<EF: ExecutorFactory> Stream for InstantBlockProducer<EF> {
             if let Poll::Ready(outcome) = mining.poll_unpin(cx) {
                 match outcome {
                     Ok(Ok((outcome, txs))) => {
+                        pin.backend.executed_tx_notifier.notify(&txs);
                         pin.notify_listener(txs);
                         return Poll::Ready(Some(Ok(outcome)));
                     }
@@ -36,12 +36,40 @@ async fn interval_force_mine_without_transactions() {
     let backend = test_backend();
 
     let mut producer = IntervalBlockProducer::new(backend.clone(), None);
-    producer.force_mine();
+    let outcome = producer.force_mine().unwrap();
+    assert_eq!(outcome.block_number, 1);
 
     let latest_num = backend.blockchain.provider().latest_number().unwrap();
     assert_eq!(latest_num, 1);
 }
 
+#[tokio::test]
+async fn on_demand_force_mine() {
+    let backend = test_backend();
+    let producer = BlockProducer::on_demand(backend.clone());
+
+    for expected in 1..=2 {
+        let outcome = producer.force_mine().unwrap().expect("should mine block");
+        assert_eq!(outcome.block_number, expected);
+        assert_eq!(backend.blockchain.provider().latest_number().unwrap(), expected);
+    }
+}
+
+#[tokio::test]
+async fn seal_empty_pending_block() {
+    let backend = test_backend();
+    let producer = BlockProducer::on_demand(backend.clone());
+    assert!(producer.is_idle());
+
+    // nothing has been executed, so there is nothing to seal
+    assert!(producer.seal_pending_block().unwrap().is_none());
+    assert_eq!(backend.blockchain.provider().latest_number().unwrap(), 0);
+
+    let producer = BlockProducer::instant(backend.clone());
+    assert!(producer.is_idle());
+    assert!(producer.seal_pending_block().unwrap().is_none());
+}
+
 #[tokio::test]
 async fn interval_mine_after_timer() {
     let backend = test_backend();
@@ -1,5 +1,5 @@
 use dojo_metrics::Metrics;
-use metrics::Counter;
+use metrics::{Counter, Histogram};
 
 #[derive(Metrics)]
 #[metrics(scope = "block_producer")]
@@ -8,4 +8,10 @@ pub(crate) struct BlockProducerMetrics {
     pub(crate) l1_gas_processed_total: Counter,
     /// The amount of Cairo steps processed in a block.
     pub(crate) cairo_steps_processed_total: Counter,
+    /// The number of blocks mined.
+    pub(crate) blocks_mined_total: Counter,
+    /// The number of transactions included in the mined blocks.
+    pub(crate) transactions_mined_total: Counter,
+    /// The time it took to seal and commit a block, in seconds.
+    pub(crate) block_production_seconds: Histogram,
 }
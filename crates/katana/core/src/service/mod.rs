@@ -16,6 +16,8 @@ use self::metrics::BlockProducerMetrics;
 
 pub mod block_producer;
 mod metrics;
+pub mod pruner;
+pub mod settlement;
 
 pub(crate) const LOG_TARGET: &str = "node";
 
@@ -77,6 +79,11 @@ where
                         let steps_used = outcome.stats.cairo_steps_used;
                         this.metrics.l1_gas_processed_total.increment(gas_used as u64);
                         this.metrics.cairo_steps_processed_total.increment(steps_used as u64);
+                        this.metrics.blocks_mined_total.increment(1);
+                        this.metrics.transactions_mined_total.increment(outcome.txs.len() as u64);
+                        this.metrics
+                            .block_production_seconds
+                            .record(outcome.duration.as_secs_f64());
 
                         // remove mined transactions from the pool
                         this.pool.remove_transactions(&outcome.txs);
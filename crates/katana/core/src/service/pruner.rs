@@ -0,0 +1,96 @@
+//! Pruning of the historical data of the chain.
+//!
+//! The [`Pruner`] removes the receipts, execution traces and state diffs of the blocks that are
+//! older than a configured distance from the tip of the chain, as new blocks are mined. Headers,
+//! transactions and the latest state are always kept.
+
+use std::sync::Arc;
+
+use katana_primitives::block::BlockNumber;
+use katana_provider::traits::prune::PruneWriter;
+use katana_provider::ProviderResult;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
+use tracing::{error, trace};
+
+use crate::backend::notification::NewBlock;
+
+const LOG_TARGET: &str = "pruner";
+
+/// Removes the historical data of the chain that is older than the configured distances.
+#[derive(Debug)]
+pub struct Pruner<P> {
+    provider: P,
+    /// The number of most recent blocks whose receipts are kept.
+    receipts_distance: Option<u64>,
+    /// The number of most recent blocks whose execution traces are kept.
+    traces_distance: Option<u64>,
+    /// The number of most recent blocks whose state diffs are kept.
+    state_diffs_distance: Option<u64>,
+}
+
+impl<P: PruneWriter> Pruner<P> {
+    /// Creates a new pruner that doesn't prune anything until a distance is configured.
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            receipts_distance: None,
+            traces_distance: None,
+            state_diffs_distance: None,
+        }
+    }
+
+    /// Only keep the receipts of the latest `distance` blocks.
+    pub fn receipts(mut self, distance: u64) -> Self {
+        self.receipts_distance = Some(distance);
+        self
+    }
+
+    /// Only keep the execution traces of the latest `distance` blocks.
+    pub fn traces(mut self, distance: u64) -> Self {
+        self.traces_distance = Some(distance);
+        self
+    }
+
+    /// Only keep the state diffs of the latest `distance` blocks.
+    pub fn state_diffs(mut self, distance: u64) -> Self {
+        self.state_diffs_distance = Some(distance);
+        self
+    }
+
+    /// Prunes the data that is out of the configured distances, relative to the `tip` block.
+    pub fn prune(&self, tip: BlockNumber) -> ProviderResult<()> {
+        if let Some(block) = self.receipts_distance.and_then(|d| tip.checked_sub(d)) {
+            trace!(target: LOG_TARGET, %block, "Pruning receipts.");
+            self.provider.prune_receipts(block)?;
+        }
+
+        if let Some(block) = self.traces_distance.and_then(|d| tip.checked_sub(d)) {
+            trace!(target: LOG_TARGET, %block, "Pruning traces.");
+            self.provider.prune_traces(block)?;
+        }
+
+        if let Some(block) = self.state_diffs_distance.and_then(|d| tip.checked_sub(d)) {
+            trace!(target: LOG_TARGET, %block, "Pruning state diffs.");
+            self.provider.prune_state_diffs(block)?;
+        }
+
+        Ok(())
+    }
+
+    /// Prunes the chain every time a new block is mined, until the `blocks` channel is closed.
+    pub async fn run(self, mut blocks: Receiver<Arc<NewBlock>>) {
+        loop {
+            let tip = match blocks.recv().await {
+                Ok(block) => block.header.number,
+                // pruning relative to the next received block also covers the missed ones
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break,
+            };
+
+            if let Err(error) = self.prune(tip) {
+                error!(target: LOG_TARGET, %error, "Pruning historical data.");
+            }
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Tracking of the blocks settled on the settlement layer.
+//!
+//! The [`SettlementTracker`] periodically queries the core contract of the settlement layer for
+//! the latest settled block, and marks the local blocks up to it as accepted on L1. The
+//! transactions of those blocks are then reported as `ACCEPTED_ON_L1`.
+
+use std::time::Duration;
+
+use alloy_provider::ProviderBuilder;
+use alloy_sol_types::sol;
+use anyhow::Context;
+use katana_chain_spec::SettlementLayer;
+use katana_primitives::block::{BlockNumber, FinalityStatus};
+use katana_primitives::{eth, ContractAddress};
+use katana_provider::traits::block::{BlockNumberProvider, BlockStatusProvider, BlockStatusWriter};
+use katana_provider::ProviderResult;
+use starknet::core::types::{BlockId, BlockTag, FunctionCall};
+use starknet::macros::selector;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Provider};
+use tracing::{error, info};
+use url::Url;
+
+const LOG_TARGET: &str = "settlement";
+
+/// The default interval at which the settlement layer is queried.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+sol! {
+    #[sol(rpc)]
+    interface IStarknetCore {
+        function stateBlockNumber() external view returns (int256);
+    }
+}
+
+/// The core contract on which the blocks of the chain are settled.
+#[derive(Debug, Clone)]
+pub enum SettlementContract {
+    Ethereum { rpc_url: Url, address: eth::Address },
+    Starknet { rpc_url: Url, address: ContractAddress },
+}
+
+impl SettlementContract {
+    /// Returns the core contract of the settlement layer, or `None` if the chain is sovereign.
+    pub fn from_settlement_layer(layer: &SettlementLayer) -> Option<Self> {
+        match layer {
+            SettlementLayer::Ethereum { rpc_url, core_contract, .. } => {
+                Some(Self::Ethereum { rpc_url: rpc_url.clone(), address: *core_contract })
+            }
+            SettlementLayer::Starknet { rpc_url, core_contract, .. } => {
+                Some(Self::Starknet { rpc_url: rpc_url.clone(), address: *core_contract })
+            }
+            SettlementLayer::Sovereign { .. } => None,
+        }
+    }
+
+    /// Returns the latest block settled on the core contract, or `None` if no block has been
+    /// settled yet.
+    pub async fn settled_block(&self) -> anyhow::Result<Option<BlockNumber>> {
+        match self {
+            Self::Ethereum { rpc_url, address } => {
+                let provider = ProviderBuilder::new().on_http(rpc_url.clone());
+                let contract = IStarknetCore::new(*address, provider);
+                let block = contract.stateBlockNumber().call().await?._0;
+                // the block number is negative until the first state update
+                Ok(u64::try_from(block).ok())
+            }
+
+            Self::Starknet { rpc_url, address } => {
+                let provider = JsonRpcClient::new(HttpTransport::new(rpc_url.clone()));
+                let call = FunctionCall {
+                    contract_address: (*address).into(),
+                    entry_point_selector: selector!("get_state"),
+                    calldata: Vec::new(),
+                };
+
+                // the state is made of the state root, the block number and the block hash, with
+                // the block number being out of range until the first state update
+                let state = provider.call(call, BlockId::Tag(BlockTag::Latest)).await?;
+                let block = state.get(1).context("Invalid state of the core contract.")?;
+                Ok(u64::try_from(*block).ok())
+            }
+        }
+    }
+}
+
+/// Marks the blocks settled on the settlement layer as accepted on L1.
+#[derive(Debug)]
+pub struct SettlementTracker<P> {
+    provider: P,
+    contract: SettlementContract,
+    poll_interval: Duration,
+}
+
+impl<P> SettlementTracker<P>
+where
+    P: BlockNumberProvider + BlockStatusProvider + BlockStatusWriter,
+{
+    pub fn new(provider: P, contract: SettlementContract) -> Self {
+        Self { provider, contract, poll_interval: DEFAULT_POLL_INTERVAL }
+    }
+
+    /// Sets the interval at which the settlement layer is queried.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Marks the blocks up to `settled` as accepted on L1, and returns how many blocks have been
+    /// marked.
+    ///
+    /// Blocks are settled in order, so the blocks are walked backward from `settled` until one
+    /// that is already accepted on L1 is found.
+    pub fn mark_settled(&self, settled: BlockNumber) -> ProviderResult<u64> {
+        // the settlement layer can't be ahead of the local chain, unless the local chain has been
+        // reset
+        let mut block = settled.min(self.provider.latest_number()?);
+        let mut marked = 0;
+
+        while let Some(FinalityStatus::AcceptedOnL2) = self.provider.block_status(block.into())? {
+            self.provider.update_block_status(block, FinalityStatus::AcceptedOnL1)?;
+            marked += 1;
+
+            let Some(previous) = block.checked_sub(1) else { break };
+            block = previous;
+        }
+
+        Ok(marked)
+    }
+
+    /// Queries the settlement layer every poll interval, marking the newly settled blocks.
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(self.poll_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            interval.tick().await;
+
+            // the settlement layer being temporarily unreachable must not bring the node down
+            let settled = match self.contract.settled_block().await {
+                Ok(Some(block)) => block,
+                Ok(None) => continue,
+                Err(error) => {
+                    error!(target: LOG_TARGET, %error, "Fetching the settled block.");
+                    continue;
+                }
+            };
+
+            match self.mark_settled(settled) {
+                Ok(0) => {}
+                Ok(marked) => {
+                    info!(target: LOG_TARGET, block = %settled, %marked, "Blocks accepted on L1.")
+                }
+                Err(error) => error!(target: LOG_TARGET, %error, "Marking the settled blocks."),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use katana_provider::test_utils::provider_with_blocks;
+
+    use super::*;
+
+    #[test]
+    fn mark_settled_blocks() {
+        let provider = provider_with_blocks(5);
+        // only the genesis block is accepted on L1 initially
+        provider.update_block_status(0, FinalityStatus::AcceptedOnL1).unwrap();
+
+        let contract = SettlementContract::Starknet {
+            rpc_url: Url::parse("http://localhost:5050").unwrap(),
+            address: ContractAddress::default(),
+        };
+        let tracker = SettlementTracker::new(provider, contract);
+
+        assert_eq!(tracker.mark_settled(2).unwrap(), 2);
+        assert_eq!(tracker.mark_settled(2).unwrap(), 0);
+
+        let status = |num: BlockNumber| tracker.provider.block_status(num.into()).unwrap();
+        assert_eq!(status(2), Some(FinalityStatus::AcceptedOnL1));
+        assert_eq!(status(3), Some(FinalityStatus::AcceptedOnL2));
+
+        // blocks that don't exist locally are ignored
+        assert_eq!(tracker.mark_settled(10).unwrap(), 2);
+        assert_eq!(status(4), Some(FinalityStatus::AcceptedOnL1));
+    }
+}
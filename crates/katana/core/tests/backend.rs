@@ -1,18 +1,24 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use alloy_primitives::U256;
 use katana_chain_spec::rollup::{self, FeeContract};
 use katana_chain_spec::{dev, ChainSpec, SettlementLayer};
 use katana_core::backend::gas_oracle::GasOracle;
 use katana_core::backend::storage::{Blockchain, Database};
 use katana_core::backend::Backend;
+use katana_core::constants::DETERMINISTIC_BLOCK_TIME;
 use katana_executor::implementation::blockifier::BlockifierFactory;
 use katana_executor::BlockLimits;
+use katana_primitives::block::GasPrices;
 use katana_primitives::chain::ChainId;
-use katana_primitives::env::CfgEnv;
+use katana_primitives::env::{BlockEnv, CfgEnv};
 use katana_primitives::felt;
 use katana_primitives::genesis::allocation::DevAllocationsGenerator;
 use katana_primitives::genesis::constant::DEFAULT_PREFUNDED_ACCOUNT_BALANCE;
 use katana_primitives::genesis::Genesis;
 use katana_provider::providers::db::DbProvider;
+use katana_provider::traits::block::{BlockNumberProvider, HeaderProvider};
+use katana_provider::traits::env::BlockEnvProvider;
 use rstest::rstest;
 use url::Url;
 
@@ -109,3 +115,78 @@ fn reinitialize_with_different_rollup_chain_spec() {
     let err = backend2.init_genesis().unwrap_err().to_string();
     assert!(err.as_str().contains("Genesis block hash mismatch"));
 }
+
+#[test]
+fn resume_from_existing_database() {
+    let db = DbProvider::new_ephemeral();
+    let chain = ChainSpec::Dev(dev_chain_spec());
+
+    let gas_prices = GasPrices { eth: 100, strk: 200 };
+    let data_gas_prices = GasPrices { eth: 300, strk: 400 };
+
+    let backend = Backend::new(
+        chain.clone().into(),
+        Blockchain::new(db.clone()),
+        GasOracle::fixed(gas_prices.clone(), data_gas_prices.clone()),
+        executor(&chain),
+    );
+    backend.init_genesis().expect("failed to initialize genesis");
+
+    // mine a block that is way ahead of the current time
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    let timestamp = now.as_secs() + 1_000_000;
+    let mut block_env = BlockEnv::default();
+    backend.update_block_env(&mut block_env);
+    block_env.timestamp = timestamp;
+    backend.mine_empty_block(&block_env).unwrap();
+
+    // resume the chain with an oracle that hasn't sampled any prices yet
+    let url = Url::parse("http://localhost:8545").unwrap();
+    let backend = Backend::new(
+        chain.clone().into(),
+        Blockchain::new(db),
+        GasOracle::sampled_ethereum(url),
+        executor(&chain),
+    );
+    backend.init_genesis().expect("failed to resume chain");
+
+    assert_eq!(backend.gas_oracle.current_gas_prices(), gas_prices);
+    assert_eq!(backend.gas_oracle.current_data_gas_prices(), data_gas_prices);
+
+    let mut block_env = BlockEnv { number: 1, ..Default::default() };
+    backend.update_block_env(&mut block_env);
+
+    assert_eq!(block_env.number, 2);
+    assert!(block_env.timestamp >= timestamp);
+}
+
+#[test]
+fn deterministic_block_timestamps() {
+    let chain = ChainSpec::Dev(dev_chain_spec());
+    let genesis_timestamp = chain.genesis().timestamp;
+
+    let mine_blocks = || {
+        let backend = backend(&chain);
+        backend.block_context_generator.write().fixed_block_time = Some(DETERMINISTIC_BLOCK_TIME);
+        backend.init_genesis().expect("failed to initialize genesis");
+
+        let provider = backend.blockchain.provider();
+        for _ in 0..3 {
+            let latest = provider.latest_number().unwrap();
+            let mut block_env = provider.block_env_at(latest.into()).unwrap().unwrap();
+            backend.update_block_env(&mut block_env);
+            backend.mine_empty_block(&block_env).unwrap();
+        }
+
+        (1..=3).map(|num| provider.header(num.into()).unwrap().unwrap()).collect::<Vec<_>>()
+    };
+
+    let headers = mine_blocks();
+    for (i, header) in headers.iter().enumerate() {
+        let expected = genesis_timestamp + (i as u64 + 1) * DETERMINISTIC_BLOCK_TIME;
+        assert_eq!(header.timestamp, expected);
+    }
+
+    // running the chain again must produce the exact same blocks
+    assert_eq!(mine_blocks(), headers);
+}
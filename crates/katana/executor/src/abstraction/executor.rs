@@ -1,4 +1,6 @@
 use katana_primitives::block::ExecutableBlock;
+use katana_primitives::class::ClassHash;
+use katana_primitives::contract::{ContractAddress, Nonce, StorageKey, StorageValue};
 use katana_primitives::env::{BlockEnv, CfgEnv};
 use katana_primitives::fee::TxFeeInfo;
 use katana_primitives::transaction::{ExecutableTxWithHash, TxWithHash};
@@ -56,6 +58,33 @@ pub trait BlockExecutor<'a>: ExecutorExt + Send + Sync + core::fmt::Debug {
 
     /// Returns the current block environment of the executor.
     fn block_env(&self) -> BlockEnv;
+
+    /// Sets the value of a contract storage slot without executing any transaction.
+    ///
+    /// The change is applied on the executor's state and is included in the state updates of its
+    /// execution output.
+    fn set_storage_at(
+        &mut self,
+        address: ContractAddress,
+        key: StorageKey,
+        value: StorageValue,
+    ) -> ExecutorResult<()>;
+
+    /// Sets the nonce of a contract without executing any transaction.
+    ///
+    /// The change is applied on the executor's state and is included in the state updates of its
+    /// execution output.
+    fn set_nonce(&mut self, address: ContractAddress, nonce: Nonce) -> ExecutorResult<()>;
+
+    /// Sets the class hash of a contract without executing any transaction.
+    ///
+    /// The change is applied on the executor's state and is included in the state updates of its
+    /// execution output.
+    fn set_class_hash_at(
+        &mut self,
+        address: ContractAddress,
+        class_hash: ClassHash,
+    ) -> ExecutorResult<()>;
 }
 
 pub trait ExecutorExt {
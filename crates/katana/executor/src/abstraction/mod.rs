@@ -1,11 +1,16 @@
 mod executor;
 
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
 pub use executor::*;
 use katana_primitives::receipt::Receipt;
 use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
 use katana_primitives::trace::TxExecInfo;
 use katana_primitives::transaction::TxWithHash;
 use katana_primitives::{ContractAddress, Felt};
+use parking_lot::RwLock;
 
 pub use crate::error::*;
 
@@ -16,11 +21,13 @@ pub type ExecutorResult<T> = Result<T, crate::error::ExecutorError>;
 pub struct BlockLimits {
     /// The maximum number of Cairo steps that can be completed within each block.
     pub cairo_steps: u64,
+    /// The maximum number of transactions that can be included in each block.
+    pub transactions: u64,
 }
 
 impl BlockLimits {
     pub fn max() -> Self {
-        Self { cairo_steps: u64::MAX }
+        Self { cairo_steps: u64::MAX, transactions: u64::MAX }
     }
 }
 
@@ -31,16 +38,23 @@ impl BlockLimits {
 #[derive(Debug, Clone)]
 pub struct ExecutionFlags {
     /// Determine whether to perform the transaction sender's account validation logic.
-    account_validation: bool,
+    account_validation: Toggle,
     /// Determine whether to perform fee related checks and operations ie., fee transfer.
-    fee: bool,
+    fee: Toggle,
     /// Determine whether to perform transaction's sender nonce check.
     nonce_check: bool,
+    /// Accounts for which the account validation logic is always skipped.
+    impersonated_accounts: ImpersonatedAccounts,
 }
 
 impl Default for ExecutionFlags {
     fn default() -> Self {
-        Self { account_validation: true, fee: true, nonce_check: true }
+        Self {
+            account_validation: Toggle::new(true),
+            fee: Toggle::new(true),
+            nonce_check: true,
+            impersonated_accounts: ImpersonatedAccounts::default(),
+        }
     }
 }
 
@@ -51,13 +65,13 @@ impl ExecutionFlags {
 
     /// Set whether to enable or disable the account validation.
     pub fn with_account_validation(mut self, enable: bool) -> Self {
-        self.account_validation = enable;
+        self.account_validation = Toggle::new(enable);
         self
     }
 
     /// Set whether to enable or disable the fee related operations.
     pub fn with_fee(mut self, enable: bool) -> Self {
-        self.fee = enable;
+        self.fee = Toggle::new(enable);
         self
     }
 
@@ -67,14 +81,43 @@ impl ExecutionFlags {
         self
     }
 
+    /// Enables or disables the account validation at runtime, for these flags and all of their
+    /// clones.
+    pub fn set_account_validation(&self, enable: bool) {
+        self.account_validation.set(enable);
+    }
+
+    /// Enables or disables the fee related operations at runtime, for these flags and all of their
+    /// clones.
+    pub fn set_fee(&self, enable: bool) {
+        self.fee.set(enable);
+    }
+
+    /// Set the accounts for which the account validation logic should be skipped.
+    pub fn with_impersonated_accounts(mut self, accounts: ImpersonatedAccounts) -> Self {
+        self.impersonated_accounts = accounts;
+        self
+    }
+
     /// Returns whether the account validation is enabled.
     pub fn account_validation(&self) -> bool {
-        self.account_validation
+        self.account_validation.get()
+    }
+
+    /// Returns whether the account validation should be performed for transactions sent by
+    /// `sender`. This is always `false` for impersonated accounts.
+    pub fn account_validation_for(&self, sender: ContractAddress) -> bool {
+        self.account_validation.get() && !self.impersonated_accounts.contains(sender)
+    }
+
+    /// Returns the set of impersonated accounts.
+    pub fn impersonated_accounts(&self) -> &ImpersonatedAccounts {
+        &self.impersonated_accounts
     }
 
     /// Returns whether the fee related operations are enabled.
     pub fn fee(&self) -> bool {
-        self.fee
+        self.fee.get()
     }
 
     /// Returns whether the nonce check is enabled.
@@ -83,6 +126,50 @@ impl ExecutionFlags {
     }
 }
 
+/// A set of accounts whose transactions are executed without running their account validation
+/// logic (ie `__validate__`). This allows sending transactions on behalf of any account without
+/// knowing its private key.
+///
+/// The set is shared by all of its clones, so accounts impersonated at runtime are picked up by
+/// every executor and validator created from the same [`ExecutionFlags`].
+#[derive(Debug, Clone, Default)]
+pub struct ImpersonatedAccounts(Arc<RwLock<HashSet<ContractAddress>>>);
+
+impl ImpersonatedAccounts {
+    /// Starts impersonating `address`. Returns `false` if it was already being impersonated.
+    pub fn add(&self, address: ContractAddress) -> bool {
+        self.0.write().insert(address)
+    }
+
+    /// Stops impersonating `address`. Returns `false` if it was not being impersonated.
+    pub fn remove(&self, address: ContractAddress) -> bool {
+        self.0.write().remove(&address)
+    }
+
+    /// Returns whether `address` is being impersonated.
+    pub fn contains(&self, address: ContractAddress) -> bool {
+        self.0.read().contains(&address)
+    }
+}
+
+/// A boolean flag shared by all of its clones, like [`ImpersonatedAccounts`].
+#[derive(Debug, Clone)]
+struct Toggle(Arc<AtomicBool>);
+
+impl Toggle {
+    fn new(enable: bool) -> Self {
+        Self(Arc::new(AtomicBool::new(enable)))
+    }
+
+    fn get(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn set(&self, enable: bool) {
+        self.0.store(enable, Ordering::Relaxed)
+    }
+}
+
 /// Stats about the transactions execution.
 #[derive(Debug, Clone, Default)]
 pub struct ExecutionStats {
@@ -116,14 +203,21 @@ pub struct EntryPointCall {
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum ExecutionResult {
-    Success { receipt: Receipt, trace: TxExecInfo },
-    Failed { error: ExecutionError },
+    Success {
+        receipt: Receipt,
+        trace: TxExecInfo,
+        /// The state changes made by the transaction.
+        states: StateUpdates,
+    },
+    Failed {
+        error: ExecutionError,
+    },
 }
 
 impl ExecutionResult {
     /// Creates a new successful execution result.
-    pub fn new_success(receipt: Receipt, trace: TxExecInfo) -> Self {
-        ExecutionResult::Success { receipt, trace }
+    pub fn new_success(receipt: Receipt, trace: TxExecInfo, states: StateUpdates) -> Self {
+        ExecutionResult::Success { receipt, trace, states }
     }
 
     /// Creates a new failed execution result with the given error.
@@ -156,10 +250,20 @@ impl ExecutionResult {
             _ => None,
         }
     }
+
+    /// Returns the state changes made by the transaction if it was successful. Otherwise, returns
+    /// `None`.
+    pub fn states(&self) -> Option<&StateUpdates> {
+        match self {
+            ExecutionResult::Success { states, .. } => Some(states),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ResultAndStates {
     pub result: ExecutionResult,
+    /// The state changes made by the transaction.
     pub states: StateUpdates,
 }
@@ -4,38 +4,46 @@ use blockifier::bouncer::{Bouncer, BouncerConfig, BouncerWeights};
 
 pub mod call;
 mod error;
+#[cfg(feature = "native")]
+mod native;
+mod parallel;
 pub mod state;
 pub mod utils;
 
 use std::collections::HashMap;
-use std::num::NonZeroU128;
-use std::sync::{Arc, LazyLock};
+use std::num::{NonZeroU128, NonZeroUsize};
+use std::sync::LazyLock;
 
 use blockifier::blockifier::block::{BlockInfo, GasPrices};
 use blockifier::context::BlockContext;
-use blockifier::execution::contract_class::ContractClass as BlockifierContractClass;
-use blockifier::state::cached_state::{self, MutRefState};
-use blockifier::state::state_api::StateReader;
+use blockifier::state::cached_state::{self, MutRefState, StateMaps, TransactionalState};
+use blockifier::state::state_api::State;
 use katana_cairo::starknet_api::block::{BlockNumber, BlockTimestamp};
+use katana_cairo::starknet_api::core::{ClassHash as BlockifierClassHash, Nonce};
+use katana_cairo::starknet_api::state::StorageKey;
 use katana_primitives::block::{ExecutableBlock, GasPrices as KatanaGasPrices, PartialHeader};
-use katana_primitives::class::ClassHash;
 use katana_primitives::env::{BlockEnv, CfgEnv};
 use katana_primitives::fee::TxFeeInfo;
+use katana_primitives::state::StateUpdates;
 use katana_primitives::transaction::{ExecutableTx, ExecutableTxWithHash, TxWithHash};
 use katana_primitives::Felt;
 use katana_provider::traits::state::StateProvider;
-use parking_lot::Mutex;
 use tracing::info;
 
-use self::state::CachedState;
+use self::parallel::ParallelExecution;
+use self::state::{CachedState, ClassCache};
 use crate::{
     BlockExecutor, BlockLimits, EntryPointCall, ExecutionError, ExecutionFlags, ExecutionOutput,
     ExecutionResult, ExecutionStats, ExecutorError, ExecutorExt, ExecutorFactory, ExecutorResult,
     ResultAndStates,
 };
 
-pub static COMPILED_CLASS_CACHE: LazyLock<Arc<Mutex<HashMap<ClassHash, BlockifierContractClass>>>> =
-    LazyLock::new(|| Arc::new(Mutex::new(HashMap::default())));
+/// The cache of the compiled classes, shared by all the executors of the process.
+pub static COMPILED_CLASS_CACHE: LazyLock<ClassCache> = LazyLock::new(ClassCache::default);
+
+/// The cache of the classes compiled to native code, used when native execution is enabled.
+#[cfg(feature = "native")]
+pub static NATIVE_CLASS_CACHE: LazyLock<ClassCache> = LazyLock::new(ClassCache::native);
 
 pub(crate) const LOG_TARGET: &str = "katana::executor::blockifier";
 
@@ -45,17 +53,45 @@ pub struct BlockifierFactory {
     flags: ExecutionFlags,
     limits: BlockLimits,
     max_call_gas: u64,
+    concurrency: NonZeroUsize,
+    class_cache: ClassCache,
 }
 
 impl BlockifierFactory {
     /// Create a new factory with the given configuration and simulation flags.
     pub fn new(cfg: CfgEnv, flags: ExecutionFlags, limits: BlockLimits) -> Self {
-        Self { cfg, flags, limits, max_call_gas: 1_000_000_000 }
+        Self {
+            cfg,
+            flags,
+            limits,
+            max_call_gas: 1_000_000_000,
+            concurrency: NonZeroUsize::MIN,
+            class_cache: COMPILED_CLASS_CACHE.clone(),
+        }
+    }
+
+    /// Runs the Sierra classes as native code compiled with Cairo Native, instead of running them
+    /// on the Cairo VM.
+    ///
+    /// Each class is compiled the first time it is executed, which can take a while for large
+    /// classes.
+    #[cfg(feature = "native")]
+    pub fn enable_native(&mut self) {
+        self.class_cache = NATIVE_CLASS_CACHE.clone();
     }
 
     pub fn set_max_call_gas(&mut self, max_call_gas: u64) {
         self.max_call_gas = max_call_gas;
     }
+
+    /// Sets the maximum number of threads used to execute the transactions of a block.
+    ///
+    /// With more than one thread, the transactions are executed optimistically in parallel, and
+    /// the ones that conflict with a preceding transaction are re-executed. The resulting state is
+    /// the same as if the transactions were executed one after another.
+    pub fn set_concurrency(&mut self, concurrency: NonZeroUsize) {
+        self.concurrency = concurrency;
+    }
 }
 
 impl ExecutorFactory for BlockifierFactory {
@@ -84,6 +120,8 @@ impl ExecutorFactory for BlockifierFactory {
             flags,
             limits,
             self.max_call_gas,
+            self.concurrency,
+            self.class_cache.clone(),
         ))
     }
 
@@ -105,10 +143,13 @@ pub struct StarknetVMProcessor<'a> {
     simulation_flags: ExecutionFlags,
     stats: ExecutionStats,
     bouncer: Bouncer,
+    max_txs: u64,
     max_call_gas: u64,
+    concurrency: NonZeroUsize,
 }
 
 impl<'a> StarknetVMProcessor<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         state: impl StateProvider + 'a,
         block_env: BlockEnv,
@@ -116,10 +157,12 @@ impl<'a> StarknetVMProcessor<'a> {
         simulation_flags: ExecutionFlags,
         limits: BlockLimits,
         max_call_gas: u64,
+        concurrency: NonZeroUsize,
+        class_cache: ClassCache,
     ) -> Self {
         let transactions = Vec::new();
         let block_context = utils::block_context_from_envs(&block_env, &cfg_env);
-        let state = state::CachedState::new(state, COMPILED_CLASS_CACHE.clone());
+        let state = state::CachedState::new(state, class_cache);
 
         let mut block_max_capacity = BouncerWeights::max();
         block_max_capacity.n_steps = limits.cairo_steps as usize;
@@ -132,7 +175,9 @@ impl<'a> StarknetVMProcessor<'a> {
             simulation_flags,
             stats: Default::default(),
             bouncer,
+            max_txs: limits.transactions,
             max_call_gas,
+            concurrency,
         }
     }
 
@@ -169,18 +214,25 @@ impl<'a> StarknetVMProcessor<'a> {
             use_kzg_da: false,
         };
 
+        // the block capacity is enforced by our own bouncer, so the block context mustn't impose
+        // any builtin or step limits of its own
         self.block_context =
-            BlockContext::new(block_info, chain_info, versioned_constants, Default::default());
+            BlockContext::new(block_info, chain_info, versioned_constants, BouncerConfig::max());
     }
 
+    /// Executes the transactions on top of the current state without committing the changes.
+    ///
+    /// If `with_state_diff` is `true`, the state changes made by each transaction are passed to
+    /// `op` along with its execution result.
     fn simulate_with<F, T>(
         &self,
         transactions: Vec<ExecutableTxWithHash>,
         flags: &ExecutionFlags,
+        with_state_diff: bool,
         mut op: F,
     ) -> Vec<T>
     where
-        F: FnMut(&mut dyn StateReader, (TxWithHash, ExecutionResult)) -> T,
+        F: FnMut(Option<StateUpdates>, (TxWithHash, ExecutionResult)) -> T,
     {
         let block_context = &self.block_context;
         let state = &mut self.state.inner.lock().cached_state;
@@ -189,10 +241,18 @@ impl<'a> StarknetVMProcessor<'a> {
         let mut results = Vec::with_capacity(transactions.len());
         for exec_tx in transactions {
             let tx = TxWithHash::from(&exec_tx);
+            // Each transaction is executed on its own transactional state so that its state
+            // changes can be isolated from the ones made by the previous transactions.
+            let mut tx_state = TransactionalState::create_transactional(&mut state);
+
             // Safe to unwrap here because the only way the call to `transact` can return an error
             // is when bouncer is `Some`.
-            let res = utils::transact(&mut state, block_context, flags, exec_tx, None).unwrap();
-            results.push(op(&mut state, (tx, res)));
+            let res = utils::transact(&mut tx_state, block_context, flags, exec_tx, None).unwrap();
+
+            let state_diff = if with_state_diff { res.states().cloned() } else { None };
+
+            tx_state.commit();
+            results.push(op(state_diff, (tx, res)));
         }
 
         results
@@ -214,8 +274,34 @@ impl<'a> BlockExecutor<'a> for StarknetVMProcessor<'a> {
         let flags = &self.simulation_flags;
         let mut state = self.state.inner.lock();
 
+        // Only the successful transactions (including the reverted ones) end up in the block.
+        let mut included = self.transactions.iter().filter(|(_, res)| res.is_success()).count();
+
+        // Execute the transactions speculatively in parallel first, their results are then
+        // committed (or re-executed if they turn out to be stale) in order below.
+        let mut parallel = None;
+        let mut speculations = Vec::new().into_iter();
+
+        if self.concurrency.get() > 1 && transactions.len() > 1 {
+            // No need to speculate on the transactions that can't fit in the block anyway. The
+            // failed transactions don't count towards the limit, so the transactions past this
+            // point may still be included, in which case they are executed without speculation.
+            let remaining = self.max_txs.saturating_sub(included as u64);
+            let batch = &transactions[..remaining.min(transactions.len() as u64) as usize];
+
+            let execution = ParallelExecution::new(block_context, flags);
+            let results = execution.speculate(&mut state.cached_state, batch, self.concurrency);
+
+            speculations = results.into_iter();
+            parallel = Some(execution);
+        }
+
         let mut total_executed = 0;
         for exec_tx in transactions {
+            if included as u64 >= self.max_txs {
+                return Ok((total_executed, Some(ExecutorError::LimitsExhausted)));
+            }
+
             // Collect class artifacts if its a declare tx
             let class_decl_artifacts = if let ExecutableTx::Declare(tx) = exec_tx.as_ref() {
                 let class_hash = tx.class_hash();
@@ -226,35 +312,37 @@ impl<'a> BlockExecutor<'a> for StarknetVMProcessor<'a> {
 
             let tx = TxWithHash::from(&exec_tx);
             let hash = tx.hash;
-            let result = utils::transact(
-                &mut state.cached_state,
-                block_context,
-                flags,
-                exec_tx,
-                Some(&mut self.bouncer),
-            );
+            let result = if let Some(parallel) = parallel.as_mut() {
+                let speculation = speculations.next().flatten();
+                parallel.commit(&mut state.cached_state, exec_tx, speculation, &mut self.bouncer)
+            } else {
+                utils::transact(
+                    &mut state.cached_state,
+                    block_context,
+                    flags,
+                    exec_tx,
+                    Some(&mut self.bouncer),
+                )
+            };
 
             match result {
                 Ok(exec_result) => {
                     match &exec_result {
-                        ExecutionResult::Success { receipt, trace } => {
+                        ExecutionResult::Success { receipt, .. } => {
+                            included += 1;
                             self.stats.l1_gas_used += receipt.fee().gas_consumed;
                             self.stats.cairo_steps_used +=
                                 receipt.resources_used().vm_resources.n_steps as u128;
 
-                            if let Some(reason) = receipt.revert_reason() {
-                                info!(target: LOG_TARGET, hash = format!("{hash:#x}"), %reason, "Transaction reverted.");
-                            }
-
                             if let Some((class_hash, class)) = class_decl_artifacts {
                                 state.declared_classes.insert(class_hash, class.as_ref().clone());
                             }
 
-                            crate::utils::log_resources(&trace.actual_resources);
+                            crate::utils::log_execution_summary(hash, receipt);
                         }
 
                         ExecutionResult::Failed { error } => {
-                            info!(target: LOG_TARGET, hash = format!("{hash:#x}"), %error, "Executing transaction.");
+                            info!(target: LOG_TARGET, tx_hash = format!("{hash:#x}"), %error, "Executing transaction.");
                         }
                     }
 
@@ -303,6 +391,53 @@ impl<'a> BlockExecutor<'a> for StarknetVMProcessor<'a> {
             },
         }
     }
+
+    fn set_storage_at(
+        &mut self,
+        address: katana_primitives::contract::ContractAddress,
+        key: katana_primitives::contract::StorageKey,
+        value: katana_primitives::contract::StorageValue,
+    ) -> ExecutorResult<()> {
+        let address = utils::to_blk_address(address);
+        let key = StorageKey(key.try_into().map_err(|e| ExecutorError::Other(Box::new(e)))?);
+
+        let mut state = self.state.inner.lock();
+        state
+            .cached_state
+            .set_storage_at(address, key, value)
+            .map_err(|e| ExecutorError::Other(Box::new(e)))
+    }
+
+    fn set_nonce(
+        &mut self,
+        address: katana_primitives::contract::ContractAddress,
+        nonce: katana_primitives::contract::Nonce,
+    ) -> ExecutorResult<()> {
+        let address = utils::to_blk_address(address);
+
+        // the state api only allows incrementing nonces, so the nonce is written directly instead
+        let mut writes = StateMaps::default();
+        writes.nonces.insert(address, Nonce(nonce));
+
+        let mut state = self.state.inner.lock();
+        state.cached_state.apply_writes(&writes, &HashMap::new(), &HashMap::new());
+
+        Ok(())
+    }
+
+    fn set_class_hash_at(
+        &mut self,
+        address: katana_primitives::contract::ContractAddress,
+        class_hash: katana_primitives::class::ClassHash,
+    ) -> ExecutorResult<()> {
+        let address = utils::to_blk_address(address);
+
+        let mut state = self.state.inner.lock();
+        state
+            .cached_state
+            .set_class_hash_at(address, BlockifierClassHash(class_hash))
+            .map_err(|e| ExecutorError::Other(Box::new(e)))
+    }
 }
 
 impl ExecutorExt for StarknetVMProcessor<'_> {
@@ -311,9 +446,9 @@ impl ExecutorExt for StarknetVMProcessor<'_> {
         transactions: Vec<ExecutableTxWithHash>,
         flags: ExecutionFlags,
     ) -> Vec<ResultAndStates> {
-        self.simulate_with(transactions, &flags, |_, (_, result)| ResultAndStates {
+        self.simulate_with(transactions, &flags, true, |states, (_, result)| ResultAndStates {
             result,
-            states: Default::default(),
+            states: states.unwrap_or_default(),
         })
     }
 
@@ -322,7 +457,7 @@ impl ExecutorExt for StarknetVMProcessor<'_> {
         transactions: Vec<ExecutableTxWithHash>,
         flags: ExecutionFlags,
     ) -> Vec<Result<TxFeeInfo, ExecutionError>> {
-        self.simulate_with(transactions, &flags, |_, (_, res)| match res {
+        self.simulate_with(transactions, &flags, false, |_, (_, res)| match res {
             ExecutionResult::Success { receipt, .. } => {
                 // if the transaction was reverted, return as error
                 if let Some(reason) = receipt.revert_reason() {
@@ -0,0 +1,27 @@
+//! Execution of Sierra classes as native code, using [Cairo Native].
+//!
+//! [Cairo Native]: https://github.com/lambdaclass/cairo_native
+
+use blockifier::execution::contract_class::{ContractClass, ContractClassV1};
+use blockifier::execution::native::contract_class::NativeContractClassV1;
+use cairo_native::executor::AotContractExecutor;
+use cairo_native::OptLevel;
+use katana_primitives::class::{CasmContractClass, SierraContractClass};
+
+type CompilationError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Compiles a Sierra class into native code.
+///
+/// The CASM of the class is still needed by the executor, as it's used to charge the class'
+/// resources when computing the fee of a transaction.
+pub(super) fn compile(
+    sierra: SierraContractClass,
+    casm: CasmContractClass,
+) -> Result<ContractClass, CompilationError> {
+    let program = sierra.extract_sierra_program()?;
+    let executor =
+        AotContractExecutor::new(&program, &sierra.entry_points_by_type, OptLevel::Default)?;
+
+    let casm = ContractClassV1::try_from(casm)?;
+    Ok(ContractClass::V1Native(NativeContractClassV1::new(executor, casm)))
+}
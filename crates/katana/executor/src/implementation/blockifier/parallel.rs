@@ -0,0 +1,398 @@
+//! Optimistic parallel execution of transactions.
+//!
+//! The transactions of a batch are first executed speculatively on multiple threads, all of them
+//! against the state as it was before the batch, while keeping track of the parts of the state that
+//! each transaction has read. The speculative results are then validated and committed one by one,
+//! in the order of the transactions. A transaction that has read a part of the state which was
+//! modified by a transaction committed before it was executed against a stale state, so it is
+//! re-executed on top of the up-to-date state instead.
+//!
+//! Every transaction that is charged a fee writes to the balance of the sequencer, which would make
+//! all of them conflict with each other. So the sequencer balance is left out of the conflict
+//! detection, and the balance change made by a transaction is applied on top of the up-to-date
+//! balance when the transaction is committed. As a consequence, a transaction that reads the
+//! sequencer balance may not observe the fees paid by the preceding transactions of its batch.
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use blockifier::bouncer::Bouncer;
+use blockifier::context::BlockContext;
+use blockifier::execution::contract_class::ContractClass;
+use blockifier::state::cached_state::{
+    self, MutRefState, StateChangesKeys, StateMaps, TransactionalState,
+};
+use blockifier::state::state_api::{StateReader, StateResult, UpdatableState};
+use blockifier::transaction::objects::TransactionExecutionInfo;
+use katana_cairo::starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use katana_cairo::starknet_api::hash::StarkHash;
+use katana_cairo::starknet_api::state::StorageKey;
+use katana_primitives::fee::TxFeeInfo;
+use katana_primitives::genesis::constant::get_fee_token_balance_base_storage_address;
+use katana_primitives::transaction::{ExecutableTx, ExecutableTxWithHash};
+use katana_primitives::utils::split_u256;
+use katana_primitives::{Felt, U256};
+use parking_lot::Mutex;
+
+use super::utils;
+use crate::abstraction::ExecutionFlags;
+use crate::utils::build_receipt;
+use crate::{ExecutionError, ExecutionResult, ExecutorError, ExecutorResult};
+
+type TxOutcome = Result<(TransactionExecutionInfo, TxFeeInfo), ExecutionError>;
+
+/// A part of the state that can be read by a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum StateKey {
+    Storage(ContractAddress, StorageKey),
+    Nonce(ContractAddress),
+    ClassHash(ContractAddress),
+    CompiledClassHash(ClassHash),
+}
+
+/// The storage slots of the sequencer balance (low, high) in each of the fee tokens.
+#[derive(Debug, Clone)]
+struct SequencerBalances {
+    slots: Vec<(ContractAddress, StorageKey, StorageKey)>,
+}
+
+impl SequencerBalances {
+    fn new(block_context: &BlockContext) -> Self {
+        let sequencer = utils::to_address(block_context.block_info().sequencer_address);
+        let base = get_fee_token_balance_base_storage_address(sequencer);
+
+        let low = StorageKey(base.try_into().expect("valid storage key"));
+        let high = StorageKey((base + Felt::ONE).try_into().expect("valid storage key"));
+
+        let tokens = &block_context.chain_info().fee_token_addresses;
+        let slots = vec![
+            (tokens.eth_fee_token_address, low, high),
+            (tokens.strk_fee_token_address, low, high),
+        ];
+
+        Self { slots }
+    }
+
+    fn contains(&self, address: ContractAddress, key: StorageKey) -> bool {
+        self.slots.iter().any(|&(token, low, high)| token == address && (key == low || key == high))
+    }
+}
+
+/// A state reader that reads from the shared base state, and records every part of the state that
+/// is read through it.
+struct RecordingState<'s, S> {
+    base: &'s Mutex<S>,
+    balances: &'s SequencerBalances,
+    reads: RefCell<HashSet<StateKey>>,
+    /// The values of the sequencer balance slots that were read.
+    balance_reads: RefCell<HashMap<(ContractAddress, StorageKey), Felt>>,
+}
+
+impl<'s, S: StateReader> RecordingState<'s, S> {
+    fn new(base: &'s Mutex<S>, balances: &'s SequencerBalances) -> Self {
+        Self { base, balances, reads: Default::default(), balance_reads: Default::default() }
+    }
+
+    fn record(&self, key: StateKey) {
+        self.reads.borrow_mut().insert(key);
+    }
+}
+
+impl<S: StateReader> StateReader for RecordingState<'_, S> {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<StarkHash> {
+        let value = self.base.lock().get_storage_at(contract_address, key)?;
+
+        if self.balances.contains(contract_address, key) {
+            self.balance_reads.borrow_mut().insert((contract_address, key), value);
+        } else {
+            self.record(StateKey::Storage(contract_address, key));
+        }
+
+        Ok(value)
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        self.record(StateKey::Nonce(contract_address));
+        self.base.lock().get_nonce_at(contract_address)
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        self.record(StateKey::ClassHash(contract_address));
+        self.base.lock().get_class_hash_at(contract_address)
+    }
+
+    fn get_compiled_contract_class(&self, class_hash: ClassHash) -> StateResult<ContractClass> {
+        self.record(StateKey::CompiledClassHash(class_hash));
+        self.base.lock().get_compiled_contract_class(class_hash)
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        self.record(StateKey::CompiledClassHash(class_hash));
+        self.base.lock().get_compiled_class_hash(class_hash)
+    }
+}
+
+/// The result of executing a transaction against the state as it was before its batch.
+pub(super) struct Speculation {
+    outcome: TxOutcome,
+    reads: HashSet<StateKey>,
+    writes: StateMaps,
+    changes_keys: StateChangesKeys,
+    balance_reads: HashMap<(ContractAddress, StorageKey), Felt>,
+}
+
+/// Executes the batch of transactions under a parallel execution scheme, and commits their
+/// state changes into `state`, in order.
+///
+/// Declare transactions are never executed speculatively, as the class they declare is not
+/// visible to the rest of the batch until they are committed.
+pub(super) struct ParallelExecution<'c> {
+    block_context: &'c BlockContext,
+    flags: &'c ExecutionFlags,
+    balances: SequencerBalances,
+    /// The parts of the state modified by the transactions committed so far.
+    written: HashSet<StateKey>,
+}
+
+impl<'c> ParallelExecution<'c> {
+    pub(super) fn new(block_context: &'c BlockContext, flags: &'c ExecutionFlags) -> Self {
+        let balances = SequencerBalances::new(block_context);
+        Self { block_context, flags, balances, written: HashSet::new() }
+    }
+
+    /// Executes all the transactions speculatively, using up to `concurrency` threads.
+    ///
+    /// The returned speculations are in the same order as `transactions`.
+    pub(super) fn speculate<S: StateReader + Send>(
+        &self,
+        state: &mut cached_state::CachedState<S>,
+        transactions: &[ExecutableTxWithHash],
+        concurrency: NonZeroUsize,
+    ) -> Vec<Option<Speculation>> {
+        let base = Mutex::new(MutRefState::new(state));
+        let next = AtomicUsize::new(0);
+        let workers = concurrency.get().min(transactions.len());
+
+        let mut speculations = thread::scope(|scope| {
+            let handles = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut speculations = Vec::new();
+
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(tx) = transactions.get(index) else { break };
+
+                            if let ExecutableTx::Declare(_) = tx.transaction {
+                                continue;
+                            }
+
+                            if let Some(speculation) = self.speculate_one(&base, tx) {
+                                speculations.push((index, speculation));
+                            }
+                        }
+
+                        speculations
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("speculative execution panicked"))
+                .collect::<HashMap<_, _>>()
+        });
+
+        (0..transactions.len()).map(|index| speculations.remove(&index)).collect()
+    }
+
+    /// Returns `None` if the state changes of the transaction can't be computed, in which case the
+    /// transaction is simply re-executed when it is committed.
+    fn speculate_one<S: StateReader>(
+        &self,
+        base: &Mutex<S>,
+        tx: &ExecutableTxWithHash,
+    ) -> Option<Speculation> {
+        let reader = RecordingState::new(base, &self.balances);
+        let mut tx_state = cached_state::CachedState::new(reader);
+
+        let sender = utils::sender_address(&tx.transaction);
+        let transaction = utils::to_executor_tx(tx.clone());
+        let outcome =
+            utils::execute_tx(&mut tx_state, self.block_context, self.flags, transaction, sender);
+
+        let writes = tx_state.to_state_diff().ok()?;
+        let changes_keys = tx_state.get_actual_state_changes().ok()?.into_keys();
+
+        let RecordingState { reads, balance_reads, .. } = tx_state.state;
+        let reads = reads.into_inner();
+        let balance_reads = balance_reads.into_inner();
+
+        Some(Speculation { outcome, reads, writes, changes_keys, balance_reads })
+    }
+
+    /// Commits the speculative execution of a transaction into `state`, or re-executes the
+    /// transaction on top of `state` if its speculation is missing or stale.
+    pub(super) fn commit<S: StateReader>(
+        &mut self,
+        state: &mut cached_state::CachedState<S>,
+        tx: ExecutableTxWithHash,
+        speculation: Option<Speculation>,
+        bouncer: &mut Bouncer,
+    ) -> ExecutorResult<ExecutionResult> {
+        let speculation = speculation.filter(|s| s.reads.is_disjoint(&self.written));
+
+        let Some(speculation) = speculation else {
+            return self.execute(state, tx, bouncer);
+        };
+
+        let Speculation { outcome, mut writes, changes_keys, balance_reads, .. } = speculation;
+
+        if let Ok((info, _)) = &outcome {
+            bouncer.try_update(
+                &*state,
+                &changes_keys,
+                &info.summarize(),
+                &info.transaction_receipt.resources,
+            )?;
+        }
+
+        self.rebase_balances(state, &mut writes, &balance_reads)?;
+        self.record_writes(&writes);
+
+        let result = to_execution_result(&tx, outcome, &writes, &*state);
+        state.apply_writes(&writes, &HashMap::new(), &HashMap::new());
+
+        Ok(result)
+    }
+
+    /// Executes a transaction directly on top of `state`.
+    fn execute<S: StateReader>(
+        &mut self,
+        state: &mut cached_state::CachedState<S>,
+        tx: ExecutableTxWithHash,
+        bouncer: &mut Bouncer,
+    ) -> ExecutorResult<ExecutionResult> {
+        let sender = utils::sender_address(&tx.transaction);
+        let transaction = utils::to_executor_tx(tx.clone());
+
+        let mut tx_state = TransactionalState::create_transactional(state);
+        let outcome =
+            utils::execute_tx(&mut tx_state, self.block_context, self.flags, transaction, sender);
+
+        if let Ok((info, _)) = &outcome {
+            let changes_keys = match tx_state.get_actual_state_changes() {
+                Ok(changes) => changes.into_keys(),
+                Err(e) => {
+                    tx_state.abort();
+                    return Ok(ExecutionResult::new_failed(e));
+                }
+            };
+
+            bouncer.try_update(
+                &tx_state,
+                &changes_keys,
+                &info.summarize(),
+                &info.transaction_receipt.resources,
+            )?;
+        }
+
+        let writes = match tx_state.to_state_diff() {
+            Ok(writes) => writes,
+            Err(e) => {
+                tx_state.abort();
+                return Ok(ExecutionResult::new_failed(e));
+            }
+        };
+
+        self.record_writes(&writes);
+
+        let result = to_execution_result(&tx, outcome, &writes, &tx_state.state);
+        tx_state.commit();
+
+        Ok(result)
+    }
+
+    /// Replaces the sequencer balances written by a speculative execution with the up-to-date
+    /// balances plus the change made by the transaction.
+    fn rebase_balances<S: StateReader>(
+        &self,
+        state: &cached_state::CachedState<S>,
+        writes: &mut StateMaps,
+        reads: &HashMap<(ContractAddress, StorageKey), Felt>,
+    ) -> ExecutorResult<()> {
+        for &(token, low, high) in &self.balances.slots {
+            let written_low = writes.storage.remove(&(token, low));
+            let written_high = writes.storage.remove(&(token, high));
+
+            if written_low.is_none() && written_high.is_none() {
+                continue;
+            }
+
+            let read = |key| reads.get(&(token, key)).copied().unwrap_or_default();
+            let (read_low, read_high) = (read(low), read(high));
+
+            let written =
+                to_u256(written_low.unwrap_or(read_low), written_high.unwrap_or(read_high));
+            let read = to_u256(read_low, read_high);
+
+            let current_low =
+                state.get_storage_at(token, low).map_err(|e| ExecutorError::Other(Box::new(e)))?;
+            let current_high =
+                state.get_storage_at(token, high).map_err(|e| ExecutorError::Other(Box::new(e)))?;
+            let current = to_u256(current_low, current_high);
+
+            let (new_low, new_high) = split_u256(current.wrapping_add(written).wrapping_sub(read));
+            writes.storage.insert((token, low), new_low);
+            writes.storage.insert((token, high), new_high);
+        }
+
+        Ok(())
+    }
+
+    fn record_writes(&mut self, writes: &StateMaps) {
+        let storage = writes
+            .storage
+            .keys()
+            .filter(|(address, key)| !self.balances.contains(*address, *key))
+            .map(|&(address, key)| StateKey::Storage(address, key));
+        let nonces = writes.nonces.keys().map(|&address| StateKey::Nonce(address));
+        let class_hashes = writes.class_hashes.keys().map(|&address| StateKey::ClassHash(address));
+        let compiled_class_hashes =
+            writes.compiled_class_hashes.keys().map(|&hash| StateKey::CompiledClassHash(hash));
+
+        let keys = storage.chain(nonces).chain(class_hashes).chain(compiled_class_hashes);
+        self.written.extend(keys);
+    }
+}
+
+/// `state` must be the state on top of which the transaction was executed.
+fn to_execution_result(
+    tx: &ExecutableTxWithHash,
+    outcome: TxOutcome,
+    writes: &StateMaps,
+    state: &impl StateReader,
+) -> ExecutionResult {
+    match outcome {
+        Ok((info, fee)) => {
+            let trace = utils::to_exec_info(info, tx.r#type());
+            let receipt = build_receipt(tx.tx_ref(), fee, &trace);
+            let states = utils::to_tx_state_updates(tx, writes.clone(), state);
+            ExecutionResult::new_success(receipt, trace, states)
+        }
+        Err(e) => ExecutionResult::new_failed(e),
+    }
+}
+
+fn to_u256(low: Felt, high: Felt) -> U256 {
+    let low = U256::from_be_bytes(low.to_bytes_be());
+    let high = U256::from_be_bytes(high.to_bytes_be());
+    (high << 128) + low
+}
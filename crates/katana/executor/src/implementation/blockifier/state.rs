@@ -6,6 +6,7 @@ use blockifier::execution::contract_class::ContractClass as BlockifierContractCl
 use blockifier::state::cached_state;
 use blockifier::state::errors::StateError;
 use blockifier::state::state_api::{StateReader, StateResult};
+use hashlink::LruCache;
 use katana_cairo::starknet_api::core::{ClassHash, CompiledClassHash, Nonce};
 use katana_cairo::starknet_api::state::StorageKey;
 use katana_primitives::class::{self, ContractClass};
@@ -19,6 +20,54 @@ use tracing::trace;
 
 use super::utils::{self};
 
+/// The default number of classes kept in a [`ClassCache`].
+pub const DEFAULT_CLASS_CACHE_SIZE: usize = 1024;
+
+/// A cache of the classes in the form that is run by the executor.
+///
+/// The cache is meant to be shared across executors so that each class only has to be converted
+/// once, which matters the most when the classes are compiled to native code. Classes are keyed by
+/// their compiled class hash, or by their class hash for legacy classes which don't have one, and
+/// the least recently used ones are evicted once the cache is full.
+#[derive(Debug, Clone)]
+pub struct ClassCache {
+    classes: Arc<Mutex<LruCache<Felt, BlockifierContractClass>>>,
+    /// Whether the Sierra classes are compiled to native code using Cairo Native.
+    #[cfg(feature = "native")]
+    native: bool,
+}
+
+impl ClassCache {
+    /// Creates a cache that holds up to `capacity` classes.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            classes: Arc::new(Mutex::new(LruCache::new(capacity))),
+            #[cfg(feature = "native")]
+            native: false,
+        }
+    }
+
+    /// Creates a cache whose Sierra classes are compiled to native code using Cairo Native.
+    #[cfg(feature = "native")]
+    pub fn native() -> Self {
+        Self { native: true, ..Self::default() }
+    }
+
+    fn get(&self, key: &Felt) -> Option<BlockifierContractClass> {
+        self.classes.lock().get(key).cloned()
+    }
+
+    fn insert(&self, key: Felt, class: BlockifierContractClass) {
+        self.classes.lock().insert(key, class);
+    }
+}
+
+impl Default for ClassCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CLASS_CACHE_SIZE)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CachedState<'a> {
     pub(crate) inner: Arc<Mutex<CachedStateInner<'a>>>,
@@ -33,9 +82,9 @@ pub(crate) struct CachedStateInner<'a> {
 impl<'a> CachedState<'a> {
     pub(super) fn new(
         state: impl StateProvider + 'a,
-        compiled_class_cache: Arc<Mutex<HashMap<class::ClassHash, BlockifierContractClass>>>,
+        class_cache: ClassCache,
     ) -> Self {
-        let state = StateProviderDb::new(Box::new(state), compiled_class_cache);
+        let state = StateProviderDb::new(Box::new(state), class_cache);
         let cached_state = cached_state::CachedState::new(state);
 
         let declared_classes = HashMap::new();
@@ -126,7 +175,7 @@ impl<'a> StateRootProvider for CachedState<'a> {}
 #[derive(Debug)]
 pub struct StateProviderDb<'a> {
     provider: Box<dyn StateProvider + 'a>,
-    compiled_class_cache: Arc<Mutex<HashMap<class::ClassHash, BlockifierContractClass>>>,
+    class_cache: ClassCache,
 }
 
 impl<'a> Deref for StateProviderDb<'a> {
@@ -140,9 +189,35 @@ impl<'a> Deref for StateProviderDb<'a> {
 impl<'a> StateProviderDb<'a> {
     pub fn new(
         provider: Box<dyn StateProvider + 'a>,
-        compiled_class_cache: Arc<Mutex<HashMap<class::ClassHash, BlockifierContractClass>>>,
+        class_cache: ClassCache,
     ) -> Self {
-        Self { provider, compiled_class_cache }
+        Self { provider, class_cache }
+    }
+
+    /// Converts a compiled class into the form that is run by the executor.
+    #[cfg_attr(not(feature = "native"), allow(unused_variables))]
+    fn to_executable_class(
+        &self,
+        hash: class::ClassHash,
+        class: class::CompiledClass,
+    ) -> StateResult<BlockifierContractClass> {
+        #[cfg(feature = "native")]
+        if self.class_cache.native {
+            if let class::CompiledClass::Class(casm) = class {
+                let sierra = match self.provider.class(hash) {
+                    Ok(Some(ContractClass::Class(sierra))) => sierra,
+                    Ok(_) => return Err(StateError::UndeclaredClassHash(ClassHash(hash))),
+                    Err(e) => return Err(StateError::StateReadError(e.to_string())),
+                };
+
+                trace!(target: "executor", class = format!("{hash:#x}"), "Compiling class to native code.");
+
+                return super::native::compile(sierra, casm)
+                    .map_err(|e| StateError::StateReadError(e.to_string()));
+            }
+        }
+
+        utils::to_class(class).map_err(|e| StateError::StateReadError(e.to_string()))
     }
 }
 
@@ -176,11 +251,15 @@ impl<'a> StateReader for StateProviderDb<'a> {
         &self,
         class_hash: ClassHash,
     ) -> StateResult<BlockifierContractClass> {
-        let mut class_cache = self.compiled_class_cache.lock();
+        let key = self
+            .provider
+            .compiled_class_hash_of_class_hash(class_hash.0)
+            .map_err(|e| StateError::StateReadError(e.to_string()))?
+            .unwrap_or(class_hash.0);
 
-        if let Some(class) = class_cache.get(&class_hash.0) {
+        if let Some(class) = self.class_cache.get(&key) {
             trace!(target: "executor", class = format!("{}", class_hash.to_hex_string()), "Class cache hit");
-            return Ok(class.clone());
+            return Ok(class);
         }
 
         if let Some(class) = self
@@ -190,10 +269,11 @@ impl<'a> StateReader for StateProviderDb<'a> {
         {
             trace!(target: "executor", class = format!("{}", class_hash.to_hex_string()), "Class cache miss");
 
-            let class =
-                utils::to_class(class).map_err(|e| StateError::StateReadError(e.to_string()))?;
+            // the cache isn't locked while the class is being converted, so that other executors
+            // aren't blocked in the meantime.
+            let class = self.to_executable_class(class_hash.0, class)?;
 
-            class_cache.insert(class_hash.0, class.clone());
+            self.class_cache.insert(key, class.clone());
             return Ok(class);
         }
 
@@ -220,3 +300,35 @@ impl<'a> StateReader for StateProviderDb<'a> {
             .map_err(|e| StateError::StateReadError(e.to_string()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use katana_primitives::genesis::constant::{
+        DEFAULT_ACCOUNT_CLASS_HASH, DEFAULT_ACCOUNT_COMPILED_CLASS_HASH,
+        DEFAULT_LEGACY_ERC20_CLASS_HASH,
+    };
+    use katana_provider::test_utils::test_provider;
+    use katana_provider::traits::state::StateFactoryProvider;
+
+    use super::*;
+
+    #[test]
+    fn class_cache_is_shared_and_bounded() {
+        let provider = test_provider();
+        let cache = ClassCache::new(1);
+
+        let state = StateProviderDb::new(provider.latest().unwrap(), cache.clone());
+        state.get_compiled_contract_class(ClassHash(DEFAULT_ACCOUNT_CLASS_HASH)).unwrap();
+
+        // the class is cached by its compiled class hash, for every state using the same cache
+        assert!(cache.get(&DEFAULT_ACCOUNT_COMPILED_CLASS_HASH).is_some());
+        assert!(cache.get(&DEFAULT_ACCOUNT_CLASS_HASH).is_none());
+
+        let other = StateProviderDb::new(provider.latest().unwrap(), cache.clone());
+        other.get_compiled_contract_class(ClassHash(DEFAULT_LEGACY_ERC20_CLASS_HASH)).unwrap();
+
+        // the least recently used class is evicted once the cache is full
+        assert!(cache.get(&DEFAULT_ACCOUNT_COMPILED_CLASS_HASH).is_none());
+        assert_eq!(cache.classes.lock().len(), 1);
+    }
+}
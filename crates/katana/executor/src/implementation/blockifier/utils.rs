@@ -13,7 +13,7 @@ use blockifier::execution::contract_class::{
 };
 use blockifier::execution::entry_point::CallType;
 use blockifier::fee::fee_utils::get_fee_by_gas_vector;
-use blockifier::state::cached_state::{self, TransactionalState};
+use blockifier::state::cached_state::{self, StateMaps, TransactionalState};
 use blockifier::state::state_api::{StateReader, UpdatableState};
 use blockifier::transaction::account_transaction::AccountTransaction;
 use blockifier::transaction::objects::{FeeType, HasRelatedFeeType, TransactionExecutionInfo};
@@ -47,7 +47,7 @@ use katana_primitives::trace::{L1Gas, TxExecInfo, TxResources};
 use katana_primitives::transaction::{
     DeclareTx, DeployAccountTx, ExecutableTx, ExecutableTxWithHash, InvokeTx, TxType,
 };
-use katana_primitives::{class, event, message, trace};
+use katana_primitives::{class, contract, event, message, trace, Felt};
 use katana_provider::traits::contract::ContractClassProvider;
 use starknet::core::utils::parse_cairo_short_string;
 
@@ -56,72 +56,67 @@ use crate::abstraction::ExecutionFlags;
 use crate::utils::build_receipt;
 use crate::{ExecutionError, ExecutionResult, ExecutorResult};
 
-pub fn transact<S: StateReader>(
-    state: &mut cached_state::CachedState<S>,
+/// Executes a single transaction on top of `state`, without committing its state changes.
+pub(super) fn execute_tx<U: UpdatableState>(
+    state: &mut U,
     block_context: &BlockContext,
     simulation_flags: &ExecutionFlags,
-    tx: ExecutableTxWithHash,
-    bouncer: Option<&mut Bouncer>,
-) -> ExecutorResult<ExecutionResult> {
-    fn transact_inner<U: UpdatableState>(
-        state: &mut U,
-        block_context: &BlockContext,
-        simulation_flags: &ExecutionFlags,
-        tx: Transaction,
-    ) -> Result<(TransactionExecutionInfo, TxFeeInfo), ExecutionError> {
-        let validate = simulation_flags.account_validation();
-        let charge_fee = simulation_flags.fee();
-        // Blockifier doesn't provide a way to fully skip nonce check during the tx validation
-        // stage. The `nonce_check` flag in `tx.execute()` only 'relaxes' the check for
-        // nonce that is equal or higher than the current (expected) account nonce.
-        //
-        // Related commit on Blockifier: https://github.com/dojoengine/blockifier/commit/2410b6055453f247d48759f223c34b3fb5fa777
-        let nonce_check = simulation_flags.nonce_check();
+    tx: Transaction,
+    sender: contract::ContractAddress,
+) -> Result<(TransactionExecutionInfo, TxFeeInfo), ExecutionError> {
+    let validate = simulation_flags.account_validation_for(sender);
+    let charge_fee = simulation_flags.fee();
+    // Blockifier doesn't provide a way to fully skip nonce check during the tx validation
+    // stage. The `nonce_check` flag in `tx.execute()` only 'relaxes' the check for
+    // nonce that is equal or higher than the current (expected) account nonce.
+    //
+    // Related commit on Blockifier: https://github.com/dojoengine/blockifier/commit/2410b6055453f247d48759f223c34b3fb5fa777
+    let nonce_check = simulation_flags.nonce_check();
 
-        let fee_type = get_fee_type_from_tx(&tx);
-        let info = match tx {
-            Transaction::AccountTransaction(tx) => {
-                tx.execute(state, block_context, charge_fee, validate, nonce_check)
-            }
-            Transaction::L1HandlerTransaction(tx) => {
-                tx.execute(state, block_context, charge_fee, validate, nonce_check)
-            }
-        }?;
-
-        // There are a few case where the `actual_fee` field of the transaction info is not set
-        // where the fee is skipped and thus not charged for the transaction (e.g. when the
-        // `skip_fee_transfer` is explicitly set, or when the transaction `max_fee` is set to 0). In
-        // these cases, we still want to calculate the fee.
-        let fee = if info.transaction_receipt.fee == Fee(0) {
-            get_fee_by_gas_vector(
-                block_context.block_info(),
-                info.transaction_receipt.gas,
-                &fee_type,
-            )
-        } else {
-            info.transaction_receipt.fee
-        };
+    let fee_type = get_fee_type_from_tx(&tx);
+    let info = match tx {
+        Transaction::AccountTransaction(tx) => {
+            tx.execute(state, block_context, charge_fee, validate, nonce_check)
+        }
+        Transaction::L1HandlerTransaction(tx) => {
+            tx.execute(state, block_context, charge_fee, validate, nonce_check)
+        }
+    }?;
+
+    // There are a few case where the `actual_fee` field of the transaction info is not set
+    // where the fee is skipped and thus not charged for the transaction (e.g. when the
+    // `skip_fee_transfer` is explicitly set, or when the transaction `max_fee` is set to 0). In
+    // these cases, we still want to calculate the fee.
+    let fee = if info.transaction_receipt.fee == Fee(0) {
+        get_fee_by_gas_vector(block_context.block_info(), info.transaction_receipt.gas, &fee_type)
+    } else {
+        info.transaction_receipt.fee
+    };
 
-        let gas_consumed = info.transaction_receipt.gas.l1_gas;
+    let gas_consumed = info.transaction_receipt.gas.l1_gas;
 
-        let (unit, gas_price) = match fee_type {
-            FeeType::Eth => {
-                (PriceUnit::Wei, block_context.block_info().gas_prices.eth_l1_gas_price)
-            }
-            FeeType::Strk => {
-                (PriceUnit::Fri, block_context.block_info().gas_prices.strk_l1_gas_price)
-            }
-        };
+    let (unit, gas_price) = match fee_type {
+        FeeType::Eth => (PriceUnit::Wei, block_context.block_info().gas_prices.eth_l1_gas_price),
+        FeeType::Strk => (PriceUnit::Fri, block_context.block_info().gas_prices.strk_l1_gas_price),
+    };
 
-        let fee_info =
-            TxFeeInfo { gas_consumed, gas_price: gas_price.into(), unit, overall_fee: fee.0 };
+    let fee_info =
+        TxFeeInfo { gas_consumed, gas_price: gas_price.into(), unit, overall_fee: fee.0 };
 
-        Ok((info, fee_info))
-    }
+    Ok((info, fee_info))
+}
 
+pub fn transact<S: StateReader>(
+    state: &mut cached_state::CachedState<S>,
+    block_context: &BlockContext,
+    simulation_flags: &ExecutionFlags,
+    tx: ExecutableTxWithHash,
+    bouncer: Option<&mut Bouncer>,
+) -> ExecutorResult<ExecutionResult> {
+    let sender = sender_address(&tx.transaction);
     let transaction = to_executor_tx(tx.clone());
     let mut tx_state = TransactionalState::create_transactional(state);
-    let result = transact_inner(&mut tx_state, block_context, simulation_flags, transaction);
+    let result = execute_tx(&mut tx_state, block_context, simulation_flags, transaction, sender);
 
     match result {
         Ok((info, fee)) => {
@@ -137,12 +132,21 @@ pub fn transact<S: StateReader>(
                 )?;
             }
 
+            let state_diff = match tx_state.to_state_diff() {
+                Ok(state_diff) => state_diff,
+                Err(e) => {
+                    tx_state.abort();
+                    return Ok(ExecutionResult::new_failed(e));
+                }
+            };
+
+            let states = to_tx_state_updates(&tx, state_diff, &tx_state.state);
             tx_state.commit();
 
             // get the trace and receipt from the execution info
             let trace = to_exec_info(info, tx.r#type());
             let receipt = build_receipt(tx.tx_ref(), fee, &trace);
-            Ok(ExecutionResult::new_success(receipt, trace))
+            Ok(ExecutionResult::new_success(receipt, trace, states))
         }
 
         Err(e) => {
@@ -417,6 +421,31 @@ pub(super) fn state_update_from_cached_state(state: &CachedState<'_>) -> StateUp
         katana_primitives::class::ContractClass,
     > = BTreeMap::new();
 
+    for class_hash in state_diff.compiled_class_hashes.keys() {
+        let hash = class_hash.0;
+        let class = state.class(hash).unwrap().expect("must exist if declared");
+        declared_contract_classes.insert(hash, class);
+    }
+
+    let state_updates = to_state_updates(
+        state_diff,
+        |hash| declared_contract_classes[&hash].is_legacy(),
+        |address| is_undeployed(&state.inner.lock().cached_state.state, address),
+    );
+
+    StateUpdatesWithClasses { classes: declared_contract_classes, state_updates }
+}
+
+/// Converts the state changes tracked by a blockifier cached state into [`StateUpdates`].
+///
+/// `is_legacy` is used to determine whether a declared class is a legacy (Cairo 0) class, and
+/// `is_undeployed` whether an address had no contract deployed at it before the changes were made,
+/// to distinguish newly deployed contracts from the ones whose class has been replaced.
+pub(super) fn to_state_updates(
+    state_diff: StateMaps,
+    is_legacy: impl Fn(katana_primitives::class::ClassHash) -> bool,
+    is_undeployed: impl Fn(ContractAddress) -> bool,
+) -> StateUpdates {
     let mut declared_classes = BTreeMap::new();
     let mut deprecated_declared_classes = BTreeSet::new();
 
@@ -424,15 +453,11 @@ pub(super) fn state_update_from_cached_state(state: &CachedState<'_>) -> StateUp
     // in our fork of `blockifier. Check if it's possible to remove it now.
     for (class_hash, compiled_hash) in state_diff.compiled_class_hashes {
         let hash = class_hash.0;
-        let class = state.class(hash).unwrap().expect("must exist if declared");
-
-        if class.is_legacy() {
+        if is_legacy(hash) {
             deprecated_declared_classes.insert(hash);
         } else {
             declared_classes.insert(hash, compiled_hash.0);
         }
-
-        declared_contract_classes.insert(hash, class);
     }
 
     let nonce_updates =
@@ -457,27 +482,43 @@ pub(super) fn state_update_from_cached_state(state: &CachedState<'_>) -> StateUp
         },
     );
 
-    let deployed_contracts =
-        state_diff
-            .class_hashes
-            .into_iter()
-            .map(|(key, value)| (to_address(key), value.0))
-            .collect::<BTreeMap<
-                katana_primitives::contract::ContractAddress,
-                katana_primitives::class::ClassHash,
-            >>();
+    let mut deployed_contracts = BTreeMap::new();
+    let mut replaced_classes = BTreeMap::new();
 
-    StateUpdatesWithClasses {
-        classes: declared_contract_classes,
-        state_updates: StateUpdates {
-            nonce_updates,
-            storage_updates,
-            declared_classes,
-            deployed_contracts,
-            deprecated_declared_classes,
-            replaced_classes: BTreeMap::default(),
-        },
+    for (address, class_hash) in state_diff.class_hashes {
+        if is_undeployed(address) {
+            deployed_contracts.insert(to_address(address), class_hash.0);
+        } else {
+            replaced_classes.insert(to_address(address), class_hash.0);
+        }
     }
+
+    StateUpdates {
+        nonce_updates,
+        storage_updates,
+        declared_classes,
+        deployed_contracts,
+        deprecated_declared_classes,
+        replaced_classes,
+    }
+}
+
+/// Converts the state changes made by a single transaction into [`StateUpdates`].
+///
+/// `state` must be the state on top of which the transaction was executed.
+pub(super) fn to_tx_state_updates(
+    tx: &ExecutableTxWithHash,
+    state_diff: StateMaps,
+    state: &impl StateReader,
+) -> StateUpdates {
+    // the only class that can be declared by a transaction is the one of the declare transaction
+    let is_legacy = matches!(tx.as_ref(), ExecutableTx::Declare(tx) if tx.class.is_legacy());
+    to_state_updates(state_diff, |_| is_legacy, |address| is_undeployed(state, address))
+}
+
+/// Returns `true` if there is no contract deployed at `address` in `state`.
+fn is_undeployed(state: &impl StateReader, address: ContractAddress) -> bool {
+    state.get_class_hash_at(address).unwrap_or_default() == ClassHash::default()
 }
 
 fn to_api_da_mode(mode: katana_primitives::da::DataAvailabilityMode) -> DataAvailabilityMode {
@@ -512,6 +553,26 @@ fn get_fee_type_from_tx(transaction: &Transaction) -> FeeType {
     }
 }
 
+/// Returns the address of the account (or contract, for L1 handler transactions) that the
+/// transaction is executed on behalf of.
+pub(super) fn sender_address(tx: &ExecutableTx) -> contract::ContractAddress {
+    match tx {
+        ExecutableTx::Invoke(tx) => match tx {
+            InvokeTx::V0(tx) => tx.contract_address,
+            InvokeTx::V1(tx) => tx.sender_address,
+            InvokeTx::V3(tx) => tx.sender_address,
+        },
+        ExecutableTx::Declare(tx) => match &tx.transaction {
+            DeclareTx::V0(tx) => tx.sender_address,
+            DeclareTx::V1(tx) => tx.sender_address,
+            DeclareTx::V2(tx) => tx.sender_address,
+            DeclareTx::V3(tx) => tx.sender_address,
+        },
+        ExecutableTx::DeployAccount(tx) => tx.contract_address(),
+        ExecutableTx::L1Handler(tx) => tx.contract_address,
+    }
+}
+
 pub fn to_blk_address(address: katana_primitives::contract::ContractAddress) -> ContractAddress {
     address.0.try_into().expect("valid address")
 }
@@ -573,6 +634,44 @@ pub fn to_class(class: class::CompiledClass) -> Result<ContractClass, ProgramErr
     }
 }
 
+/// Annotates the felts in a revert reason with their Cairo short string representation, so that
+/// the panic data of a Cairo program is readable, eg `0x4e6f7420656e6f756768` becomes
+/// `0x4e6f7420656e6f756768 ('Not enough')`.
+///
+/// Felts that are already annotated, or that don't represent a printable short string, are left
+/// as is.
+pub(crate) fn decode_revert_reason(reason: &str) -> String {
+    let mut output = String::with_capacity(reason.len());
+    let mut rest = reason;
+
+    while let Some(start) = rest.find("0x") {
+        let (before, after) = rest.split_at(start);
+        output.push_str(before);
+
+        let len = after[2..].find(|c: char| !c.is_ascii_hexdigit()).map_or(after.len(), |i| i + 2);
+        let (felt, remaining) = after.split_at(len);
+        output.push_str(felt);
+
+        let annotated = remaining.starts_with(" ('") || remaining.starts_with("('");
+        if let Some(string) = (!annotated).then(|| printable_short_string(felt)).flatten() {
+            output.push_str(&format!(" ('{string}')"));
+        }
+
+        rest = remaining;
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Decodes a hex encoded felt as a Cairo short string, if it only contains printable characters.
+fn printable_short_string(hex: &str) -> Option<String> {
+    let felt = Felt::from_hex(hex).ok()?;
+    let string = parse_cairo_short_string(&felt).ok()?;
+    let printable = string.chars().all(|c| c.is_ascii_graphic() || c == ' ');
+    (!string.is_empty() && printable).then_some(string)
+}
+
 pub fn to_exec_info(exec_info: TransactionExecutionInfo, r#type: TxType) -> TxExecInfo {
     TxExecInfo {
         r#type,
@@ -580,7 +679,7 @@ pub fn to_exec_info(exec_info: TransactionExecutionInfo, r#type: TxType) -> TxEx
         execute_call_info: exec_info.execute_call_info.map(to_call_info),
         fee_transfer_call_info: exec_info.fee_transfer_call_info.map(to_call_info),
         actual_fee: exec_info.transaction_receipt.fee.0,
-        revert_error: exec_info.revert_error.clone(),
+        revert_error: exec_info.revert_error.as_deref().map(decode_revert_reason),
         actual_resources: TxResources {
             vm_resources: to_execution_resources(
                 exec_info.transaction_receipt.resources.vm_resources,
@@ -850,4 +949,20 @@ mod tests {
         assert_eq!(call.gas_consumed, expected_gas_consumed);
         assert_eq!(call.failed, expected_failed);
     }
+
+    #[test]
+    fn decode_panic_felts_in_revert_reason() {
+        let reason = "Error in contract (0x123): Execution failed. Failure reason: \
+                      0x4e6f7420656e6f7567682062616c616e6365.";
+        let expected = "Error in contract (0x123): Execution failed. Failure reason: \
+                        0x4e6f7420656e6f7567682062616c616e6365 ('Not enough balance').";
+        assert_eq!(decode_revert_reason(reason), expected);
+
+        // already annotated felts are left as is
+        assert_eq!(decode_revert_reason(expected), expected);
+
+        // felts that aren't printable strings are left as is
+        let reason = "Failure reason: (0x1, 0x0).";
+        assert_eq!(decode_revert_reason(reason), reason);
+    }
 }
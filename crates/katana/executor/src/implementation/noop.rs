@@ -120,6 +120,27 @@ impl<'a> BlockExecutor<'a> for NoopExecutor {
     fn block_env(&self) -> BlockEnv {
         self.block_env.clone()
     }
+
+    fn set_storage_at(
+        &mut self,
+        _address: ContractAddress,
+        _key: StorageKey,
+        _value: StorageValue,
+    ) -> ExecutorResult<()> {
+        Ok(())
+    }
+
+    fn set_nonce(&mut self, _address: ContractAddress, _nonce: Nonce) -> ExecutorResult<()> {
+        Ok(())
+    }
+
+    fn set_class_hash_at(
+        &mut self,
+        _address: ContractAddress,
+        _class_hash: ClassHash,
+    ) -> ExecutorResult<()> {
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -3,25 +3,49 @@ use katana_primitives::receipt::{
     DeclareTxReceipt, DeployAccountTxReceipt, Event, InvokeTxReceipt, L1HandlerTxReceipt,
     MessageToL1, Receipt,
 };
-use katana_primitives::trace::{CallInfo, TxExecInfo, TxResources};
-use katana_primitives::transaction::TxRef;
-use tracing::trace;
+use katana_primitives::trace::{CallInfo, TxExecInfo};
+use katana_primitives::transaction::{TxHash, TxRef};
+use tracing::info;
 
 pub(crate) const LOG_TARGET: &str = "executor";
 
-pub fn log_resources(resources: &TxResources) {
-    let mut mapped_strings = Vec::new();
-
-    for (builtin, count) in &resources.vm_resources.builtin_instance_counter {
-        mapped_strings.push(format!("{builtin}: {count}"));
-    }
-
-    // Sort the strings alphabetically
-    mapped_strings.sort();
-    mapped_strings.insert(0, format!("steps: {}", resources.vm_resources.n_steps));
-    mapped_strings.insert(1, format!("memory holes: {}", resources.vm_resources.n_memory_holes));
-
-    trace!(target: LOG_TARGET, usage = mapped_strings.join(" | "), "Transaction resource usage.");
+/// Logs a summary of the execution of a transaction that is included in a block, as a single
+/// structured event.
+pub fn log_execution_summary(hash: TxHash, receipt: &Receipt) {
+    let resources = receipt.resources_used();
+    let fee = receipt.fee();
+
+    let r#type = match receipt {
+        Receipt::Invoke(_) => "invoke",
+        Receipt::Declare(_) => "declare",
+        Receipt::L1Handler(_) => "l1_handler",
+        Receipt::DeployAccount(_) => "deploy_account",
+    };
+
+    let mut builtins = resources
+        .vm_resources
+        .builtin_instance_counter
+        .iter()
+        .map(|(builtin, count)| format!("{builtin}: {count}"))
+        .collect::<Vec<_>>();
+    builtins.sort();
+
+    info!(
+        target: LOG_TARGET,
+        tx_hash = format!("{hash:#x}"),
+        r#type,
+        steps = resources.vm_resources.n_steps,
+        memory_holes = resources.vm_resources.n_memory_holes,
+        reverted_steps = resources.n_reverted_steps,
+        builtins = builtins.join(" | "),
+        events = receipt.events().len(),
+        messages_sent = receipt.messages_sent().len(),
+        gas_consumed = fee.gas_consumed,
+        fee = fee.overall_fee,
+        fee_unit = ?fee.unit,
+        revert_reason = receipt.revert_reason(),
+        "Transaction executed."
+    );
 }
 
 pub(crate) fn build_receipt(tx: TxRef<'_>, fee: TxFeeInfo, info: &TxExecInfo) -> Receipt {
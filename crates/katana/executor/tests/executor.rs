@@ -322,8 +322,14 @@ fn test_executor_with_valid_blocks_impl<EF: ExecutorFactory>(
 
 #[cfg(feature = "blockifier")]
 mod blockifier {
+    use std::num::NonZeroUsize;
+
     use fixtures::blockifier::factory;
+    use fixtures::{cfg, flags};
     use katana_executor::implementation::blockifier::BlockifierFactory;
+    use katana_executor::{BlockLimits, ExecutionFlags, ExecutorError};
+    use katana_primitives::env::{BlockEnv, CfgEnv};
+    use katana_primitives::transaction::{ExecutableTx, ExecutableTxWithHash, InvokeTx, InvokeTxV1};
 
     use super::*;
 
@@ -335,4 +341,116 @@ mod blockifier {
     ) {
         test_executor_with_valid_blocks_impl(factory, state, blocks)
     }
+
+    #[rstest::rstest]
+    fn test_executor_with_valid_blocks_in_parallel(
+        mut factory: BlockifierFactory,
+        #[from(state_provider)] state: Box<dyn StateProvider>,
+        #[from(valid_blocks)] blocks: [ExecutableBlock; 3],
+    ) {
+        factory.set_concurrency(NonZeroUsize::new(4).unwrap());
+        test_executor_with_valid_blocks_impl(factory, state, blocks)
+    }
+
+    #[cfg(feature = "native")]
+    #[rstest::rstest]
+    fn test_executor_with_valid_blocks_natively(
+        mut factory: BlockifierFactory,
+        #[from(state_provider)] state: Box<dyn StateProvider>,
+        #[from(valid_blocks)] blocks: [ExecutableBlock; 3],
+    ) {
+        factory.enable_native();
+        test_executor_with_valid_blocks_impl(factory, state, blocks)
+    }
+
+    #[rstest::rstest]
+    fn test_parallel_execution_with_conflicting_transactions(
+        mut factory: BlockifierFactory,
+        #[from(state_provider)] state: Box<dyn StateProvider>,
+        #[from(state_provider)] parallel_state: Box<dyn StateProvider>,
+        #[from(valid_blocks)] blocks: [ExecutableBlock; 3],
+    ) {
+        let [block, ..] = blocks;
+        let block_env = BlockEnv {
+            number: block.header.number,
+            timestamp: block.header.timestamp,
+            l1_gas_prices: block.header.l1_gas_prices,
+            l1_data_gas_prices: block.header.l1_data_gas_prices,
+            sequencer_address: block.header.sequencer_address,
+        };
+
+        // two transfers from the same account, the second one reads the nonce and the balance that
+        // are written by the first one, so its speculative execution is stale and it must be
+        // re-executed on top of the state left by the first one
+        let transfer = block.body[0].clone();
+        let ExecutableTx::Invoke(InvokeTx::V1(tx)) = transfer.transaction.clone() else {
+            panic!("expected an invoke transaction")
+        };
+
+        let main_account = tx.sender_address;
+        let next_transfer = InvokeTxV1 { nonce: Felt::ONE, ..tx };
+        let next_transfer =
+            ExecutableTxWithHash::new(ExecutableTx::Invoke(InvokeTx::V1(next_transfer)));
+        let transactions = vec![transfer, next_transfer];
+
+        let execute = |factory: &BlockifierFactory, state: Box<dyn StateProvider>| {
+            let mut executor = factory.with_state_and_block_env(state, block_env.clone());
+            let (executed, error) = executor.execute_transactions(transactions.clone()).unwrap();
+
+            assert_eq!(executed, 2);
+            assert!(error.is_none());
+
+            executor.take_execution_output().unwrap()
+        };
+
+        let expected = execute(&factory, state);
+        factory.set_concurrency(NonZeroUsize::new(2).unwrap());
+        let actual = execute(&factory, parallel_state);
+
+        for ((_, expected), (_, actual)) in expected.transactions.iter().zip(&actual.transactions) {
+            let (
+                ExecutionResult::Success { receipt: expected, .. },
+                ExecutionResult::Success { receipt: actual, .. },
+            ) = (expected, actual)
+            else {
+                panic!("all transactions should succeed")
+            };
+
+            similar_asserts::assert_eq!(actual, expected);
+        }
+
+        let nonce = actual.states.state_updates.nonce_updates.get(&main_account);
+        assert_eq!(nonce, Some(&Felt::TWO));
+        similar_asserts::assert_eq!(actual.states.state_updates, expected.states.state_updates);
+    }
+
+    #[rstest::rstest]
+    fn test_executor_with_transactions_limit(
+        cfg: CfgEnv,
+        #[with(true)] flags: ExecutionFlags,
+        #[from(state_provider)] state: Box<dyn StateProvider>,
+        #[from(valid_blocks)] blocks: [ExecutableBlock; 3],
+    ) {
+        let limits = BlockLimits { transactions: 1, ..BlockLimits::max() };
+        let factory = BlockifierFactory::new(cfg, flags, limits);
+
+        let [block, ..] = blocks;
+        let block_env = BlockEnv {
+            number: block.header.number,
+            timestamp: block.header.timestamp,
+            l1_gas_prices: block.header.l1_gas_prices,
+            l1_data_gas_prices: block.header.l1_data_gas_prices,
+            sequencer_address: block.header.sequencer_address,
+        };
+
+        let mut executor = factory.with_state_and_block_env(state, block_env);
+        let (executed, error) = executor.execute_transactions(block.body).unwrap();
+
+        assert_eq!(executed, 1, "only one transaction should be executed");
+        assert!(matches!(error, Some(ExecutorError::LimitsExhausted)));
+
+        let transactions = executor.transactions();
+        assert_eq!(transactions.len(), 1);
+        assert!(transactions[0].1.is_success());
+    }
 }
@@ -6,7 +6,7 @@ use katana_executor::{ExecutionFlags, ExecutionOutput, ExecutorFactory};
 use katana_primitives::block::GasPrices;
 use katana_primitives::env::BlockEnv;
 use katana_primitives::fee::PriceUnit;
-use katana_primitives::transaction::ExecutableTxWithHash;
+use katana_primitives::transaction::{ExecutableTx, ExecutableTxWithHash, InvokeTx};
 use katana_provider::traits::state::StateProvider;
 use rstest_reuse::{self, *};
 use starknet::macros::felt;
@@ -48,6 +48,11 @@ fn test_simulate_tx_impl<EF: ExecutorFactory>(
     let fees = executor.estimate_fee(transactions, flags);
 
     assert!(results.iter().all(|res| res.result.is_success()), "all txs should be successful");
+    // the state diff of each simulated tx should at least include the sender's nonce update
+    assert!(
+        results.iter().all(|res| !res.states.nonce_updates.is_empty()),
+        "simulated txs should have a state diff"
+    );
     assert!(fees.iter().all(|res| {
         match res {
             // makes sure that the fee is non-zero
@@ -94,4 +99,21 @@ mod blockifier {
     ) {
         test_simulate_tx_impl(executor_factory, block_env, state_provider, tx, flags);
     }
+
+    #[rstest::rstest]
+    fn test_simulate_tx_no_signature_impersonated(
+        #[with(factory::default())] executor_factory: BlockifierFactory,
+        block_env: BlockEnv,
+        state_provider: Box<dyn StateProvider>,
+        #[with(false)] executable_tx: ExecutableTxWithHash,
+    ) {
+        let ExecutableTx::Invoke(InvokeTx::V1(ref tx)) = executable_tx.transaction else {
+            panic!("expected an invoke v1 transaction");
+        };
+
+        let flags = ExecutionFlags::new();
+        flags.impersonated_accounts().add(tx.sender_address);
+
+        test_simulate_tx_impl(executor_factory, block_env, state_provider, executable_tx, flags);
+    }
 }
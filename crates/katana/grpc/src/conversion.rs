@@ -0,0 +1,451 @@
+//! Conversions between the Katana primitive types and their protobuf representation.
+//!
+//! The strings of the protobuf messages, like the type of a transaction or the status of a
+//! receipt, use the same values as the Starknet JSON-RPC specification.
+
+use katana_primitives::block::{FinalityStatus, SealedBlock};
+use katana_primitives::contract::ContractAddress;
+use katana_primitives::da::{DataAvailabilityMode, L1DataAvailabilityMode};
+use katana_primitives::fee::{self, PriceUnit};
+use katana_primitives::receipt::{self, Receipt, ReceiptWithTxHash};
+use katana_primitives::state::StateUpdates;
+use katana_primitives::trace::TxResources;
+use katana_primitives::transaction::{DeclareTx, DeployAccountTx, InvokeTx, Tx};
+use katana_primitives::Felt;
+
+use crate::proto::types as proto;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConversionError {
+    #[error("invalid felt of {0} bytes, expected at most 32 bytes")]
+    InvalidFelt(usize),
+    #[error("missing field `{0}`")]
+    MissingField(&'static str),
+    #[error("{0} transaction is not supported")]
+    UnsupportedTransaction(&'static str),
+}
+
+impl From<Felt> for proto::Felt {
+    fn from(value: Felt) -> Self {
+        Self { value: value.to_bytes_be().to_vec() }
+    }
+}
+
+impl From<ContractAddress> for proto::Felt {
+    fn from(value: ContractAddress) -> Self {
+        Felt::from(value).into()
+    }
+}
+
+impl TryFrom<proto::Felt> for Felt {
+    type Error = ConversionError;
+
+    fn try_from(value: proto::Felt) -> Result<Self, Self::Error> {
+        if value.value.len() > 32 {
+            return Err(ConversionError::InvalidFelt(value.value.len()));
+        }
+
+        Ok(Felt::from_bytes_be_slice(&value.value))
+    }
+}
+
+impl From<&SealedBlock> for proto::BlockHeader {
+    fn from(block: &SealedBlock) -> Self {
+        let header = &block.header;
+
+        Self {
+            block_hash: Some(block.hash.into()),
+            parent_hash: Some(header.parent_hash.into()),
+            block_number: header.number,
+            new_root: Some(header.state_root.into()),
+            timestamp: header.timestamp,
+            sequencer_address: Some(header.sequencer_address.into()),
+            l1_gas_price: Some(proto::ResourcePrice {
+                price_in_wei: Some(Felt::from(header.l1_gas_prices.eth).into()),
+                price_in_fri: Some(Felt::from(header.l1_gas_prices.strk).into()),
+            }),
+            l1_data_gas_price: Some(proto::ResourcePrice {
+                price_in_wei: Some(Felt::from(header.l1_data_gas_prices.eth).into()),
+                price_in_fri: Some(Felt::from(header.l1_data_gas_prices.strk).into()),
+            }),
+            l1_da_mode: match header.l1_da_mode {
+                L1DataAvailabilityMode::Blob => "BLOB",
+                L1DataAvailabilityMode::Calldata => "CALLDATA",
+            }
+            .to_string(),
+            starknet_version: header.protocol_version.to_string(),
+        }
+    }
+}
+
+impl From<StateUpdates> for proto::StateDiff {
+    fn from(updates: StateUpdates) -> Self {
+        Self {
+            storage_diffs: updates
+                .storage_updates
+                .into_iter()
+                .map(|(address, entries)| proto::StorageDiff {
+                    address: Some(address.into()),
+                    storage_entries: entries
+                        .into_iter()
+                        .map(|(key, value)| proto::StorageEntry {
+                            key: Some(key.into()),
+                            value: Some(value.into()),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            deprecated_declared_classes: updates
+                .deprecated_declared_classes
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            declared_classes: updates
+                .declared_classes
+                .into_iter()
+                .map(|(class_hash, compiled_class_hash)| proto::DeclaredClass {
+                    class_hash: Some(class_hash.into()),
+                    compiled_class_hash: Some(compiled_class_hash.into()),
+                })
+                .collect(),
+            deployed_contracts: updates
+                .deployed_contracts
+                .into_iter()
+                .map(|(address, class_hash)| proto::DeployedContract {
+                    address: Some(address.into()),
+                    class_hash: Some(class_hash.into()),
+                })
+                .collect(),
+            replaced_classes: updates
+                .replaced_classes
+                .into_iter()
+                .map(|(address, class_hash)| proto::ReplacedClass {
+                    contract_address: Some(address.into()),
+                    class_hash: Some(class_hash.into()),
+                })
+                .collect(),
+            nonces: updates
+                .nonce_updates
+                .into_iter()
+                .map(|(address, nonce)| proto::Nonce {
+                    contract_address: Some(address.into()),
+                    nonce: Some(nonce.into()),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::StateDiff> for StateUpdates {
+    type Error = ConversionError;
+
+    fn try_from(diff: proto::StateDiff) -> Result<Self, Self::Error> {
+        let mut updates = StateUpdates::default();
+
+        for storage_diff in diff.storage_diffs {
+            let address = felt(storage_diff.address, "address")?.into();
+            let entries = updates.storage_updates.entry(address).or_default();
+
+            for entry in storage_diff.storage_entries {
+                entries.insert(felt(entry.key, "key")?, felt(entry.value, "value")?);
+            }
+        }
+
+        for class_hash in diff.deprecated_declared_classes {
+            updates.deprecated_declared_classes.insert(class_hash.try_into()?);
+        }
+
+        for class in diff.declared_classes {
+            updates.declared_classes.insert(
+                felt(class.class_hash, "class_hash")?,
+                felt(class.compiled_class_hash, "compiled_class_hash")?,
+            );
+        }
+
+        for contract in diff.deployed_contracts {
+            updates.deployed_contracts.insert(
+                felt(contract.address, "address")?.into(),
+                felt(contract.class_hash, "class_hash")?,
+            );
+        }
+
+        for class in diff.replaced_classes {
+            updates.replaced_classes.insert(
+                felt(class.contract_address, "contract_address")?.into(),
+                felt(class.class_hash, "class_hash")?,
+            );
+        }
+
+        for nonce in diff.nonces {
+            updates.nonce_updates.insert(
+                felt(nonce.contract_address, "contract_address")?.into(),
+                felt(nonce.nonce, "nonce")?,
+            );
+        }
+
+        Ok(updates)
+    }
+}
+
+impl From<receipt::Event> for proto::Event {
+    fn from(event: receipt::Event) -> Self {
+        Self {
+            from_address: Some(event.from_address.into()),
+            keys: felts(event.keys),
+            data: felts(event.data),
+        }
+    }
+}
+
+impl From<receipt::MessageToL1> for proto::MessageToL1 {
+    fn from(message: receipt::MessageToL1) -> Self {
+        Self {
+            from_address: Some(message.from_address.into()),
+            to_address: Some(message.to_address.into()),
+            payload: felts(message.payload),
+        }
+    }
+}
+
+impl From<&TxResources> for proto::ExecutionResources {
+    fn from(resources: &TxResources) -> Self {
+        let vm = &resources.vm_resources;
+        let builtins = &vm.builtin_instance_counter;
+
+        Self {
+            steps: vm.n_steps as u64,
+            memory_holes: vm.n_memory_holes as u64,
+            range_check_builtin_applications: builtins.range_check().unwrap_or_default(),
+            pedersen_builtin_applications: builtins.pedersen().unwrap_or_default(),
+            poseidon_builtin_applications: builtins.poseidon().unwrap_or_default(),
+            ec_op_builtin_applications: builtins.ec_op().unwrap_or_default(),
+            ecdsa_builtin_applications: builtins.ecdsa().unwrap_or_default(),
+            bitwise_builtin_applications: builtins.bitwise().unwrap_or_default(),
+            keccak_builtin_applications: builtins.keccak().unwrap_or_default(),
+            segment_arena_builtin: builtins.segment_arena().unwrap_or_default(),
+            data_availability: Some(proto::DataAvailability {
+                l1_gas: resources.data_availability.l1_gas as u64,
+                l1_data_gas: resources.data_availability.l1_data_gas as u64,
+            }),
+        }
+    }
+}
+
+impl proto::TransactionReceipt {
+    /// Creates the protobuf receipt of a transaction, with the finality status of its block.
+    pub fn new(receipt: ReceiptWithTxHash, finality_status: FinalityStatus) -> Self {
+        let r#type = match receipt.receipt {
+            Receipt::Invoke(_) => "INVOKE",
+            Receipt::Declare(_) => "DECLARE",
+            Receipt::L1Handler(_) => "L1_HANDLER",
+            Receipt::DeployAccount(_) => "DEPLOY_ACCOUNT",
+        };
+
+        let fee = receipt.fee();
+        let (execution_status, revert_reason) = match receipt.revert_reason() {
+            Some(reason) => ("REVERTED", reason.to_string()),
+            None => ("SUCCEEDED", String::new()),
+        };
+
+        Self {
+            r#type: r#type.to_string(),
+            transaction_hash: Some(receipt.tx_hash.into()),
+            actual_fee: Some(proto::FeePayment {
+                amount: Some(Felt::from(fee.overall_fee).into()),
+                unit: match fee.unit {
+                    PriceUnit::Wei => "WEI",
+                    PriceUnit::Fri => "FRI",
+                }
+                .to_string(),
+            }),
+            finality_status: match finality_status {
+                FinalityStatus::AcceptedOnL2 => "ACCEPTED_ON_L2",
+                FinalityStatus::AcceptedOnL1 => "ACCEPTED_ON_L1",
+            }
+            .to_string(),
+            messages_sent: receipt.messages_sent().iter().cloned().map(Into::into).collect(),
+            events: receipt.events().iter().cloned().map(Into::into).collect(),
+            execution_resources: Some(receipt.resources_used().into()),
+            execution_status: execution_status.to_string(),
+            revert_reason,
+        }
+    }
+}
+
+impl TryFrom<Tx> for proto::Transaction {
+    type Error = ConversionError;
+
+    fn try_from(tx: Tx) -> Result<Self, Self::Error> {
+        use proto::transaction::Transaction;
+
+        let transaction = match tx {
+            Tx::Invoke(InvokeTx::V1(tx)) => Transaction::InvokeV1(proto::InvokeTxnV1 {
+                max_fee: Some(Felt::from(tx.max_fee).into()),
+                version: "0x1".to_string(),
+                signature: felts(tx.signature),
+                nonce: Some(tx.nonce.into()),
+                r#type: "INVOKE".to_string(),
+                sender_address: Some(tx.sender_address.into()),
+                calldata: felts(tx.calldata),
+            }),
+            Tx::Invoke(InvokeTx::V3(tx)) => Transaction::InvokeV3(proto::InvokeTxnV3 {
+                r#type: "INVOKE".to_string(),
+                sender_address: Some(tx.sender_address.into()),
+                calldata: felts(tx.calldata),
+                version: "0x3".to_string(),
+                signature: felts(tx.signature),
+                nonce: Some(tx.nonce.into()),
+                resource_bounds: Some(tx.resource_bounds.into()),
+                tip: Some(Felt::from(tx.tip).into()),
+                paymaster_data: felts(tx.paymaster_data),
+                account_deployment_data: felts(tx.account_deployment_data),
+                nonce_data_availability_mode: da_mode(tx.nonce_data_availability_mode),
+                fee_data_availability_mode: da_mode(tx.fee_data_availability_mode),
+            }),
+            Tx::Declare(DeclareTx::V1(tx)) => Transaction::DeclareV1(proto::DeclareTxnV1 {
+                max_fee: Some(Felt::from(tx.max_fee).into()),
+                version: "0x1".to_string(),
+                signature: felts(tx.signature),
+                nonce: Some(tx.nonce.into()),
+                r#type: "DECLARE".to_string(),
+                class_hash: Some(tx.class_hash.into()),
+                sender_address: Some(tx.sender_address.into()),
+            }),
+            Tx::Declare(DeclareTx::V3(tx)) => Transaction::DeclareV3(proto::DeclareTxnV3 {
+                r#type: "DECLARE".to_string(),
+                sender_address: Some(tx.sender_address.into()),
+                compiled_class_hash: Some(tx.compiled_class_hash.into()),
+                version: "0x3".to_string(),
+                signature: felts(tx.signature),
+                nonce: Some(tx.nonce.into()),
+                class_hash: Some(tx.class_hash.into()),
+                resource_bounds: Some(tx.resource_bounds.into()),
+                tip: Some(Felt::from(tx.tip).into()),
+                paymaster_data: felts(tx.paymaster_data),
+                account_deployment_data: felts(tx.account_deployment_data),
+                nonce_data_availability_mode: da_mode(tx.nonce_data_availability_mode),
+                fee_data_availability_mode: da_mode(tx.fee_data_availability_mode),
+            }),
+            Tx::DeployAccount(DeployAccountTx::V1(tx)) => {
+                Transaction::DeployAccount(proto::DeployAccountTxn {
+                    max_fee: Some(Felt::from(tx.max_fee).into()),
+                    version: "0x1".to_string(),
+                    signature: felts(tx.signature),
+                    nonce: Some(tx.nonce.into()),
+                    r#type: "DEPLOY_ACCOUNT".to_string(),
+                    class_hash: Some(tx.class_hash.into()),
+                    contract_address_salt: Some(tx.contract_address_salt.into()),
+                    constructor_calldata: felts(tx.constructor_calldata),
+                })
+            }
+            Tx::DeployAccount(DeployAccountTx::V3(tx)) => {
+                Transaction::DeployAccountV3(proto::DeployAccountTxnV3 {
+                    r#type: "DEPLOY_ACCOUNT".to_string(),
+                    version: "0x3".to_string(),
+                    signature: felts(tx.signature),
+                    nonce: Some(tx.nonce.into()),
+                    contract_address_salt: Some(tx.contract_address_salt.into()),
+                    constructor_calldata: felts(tx.constructor_calldata),
+                    class_hash: Some(tx.class_hash.into()),
+                    resource_bounds: Some(tx.resource_bounds.into()),
+                    tip: Some(Felt::from(tx.tip).into()),
+                    paymaster_data: felts(tx.paymaster_data),
+                    nonce_data_availability_mode: da_mode(tx.nonce_data_availability_mode),
+                    fee_data_availability_mode: da_mode(tx.fee_data_availability_mode),
+                })
+            }
+            // The protobuf declare v2 transaction embeds the contract class, which is not part of
+            // the transaction itself.
+            Tx::Declare(DeclareTx::V2(_)) => {
+                return Err(ConversionError::UnsupportedTransaction("declare v2"));
+            }
+            Tx::Declare(DeclareTx::V0(_)) => {
+                return Err(ConversionError::UnsupportedTransaction("declare v0"));
+            }
+            Tx::Invoke(InvokeTx::V0(_)) => {
+                return Err(ConversionError::UnsupportedTransaction("invoke v0"));
+            }
+            Tx::L1Handler(_) => return Err(ConversionError::UnsupportedTransaction("l1 handler")),
+            Tx::Deploy(_) => return Err(ConversionError::UnsupportedTransaction("deploy")),
+        };
+
+        Ok(Self { transaction: Some(transaction) })
+    }
+}
+
+impl From<fee::ResourceBoundsMapping> for proto::ResourceBoundsMapping {
+    fn from(bounds: fee::ResourceBoundsMapping) -> Self {
+        Self { l1_gas: Some(bounds.l1_gas.into()), l2_gas: Some(bounds.l2_gas.into()) }
+    }
+}
+
+impl From<fee::ResourceBounds> for proto::ResourceBounds {
+    fn from(bounds: fee::ResourceBounds) -> Self {
+        Self {
+            max_amount: Some(Felt::from(bounds.max_amount).into()),
+            max_price_per_unit: Some(Felt::from(bounds.max_price_per_unit).into()),
+        }
+    }
+}
+
+fn felts(values: Vec<Felt>) -> Vec<proto::Felt> {
+    values.into_iter().map(Into::into).collect()
+}
+
+fn felt(value: Option<proto::Felt>, field: &'static str) -> Result<Felt, ConversionError> {
+    value.ok_or(ConversionError::MissingField(field))?.try_into()
+}
+
+fn da_mode(mode: DataAvailabilityMode) -> String {
+    match mode {
+        DataAvailabilityMode::L1 => "L1",
+        DataAvailabilityMode::L2 => "L2",
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn felt_roundtrip() {
+        let value = Felt::from(0x1337u64);
+        let proto = proto::Felt::from(value);
+
+        assert_eq!(proto.value.len(), 32);
+        assert_eq!(Felt::try_from(proto).unwrap(), value);
+        assert!(Felt::try_from(proto::Felt { value: vec![1; 33] }).is_err());
+    }
+
+    #[test]
+    fn state_diff_roundtrip() {
+        let mut updates = StateUpdates::default();
+        updates.nonce_updates.insert(Felt::from(0x1u64).into(), Felt::from(0x2u64));
+        updates
+            .storage_updates
+            .entry(Felt::from(0x1u64).into())
+            .or_default()
+            .insert(Felt::from(0x3u64), Felt::from(0x4u64));
+        updates.deployed_contracts.insert(Felt::from(0x5u64).into(), Felt::from(0x6u64));
+        updates.declared_classes.insert(Felt::from(0x6u64), Felt::from(0x7u64));
+        updates.deprecated_declared_classes.insert(Felt::from(0x8u64));
+        updates.replaced_classes.insert(Felt::from(0x1u64).into(), Felt::from(0x9u64));
+
+        let diff = proto::StateDiff::from(updates.clone());
+        assert_eq!(StateUpdates::try_from(diff).unwrap(), updates);
+    }
+
+    #[test]
+    fn state_diff_missing_field() {
+        let diff = proto::StateDiff {
+            nonces: vec![proto::Nonce { contract_address: None, nonce: Some(Felt::ONE.into()) }],
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            StateUpdates::try_from(diff),
+            Err(ConversionError::MissingField("contract_address"))
+        ));
+    }
+}
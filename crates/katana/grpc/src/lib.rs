@@ -1 +1,16 @@
 //! gRPC implementations.
+//!
+//! The protobuf definitions of the Starknet types are exposed in [`proto`], with conversions from
+//! and to the Katana primitive types in [`conversion`].
+
+pub mod conversion;
+
+pub mod proto {
+    pub mod types {
+        tonic::include_proto!("types");
+    }
+
+    pub mod starknet {
+        tonic::include_proto!("starknet");
+    }
+}
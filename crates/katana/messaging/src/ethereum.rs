@@ -18,9 +18,9 @@ use katana_primitives::utils::transaction::{
 };
 use katana_primitives::Felt;
 use starknet::core::types::EthAddress;
-use tracing::{debug, trace};
+use tracing::{debug, error, trace};
 
-use super::{MessagingConfig, Messenger, MessengerResult, LOG_TARGET};
+use super::{Error, MessagingConfig, Messenger, MessengerResult, LOG_TARGET};
 
 sol! {
     #[sol(rpc, rename_all = "snakecase")]
@@ -145,6 +145,56 @@ impl Messenger for EthereumMessaging {
 
         Ok((to_block, l1_handler_txs))
     }
+
+    /// The message hashes are added directly to the `StarknetMessagingLocal` contract, which makes
+    /// them consumable on L1 without having to wait for the block to be proven. The transaction is
+    /// sent from the first unlocked account of the settlement node (eg, one of Anvil's default
+    /// accounts).
+    async fn send_messages(
+        &self,
+        messages: &[MessageToL1],
+    ) -> MessengerResult<Vec<Self::MessageHash>> {
+        if messages.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let hashes = parse_messages(messages);
+
+        let accounts = self.provider.get_accounts().await?;
+        let Some(sender) = accounts.first().copied() else {
+            error!(target: LOG_TARGET, "No unlocked account available on the settlement chain.");
+            return Err(Error::SendError);
+        };
+
+        let contract = StarknetMessagingLocal::new(self.messaging_contract_address, &self.provider);
+        let call = contract.addMessageHashesFromL2(hashes.clone()).from(sender);
+
+        let receipt = match call.send().await {
+            Ok(pending) => pending.get_receipt().await.map_err(|error| {
+                error!(target: LOG_TARGET, %error, "Waiting for messages registration.");
+                Error::SendError
+            })?,
+            Err(error) => {
+                error!(target: LOG_TARGET, %error, "Sending messages to settlement chain.");
+                return Err(Error::SendError);
+            }
+        };
+
+        if !receipt.status() {
+            let tx_hash = receipt.transaction_hash;
+            error!(target: LOG_TARGET, %tx_hash, "Messages registration reverted.");
+            return Err(Error::SendError);
+        }
+
+        trace!(
+            target: LOG_TARGET,
+            tx_hash = %receipt.transaction_hash,
+            count = hashes.len(),
+            "Messages sent to settlement chain."
+        );
+
+        Ok(hashes)
+    }
 }
 
 // TODO: refactor this as a method of the message log struct
@@ -50,6 +50,7 @@ use async_trait::async_trait;
 use ethereum::EthereumMessaging;
 use futures::StreamExt;
 use katana_primitives::chain::ChainId;
+use katana_primitives::receipt::MessageToL1;
 use serde::{Deserialize, Serialize};
 use tracing::{error, info, trace};
 
@@ -71,6 +72,10 @@ pub enum Error {
     UnsupportedChain,
     #[error("Failed to gather messages from settlement chain")]
     GatherError,
+    #[error("Failed to send messages to settlement chain")]
+    SendError,
+    #[error("No account configured to send messages to settlement chain")]
+    MissingSender,
     #[error(transparent)]
     Provider(ProviderError),
 }
@@ -103,6 +108,14 @@ pub struct MessagingConfig {
     pub interval: u64,
     /// The block on settlement chain from where Katana will start fetching messages.
     pub from_block: u64,
+    /// The address of the account used to send messages to the settlement chain.
+    ///
+    /// Only required when the settlement chain is a Starknet chain.
+    #[serde(default)]
+    pub sender_address: String,
+    /// The private key of the account used to send messages to the settlement chain.
+    #[serde(default)]
+    pub private_key: String,
 }
 
 impl MessagingConfig {
@@ -127,6 +140,7 @@ impl MessagingConfig {
                 contract_address: core_contract.to_string(),
                 from_block: *block,
                 interval: 2,
+                ..Default::default()
             },
             katana_chain_spec::SettlementLayer::Starknet {
                 rpc_url, core_contract, block, ..
@@ -136,6 +150,7 @@ impl MessagingConfig {
                 contract_address: core_contract.to_string(),
                 from_block: *block,
                 interval: 2,
+                ..Default::default()
             },
             katana_chain_spec::SettlementLayer::Sovereign { .. } => Self {
                 chain: CONFIG_CHAIN_SOVEREIGN.to_string(),
@@ -173,6 +188,17 @@ pub trait Messenger {
         max_blocks: u64,
         chain_id: ChainId,
     ) -> MessengerResult<(u64, Vec<Self::MessageTransaction>)>;
+
+    /// Computes the hashes of the given messages and sends them to the settlement chain, where
+    /// they can then be consumed. Returns the hashes of the sent messages.
+    ///
+    /// # Arguments
+    ///
+    /// * `messages` - The messages sent by the locally executed transactions, in order.
+    async fn send_messages(
+        &self,
+        messages: &[MessageToL1],
+    ) -> MessengerResult<Vec<Self::MessageHash>>;
 }
 
 #[derive(Debug)]
@@ -3,10 +3,12 @@ use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::time::Duration;
 
-use futures::{Future, FutureExt, Stream};
+use futures::stream::BoxStream;
+use futures::{Future, FutureExt, Stream, StreamExt};
 use katana_chain_spec::ChainSpec;
 use katana_pool::{TransactionPool, TxPool};
 use katana_primitives::chain::ChainId;
+use katana_primitives::receipt::MessageToL1;
 use katana_primitives::transaction::{ExecutableTxWithHash, L1HandlerTx, TxHash};
 use tokio::time::{interval_at, Instant, Interval};
 use tracing::{error, info, warn};
@@ -15,6 +17,7 @@ use super::{MessagingConfig, Messenger, MessengerMode, MessengerResult, LOG_TARG
 
 type MessagingFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
 type MessageGatheringFuture = MessagingFuture<MessengerResult<(u64, usize)>>;
+type MessageSendingFuture = MessagingFuture<(Vec<MessageToL1>, MessengerResult<usize>)>;
 
 #[allow(missing_debug_implementations)]
 pub struct MessagingService {
@@ -28,6 +31,12 @@ pub struct MessagingService {
     gather_from_block: u64,
     /// The message gathering future.
     msg_gather_fut: Option<MessageGatheringFuture>,
+    /// The stream of messages sent to the settlement chain by the locally mined blocks.
+    messages_to_send: Option<BoxStream<'static, Vec<MessageToL1>>>,
+    /// The messages waiting to be sent to the settlement chain, in the order they were produced.
+    pending_messages: Vec<MessageToL1>,
+    /// The message sending future.
+    msg_send_fut: Option<MessageSendingFuture>,
 }
 
 impl MessagingService {
@@ -50,7 +59,44 @@ impl MessagingService {
             }
         };
 
-        Ok(Self { pool, interval, messenger, chain_spec, gather_from_block, msg_gather_fut: None })
+        Ok(Self {
+            pool,
+            interval,
+            messenger,
+            chain_spec,
+            gather_from_block,
+            msg_gather_fut: None,
+            messages_to_send: None,
+            pending_messages: Vec::new(),
+            msg_send_fut: None,
+        })
+    }
+
+    /// Sets the stream of L2 -> L1 messages that the service should send to the settlement chain.
+    ///
+    /// Each item of the stream is the list of messages sent by the transactions of a mined block.
+    pub fn with_messages_to_send(mut self, messages: BoxStream<'static, Vec<MessageToL1>>) -> Self {
+        self.messages_to_send = Some(messages);
+        self
+    }
+
+    async fn send_messages(
+        messenger: Arc<MessengerMode>,
+        messages: Vec<MessageToL1>,
+    ) -> (Vec<MessageToL1>, MessengerResult<usize>) {
+        let result = match messenger.as_ref() {
+            MessengerMode::Ethereum(inner) => {
+                inner.send_messages(&messages).await.map(|hashes| hashes.len())
+            }
+
+            MessengerMode::Starknet(inner) => {
+                inner.send_messages(&messages).await.map(|hashes| hashes.len())
+            }
+
+            MessengerMode::Sovereign(_) => Ok(0),
+        };
+
+        (messages, result)
     }
 
     async fn gather_messages(
@@ -117,13 +163,46 @@ impl Stream for MessagingService {
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let pin = self.get_mut();
 
-        if pin.interval.poll_tick(cx).is_ready() && pin.msg_gather_fut.is_none() {
-            pin.msg_gather_fut = Some(Box::pin(Self::gather_messages(
-                pin.messenger.clone(),
-                pin.pool.clone(),
-                pin.chain_spec.id(),
-                pin.gather_from_block,
-            )));
+        // Collect the messages of the newly mined blocks.
+        if let Some(stream) = pin.messages_to_send.as_mut() {
+            while let Poll::Ready(Some(messages)) = stream.poll_next_unpin(cx) {
+                pin.pending_messages.extend(messages);
+            }
+        }
+
+        if pin.interval.poll_tick(cx).is_ready() {
+            if pin.msg_gather_fut.is_none() {
+                pin.msg_gather_fut = Some(Box::pin(Self::gather_messages(
+                    pin.messenger.clone(),
+                    pin.pool.clone(),
+                    pin.chain_spec.id(),
+                    pin.gather_from_block,
+                )));
+            }
+
+            if pin.msg_send_fut.is_none() && !pin.pending_messages.is_empty() {
+                let messages = std::mem::take(&mut pin.pending_messages);
+                pin.msg_send_fut =
+                    Some(Box::pin(Self::send_messages(pin.messenger.clone(), messages)));
+            }
+        }
+
+        // Poll the sending future.
+        if let Some(mut send_fut) = pin.msg_send_fut.take() {
+            match send_fut.poll_unpin(cx) {
+                Poll::Ready((_, Ok(count))) => {
+                    if count > 0 {
+                        info!(target: LOG_TARGET, %count, "Sent messages to settlement chain.");
+                    }
+                }
+                Poll::Ready((mut messages, Err(e))) => {
+                    error!(target: LOG_TARGET, error = %e, "Sending messages to settlement chain.");
+                    // Retry at the next tick, while preserving the order of the messages.
+                    messages.append(&mut pin.pending_messages);
+                    pin.pending_messages = messages;
+                }
+                Poll::Pending => pin.msg_send_fut = Some(send_fut),
+            }
         }
 
         // Poll the gathering future.
@@ -2,11 +2,15 @@ use alloy_primitives::B256;
 use anyhow::Result;
 use async_trait::async_trait;
 use katana_primitives::chain::ChainId;
+use katana_primitives::receipt::MessageToL1;
 use katana_primitives::transaction::L1HandlerTx;
-use starknet::core::types::{BlockId, EmittedEvent, EventFilter, Felt};
+use starknet::accounts::{Account, ExecutionEncoding, SingleOwnerAccount};
+use starknet::core::types::{BlockId, BlockTag, Call, EmittedEvent, EventFilter, Felt};
+use starknet::core::utils::starknet_keccak;
 use starknet::macros::selector;
 use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::{AnyProvider, JsonRpcClient, Provider};
+use starknet::signers::{LocalWallet, SigningKey};
 use tracing::{debug, error, trace, warn};
 use url::Url;
 
@@ -15,21 +19,49 @@ use super::{Error, MessagingConfig, Messenger, MessengerResult, LOG_TARGET};
 /// TODO: This may come from the configuration.
 pub const MESSAGE_SENT_EVENT_KEY: Felt = selector!("MessageSent");
 
+/// The selector of the settlement contract function used to register the messages sent from the
+/// appchain.
+const ADD_MESSAGES_HASHES_SELECTOR: Felt = selector!("add_messages_hashes_from_appchain");
+
 #[derive(Debug)]
 pub struct StarknetMessaging {
     provider: AnyProvider,
+    /// The account used to send messages to the settlement chain, if configured.
+    sender: Option<SingleOwnerAccount<AnyProvider, LocalWallet>>,
     messaging_contract_address: Felt,
 }
 
 impl StarknetMessaging {
     pub async fn new(config: MessagingConfig) -> Result<StarknetMessaging> {
-        let provider = AnyProvider::JsonRpcHttp(JsonRpcClient::new(HttpTransport::new(
-            Url::parse(&config.rpc_url)?,
-        )));
+        let url = Url::parse(&config.rpc_url)?;
+        let provider =
+            AnyProvider::JsonRpcHttp(JsonRpcClient::new(HttpTransport::new(url.clone())));
 
         let messaging_contract_address = Felt::from_hex(&config.contract_address)?;
 
-        Ok(StarknetMessaging { provider, messaging_contract_address })
+        let sender = if config.sender_address.is_empty() || config.private_key.is_empty() {
+            None
+        } else {
+            let chain_id = provider.chain_id().await?;
+            let address = Felt::from_hex(&config.sender_address)?;
+            let private_key = Felt::from_hex(&config.private_key)?;
+
+            let signer = LocalWallet::from_signing_key(SigningKey::from_secret_scalar(private_key));
+            let provider = AnyProvider::JsonRpcHttp(JsonRpcClient::new(HttpTransport::new(url)));
+
+            let mut account = SingleOwnerAccount::new(
+                provider,
+                signer,
+                address,
+                chain_id,
+                ExecutionEncoding::New,
+            );
+
+            account.set_block_id(BlockId::Tag(BlockTag::Pending));
+            Some(account)
+        };
+
+        Ok(StarknetMessaging { provider, sender, messaging_contract_address })
     }
 
     pub async fn fetch_events(
@@ -132,6 +164,59 @@ impl Messenger for StarknetMessaging {
 
         Ok((to_block, l1_handler_txs))
     }
+
+    /// The message hashes are registered on the settlement contract by the configured sender
+    /// account, which must be the appchain account authorized by the contract.
+    async fn send_messages(
+        &self,
+        messages: &[MessageToL1],
+    ) -> MessengerResult<Vec<Self::MessageHash>> {
+        if messages.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // the messages are kept by the service and sent again once it's possible
+        let Some(sender) = &self.sender else { return Err(Error::MissingSender) };
+
+        let hashes = messages
+            .iter()
+            .map(|msg| {
+                compute_appchain_to_starknet_message_hash(
+                    msg.from_address.into(),
+                    msg.to_address,
+                    &msg.payload,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        // the hashes are serialized as a `Span<felt252>`
+        let mut calldata = vec![Felt::from(hashes.len())];
+        calldata.extend(&hashes);
+
+        let call = Call {
+            to: self.messaging_contract_address,
+            selector: ADD_MESSAGES_HASHES_SELECTOR,
+            calldata,
+        };
+
+        match sender.execute_v1(vec![call]).send().await {
+            Ok(res) => {
+                trace!(
+                    target: LOG_TARGET,
+                    tx_hash = %format!("{:#x}", res.transaction_hash),
+                    count = hashes.len(),
+                    "Messages sent to settlement chain."
+                );
+
+                Ok(hashes)
+            }
+
+            Err(error) => {
+                error!(target: LOG_TARGET, %error, "Sending messages to settlement chain.");
+                Err(Error::SendError)
+            }
+        }
+    }
 }
 
 fn l1_handler_tx_from_event(event: &EmittedEvent, chain_id: ChainId) -> Result<L1HandlerTx> {
@@ -183,6 +268,28 @@ fn l1_handler_tx_from_event(event: &EmittedEvent, chain_id: ChainId) -> Result<L
     })
 }
 
+/// Computes the hash of a L3 to L2 message.
+///
+/// This must match the hash computed by the settlement contract when the message is consumed,
+/// which is the starknet keccak of the message.
+/// <https://github.com/starkware-libs/cairo-lang/blob/caba294d82eeeccc3d86a158adb8ba209bf2d8fc/src/starkware/starknet/solidity/StarknetMessaging.sol#L137>
+fn compute_appchain_to_starknet_message_hash(
+    from_address: Felt,
+    to_address: Felt,
+    payload: &[Felt],
+) -> Felt {
+    let mut buf = Vec::with_capacity((3 + payload.len()) * 32);
+    buf.extend(from_address.to_bytes_be());
+    buf.extend(to_address.to_bytes_be());
+    buf.extend(Felt::from(payload.len()).to_bytes_be());
+
+    for p in payload {
+        buf.extend(p.to_bytes_be());
+    }
+
+    starknet_keccak(&buf)
+}
+
 /// Computes the hash of a L2 to L3 message.
 ///
 /// Piltover uses poseidon hash for all hashes computation.
@@ -205,7 +312,9 @@ fn compute_starknet_to_appchain_message_hash(
 
 #[cfg(test)]
 mod tests {
-    use katana_primitives::utils::transaction::compute_l1_handler_tx_hash;
+    use katana_primitives::utils::transaction::{
+        compute_l1_handler_tx_hash, compute_l2_to_l1_message_hash,
+    };
     use starknet::macros::felt;
 
     use super::*;
@@ -315,4 +424,19 @@ mod tests {
 
         let _tx = l1_handler_tx_from_event(&event, ChainId::default()).unwrap();
     }
+
+    #[test]
+    fn appchain_to_starknet_message_hash() {
+        let from_address = selector!("from_address");
+        let to_address = selector!("to_address");
+        let payload = vec![Felt::ONE, Felt::TWO];
+
+        let hash = compute_appchain_to_starknet_message_hash(from_address, to_address, &payload);
+
+        // starknet keccak is the keccak hash of the same data truncated to 250 bits
+        let mut expected = compute_l2_to_l1_message_hash(from_address, to_address, &payload).0;
+        expected[0] &= 0x03;
+
+        assert_eq!(hash, Felt::from_bytes_be(&expected));
+    }
 }
@@ -0,0 +1,209 @@
+//! Builder for running Katana as a library.
+//!
+//! [`KatanaNodeBuilder`] provides a fluent API on top of [`Config`] for the most common options,
+//! which is convenient for spinning up an in-process node, eg in integration tests:
+//!
+//! ```no_run
+//! # async fn example() -> anyhow::Result<()> {
+//! use katana_node::builder::KatanaNodeBuilder;
+//!
+//! let node = KatanaNodeBuilder::new().dev_accounts(5).block_time(1000).launch().await?;
+//!
+//! println!("RPC server listening on {}", node.rpc.addr());
+//! let backend = &node.node.backend;
+//! let block_producer = &node.node.block_producer;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use katana_chain_spec::{dev, ChainSpec};
+use katana_core::constants::DEFAULT_SEQUENCER_ADDRESS;
+use katana_primitives::genesis::allocation::DevAllocationsGenerator;
+use katana_primitives::genesis::constant::DEFAULT_PREFUNDED_ACCOUNT_BALANCE;
+use katana_primitives::U256;
+
+use crate::config::rpc::{RpcConfig, RpcModulesList};
+use crate::config::Config;
+use crate::{LaunchedNode, Node};
+
+/// The number of dev accounts generated by default.
+const DEFAULT_DEV_ACCOUNTS: u16 = 10;
+
+/// Builder for configuring and launching a node. See the [module](self) level docs for more
+/// details.
+#[derive(Debug, Clone)]
+pub struct KatanaNodeBuilder {
+    config: Config,
+    dev_accounts: u16,
+    seed: [u8; 32],
+}
+
+impl KatanaNodeBuilder {
+    /// Creates a builder for a development node, with [`DEFAULT_DEV_ACCOUNTS`] prefunded
+    /// accounts and the RPC server listening on a random port of the localhost interface.
+    pub fn new() -> Self {
+        let mut chain = dev::DEV_UNALLOCATED.clone();
+        chain.genesis.sequencer_address = *DEFAULT_SEQUENCER_ADDRESS;
+
+        let config = Config {
+            chain: Arc::new(ChainSpec::Dev(chain)),
+            rpc: RpcConfig { port: 0, ..Default::default() },
+            ..Default::default()
+        };
+
+        Self::with_config(config)
+    }
+
+    /// Creates a builder starting from an existing node configuration.
+    ///
+    /// The chain specification of `config` is used as is, ie no dev accounts are generated unless
+    /// [`KatanaNodeBuilder::dev_accounts`] is called.
+    pub fn with_config(config: Config) -> Self {
+        let dev_accounts = match config.chain.as_ref() {
+            ChainSpec::Dev(cs) if cs.genesis.accounts().next().is_none() => DEFAULT_DEV_ACCOUNTS,
+            _ => 0,
+        };
+
+        Self { config, dev_accounts, seed: [0u8; 32] }
+    }
+
+    /// Sets the chain specification.
+    ///
+    /// No dev accounts are generated for the chain unless [`KatanaNodeBuilder::dev_accounts`] is
+    /// called afterward.
+    pub fn chain_spec(mut self, chain: ChainSpec) -> Self {
+        self.config.chain = Arc::new(chain);
+        self.dev_accounts = 0;
+        self
+    }
+
+    /// Sets the number of prefunded dev accounts to generate in the genesis block. Only supported
+    /// by dev chain specifications.
+    pub fn dev_accounts(mut self, total: u16) -> Self {
+        self.dev_accounts = total;
+        self
+    }
+
+    /// Sets the seed used to generate the dev accounts.
+    pub fn seed(mut self, seed: [u8; 32]) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Produces a new block every `block_time` milliseconds, instead of a block per transaction.
+    pub fn block_time(mut self, block_time: u64) -> Self {
+        self.config.sequencing.block_time = Some(block_time);
+        self
+    }
+
+    /// Disables automatic block production, blocks are only produced using the dev api.
+    pub fn no_mining(mut self) -> Self {
+        self.config.sequencing.no_mining = true;
+        self
+    }
+
+    /// Persists the chain in a database at `dir`, instead of keeping it in memory.
+    pub fn db_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.config.db.dir = Some(dir.into());
+        self
+    }
+
+    /// Sets the address the RPC server listens on. Use port `0` for a random port.
+    pub fn rpc_addr(mut self, addr: SocketAddr) -> Self {
+        self.config.rpc.addr = addr.ip();
+        self.config.rpc.port = addr.port();
+        self
+    }
+
+    /// Sets the RPC modules served by the node.
+    pub fn rpc_modules(mut self, modules: RpcModulesList) -> Self {
+        self.config.rpc.apis = modules;
+        self
+    }
+
+    /// Disables fee charging when executing transactions.
+    pub fn no_fee(mut self) -> Self {
+        self.config.dev.fee = false;
+        self
+    }
+
+    /// Disables the validation of the transactions' senders.
+    pub fn no_account_validation(mut self) -> Self {
+        self.config.dev.account_validation = false;
+        self
+    }
+
+    /// Lifts the Cairo step limits of the transactions, and the limit on the number of Cairo steps
+    /// per block.
+    pub fn unlimited_resources(mut self) -> Self {
+        self.config.execution.remove_step_limits();
+        self.config.sequencing.block_cairo_steps_limit = None;
+        self
+    }
+
+    /// Returns the configuration the node will be built with, excluding the dev accounts.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Builds the node components.
+    pub async fn build(self) -> Result<Node> {
+        let Self { mut config, dev_accounts, seed } = self;
+
+        if dev_accounts > 0 {
+            let mut chain = config.chain.as_ref().clone();
+            let ChainSpec::Dev(cs) = &mut chain else {
+                bail!("dev accounts can only be generated for a dev chain specification");
+            };
+
+            let accounts = DevAllocationsGenerator::new(dev_accounts)
+                .with_seed(seed)
+                .with_balance(U256::from(DEFAULT_PREFUNDED_ACCOUNT_BALANCE))
+                .generate();
+
+            cs.genesis.extend_allocations(accounts.into_iter().map(|(k, v)| (k, v.into())));
+            config.chain = Arc::new(chain);
+        }
+
+        crate::build(config).await
+    }
+
+    /// Builds and launches the node.
+    pub async fn launch(self) -> Result<LaunchedNode> {
+        self.build().await?.launch().await
+    }
+}
+
+impl Default for KatanaNodeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use katana_provider::traits::block::BlockNumberProvider;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn launch_dev_node() {
+        let node = KatanaNodeBuilder::new().dev_accounts(3).no_mining().launch().await.unwrap();
+
+        let accounts = node.node.backend.chain_spec.genesis().accounts().count();
+        assert_eq!(accounts, 3);
+        assert_ne!(node.rpc.addr().port(), 0);
+
+        let outcome = node.node.block_producer.force_mine().unwrap().expect("should mine block");
+        assert_eq!(outcome.block_number, 1);
+        let latest = node.node.backend.blockchain.provider().latest_number().unwrap();
+        assert_eq!(latest, 1);
+
+        node.stop().await.unwrap();
+    }
+}
@@ -5,4 +5,12 @@ use std::path::PathBuf;
 pub struct DbConfig {
     /// The path to the database directory.
     pub dir: Option<PathBuf>,
+
+    /// The path to a state dump to restore the chain from on startup.
+    ///
+    /// The dump can only be loaded on top of a chain that contains nothing but the genesis block.
+    pub load_state: Option<PathBuf>,
+
+    /// The path to write the chain state to when the node is stopped.
+    pub dump_state: Option<PathBuf>,
 }
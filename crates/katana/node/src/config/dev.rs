@@ -3,6 +3,7 @@ use katana_core::constants::{
     DEFAULT_STRK_L1_GAS_PRICE,
 };
 use katana_primitives::block::GasPrices;
+use url::Url;
 
 /// Development configuration.
 #[derive(Debug, Clone)]
@@ -31,6 +32,23 @@ pub struct DevConfig {
     ///
     /// These are the prices that will be used for calculating the gas fee for transactions.
     pub fixed_gas_prices: Option<FixedL1GasPriceConfig>,
+
+    /// The Ethereum RPC provider to sample the L1 gas prices from.
+    ///
+    /// If set, the L2 gas prices will be derived from the base fee and blob gas price of the L1
+    /// chain instead of using the default values. Ignored if [`DevConfig::fixed_gas_prices`] is
+    /// set.
+    pub l1_provider_url: Option<Url>,
+
+    /// Whether to run the node in deterministic mode.
+    ///
+    /// If enabled, the block timestamps no longer depend on the current time: the timestamp of
+    /// each block is the timestamp of its parent plus [`DETERMINISTIC_BLOCK_TIME`]. Together with
+    /// a fixed dev accounts seed, running the same transactions always results in the same state
+    /// roots.
+    ///
+    /// [`DETERMINISTIC_BLOCK_TIME`]: katana_core::constants::DETERMINISTIC_BLOCK_TIME
+    pub deterministic: bool,
 }
 
 /// Fixed gas prices for development.
@@ -54,6 +72,12 @@ impl std::default::Default for FixedL1GasPriceConfig {
 
 impl std::default::Default for DevConfig {
     fn default() -> Self {
-        Self { fee: true, account_validation: true, fixed_gas_prices: None }
+        Self {
+            fee: true,
+            account_validation: true,
+            fixed_gas_prices: None,
+            l1_provider_url: None,
+            deterministic: false,
+        }
     }
 }
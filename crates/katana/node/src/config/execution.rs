@@ -8,6 +8,20 @@ pub struct ExecutionConfig {
     pub invocation_max_steps: u32,
     pub validation_max_steps: u32,
     pub max_recursion_depth: usize,
+    /// Whether to run the Sierra classes as native code compiled with Cairo Native, instead of
+    /// running them on the Cairo VM.
+    ///
+    /// Requires the node to be built with the `native` feature.
+    pub native: bool,
+}
+
+impl ExecutionConfig {
+    /// Lifts the Cairo step limits of the transactions' account validation and execution logic, by
+    /// setting them to their maximum value.
+    pub fn remove_step_limits(&mut self) {
+        self.invocation_max_steps = u32::MAX;
+        self.validation_max_steps = u32::MAX;
+    }
 }
 
 impl std::default::Default for ExecutionConfig {
@@ -16,6 +30,7 @@ impl std::default::Default for ExecutionConfig {
             max_recursion_depth: MAX_RECURSION_DEPTH,
             invocation_max_steps: DEFAULT_INVOCATION_MAX_STEPS,
             validation_max_steps: DEFAULT_VALIDATION_MAX_STEPS,
+            native: false,
         }
     }
 }
@@ -0,0 +1,26 @@
+use std::time::Duration;
+
+use url::Url;
+
+/// The default interval at which the follower polls the primary node for new blocks.
+pub const DEFAULT_FOLLOWER_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Follower mode configurations.
+///
+/// In follower mode, the node doesn't produce blocks itself. Instead, it replicates the blocks of a
+/// primary node - through the primary's `replication` RPC API - and only serves read requests.
+/// Both nodes must be using the same chain spec.
+#[derive(Debug, Clone)]
+pub struct FollowerConfig {
+    /// The RPC URL of the primary node. The primary must have the `replication` module enabled.
+    pub primary_url: Url,
+    /// The interval at which the primary is polled for new blocks.
+    pub poll_interval: Duration,
+}
+
+impl FollowerConfig {
+    /// Creates a configuration for following the node at `primary_url`.
+    pub fn new(primary_url: Url) -> Self {
+        Self { primary_url, poll_interval: DEFAULT_FOLLOWER_POLL_INTERVAL }
+    }
+}
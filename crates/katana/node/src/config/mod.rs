@@ -3,18 +3,22 @@ use std::sync::Arc;
 pub mod db;
 pub mod dev;
 pub mod execution;
+pub mod follower;
 pub mod fork;
 pub mod metrics;
+pub mod pruning;
 pub mod rpc;
 pub mod sequencing;
 
 use db::DbConfig;
 use dev::DevConfig;
 use execution::ExecutionConfig;
+use follower::FollowerConfig;
 use fork::ForkingConfig;
 use katana_chain_spec::ChainSpec;
 use katana_messaging::MessagingConfig;
 use metrics::MetricsConfig;
+use pruning::PruningConfig;
 use rpc::RpcConfig;
 use sequencing::SequencingConfig;
 #[cfg(feature = "cartridge")]
@@ -34,6 +38,10 @@ pub struct Config {
     /// Forking options.
     pub forking: Option<ForkingConfig>,
 
+    /// Follower mode options. If set, the node replicates the blocks of a primary node instead of
+    /// producing its own.
+    pub follower: Option<FollowerConfig>,
+
     /// Rpc options.
     pub rpc: RpcConfig,
 
@@ -52,6 +60,9 @@ pub struct Config {
     /// Development options.
     pub dev: DevConfig,
 
+    /// Pruning options.
+    pub pruning: PruningConfig,
+
     /// Cartridge paymaster options.
     #[cfg(feature = "cartridge")]
     pub paymaster: Option<Paymaster>,
@@ -0,0 +1,28 @@
+/// Pruning configurations.
+///
+/// Pruning bounds the disk usage of long-running nodes by removing the historical data of the
+/// blocks that are older than the configured distances from the tip of the chain. Headers,
+/// transactions and the latest state are always kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PruningConfig {
+    /// The number of most recent blocks whose transaction receipts are kept. If `None`, receipts
+    /// are never pruned.
+    pub receipts: Option<u64>,
+
+    /// The number of most recent blocks whose transaction execution traces are kept. If `None`,
+    /// traces are never pruned.
+    pub traces: Option<u64>,
+
+    /// The number of most recent blocks whose state diffs are kept. If `None`, state diffs are
+    /// never pruned.
+    ///
+    /// The state at the pruned blocks can no longer be queried.
+    pub state_diffs: Option<u64>,
+}
+
+impl PruningConfig {
+    /// Returns `true` if any kind of data is pruned.
+    pub fn is_enabled(&self) -> bool {
+        self.receipts.is_some() || self.traces.is_some() || self.state_diffs.is_some()
+    }
+}
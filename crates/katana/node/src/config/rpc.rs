@@ -1,5 +1,6 @@
 use std::collections::HashSet;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
 
 use katana_rpc::cors::HeaderValue;
 use serde::{Deserialize, Serialize};
@@ -33,6 +34,8 @@ pub enum RpcModuleKind {
     Torii,
     Saya,
     Dev,
+    Replication,
+    Katana,
     #[cfg(feature = "cartridge")]
     Cartridge,
 }
@@ -44,9 +47,26 @@ pub struct RpcConfig {
     pub port: u16,
     pub apis: RpcModulesList,
     pub cors_origins: Vec<HeaderValue>,
+    /// The path prefix under which the server is served.
+    pub path_prefix: Option<String>,
+    /// The interface of the WebSocket server, defaults to the interface of the HTTP server.
+    pub ws_addr: Option<IpAddr>,
+    /// The port of the WebSocket server, if it's served separately from the HTTP server.
+    pub ws_port: Option<u16>,
+    /// The path of the Unix domain socket on which the RPC methods are also served, if any.
+    pub ipc_path: Option<PathBuf>,
     pub max_connections: Option<u32>,
     pub max_request_body_size: Option<u32>,
     pub max_response_body_size: Option<u32>,
+    pub max_concurrent_requests: Option<u32>,
+    pub rate_limit: Option<u32>,
+    /// The maximum number of calls of a batch request.
+    pub max_batch_size: Option<u32>,
+    /// The maximum number of calls of a batch request executed concurrently.
+    pub batch_concurrency: Option<u32>,
+    /// The API key required for calling the state-mutating methods. If `None`, all the methods are
+    /// open to everyone.
+    pub api_key: Option<String>,
     pub max_proof_keys: Option<u64>,
     pub max_event_page_size: Option<u64>,
     pub max_call_gas: Option<u64>,
@@ -57,17 +77,32 @@ impl RpcConfig {
     pub fn socket_addr(&self) -> SocketAddr {
         SocketAddr::new(self.addr, self.port)
     }
+
+    /// Returns the [`SocketAddr`] for the WebSocket server, if it's served separately from the
+    /// HTTP server.
+    pub fn ws_socket_addr(&self) -> Option<SocketAddr> {
+        self.ws_port.map(|port| SocketAddr::new(self.ws_addr.unwrap_or(self.addr), port))
+    }
 }
 
 impl Default for RpcConfig {
     fn default() -> Self {
         Self {
             cors_origins: Vec::new(),
+            path_prefix: None,
+            ws_addr: None,
+            ws_port: None,
+            ipc_path: None,
             addr: DEFAULT_RPC_ADDR,
             port: DEFAULT_RPC_PORT,
             max_connections: None,
             max_request_body_size: None,
             max_response_body_size: None,
+            max_concurrent_requests: None,
+            rate_limit: None,
+            max_batch_size: None,
+            batch_concurrency: None,
+            api_key: None,
             apis: RpcModulesList::default(),
             max_event_page_size: Some(DEFAULT_RPC_MAX_EVENT_PAGE_SIZE),
             max_proof_keys: Some(DEFAULT_RPC_MAX_PROOF_KEYS),
@@ -97,6 +132,8 @@ impl RpcModulesList {
             RpcModuleKind::Torii,
             RpcModuleKind::Saya,
             RpcModuleKind::Dev,
+            RpcModuleKind::Replication,
+            RpcModuleKind::Katana,
             #[cfg(feature = "cartridge")]
             RpcModuleKind::Cartridge,
         ]))
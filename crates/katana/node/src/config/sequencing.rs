@@ -1,3 +1,5 @@
+use std::num::NonZeroUsize;
+
 use katana_executor::BlockLimits;
 
 /// Configurations related to block production.
@@ -20,10 +22,32 @@ pub struct SequencingConfig {
     ///
     /// See <https://docs.starknet.io/chain-info/#current_limits>.
     pub block_cairo_steps_limit: Option<u64>,
+
+    /// The maximum number of transactions in a block.
+    ///
+    /// The block will automatically be closed when it has reached this number of transactions.
+    ///
+    /// NOTE: This only affect interval block production.
+    pub block_max_txs: Option<u64>,
+
+    /// The maximum number of threads used to execute the transactions of a block.
+    ///
+    /// When more than one, the transactions are executed optimistically in parallel. Defaults to
+    /// executing the transactions sequentially.
+    pub concurrency: Option<NonZeroUsize>,
+
+    /// Record the SNOS inputs of the produced blocks, ie the state accessed by each block.
+    ///
+    /// They are stored along with the block and served by the `saya` RPC module, so that the
+    /// blocks can be run through SNOS without being re-executed.
+    pub provable: bool,
 }
 
 impl SequencingConfig {
     pub fn block_limits(&self) -> BlockLimits {
-        BlockLimits { cairo_steps: self.block_cairo_steps_limit.unwrap_or(u64::MAX) }
+        BlockLimits {
+            cairo_steps: self.block_cairo_steps_limit.unwrap_or(u64::MAX),
+            transactions: self.block_max_txs.unwrap_or(u64::MAX),
+        }
     }
 }
@@ -0,0 +1,280 @@
+//! Dumping and restoring the node's chain state.
+//!
+//! A state dump contains every block that was produced on top of the genesis block, along with
+//! the state updates, declared classes, receipts and execution traces of each block. Restoring a
+//! dump replays the blocks on top of the genesis of the node it's being loaded into, so the dump
+//! must be loaded into a node that is using the same chain spec as the one it was dumped from.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use katana_primitives::block::{
+    BlockHash, BlockHashOrNumber, BlockNumber, FinalityStatus, Header, SealedBlock,
+    SealedBlockWithStatus,
+};
+use katana_primitives::class::{ClassHash, ContractClass};
+use katana_primitives::receipt::Receipt;
+use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
+use katana_primitives::trace::TxExecInfo;
+use katana_primitives::transaction::TxWithHash;
+use katana_provider::traits::block::{
+    BlockHashProvider, BlockNumberProvider, BlockProvider, BlockStatusProvider, BlockWriter,
+};
+use katana_provider::traits::contract::ContractClassProvider;
+use katana_provider::traits::state::StateFactoryProvider;
+use katana_provider::traits::state_update::StateUpdateProvider;
+use katana_provider::traits::transaction::{ReceiptProvider, TransactionTraceProvider};
+use katana_provider::traits::trie::TrieWriter;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// The version of the state dump format.
+///
+/// Must be bumped whenever a breaking change is made to the [`StateDump`] format.
+pub const STATE_DUMP_VERSION: u32 = 1;
+
+/// A serializable snapshot of the chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateDump {
+    /// The version of the dump format.
+    pub version: u32,
+    /// The hash of the genesis block the dumped blocks are built on.
+    pub genesis_hash: BlockHash,
+    /// All the blocks after the genesis block, in ascending order.
+    pub blocks: Vec<DumpedBlock>,
+}
+
+/// A block along with all of its execution artifacts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DumpedBlock {
+    pub hash: BlockHash,
+    pub header: Header,
+    pub status: FinalityStatus,
+    pub body: Vec<TxWithHash>,
+    pub state_updates: StateUpdates,
+    pub classes: BTreeMap<ClassHash, ContractClass>,
+    pub receipts: Vec<Receipt>,
+    pub traces: Vec<TxExecInfo>,
+}
+
+impl StateDump {
+    /// Reads a state dump from a file.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .with_context(|| format!("failed to open state dump at {}", path.display()))?;
+
+        let dump: Self =
+            serde_json::from_reader(BufReader::new(file)).context("failed to parse state dump")?;
+
+        if dump.version != STATE_DUMP_VERSION {
+            bail!(
+                "unsupported state dump version: expected {STATE_DUMP_VERSION}, got {}",
+                dump.version
+            );
+        }
+
+        Ok(dump)
+    }
+
+    /// Writes the state dump to a file, overwriting it if it already exists.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let file = File::create(path)
+            .with_context(|| format!("failed to create state dump at {}", path.display()))?;
+        serde_json::to_writer(BufWriter::new(file), self).context("failed to write state dump")?;
+        Ok(())
+    }
+}
+
+/// Dumps all the blocks in the `provider` that come after the genesis block.
+pub fn dump_state<P>(provider: &P, genesis_number: u64) -> Result<StateDump>
+where
+    P: BlockProvider
+        + BlockStatusProvider
+        + StateUpdateProvider
+        + StateFactoryProvider
+        + ReceiptProvider
+        + TransactionTraceProvider,
+{
+    let genesis_hash =
+        provider.block_hash_by_num(genesis_number)?.context("missing genesis block")?;
+
+    let latest = provider.latest_number()?;
+    let state = provider.latest()?;
+
+    let mut blocks = Vec::with_capacity(latest.saturating_sub(genesis_number) as usize);
+
+    for num in (genesis_number + 1)..=latest {
+        blocks.push(dump_block(provider, &*state, num)?);
+    }
+
+    Ok(StateDump { version: STATE_DUMP_VERSION, genesis_hash, blocks })
+}
+
+/// Collects the block `num` along with all of its execution artifacts. The classes declared in the
+/// block are read from `state`.
+pub(crate) fn dump_block<P>(
+    provider: &P,
+    state: &(impl ContractClassProvider + ?Sized),
+    num: BlockNumber,
+) -> Result<DumpedBlock>
+where
+    P: BlockProvider
+        + BlockStatusProvider
+        + StateUpdateProvider
+        + ReceiptProvider
+        + TransactionTraceProvider,
+{
+    let id = BlockHashOrNumber::Num(num);
+    let missing = || format!("missing data for block {num}");
+
+    let hash = provider.block_hash_by_num(num)?.with_context(missing)?;
+    let block = provider.block(id)?.with_context(missing)?;
+    let status = provider.block_status(id)?.with_context(missing)?;
+    let state_updates = provider.state_update(id)?.with_context(missing)?;
+    let receipts = provider.receipts_by_block(id)?.with_context(missing)?;
+    let traces = provider.transaction_executions_by_block(id)?.with_context(missing)?;
+
+    let mut classes = BTreeMap::new();
+    let declared = state_updates.declared_classes.keys();
+    let deprecated = state_updates.deprecated_declared_classes.iter();
+
+    for class_hash in declared.chain(deprecated) {
+        let class = state.class(*class_hash)?.with_context(missing)?;
+        classes.insert(*class_hash, class);
+    }
+
+    Ok(DumpedBlock {
+        hash,
+        status,
+        classes,
+        receipts,
+        traces,
+        state_updates,
+        body: block.body,
+        header: block.header,
+    })
+}
+
+/// Restores a state dump into the `provider`.
+///
+/// The provider must only contain the genesis block, and the genesis block must be the same as the
+/// one the dump was created from.
+pub fn load_state<P>(provider: &P, genesis_number: u64, dump: StateDump) -> Result<()>
+where
+    P: BlockProvider + BlockWriter + TrieWriter,
+{
+    let genesis_hash =
+        provider.block_hash_by_num(genesis_number)?.context("missing genesis block")?;
+
+    if genesis_hash != dump.genesis_hash {
+        bail!(
+            "Genesis block hash mismatch: expected {genesis_hash:#x}, got {:#x}",
+            dump.genesis_hash
+        );
+    }
+
+    let latest = provider.latest_number()?;
+    if latest != genesis_number {
+        bail!("state dump can only be loaded on top of a fresh chain, but found block {latest}");
+    }
+
+    let total = dump.blocks.len();
+
+    for block in dump.blocks {
+        insert_block(provider, block)?;
+    }
+
+    info!(target: "node", blocks = %total, "State dump loaded.");
+
+    Ok(())
+}
+
+/// Inserts a dumped block into the `provider`, rebuilding the tries so that the state roots and
+/// proofs are available for the block.
+pub(crate) fn insert_block<P>(provider: &P, block: DumpedBlock) -> Result<()>
+where
+    P: BlockWriter + TrieWriter,
+{
+    let number = block.header.number;
+
+    provider.trie_insert_declared_classes(number, &block.state_updates.declared_classes)?;
+    provider.trie_insert_contract_updates(number, &block.state_updates)?;
+
+    let sealed = SealedBlock { hash: block.hash, header: block.header, body: block.body };
+    let sealed = SealedBlockWithStatus { block: sealed, status: block.status };
+    let states =
+        StateUpdatesWithClasses { state_updates: block.state_updates, classes: block.classes };
+
+    provider
+        .insert_block_with_states_and_receipts(sealed, states, block.receipts, block.traces)
+        .with_context(|| format!("failed to insert block {number}"))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use katana_chain_spec::{dev, ChainSpec};
+    use katana_core::backend::gas_oracle::GasOracle;
+    use katana_core::backend::storage::Blockchain;
+    use katana_core::backend::Backend;
+    use katana_executor::implementation::blockifier::BlockifierFactory;
+    use katana_executor::BlockLimits;
+    use katana_primitives::env::{BlockEnv, CfgEnv};
+    use katana_provider::providers::db::DbProvider;
+
+    use super::*;
+
+    fn backend() -> Backend<BlockifierFactory> {
+        let chain_spec = ChainSpec::Dev(dev::ChainSpec::default());
+        let executor = BlockifierFactory::new(
+            CfgEnv { chain_id: chain_spec.id(), ..Default::default() },
+            Default::default(),
+            BlockLimits::max(),
+        );
+
+        let backend = Backend::new(
+            chain_spec.into(),
+            Blockchain::new(DbProvider::new_ephemeral()),
+            GasOracle::sampled_starknet(),
+            executor,
+        );
+
+        backend.init_genesis().expect("failed to initialize genesis");
+        backend
+    }
+
+    #[test]
+    fn dump_and_load_roundtrip() {
+        let source = backend();
+        let provider = source.blockchain.provider();
+
+        let mut block_env = BlockEnv::default();
+        for _ in 0..3 {
+            source.update_block_env(&mut block_env);
+            source.mine_empty_block(&block_env).unwrap();
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("katana-state-dump.json");
+        dump_state(provider, 0).unwrap().write(&path).unwrap();
+        let dump = StateDump::read(&path).unwrap();
+        assert_eq!(dump.blocks.len(), 3);
+
+        let target = backend();
+        let target_provider = target.blockchain.provider();
+        load_state(target_provider, 0, dump).unwrap();
+
+        assert_eq!(target_provider.latest_number().unwrap(), 3);
+        assert_eq!(target_provider.latest_hash().unwrap(), provider.latest_hash().unwrap());
+
+        // loading on top of a chain that is not fresh must fail
+        let dump = dump_state(provider, 0).unwrap();
+        assert!(load_state(target_provider, 0, dump).is_err());
+    }
+}
@@ -0,0 +1,310 @@
+//! Follower mode, where the node replicates the chain of a primary node instead of producing its
+//! own blocks.
+//!
+//! The follower polls the primary's `replication` RPC API for new blocks and inserts them - along
+//! with their state updates, classes, receipts and traces - into its own database as is, without
+//! re-executing them. This is a cheap way of scaling out the read traffic of a node, as any number
+//! of followers can be pointed at the same primary.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, ensure, Context, Result};
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use katana_core::backend::notification::NewBlock;
+use katana_core::backend::Backend;
+use katana_executor::ExecutorFactory;
+use katana_primitives::block::BlockNumber;
+use katana_primitives::receipt::ReceiptWithTxHash;
+use katana_provider::traits::block::{BlockHashProvider, BlockNumberProvider, BlockWriter};
+use katana_rpc_api::replication::ReplicationApiClient;
+use katana_rpc_types::replication::{ReplicatedBlock, MAX_REPLICATED_BLOCKS_PER_REQUEST};
+use tracing::{info, warn};
+use url::Url;
+
+use crate::config::follower::FollowerConfig;
+use crate::dump::{self, DumpedBlock};
+
+const LOG_TARGET: &str = "follower";
+
+/// Replicates the blocks of a primary node. See the [module](self) level docs for more details.
+#[allow(missing_debug_implementations)]
+pub struct Follower<EF: ExecutorFactory> {
+    backend: Arc<Backend<EF>>,
+    client: HttpClient,
+    primary_url: Url,
+    poll_interval: Duration,
+}
+
+impl<EF: ExecutorFactory> Follower<EF> {
+    pub fn new(backend: Arc<Backend<EF>>, config: &FollowerConfig) -> Result<Self> {
+        let client = HttpClientBuilder::default()
+            .build(config.primary_url.as_str())
+            .context("failed to create primary node client")?;
+        let primary_url = config.primary_url.clone();
+        Ok(Self { backend, client, primary_url, poll_interval: config.poll_interval })
+    }
+
+    /// Replicates the blocks of the primary node until it's stopped.
+    ///
+    /// Fails if the primary is on a different chain. If the primary reorgs, the blocks that are no
+    /// longer part of its chain are unwound and the new ones are replicated instead. Errors when
+    /// fetching the blocks from the primary are only logged, and the request is retried on the
+    /// next poll.
+    pub async fn run(self) -> Result<()> {
+        self.check_genesis().await?;
+        info!(target: LOG_TARGET, primary = %self.primary_url, "Following primary node.");
+
+        let mut next = self.backend.blockchain.provider().latest_number()? + 1;
+
+        loop {
+            match self.client.get_blocks(next, MAX_REPLICATED_BLOCKS_PER_REQUEST).await {
+                // keep fetching right away until we've caught up with the primary
+                Ok(blocks) if !blocks.is_empty() => {
+                    next = self.insert_blocks(blocks).await?;
+                    continue;
+                }
+                // we're at the primary's tip, unless it has reverted some of the blocks that we've
+                // already replicated
+                Ok(_) => match self.client.block_number().await {
+                    Ok(tip) if tip + 1 < next => {
+                        next = self.unwind_to_common_ancestor(tip).await? + 1;
+                        continue;
+                    }
+                    Ok(_) => {}
+                    Err(error) => {
+                        warn!(target: LOG_TARGET, %error, "Failed to fetch primary node tip.")
+                    }
+                },
+                Err(error) => {
+                    warn!(target: LOG_TARGET, %error, "Failed to fetch blocks from primary node.")
+                }
+            }
+
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Ensures that the primary node is using the same genesis block as this node.
+    async fn check_genesis(&self) -> Result<()> {
+        let number = self.backend.chain_spec.genesis().number;
+        let local = self.backend.blockchain.provider().block_hash_by_num(number)?;
+        let local = local.context("missing genesis block")?;
+
+        let blocks = self
+            .client
+            .get_blocks(number, 1)
+            .await
+            .context("failed to fetch genesis block from primary node")?;
+
+        match blocks.first() {
+            Some(block) if block.hash == local => Ok(()),
+            Some(block) => bail!(
+                "Genesis block hash mismatch: expected {local:#x}, primary has {:#x}",
+                block.hash
+            ),
+            None => bail!("primary node doesn't have a genesis block"),
+        }
+    }
+
+    /// Inserts the blocks replicated from the primary node, and returns the number of the next
+    /// block to fetch.
+    ///
+    /// If a block doesn't extend our chain, the primary has reorged: our chain is unwound to the
+    /// latest block that it shares with the primary, and the remaining blocks are discarded so that
+    /// the new ones are fetched instead.
+    async fn insert_blocks(&self, blocks: Vec<ReplicatedBlock>) -> Result<BlockNumber> {
+        let provider = self.backend.blockchain.provider();
+
+        for block in blocks {
+            let number = block.header.number;
+            let latest = provider.latest_number()?;
+            let latest_hash = provider.latest_hash()?;
+
+            if number != latest + 1 {
+                bail!("expected block {} from primary node, got block {number}", latest + 1);
+            }
+
+            if block.header.parent_hash != latest_hash {
+                warn!(target: LOG_TARGET, block_number = %number, "Diverged from primary node.");
+                return Ok(self.unwind_to_common_ancestor(latest).await? + 1);
+            }
+
+            let notification = self.backend.block_notifier.has_subscribers().then(|| NewBlock {
+                hash: block.hash,
+                header: block.header.clone(),
+                receipts: block
+                    .body
+                    .iter()
+                    .zip(&block.receipts)
+                    .map(|(tx, receipt)| ReceiptWithTxHash::new(tx.hash, receipt.clone()))
+                    .collect(),
+            });
+
+            dump::insert_block(provider, block.into())?;
+
+            if let Some(notification) = notification {
+                self.backend.block_notifier.notify(notification);
+            }
+
+            info!(target: LOG_TARGET, block_number = %number, "Block replicated.");
+        }
+
+        Ok(provider.latest_number()? + 1)
+    }
+
+    /// Unwinds our chain to the latest block, at or before `from`, that is also part of the
+    /// primary's chain, and returns its number.
+    async fn unwind_to_common_ancestor(&self, from: BlockNumber) -> Result<BlockNumber> {
+        let provider = self.backend.blockchain.provider();
+        let genesis = self.backend.chain_spec.genesis().number;
+
+        let mut number = from.min(provider.latest_number()?);
+        loop {
+            let local = provider.block_hash_by_num(number)?.context("missing block")?;
+            let remote = self
+                .client
+                .get_blocks(number, 1)
+                .await
+                .context("failed to fetch block from primary node")?;
+
+            if remote.first().is_some_and(|block| block.hash == local) {
+                break;
+            }
+
+            // the genesis block is the same on both nodes, as checked when starting
+            ensure!(number > genesis, "no common block with primary node");
+            number -= 1;
+        }
+
+        provider.unwind_to(number)?;
+        info!(target: LOG_TARGET, block_number = %number, "Unwound to common block with primary node.");
+
+        Ok(number)
+    }
+}
+
+impl From<ReplicatedBlock> for DumpedBlock {
+    fn from(block: ReplicatedBlock) -> Self {
+        Self {
+            hash: block.hash,
+            header: block.header,
+            status: block.status,
+            body: block.body,
+            state_updates: block.state_updates,
+            classes: block.classes,
+            receipts: block.receipts,
+            traces: block.traces,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::KatanaNodeBuilder;
+    use crate::config::rpc::{RpcConfig, RpcModuleKind, RpcModulesList};
+    use crate::config::Config;
+    use crate::LaunchedNode;
+
+    async fn launch_primary() -> LaunchedNode {
+        let mut modules = RpcModulesList::new();
+        modules.add(RpcModuleKind::Starknet);
+        modules.add(RpcModuleKind::Replication);
+
+        KatanaNodeBuilder::new()
+            .dev_accounts(1)
+            .no_mining()
+            .rpc_modules(modules)
+            .launch()
+            .await
+            .unwrap()
+    }
+
+    async fn launch_follower(primary: &LaunchedNode) -> LaunchedNode {
+        let primary_url = Url::parse(&format!("http://{}", primary.rpc.addr())).unwrap();
+        let follower = FollowerConfig { primary_url, poll_interval: Duration::from_millis(10) };
+        let config = Config {
+            chain: primary.node.backend.chain_spec.clone(),
+            rpc: RpcConfig { port: 0, ..Default::default() },
+            follower: Some(follower),
+            ..Default::default()
+        };
+
+        KatanaNodeBuilder::with_config(config).launch().await.unwrap()
+    }
+
+    /// Waits until the follower has the same chain as the primary, up to the primary's tip.
+    async fn wait_for_primary(follower: &LaunchedNode, primary: &LaunchedNode) {
+        let primary_provider = primary.node.backend.blockchain.provider();
+        let follower_provider = follower.node.backend.blockchain.provider();
+
+        let tip = primary_provider.latest_number().unwrap();
+        let is_synced = || {
+            follower_provider.latest_number().unwrap() == tip
+                && (0..=tip).all(|num| {
+                    follower_provider.block_hash_by_num(num).unwrap()
+                        == primary_provider.block_hash_by_num(num).unwrap()
+                })
+        };
+
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while !is_synced() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("follower should replicate the primary's chain");
+    }
+
+    /// Reverts the primary's chain to `block_number`, and makes sure that the blocks mined
+    /// after that differ from the reverted ones.
+    fn revert_primary(primary: &LaunchedNode, block_number: BlockNumber) {
+        assert!(primary.node.block_producer.revert_to(block_number).unwrap());
+        primary.node.backend.block_context_generator.write().block_timestamp_offset += 100;
+    }
+
+    #[tokio::test]
+    async fn replicate_primary_blocks() {
+        let primary = launch_primary().await;
+        for _ in 0..3 {
+            primary.node.block_producer.force_mine().unwrap();
+        }
+
+        let follower = launch_follower(&primary).await;
+        wait_for_primary(&follower, &primary).await;
+
+        // blocks mined after the follower has caught up are replicated too
+        primary.node.block_producer.force_mine().unwrap();
+        wait_for_primary(&follower, &primary).await;
+
+        follower.stop().await.unwrap();
+        primary.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn follow_primary_reorgs() {
+        let primary = launch_primary().await;
+        for _ in 0..3 {
+            primary.node.block_producer.force_mine().unwrap();
+        }
+
+        let follower = launch_follower(&primary).await;
+        wait_for_primary(&follower, &primary).await;
+
+        // the primary's new chain is longer than the replicated one
+        revert_primary(&primary, 1);
+        for _ in 0..4 {
+            primary.node.block_producer.force_mine().unwrap();
+        }
+        wait_for_primary(&follower, &primary).await;
+
+        // the primary's new chain is shorter than the replicated one
+        revert_primary(&primary, 2);
+        primary.node.block_producer.force_mine().unwrap();
+        wait_for_primary(&follower, &primary).await;
+
+        follower.stop().await.unwrap();
+        primary.stop().await.unwrap();
+    }
+}
@@ -71,7 +71,7 @@ async fn main() -> Result<()> {
     let config = Config {
         metrics: None,
         gateway_api_key: cli.gateway_api_key,
-        db: DbConfig { dir: Some(cli.db_dir) },
+        db: DbConfig { dir: Some(cli.db_dir), ..Default::default() },
     };
 
     let node = Node::build(config)?.launch()?;
@@ -3,16 +3,20 @@
 #[cfg(feature = "full-node")]
 pub mod full;
 
+pub mod builder;
 pub mod config;
+pub mod dump;
 pub mod exit;
+pub mod follower;
+pub mod snapshot;
 pub mod version;
 
+use std::collections::HashSet;
 use std::future::IntoFuture;
 use std::sync::Arc;
+use std::time::Duration;
 
-#[cfg(feature = "cartridge")]
-use anyhow::ensure;
-use anyhow::{Context, Result};
+use anyhow::{ensure, Context, Result};
 use config::rpc::RpcModuleKind;
 use config::Config;
 use dojo_metrics::exporters::prometheus::PrometheusRecorder;
@@ -25,21 +29,29 @@ use katana_core::backend::storage::Blockchain;
 use katana_core::backend::Backend;
 use katana_core::constants::{
     DEFAULT_ETH_L1_DATA_GAS_PRICE, DEFAULT_ETH_L1_GAS_PRICE, DEFAULT_STRK_L1_DATA_GAS_PRICE,
-    DEFAULT_STRK_L1_GAS_PRICE,
+    DEFAULT_STRK_L1_GAS_PRICE, DETERMINISTIC_BLOCK_TIME,
 };
 use katana_core::env::BlockContextGenerator;
 use katana_core::service::block_producer::BlockProducer;
+use katana_core::service::pruner::Pruner;
+use katana_core::service::settlement::{SettlementContract, SettlementTracker};
 use katana_db::mdbx::DbEnv;
 use katana_executor::implementation::blockifier::BlockifierFactory;
 use katana_executor::ExecutionFlags;
 use katana_pool::ordering::FiFo;
-use katana_pool::TxPool;
+use katana_pool::{TransactionPool, TxPool};
 use katana_primitives::block::GasPrices;
 use katana_primitives::env::{CfgEnv, FeeTokenAddressses};
+use katana_primitives::transaction::TxHash;
+use katana_provider::providers::db::DbProvider;
+use katana_provider::traits::transaction::TransactionProvider;
+use katana_rpc::auth::Auth;
 #[cfg(feature = "cartridge")]
 use katana_rpc::cartridge::CartridgeApi;
 use katana_rpc::cors::Cors;
 use katana_rpc::dev::DevApi;
+use katana_rpc::katana::KatanaApi;
+use katana_rpc::replication::ReplicationApi;
 use katana_rpc::saya::SayaApi;
 use katana_rpc::starknet::forking::ForkedClient;
 #[cfg(feature = "cartridge")]
@@ -50,14 +62,24 @@ use katana_rpc::{RpcServer, RpcServerHandle};
 #[cfg(feature = "cartridge")]
 use katana_rpc_api::cartridge::CartridgeApiServer;
 use katana_rpc_api::dev::DevApiServer;
+use katana_rpc_api::katana::KatanaApiServer;
+use katana_rpc_api::replication::ReplicationApiServer;
 use katana_rpc_api::saya::SayaApiServer;
-use katana_rpc_api::starknet::{StarknetApiServer, StarknetTraceApiServer, StarknetWriteApiServer};
+use katana_rpc_api::starknet::{
+    StarknetApiServer, StarknetSubscriptionApiServer, StarknetTraceApiServer,
+    StarknetWriteApiServer,
+};
 use katana_rpc_api::torii::ToriiApiServer;
 use katana_stage::Sequencing;
 use katana_tasks::TaskManager;
-use tracing::info;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
 
 use crate::exit::NodeStoppedFuture;
+use crate::follower::Follower;
+
+/// The maximum time to wait for the pending transactions to be executed when stopping the node.
+const SHUTDOWN_FLUSH_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// A handle to the launched node.
 #[allow(missing_debug_implementations)]
@@ -74,7 +96,23 @@ impl LaunchedNode {
     pub async fn stop(&self) -> Result<()> {
         // TODO: wait for the rpc server to stop instead of just stopping it.
         self.rpc.stop()?;
+
+        // no new transactions can be received at this point, so we can safely wait for the
+        // remaining ones to be executed before stopping the block production.
+        self.node.flush_pending_transactions().await?;
         self.node.task_manager.shutdown().await;
+
+        if let Some(db) = &self.node.db {
+            db.sync().context("failed to flush database")?;
+        }
+
+        if let Some(path) = &self.node.config.db.dump_state {
+            let provider = self.node.backend.blockchain.provider();
+            let genesis = self.node.backend.chain_spec.genesis().number;
+            dump::dump_state(provider, genesis)?.write(path)?;
+            info!(path = %path.display(), "State dumped.");
+        }
+
         Ok(())
     }
 
@@ -116,6 +154,8 @@ impl Node {
                 reports.push(Box::new(db.clone()) as Box<dyn Report>);
             }
 
+            reports.push(Box::new(self.pool.clone()) as Box<dyn Report>);
+
             let exporter = PrometheusRecorder::current().expect("qed; should exist at this point");
             let server = MetricsServer::new(exporter).with_process_metrics().with_reports(reports);
 
@@ -127,22 +167,32 @@ impl Node {
         let backend = self.backend.clone();
         let block_producer = self.block_producer.clone();
 
-        // --- build and run sequencing task
+        // --- build and run sequencing task, or replicate the primary node in follower mode
 
-        let sequencing = Sequencing::new(
-            pool.clone(),
-            backend.clone(),
-            self.task_manager.task_spawner(),
-            block_producer.clone(),
-            self.config.messaging.clone(),
-        );
-
-        self.task_manager
-            .task_spawner()
-            .build_task()
-            .critical()
-            .name("Sequencing")
-            .spawn(sequencing.into_future());
+        if let Some(cfg) = &self.config.follower {
+            let follower = Follower::new(backend.clone(), cfg)?;
+            self.task_manager
+                .task_spawner()
+                .build_task()
+                .critical()
+                .name("Follower")
+                .spawn(follower.run());
+        } else {
+            let sequencing = Sequencing::new(
+                pool.clone(),
+                backend.clone(),
+                self.task_manager.task_spawner(),
+                block_producer.clone(),
+                self.config.messaging.clone(),
+            );
+
+            self.task_manager
+                .task_spawner()
+                .build_task()
+                .critical()
+                .name("Sequencing")
+                .spawn(sequencing.into_future());
+        }
 
         // --- start the rpc server
 
@@ -152,8 +202,109 @@ impl Node {
         self.backend.gas_oracle.run_worker(self.task_manager.task_spawner());
         info!(target: "node", "Gas price oracle worker started.");
 
+        // --- start the pruner task
+
+        let pruning = &self.config.pruning;
+        if let Some(db) = self.db.as_ref().filter(|_| pruning.is_enabled()) {
+            let mut pruner = Pruner::new(DbProvider::new(db.clone()));
+
+            if let Some(distance) = pruning.receipts {
+                pruner = pruner.receipts(distance);
+            }
+
+            if let Some(distance) = pruning.traces {
+                pruner = pruner.traces(distance);
+            }
+
+            if let Some(distance) = pruning.state_diffs {
+                pruner = pruner.state_diffs(distance);
+            }
+
+            let blocks = self.backend.block_notifier.subscribe();
+            self.task_manager.task_spawner().build_task().name("Pruner").spawn(pruner.run(blocks));
+            info!(target: "node", "Pruner started.");
+        } else if pruning.is_enabled() {
+            warn!(target: "node", "Pruning is only supported with a local database, nothing will be pruned.");
+        }
+
+        // --- start the settlement tracker task
+
+        let contract =
+            self.config.chain.settlement().and_then(SettlementContract::from_settlement_layer);
+        if let (Some(db), Some(contract)) = (self.db.as_ref(), contract) {
+            let tracker = SettlementTracker::new(DbProvider::new(db.clone()), contract);
+            self.task_manager
+                .task_spawner()
+                .build_task()
+                .name("Settlement tracker")
+                .spawn(tracker.run());
+            info!(target: "node", "Settlement tracker started.");
+        }
+
         Ok(LaunchedNode { node: self, rpc: rpc_handle })
     }
+
+    /// Waits for the ready transactions in the pool to be executed, and then seals the pending
+    /// block so that they're not lost when the node is stopped.
+    ///
+    /// Gives up after [`SHUTDOWN_FLUSH_TIMEOUT`], or if the node tasks have already been stopped.
+    async fn flush_pending_transactions(&self) -> Result<()> {
+        let provider = self.backend.blockchain.provider();
+
+        // subscribe before looking at the pool so that no execution is missed in between
+        let mut executed = self.backend.executed_tx_notifier.subscribe();
+
+        // the ready transactions in the pool that are neither in the pending block nor mined yet.
+        // the parked ones aren't waited for, as they can't be executed until their nonce gap is
+        // filled.
+        let unexecuted = |hashes: Vec<TxHash>| -> Result<HashSet<TxHash>> {
+            let pending: HashSet<_> =
+                self.block_producer.pending_transaction_hashes().into_iter().collect();
+
+            let mut unexecuted = HashSet::new();
+            for hash in hashes {
+                if !pending.contains(&hash) && provider.transaction_by_hash(hash)?.is_none() {
+                    unexecuted.insert(hash);
+                }
+            }
+
+            Ok(unexecuted)
+        };
+
+        let mut remaining = unexecuted(self.pool.ready_transaction_hashes())?;
+
+        let wait = async {
+            while !remaining.is_empty() {
+                match executed.recv().await {
+                    Ok(tx) => {
+                        remaining.remove(&tx.tx.hash);
+                    }
+                    // some notifications were missed, so look up what's left instead
+                    Err(RecvError::Lagged(_)) => {
+                        remaining = unexecuted(remaining.drain().collect())?;
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+            Ok::<_, anyhow::Error>(())
+        };
+
+        tokio::select! {
+            res = tokio::time::timeout(SHUTDOWN_FLUSH_TIMEOUT, wait) => match res {
+                Ok(res) => res?,
+                Err(_) => warn!(target: "node", "Timed out waiting for transactions to execute."),
+            },
+            // the block production has already stopped, so nothing will be executed anymore
+            _ = self.task_manager.wait_for_shutdown() => return Ok(()),
+        }
+
+        if let Some(outcome) = self.block_producer.seal_pending_block()? {
+            self.pool.remove_transactions(&outcome.txs);
+            info!(target: "node", block_number = %outcome.block_number, "Sealed pending block.");
+        }
+
+        Ok(())
+    }
 }
 
 /// Build the node components from the given [`Config`].
@@ -167,6 +318,16 @@ pub async fn build(mut config: Config) -> Result<Node> {
         let _ = PrometheusRecorder::install("katana")?;
     }
 
+    if config.follower.is_some() {
+        ensure!(config.forking.is_none(), "Forking is not supported in follower mode");
+        ensure!(
+            !config.rpc.apis.contains(&RpcModuleKind::Dev),
+            "The dev API is not supported in follower mode"
+        );
+        #[cfg(feature = "cartridge")]
+        ensure!(config.paymaster.is_none(), "Paymaster is not supported in follower mode");
+    }
+
     // --- build executor factory
 
     let fee_token_addresses = match config.chain.as_ref() {
@@ -198,12 +359,30 @@ pub async fn build(mut config: Config) -> Result<Node> {
             factory.set_max_call_gas(max_call_gas);
         }
 
+        if let Some(concurrency) = config.sequencing.concurrency {
+            factory.set_concurrency(concurrency);
+        }
+
+        if config.execution.native {
+            #[cfg(feature = "native")]
+            factory.enable_native();
+
+            #[cfg(not(feature = "native"))]
+            return Err(anyhow::anyhow!(
+                "Native execution requires Katana to be built with the `native` feature"
+            ));
+        }
+
         Arc::new(factory)
     };
 
     // --- build backend
 
     let (blockchain, db, forked_client) = if let Some(cfg) = &config.forking {
+        if config.pruning.is_enabled() {
+            return Err(anyhow::anyhow!("Pruning is not supported in forking mode"));
+        }
+
         let chain_spec = Arc::get_mut(&mut config.chain).expect("get mut Arc");
 
         let ChainSpec::Dev(chain_spec) = chain_spec else {
@@ -231,6 +410,9 @@ pub async fn build(mut config: Config) -> Result<Node> {
     let gas_oracle = if let Some(fixed_prices) = &config.dev.fixed_gas_prices {
         // Use fixed gas prices if provided in the configuration
         GasOracle::fixed(fixed_prices.gas_price.clone(), fixed_prices.data_gas_price.clone())
+    } else if let Some(url) = &config.dev.l1_provider_url {
+        // Sample the gas prices from the given L1 provider
+        GasOracle::sampled_ethereum(url.clone())
     } else if let Some(settlement) = config.chain.settlement() {
         match settlement {
             SettlementLayer::Starknet { .. } => GasOracle::sampled_starknet(),
@@ -253,20 +435,36 @@ pub async fn build(mut config: Config) -> Result<Node> {
         )
     };
 
-    let block_context_generator = BlockContextGenerator::default().into();
+    let fixed_block_time = config.dev.deterministic.then_some(DETERMINISTIC_BLOCK_TIME);
+    let block_context_generator = BlockContextGenerator { fixed_block_time, ..Default::default() };
+    let block_context_generator = block_context_generator.into();
     let backend = Arc::new(Backend {
         gas_oracle,
         blockchain,
         executor_factory,
         block_context_generator,
         chain_spec: config.chain.clone(),
+        block_notifier: Default::default(),
+        executed_tx_notifier: Default::default(),
+        provable: config.sequencing.provable,
     });
 
     backend.init_genesis().context("failed to initialize genesis")?;
 
+    if let Some(path) = &config.db.load_state {
+        let dump = dump::StateDump::read(path)?;
+        let genesis = config.chain.genesis().number;
+        dump::load_state(backend.blockchain.provider(), genesis, dump)
+            .context("failed to load state dump")?;
+    }
+
     // --- build block producer
 
-    let block_producer = if config.sequencing.block_time.is_some() || config.sequencing.no_mining {
+    // a follower never produces blocks, so it must not keep a pending block that would go stale
+    // as blocks are replicated from the primary.
+    let block_producer = if config.follower.is_some() {
+        BlockProducer::instant(Arc::clone(&backend))
+    } else if config.sequencing.block_time.is_some() || config.sequencing.no_mining {
         if let Some(interval) = config.sequencing.block_time {
             BlockProducer::interval(Arc::clone(&backend), interval)
         } else {
@@ -284,12 +482,14 @@ pub async fn build(mut config: Config) -> Result<Node> {
     // --- build rpc server
 
     let mut rpc_modules = RpcModule::new(());
+    // the state-mutating methods, which require the api key if one is configured
+    let mut protected_methods = HashSet::new();
 
     let cors = Cors::new()
         .allow_origins(config.rpc.cors_origins.clone())
         // Allow `POST` when accessing the resource
         .allow_methods([Method::POST, Method::GET])
-        .allow_headers([hyper::header::CONTENT_TYPE, "argent-client".parse().unwrap(), "argent-version".parse().unwrap()]);
+        .allow_headers([hyper::header::CONTENT_TYPE, hyper::header::AUTHORIZATION, "argent-client".parse().unwrap(), "argent-version".parse().unwrap()]);
 
     #[cfg(feature = "cartridge")]
     let paymaster = if let Some(paymaster) = &config.paymaster {
@@ -304,7 +504,9 @@ pub async fn build(mut config: Config) -> Result<Node> {
             pool.clone(),
             paymaster.cartridge_api_url.clone(),
         );
-        rpc_modules.merge(api.into_rpc())?;
+        let module = api.into_rpc();
+        protected_methods.extend(module.method_names().map(String::from));
+        rpc_modules.merge(module)?;
 
         Some(PaymasterConfig { cartridge_api_url: paymaster.cartridge_api_url.clone() })
     } else {
@@ -332,13 +534,23 @@ pub async fn build(mut config: Config) -> Result<Node> {
         };
 
         rpc_modules.merge(StarknetApiServer::into_rpc(api.clone()))?;
-        rpc_modules.merge(StarknetWriteApiServer::into_rpc(api.clone()))?;
-        rpc_modules.merge(StarknetTraceApiServer::into_rpc(api))?;
+
+        // transactions can only be submitted to the primary node
+        if config.follower.is_none() {
+            let write_module = StarknetWriteApiServer::into_rpc(api.clone());
+            protected_methods.extend(write_module.method_names().map(String::from));
+            rpc_modules.merge(write_module)?;
+        }
+
+        rpc_modules.merge(StarknetTraceApiServer::into_rpc(api.clone()))?;
+        rpc_modules.merge(StarknetSubscriptionApiServer::into_rpc(api))?;
     }
 
     if config.rpc.apis.contains(&RpcModuleKind::Dev) {
         let api = DevApi::new(backend.clone(), block_producer.clone());
-        rpc_modules.merge(DevApiServer::into_rpc(api))?;
+        let module = DevApiServer::into_rpc(api);
+        protected_methods.extend(module.method_names().map(String::from));
+        rpc_modules.merge(module)?;
     }
 
     if config.rpc.apis.contains(&RpcModuleKind::Torii) {
@@ -351,7 +563,52 @@ pub async fn build(mut config: Config) -> Result<Node> {
         rpc_modules.merge(api.into_rpc())?;
     }
 
-    let rpc_server = RpcServer::new().metrics().health_check().cors(cors).module(rpc_modules);
+    if config.rpc.apis.contains(&RpcModuleKind::Replication) {
+        let api = ReplicationApi::new(backend.clone());
+        rpc_modules.merge(api.into_rpc())?;
+    }
+
+    if config.rpc.apis.contains(&RpcModuleKind::Katana) {
+        let api = KatanaApi::new(backend.clone(), block_producer.clone());
+        rpc_modules.merge(api.into_rpc())?;
+    }
+
+    let mut rpc_server = RpcServer::new().metrics().health_check().cors(cors).module(rpc_modules);
+
+    if let Some(max) = config.rpc.max_connections {
+        rpc_server = rpc_server.max_connections(max);
+    }
+    if let Some(max) = config.rpc.max_request_body_size {
+        rpc_server = rpc_server.max_request_body_size(max);
+    }
+    if let Some(max) = config.rpc.max_response_body_size {
+        rpc_server = rpc_server.max_response_body_size(max);
+    }
+    if let Some(max) = config.rpc.max_concurrent_requests {
+        rpc_server = rpc_server.max_concurrent_requests(max);
+    }
+    if let Some(per_second) = config.rpc.rate_limit {
+        rpc_server = rpc_server.rate_limit(per_second);
+    }
+    if let Some(max) = config.rpc.max_batch_size {
+        rpc_server = rpc_server.max_batch_size(max);
+    }
+    if let Some(max) = config.rpc.batch_concurrency {
+        rpc_server = rpc_server.batch_concurrency(max);
+    }
+    if let Some(prefix) = &config.rpc.path_prefix {
+        rpc_server = rpc_server.path_prefix(prefix.clone());
+    }
+    if let Some(addr) = config.rpc.ws_socket_addr() {
+        rpc_server = rpc_server.ws(addr);
+    }
+    #[cfg(unix)]
+    if let Some(path) = &config.rpc.ipc_path {
+        rpc_server = rpc_server.ipc(path.clone());
+    }
+    if let Some(api_key) = &config.rpc.api_key {
+        rpc_server = rpc_server.auth(Auth::new(api_key.clone(), protected_methods));
+    }
 
     Ok(Node {
         db,
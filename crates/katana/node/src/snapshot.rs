@@ -0,0 +1,253 @@
+//! Portable snapshots of the chain.
+//!
+//! Unlike a copy of the database directory, a snapshot doesn't depend on the on-disk format of the
+//! database, so it can be shared between machines and restored by nodes using a different
+//! database version. A snapshot contains every block of the chain - including the genesis block -
+//! along with the state updates, declared classes, receipts and execution traces of each block.
+//!
+//! A snapshot file starts with the [`SNAPSHOT_MAGIC`] bytes and the format version as a little
+//! endian `u32`, followed by a gzip-compressed stream of JSON-encoded [`DumpedBlock`]s in
+//! ascending order.
+
+use std::fs;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use katana_primitives::block::BlockHash;
+use katana_provider::error::ProviderError;
+use katana_provider::providers::db::DbProvider;
+use katana_provider::traits::block::{
+    BlockHashProvider, BlockNumberProvider, BlockProvider, BlockStatusProvider, BlockWriter,
+};
+use katana_provider::traits::state::StateFactoryProvider;
+use katana_provider::traits::state_update::StateUpdateProvider;
+use katana_provider::traits::transaction::{ReceiptProvider, TransactionTraceProvider};
+use katana_provider::traits::trie::TrieWriter;
+use tracing::info;
+
+use crate::dump::{self, DumpedBlock};
+
+/// The bytes that every snapshot file starts with.
+pub const SNAPSHOT_MAGIC: [u8; 8] = *b"KTNSNAP\0";
+
+/// The version of the snapshot format.
+///
+/// Must be bumped whenever a breaking change is made to the snapshot format, including changes to
+/// the [`DumpedBlock`] type.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Writes a snapshot of all the blocks in the `provider` to `writer`.
+///
+/// Returns the number of exported blocks.
+pub fn export_snapshot<P>(provider: &P, writer: impl Write) -> Result<u64>
+where
+    P: BlockProvider
+        + BlockStatusProvider
+        + StateUpdateProvider
+        + StateFactoryProvider
+        + ReceiptProvider
+        + TransactionTraceProvider,
+{
+    let latest = provider.latest_number()?;
+    let state = provider.latest()?;
+
+    // the genesis block isn't necessarily the block 0, so we look for the first block of the chain
+    let mut first = latest;
+    while first > 0 && provider.block_hash_by_num(first - 1)?.is_some() {
+        first -= 1;
+    }
+
+    let mut writer = BufWriter::new(writer);
+    writer.write_all(&SNAPSHOT_MAGIC)?;
+    writer.write_all(&SNAPSHOT_VERSION.to_le_bytes())?;
+
+    let mut encoder = GzEncoder::new(writer, Compression::default());
+
+    for num in first..=latest {
+        let block = dump::dump_block(provider, &*state, num)?;
+        serde_json::to_writer(&mut encoder, &block)
+            .with_context(|| format!("failed to write block {num}"))?;
+    }
+
+    encoder.finish()?.flush()?;
+
+    let total = latest - first + 1;
+    info!(target: "node", blocks = %total, "Snapshot exported.");
+
+    Ok(total)
+}
+
+/// Restores a snapshot read from `reader` into a new database at `path`.
+///
+/// The directory at `path` must either not exist or be empty. The snapshot is first imported into a
+/// temporary database next to `path`, which is only moved to `path` once the whole snapshot has
+/// been imported, so a failed import never leaves a partially imported database behind. Returns
+/// the number of imported blocks.
+pub fn import_snapshot_at(path: &Path, reader: impl Read) -> Result<u64> {
+    if path.exists() && fs::read_dir(path)?.next().is_some() {
+        bail!(
+            "snapshot can only be imported into an empty directory, but {} isn't",
+            path.display()
+        );
+    }
+
+    let parent = path.parent().context("invalid database path")?;
+    fs::create_dir_all(parent)?;
+
+    let temp = tempfile::Builder::new().prefix(".katana-import-").tempdir_in(parent)?;
+
+    // the database must be closed before it can be moved
+    let total = {
+        let provider = DbProvider::new(katana_db::init_db(temp.path())?);
+        import_snapshot(&provider, reader)?
+    };
+
+    if path.exists() {
+        fs::remove_dir(path)?;
+    }
+
+    fs::rename(temp.into_path(), path)
+        .with_context(|| format!("failed to move the imported database to {}", path.display()))?;
+
+    Ok(total)
+}
+
+/// Restores a snapshot read from `reader` into the `provider`.
+///
+/// The provider must not contain any blocks. The blocks are inserted one by one, so the provider
+/// is left with the blocks imported so far if the import fails midway, see [`import_snapshot_at`]
+/// for an atomic import. Returns the number of imported blocks.
+pub fn import_snapshot<P>(provider: &P, reader: impl Read) -> Result<u64>
+where
+    P: BlockNumberProvider + BlockHashProvider + BlockWriter + TrieWriter,
+{
+    match provider.latest_number() {
+        Err(ProviderError::MissingLatestBlockNumber) => {}
+        Ok(latest) => {
+            bail!("snapshot can only be imported into an empty database, but found block {latest}")
+        }
+        Err(error) => return Err(error.into()),
+    }
+
+    let mut reader = BufReader::new(reader);
+
+    let mut magic = [0u8; SNAPSHOT_MAGIC.len()];
+    reader.read_exact(&mut magic).context("failed to read snapshot header")?;
+    if magic != SNAPSHOT_MAGIC {
+        bail!("not a katana snapshot");
+    }
+
+    let mut version = [0u8; 4];
+    reader.read_exact(&mut version).context("failed to read snapshot header")?;
+    let version = u32::from_le_bytes(version);
+    if version != SNAPSHOT_VERSION {
+        bail!("unsupported snapshot version: expected {SNAPSHOT_VERSION}, got {version}");
+    }
+
+    let decoder = GzDecoder::new(reader);
+    let blocks = serde_json::Deserializer::from_reader(decoder).into_iter::<DumpedBlock>();
+
+    let mut total = 0;
+    let mut parent: Option<(u64, BlockHash)> = None;
+
+    for block in blocks {
+        let block = block.context("failed to read block from snapshot")?;
+        let number = block.header.number;
+
+        // the blocks must form a chain, otherwise the snapshot is corrupted
+        if let Some((parent_number, parent_hash)) = parent {
+            if number != parent_number + 1 || block.header.parent_hash != parent_hash {
+                bail!("block {number} in snapshot is not a child of block {parent_number}");
+            }
+        }
+
+        parent = Some((number, block.hash));
+        dump::insert_block(provider, block)?;
+        total += 1;
+    }
+
+    if total == 0 {
+        bail!("snapshot doesn't contain any blocks");
+    }
+
+    info!(target: "node", blocks = %total, "Snapshot imported.");
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use katana_provider::test_utils::provider_with_blocks;
+
+    use super::*;
+
+    #[test]
+    fn export_and_import_roundtrip() {
+        let source = provider_with_blocks(5);
+
+        let mut snapshot = Vec::new();
+        assert_eq!(export_snapshot(&source, &mut snapshot).unwrap(), 5);
+        assert!(snapshot.starts_with(&SNAPSHOT_MAGIC));
+
+        let target = DbProvider::new_ephemeral();
+        assert_eq!(import_snapshot(&target, snapshot.as_slice()).unwrap(), 5);
+
+        assert_eq!(target.latest_number().unwrap(), 4);
+        for num in 0..5 {
+            assert_eq!(
+                target.block_hash_by_num(num).unwrap(),
+                source.block_hash_by_num(num).unwrap()
+            );
+        }
+
+        // importing into a database that already has blocks must fail
+        assert!(import_snapshot(&target, snapshot.as_slice()).is_err());
+    }
+
+    #[test]
+    fn reject_invalid_snapshot() {
+        let source = provider_with_blocks(1);
+        let mut snapshot = Vec::new();
+        export_snapshot(&source, &mut snapshot).unwrap();
+
+        // unknown format version
+        let mut unsupported = snapshot.clone();
+        unsupported[SNAPSHOT_MAGIC.len()] += 1;
+        let err = import_snapshot(&DbProvider::new_ephemeral(), unsupported.as_slice());
+        assert!(err.unwrap_err().to_string().contains("unsupported snapshot version"));
+
+        // not a snapshot at all
+        let err = import_snapshot(&DbProvider::new_ephemeral(), b"{}".as_slice());
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn import_snapshot_atomically() {
+        let source = provider_with_blocks(5);
+        let mut snapshot = Vec::new();
+        export_snapshot(&source, &mut snapshot).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+
+        // a failed import must not leave a partially imported database behind
+        let path = dir.path().join("truncated");
+        let truncated = &snapshot[..snapshot.len() / 2];
+        assert!(import_snapshot_at(&path, truncated).is_err());
+        assert!(!path.exists());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+
+        let path = dir.path().join("db");
+        assert_eq!(import_snapshot_at(&path, snapshot.as_slice()).unwrap(), 5);
+
+        let provider = DbProvider::new(katana_db::init_db(&path).unwrap());
+        assert_eq!(provider.latest_number().unwrap(), 4);
+        drop(provider);
+
+        // the directory isn't empty anymore
+        assert!(import_snapshot_at(&path, snapshot.as_slice()).is_err());
+    }
+}
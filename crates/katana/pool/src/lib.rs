@@ -28,6 +28,8 @@ pub type PoolResult<T> = Result<T, PoolError>;
 pub enum PoolError {
     #[error("Invalid transaction: {0}")]
     InvalidTransaction(Box<InvalidTransactionError>),
+    #[error("Too many transactions are waiting for a nonce gap to be filled")]
+    ParkedPoolFull,
     #[error("Internal error: {0}")]
     Internal(Box<dyn std::error::Error>),
 }
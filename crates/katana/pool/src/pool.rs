@@ -1,9 +1,12 @@
 use core::fmt;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
+use dojo_metrics::metrics::gauge;
+use dojo_metrics::Report;
 use futures::channel::mpsc::{channel, Receiver, Sender};
 use katana_primitives::transaction::TxHash;
+use katana_primitives::Felt;
 use parking_lot::RwLock;
 use tokio::sync::mpsc;
 use tracing::{error, info, warn};
@@ -26,11 +29,33 @@ where
     inner: Arc<Inner<T, V, O>>,
 }
 
+/// The maximum distance between a transaction's nonce and its sender's current nonce for the
+/// transaction to be parked in the pool until the nonce gap is filled.
+pub const MAX_NONCE_GAP: u64 = 64;
+
+/// The maximum number of transactions that can be parked in the pool at once.
+pub const MAX_PARKED_TRANSACTIONS: usize = 4096;
+
+/// The minimum increase, in percent, of the tip or the max fee of a transaction for it to replace
+/// the transaction with the same sender and nonce in the pool.
+pub const REPLACEMENT_FEE_BUMP: u128 = 10;
+
 #[derive(Debug)]
 struct Inner<T, V, O: PoolOrd> {
     /// List of all valid txs in the pool.
     transactions: RwLock<BTreeSet<PendingTx<T, O>>>,
 
+    /// The valid txs in the pool, indexed by their id.
+    by_id: RwLock<BTreeMap<TxId, PendingTx<T, O>>>,
+
+    /// The ids of the valid txs that have been handed to a consumer of the pending transactions,
+    /// ie the block producer, and thus can no longer be replaced.
+    dispatched: RwLock<BTreeSet<TxId>>,
+
+    /// Transactions whose nonce is ahead of their sender's current nonce. They are kept aside
+    /// until all the transactions with the preceding nonces have been added to the pool.
+    parked: RwLock<BTreeMap<TxId, T>>,
+
     /// listeners for incoming txs
     listeners: RwLock<Vec<Sender<TxHash>>>,
 
@@ -56,7 +81,10 @@ where
             inner: Arc::new(Inner {
                 ordering,
                 validator,
+                parked: Default::default(),
                 transactions: Default::default(),
+                by_id: Default::default(),
+                dispatched: Default::default(),
                 subscribers: Default::default(),
                 listeners: Default::default(),
             }),
@@ -92,6 +120,10 @@ where
 
     fn notify_subscribers(&self, tx: PendingTx<T, O>) {
         let mut subscribers = self.inner.subscribers.write();
+        if !subscribers.is_empty() {
+            self.inner.dispatched.write().insert(tx.id.clone());
+        }
+
         // this is basically a retain but with mut reference
         for n in (0..subscribers.len()).rev() {
             let sender = subscribers.swap_remove(n);
@@ -121,59 +153,85 @@ where
         self.inner.subscribers.write().push(tx);
         subscriber
     }
-}
 
-impl<T, V, O> TransactionPool for Pool<T, V, O>
-where
-    T: PoolTransaction + fmt::Debug,
-    V: Validator<Transaction = T>,
-    O: PoolOrd<Transaction = T>,
-{
-    type Transaction = T;
-    type Validator = V;
-    type Ordering = O;
-
-    fn add_transaction(&self, tx: T) -> PoolResult<TxHash> {
-        let hash = tx.hash();
-        let id = TxId::new(tx.sender(), tx.nonce());
+    /// Returns the total number of transactions that are waiting for a nonce gap to be filled.
+    pub fn parked_size(&self) -> usize {
+        self.inner.parked.read().len()
+    }
 
-        info!(target: "pool", hash = format!("{hash:#x}"), "Transaction received.");
+    /// Returns the hashes of the transactions that are ready to be executed, ie all the
+    /// transactions in the pool except the parked ones.
+    ///
+    /// Unlike [`TransactionPool::pending_transactions`], the transactions aren't dispatched.
+    pub fn ready_transaction_hashes(&self) -> Vec<TxHash> {
+        self.inner.transactions.read().iter().map(|tx| tx.tx.hash()).collect()
+    }
 
-        match self.inner.validator.validate(tx) {
-            Ok(outcome) => {
-                match outcome {
-                    ValidationOutcome::Valid(tx) => {
-                        // get the priority of the validated tx
-                        let priority = self.inner.ordering.priority(&tx);
-                        let tx = PendingTx::new(id, tx, priority);
+    /// Returns `true` if there is already a transaction with the given id in the pool.
+    fn is_occupied(&self, id: &TxId) -> bool {
+        self.inner.parked.read().contains_key(id) || self.inner.by_id.read().contains_key(id)
+    }
 
-                        // insert the tx in the pool
-                        self.inner.transactions.write().insert(tx.clone());
-                        self.notify(tx);
+    fn validate_and_insert(&self, id: TxId, tx: T) -> PoolResult<()> {
+        let hash = tx.hash();
+        let result = self.inner.validator.validate(tx);
+        self.insert(id, hash, result)
+    }
 
-                        Ok(hash)
-                    }
+    /// Inserts a transaction in the pool according to the outcome of its validation.
+    fn insert(
+        &self,
+        id: TxId,
+        hash: TxHash,
+        result: crate::validation::ValidationResult<T>,
+    ) -> PoolResult<()> {
+        match result {
+            Ok(outcome) => match outcome {
+                ValidationOutcome::Valid(tx) => {
+                    // get the priority of the validated tx
+                    let priority = self.inner.ordering.priority(&tx);
+                    let tx = PendingTx::new(id.clone(), tx, priority);
+
+                    // insert the tx in the pool
+                    self.inner.transactions.write().insert(tx.clone());
+                    self.inner.by_id.write().insert(id.clone(), tx.clone());
+                    self.notify(tx);
+
+                    // the sender's next nonce may now be available
+                    self.promote_parked(&id);
+
+                    Ok(())
+                }
 
-                    // TODO: create a small cache for rejected transactions to respect the rpc spec
-                    // `getTransactionStatus`
-                    ValidationOutcome::Invalid { error, .. } => {
-                        warn!(target: "pool", hash = format!("{hash:#x}"), %error, "Invalid transaction.");
-                        Err(PoolError::InvalidTransaction(Box::new(error)))
-                    }
+                // TODO: create a small cache for rejected transactions to respect the rpc spec
+                // `getTransactionStatus`
+                ValidationOutcome::Invalid { error, .. } => {
+                    warn!(target: "pool", hash = format!("{hash:#x}"), %error, "Invalid transaction.");
+                    Err(PoolError::InvalidTransaction(Box::new(error)))
+                }
 
-                    // return as error for now but ideally we should kept the tx in a separate
-                    // queue and revalidate it when the parent tx is added to the pool
-                    ValidationOutcome::Dependent { tx, tx_nonce, current_nonce } => {
-                        info!(target: "pool", hash = format!("{hash:#x}"), %tx_nonce, %current_nonce, "Dependent transaction.");
+                // keep the tx aside until the transactions with the preceding nonces are added
+                ValidationOutcome::Dependent { tx, tx_nonce, current_nonce } => {
+                    if tx_nonce - current_nonce > Felt::from(MAX_NONCE_GAP) {
                         let err = InvalidTransactionError::InvalidNonce {
                             address: tx.sender(),
                             current_nonce,
                             tx_nonce,
                         };
-                        Err(PoolError::InvalidTransaction(Box::new(err)))
+                        return Err(PoolError::InvalidTransaction(Box::new(err)));
                     }
+
+                    let mut parked = self.inner.parked.write();
+                    if parked.len() >= MAX_PARKED_TRANSACTIONS {
+                        warn!(target: "pool", hash = format!("{hash:#x}"), "Too many parked transactions.");
+                        return Err(PoolError::ParkedPoolFull);
+                    }
+
+                    info!(target: "pool", hash = format!("{hash:#x}"), %tx_nonce, %current_nonce, "Dependent transaction parked.");
+                    parked.insert(id, tx);
+                    Ok(())
                 }
-            }
+            },
 
             Err(error @ crate::validation::Error { hash, .. }) => {
                 error!(target: "pool", hash = format!("{hash:#x}"), %error, "Failed to validate transaction.");
@@ -182,12 +240,129 @@ where
         }
     }
 
+    /// Moves the parked transaction that directly follows `id` (if any) into the pool.
+    ///
+    /// This is done recursively, so every parked transaction whose nonce gap has been filled will
+    /// be promoted.
+    fn promote_parked(&self, id: &TxId) {
+        let descendent = id.descendent();
+        let Some(tx) = self.inner.parked.write().remove(&descendent) else { return };
+
+        let hash = tx.hash();
+        if let Err(error) = self.validate_and_insert(descendent, tx) {
+            warn!(target: "pool", hash = format!("{hash:#x}"), %error, "Dropping parked transaction.");
+        }
+    }
+
+    /// Replaces the transaction with the same id as `tx`.
+    ///
+    /// The replacement is only accepted if it pays more than the existing transaction, ie it bumps
+    /// its tip or max fee by at least [`REPLACEMENT_FEE_BUMP`] percent, and is not lower in either
+    /// of them. A valid transaction that has already been handed to the block producer can't be
+    /// replaced anymore.
+    fn replace_transaction(&self, id: TxId, tx: T) -> PoolResult<()> {
+        fn is_bumped(new: u128, old: u128) -> bool {
+            let min_bump = old
+                .checked_mul(REPLACEMENT_FEE_BUMP)
+                .map_or(old / 100 * REPLACEMENT_FEE_BUMP, |v| v / 100);
+            new > old && new - old >= min_bump
+        }
+
+        fn is_underpriced<T: PoolTransaction>(new: &T, old: &T) -> bool {
+            let (new_tip, old_tip) = (new.tip() as u128, old.tip() as u128);
+            let (new_fee, old_fee) = (new.max_fee(), old.max_fee());
+            new_tip < old_tip
+                || new_fee < old_fee
+                || !(is_bumped(new_tip, old_tip) || is_bumped(new_fee, old_fee))
+        }
+
+        let invalid = |error| PoolError::InvalidTransaction(Box::new(error));
+        let (address, nonce) = (tx.sender(), tx.nonce());
+        let hash = tx.hash();
+
+        let parked = self.inner.parked.read().get(&id).cloned();
+        let result = if let Some(existing) = parked {
+            if is_underpriced(&tx, &existing) {
+                return Err(invalid(InvalidTransactionError::ReplacementUnderpriced {
+                    address,
+                    nonce,
+                }));
+            }
+
+            // the nonce of a parked transaction hasn't been accounted for by the validator yet
+            self.inner.validator.validate(tx)
+        } else {
+            let existing = self.inner.by_id.read().get(&id).cloned();
+            let Some(existing) = existing else { return self.validate_and_insert(id, tx) };
+
+            if self.inner.dispatched.read().contains(&id) {
+                return Err(invalid(InvalidTransactionError::ReplacementNotAllowed {
+                    address,
+                    nonce,
+                }));
+            }
+
+            if is_underpriced(&tx, &existing.tx) {
+                return Err(invalid(InvalidTransactionError::ReplacementUnderpriced {
+                    address,
+                    nonce,
+                }));
+            }
+
+            self.inner.validator.validate_replacement(tx)
+        };
+
+        // only evict the existing transaction once the replacement is known to be valid
+        if let Ok(ValidationOutcome::Valid(..) | ValidationOutcome::Dependent { .. }) = result {
+            self.remove_by_id(&id);
+            info!(target: "pool", hash = format!("{hash:#x}"), "Transaction replaced.");
+        }
+
+        self.insert(id, hash, result)
+    }
+
+    /// Removes the transaction with the given id from the pool, whether it's parked or not.
+    fn remove_by_id(&self, id: &TxId) {
+        self.inner.parked.write().remove(id);
+        let removed = self.inner.by_id.write().remove(id);
+        if removed.is_some() {
+            self.inner.transactions.write().retain(|t| &t.id != id);
+        }
+        self.inner.dispatched.write().remove(id);
+    }
+}
+
+impl<T, V, O> TransactionPool for Pool<T, V, O>
+where
+    T: PoolTransaction + fmt::Debug,
+    V: Validator<Transaction = T>,
+    O: PoolOrd<Transaction = T>,
+{
+    type Transaction = T;
+    type Validator = V;
+    type Ordering = O;
+
+    fn add_transaction(&self, tx: T) -> PoolResult<TxHash> {
+        let hash = tx.hash();
+        let id = TxId::new(tx.sender(), tx.nonce());
+
+        info!(target: "pool", hash = format!("{hash:#x}"), "Transaction received.");
+
+        if self.is_occupied(&id) {
+            self.replace_transaction(id, tx)?;
+        } else {
+            self.validate_and_insert(id, tx)?;
+        }
+
+        Ok(hash)
+    }
+
     fn pending_transactions(&self) -> PendingTransactions<Self::Transaction, Self::Ordering> {
         // take all the transactions
-        PendingTransactions {
-            subscription: self.subscribe(),
-            all: self.inner.transactions.read().clone().into_iter(),
-        }
+        let subscription = self.subscribe();
+        let all = self.inner.transactions.read().clone();
+        self.inner.dispatched.write().extend(all.iter().map(|tx| tx.id.clone()));
+        PendingTransactions { subscription, all: all.into_iter() }
     }
 
     // check if a tx is in the pool
@@ -196,12 +371,18 @@ where
     }
 
     fn get(&self, hash: TxHash) -> Option<Arc<T>> {
-        self.inner
+        let pending = self
+            .inner
             .transactions
             .read()
             .iter()
             .find(|tx| tx.tx.hash() == hash)
-            .map(|t| Arc::clone(&t.tx))
+            .map(|t| Arc::clone(&t.tx));
+
+        pending.or_else(|| {
+            let parked = self.inner.parked.read();
+            parked.values().find(|tx| tx.hash() == hash).map(|tx| Arc::new(tx.clone()))
+        })
     }
 
     fn add_listener(&self) -> Receiver<TxHash> {
@@ -213,8 +394,21 @@ where
 
     fn remove_transactions(&self, hashes: &[TxHash]) {
         // retain only transactions that aren't included in the list
-        let mut txs = self.inner.transactions.write();
-        txs.retain(|t| !hashes.contains(&t.tx.hash()))
+        self.inner.transactions.write().retain(|t| !hashes.contains(&t.tx.hash()));
+
+        let mut removed = Vec::new();
+        self.inner.by_id.write().retain(|id, t| {
+            let retain = !hashes.contains(&t.tx.hash());
+            if !retain {
+                removed.push(id.clone());
+            }
+            retain
+        });
+
+        let mut dispatched = self.inner.dispatched.write();
+        for id in removed {
+            dispatched.remove(&id);
+        }
     }
 
     fn size(&self) -> usize {
@@ -237,6 +431,18 @@ where
     }
 }
 
+impl<T, V, O> Report for Pool<T, V, O>
+where
+    T: PoolTransaction + fmt::Debug,
+    V: Validator<Transaction = T>,
+    O: PoolOrd<Transaction = T>,
+    Self: Send + Sync,
+{
+    fn report(&self) {
+        gauge!("pool.transactions").set(self.size() as f64);
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod test_utils {
 
@@ -291,6 +497,50 @@ pub(crate) mod test_utils {
             self.nonce = nonce;
             self
         }
+
+        pub fn with_max_fee(mut self, max_fee: u128) -> Self {
+            self.max_fee = max_fee;
+            self
+        }
+    }
+
+    /// A validator that only checks the transaction nonce against the nonces it has seen so far.
+    #[derive(Debug, Default)]
+    pub struct NonceValidator {
+        nonces: parking_lot::Mutex<std::collections::HashMap<ContractAddress, Nonce>>,
+    }
+
+    impl Validator for NonceValidator {
+        type Transaction = PoolTx;
+
+        fn validate(&self, tx: PoolTx) -> crate::validation::ValidationResult<PoolTx> {
+            let mut nonces = self.nonces.lock();
+            let current_nonce = nonces.get(&tx.sender).copied().unwrap_or_default();
+
+            if tx.nonce > current_nonce {
+                let tx_nonce = tx.nonce;
+                Ok(ValidationOutcome::Dependent { tx, tx_nonce, current_nonce })
+            } else if tx.nonce < current_nonce {
+                let error = InvalidTransactionError::InvalidNonce {
+                    address: tx.sender,
+                    current_nonce,
+                    tx_nonce: tx.nonce,
+                };
+                Ok(ValidationOutcome::Invalid { tx, error })
+            } else {
+                nonces.insert(tx.sender, current_nonce + Felt::ONE);
+                Ok(ValidationOutcome::Valid(tx))
+            }
+        }
+
+        fn validate_replacement(&self, tx: PoolTx) -> crate::validation::ValidationResult<PoolTx> {
+            let current_nonce = self.nonces.lock().get(&tx.sender).copied().unwrap_or_default();
+            if tx.nonce < current_nonce {
+                Ok(ValidationOutcome::Valid(tx))
+            } else {
+                self.validate(tx)
+            }
+        }
     }
 
     impl PoolTransaction for PoolTx {
@@ -323,9 +573,10 @@ mod tests {
     use katana_primitives::contract::{ContractAddress, Nonce};
     use katana_primitives::transaction::TxHash;
     use katana_primitives::Felt;
+    use rand::seq::SliceRandom;
 
     use super::test_utils::*;
-    use super::Pool;
+    use super::{Pool, MAX_NONCE_GAP, MAX_PARKED_TRANSACTIONS};
     use crate::ordering::FiFo;
     use crate::tx::PoolTransaction;
     use crate::validation::NoopValidator;
@@ -461,10 +712,16 @@ mod tests {
         });
     }
 
+    /// Tx pool that keeps track of the senders' nonces.
+    type NonceTestPool = Pool<PoolTx, NonceValidator, FiFo<PoolTx>>;
+
+    fn nonce_test_pool() -> NonceTestPool {
+        Pool::new(NonceValidator::default(), FiFo::new())
+    }
+
     #[tokio::test]
-    #[ignore = "Txs dependency management not fully implemented yet"]
     async fn dependent_txs_linear_insertion() {
-        let pool = TestPool::test();
+        let pool = nonce_test_pool();
 
         // Create 100 transactions with the same sender but increasing nonce
         let total = 100u128;
@@ -489,7 +746,147 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn dependent_txs_random_insertion() {
+        let pool = nonce_test_pool();
+
+        let total = 50u128;
+        let sender = ContractAddress::from(Felt::from_hex("0x1337").unwrap());
+        let mut txs: Vec<PoolTx> = (0..total)
+            .map(|i| PoolTx::new().with_sender(sender).with_nonce(Nonce::from(i)))
+            .collect();
+
+        txs.shuffle(&mut rand::thread_rng());
+
+        // None of the transactions should be rejected, the ones with a nonce gap are only parked.
+        for tx in &txs {
+            pool.add_transaction(tx.clone()).expect("failed to add tx");
+        }
+
+        // Once all the nonce gaps are filled, every transaction should be pending.
+        assert_eq!(pool.size(), total as usize);
+        assert_eq!(pool.parked_size(), 0);
+
+        // The transactions must be yielded according to their nonce regardless of the insertion
+        // order.
+        let mut pendings = pool.pending_transactions();
+        for i in 0..total {
+            let pending_tx = pendings.next().await.unwrap();
+            assert_eq!(pending_tx.tx.nonce(), Nonce::from(i));
+        }
+    }
+
+    #[test]
+    fn parked_txs() {
+        let pool = nonce_test_pool();
+        let sender = ContractAddress::from(Felt::from_hex("0x1337").unwrap());
+
+        let tx2 = PoolTx::new().with_sender(sender).with_nonce(Nonce::from(2u8));
+        pool.add_transaction(tx2.clone()).unwrap();
+
+        // the tx is parked but can still be queried from the pool
+        assert_eq!(pool.size(), 0);
+        assert_eq!(pool.parked_size(), 1);
+        assert!(pool.contains(tx2.hash()));
+        assert!(pool.ready_transaction_hashes().is_empty());
+
+        // transactions too far ahead of the current nonce are rejected
+        let far = Nonce::from(MAX_NONCE_GAP + 1);
+        let tx_far = PoolTx::new().with_sender(sender).with_nonce(far);
+        assert!(pool.add_transaction(tx_far).is_err());
+
+        let tx0 = PoolTx::new().with_sender(sender).with_nonce(Nonce::ZERO);
+        pool.add_transaction(tx0.clone()).unwrap();
+        assert_eq!(pool.size(), 1);
+        assert_eq!(pool.parked_size(), 1);
+        assert_eq!(pool.ready_transaction_hashes(), vec![tx0.hash()]);
+
+        // filling the gap should promote the parked tx
+        let tx1 = PoolTx::new().with_sender(sender).with_nonce(Nonce::ONE);
+        pool.add_transaction(tx1).unwrap();
+        assert_eq!(pool.size(), 3);
+        assert_eq!(pool.parked_size(), 0);
+    }
+
+    #[test]
+    fn replace_transaction() {
+        let pool = nonce_test_pool();
+        let sender = ContractAddress::from(Felt::from_hex("0x1337").unwrap());
+
+        let tx = PoolTx::new().with_sender(sender).with_nonce(Nonce::ZERO).with_tip(10);
+        let tx = tx.with_max_fee(100);
+        pool.add_transaction(tx.clone()).unwrap();
+
+        // same fees are considered underpriced
+        let same = PoolTx::new().with_sender(sender).with_nonce(Nonce::ZERO).with_tip(10);
+        assert!(pool.add_transaction(same.with_max_fee(100)).is_err());
+
+        // so is a bump smaller than the minimum
+        let small = PoolTx::new().with_sender(sender).with_nonce(Nonce::ZERO).with_tip(10);
+        assert!(pool.add_transaction(small.with_max_fee(105)).is_err());
+
+        // a lower max fee is underpriced even if the tip is higher
+        let lower = PoolTx::new().with_sender(sender).with_nonce(Nonce::ZERO).with_tip(20);
+        assert!(pool.add_transaction(lower.with_max_fee(50)).is_err());
+
+        let higher = PoolTx::new().with_sender(sender).with_nonce(Nonce::ZERO).with_tip(20);
+        let higher = higher.with_max_fee(100);
+        pool.add_transaction(higher.clone()).unwrap();
+
+        assert_eq!(pool.size(), 1);
+        assert!(!pool.contains(tx.hash()));
+        assert!(pool.contains(higher.hash()));
+
+        // parked transactions can be replaced too
+        let parked = PoolTx::new().with_sender(sender).with_nonce(Nonce::TWO).with_tip(1);
+        let parked = parked.with_max_fee(1);
+        pool.add_transaction(parked.clone()).unwrap();
+
+        let replacement = PoolTx::new().with_sender(sender).with_nonce(Nonce::TWO).with_tip(2);
+        let replacement = replacement.with_max_fee(1);
+        pool.add_transaction(replacement.clone()).unwrap();
+
+        assert_eq!(pool.parked_size(), 1);
+        assert!(!pool.contains(parked.hash()));
+        assert!(pool.contains(replacement.hash()));
+    }
+
+    #[test]
+    fn dispatched_transaction_cannot_be_replaced() {
+        let pool = nonce_test_pool();
+        let sender = ContractAddress::from(Felt::from_hex("0x1337").unwrap());
+
+        let tx = PoolTx::new().with_sender(sender).with_nonce(Nonce::ZERO).with_tip(10);
+        pool.add_transaction(tx.clone().with_max_fee(100)).unwrap();
+
+        // the transaction is handed to the consumer of the pending transactions
+        let _pending = pool.pending_transactions();
+
+        let higher = PoolTx::new().with_sender(sender).with_nonce(Nonce::ZERO).with_tip(20);
+        assert!(pool.add_transaction(higher.with_max_fee(200)).is_err());
+        assert!(pool.contains(tx.hash()));
+
+        // a transaction received after the subscription is handed to it right away
+        let next = PoolTx::new().with_sender(sender).with_nonce(Nonce::ONE).with_tip(10);
+        pool.add_transaction(next.with_max_fee(100)).unwrap();
+
+        let higher = PoolTx::new().with_sender(sender).with_nonce(Nonce::ONE).with_tip(20);
+        assert!(pool.add_transaction(higher.with_max_fee(200)).is_err());
+    }
+
     #[test]
-    #[ignore = "Txs dependency management not fully implemented yet"]
-    fn dependent_txs_random_insertion() {}
+    fn parked_txs_are_bounded() {
+        let pool = nonce_test_pool();
+
+        for i in 0..MAX_PARKED_TRANSACTIONS {
+            let sender = ContractAddress::from(Felt::from(i));
+            let tx = PoolTx::new().with_sender(sender).with_nonce(Nonce::ONE);
+            pool.add_transaction(tx).unwrap();
+        }
+
+        let sender = ContractAddress::from(Felt::from(MAX_PARKED_TRANSACTIONS));
+        let tx = PoolTx::new().with_sender(sender).with_nonce(Nonce::ONE);
+        assert!(pool.add_transaction(tx).is_err());
+        assert_eq!(pool.parked_size(), MAX_PARKED_TRANSACTIONS);
+    }
 }
@@ -64,6 +64,32 @@ pub enum InvalidTransactionError {
         tx_nonce: Nonce,
     },
 
+    /// Error when a transaction is trying to replace an existing transaction in the pool with the
+    /// same sender and nonce, without paying more in fees than the existing one.
+    #[error(
+        "Replacement transaction underpriced. Transaction of contract at address {address} with \
+         nonce {nonce:#x} already exists in the pool."
+    )]
+    ReplacementUnderpriced {
+        /// The address of the transaction sender.
+        address: ContractAddress,
+        /// The nonce shared by both transactions.
+        nonce: Nonce,
+    },
+
+    /// Error when a transaction is trying to replace a transaction in the pool that has already
+    /// been handed to the block producer.
+    #[error(
+        "Transaction of contract at address {address} with nonce {nonce:#x} is already being \
+         executed and can't be replaced."
+    )]
+    ReplacementNotAllowed {
+        /// The address of the transaction sender.
+        address: ContractAddress,
+        /// The nonce shared by both transactions.
+        nonce: Nonce,
+    },
+
     /// Error when a Declare transaction is trying to declare a class that has already been
     /// declared.
     #[error("Class with hash {class_hash:#x} has already been declared.")]
@@ -36,6 +36,15 @@ pub trait Validator {
     /// transaction is invalid. For that purpose, use the [`ValidationOutcome::Invalid`] enum.
     fn validate(&self, tx: Self::Transaction) -> ValidationResult<Self::Transaction>;
 
+    /// Validate a transaction that replaces a transaction of the pool with the same sender and
+    /// nonce.
+    ///
+    /// Unlike [`Validator::validate`], the nonce of the transaction has already been accounted
+    /// for by the transaction being replaced.
+    fn validate_replacement(&self, tx: Self::Transaction) -> ValidationResult<Self::Transaction> {
+        self.validate(tx)
+    }
+
     /// Validate a batch of transactions.
     fn validate_all(
         &self,
@@ -69,6 +69,16 @@ impl TxValidator {
         this.state = Arc::new(new_state);
     }
 
+    /// Same as [`TxValidator::update`], but also discards the nonces of all the transactions that
+    /// have been validated so far. This method is used when the chain is reverted to an earlier
+    /// block, as the nonces tracked by the validator are no longer valid.
+    pub fn reset(&self, new_state: Box<dyn StateProvider>, block_env: BlockEnv) {
+        let mut this = self.inner.lock();
+        this.block_env = block_env;
+        this.state = Arc::new(new_state);
+        this.pool_nonces.clear();
+    }
+
     // NOTE:
     // If you check the get_nonce method of StatefulValidator in blockifier, under the hood it
     // unwraps the Option to get the state of the TransactionExecutor struct. StatefulValidator
@@ -99,6 +109,20 @@ impl Validator for TxValidator {
     type Transaction = ExecutableTxWithHash;
 
     fn validate(&self, tx: Self::Transaction) -> ValidationResult<Self::Transaction> {
+        self.validate_tx(tx, false)
+    }
+
+    fn validate_replacement(&self, tx: Self::Transaction) -> ValidationResult<Self::Transaction> {
+        self.validate_tx(tx, true)
+    }
+}
+
+impl TxValidator {
+    fn validate_tx(
+        &self,
+        tx: ExecutableTxWithHash,
+        replacement: bool,
+    ) -> ValidationResult<ExecutableTxWithHash> {
         let _permit = self.permit.lock();
         let mut this = self.inner.lock();
 
@@ -131,6 +155,18 @@ impl Validator for TxValidator {
             return Ok(ValidationOutcome::Dependent { current_nonce, tx_nonce, tx });
         }
 
+        // The nonce of a replacement has already been counted in the pool nonce of the sender by
+        // the transaction it replaces, so it's the account nonce in the state that matters.
+        let replaces = replacement && tx_nonce < current_nonce;
+        let account_nonce = if replaces {
+            this.state
+                .nonce(address)
+                .map_err(|e| Error::new(tx.hash, e.into()))?
+                .unwrap_or_default()
+        } else {
+            current_nonce
+        };
+
         // Check if validation of an invoke transaction should be skipped due to deploy_account not
         // being proccessed yet. This feature is used to improve UX for users sending
         // deploy_account + invoke at once.
@@ -138,7 +174,7 @@ impl Validator for TxValidator {
             // we skip validation for invoke tx with nonce 1 and nonce 0 in the state, this
             ExecutableTx::DeployAccount(_) | ExecutableTx::Declare(_) => false,
             // we skip validation for invoke tx with nonce 1 and nonce 0 in the state, this
-            _ => tx.nonce() == Nonce::ONE && current_nonce == Nonce::ZERO,
+            _ => tx.nonce() == Nonce::ONE && account_nonce == Nonce::ZERO,
         };
 
         // prepare a stateful validator and run the account validation logic (ie __validate__
@@ -146,12 +182,12 @@ impl Validator for TxValidator {
         let result = validate(
             this.prepare(),
             tx,
-            !this.execution_flags.account_validation() || skip_validate,
+            !this.execution_flags.account_validation_for(address) || skip_validate,
             !this.execution_flags.fee(),
         );
 
         match result {
-            res @ Ok(ValidationOutcome::Valid { .. }) => {
+            res @ Ok(ValidationOutcome::Valid { .. }) if !replaces => {
                 // update the nonce of the account in the pool only for valid tx
                 let updated_nonce = current_nonce + Felt::ONE;
                 this.pool_nonces.insert(address, updated_nonce);
@@ -240,6 +240,7 @@ pub struct Block {
 
 /// A block with only the transaction hashes.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BlockWithTxHashes {
     pub header: Header,
     pub body: Vec<TxHash>,
@@ -268,7 +269,8 @@ impl Block {
 }
 
 /// A full Starknet block that has been sealed.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SealedBlock {
     /// The block hash.
     pub hash: BlockHash,
@@ -288,7 +290,8 @@ impl SealedBlock {
 /// A sealed block along with its status.
 ///
 /// Block whose commitment has been computed.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SealedBlockWithStatus {
     pub block: SealedBlock,
     /// The block status.
@@ -329,6 +332,18 @@ mod tests {
     use super::*;
     use crate::felt;
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn sealed_block_json_roundtrip() {
+        let block = Block { header: Header { number: 1, ..Default::default() }, body: vec![] }
+            .seal_with_hash_and_status(felt!("0x1337"), FinalityStatus::AcceptedOnL1);
+
+        let json = serde_json::to_string(&block).unwrap();
+        let deserialized: SealedBlockWithStatus = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized, block);
+    }
+
     #[test]
     fn header_concat_counts() {
         let expected = felt!("0x6400000000000000c8000000000000012c0000000000000000");
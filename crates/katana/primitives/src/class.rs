@@ -1,3 +1,4 @@
+use std::fmt;
 use std::str::FromStr;
 
 use katana_cairo::lang::starknet_classes::abi;
@@ -28,6 +29,81 @@ pub type CasmContractClass =
 pub enum ContractClassCompilationError {
     #[error(transparent)]
     SierraCompilation(#[from] StarknetSierraCompilationError),
+
+    #[error("invalid sierra program: missing or malformed version")]
+    InvalidSierraVersion,
+
+    #[error(
+        "unsupported sierra version {version}: supported versions are \
+         {MIN_SUPPORTED_SIERRA_VERSION} to {MAX_SUPPORTED_SIERRA_VERSION}"
+    )]
+    UnsupportedSierraVersion { version: SierraVersion },
+}
+
+/// The oldest Sierra version that can be compiled to CASM.
+pub const MIN_SUPPORTED_SIERRA_VERSION: SierraVersion = SierraVersion::new(1, 0, 0);
+
+/// The newest Sierra version that can be compiled to CASM, ie the version emitted by the Cairo
+/// compiler that Katana is built with. Only the major and minor versions are taken into account
+/// when checking whether a version is supported.
+pub const MAX_SUPPORTED_SIERRA_VERSION: SierraVersion = SierraVersion::new(1, 6, 0);
+
+/// The version of a Sierra program.
+///
+/// The version is encoded in the first three felts of the serialized program, followed by the
+/// version of the compiler that produced it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SierraVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl SierraVersion {
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// Extracts the Sierra version of the given class from its program.
+    pub fn from_class(class: &SierraContractClass) -> Result<Self, ContractClassCompilationError> {
+        let mut parts = class.sierra_program.iter().map(|felt| u64::try_from(&felt.value).ok());
+
+        let mut next =
+            || parts.next().flatten().ok_or(ContractClassCompilationError::InvalidSierraVersion);
+        let (major, minor, patch) = (next()?, next()?, next()?);
+
+        Ok(Self::new(major, minor, patch))
+    }
+
+    /// Returns `true` if programs of this version can be compiled to CASM.
+    pub fn is_supported(&self) -> bool {
+        let version = (self.major, self.minor);
+        let min = (MIN_SUPPORTED_SIERRA_VERSION.major, MIN_SUPPORTED_SIERRA_VERSION.minor);
+        let max = (MAX_SUPPORTED_SIERRA_VERSION.major, MAX_SUPPORTED_SIERRA_VERSION.minor);
+        min <= version && version <= max
+    }
+}
+
+impl fmt::Display for SierraVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Compiles the given Sierra class to CASM.
+///
+/// Returns [`ContractClassCompilationError::UnsupportedSierraVersion`] if the class was emitted by
+/// a Sierra version that the bundled compiler can't handle, instead of failing somewhere in the
+/// middle of the compilation.
+pub fn compile_sierra_class(
+    class: SierraContractClass,
+) -> Result<CasmContractClass, ContractClassCompilationError> {
+    let version = SierraVersion::from_class(&class)?;
+    if !version.is_supported() {
+        return Err(ContractClassCompilationError::UnsupportedSierraVersion { version });
+    }
+
+    Ok(CasmContractClass::from_contract_class(class, true, usize::MAX)?)
 }
 
 // NOTE:
@@ -56,7 +132,7 @@ impl ContractClass {
         match self {
             Self::Legacy(class) => Ok(CompiledClass::Legacy(class)),
             Self::Class(class) => {
-                let casm = CasmContractClass::from_contract_class(class, true, usize::MAX)?;
+                let casm = compile_sierra_class(class)?;
                 let casm = CompiledClass::Class(casm);
                 Ok(casm)
             }
@@ -153,6 +229,12 @@ impl CompiledClass {
 pub enum ComputeClassHashError {
     #[error(transparent)]
     AbiConversion(#[from] serde_json_pythonic::Error),
+
+    #[error(transparent)]
+    LegacyClassConversion(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Legacy(#[from] starknet::core::types::contract::ComputeClassHashError),
 }
 
 // Taken from starknet-rs
@@ -201,9 +283,9 @@ fn entrypoints_hash(entrypoints: &[ContractEntryPoint]) -> Felt {
 fn compute_legacy_class_hash(class: &LegacyContractClass) -> Result<Felt, ComputeClassHashError> {
     pub use starknet::core::types::contract::legacy::LegacyContractClass as StarknetRsLegacyContractClass;
 
-    let value = serde_json::to_value(class).unwrap();
-    let class = serde_json::from_value::<StarknetRsLegacyContractClass>(value).unwrap();
-    let hash = class.class_hash().unwrap();
+    let value = serde_json::to_value(class)?;
+    let class = serde_json::from_value::<StarknetRsLegacyContractClass>(value)?;
+    let hash = class.class_hash()?;
 
     Ok(hash)
 }
@@ -214,7 +296,10 @@ mod tests {
     use starknet::core::types::contract::legacy::LegacyContractClass as StarknetRsLegacyContractClass;
     use starknet::core::types::contract::SierraClass as StarknetRsSierraContractClass;
 
-    use super::{ContractClass, LegacyContractClass, SierraContractClass};
+    use super::{
+        ContractClass, ContractClassCompilationError, LegacyContractClass, SierraContractClass,
+        SierraVersion,
+    };
 
     #[test]
     fn compute_class_hash() {
@@ -245,4 +330,27 @@ mod tests {
 
         assert_eq!(actual_hash, expected_hash);
     }
+
+    #[test]
+    fn sierra_version() {
+        let artifact = include_str!("../../contracts/build/default_account.json");
+        let mut class = serde_json::from_str::<SierraContractClass>(artifact).unwrap();
+
+        let version = SierraVersion::from_class(&class).unwrap();
+        assert!(version.is_supported());
+        assert!(ContractClass::Class(class.clone()).compile().is_ok());
+
+        // a version emitted by a newer compiler than the bundled one
+        class.sierra_program[1].value = 99u64.into();
+        let version = SierraVersion::from_class(&class).unwrap();
+        assert_eq!(version.minor, 99);
+        assert!(!version.is_supported());
+
+        let err = ContractClass::Class(class.clone()).compile().unwrap_err();
+        assert!(matches!(err, ContractClassCompilationError::UnsupportedSierraVersion { .. }));
+
+        class.sierra_program.truncate(2);
+        let err = SierraVersion::from_class(&class).unwrap_err();
+        assert!(matches!(err, ContractClassCompilationError::InvalidSierraVersion));
+    }
 }
@@ -3,7 +3,6 @@ use std::io::{self, Read, Write};
 use std::mem;
 
 use anyhow::{Context, Result};
-use katana_cairo::lang::starknet_classes::casm_contract_class::CasmContractClass;
 use katana_cairo::starknet_api::deprecated_contract_class::{
     ContractClassAbiEntry, EntryPoint, EntryPointType, TypedParameter,
 };
@@ -25,7 +24,7 @@ use starknet::core::types::{
     LegacyStructAbiType, LegacyStructMember, LegacyTypedParameter,
 };
 
-use crate::class::{ClassHash, CompiledClassHash, LegacyContractClass};
+use crate::class::{compile_sierra_class, ClassHash, CompiledClassHash, LegacyContractClass};
 use crate::Felt;
 
 /// Converts the legacy inner compiled class type [DeprecatedCompiledClass] into its RPC equivalent
@@ -143,7 +142,7 @@ pub fn flattened_sierra_to_compiled_class(
 
     let class = rpc_to_cairo_contract_class(contract_class)?;
 
-    let casm = CasmContractClass::from_contract_class(class, true, usize::MAX)?;
+    let casm = compile_sierra_class(class)?;
     let compiled_hash = casm.compiled_class_hash();
 
     let class = crate::class::CompiledClass::Class(casm);
@@ -155,7 +154,7 @@ pub fn compiled_class_hash_from_flattened_sierra_class(
     contract_class: &FlattenedSierraClass,
 ) -> Result<Felt> {
     let contract_class = rpc_to_cairo_contract_class(contract_class)?;
-    let casm = CasmContractClass::from_contract_class(contract_class, true, usize::MAX)?;
+    let casm = compile_sierra_class(contract_class)?;
     let compiled_class: CompiledClass = serde_json::from_str(&serde_json::to_string(&casm)?)?;
     Ok(compiled_class.class_hash()?)
 }
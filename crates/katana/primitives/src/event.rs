@@ -1,6 +1,8 @@
 use core::fmt;
 use std::num::ParseIntError;
 
+use crate::contract::ContractAddress;
+use crate::receipt::Event;
 use crate::Felt;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -12,6 +14,45 @@ pub struct OrderedEvent {
     pub data: Vec<Felt>,
 }
 
+/// An object to specify how events should be filtered.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EventFilter {
+    /// The contract address to filter by.
+    ///
+    /// If `None`, all events are considered. If `Some`, only events emitted by the specified
+    /// contract are considered.
+    pub address: Option<ContractAddress>,
+    /// The keys to filter by.
+    ///
+    /// Per key (by position), designate the possible values to be matched for events to be
+    /// returned. An empty array designates 'any' value.
+    pub keys: Option<Vec<Vec<Felt>>>,
+}
+
+impl EventFilter {
+    /// Returns `true` if the `event` matches the filter.
+    pub fn matches(&self, event: &Event) -> bool {
+        // Check if the event matches the address filter
+        if !self.address.map_or(true, |addr| addr == event.from_address) {
+            return false;
+        }
+
+        match &self.keys {
+            None => true,
+            // Lets say we want to filter events which are either named `Event1` or `Event2` and
+            // custom key `0x1` or `0x2`. Filter: [[sn_keccak("Event1"), sn_keccak("Event2")],
+            // ["0x1", "0x2"]]
+            //
+            // The event must have at least as many keys as the filter, and each of its keys must
+            // either be one of the requested values for that position, or the position must
+            // accept any value (ie an empty array).
+            Some(filters) => filters.iter().enumerate().all(|(i, keys)| {
+                event.keys.len() > i && (keys.is_empty() || keys.contains(&event.keys[i]))
+            }),
+        }
+    }
+}
+
 /// Represents a continuation token for implementing paging in event queries.
 ///
 /// This struct stores the necessary information to resume fetching events
@@ -20,6 +61,10 @@ pub struct OrderedEvent {
 ///
 /// There JSON-RPC specification does not specify the format of the continuation token,
 /// so how the node should handle it is implementation specific.
+///
+/// The token points to the next event to be examined by its position in the chain, regardless of
+/// the filter that is being used. As blocks are never modified once they're stored, a token stays
+/// valid for as long as the block it points to exists, including across node restarts.
 #[derive(PartialEq, Eq, Debug, Clone, Default)]
 pub struct ContinuationToken {
     /// The block number to continue from.
@@ -167,6 +212,31 @@ mod test {
         );
     }
 
+    #[test]
+    fn filter_matches_events() {
+        let event = Event {
+            from_address: ContractAddress::from(Felt::ONE),
+            keys: vec![Felt::from(10u8), Felt::from(20u8)],
+            data: vec![],
+        };
+
+        let filter = |address: Option<u8>, keys: Option<Vec<Vec<u8>>>| EventFilter {
+            address: address.map(|a| Felt::from(a).into()),
+            keys: keys
+                .map(|k| k.into_iter().map(|k| k.into_iter().map(Felt::from).collect()).collect()),
+        };
+
+        assert!(filter(None, None).matches(&event));
+        assert!(filter(Some(1), None).matches(&event));
+        assert!(!filter(Some(2), None).matches(&event));
+
+        assert!(filter(None, Some(vec![vec![10]])).matches(&event));
+        assert!(filter(None, Some(vec![vec![], vec![20, 30]])).matches(&event));
+        assert!(!filter(None, Some(vec![vec![20]])).matches(&event));
+        // the event doesn't have a third key
+        assert!(!filter(None, Some(vec![vec![], vec![], vec![]])).matches(&event));
+    }
+
     #[test]
     fn parse_forked_token_works() {
         let forked_token = "FK_test_token";
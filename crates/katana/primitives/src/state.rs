@@ -46,6 +46,30 @@ impl StateUpdates {
 
         len
     }
+
+    /// Merges the state updates that happened after `self` into `self`.
+    pub fn merge(&mut self, other: StateUpdates) {
+        self.nonce_updates.extend(other.nonce_updates);
+        self.declared_classes.extend(other.declared_classes);
+        self.deprecated_declared_classes.extend(other.deprecated_declared_classes);
+
+        for (address, entries) in other.storage_updates {
+            self.storage_updates.entry(address).or_default().extend(entries);
+        }
+
+        self.deployed_contracts.extend(other.deployed_contracts);
+
+        // replacing the class of a contract that is deployed in the same updates is still a
+        // deployment, only with a different class.
+        for (address, class_hash) in other.replaced_classes {
+            match self.deployed_contracts.get_mut(&address) {
+                Some(deployed) => *deployed = class_hash,
+                None => {
+                    self.replaced_classes.insert(address, class_hash);
+                }
+            }
+        }
+    }
 }
 
 /// State update with declared classes artifacts.
@@ -103,3 +127,43 @@ pub fn compute_state_diff_hash(states: StateUpdates) -> Felt {
 
     hash::Poseidon::hash_array(&elements)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{address, felt};
+
+    #[test]
+    fn merge_state_updates() {
+        let mut updates = StateUpdates {
+            nonce_updates: BTreeMap::from([(address!("1"), felt!("1"))]),
+            storage_updates: BTreeMap::from([(
+                address!("1"),
+                BTreeMap::from([(felt!("1"), felt!("1")), (felt!("2"), felt!("2"))]),
+            )]),
+            deployed_contracts: BTreeMap::from([(address!("1"), felt!("0xa"))]),
+            ..Default::default()
+        };
+
+        updates.merge(StateUpdates {
+            nonce_updates: BTreeMap::from([(address!("1"), felt!("2"))]),
+            storage_updates: BTreeMap::from([(
+                address!("1"),
+                BTreeMap::from([(felt!("2"), felt!("3"))]),
+            )]),
+            replaced_classes: BTreeMap::from([
+                (address!("1"), felt!("0xb")),
+                (address!("2"), felt!("0xc")),
+            ]),
+            ..Default::default()
+        });
+
+        assert_eq!(updates.nonce_updates, BTreeMap::from([(address!("1"), felt!("2"))]));
+        assert_eq!(
+            updates.storage_updates[&address!("1")],
+            BTreeMap::from([(felt!("1"), felt!("1")), (felt!("2"), felt!("3"))])
+        );
+        assert_eq!(updates.deployed_contracts, BTreeMap::from([(address!("1"), felt!("0xb"))]));
+        assert_eq!(updates.replaced_classes, BTreeMap::from([(address!("2"), felt!("0xc"))]));
+    }
+}
@@ -1,9 +1,9 @@
-use std::collections::{hash_map, HashMap, HashSet};
+use std::collections::{hash_map, BTreeMap, HashMap, HashSet};
 
 pub use katana_cairo::cairo_vm::types::builtin_name::BuiltinName;
 
-use crate::class::ClassHash;
-use crate::contract::ContractAddress;
+use crate::class::{ClassHash, CompiledClassHash};
+use crate::contract::{ContractAddress, GenericContractInfo, StorageKey, StorageValue};
 use crate::event::OrderedEvent;
 use crate::message::OrderedL2ToL1Message;
 use crate::transaction::TxType;
@@ -39,6 +39,26 @@ pub struct TxExecInfo {
     pub r#type: TxType,
 }
 
+impl TxExecInfo {
+    /// Returns all the calls of the transaction, including the inner calls, in depth-first order.
+    pub fn calls(&self) -> Vec<&CallInfo> {
+        let mut calls = Vec::new();
+        let mut stack =
+            [&self.validate_call_info, &self.execute_call_info, &self.fee_transfer_call_info]
+                .into_iter()
+                .rev()
+                .flatten()
+                .collect::<Vec<_>>();
+
+        while let Some(call) = stack.pop() {
+            calls.push(call);
+            stack.extend(call.inner_calls.iter().rev());
+        }
+
+        calls
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -124,6 +144,25 @@ pub struct CallInfo {
     pub failed: bool,
 }
 
+/// The state accessed by a block, required by SNOS to prove the block on top of its parent state.
+///
+/// It's recorded by the node when the block is produced in provable mode, so that the proving
+/// pipeline doesn't have to re-execute the block to find out which state it depends on. Running
+/// SNOS itself, and thus producing the block's Cairo PIE, is still left to the prover.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockExecutionArtifacts {
+    /// The storage accessed by the block, with the values before the block.
+    pub initial_storage: BTreeMap<ContractAddress, BTreeMap<StorageKey, StorageValue>>,
+    /// The contracts accessed by the block, with their nonce and class before the block.
+    ///
+    /// The contracts deployed by the block are not included.
+    pub initial_contracts: BTreeMap<ContractAddress, GenericContractInfo>,
+    /// The classes executed by the block, with their compiled class hash.
+    pub executed_classes: BTreeMap<ClassHash, CompiledClassHash>,
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -247,4 +286,23 @@ mod tests {
         assert_eq!(counters.pedersen(), Some(2));
         assert_eq!(counters.ecdsa(), None);
     }
+
+    #[test]
+    fn test_tx_exec_info_calls_are_depth_first() {
+        let call = |selector: u64, inner_calls: Vec<CallInfo>| CallInfo {
+            entry_point_selector: Felt::from(selector),
+            inner_calls,
+            ..Default::default()
+        };
+
+        let info = TxExecInfo {
+            validate_call_info: Some(call(0, vec![])),
+            execute_call_info: Some(call(1, vec![call(2, vec![call(3, vec![])]), call(4, vec![])])),
+            fee_transfer_call_info: Some(call(5, vec![])),
+            ..Default::default()
+        };
+
+        let selectors = info.calls().iter().map(|c| c.entry_point_selector).collect::<Vec<_>>();
+        assert_eq!(selectors, (0..6u64).map(Felt::from).collect::<Vec<_>>());
+    }
 }
@@ -1,7 +1,13 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
-use katana_primitives::Felt;
+use katana_primitives::block::BlockNumber;
+use katana_primitives::class::ClassHash;
+use katana_primitives::contract::{ContractAddress, Nonce, StorageKey, StorageValue};
+use katana_primitives::U256;
 use katana_rpc_types::account::Account;
+use katana_rpc_types::receipt::TxReceiptWithBlockInfo;
+use katana_rpc_types::transaction::BroadcastedTx;
+use katana_rpc_types::FeeToken;
 
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "dev"))]
 #[cfg_attr(feature = "client", rpc(client, server, namespace = "dev"))]
@@ -9,6 +15,18 @@ pub trait DevApi {
     #[method(name = "generateBlock")]
     async fn generate_block(&self) -> RpcResult<()>;
 
+    /// Seals the pending block, including all the transactions that have been executed so far,
+    /// and returns the number of the newly mined block.
+    #[method(name = "mine")]
+    async fn mine(&self) -> RpcResult<BlockNumber>;
+
+    /// Reverts the chain to the block `block_number`, discarding all the blocks after it along
+    /// with their state changes, as well as the transactions in the pending block.
+    ///
+    /// This can be used to simulate chain reorgs.
+    #[method(name = "revertToBlock")]
+    async fn revert_to_block(&self, block_number: BlockNumber) -> RpcResult<()>;
+
     #[method(name = "nextBlockTimestamp")]
     async fn next_block_timestamp(&self) -> RpcResult<()>;
 
@@ -18,9 +36,74 @@ pub trait DevApi {
     #[method(name = "increaseNextBlockTimestamp")]
     async fn increase_next_block_timestamp(&self, timestamp: u64) -> RpcResult<()>;
 
+    /// Moves the timestamp of the next block forward by `seconds`.
+    ///
+    /// Alias of `dev_increaseNextBlockTimestamp`.
+    #[method(name = "increaseTime")]
+    async fn increase_time(&self, seconds: u64) -> RpcResult<()>;
+
+    /// Sets the value of the storage slot `key` of the contract at `contract_address`, without
+    /// executing any transaction.
     #[method(name = "setStorageAt")]
-    async fn set_storage_at(&self, contract_address: Felt, key: Felt, value: Felt)
-    -> RpcResult<()>;
+    async fn set_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+        value: StorageValue,
+    ) -> RpcResult<()>;
+
+    /// Sets the nonce of the contract at `contract_address`, without executing any transaction.
+    #[method(name = "setNonce")]
+    async fn set_nonce(&self, contract_address: ContractAddress, nonce: Nonce) -> RpcResult<()>;
+
+    /// Replaces the class of the contract at `contract_address` with `class_hash`, without
+    /// executing any transaction. The class must already be declared.
+    #[method(name = "setClassHashAt")]
+    async fn set_class_hash_at(
+        &self,
+        contract_address: ContractAddress,
+        class_hash: ClassHash,
+    ) -> RpcResult<()>;
+
+    /// Skips the account validation logic for all transactions sent by `address`, allowing to
+    /// submit transactions on its behalf without its signature.
+    #[method(name = "impersonateAccount")]
+    async fn impersonate_account(&self, address: ContractAddress) -> RpcResult<()>;
+
+    /// Stops impersonating an account previously impersonated with `dev_impersonateAccount`.
+    #[method(name = "stopImpersonatingAccount")]
+    async fn stop_impersonating_account(&self, address: ContractAddress) -> RpcResult<()>;
+
+    /// Enables or disables fee charging for all the transactions executed from now on. Overrides
+    /// the `--dev.no-fee` flag of the node.
+    #[method(name = "setFeeEnabled")]
+    async fn set_fee_enabled(&self, enabled: bool) -> RpcResult<()>;
+
+    /// Enables or disables the account validation logic for all the transactions executed from now
+    /// on. Overrides the `--dev.no-account-validation` flag of the node.
+    #[method(name = "setAccountValidationEnabled")]
+    async fn set_account_validation_enabled(&self, enabled: bool) -> RpcResult<()>;
+
+    /// Mints `amount` of the fee `token` to `address`, by directly updating the storage of the
+    /// token contract. The ETH fee token is used if `token` is not specified.
+    #[method(name = "mint")]
+    async fn mint(
+        &self,
+        address: ContractAddress,
+        amount: U256,
+        token: Option<FeeToken>,
+    ) -> RpcResult<()>;
+
+    /// Executes `transactions` in order and mines them in a block of their own, without
+    /// interleaving them with transactions from other clients. The transactions are committed
+    /// only if all of them are successfully executed, otherwise none of them are.
+    ///
+    /// On success, returns the receipts of all the transactions.
+    #[method(name = "executeBatch")]
+    async fn execute_batch(
+        &self,
+        transactions: Vec<BroadcastedTx>,
+    ) -> RpcResult<Vec<TxReceiptWithBlockInfo>>;
 
     #[method(name = "predeployedAccounts")]
     async fn predeployed_accounts(&self) -> RpcResult<Vec<Account>>;
@@ -0,0 +1,16 @@
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use katana_primitives::block::BlockIdOrTag;
+use katana_rpc_types::report::BlockExecutionReport;
+
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "katana"))]
+#[cfg_attr(feature = "client", rpc(client, server, namespace = "katana"))]
+pub trait KatanaApi {
+    /// Returns the resources - Cairo steps, builtins, syscalls and L1 gas - used by each
+    /// transaction of the block `block_id`, along with their sum.
+    #[method(name = "getBlockExecutionReport")]
+    async fn get_block_execution_report(
+        &self,
+        block_id: BlockIdOrTag,
+    ) -> RpcResult<BlockExecutionReport>;
+}
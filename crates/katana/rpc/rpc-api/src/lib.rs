@@ -1,4 +1,6 @@
 pub mod dev;
+pub mod katana;
+pub mod replication;
 pub mod saya;
 pub mod starknet;
 pub mod torii;
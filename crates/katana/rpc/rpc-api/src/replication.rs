@@ -0,0 +1,20 @@
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use katana_primitives::block::BlockNumber;
+use katana_rpc_types::replication::ReplicatedBlock;
+
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "replication"))]
+#[cfg_attr(feature = "client", rpc(client, server, namespace = "replication"))]
+pub trait ReplicationApi {
+    /// Returns the number of the latest block.
+    #[method(name = "blockNumber")]
+    async fn block_number(&self) -> RpcResult<BlockNumber>;
+
+    /// Returns up to `count` consecutive blocks starting from block `from`, along with all the data
+    /// needed to replicate them.
+    ///
+    /// Fewer blocks are returned if the chain doesn't have enough blocks, or if `count` is larger
+    /// than `MAX_REPLICATED_BLOCKS_PER_REQUEST`.
+    #[method(name = "getBlocks")]
+    async fn get_blocks(&self, from: BlockNumber, count: u64) -> RpcResult<Vec<ReplicatedBlock>>;
+}
@@ -1,6 +1,7 @@
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use katana_primitives::block::BlockIdOrTag;
+use katana_primitives::trace::BlockExecutionArtifacts;
 use katana_rpc_types::trace::TxExecutionInfo;
 
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "saya"))]
@@ -12,4 +13,12 @@ pub trait SayaApi {
         &self,
         block_id: BlockIdOrTag,
     ) -> RpcResult<Vec<TxExecutionInfo>>;
+
+    /// Retrieves the SNOS inputs of a block, ie the state it accessed, recorded when the node runs
+    /// in provable mode.
+    #[method(name = "getBlockExecutionArtifacts")]
+    async fn block_execution_artifacts(
+        &self,
+        block_id: BlockIdOrTag,
+    ) -> RpcResult<BlockExecutionArtifacts>;
 }
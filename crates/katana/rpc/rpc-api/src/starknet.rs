@@ -7,26 +7,24 @@ use katana_primitives::class::ClassHash;
 use katana_primitives::transaction::TxHash;
 use katana_primitives::{ContractAddress, Felt};
 use katana_rpc_types::block::{
-    BlockHashAndNumber, BlockTxCount, MaybePendingBlockWithReceipts, MaybePendingBlockWithTxHashes,
-    MaybePendingBlockWithTxs,
+    BlockHashAndNumber, BlockHeader, BlockTxCount, MaybePendingBlockWithReceipts,
+    MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs,
 };
 use katana_rpc_types::class::RpcContractClass;
-use katana_rpc_types::event::{EventFilterWithPage, EventsPage};
+use katana_rpc_types::event::{EmittedEvent, EventFilterWithPage, EventsPage};
 use katana_rpc_types::message::MsgFromL1;
 use katana_rpc_types::receipt::TxReceiptWithBlockInfo;
 use katana_rpc_types::state_update::MaybePendingStateUpdate;
 use katana_rpc_types::transaction::{
     BroadcastedDeclareTx, BroadcastedDeployAccountTx, BroadcastedInvokeTx, BroadcastedTx,
-    DeclareTxResult, DeployAccountTxResult, InvokeTxResult, Tx,
+    DeclareTxResult, DeployAccountTxResult, InvokeTxResult, Tx, TxStatus,
 };
 use katana_rpc_types::trie::{ContractStorageKeys, GetStorageProofResponse};
 use katana_rpc_types::{
     FeeEstimate, FeltAsHex, FunctionCall, SimulationFlag, SimulationFlagForEstimateFee,
     SyncingStatus,
 };
-use starknet::core::types::{
-    SimulatedTransaction, TransactionStatus, TransactionTrace, TransactionTraceWithHash,
-};
+use starknet::core::types::{SimulatedTransaction, TransactionTrace, TransactionTraceWithHash};
 
 /// The currently supported version of the Starknet JSON-RPC specification.
 pub const RPC_SPEC_VERSION: &str = "0.7.1";
@@ -78,10 +76,7 @@ pub trait StarknetApi {
     /// Gets the transaction status (possibly reflecting that the tx is still in the mempool, or
     /// dropped from it).
     #[method(name = "getTransactionStatus")]
-    async fn get_transaction_status(
-        &self,
-        transaction_hash: TxHash,
-    ) -> RpcResult<TransactionStatus>;
+    async fn get_transaction_status(&self, transaction_hash: TxHash) -> RpcResult<TxStatus>;
 
     /// Get the details and status of a submitted transaction.
     #[method(name = "getTransactionByHash")]
@@ -249,3 +244,35 @@ pub trait StarknetTraceApi {
         block_id: BlockIdOrTag,
     ) -> RpcResult<Vec<TransactionTraceWithHash>>;
 }
+
+/// Subscription API.
+///
+/// Only available over WebSocket connections.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "starknet"))]
+#[cfg_attr(feature = "client", rpc(client, server, namespace = "starknet"))]
+pub trait StarknetSubscriptionApi {
+    /// Subscribes to the headers of newly mined blocks.
+    #[subscription(
+        name = "subscribeNewHeads",
+        unsubscribe = "unsubscribeNewHeads",
+        item = BlockHeader
+    )]
+    fn subscribe_new_heads(&self);
+
+    /// Subscribes to the events emitted in newly mined blocks, optionally filtered by the
+    /// emitting contract and the event keys (using the same semantics as `starknet_getEvents`).
+    #[subscription(
+        name = "subscribeEvents",
+        unsubscribe = "unsubscribeEvents",
+        item = EmittedEvent
+    )]
+    fn subscribe_events(&self, from_address: Option<ContractAddress>, keys: Option<Vec<Vec<Felt>>>);
+
+    /// Subscribes to the hashes of the transactions that are added to the pool.
+    #[subscription(
+        name = "subscribePendingTransactions",
+        unsubscribe = "unsubscribePendingTransactions",
+        item = TxHash
+    )]
+    fn subscribe_pending_transactions(&self);
+}
@@ -3,6 +3,7 @@ use katana_primitives::block::{
 };
 use katana_primitives::receipt::Receipt;
 use katana_primitives::transaction::{TxHash, TxWithHash};
+use katana_primitives::Felt;
 use serde::{Deserialize, Serialize};
 use starknet::core::types::{
     BlockStatus, L1DataAvailabilityMode, ResourcePrice, TransactionWithReceipt,
@@ -337,3 +338,50 @@ impl From<starknet::core::types::MaybePendingBlockWithReceipts> for MaybePending
         }
     }
 }
+
+/// The header of a block, as sent to `starknet_subscribeNewHeads` subscribers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockHeader {
+    pub block_hash: BlockHash,
+    pub parent_hash: BlockHash,
+    pub block_number: BlockNumber,
+    pub new_root: Felt,
+    pub timestamp: u64,
+    pub sequencer_address: Felt,
+    pub l1_gas_price: ResourcePrice,
+    pub l1_data_gas_price: ResourcePrice,
+    pub l1_da_mode: L1DataAvailabilityMode,
+    pub starknet_version: String,
+}
+
+impl BlockHeader {
+    pub fn new(block_hash: BlockHash, header: Header) -> Self {
+        let l1_gas_price = ResourcePrice {
+            price_in_wei: header.l1_gas_prices.eth.into(),
+            price_in_fri: header.l1_gas_prices.strk.into(),
+        };
+
+        let l1_data_gas_price = ResourcePrice {
+            price_in_wei: header.l1_data_gas_prices.eth.into(),
+            price_in_fri: header.l1_data_gas_prices.strk.into(),
+        };
+
+        Self {
+            block_hash,
+            l1_gas_price,
+            l1_data_gas_price,
+            new_root: header.state_root,
+            timestamp: header.timestamp,
+            block_number: header.number,
+            parent_hash: header.parent_hash,
+            starknet_version: header.protocol_version.to_string(),
+            sequencer_address: header.sequencer_address.into(),
+            l1_da_mode: match header.l1_da_mode {
+                katana_primitives::da::L1DataAvailabilityMode::Blob => L1DataAvailabilityMode::Blob,
+                katana_primitives::da::L1DataAvailabilityMode::Calldata => {
+                    L1DataAvailabilityMode::Calldata
+                }
+            },
+        }
+    }
+}
@@ -7,6 +7,18 @@ use jsonrpsee::types::ErrorObject;
 pub enum DevApiError {
     #[error("Wait for pending transactions.")]
     PendingTransactions,
+    #[error("Unable to mine while a block is being mined.")]
+    MiningInProgress,
+    #[error("Failed to mine block.")]
+    FailedToMineBlock,
+    #[error("Failed to update storage.")]
+    FailedToUpdateStorage,
+    #[error("Minting the requested amount would overflow the balance.")]
+    BalanceOverflow,
+    #[error("Failed to revert to the requested block.")]
+    FailedToRevertBlock,
+    #[error("Class hash is not declared.")]
+    ClassNotDeclared,
 }
 
 impl From<DevApiError> for Error {
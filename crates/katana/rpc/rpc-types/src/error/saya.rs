@@ -12,6 +12,8 @@ pub enum SayaApiError {
     BlockNotFound,
     #[error("Transaction not found")]
     TransactionNotFound,
+    #[error("SNOS inputs not found, the block was not produced in provable mode")]
+    ArtifactsNotFound,
     #[error("An unexpected error occured: {reason}")]
     UnexpectedError { reason: String },
 }
@@ -22,6 +24,7 @@ impl SayaApiError {
             SayaApiError::TransactionOutOfBounds => 1,
             SayaApiError::BlockNotFound => 24,
             SayaApiError::TransactionNotFound => 25,
+            SayaApiError::ArtifactsNotFound => 26,
             SayaApiError::UnexpectedError { .. } => 63,
         }
     }
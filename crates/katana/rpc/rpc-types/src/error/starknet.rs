@@ -4,6 +4,7 @@ use jsonrpsee::types::ErrorObject;
 use katana_pool::validation::error::InvalidTransactionError;
 use katana_pool::PoolError;
 use katana_primitives::block::BlockNumber;
+use katana_primitives::class::ContractClassCompilationError;
 use katana_primitives::event::ContinuationTokenError;
 use katana_provider::error::ProviderError;
 use serde::Serialize;
@@ -179,6 +180,22 @@ impl From<ContinuationTokenError> for StarknetApiError {
     }
 }
 
+impl From<ContractClassCompilationError> for StarknetApiError {
+    fn from(value: ContractClassCompilationError) -> Self {
+        match value {
+            ContractClassCompilationError::UnsupportedSierraVersion { .. } => {
+                StarknetApiError::UnsupportedContractClassVersion
+            }
+            ContractClassCompilationError::InvalidSierraVersion => {
+                StarknetApiError::InvalidContractClass
+            }
+            ContractClassCompilationError::SierraCompilation(_) => {
+                StarknetApiError::CompilationFailed
+            }
+        }
+    }
+}
+
 impl From<anyhow::Error> for StarknetApiError {
     fn from(value: anyhow::Error) -> Self {
         StarknetApiError::UnexpectedError { reason: value.to_string() }
@@ -189,6 +206,7 @@ impl From<PoolError> for StarknetApiError {
     fn from(error: PoolError) -> Self {
         match error {
             PoolError::InvalidTransaction(err) => err.into(),
+            PoolError::ParkedPoolFull => StarknetApiError::FailedToReceiveTxn,
             PoolError::Internal(err) => {
                 StarknetApiError::UnexpectedError { reason: err.to_string() }
             }
@@ -203,7 +221,9 @@ impl From<Box<InvalidTransactionError>> for StarknetApiError {
             InvalidTransactionError::ClassAlreadyDeclared { .. } => Self::ClassAlreadyDeclared,
             InvalidTransactionError::IntrinsicFeeTooLow { .. } => Self::InsufficientMaxFee,
             InvalidTransactionError::NonAccount { .. } => Self::NonAccount,
-            InvalidTransactionError::InvalidNonce { .. } => {
+            InvalidTransactionError::InvalidNonce { .. }
+            | InvalidTransactionError::ReplacementUnderpriced { .. }
+            | InvalidTransactionError::ReplacementNotAllowed { .. } => {
                 Self::InvalidTransactionNonce { reason: error.to_string() }
             }
             InvalidTransactionError::ValidationFailure { error, .. } => {
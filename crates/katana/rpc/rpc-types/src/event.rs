@@ -1,2 +1,3 @@
 pub type EventFilterWithPage = starknet::core::types::EventFilterWithPage;
 pub type EventsPage = starknet::core::types::EventsPage;
+pub type EmittedEvent = starknet::core::types::EmittedEvent;
@@ -10,6 +10,8 @@ pub mod error;
 pub mod event;
 pub mod message;
 pub mod receipt;
+pub mod replication;
+pub mod report;
 pub mod state_update;
 pub mod trace;
 pub mod transaction;
@@ -52,7 +54,28 @@ pub type FeeEstimate = starknet::core::types::FeeEstimate;
 
 // pub type ContractClass = starknet::core::types::ContractClass;
 
-pub type SimulationFlagForEstimateFee = starknet::core::types::SimulationFlagForEstimateFee;
+/// Flags that indicate how to estimate the fee of a transaction.
+///
+/// This is a superset of [`starknet::core::types::SimulationFlagForEstimateFee`] that additionally
+/// allows skipping the fee charge, so that fees can be estimated for accounts that don't have
+/// enough balance to cover the fee.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SimulationFlagForEstimateFee {
+    #[serde(rename = "SKIP_VALIDATE")]
+    SkipValidate,
+    #[serde(rename = "SKIP_FEE_CHARGE")]
+    SkipFeeCharge,
+}
+
+/// The fee tokens that can be minted with the `dev_mint` method.
+#[derive(Debug, Default, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FeeToken {
+    #[default]
+    #[serde(rename = "ETH")]
+    Eth,
+    #[serde(rename = "STRK")]
+    Strk,
+}
 
 pub type SimulationFlag = starknet::core::types::SimulationFlag;
 
@@ -63,7 +86,7 @@ mod tests {
     use serde_json::json;
     use starknet::macros::felt;
 
-    use super::FeltAsHex;
+    use super::{FeltAsHex, SimulationFlagForEstimateFee};
 
     #[test]
     fn serde_felt() {
@@ -78,4 +101,16 @@ mod tests {
         let actual_ser_value = serde_json::to_value(expected_value).unwrap();
         assert_eq!(value_as_hex, actual_ser_value, "should serialize to hex");
     }
+
+    #[test]
+    fn serde_simulation_flag_for_estimate_fee() {
+        let flags: Vec<SimulationFlagForEstimateFee> =
+            serde_json::from_value(json!(["SKIP_VALIDATE", "SKIP_FEE_CHARGE"])).unwrap();
+
+        let expected = vec![
+            SimulationFlagForEstimateFee::SkipValidate,
+            SimulationFlagForEstimateFee::SkipFeeCharge,
+        ];
+        assert_eq!(flags, expected);
+    }
 }
@@ -0,0 +1,30 @@
+//! Types used by the `replication` API, which lets a follower node replicate the chain of another
+//! node.
+
+use std::collections::BTreeMap;
+
+use katana_primitives::block::{BlockHash, FinalityStatus, Header};
+use katana_primitives::class::{ClassHash, ContractClass};
+use katana_primitives::receipt::Receipt;
+use katana_primitives::state::StateUpdates;
+use katana_primitives::trace::TxExecInfo;
+use katana_primitives::transaction::TxWithHash;
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of blocks that can be requested in a single `replication_getBlocks` call.
+pub const MAX_REPLICATED_BLOCKS_PER_REQUEST: u64 = 100;
+
+/// A block along with everything that is needed to insert it into another node's database without
+/// re-executing it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplicatedBlock {
+    pub hash: BlockHash,
+    pub header: Header,
+    pub status: FinalityStatus,
+    pub body: Vec<TxWithHash>,
+    pub state_updates: StateUpdates,
+    /// The classes declared in the block.
+    pub classes: BTreeMap<ClassHash, ContractClass>,
+    pub receipts: Vec<Receipt>,
+    pub traces: Vec<TxExecInfo>,
+}
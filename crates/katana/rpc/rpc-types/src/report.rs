@@ -0,0 +1,205 @@
+//! Types used by the `katana_getBlockExecutionReport` method, which reports the resources used by
+//! the transactions of a block.
+
+use std::collections::HashMap;
+use std::ops::AddAssign;
+
+use katana_primitives::block::{BlockHash, BlockNumber};
+use katana_primitives::receipt::Receipt;
+use katana_primitives::trace::{BuiltinName, CallInfo, CallType, EntryPointType, TxExecInfo};
+use katana_primitives::transaction::TxHash;
+use serde::{Deserialize, Serialize};
+
+/// The resources used by the execution of a block, broken down per transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockExecutionReport {
+    /// The hash of the block. `None` for the pending block.
+    pub block_hash: Option<BlockHash>,
+    pub block_number: BlockNumber,
+    /// The sum of the resources used by all the transactions of the block.
+    pub total: ExecutionResourcesReport,
+    /// The resources used by each transaction, in the order they are included in the block.
+    pub transactions: Vec<TxExecutionReport>,
+}
+
+impl BlockExecutionReport {
+    pub fn new(
+        block_hash: Option<BlockHash>,
+        block_number: BlockNumber,
+        transactions: Vec<TxExecutionReport>,
+    ) -> Self {
+        let mut total = ExecutionResourcesReport::default();
+        for tx in &transactions {
+            total += &tx.resources;
+        }
+
+        Self { block_hash, block_number, total, transactions }
+    }
+}
+
+/// The resources used by the execution of a single transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxExecutionReport {
+    pub transaction_hash: TxHash,
+    pub reverted: bool,
+    pub resources: ExecutionResourcesReport,
+}
+
+impl TxExecutionReport {
+    pub fn new(transaction_hash: TxHash, receipt: &Receipt, trace: &TxExecInfo) -> Self {
+        let resources = receipt.resources_used();
+
+        let mut syscalls = SyscallsReport::default();
+        let calls =
+            [&trace.validate_call_info, &trace.execute_call_info, &trace.fee_transfer_call_info];
+        for call in calls.into_iter().flatten() {
+            syscalls.count_calls(call);
+        }
+
+        let builtins = (&resources.vm_resources.builtin_instance_counter)
+            .into_iter()
+            .map(|(builtin, count)| (*builtin, *count as u64))
+            .collect();
+
+        Self {
+            transaction_hash,
+            reverted: receipt.is_reverted(),
+            resources: ExecutionResourcesReport {
+                steps: resources.vm_resources.n_steps as u64,
+                reverted_steps: resources.n_reverted_steps as u64,
+                memory_holes: resources.vm_resources.n_memory_holes as u64,
+                builtins,
+                syscalls,
+                l1_gas: resources.total_gas_consumed.l1_gas,
+                l1_data_gas: resources.total_gas_consumed.l1_data_gas,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionResourcesReport {
+    /// The number of Cairo steps.
+    pub steps: u64,
+    /// The number of Cairo steps executed before the transaction was reverted.
+    pub reverted_steps: u64,
+    pub memory_holes: u64,
+    /// The number of instances of each builtin used.
+    pub builtins: HashMap<BuiltinName, u64>,
+    pub syscalls: SyscallsReport,
+    /// The total L1 gas consumed, including the gas used for publishing the state diffs.
+    pub l1_gas: u128,
+    /// The L1 data gas consumed for publishing the state diffs as blobs.
+    pub l1_data_gas: u128,
+}
+
+impl AddAssign<&ExecutionResourcesReport> for ExecutionResourcesReport {
+    fn add_assign(&mut self, other: &ExecutionResourcesReport) {
+        self.steps += other.steps;
+        self.reverted_steps += other.reverted_steps;
+        self.memory_holes += other.memory_holes;
+        for (builtin, count) in &other.builtins {
+            *self.builtins.entry(*builtin).or_default() += count;
+        }
+        self.syscalls += &other.syscalls;
+        self.l1_gas += other.l1_gas;
+        self.l1_data_gas += other.l1_data_gas;
+    }
+}
+
+/// The number of syscalls made, counted from the call traces. Only the syscalls that leave a trace
+/// are reported.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyscallsReport {
+    pub call_contract: u64,
+    pub library_call: u64,
+    pub deploy: u64,
+    pub storage_read: u64,
+    pub emit_event: u64,
+    pub send_message_to_l1: u64,
+}
+
+impl SyscallsReport {
+    /// Counts the syscalls made by `call` and all of its inner calls.
+    fn count_calls(&mut self, call: &CallInfo) {
+        self.storage_read += call.storage_read_values.len() as u64;
+        self.emit_event += call.events.len() as u64;
+        self.send_message_to_l1 += call.l2_to_l1_messages.len() as u64;
+
+        for inner in &call.inner_calls {
+            match (&inner.entry_point_type, &inner.call_type) {
+                (EntryPointType::Constructor, _) => self.deploy += 1,
+                (_, CallType::Delegate) => self.library_call += 1,
+                (_, CallType::Call) => self.call_contract += 1,
+            }
+
+            self.count_calls(inner);
+        }
+    }
+}
+
+impl AddAssign<&SyscallsReport> for SyscallsReport {
+    fn add_assign(&mut self, other: &SyscallsReport) {
+        self.call_contract += other.call_contract;
+        self.library_call += other.library_call;
+        self.deploy += other.deploy;
+        self.storage_read += other.storage_read;
+        self.emit_event += other.emit_event;
+        self.send_message_to_l1 += other.send_message_to_l1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use katana_primitives::event::OrderedEvent;
+    use katana_primitives::fee::{PriceUnit, TxFeeInfo};
+    use katana_primitives::receipt::InvokeTxReceipt;
+    use katana_primitives::trace::TxResources;
+
+    use super::*;
+
+    #[test]
+    fn count_syscalls_and_sum_resources() {
+        let library_call = CallInfo { call_type: CallType::Delegate, ..Default::default() };
+        let deploy =
+            CallInfo { entry_point_type: EntryPointType::Constructor, ..Default::default() };
+        let call_contract = CallInfo {
+            storage_read_values: vec![1u8.into(), 2u8.into()],
+            events: vec![OrderedEvent { order: 0, keys: Vec::new(), data: Vec::new() }],
+            inner_calls: vec![library_call],
+            ..Default::default()
+        };
+
+        let execute = CallInfo { inner_calls: vec![call_contract, deploy], ..Default::default() };
+        let trace = TxExecInfo { execute_call_info: Some(execute), ..Default::default() };
+
+        let mut execution_resources = TxResources::default();
+        execution_resources.vm_resources.n_steps = 100;
+        execution_resources.total_gas_consumed.l1_gas = 10;
+
+        let receipt = Receipt::Invoke(InvokeTxReceipt {
+            fee: TxFeeInfo { gas_consumed: 0, gas_price: 0, overall_fee: 0, unit: PriceUnit::Wei },
+            events: Vec::new(),
+            messages_sent: Vec::new(),
+            revert_error: None,
+            execution_resources,
+        });
+
+        let tx = TxExecutionReport::new(TxHash::ONE, &receipt, &trace);
+        let expected = SyscallsReport {
+            call_contract: 1,
+            library_call: 1,
+            deploy: 1,
+            storage_read: 2,
+            emit_event: 1,
+            send_message_to_l1: 0,
+        };
+        assert_eq!(tx.resources.syscalls, expected);
+        assert_eq!(tx.resources.steps, 100);
+
+        let report = BlockExecutionReport::new(None, 1, vec![tx.clone(), tx]);
+        assert_eq!(report.total.steps, 200);
+        assert_eq!(report.total.l1_gas, 20);
+        assert_eq!(report.total.syscalls.call_contract, 2);
+    }
+}
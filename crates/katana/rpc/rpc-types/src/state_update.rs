@@ -1,7 +1,8 @@
 use katana_primitives::class::ClassHash;
 use serde::{Deserialize, Serialize};
 use starknet::core::types::{
-    ContractStorageDiffItem, DeclaredClassItem, DeployedContractItem, NonceUpdate, StorageEntry,
+    ContractStorageDiffItem, DeclaredClassItem, DeployedContractItem, NonceUpdate,
+    ReplacedClassItem, StorageEntry,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -68,6 +69,15 @@ impl From<katana_primitives::state::StateUpdates> for StateDiff {
             .map(|(addr, class_hash)| DeployedContractItem { address: addr.into(), class_hash })
             .collect();
 
+        let replaced_classes: Vec<ReplacedClassItem> = value
+            .replaced_classes
+            .into_iter()
+            .map(|(addr, class_hash)| ReplacedClassItem {
+                contract_address: addr.into(),
+                class_hash,
+            })
+            .collect();
+
         let storage_diffs: Vec<ContractStorageDiffItem> = value
             .storage_updates
             .into_iter()
@@ -86,7 +96,7 @@ impl From<katana_primitives::state::StateUpdates> for StateDiff {
             declared_classes,
             deployed_contracts,
             deprecated_declared_classes,
-            replaced_classes: Default::default(),
+            replaced_classes,
         })
     }
 }
@@ -5,7 +5,9 @@ use derive_more::Deref;
 use katana_primitives::chain::ChainId;
 use katana_primitives::class::{ClassHash, ContractClass};
 use katana_primitives::contract::ContractAddress;
-use katana_primitives::conversion::rpc::compiled_class_hash_from_flattened_sierra_class;
+use katana_primitives::conversion::rpc::{
+    compiled_class_hash_from_flattened_sierra_class, legacy_rpc_to_class,
+};
 use katana_primitives::da::DataAvailabilityMode;
 use katana_primitives::fee::{ResourceBounds, ResourceBoundsMapping};
 use katana_primitives::transaction::{
@@ -19,10 +21,11 @@ use starknet::core::types::{
     BroadcastedDeclareTransaction, BroadcastedDeployAccountTransaction,
     BroadcastedInvokeTransaction, DeclareTransactionResult, DeployAccountTransactionResult,
     DeployAccountTransactionV1, DeployAccountTransactionV3, InvokeTransactionResult,
+    TransactionExecutionStatus, TransactionStatus,
 };
 use starknet::core::utils::get_contract_address;
 
-use crate::class::{RpcContractClass, RpcLegacyContractClass, RpcSierraContractClass};
+use crate::class::{RpcContractClass, RpcSierraContractClass};
 use crate::receipt::TxReceiptWithBlockInfo;
 
 pub const CHUNK_SIZE_DEFAULT: u64 = 100;
@@ -98,11 +101,9 @@ impl BroadcastedDeclareTx {
     pub fn try_into_tx_with_chain_id(self, chain_id: ChainId) -> Result<DeclareTxWithClass> {
         match self.0 {
             BroadcastedDeclareTransaction::V1(tx) => {
-                let rpc_class = Arc::unwrap_or_clone(tx.contract_class);
-                let rpc_class = RpcLegacyContractClass::try_from(rpc_class).unwrap();
-                let class = ContractClass::try_from(RpcContractClass::Legacy(rpc_class)).unwrap();
-
-                let class_hash = class.class_hash().unwrap();
+                // The class hash is computed from the ABI as it was sent, as the original ABI may
+                // contain entries that are dropped when converting to the inner class type.
+                let (class_hash, class) = legacy_rpc_to_class(&tx.contract_class)?;
 
                 let tx = DeclareTx::V1(DeclareTxV1 {
                     chain_id,
@@ -120,8 +121,8 @@ impl BroadcastedDeclareTx {
                 let class_hash = tx.contract_class.class_hash();
 
                 let rpc_class = Arc::unwrap_or_clone(tx.contract_class);
-                let rpc_class = RpcSierraContractClass::try_from(rpc_class).unwrap();
-                let class = ContractClass::try_from(RpcContractClass::Class(rpc_class)).unwrap();
+                let rpc_class = RpcSierraContractClass::try_from(rpc_class)?;
+                let class = ContractClass::try_from(RpcContractClass::Class(rpc_class))?;
 
                 let tx = DeclareTx::V2(DeclareTxV2 {
                     chain_id,
@@ -140,8 +141,8 @@ impl BroadcastedDeclareTx {
                 let class_hash = tx.contract_class.class_hash();
 
                 let rpc_class = Arc::unwrap_or_clone(tx.contract_class);
-                let rpc_class = RpcSierraContractClass::try_from(rpc_class).unwrap();
-                let class = ContractClass::try_from(RpcContractClass::Class(rpc_class)).unwrap();
+                let rpc_class = RpcSierraContractClass::try_from(rpc_class)?;
+                let class = ContractClass::try_from(RpcContractClass::Class(rpc_class))?;
 
                 let tx = DeclareTx::V3(DeclareTxV3 {
                     chain_id,
@@ -572,6 +573,66 @@ pub struct TransactionsPage {
     pub cursor: TransactionsPageCursor,
 }
 
+/// The finality status of a transaction, including the transactions that haven't been included in
+/// a block yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TxFinalityStatus {
+    Received,
+    Rejected,
+    #[serde(rename = "ACCEPTED_ON_L2")]
+    AcceptedOnL2,
+    #[serde(rename = "ACCEPTED_ON_L1")]
+    AcceptedOnL1,
+}
+
+/// The response of `starknet_getTransactionStatus`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxStatus {
+    pub finality_status: TxFinalityStatus,
+    /// Only present once the transaction has been executed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_status: Option<TransactionExecutionStatus>,
+    /// The reason the transaction was reverted or rejected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+}
+
+impl TxStatus {
+    pub fn received() -> Self {
+        Self {
+            finality_status: TxFinalityStatus::Received,
+            execution_status: None,
+            failure_reason: None,
+        }
+    }
+
+    pub fn rejected(reason: impl Into<String>) -> Self {
+        Self {
+            finality_status: TxFinalityStatus::Rejected,
+            execution_status: None,
+            failure_reason: Some(reason.into()),
+        }
+    }
+}
+
+impl From<TransactionStatus> for TxStatus {
+    fn from(value: TransactionStatus) -> Self {
+        let (finality_status, execution_status) = match value {
+            TransactionStatus::Received => (TxFinalityStatus::Received, None),
+            TransactionStatus::Rejected => (TxFinalityStatus::Rejected, None),
+            TransactionStatus::AcceptedOnL2(status) => {
+                (TxFinalityStatus::AcceptedOnL2, Some(status))
+            }
+            TransactionStatus::AcceptedOnL1(status) => {
+                (TxFinalityStatus::AcceptedOnL1, Some(status))
+            }
+        };
+
+        Self { finality_status, execution_status, failure_reason: None }
+    }
+}
+
 // TODO: find a solution to avoid doing this conversion, this is not pretty at all. the reason why
 // we had to do this in the first place is because of the orphan rule. i think eventually we should
 // not rely on `starknet-rs` rpc types anymore and should instead define the types ourselves to have
@@ -620,3 +681,54 @@ fn to_rpc_resource_bounds(
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use starknet::core::types::contract::legacy::LegacyContractClass as StarknetRsLegacyContractClass;
+    use starknet::core::types::BroadcastedDeclareTransactionV1;
+
+    use super::*;
+
+    #[test]
+    fn serde_tx_status() {
+        let status = TxStatus {
+            finality_status: TxFinalityStatus::AcceptedOnL2,
+            execution_status: Some(TransactionExecutionStatus::Reverted),
+            failure_reason: Some("Execution failed".to_string()),
+        };
+
+        let expected = json!({
+            "finality_status": "ACCEPTED_ON_L2",
+            "execution_status": "REVERTED",
+            "failure_reason": "Execution failed"
+        });
+        assert_eq!(serde_json::to_value(&status).unwrap(), expected);
+        assert_eq!(serde_json::from_value::<TxStatus>(expected).unwrap(), status);
+
+        let expected = json!({ "finality_status": "RECEIVED" });
+        assert_eq!(serde_json::to_value(TxStatus::received()).unwrap(), expected);
+    }
+
+    #[test]
+    fn legacy_declare_tx_class_hash() {
+        let json = include_str!("../../../contracts/build/erc20.json");
+        let class = serde_json::from_str::<StarknetRsLegacyContractClass>(json).unwrap();
+        let expected_hash = class.class_hash().unwrap();
+
+        let tx = BroadcastedDeclareTransactionV1 {
+            nonce: Felt::ZERO,
+            max_fee: Felt::ONE,
+            signature: Vec::new(),
+            sender_address: Felt::ONE,
+            contract_class: Arc::new(class.compress().unwrap()),
+            is_query: false,
+        };
+
+        let tx = BroadcastedDeclareTx(BroadcastedDeclareTransaction::V1(tx));
+        let tx = tx.try_into_tx_with_chain_id(ChainId::SEPOLIA).unwrap();
+
+        assert!(tx.class.is_legacy());
+        assert_eq!(tx.class_hash(), expected_hash);
+    }
+}
@@ -0,0 +1,213 @@
+//! Authentication for the state-mutating RPC methods.
+//!
+//! The [`Auth`] layer requires the requests that call any of the protected methods to include the
+//! API key as a bearer token (ie `Authorization: Bearer <key>`), while the other methods remain
+//! open to everyone. Requests without a valid key are rejected with a `401 Unauthorized`.
+//!
+//! The methods called by a request are only known once its body has been read, so the body of an
+//! unauthenticated request is buffered - up to the maximum request body size - before being
+//! forwarded. WebSocket connections can call any method once established, so they always require
+//! the API key.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::future::{self, BoxFuture, Either, Ready};
+use futures::FutureExt;
+use hyper::header::{AUTHORIZATION, CONNECTION, UPGRADE, WWW_AUTHENTICATE};
+use hyper::{Body, HeaderMap, Method, Request, Response, StatusCode};
+use jsonrpsee::core::TEN_MB_SIZE_BYTES;
+use serde_json::Value;
+use tower::{Layer, Service};
+
+use crate::utils::http::{read_body, rejection};
+
+/// Layer that requires an API key for calling the protected methods. See the [module](self) level
+/// docs for more details.
+#[derive(Debug, Clone)]
+pub struct Auth {
+    api_key: Arc<str>,
+    protected_methods: Arc<HashSet<String>>,
+    max_body_size: u32,
+}
+
+impl Auth {
+    /// Creates a new layer that requires `api_key` for calling the `protected_methods`.
+    pub fn new(api_key: impl Into<String>, protected_methods: HashSet<String>) -> Self {
+        Self {
+            api_key: Arc::from(api_key.into()),
+            protected_methods: Arc::new(protected_methods),
+            max_body_size: TEN_MB_SIZE_BYTES,
+        }
+    }
+
+    /// Sets the maximum size of the request bodies that are buffered. Default is 10 MiB.
+    pub(crate) fn max_body_size(mut self, max: u32) -> Self {
+        self.max_body_size = max;
+        self
+    }
+}
+
+impl<S> Layer<S> for Auth {
+    type Service = AuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthService { inner, auth: self.clone() }
+    }
+}
+
+/// Service created by [`Auth`].
+#[derive(Debug, Clone)]
+pub struct AuthService<S> {
+    inner: S,
+    auth: Auth,
+}
+
+impl<S> Service<Request<Body>> for AuthService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Either<
+        Either<S::Future, BoxFuture<'static, Result<Self::Response, Self::Error>>>,
+        Ready<Result<Self::Response, Self::Error>>,
+    >;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        if is_authorized(req.headers(), &self.auth.api_key) {
+            return Either::Left(Either::Left(self.inner.call(req)));
+        }
+
+        if is_websocket_upgrade(req.headers()) {
+            return Either::Right(future::ready(Ok(unauthorized())));
+        }
+
+        // only POST requests can call methods other than the health check
+        if req.method() != Method::POST {
+            return Either::Left(Either::Left(self.inner.call(req)));
+        }
+
+        // The inner service must be called right away, so it's given a body that is only sent
+        // once the original body has been checked.
+        let (parts, body) = req.into_parts();
+        let (mut sender, checked_body) = Body::channel();
+        let fut = self.inner.call(Request::from_parts(parts, checked_body));
+
+        let auth = self.auth.clone();
+        let fut = async move {
+            let bytes = match read_body(body, auth.max_body_size as usize).await {
+                Ok(bytes) => bytes,
+                Err(status) => return Ok(rejection(status)),
+            };
+
+            if calls_protected_method(&bytes, &auth.protected_methods) {
+                return Ok(unauthorized());
+            }
+
+            // the channel always has room for a single chunk
+            let _ = sender.try_send_data(bytes);
+            drop(sender);
+
+            fut.await
+        };
+
+        Either::Left(Either::Right(fut.boxed()))
+    }
+}
+
+/// Returns `true` if the request includes the API key as a bearer token.
+fn is_authorized(headers: &HeaderMap, api_key: &str) -> bool {
+    let Some(token) = headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+
+    constant_time_eq(token.trim().as_bytes(), api_key.as_bytes())
+}
+
+fn is_websocket_upgrade(headers: &HeaderMap) -> bool {
+    let contains = |name, value: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.split(',').any(|token| token.trim().eq_ignore_ascii_case(value)))
+    };
+
+    contains(CONNECTION, "upgrade") && contains(UPGRADE, "websocket")
+}
+
+/// Returns `true` if the JSON-RPC request - or any of the requests in a batch - calls one of the
+/// `protected` methods. A body that can't be parsed doesn't call any method.
+fn calls_protected_method(body: &[u8], protected: &HashSet<String>) -> bool {
+    let is_protected = |call: &Value| {
+        call.get("method").and_then(Value::as_str).is_some_and(|m| protected.contains(m))
+    };
+
+    match serde_json::from_slice::<Value>(body) {
+        Ok(Value::Array(calls)) => calls.iter().any(is_protected),
+        Ok(call) => is_protected(&call),
+        Err(_) => false,
+    }
+}
+
+/// Compares two byte strings in constant time, so that the API key can't be guessed by timing
+/// the responses.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn unauthorized() -> Response<Body> {
+    let mut res = rejection(StatusCode::UNAUTHORIZED);
+    res.headers_mut().insert(WWW_AUTHENTICATE, "Bearer".parse().expect("valid header value"));
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn protected() -> HashSet<String> {
+        HashSet::from(["starknet_addInvokeTransaction".to_string()])
+    }
+
+    #[test]
+    fn detect_protected_calls() {
+        let call = br#"{"jsonrpc":"2.0","id":1,"method":"starknet_addInvokeTransaction"}"#;
+        assert!(calls_protected_method(call, &protected()));
+
+        let call = br#"{"jsonrpc":"2.0","id":1,"method":"starknet_chainId"}"#;
+        assert!(!calls_protected_method(call, &protected()));
+
+        let batch = br#"[
+            {"jsonrpc":"2.0","id":1,"method":"starknet_chainId"},
+            {"jsonrpc":"2.0","id":2,"method":"starknet_addInvokeTransaction"}
+        ]"#;
+        assert!(calls_protected_method(batch, &protected()));
+
+        assert!(!calls_protected_method(b"not json", &protected()));
+    }
+
+    #[test]
+    fn bearer_token() {
+        let mut headers = HeaderMap::new();
+        assert!(!is_authorized(&headers, "secret"));
+
+        headers.insert(AUTHORIZATION, "Bearer secret".parse().unwrap());
+        assert!(is_authorized(&headers, "secret"));
+        assert!(!is_authorized(&headers, "other"));
+
+        headers.insert(AUTHORIZATION, "Basic secret".parse().unwrap());
+        assert!(!is_authorized(&headers, "secret"));
+    }
+}
@@ -0,0 +1,307 @@
+//! Processing of the JSON-RPC batch requests.
+//!
+//! The [`BatchLayer`] splits a batch request sent over HTTP into its calls, and forwards each call
+//! as a separate request to the inner service. The calls of a batch are executed concurrently, up
+//! to a configured number of calls at a time, and go through the other middlewares - eg the rate
+//! limiter - individually. Their responses are collected back into a single batch response, in the
+//! order of the calls.
+//!
+//! A call that fails, eg because it's rejected by a middleware, is answered with an error without
+//! affecting the other calls of the batch. Batches with more calls than the configured maximum are
+//! rejected as a whole.
+
+use std::fmt::Display;
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use futures::{future, stream, FutureExt, StreamExt};
+use hyper::header::{HeaderValue, CONTENT_LENGTH, CONTENT_TYPE};
+use hyper::{Body, Method, Request, Response};
+use jsonrpsee::core::TEN_MB_SIZE_BYTES;
+use serde_json::{json, Value};
+use tower::{Layer, Service, ServiceExt};
+
+use crate::utils::http::{read_body, rejection};
+
+/// The default maximum number of calls of a batch executed concurrently.
+pub const DEFAULT_BATCH_CONCURRENCY: u32 = 16;
+
+/// The JSON-RPC error code of a call that isn't a valid request object.
+const INVALID_REQUEST_CODE: i32 = -32600;
+/// The JSON-RPC error code of a call that failed before being executed.
+const INTERNAL_ERROR_CODE: i32 = -32603;
+/// The JSON-RPC error code of a batch with too many calls.
+const BATCH_TOO_LARGE_CODE: i32 = -32010;
+
+/// Layer that executes the calls of the batch requests concurrently. See the [module](self) level
+/// docs for more details.
+#[derive(Debug, Clone, Copy)]
+pub struct BatchLayer {
+    max_size: Option<u32>,
+    concurrency: u32,
+    max_body_size: u32,
+}
+
+impl BatchLayer {
+    pub fn new() -> Self {
+        Self {
+            max_size: None,
+            concurrency: DEFAULT_BATCH_CONCURRENCY,
+            max_body_size: TEN_MB_SIZE_BYTES,
+        }
+    }
+
+    /// Rejects the batches with more than `max` calls. Unlimited by default.
+    pub fn max_size(mut self, max: u32) -> Self {
+        self.max_size = Some(max);
+        self
+    }
+
+    /// Executes at most `max` calls of a batch concurrently. Default is
+    /// [`DEFAULT_BATCH_CONCURRENCY`].
+    pub fn concurrency(mut self, max: u32) -> Self {
+        self.concurrency = max.max(1);
+        self
+    }
+
+    /// Sets the maximum size of the request bodies that are buffered. Default is 10 MiB.
+    pub(crate) fn max_body_size(mut self, max: u32) -> Self {
+        self.max_body_size = max;
+        self
+    }
+}
+
+impl Default for BatchLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for BatchLayer {
+    type Service = BatchService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BatchService { inner, config: *self }
+    }
+}
+
+/// Service created by [`BatchLayer`].
+#[derive(Debug, Clone)]
+pub struct BatchService<S> {
+    inner: S,
+    config: BatchLayer,
+}
+
+impl<S> Service<Request<Body>> for BatchService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Display + Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        // only POST requests can hold a batch, the batches sent over a WebSocket connection are
+        // handled by the server itself
+        if req.method() != Method::POST {
+            return self.inner.call(req).boxed();
+        }
+
+        // the service that has been polled ready is kept for forwarding the request as is
+        let clone = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, clone);
+        let config = self.config;
+
+        async move {
+            let (parts, body) = req.into_parts();
+            let bytes = match read_body(body, config.max_body_size as usize).await {
+                Ok(bytes) => bytes,
+                Err(status) => return Ok(rejection(status)),
+            };
+
+            let calls = match serde_json::from_slice::<Value>(&bytes) {
+                Ok(Value::Array(calls)) if !calls.is_empty() => calls,
+                // single calls, and invalid requests, are handled by the server
+                _ => return inner.oneshot(Request::from_parts(parts, Body::from(bytes))).await,
+            };
+
+            if let Some(max) = config.max_size.filter(|max| calls.len() > *max as usize) {
+                let message = format!("The batch can't have more than {max} calls");
+                let res = error_response(Value::Null, BATCH_TOO_LARGE_CODE, message);
+                return Ok(json_response(&res));
+            }
+
+            let requests = calls.into_iter().map(|call| {
+                // a call that isn't an object has no id to be answered with
+                if !call.is_object() {
+                    let res = error_response(Value::Null, INVALID_REQUEST_CODE, "Invalid request");
+                    return future::ready(Some(res)).left_future();
+                }
+
+                let id = call.get("id").cloned();
+                let body = serde_json::to_vec(&call).expect("valid json");
+
+                let mut req = Request::new(Body::empty());
+                *req.method_mut() = parts.method.clone();
+                *req.uri_mut() = parts.uri.clone();
+                *req.version_mut() = parts.version;
+                *req.headers_mut() = parts.headers.clone();
+                req.headers_mut().insert(CONTENT_LENGTH, HeaderValue::from(body.len()));
+                *req.body_mut() = Body::from(body);
+
+                process_call(inner.clone(), req, id).right_future()
+            });
+
+            let responses = stream::iter(requests)
+                .buffered(config.concurrency as usize)
+                .filter_map(|res| async move { res })
+                .collect::<Vec<_>>()
+                .await;
+
+            // a batch made only of notifications has no response
+            if responses.is_empty() {
+                Ok(Response::new(Body::empty()))
+            } else {
+                Ok(json_response(&Value::Array(responses)))
+            }
+        }
+        .boxed()
+    }
+}
+
+/// Executes a single call of a batch, and returns its response. Notifications - ie calls without
+/// an id - have no response, even if they fail.
+async fn process_call<S>(service: S, req: Request<Body>, id: Option<Value>) -> Option<Value>
+where
+    S: Service<Request<Body>, Response = Response<Body>>,
+    S::Error: Display,
+{
+    let res = match service.oneshot(req).await {
+        Ok(res) => res,
+        Err(error) => return id.map(|id| error_response(id, INTERNAL_ERROR_CODE, error)),
+    };
+
+    let status = res.status();
+    let body = hyper::body::to_bytes(res.into_body()).await;
+    let id = id?;
+
+    // a call rejected by a middleware is answered with a plain http error
+    if !status.is_success() {
+        let reason = status.canonical_reason().unwrap_or("Request failed");
+        return Some(error_response(id, INTERNAL_ERROR_CODE, reason));
+    }
+
+    match body.map(|body| serde_json::from_slice::<Value>(&body)) {
+        Ok(Ok(response)) => Some(response),
+        Ok(Err(error)) => Some(error_response(id, INTERNAL_ERROR_CODE, error)),
+        Err(error) => Some(error_response(id, INTERNAL_ERROR_CODE, error)),
+    }
+}
+
+fn error_response(id: Value, code: i32, message: impl Display) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message.to_string() } })
+}
+
+fn json_response(value: &Value) -> Response<Body> {
+    let mut res = Response::new(Body::from(serde_json::to_vec(value).expect("valid json")));
+    res.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use hyper::StatusCode;
+
+    use super::*;
+
+    /// A service which answers each call with its method, and rejects the `reject` method.
+    fn echo(req: Request<Body>) -> BoxFuture<'static, Result<Response<Body>, Infallible>> {
+        async move {
+            let body = hyper::body::to_bytes(req.into_body()).await.unwrap();
+            let call: Value = serde_json::from_slice(&body).unwrap();
+
+            if call["method"] == "reject" {
+                return Ok(rejection(StatusCode::TOO_MANY_REQUESTS));
+            }
+
+            let res = json!({ "jsonrpc": "2.0", "id": call["id"], "result": call["method"] });
+            Ok(json_response(&res))
+        }
+        .boxed()
+    }
+
+    async fn send(layer: BatchLayer, body: &str) -> Value {
+        let service = layer.layer(tower::service_fn(echo));
+        let req = Request::post("/").body(Body::from(body.to_string())).unwrap();
+        let res = service.oneshot(req).await.unwrap();
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn single_call() {
+        let res = send(BatchLayer::new(), r#"{"jsonrpc":"2.0","id":1,"method":"a"}"#).await;
+        assert_eq!(res, json!({ "jsonrpc": "2.0", "id": 1, "result": "a" }));
+    }
+
+    #[tokio::test]
+    async fn batch_calls_are_isolated() {
+        let batch = r#"[
+            {"jsonrpc":"2.0","id":1,"method":"a"},
+            {"jsonrpc":"2.0","id":2,"method":"reject"},
+            {"jsonrpc":"2.0","method":"notification"},
+            {"jsonrpc":"2.0","id":3,"method":"b"}
+        ]"#;
+
+        let res = send(BatchLayer::new().concurrency(2), batch).await;
+        let expected = json!([
+            { "jsonrpc": "2.0", "id": 1, "result": "a" },
+            {
+                "jsonrpc": "2.0",
+                "id": 2,
+                "error": { "code": INTERNAL_ERROR_CODE, "message": "Too Many Requests" }
+            },
+            { "jsonrpc": "2.0", "id": 3, "result": "b" },
+        ]);
+
+        assert_eq!(res, expected);
+    }
+
+    #[tokio::test]
+    async fn invalid_batch_calls() {
+        let batch = r#"[1, {"jsonrpc":"2.0","id":1,"method":"a"}, "call"]"#;
+
+        let res = send(BatchLayer::new(), batch).await;
+        let invalid = json!({
+            "jsonrpc": "2.0",
+            "id": null,
+            "error": { "code": INVALID_REQUEST_CODE, "message": "Invalid request" }
+        });
+        let expected =
+            json!([invalid.clone(), { "jsonrpc": "2.0", "id": 1, "result": "a" }, invalid]);
+
+        assert_eq!(res, expected);
+    }
+
+    #[tokio::test]
+    async fn batch_too_large() {
+        let batch = r#"[
+            {"jsonrpc":"2.0","id":1,"method":"a"},
+            {"jsonrpc":"2.0","id":2,"method":"b"}
+        ]"#;
+
+        let res = send(BatchLayer::new().max_size(1), batch).await;
+        assert_eq!(res["error"]["code"], BATCH_TOO_LARGE_CODE);
+
+        let res = send(BatchLayer::new().max_size(2), batch).await;
+        assert_eq!(res.as_array().map(Vec::len), Some(2));
+    }
+}
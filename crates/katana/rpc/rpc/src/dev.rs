@@ -1,13 +1,32 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use jsonrpsee::core::{async_trait, Error};
 use katana_core::backend::Backend;
-use katana_core::service::block_producer::{BlockProducer, BlockProducerMode, PendingExecutor};
+use katana_core::service::block_producer::{
+    BlockProducer, BlockProducerMode, BlockProductionError, PendingExecutor,
+};
 use katana_executor::ExecutorFactory;
-use katana_primitives::Felt;
+use katana_primitives::block::{BlockNumber, FinalityStatus};
+use katana_primitives::class::{ClassHash, SierraVersion};
+use katana_primitives::contract::{ContractAddress, Nonce, StorageKey, StorageValue};
+use katana_primitives::genesis::constant::{
+    get_fee_token_balance_base_storage_address, ERC20_TOTAL_SUPPLY_STORAGE_SLOT,
+};
+use katana_primitives::state::StateUpdates;
+use katana_primitives::transaction::{ExecutableTx, ExecutableTxWithHash};
+use katana_primitives::utils::split_u256;
+use katana_primitives::{Felt, U256};
+use katana_provider::traits::block::BlockHashProvider;
+use katana_provider::traits::contract::ContractClassProvider;
+use katana_provider::traits::state::{StateFactoryProvider, StateProvider};
 use katana_rpc_api::dev::DevApiServer;
 use katana_rpc_types::account::Account;
 use katana_rpc_types::error::dev::DevApiError;
+use katana_rpc_types::error::starknet::StarknetApiError;
+use katana_rpc_types::receipt::{ReceiptBlock, TxReceiptWithBlockInfo};
+use katana_rpc_types::transaction::BroadcastedTx;
+use katana_rpc_types::FeeToken;
 
 #[allow(missing_debug_implementations)]
 pub struct DevApi<EF: ExecutorFactory> {
@@ -36,6 +55,24 @@ impl<EF: ExecutorFactory> DevApi<EF> {
         }
     }
 
+    /// Seals the current pending block and returns its block number.
+    pub fn mine(&self) -> Result<BlockNumber, DevApiError> {
+        match self.block_producer.force_mine() {
+            Ok(Some(outcome)) => Ok(outcome.block_number),
+            Ok(None) => Err(DevApiError::MiningInProgress),
+            Err(_) => Err(DevApiError::FailedToMineBlock),
+        }
+    }
+
+    /// Reverts the chain to `block_number`.
+    pub fn revert_to_block(&self, block_number: BlockNumber) -> Result<(), DevApiError> {
+        match self.block_producer.revert_to(block_number) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(DevApiError::MiningInProgress),
+            Err(_) => Err(DevApiError::FailedToRevertBlock),
+        }
+    }
+
     pub fn set_next_block_timestamp(&self, timestamp: u64) -> Result<(), DevApiError> {
         if self.has_pending_transactions() {
             return Err(DevApiError::PendingTransactions);
@@ -53,19 +90,216 @@ impl<EF: ExecutorFactory> DevApi<EF> {
         }
 
         let mut block_context_generator = self.backend.block_context_generator.write();
-        block_context_generator.block_timestamp_offset += offset as i64;
+
+        // if an explicit timestamp has already been set for the next block, move it forward instead
+        // because it takes precedence over the offset.
+        if block_context_generator.next_block_start_time != 0 {
+            block_context_generator.next_block_start_time += offset;
+        } else {
+            block_context_generator.block_timestamp_offset += offset as i64;
+        }
 
         Ok(())
     }
+
+    pub fn set_storage_at(
+        &self,
+        address: ContractAddress,
+        key: StorageKey,
+        value: StorageValue,
+    ) -> Result<(), DevApiError> {
+        self.block_producer
+            .set_storage_at(address, key, value)
+            .map_err(|_| DevApiError::FailedToUpdateStorage)
+    }
+
+    pub fn set_nonce(&self, address: ContractAddress, nonce: Nonce) -> Result<(), DevApiError> {
+        self.block_producer
+            .set_nonce(address, nonce)
+            .map_err(|_| DevApiError::FailedToUpdateStorage)
+    }
+
+    /// Replaces the class of the contract at `address` with `class_hash`, which must already be
+    /// declared.
+    pub fn set_class_hash_at(
+        &self,
+        address: ContractAddress,
+        class_hash: ClassHash,
+    ) -> Result<(), DevApiError> {
+        let state = self.state()?;
+        let class = state.class(class_hash).map_err(|_| DevApiError::FailedToUpdateStorage)?;
+        if class.is_none() {
+            return Err(DevApiError::ClassNotDeclared);
+        }
+
+        self.block_producer
+            .set_class_hash_at(address, class_hash)
+            .map_err(|_| DevApiError::FailedToUpdateStorage)
+    }
+
+    /// Mints `amount` of the fee `token` to `address`.
+    ///
+    /// Both the balance of `address` and the total supply of the token are increased by `amount`.
+    pub fn mint(
+        &self,
+        address: ContractAddress,
+        amount: U256,
+        token: FeeToken,
+    ) -> Result<(), DevApiError> {
+        let fee_tokens = &self.backend.executor_factory.cfg().fee_token_addresses;
+        let token = match token {
+            FeeToken::Eth => fee_tokens.eth,
+            FeeToken::Strk => fee_tokens.strk,
+        };
+
+        // read from the pending state, if any, so that the balance changes made by the pending
+        // transactions are accounted for
+        let state = self.state()?;
+
+        let mut storage = BTreeMap::new();
+        let balance_key = get_fee_token_balance_base_storage_address(address);
+        add_to_u256_storage(state.as_ref(), token, balance_key, amount, &mut storage)?;
+        let supply_key = ERC20_TOTAL_SUPPLY_STORAGE_SLOT;
+        add_to_u256_storage(state.as_ref(), token, supply_key, amount, &mut storage)?;
+
+        // the balance and the total supply are overridden together so that, on instant mining,
+        // they are updated in the same block
+        let mut updates = StateUpdates::default();
+        updates.storage_updates.insert(token, storage);
+
+        self.block_producer.override_state(updates).map_err(|_| DevApiError::FailedToUpdateStorage)
+    }
+
+    /// Executes `transactions` atomically in a block of their own, and returns their receipts.
+    pub fn execute_batch(
+        &self,
+        transactions: Vec<BroadcastedTx>,
+    ) -> Result<Vec<TxReceiptWithBlockInfo>, Error> {
+        let chain_id = self.backend.chain_spec.id();
+
+        let transactions = transactions
+            .into_iter()
+            .map(|tx| -> Result<ExecutableTxWithHash, StarknetApiError> {
+                let tx = match tx {
+                    BroadcastedTx::Invoke(tx) if !tx.is_query() => {
+                        ExecutableTx::Invoke(tx.into_tx_with_chain_id(chain_id))
+                    }
+                    BroadcastedTx::DeployAccount(tx) if !tx.is_query() => {
+                        ExecutableTx::DeployAccount(tx.into_tx_with_chain_id(chain_id))
+                    }
+                    BroadcastedTx::Declare(tx) if !tx.is_query() => {
+                        let tx = tx
+                            .try_into_tx_with_chain_id(chain_id)
+                            .map_err(|_| StarknetApiError::InvalidContractClass)?;
+
+                        if let Some(class) = tx.class.as_sierra() {
+                            if !SierraVersion::from_class(class)?.is_supported() {
+                                return Err(StarknetApiError::UnsupportedContractClassVersion);
+                            }
+                        }
+
+                        ExecutableTx::Declare(tx)
+                    }
+                    _ => return Err(StarknetApiError::UnsupportedTransactionVersion),
+                };
+
+                Ok(ExecutableTxWithHash::new(tx))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (outcome, txs) = match self.block_producer.execute_batch(transactions) {
+            Ok(Some(result)) => result,
+            Ok(None) => return Err(DevApiError::MiningInProgress.into()),
+            Err(BlockProductionError::BatchTransactionFailed { index, reason }) => {
+                return Err(StarknetApiError::TransactionExecutionError {
+                    transaction_index: index as u64,
+                    execution_error: reason,
+                }
+                .into());
+            }
+            Err(_) => return Err(DevApiError::FailedToMineBlock.into()),
+        };
+
+        let block_number = outcome.block_number;
+        let block_hash = self
+            .backend
+            .blockchain
+            .provider()
+            .block_hash_by_num(block_number)
+            .map_err(StarknetApiError::from)?
+            .expect("block must exist");
+
+        let block = ReceiptBlock::Block { block_hash, block_number };
+        let receipts = txs
+            .into_iter()
+            .map(|tx| {
+                let status = FinalityStatus::AcceptedOnL2;
+                TxReceiptWithBlockInfo::new(block.clone(), tx.tx.hash, status, tx.receipt)
+            })
+            .collect();
+
+        Ok(receipts)
+    }
+
+    /// Returns the pending state if there is one, otherwise the latest state.
+    fn state(&self) -> Result<Box<dyn StateProvider>, DevApiError> {
+        match self.pending_executor() {
+            Some(executor) => Ok(executor.read().state()),
+            None => self
+                .backend
+                .blockchain
+                .provider()
+                .latest()
+                .map_err(|_| DevApiError::FailedToUpdateStorage),
+        }
+    }
+}
+
+/// Adds `amount` to the u256 value stored in the `key` (low) and `key + 1` (high) storage slots
+/// of the `token` contract, and puts the new values in `storage`.
+fn add_to_u256_storage(
+    state: &dyn StateProvider,
+    token: ContractAddress,
+    key: StorageKey,
+    amount: U256,
+    storage: &mut BTreeMap<StorageKey, StorageValue>,
+) -> Result<(), DevApiError> {
+    let low_key = key;
+    let high_key = key + Felt::ONE;
+
+    let read = |key| -> Result<Felt, DevApiError> {
+        let value = state.storage(token, key).map_err(|_| DevApiError::FailedToUpdateStorage)?;
+        Ok(value.unwrap_or_default())
+    };
+
+    let low = U256::from_be_bytes(read(low_key)?.to_bytes_be());
+    let high = U256::from_be_bytes(read(high_key)?.to_bytes_be());
+    let current = (high << 128) + low;
+
+    let new = current.checked_add(amount).ok_or(DevApiError::BalanceOverflow)?;
+    let (new_low, new_high) = split_u256(new);
+
+    storage.insert(low_key, new_low);
+    storage.insert(high_key, new_high);
+
+    Ok(())
 }
 
 #[async_trait]
 impl<EF: ExecutorFactory> DevApiServer for DevApi<EF> {
     async fn generate_block(&self) -> Result<(), Error> {
-        self.block_producer.force_mine();
+        self.mine()?;
         Ok(())
     }
 
+    async fn mine(&self) -> Result<BlockNumber, Error> {
+        Ok(self.mine()?)
+    }
+
+    async fn revert_to_block(&self, block_number: BlockNumber) -> Result<(), Error> {
+        Ok(self.revert_to_block(block_number)?)
+    }
+
     async fn next_block_timestamp(&self) -> Result<(), Error> {
         // Ok(self.sequencer.backend().env.read().block.block_timestamp.0)
         Ok(())
@@ -79,19 +313,71 @@ impl<EF: ExecutorFactory> DevApiServer for DevApi<EF> {
         Ok(self.increase_next_block_timestamp(timestamp)?)
     }
 
+    async fn increase_time(&self, seconds: u64) -> Result<(), Error> {
+        Ok(self.increase_next_block_timestamp(seconds)?)
+    }
+
     async fn set_storage_at(
         &self,
-        _contract_address: Felt,
-        _key: Felt,
-        _value: Felt,
+        contract_address: ContractAddress,
+        key: StorageKey,
+        value: StorageValue,
+    ) -> Result<(), Error> {
+        Ok(self.set_storage_at(contract_address, key, value)?)
+    }
+
+    async fn set_nonce(
+        &self,
+        contract_address: ContractAddress,
+        nonce: Nonce,
     ) -> Result<(), Error> {
-        // self.sequencer
-        //     .set_storage_at(contract_address.into(), key, value)
-        //     .await
-        //     .map_err(|_| Error::from(KatanaApiError::FailedToUpdateStorage))
+        Ok(self.set_nonce(contract_address, nonce)?)
+    }
+
+    async fn set_class_hash_at(
+        &self,
+        contract_address: ContractAddress,
+        class_hash: ClassHash,
+    ) -> Result<(), Error> {
+        Ok(self.set_class_hash_at(contract_address, class_hash)?)
+    }
+
+    async fn impersonate_account(&self, address: ContractAddress) -> Result<(), Error> {
+        self.backend.executor_factory.execution_flags().impersonated_accounts().add(address);
+        Ok(())
+    }
+
+    async fn stop_impersonating_account(&self, address: ContractAddress) -> Result<(), Error> {
+        self.backend.executor_factory.execution_flags().impersonated_accounts().remove(address);
         Ok(())
     }
 
+    async fn set_fee_enabled(&self, enabled: bool) -> Result<(), Error> {
+        self.backend.executor_factory.execution_flags().set_fee(enabled);
+        Ok(())
+    }
+
+    async fn set_account_validation_enabled(&self, enabled: bool) -> Result<(), Error> {
+        self.backend.executor_factory.execution_flags().set_account_validation(enabled);
+        Ok(())
+    }
+
+    async fn mint(
+        &self,
+        address: ContractAddress,
+        amount: U256,
+        token: Option<FeeToken>,
+    ) -> Result<(), Error> {
+        Ok(self.mint(address, amount, token.unwrap_or_default())?)
+    }
+
+    async fn execute_batch(
+        &self,
+        transactions: Vec<BroadcastedTx>,
+    ) -> Result<Vec<TxReceiptWithBlockInfo>, Error> {
+        self.execute_batch(transactions)
+    }
+
     async fn predeployed_accounts(&self) -> Result<Vec<Account>, Error> {
         Ok(self.backend.chain_spec.genesis().accounts().map(|e| Account::new(*e.0, e.1)).collect())
     }
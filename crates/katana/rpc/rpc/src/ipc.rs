@@ -0,0 +1,406 @@
+//! IPC transport of the RPC server.
+//!
+//! The [`IpcServer`] serves the RPC methods over a Unix domain socket, so that the services running
+//! on the same host - eg Torii or Saya - can talk to the node without going through the network
+//! stack, nor exposing a network port.
+//!
+//! The messages are JSON-RPC requests - single calls or batches - written back to back on the
+//! connection, each response being written as a single line. Subscriptions are supported, their
+//! notifications being written on the connection of the subscriber.
+//!
+//! The connections are not authenticated, nor rate limited, as the access to the node is guarded
+//! by the permissions of the socket file, which is only accessible by its owner.
+
+use std::io;
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use futures::future::join_all;
+use futures::StreamExt;
+use jsonrpsee::core::server::rpc_module::Methods;
+use jsonrpsee::core::TEN_MB_SIZE_BYTES;
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::unix::OwnedWriteHalf;
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{mpsc, watch};
+use tracing::{debug, info};
+
+const LOG_TARGET: &str = "rpc::ipc";
+
+/// The JSON-RPC error code of a message that isn't valid JSON.
+const PARSE_ERROR_CODE: i32 = -32700;
+/// The JSON-RPC error code of a message that isn't a valid request.
+const INVALID_REQUEST_CODE: i32 = -32600;
+/// The JSON-RPC error code of a message that is larger than the maximum request size.
+const OVERSIZED_REQUEST_CODE: i32 = -32007;
+
+/// The handle to a running [`IpcServer`].
+#[derive(Debug, Clone)]
+pub struct IpcServerHandle {
+    path: PathBuf,
+    stop: Arc<watch::Sender<()>>,
+}
+
+impl IpcServerHandle {
+    /// Returns the path of the socket the server is listening on.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Tell the server to stop without waiting for the server to stop. Returns `false` if the
+    /// server has already been stopped.
+    pub fn stop(&self) -> bool {
+        self.stop.send(()).is_ok()
+    }
+
+    /// Wait until the server, and all its connections, have stopped.
+    pub async fn stopped(self) {
+        self.stop.closed().await
+    }
+}
+
+/// Server serving the RPC methods over a Unix domain socket. See the [module](self) level docs for
+/// more details.
+#[derive(Debug)]
+pub struct IpcServer {
+    path: PathBuf,
+    max_request_size: u32,
+}
+
+impl IpcServer {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), max_request_size: TEN_MB_SIZE_BYTES }
+    }
+
+    /// Set the maximum size of a request (in bytes). Default is 10 MiB.
+    pub fn max_request_size(mut self, max: u32) -> Self {
+        self.max_request_size = max;
+        self
+    }
+
+    /// Binds the socket and starts serving the `methods` on it.
+    ///
+    /// A socket file left over by a previous run is replaced, but any other file at the path is
+    /// left untouched and the server fails to start.
+    pub fn start(self, methods: impl Into<Methods>) -> io::Result<IpcServerHandle> {
+        match std::fs::symlink_metadata(&self.path) {
+            Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(&self.path)?,
+            Ok(_) => {
+                let message = format!("{} exists and is not a socket", self.path.display());
+                return Err(io::Error::new(io::ErrorKind::AlreadyExists, message));
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => return Err(error),
+        }
+
+        let listener = UnixListener::bind(&self.path)?;
+        std::fs::set_permissions(&self.path, std::fs::Permissions::from_mode(0o600))?;
+        let (stop, stopped) = watch::channel(());
+
+        let server = Server {
+            path: self.path.clone(),
+            listener,
+            methods: methods.into(),
+            max_request_size: self.max_request_size as usize,
+            stopped,
+        };
+
+        tokio::spawn(server.run());
+
+        info!(target: LOG_TARGET, path = %self.path.display(), "IPC server started.");
+
+        Ok(IpcServerHandle { path: self.path, stop: Arc::new(stop) })
+    }
+}
+
+struct Server {
+    path: PathBuf,
+    listener: UnixListener,
+    methods: Methods,
+    max_request_size: usize,
+    stopped: watch::Receiver<()>,
+}
+
+impl Server {
+    async fn run(mut self) {
+        loop {
+            let stream = tokio::select! {
+                _ = self.stopped.changed() => break,
+                res = self.listener.accept() => match res {
+                    Ok((stream, _)) => stream,
+                    Err(error) => {
+                        debug!(target: LOG_TARGET, %error, "Accepting connection.");
+                        continue;
+                    }
+                },
+            };
+
+            let connection = Connection {
+                methods: self.methods.clone(),
+                max_request_size: self.max_request_size,
+                stopped: self.stopped.clone(),
+            };
+
+            tokio::spawn(connection.serve(stream));
+        }
+
+        // removed before the handle is notified that the server has stopped
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+struct Connection {
+    methods: Methods,
+    max_request_size: usize,
+    stopped: watch::Receiver<()>,
+}
+
+impl Connection {
+    async fn serve(mut self, stream: UnixStream) {
+        let (mut reader, writer) = stream.into_split();
+
+        // the responses and the subscription notifications are all written by the same task, in
+        // the order they are produced
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let mut writer = tokio::spawn(write_messages(writer, receiver));
+
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 8192];
+
+        let stopping = loop {
+            let read = tokio::select! {
+                _ = self.stopped.changed() => break true,
+                read = reader.read(&mut chunk) => read,
+            };
+
+            match read {
+                Ok(0) | Err(_) => break false,
+                Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+            }
+
+            if buffer.len() > self.max_request_size {
+                let message =
+                    format!("Request is too big, the maximum is {} bytes", self.max_request_size);
+                let _ = sender.send(error_response(Value::Null, OVERSIZED_REQUEST_CODE, message));
+                break false;
+            }
+
+            let Ok((messages, consumed)) = split_messages(&buffer) else {
+                let _ = sender.send(error_response(Value::Null, PARSE_ERROR_CODE, "Parse error"));
+                break false;
+            };
+            buffer.drain(..consumed);
+
+            for message in messages {
+                let methods = self.methods.clone();
+                let sender = sender.clone();
+
+                tokio::spawn(async move {
+                    if let Some(response) = process_message(&methods, message, &sender).await {
+                        let _ = sender.send(response);
+                    }
+                });
+            }
+        };
+
+        drop(sender);
+
+        // the pending responses are still written once the client has stopped sending requests,
+        // unless the server is stopped in the meantime
+        if !stopping {
+            tokio::select! {
+                _ = &mut writer => return,
+                _ = self.stopped.changed() => {}
+            }
+        }
+
+        writer.abort();
+    }
+}
+
+/// Splits the complete messages at the start of `buffer`, returning them along with the number of
+/// bytes they span. The trailing incomplete message, if any, is left in the buffer.
+fn split_messages(buffer: &[u8]) -> Result<(Vec<Value>, usize), serde_json::Error> {
+    let mut stream = serde_json::Deserializer::from_slice(buffer).into_iter::<Value>();
+    let mut messages = Vec::new();
+
+    loop {
+        match stream.next() {
+            Some(Ok(message)) => messages.push(message),
+            Some(Err(error)) if !error.is_eof() => return Err(error),
+            Some(Err(_)) | None => break,
+        }
+    }
+
+    Ok((messages, stream.byte_offset()))
+}
+
+/// Executes a single call or a batch, and returns its response. Messages made only of
+/// notifications have no response.
+async fn process_message(
+    methods: &Methods,
+    message: Value,
+    sender: &mpsc::UnboundedSender<String>,
+) -> Option<String> {
+    match message {
+        Value::Array(calls) if calls.is_empty() => {
+            Some(error_response(Value::Null, INVALID_REQUEST_CODE, "Empty batch"))
+        }
+
+        Value::Array(calls) => {
+            let calls = calls.into_iter().map(|call| process_call(methods, call, sender));
+            let responses = join_all(calls).await.into_iter().flatten().collect::<Vec<_>>();
+
+            if responses.is_empty() { None } else { Some(format!("[{}]", responses.join(","))) }
+        }
+
+        call => process_call(methods, call, sender).await,
+    }
+}
+
+/// Executes a single call, and returns its response. Notifications - ie calls without an id - are
+/// executed but have no response.
+async fn process_call(
+    methods: &Methods,
+    mut call: Value,
+    sender: &mpsc::UnboundedSender<String>,
+) -> Option<String> {
+    let id = call.get("id").cloned();
+
+    // notifications are executed as calls with a null id, whose response is discarded
+    if let (None, Some(call)) = (&id, call.as_object_mut()) {
+        call.insert("id".to_string(), Value::Null);
+    }
+
+    let request = serde_json::to_string(&call).expect("valid json");
+
+    match methods.raw_json_request(&request).await {
+        Ok((response, mut notifications)) => {
+            // forward the notifications of the subscription, if the call created one, until
+            // either the subscription or the connection is closed
+            let sender = sender.clone();
+            tokio::spawn(async move {
+                while let Some(notification) = notifications.next().await {
+                    if sender.send(notification).is_err() {
+                        break;
+                    }
+                }
+            });
+
+            id.map(|_| response.result)
+        }
+
+        Err(_) => {
+            Some(error_response(id.unwrap_or_default(), INVALID_REQUEST_CODE, "Invalid request"))
+        }
+    }
+}
+
+async fn write_messages(mut writer: OwnedWriteHalf, mut messages: mpsc::UnboundedReceiver<String>) {
+    while let Some(mut message) = messages.recv().await {
+        message.push('\n');
+        if writer.write_all(message.as_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+fn error_response(id: Value, code: i32, message: impl Into<String>) -> String {
+    let error = json!({ "code": code, "message": message.into() });
+    json!({ "jsonrpc": "2.0", "id": id, "error": error }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpsee::RpcModule;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    use super::*;
+
+    fn module() -> RpcModule<()> {
+        let mut module = RpcModule::new(());
+        module.register_method("echo", |params, _| Ok(params.one::<Value>()?)).unwrap();
+        module
+    }
+
+    #[test]
+    fn split_incomplete_messages() {
+        let buffer = br#"{"id":1} [{"id":2}] {"id":"#;
+        let (messages, consumed) = split_messages(buffer).unwrap();
+        assert_eq!(messages, vec![json!({ "id": 1 }), json!([{ "id": 2 }])]);
+        assert_eq!(&buffer[consumed..], br#" {"id":"#);
+
+        assert!(split_messages(b"{]").is_err());
+    }
+
+    #[tokio::test]
+    async fn serve_over_unix_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("katana.ipc");
+
+        let handle = IpcServer::new(&path).start(module()).unwrap();
+
+        let mut stream = UnixStream::connect(&path).await.unwrap();
+        let single = r#"{"jsonrpc":"2.0","id":1,"method":"echo","params":["a"]}"#;
+        let batch = r#"[
+            {"jsonrpc":"2.0","id":2,"method":"echo","params":["b"]},
+            {"jsonrpc":"2.0","method":"echo","params":["notification"]},
+            {"jsonrpc":"2.0","id":3,"method":"echo","params":["c"]}
+        ]"#;
+        stream.write_all(single.as_bytes()).await.unwrap();
+        stream.write_all(batch.as_bytes()).await.unwrap();
+
+        let mut lines = BufReader::new(stream).lines();
+        let mut responses = Vec::new();
+        for _ in 0..2 {
+            let line = lines.next_line().await.unwrap().unwrap();
+            responses.push(serde_json::from_str::<Value>(&line).unwrap());
+        }
+
+        // the messages are processed concurrently, so their responses may be in any order
+        responses.sort_by_key(|res| res.is_array());
+        assert_eq!(responses[0], json!({ "jsonrpc": "2.0", "id": 1, "result": "a" }));
+        assert_eq!(
+            responses[1],
+            json!([
+                { "jsonrpc": "2.0", "id": 2, "result": "b" },
+                { "jsonrpc": "2.0", "id": 3, "result": "c" },
+            ])
+        );
+
+        assert!(handle.stop());
+        handle.stopped().await;
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn socket_file_is_private() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("katana.ipc");
+
+        // a socket left over by a previous run is replaced
+        let stale = std::os::unix::net::UnixListener::bind(&path).unwrap();
+        drop(stale);
+
+        let handle = IpcServer::new(&path).start(module()).unwrap();
+        let metadata = std::fs::metadata(&path).unwrap();
+        assert!(metadata.file_type().is_socket());
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+
+        assert!(handle.stop());
+        handle.stopped().await;
+    }
+
+    #[tokio::test]
+    async fn other_files_are_not_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("katana.ipc");
+        std::fs::write(&path, "data").unwrap();
+
+        let error = IpcServer::new(&path).start(module()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "data");
+    }
+}
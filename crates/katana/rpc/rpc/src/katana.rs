@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use jsonrpsee::core::{async_trait, RpcResult};
+use katana_core::backend::Backend;
+use katana_core::service::block_producer::{BlockProducer, BlockProducerMode, PendingExecutor};
+use katana_executor::ExecutorFactory;
+use katana_primitives::block::{BlockHashOrNumber, BlockIdOrTag, BlockTag};
+use katana_provider::traits::block::{BlockHashProvider, BlockNumberProvider};
+use katana_provider::traits::transaction::{
+    ReceiptProvider, TransactionProvider, TransactionTraceProvider,
+};
+use katana_rpc_api::katana::KatanaApiServer;
+use katana_rpc_types::error::starknet::StarknetApiError;
+use katana_rpc_types::report::{BlockExecutionReport, TxExecutionReport};
+use katana_tasks::TokioTaskSpawner;
+
+#[allow(missing_debug_implementations)]
+pub struct KatanaApi<EF: ExecutorFactory> {
+    backend: Arc<Backend<EF>>,
+    block_producer: BlockProducer<EF>,
+}
+
+impl<EF: ExecutorFactory> Clone for KatanaApi<EF> {
+    fn clone(&self) -> Self {
+        Self { backend: Arc::clone(&self.backend), block_producer: self.block_producer.clone() }
+    }
+}
+
+impl<EF: ExecutorFactory> KatanaApi<EF> {
+    pub fn new(backend: Arc<Backend<EF>>, block_producer: BlockProducer<EF>) -> Self {
+        Self { backend, block_producer }
+    }
+
+    async fn on_io_blocking_task<F, T>(&self, func: F) -> T
+    where
+        F: FnOnce(Self) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let this = self.clone();
+        TokioTaskSpawner::new().unwrap().spawn_blocking(move || func(this)).await.unwrap()
+    }
+
+    /// Returns the pending state if the sequencer is running in _interval_ mode. Otherwise `None`.
+    fn pending_executor(&self) -> Option<PendingExecutor> {
+        match &*self.block_producer.producer.read() {
+            BlockProducerMode::Instant(_) => None,
+            BlockProducerMode::Interval(producer) => Some(producer.executor()),
+        }
+    }
+
+    fn block_execution_report(
+        &self,
+        block_id: BlockIdOrTag,
+    ) -> Result<BlockExecutionReport, StarknetApiError> {
+        use StarknetApiError::BlockNotFound;
+
+        let provider = self.backend.blockchain.provider();
+
+        let block_id: BlockHashOrNumber = match block_id {
+            BlockIdOrTag::Tag(BlockTag::Pending) => match self.pending_executor() {
+                Some(executor) => {
+                    let executor = executor.read();
+                    let block_number = executor.block_env().number;
+
+                    let transactions = executor.transactions().iter().filter_map(|(tx, res)| {
+                        let (receipt, trace) = (res.receipt()?, res.trace()?);
+                        Some(TxExecutionReport::new(tx.hash, receipt, trace))
+                    });
+
+                    return Ok(BlockExecutionReport::new(
+                        None,
+                        block_number,
+                        transactions.collect(),
+                    ));
+                }
+
+                // if there is no pending block, return the latest block
+                None => provider.latest_number()?.into(),
+            },
+            BlockIdOrTag::Tag(BlockTag::Latest) => provider.latest_number()?.into(),
+            BlockIdOrTag::Number(num) => num.into(),
+            BlockIdOrTag::Hash(hash) => hash.into(),
+        };
+
+        let (block_number, block_hash) = match block_id {
+            BlockHashOrNumber::Num(num) => {
+                (num, provider.block_hash_by_num(num)?.ok_or(BlockNotFound)?)
+            }
+            BlockHashOrNumber::Hash(hash) => {
+                (provider.block_number_by_hash(hash)?.ok_or(BlockNotFound)?, hash)
+            }
+        };
+
+        let txs = provider.transactions_by_block(block_id)?.ok_or(BlockNotFound)?;
+        let receipts = provider.receipts_by_block(block_id)?.ok_or(BlockNotFound)?;
+        let traces = provider.transaction_executions_by_block(block_id)?.ok_or(BlockNotFound)?;
+
+        let transactions = txs
+            .iter()
+            .zip(receipts.iter().zip(traces.iter()))
+            .map(|(tx, (receipt, trace))| TxExecutionReport::new(tx.hash, receipt, trace))
+            .collect();
+
+        Ok(BlockExecutionReport::new(Some(block_hash), block_number, transactions))
+    }
+}
+
+#[async_trait]
+impl<EF: ExecutorFactory> KatanaApiServer for KatanaApi<EF> {
+    async fn get_block_execution_report(
+        &self,
+        block_id: BlockIdOrTag,
+    ) -> RpcResult<BlockExecutionReport> {
+        Ok(self.on_io_blocking_task(move |this| this.block_execution_report(block_id)).await?)
+    }
+}
@@ -4,6 +4,8 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
 use std::net::SocketAddr;
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use jsonrpsee::core::TEN_MB_SIZE_BYTES;
@@ -15,18 +17,34 @@ use tracing::info;
 #[cfg(feature = "cartridge")]
 pub mod cartridge;
 
+pub mod auth;
+pub mod batch;
 pub mod cors;
 pub mod dev;
 pub mod health;
+#[cfg(unix)]
+pub mod ipc;
+pub mod katana;
+pub mod limits;
 pub mod metrics;
+pub mod prefix;
+pub mod replication;
 pub mod saya;
 pub mod starknet;
 pub mod torii;
 mod utils;
+pub mod version;
 
+use auth::Auth;
+use batch::{BatchLayer, DEFAULT_BATCH_CONCURRENCY};
 use cors::Cors;
 use health::HealthCheck;
+#[cfg(unix)]
+use ipc::{IpcServer, IpcServerHandle};
+use limits::RequestLimitLayer;
 use metrics::RpcServerMetrics;
+use prefix::PathPrefixLayer;
+use version::RpcVersionLayer;
 
 /// The default maximum number of concurrent RPC connections.
 pub const DEFAULT_RPC_MAX_CONNECTIONS: u32 = 100;
@@ -40,6 +58,9 @@ pub enum Error {
     #[error(transparent)]
     Jsonrpsee(#[from] jsonrpsee::core::Error),
 
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
     #[error("RPC server has already been stopped")]
     AlreadyStopped,
 }
@@ -51,16 +72,35 @@ pub struct RpcServerHandle {
     addr: SocketAddr,
     /// The handle to the spawned [`jsonrpsee::server::Server`].
     handle: ServerHandle,
+    /// The address and the handle of the WebSocket server, if it's served separately.
+    ws: Option<(SocketAddr, ServerHandle)>,
+    /// The handle to the IPC server, if it's enabled.
+    #[cfg(unix)]
+    ipc: Option<IpcServerHandle>,
 }
 
 impl RpcServerHandle {
     /// Tell the server to stop without waiting for the server to stop.
     pub fn stop(&self) -> Result<(), Error> {
+        #[cfg(unix)]
+        if let Some(ipc) = &self.ipc {
+            ipc.stop();
+        }
+        if let Some((_, handle)) = &self.ws {
+            handle.stop().map_err(|_| Error::AlreadyStopped)?;
+        }
         self.handle.stop().map_err(|_| Error::AlreadyStopped)
     }
 
     /// Wait until the server has stopped.
     pub async fn stopped(self) {
+        #[cfg(unix)]
+        if let Some(ipc) = self.ipc {
+            ipc.stopped().await;
+        }
+        if let Some((_, handle)) = self.ws {
+            handle.stopped().await;
+        }
         self.handle.stopped().await
     }
 
@@ -68,29 +108,59 @@ impl RpcServerHandle {
     pub fn addr(&self) -> &SocketAddr {
         &self.addr
     }
+
+    /// Returns the socket address the WebSocket server is listening on, if it's served separately
+    /// from the HTTP server.
+    pub fn ws_addr(&self) -> Option<&SocketAddr> {
+        self.ws.as_ref().map(|(addr, _)| addr)
+    }
+
+    /// Returns the path of the socket the IPC server is listening on, if it's enabled.
+    #[cfg(unix)]
+    pub fn ipc_path(&self) -> Option<&Path> {
+        self.ipc.as_ref().map(|ipc| ipc.path())
+    }
 }
 
 #[derive(Debug)]
 pub struct RpcServer {
     metrics: bool,
     cors: Option<Cors>,
+    auth: Option<Auth>,
     health_check: bool,
     module: RpcModule<()>,
     max_connections: u32,
     max_request_body_size: u32,
     max_response_body_size: u32,
+    max_concurrent_requests: Option<u32>,
+    rate_limit: Option<u32>,
+    max_batch_size: Option<u32>,
+    batch_concurrency: u32,
+    path_prefix: Option<String>,
+    ws_addr: Option<SocketAddr>,
+    #[cfg(unix)]
+    ipc_path: Option<PathBuf>,
 }
 
 impl RpcServer {
     pub fn new() -> Self {
         Self {
             cors: None,
+            auth: None,
             metrics: false,
             health_check: false,
             module: RpcModule::new(()),
             max_connections: 100,
             max_request_body_size: TEN_MB_SIZE_BYTES,
             max_response_body_size: TEN_MB_SIZE_BYTES,
+            max_concurrent_requests: None,
+            rate_limit: None,
+            max_batch_size: None,
+            batch_concurrency: DEFAULT_BATCH_CONCURRENCY,
+            path_prefix: None,
+            ws_addr: None,
+            #[cfg(unix)]
+            ipc_path: None,
         }
     }
 
@@ -112,6 +182,38 @@ impl RpcServer {
         self
     }
 
+    /// Set the maximum number of requests processed concurrently, across all connections. Requests
+    /// above the limit are rejected. Unlimited by default.
+    pub fn max_concurrent_requests(mut self, max: u32) -> Self {
+        self.max_concurrent_requests = Some(max);
+        self
+    }
+
+    /// Set the maximum number of requests per second allowed from a single client. Requests above
+    /// the limit are rejected. Unlimited by default.
+    ///
+    /// See [`limits`] for how clients are identified.
+    pub fn rate_limit(mut self, per_second: u32) -> Self {
+        self.rate_limit = Some(per_second);
+        self
+    }
+
+    /// Set the maximum number of calls of a batch request. Larger batches are rejected. Unlimited
+    /// by default.
+    pub fn max_batch_size(mut self, max: u32) -> Self {
+        self.max_batch_size = Some(max);
+        self
+    }
+
+    /// Set the maximum number of calls of a batch request that are executed concurrently. Default
+    /// is 16.
+    ///
+    /// See [`batch`] for how batch requests are processed.
+    pub fn batch_concurrency(mut self, max: u32) -> Self {
+        self.batch_concurrency = max;
+        self
+    }
+
     /// Collect metrics about the RPC server.
     ///
     /// See top level module of [`crate::metrics`] to see what metrics are collected.
@@ -126,11 +228,41 @@ impl RpcServer {
         self
     }
 
+    /// Serves the server under a path prefix, eg when it's behind a reverse proxy.
+    ///
+    /// See [`prefix`] for how the prefix is handled.
+    pub fn path_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.path_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Serves the WebSocket connections on a separate address. By default, both the HTTP requests
+    /// and the WebSocket connections are served on the address the server is started on.
+    pub fn ws(mut self, addr: SocketAddr) -> Self {
+        self.ws_addr = Some(addr);
+        self
+    }
+
+    /// Also serves the RPC methods over a Unix domain socket at `path`.
+    ///
+    /// See [`ipc`] for how the requests are exchanged over the socket.
+    #[cfg(unix)]
+    pub fn ipc(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ipc_path = Some(path.into());
+        self
+    }
+
     pub fn cors(mut self, cors: Cors) -> Self {
         self.cors = Some(cors);
         self
     }
 
+    /// Requires an API key for calling the methods protected by `auth`.
+    pub fn auth(mut self, auth: Auth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
     pub fn module(mut self, module: RpcModule<()>) -> Self {
         self.module = module;
         self
@@ -146,33 +278,107 @@ impl RpcServer {
             None
         };
 
+        let limits = if self.max_concurrent_requests.is_some() || self.rate_limit.is_some() {
+            let mut layer = RequestLimitLayer::new();
+            if let Some(max) = self.max_concurrent_requests {
+                layer = layer.max_concurrent_requests(max);
+            }
+            if let Some(per_second) = self.rate_limit {
+                layer = layer.rate_limit(per_second);
+            }
+            Some(layer)
+        } else {
+            None
+        };
+
+        let mut batch = BatchLayer::new()
+            .concurrency(self.batch_concurrency)
+            .max_body_size(self.max_request_body_size);
+        if let Some(max) = self.max_batch_size {
+            batch = batch.max_size(max);
+        }
+
+        let auth = self.auth.clone().map(|auth| auth.max_body_size(self.max_request_body_size));
+
+        // the calls of a batch go through the middlewares below the batch layer individually
+        let prefix = self.path_prefix.as_ref().map(PathPrefixLayer::new);
+
         let middleware = ServiceBuilder::new()
             .option_layer(self.cors.clone())
+            .option_layer(prefix)
+            .layer(batch)
+            .option_layer(limits)
+            .option_layer(auth)
             .option_layer(health_check_proxy)
+            .layer(RpcVersionLayer::new())
             .timeout(Duration::from_secs(20));
 
-        let builder = ServerBuilder::new()
-            .set_middleware(middleware)
-            .set_host_filtering(AllowHosts::Any)
-            .max_connections(self.max_connections)
-            .max_request_body_size(self.max_request_body_size)
-            .max_response_body_size(self.max_response_body_size);
+        let builder = || {
+            ServerBuilder::new()
+                .set_middleware(middleware.clone())
+                .set_host_filtering(AllowHosts::Any)
+                .max_connections(self.max_connections)
+                .max_request_body_size(self.max_request_body_size)
+                .max_response_body_size(self.max_response_body_size)
+        };
+
+        // only serve the HTTP requests on `addr` if the WebSocket connections are served apart
+        let http_builder = if self.ws_addr.is_some() { builder().http_only() } else { builder() };
+
+        // the middlewares only apply to the HTTP and WebSocket transports
+        #[cfg(unix)]
+        let ipc = match &self.ipc_path {
+            Some(path) => Some(
+                IpcServer::new(path)
+                    .max_request_size(self.max_request_body_size)
+                    .start(modules.clone())?,
+            ),
+            None => None,
+        };
 
         let handle = if self.metrics {
             let logger = RpcServerMetrics::new(&modules);
-            let server = builder.set_logger(logger).build(addr).await?;
+            let server = http_builder.set_logger(logger.clone()).build(addr).await?;
 
             let addr = server.local_addr()?;
-            let handle = server.start(modules)?;
-
-            RpcServerHandle { addr, handle }
+            let handle = server.start(modules.clone())?;
+
+            let ws = match self.ws_addr {
+                Some(ws_addr) => {
+                    let server = builder().ws_only().set_logger(logger).build(ws_addr).await?;
+                    Some((server.local_addr()?, server.start(modules)?))
+                }
+                None => None,
+            };
+
+            RpcServerHandle {
+                addr,
+                handle,
+                ws,
+                #[cfg(unix)]
+                ipc,
+            }
         } else {
-            let server = builder.build(addr).await?;
+            let server = http_builder.build(addr).await?;
 
             let addr = server.local_addr()?;
-            let handle = server.start(modules)?;
-
-            RpcServerHandle { addr, handle }
+            let handle = server.start(modules.clone())?;
+
+            let ws = match self.ws_addr {
+                Some(ws_addr) => {
+                    let server = builder().ws_only().build(ws_addr).await?;
+                    Some((server.local_addr()?, server.start(modules)?))
+                }
+                None => None,
+            };
+
+            RpcServerHandle {
+                addr,
+                handle,
+                ws,
+                #[cfg(unix)]
+                ipc,
+            }
         };
 
         // The socket address that we log out must be from the RPC handle, in the case that the
@@ -180,6 +386,9 @@ impl RpcServer {
         // a free port during the call to `ServerBuilder::build(addr)`.
 
         info!(target: "rpc", addr = %handle.addr, "RPC server started.");
+        if let Some(addr) = handle.ws_addr() {
+            info!(target: "rpc", %addr, "RPC WebSocket server started.");
+        }
 
         Ok(handle)
     }
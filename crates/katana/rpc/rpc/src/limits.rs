@@ -0,0 +1,220 @@
+//! Limits on the requests served by the RPC server.
+//!
+//! The [`RequestLimitLayer`] rejects requests - before they are parsed - once the server is already
+//! processing the maximum number of concurrent requests, or once a client has exceeded its request
+//! rate. This protects publicly exposed nodes from clients flooding them with requests.
+//!
+//! Clients are identified by their IP address as reported by the `X-Forwarded-For` or `X-Real-IP`
+//! headers, which are expected to be set by a reverse proxy in front of the node. The RPC server
+//! doesn't expose the address of the peer to its middlewares, so requests without these headers
+//! are all accounted to the same client.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures::future::{self, BoxFuture, Either, Ready};
+use futures::FutureExt;
+use hyper::{Body, Request, Response, StatusCode};
+use parking_lot::Mutex;
+use tokio::sync::Semaphore;
+use tower::{Layer, Service};
+
+/// The number of tracked clients above which the idle ones are forgotten.
+const MAX_IDLE_CLIENTS: usize = 1024;
+
+/// Layer that limits the requests served by the RPC server. See the [module](self) level docs for
+/// more details.
+#[derive(Debug, Clone, Default)]
+pub struct RequestLimitLayer {
+    concurrency: Option<Arc<Semaphore>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+impl RequestLimitLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rejects requests while `max` requests are being processed, across all clients.
+    pub fn max_concurrent_requests(mut self, max: u32) -> Self {
+        self.concurrency = Some(Arc::new(Semaphore::new(max as usize)));
+        self
+    }
+
+    /// Rejects the requests of a client that sends more than `per_second` requests per second.
+    pub fn rate_limit(mut self, per_second: u32) -> Self {
+        self.rate_limiter = Some(Arc::new(RateLimiter::new(per_second)));
+        self
+    }
+}
+
+impl<S> Layer<S> for RequestLimitLayer {
+    type Service = RequestLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestLimitService {
+            inner,
+            concurrency: self.concurrency.clone(),
+            rate_limiter: self.rate_limiter.clone(),
+        }
+    }
+}
+
+/// Service created by [`RequestLimitLayer`].
+#[derive(Debug, Clone)]
+pub struct RequestLimitService<S> {
+    inner: S,
+    concurrency: Option<Arc<Semaphore>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+impl<S> Service<Request<Body>> for RequestLimitService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Either<
+        BoxFuture<'static, Result<Self::Response, Self::Error>>,
+        Ready<Result<Self::Response, Self::Error>>,
+    >;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        if let Some(limiter) = &self.rate_limiter {
+            if !limiter.check(client_ip(&req), Instant::now()) {
+                let res = rejection(StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded");
+                return Either::Right(future::ready(Ok(res)));
+            }
+        }
+
+        let permit = match &self.concurrency {
+            Some(semaphore) => match semaphore.clone().try_acquire_owned() {
+                Ok(permit) => Some(permit),
+                Err(_) => {
+                    let res =
+                        rejection(StatusCode::SERVICE_UNAVAILABLE, "Too many concurrent requests");
+                    return Either::Right(future::ready(Ok(res)));
+                }
+            },
+            None => None,
+        };
+
+        // the permit is held until the response is ready
+        let fut = self.inner.call(req).map(move |res| {
+            drop(permit);
+            res
+        });
+
+        Either::Left(fut.boxed())
+    }
+}
+
+/// Per-client token bucket rate limiter, where each bucket holds up to a second worth of requests.
+#[derive(Debug)]
+struct RateLimiter {
+    per_second: u32,
+    clients: Mutex<HashMap<Option<IpAddr>, Bucket>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    fn new(per_second: u32) -> Self {
+        Self { per_second, clients: Mutex::new(HashMap::new()) }
+    }
+
+    /// Returns `true` if the `client` is allowed to send a request at `now`.
+    fn check(&self, client: Option<IpAddr>, now: Instant) -> bool {
+        let capacity = self.per_second as f64;
+        let mut clients = self.clients.lock();
+
+        // a bucket that hasn't been used for a second is full again, so forgetting it is the same
+        // as keeping it around.
+        if clients.len() >= MAX_IDLE_CLIENTS && !clients.contains_key(&client) {
+            clients.retain(|_, b| now.duration_since(b.last_refill) < Duration::from_secs(1));
+        }
+
+        let bucket = clients.entry(client).or_insert(Bucket { tokens: capacity, last_refill: now });
+
+        let elapsed = now.saturating_duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * capacity).min(capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Returns the IP address of the client that sent the request, as reported by a reverse proxy.
+fn client_ip(req: &Request<Body>) -> Option<IpAddr> {
+    let headers = req.headers();
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+
+    // the left-most address is the one of the original client
+    let forwarded = header("x-forwarded-for").and_then(|v| v.split(',').next());
+    forwarded.or_else(|| header("x-real-ip")).and_then(|ip| ip.trim().parse().ok())
+}
+
+fn rejection(status: StatusCode, message: &'static str) -> Response<Body> {
+    let mut res = Response::new(Body::from(message));
+    *res.status_mut() = status;
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limit_per_client() {
+        let limiter = RateLimiter::new(2);
+        let now = Instant::now();
+
+        let alice = Some("10.0.0.1".parse().unwrap());
+        let bob = Some("10.0.0.2".parse().unwrap());
+
+        assert!(limiter.check(alice, now));
+        assert!(limiter.check(alice, now));
+        assert!(!limiter.check(alice, now));
+
+        // other clients have their own budget
+        assert!(limiter.check(bob, now));
+
+        // the budget is refilled over time
+        assert!(!limiter.check(alice, now + Duration::from_millis(100)));
+        assert!(limiter.check(alice, now + Duration::from_millis(600)));
+        assert!(!limiter.check(alice, now + Duration::from_millis(600)));
+    }
+
+    #[test]
+    fn client_ip_from_headers() {
+        let req = Request::builder()
+            .header("x-forwarded-for", "203.0.113.7, 10.0.0.1")
+            .header("x-real-ip", "10.0.0.1")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(client_ip(&req), Some("203.0.113.7".parse().unwrap()));
+
+        let req = Request::builder().header("x-real-ip", "10.0.0.1").body(Body::empty()).unwrap();
+        assert_eq!(client_ip(&req), Some("10.0.0.1".parse().unwrap()));
+
+        let req = Request::builder().body(Body::empty()).unwrap();
+        assert_eq!(client_ip(&req), None);
+    }
+}
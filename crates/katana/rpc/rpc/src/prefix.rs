@@ -0,0 +1,107 @@
+//! Serving the RPC server under a path prefix.
+//!
+//! A node exposed behind a reverse proxy is often served under a path of the proxy (eg
+//! `https://example.com/katana`), with the proxy forwarding the requests with their full path. The
+//! [`PathPrefixLayer`] strips the configured prefix from the path of the requests, so that
+//! `/katana`, `/katana/rpc/v0_7` and `/katana/health` are served as `/`, `/rpc/v0_7` and `/health`.
+//!
+//! Requests whose path doesn't start with the prefix are served as is, so the node remains
+//! reachable directly.
+
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use http::uri::{PathAndQuery, Uri};
+use hyper::{Body, Request, Response};
+use tower::{Layer, Service};
+
+/// Layer that strips a path prefix from the requests. See the [module](self) level docs for more
+/// details.
+#[derive(Debug, Clone)]
+pub struct PathPrefixLayer {
+    prefix: Arc<str>,
+}
+
+impl PathPrefixLayer {
+    /// Creates a new layer stripping `prefix`, eg `/katana`. The leading and trailing slashes of
+    /// the prefix are optional.
+    pub fn new(prefix: impl AsRef<str>) -> Self {
+        let prefix = prefix.as_ref().trim_matches('/');
+        Self { prefix: Arc::from(format!("/{prefix}")) }
+    }
+}
+
+impl<S> Layer<S> for PathPrefixLayer {
+    type Service = PathPrefixService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        PathPrefixService { inner, prefix: self.prefix.clone() }
+    }
+}
+
+/// Service created by [`PathPrefixLayer`].
+#[derive(Debug, Clone)]
+pub struct PathPrefixService<S> {
+    inner: S,
+    prefix: Arc<str>,
+}
+
+impl<S> Service<Request<Body>> for PathPrefixService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
+        if let Some(uri) = strip_prefix(req.uri(), &self.prefix) {
+            *req.uri_mut() = uri;
+        }
+        self.inner.call(req)
+    }
+}
+
+/// Returns the `uri` without the path `prefix`, keeping its query (if any), or `None` if its path
+/// doesn't start with the prefix.
+fn strip_prefix(uri: &Uri, prefix: &str) -> Option<Uri> {
+    let path = match uri.path().strip_prefix(prefix)? {
+        "" => "/",
+        path if path.starts_with('/') => path,
+        // only whole path segments are stripped, eg `/katanas` is not under `/katana`
+        _ => return None,
+    };
+
+    let path_and_query = match uri.query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path.to_string(),
+    };
+
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(PathAndQuery::try_from(path_and_query).expect("valid path"));
+    Some(Uri::from_parts(parts).expect("valid uri"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_path_prefix() {
+        let prefix = PathPrefixLayer::new("katana/").prefix;
+        assert_eq!(&*prefix, "/katana");
+
+        let uri = Uri::from_static("http://localhost:5050/katana");
+        assert_eq!(strip_prefix(&uri, &prefix), Some(Uri::from_static("http://localhost:5050/")));
+
+        let uri = Uri::from_static("/katana/rpc/v0_7?foo=bar");
+        assert_eq!(strip_prefix(&uri, &prefix), Some(Uri::from_static("/rpc/v0_7?foo=bar")));
+
+        assert_eq!(strip_prefix(&Uri::from_static("/health"), &prefix), None);
+        assert_eq!(strip_prefix(&Uri::from_static("/katanas"), &prefix), None);
+    }
+}
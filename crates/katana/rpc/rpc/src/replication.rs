@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use jsonrpsee::core::{async_trait, RpcResult};
+use katana_core::backend::Backend;
+use katana_executor::ExecutorFactory;
+use katana_primitives::block::{BlockHashOrNumber, BlockNumber};
+use katana_provider::traits::block::{
+    BlockHashProvider, BlockNumberProvider, BlockProvider, BlockStatusProvider,
+};
+use katana_provider::traits::contract::ContractClassProvider;
+use katana_provider::traits::state::StateFactoryProvider;
+use katana_provider::traits::state_update::StateUpdateProvider;
+use katana_provider::traits::transaction::{ReceiptProvider, TransactionTraceProvider};
+use katana_rpc_api::replication::ReplicationApiServer;
+use katana_rpc_types::error::starknet::StarknetApiError;
+use katana_rpc_types::replication::{ReplicatedBlock, MAX_REPLICATED_BLOCKS_PER_REQUEST};
+use katana_tasks::TokioTaskSpawner;
+
+#[allow(missing_debug_implementations)]
+pub struct ReplicationApi<EF: ExecutorFactory> {
+    backend: Arc<Backend<EF>>,
+}
+
+impl<EF: ExecutorFactory> Clone for ReplicationApi<EF> {
+    fn clone(&self) -> Self {
+        Self { backend: Arc::clone(&self.backend) }
+    }
+}
+
+impl<EF: ExecutorFactory> ReplicationApi<EF> {
+    pub fn new(backend: Arc<Backend<EF>>) -> Self {
+        Self { backend }
+    }
+
+    async fn on_io_blocking_task<F, T>(&self, func: F) -> T
+    where
+        F: FnOnce(Self) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let this = self.clone();
+        TokioTaskSpawner::new().unwrap().spawn_blocking(move || func(this)).await.unwrap()
+    }
+
+    fn blocks(
+        &self,
+        from: BlockNumber,
+        count: u64,
+    ) -> Result<Vec<ReplicatedBlock>, StarknetApiError> {
+        let provider = self.backend.blockchain.provider();
+
+        let latest = provider.latest_number()?;
+        let count = count.min(MAX_REPLICATED_BLOCKS_PER_REQUEST);
+        let to = latest.min(from.saturating_add(count).saturating_sub(1));
+
+        if count == 0 || from > latest {
+            return Ok(Vec::new());
+        }
+
+        // the declared classes are never removed, so they can all be read from the latest state
+        let state = provider.latest()?;
+        let mut blocks = Vec::with_capacity((to - from + 1) as usize);
+
+        for num in from..=to {
+            let id = BlockHashOrNumber::Num(num);
+            // the data can only be missing if it has been pruned
+            let missing = || StarknetApiError::UnexpectedError {
+                reason: format!("missing data for block {num}"),
+            };
+
+            let hash = provider.block_hash_by_num(num)?.ok_or(StarknetApiError::BlockNotFound)?;
+            let block = provider.block(id)?.ok_or_else(missing)?;
+            let status = provider.block_status(id)?.ok_or_else(missing)?;
+            let state_updates = provider.state_update(id)?.ok_or_else(missing)?;
+            let receipts = provider.receipts_by_block(id)?.ok_or_else(missing)?;
+            let traces = provider.transaction_executions_by_block(id)?.ok_or_else(missing)?;
+
+            let mut classes = BTreeMap::new();
+            let declared = state_updates.declared_classes.keys();
+            let deprecated = state_updates.deprecated_declared_classes.iter();
+
+            for class_hash in declared.chain(deprecated) {
+                let class = state.class(*class_hash)?.ok_or_else(missing)?;
+                classes.insert(*class_hash, class);
+            }
+
+            blocks.push(ReplicatedBlock {
+                hash,
+                status,
+                classes,
+                receipts,
+                traces,
+                state_updates,
+                body: block.body,
+                header: block.header,
+            });
+        }
+
+        Ok(blocks)
+    }
+}
+
+#[async_trait]
+impl<EF: ExecutorFactory> ReplicationApiServer for ReplicationApi<EF> {
+    async fn block_number(&self) -> RpcResult<BlockNumber> {
+        let number = self
+            .on_io_blocking_task(move |this| this.backend.blockchain.provider().latest_number())
+            .await
+            .map_err(StarknetApiError::from)?;
+        Ok(number)
+    }
+
+    async fn get_blocks(&self, from: BlockNumber, count: u64) -> RpcResult<Vec<ReplicatedBlock>> {
+        Ok(self.on_io_blocking_task(move |this| this.blocks(from, count)).await?)
+    }
+}
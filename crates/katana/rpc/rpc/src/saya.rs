@@ -5,8 +5,9 @@ use katana_core::backend::Backend;
 use katana_core::service::block_producer::{BlockProducer, BlockProducerMode, PendingExecutor};
 use katana_executor::ExecutorFactory;
 use katana_primitives::block::{BlockIdOrTag, BlockTag};
+use katana_primitives::trace::BlockExecutionArtifacts;
 use katana_provider::error::ProviderError;
-use katana_provider::traits::block::{BlockIdReader, BlockProvider};
+use katana_provider::traits::block::{BlockArtifactsProvider, BlockIdReader, BlockProvider};
 use katana_provider::traits::transaction::{TransactionTraceProvider, TransactionsProviderExt};
 use katana_rpc_api::saya::SayaApiServer;
 use katana_rpc_types::error::saya::SayaApiError;
@@ -119,4 +120,31 @@ impl<EF: ExecutorFactory> SayaApiServer for SayaApi<EF> {
         })
         .await
     }
+
+    async fn block_execution_artifacts(
+        &self,
+        block_id: BlockIdOrTag,
+    ) -> RpcResult<BlockExecutionArtifacts> {
+        self.on_io_blocking_task(move |this| {
+            let provider = this.backend.blockchain.provider();
+
+            // the SNOS inputs are only recorded once the block is mined
+            if matches!(block_id, BlockIdOrTag::Tag(BlockTag::Pending)) {
+                return Err(SayaApiError::ArtifactsNotFound.into());
+            }
+
+            let number = provider
+                .convert_block_id(block_id)
+                .map_err(SayaApiError::from)?
+                .ok_or(SayaApiError::BlockNotFound)?;
+
+            let artifacts = provider
+                .block_execution_artifacts(number.into())
+                .map_err(SayaApiError::from)?
+                .ok_or(SayaApiError::ArtifactsNotFound)?;
+
+            Ok(artifacts)
+        })
+        .await
+    }
 }
@@ -14,9 +14,10 @@ use katana_primitives::class::ClassHash;
 use katana_primitives::contract::{ContractAddress, Nonce, StorageKey, StorageValue};
 use katana_primitives::da::L1DataAvailabilityMode;
 use katana_primitives::env::BlockEnv;
-use katana_primitives::event::MaybeForkedContinuationToken;
+use katana_primitives::event::{ContinuationToken, EventFilter, MaybeForkedContinuationToken};
+use katana_primitives::receipt::Receipt;
+use katana_primitives::state::StateUpdates;
 use katana_primitives::transaction::{ExecutableTxWithHash, TxHash, TxWithHash};
-use katana_primitives::version::CURRENT_STARKNET_VERSION;
 use katana_primitives::Felt;
 use katana_provider::error::ProviderError;
 use katana_provider::traits::block::{BlockHashProvider, BlockIdReader, BlockNumberProvider};
@@ -34,8 +35,8 @@ use katana_rpc_types::class::RpcContractClass;
 use katana_rpc_types::error::starknet::StarknetApiError;
 use katana_rpc_types::event::{EventFilterWithPage, EventsPage};
 use katana_rpc_types::receipt::{ReceiptBlock, TxReceiptWithBlockInfo};
-use katana_rpc_types::state_update::MaybePendingStateUpdate;
-use katana_rpc_types::transaction::Tx;
+use katana_rpc_types::state_update::{MaybePendingStateUpdate, StateDiff};
+use katana_rpc_types::transaction::{Tx, TxFinalityStatus, TxStatus};
 use katana_rpc_types::trie::{
     ClassesProof, ContractLeafData, ContractStorageKeys, ContractStorageProofs, ContractsProof,
     GetStorageProofResponse, GlobalRoots, Nodes,
@@ -44,15 +45,16 @@ use katana_rpc_types::FeeEstimate;
 use katana_rpc_types_builder::ReceiptBuilder;
 use katana_tasks::{BlockingTaskPool, TokioTaskSpawner};
 use starknet::core::types::{
-    PriceUnit, ResultPageRequest, TransactionExecutionStatus, TransactionStatus,
+    PendingStateUpdate, PriceUnit, ResultPageRequest, TransactionExecutionStatus,
 };
 
 use crate::utils;
-use crate::utils::events::{Cursor, EventBlockId};
+use crate::utils::events::EventBlockId;
 
 mod config;
 pub mod forking;
 mod read;
+mod subscription;
 mod trace;
 mod write;
 
@@ -65,8 +67,9 @@ type StarknetApiResult<T> = Result<T, StarknetApiError>;
 ///
 /// This struct implements all the JSON-RPC traits required to serve the Starknet API (ie,
 /// [read](katana_rpc_api::starknet::StarknetApi),
-/// [write](katana_rpc_api::starknet::StarknetWriteApi), and
-/// [trace](katana_rpc_api::starknet::StarknetTraceApi) APIs.
+/// [write](katana_rpc_api::starknet::StarknetWriteApi),
+/// [trace](katana_rpc_api::starknet::StarknetTraceApi), and
+/// [subscription](katana_rpc_api::starknet::StarknetSubscriptionApi) APIs.
 #[allow(missing_debug_implementations)]
 pub struct StarknetApi<EF>
 where
@@ -234,7 +237,7 @@ where
                 else {
                     let num = provider.latest_number()?;
                     let mut env = provider.block_env_at(num.into())?.expect("missing block env");
-                    self.inner.backend.update_block_env(&mut env);
+                    self.inner.backend.preview_block_env(&mut env);
                     Some(env)
                 }
             }
@@ -251,6 +254,23 @@ where
         env.ok_or(StarknetApiError::BlockNotFound)
     }
 
+    /// Returns the header of the block that is currently being built by the pending executor.
+    fn pending_header(&self, executor: &PendingExecutor) -> StarknetApiResult<PartialHeader> {
+        let block_env = executor.read().block_env();
+        let parent_hash = self.inner.backend.blockchain.provider().latest_hash()?;
+
+        Ok(PartialHeader {
+            parent_hash,
+            number: block_env.number,
+            timestamp: block_env.timestamp,
+            l1_da_mode: L1DataAvailabilityMode::Calldata,
+            l1_gas_prices: block_env.l1_gas_prices,
+            l1_data_gas_prices: block_env.l1_data_gas_prices,
+            sequencer_address: block_env.sequencer_address,
+            protocol_version: self.inner.backend.chain_spec.protocol_version(),
+        })
+    }
+
     fn block_hash_and_number(&self) -> StarknetApiResult<(BlockHash, BlockNumber)> {
         let provider = self.inner.backend.blockchain.provider();
         let hash = provider.latest_hash()?;
@@ -523,7 +543,7 @@ where
         }
     }
 
-    async fn transaction_status(&self, hash: TxHash) -> StarknetApiResult<TransactionStatus> {
+    async fn transaction_status(&self, hash: TxHash) -> StarknetApiResult<TxStatus> {
         let status = self
             .on_io_blocking_task(move |this| {
                 let provider = this.inner.backend.blockchain.provider();
@@ -539,22 +559,12 @@ where
                         });
                     };
 
-                    let exec_status = if receipt.is_reverted() {
-                        TransactionExecutionStatus::Reverted
-                    } else {
-                        TransactionExecutionStatus::Succeeded
+                    let finality_status = match status {
+                        FinalityStatus::AcceptedOnL1 => TxFinalityStatus::AcceptedOnL1,
+                        FinalityStatus::AcceptedOnL2 => TxFinalityStatus::AcceptedOnL2,
                     };
 
-                    let status = match status {
-                        FinalityStatus::AcceptedOnL1 => {
-                            TransactionStatus::AcceptedOnL1(exec_status)
-                        }
-                        FinalityStatus::AcceptedOnL2 => {
-                            TransactionStatus::AcceptedOnL2(exec_status)
-                        }
-                    };
-
-                    return Ok(Some(status));
+                    return Ok(Some(executed_tx_status(finality_status, &receipt)));
                 }
 
                 // seach in the pending block if the transaction is not found
@@ -566,19 +576,10 @@ where
                         .find(|(tx, _)| tx.hash == hash)
                         .ok_or(StarknetApiError::TxnHashNotFound)?;
 
-                    // TODO: should impl From<ExecutionResult> for TransactionStatus
                     let status = match res {
-                        ExecutionResult::Failed { .. } => TransactionStatus::Rejected,
+                        ExecutionResult::Failed { error } => TxStatus::rejected(error.to_string()),
                         ExecutionResult::Success { receipt, .. } => {
-                            if receipt.is_reverted() {
-                                TransactionStatus::AcceptedOnL2(
-                                    TransactionExecutionStatus::Reverted,
-                                )
-                            } else {
-                                TransactionStatus::AcceptedOnL2(
-                                    TransactionExecutionStatus::Succeeded,
-                                )
-                            }
+                            executed_tx_status(TxFinalityStatus::AcceptedOnL2, receipt)
                         }
                     };
 
@@ -593,10 +594,10 @@ where
         if let Some(status) = status {
             Ok(status)
         } else if let Some(client) = &self.inner.forked_client {
-            Ok(client.get_transaction_status(hash).await?)
+            Ok(client.get_transaction_status(hash).await?.into())
         } else {
             let _ = self.inner.pool.get(hash).ok_or(StarknetApiError::TxnHashNotFound)?;
-            Ok(TransactionStatus::Received)
+            Ok(TxStatus::received())
         }
     }
 
@@ -610,22 +611,7 @@ where
 
                 if BlockIdOrTag::Tag(BlockTag::Pending) == block_id {
                     if let Some(executor) = this.pending_executor() {
-                        let block_env = executor.read().block_env();
-                        let latest_hash = provider.latest_hash().map_err(StarknetApiError::from)?;
-
-                        let l1_gas_prices = block_env.l1_gas_prices.clone();
-                        let l1_data_gas_prices = block_env.l1_data_gas_prices.clone();
-
-                        let header = PartialHeader {
-                            l1_da_mode: L1DataAvailabilityMode::Calldata,
-                            l1_gas_prices,
-                            l1_data_gas_prices,
-                            number: block_env.number,
-                            parent_hash: latest_hash,
-                            timestamp: block_env.timestamp,
-                            sequencer_address: block_env.sequencer_address,
-                            protocol_version: CURRENT_STARKNET_VERSION,
-                        };
+                        let header = this.pending_header(&executor)?;
 
                         // TODO(kariy): create a method that can perform this filtering for us
                         // instead of doing it manually.
@@ -676,22 +662,7 @@ where
 
                 if BlockIdOrTag::Tag(BlockTag::Pending) == block_id {
                     if let Some(executor) = this.pending_executor() {
-                        let block_env = executor.read().block_env();
-                        let latest_hash = provider.latest_hash()?;
-
-                        let l1_gas_prices = block_env.l1_gas_prices.clone();
-                        let l1_data_gas_prices = block_env.l1_data_gas_prices.clone();
-
-                        let header = PartialHeader {
-                            l1_gas_prices,
-                            l1_data_gas_prices,
-                            number: block_env.number,
-                            parent_hash: latest_hash,
-                            timestamp: block_env.timestamp,
-                            l1_da_mode: L1DataAvailabilityMode::Calldata,
-                            sequencer_address: block_env.sequencer_address,
-                            protocol_version: CURRENT_STARKNET_VERSION,
-                        };
+                        let header = this.pending_header(&executor)?;
 
                         let receipts = executor
                             .read()
@@ -741,22 +712,7 @@ where
 
                 if BlockIdOrTag::Tag(BlockTag::Pending) == block_id {
                     if let Some(executor) = this.pending_executor() {
-                        let block_env = executor.read().block_env();
-                        let latest_hash = provider.latest_hash().map_err(StarknetApiError::from)?;
-
-                        let l1_gas_prices = block_env.l1_gas_prices.clone();
-                        let l1_data_gas_prices = block_env.l1_data_gas_prices.clone();
-
-                        let header = PartialHeader {
-                            l1_da_mode: L1DataAvailabilityMode::Calldata,
-                            l1_data_gas_prices,
-                            l1_gas_prices,
-                            number: block_env.number,
-                            parent_hash: latest_hash,
-                            timestamp: block_env.timestamp,
-                            protocol_version: CURRENT_STARKNET_VERSION,
-                            sequencer_address: block_env.sequencer_address,
-                        };
+                        let header = this.pending_header(&executor)?;
 
                         // TODO(kariy): create a method that can perform this filtering for us
                         // instead of doing it manually.
@@ -813,9 +769,15 @@ where
                         provider.latest_number().map(BlockHashOrNumber::Num)?
                     }
 
-                    BlockIdOrTag::Tag(BlockTag::Pending) => {
-                        return Err(StarknetApiError::BlockNotFound);
-                    }
+                    BlockIdOrTag::Tag(BlockTag::Pending) => match this.pending_executor() {
+                        Some(executor) => {
+                            let state_diff = pending_state_diff(&executor);
+                            let old_root = provider.latest()?.state_root()?;
+                            let update = PendingStateUpdate { old_root, state_diff };
+                            return Ok(Some(MaybePendingStateUpdate::Pending(update.into())));
+                        }
+                        None => provider.latest_number().map(BlockHashOrNumber::Num)?,
+                    },
                 };
 
                 let state_update =
@@ -902,7 +864,7 @@ where
 
         // reserved buffer to fill up with events to avoid reallocations
         let mut events = Vec::with_capacity(chunk_size as usize);
-        let filter = utils::events::Filter { address, keys: keys.clone() };
+        let filter = EventFilter { address, keys: keys.clone() };
 
         match (from, to) {
             (EventBlockId::Num(from), EventBlockId::Num(to)) => {
@@ -961,7 +923,7 @@ where
                     from
                 };
 
-                let cursor = continuation_token.and_then(|t| t.to_token().map(|t| t.into()));
+                let cursor = continuation_token.and_then(|t| t.to_token());
                 let block_range = from_after_forked_if_any..=to;
 
                 let cursor = utils::events::fetch_events_at_blocks(
@@ -973,7 +935,7 @@ where
                     &mut events,
                 )?;
 
-                let continuation_token = cursor.map(|c| c.into_rpc_cursor().to_string());
+                let continuation_token = cursor.map(|c| c.to_string());
                 let events_page = EventsPage { events, continuation_token };
 
                 Ok(events_page)
@@ -1035,7 +997,7 @@ where
                     from
                 };
 
-                let cursor = continuation_token.and_then(|t| t.to_token().map(|t| t.into()));
+                let cursor = continuation_token.and_then(|t| t.to_token());
                 let latest = provider.latest_number()?;
                 let block_range = from_after_forked_if_any..=latest;
 
@@ -1051,7 +1013,7 @@ where
                 // if the internal cursor is Some, meaning the buffer is full and we havent
                 // reached the latest block.
                 if let Some(c) = int_cursor {
-                    let continuation_token = Some(c.into_rpc_cursor().to_string());
+                    let continuation_token = Some(c.to_string());
                     return Ok(EventsPage { events, continuation_token });
                 }
 
@@ -1064,18 +1026,18 @@ where
                         &mut events,
                     )?;
 
-                    let continuation_token = Some(cursor.into_rpc_cursor().to_string());
+                    let continuation_token = Some(cursor.to_string());
                     Ok(EventsPage { events, continuation_token })
                 } else {
-                    let cursor = Cursor::new_block(latest + 1);
-                    let continuation_token = Some(cursor.into_rpc_cursor().to_string());
+                    let cursor = ContinuationToken { block_n: latest + 1, txn_n: 0, event_n: 0 };
+                    let continuation_token = Some(cursor.to_string());
                     Ok(EventsPage { events, continuation_token })
                 }
             }
 
             (EventBlockId::Pending, EventBlockId::Pending) => {
                 if let Some(executor) = self.pending_executor() {
-                    let cursor = continuation_token.and_then(|t| t.to_token().map(|t| t.into()));
+                    let cursor = continuation_token.and_then(|t| t.to_token());
                     let new_cursor = utils::events::fetch_pending_events(
                         &executor,
                         &filter,
@@ -1084,13 +1046,13 @@ where
                         &mut events,
                     )?;
 
-                    let continuation_token = Some(new_cursor.into_rpc_cursor().to_string());
+                    let continuation_token = Some(new_cursor.to_string());
                     Ok(EventsPage { events, continuation_token })
                 } else {
                     let latest = provider.latest_number()?;
-                    let new_cursor = Cursor::new_block(latest);
+                    let new_cursor = ContinuationToken { block_n: latest, txn_n: 0, event_n: 0 };
 
-                    let continuation_token = Some(new_cursor.into_rpc_cursor().to_string());
+                    let continuation_token = Some(new_cursor.to_string());
                     Ok(EventsPage { events, continuation_token })
                 }
             }
@@ -1238,3 +1200,27 @@ where
         Self { inner: Arc::clone(&self.inner) }
     }
 }
+
+/// Returns the status of a transaction that has been executed, along with its revert reason if
+/// the transaction was reverted.
+fn executed_tx_status(finality_status: TxFinalityStatus, receipt: &Receipt) -> TxStatus {
+    let (execution_status, failure_reason) = match receipt.revert_reason() {
+        Some(reason) => (TransactionExecutionStatus::Reverted, Some(reason.to_string())),
+        None => (TransactionExecutionStatus::Succeeded, None),
+    };
+
+    TxStatus { finality_status, execution_status: Some(execution_status), failure_reason }
+}
+
+/// Returns the state changes made by the transactions executed so far in the pending block.
+fn pending_state_diff(executor: &PendingExecutor) -> starknet::core::types::StateDiff {
+    let mut state_diff = StateUpdates::default();
+
+    for (_, result) in executor.read().transactions() {
+        if let Some(states) = result.states() {
+            state_diff.merge(states.clone());
+        }
+    }
+
+    StateDiff::from(state_diff).0
+}
@@ -24,10 +24,9 @@ use katana_rpc_types::event::{EventFilterWithPage, EventsPage};
 use katana_rpc_types::message::MsgFromL1;
 use katana_rpc_types::receipt::TxReceiptWithBlockInfo;
 use katana_rpc_types::state_update::MaybePendingStateUpdate;
-use katana_rpc_types::transaction::{BroadcastedTx, Tx};
+use katana_rpc_types::transaction::{BroadcastedTx, Tx, TxStatus};
 use katana_rpc_types::trie::{ContractStorageKeys, GetStorageProofResponse};
 use katana_rpc_types::{FeeEstimate, FeltAsHex, FunctionCall, SimulationFlagForEstimateFee};
-use starknet::core::types::TransactionStatus;
 
 use super::StarknetApi;
 #[cfg(feature = "cartridge")]
@@ -213,20 +212,33 @@ impl<EF: ExecutorFactory> StarknetApiServer for StarknetApi<EF> {
             .collect::<Result<Vec<_>, _>>()?;
 
         let skip_validate = simulation_flags.contains(&SimulationFlagForEstimateFee::SkipValidate);
+        let skip_fee_charge =
+            simulation_flags.contains(&SimulationFlagForEstimateFee::SkipFeeCharge);
 
         // If the node is run with transaction validation disabled, then we should not validate
         // transactions when estimating the fee even if the `SKIP_VALIDATE` flag is not set.
         let should_validate = !skip_validate
             && self.inner.backend.executor_factory.execution_flags().account_validation();
 
+        // Likewise, if the node is run with fee charging disabled, the fee shouldn't be charged
+        // even if the `SKIP_FEE_CHARGE` flag is not set. The fee is still computed when it's not
+        // charged, which allows estimating the fee for accounts without enough balance.
+        let should_charge_fee =
+            !skip_fee_charge && self.inner.backend.executor_factory.execution_flags().fee();
+
         // We don't care about the nonce when estimating the fee as the nonce value
         // doesn't affect transaction execution.
         //
         // This doesn't completely disregard the nonce as nonce < account nonce will
         // return an error. It only 'relaxes' the check for nonce >= account nonce.
+        let impersonated =
+            self.inner.backend.executor_factory.execution_flags().impersonated_accounts().clone();
+
         let flags = katana_executor::ExecutionFlags::new()
             .with_account_validation(should_validate)
-            .with_nonce_check(false);
+            .with_fee(should_charge_fee)
+            .with_nonce_check(false)
+            .with_impersonated_accounts(impersonated);
 
         // Hook the estimate fee to pre-deploy the controller contract
         // and enhance UX on the client side.
@@ -350,10 +362,7 @@ impl<EF: ExecutorFactory> StarknetApiServer for StarknetApi<EF> {
         .await
     }
 
-    async fn get_transaction_status(
-        &self,
-        transaction_hash: TxHash,
-    ) -> RpcResult<TransactionStatus> {
+    async fn get_transaction_status(&self, transaction_hash: TxHash) -> RpcResult<TxStatus> {
         Ok(self.transaction_status(transaction_hash).await?)
     }
 
@@ -0,0 +1,145 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use futures::{stream, Stream, StreamExt};
+use jsonrpsee::types::SubscriptionResult;
+use jsonrpsee::SubscriptionSink;
+use katana_core::backend::notification::NewBlock;
+use katana_core::backend::Backend;
+use katana_executor::ExecutorFactory;
+use katana_pool::TransactionPool;
+use katana_primitives::block::{BlockHashOrNumber, BlockNumber};
+use katana_primitives::event::EventFilter;
+use katana_primitives::receipt::ReceiptWithTxHash;
+use katana_primitives::{ContractAddress, Felt};
+use katana_provider::traits::block::{BlockHashProvider, BlockNumberProvider, HeaderProvider};
+use katana_provider::traits::transaction::{ReceiptProvider, TransactionProvider};
+use katana_provider::ProviderResult;
+use katana_rpc_api::starknet::StarknetSubscriptionApiServer;
+use katana_rpc_types::block::BlockHeader;
+use katana_rpc_types::event::EmittedEvent;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{error, warn};
+
+use super::StarknetApi;
+
+impl<EF: ExecutorFactory> StarknetApi<EF> {
+    /// Returns a stream of all the blocks that are mined from now on.
+    fn new_blocks_stream(&self) -> impl Stream<Item = Arc<NewBlock>> + Send + Unpin + 'static {
+        let backend = self.inner.backend.clone();
+        let receiver = backend.block_notifier.subscribe();
+        // the number of the next block to be yielded by the stream
+        let next = backend.blockchain.provider().latest_number().map_or(0, |n| n + 1);
+
+        let state = (receiver, next, VecDeque::new());
+        Box::pin(stream::unfold(state, move |(mut receiver, mut next, mut missed)| {
+            let backend = backend.clone();
+            async move {
+                loop {
+                    if let Some(block) = missed.pop_front() {
+                        return Some((block, (receiver, next, missed)));
+                    }
+
+                    match receiver.recv().await {
+                        // the block has already been read from storage after lagging behind
+                        Ok(block) if block.header.number < next => continue,
+                        Ok(block) => {
+                            next = block.header.number + 1;
+                            return Some((block, (receiver, next, missed)));
+                        }
+                        // the channel only buffers the most recent blocks, so the missed ones are
+                        // read from storage instead
+                        Err(RecvError::Lagged(skipped)) => {
+                            warn!(target: "rpc", %skipped, "Subscriber is lagging behind new blocks.");
+                            missed = match stored_blocks(&backend, next) {
+                                Ok(blocks) => blocks,
+                                Err(error) => {
+                                    error!(target: "rpc", %error, "Failed to read missed blocks.");
+                                    return None;
+                                }
+                            };
+                            next += missed.len() as u64;
+                        }
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            }
+        }))
+    }
+}
+
+/// Reads all the blocks from `from` up to the latest block from storage.
+fn stored_blocks<EF: ExecutorFactory>(
+    backend: &Backend<EF>,
+    from: BlockNumber,
+) -> ProviderResult<VecDeque<Arc<NewBlock>>> {
+    let provider = backend.blockchain.provider();
+    let mut blocks = VecDeque::new();
+
+    for number in from..=provider.latest_number()? {
+        let id = BlockHashOrNumber::Num(number);
+        let (Some(hash), Some(header), Some(txs), Some(receipts)) = (
+            provider.block_hash_by_num(number)?,
+            provider.header(id)?,
+            provider.transactions_by_block(id)?,
+            provider.receipts_by_block(id)?,
+        ) else {
+            break;
+        };
+
+        let receipts =
+            txs.iter().zip(receipts).map(|(tx, r)| ReceiptWithTxHash::new(tx.hash, r)).collect();
+        blocks.push_back(Arc::new(NewBlock { hash, header, receipts }));
+    }
+
+    Ok(blocks)
+}
+
+impl<EF: ExecutorFactory> StarknetSubscriptionApiServer for StarknetApi<EF> {
+    fn subscribe_new_heads(&self, sink: SubscriptionSink) -> SubscriptionResult {
+        let stream = self
+            .new_blocks_stream()
+            .map(|block| BlockHeader::new(block.hash, block.header.clone()));
+
+        tokio::spawn(async move { sink.pipe_from_stream(stream).await });
+        Ok(())
+    }
+
+    fn subscribe_events(
+        &self,
+        sink: SubscriptionSink,
+        from_address: Option<ContractAddress>,
+        keys: Option<Vec<Vec<Felt>>>,
+    ) -> SubscriptionResult {
+        let filter = EventFilter { address: from_address, keys };
+
+        let stream = self.new_blocks_stream().flat_map(move |block| {
+            let mut events = Vec::new();
+
+            for receipt in &block.receipts {
+                let emitted =
+                    receipt.events().iter().filter(|e| filter.matches(e)).map(|e| EmittedEvent {
+                        keys: e.keys.clone(),
+                        data: e.data.clone(),
+                        block_hash: Some(block.hash),
+                        block_number: Some(block.header.number),
+                        from_address: e.from_address.into(),
+                        transaction_hash: receipt.tx_hash,
+                    });
+
+                events.extend(emitted);
+            }
+
+            stream::iter(events)
+        });
+
+        tokio::spawn(async move { sink.pipe_from_stream(stream).await });
+        Ok(())
+    }
+
+    fn subscribe_pending_transactions(&self, sink: SubscriptionSink) -> SubscriptionResult {
+        let stream = self.inner.pool.add_listener();
+        tokio::spawn(async move { sink.pipe_from_stream(stream).await });
+        Ok(())
+    }
+}
@@ -2,12 +2,14 @@ use jsonrpsee::core::{async_trait, RpcResult};
 use katana_executor::{ExecutionResult, ExecutorFactory, ResultAndStates};
 use katana_primitives::block::{BlockHashOrNumber, BlockIdOrTag};
 use katana_primitives::fee::TxFeeInfo;
+use katana_primitives::state::StateUpdates;
 use katana_primitives::trace::TxExecInfo;
 use katana_primitives::transaction::{ExecutableTx, ExecutableTxWithHash, TxHash, TxType};
 use katana_provider::traits::block::{BlockNumberProvider, BlockProvider};
 use katana_provider::traits::transaction::{TransactionTraceProvider, TransactionsProviderExt};
 use katana_rpc_api::starknet::StarknetTraceApiServer;
 use katana_rpc_types::error::starknet::StarknetApiError;
+use katana_rpc_types::state_update::StateDiff;
 use katana_rpc_types::trace::FunctionInvocation;
 use katana_rpc_types::transaction::BroadcastedTx;
 use katana_rpc_types::{FeeEstimate, SimulationFlag};
@@ -69,12 +71,16 @@ impl<EF: ExecutorFactory> StarknetApi<EF> {
 
         // If the node is run with fee charge disabled, then we should disable charing fees even
         // if the `SKIP_FEE_CHARGE` flag is not set.
-        let should_skip_fee = !simulation_flags.contains(&SimulationFlag::SkipFeeCharge)
+        let should_charge_fee = !simulation_flags.contains(&SimulationFlag::SkipFeeCharge)
             && self.inner.backend.executor_factory.execution_flags().fee();
 
+        let impersonated =
+            self.inner.backend.executor_factory.execution_flags().impersonated_accounts().clone();
+
         let flags = katana_executor::ExecutionFlags::new()
             .with_account_validation(should_validate)
-            .with_fee(!should_skip_fee);
+            .with_fee(should_charge_fee)
+            .with_impersonated_accounts(impersonated);
 
         // get the state and block env at the specified block for execution
         let state = self.state(&block_id)?;
@@ -85,10 +91,10 @@ impl<EF: ExecutorFactory> StarknetApi<EF> {
         let results = executor.simulate(executables, flags);
 
         let mut simulated = Vec::with_capacity(results.len());
-        for (i, ResultAndStates { result, .. }) in results.into_iter().enumerate() {
+        for (i, ResultAndStates { result, states }) in results.into_iter().enumerate() {
             match result {
-                ExecutionResult::Success { trace, receipt } => {
-                    let transaction_trace = to_rpc_trace(trace);
+                ExecutionResult::Success { trace, receipt, .. } => {
+                    let transaction_trace = to_rpc_trace(trace, Some(states));
                     let fee_estimation = to_rpc_fee_estimate(receipt.fee().clone());
                     let value = SimulatedTransaction { transaction_trace, fee_estimation };
                     simulated.push(value)
@@ -124,7 +130,7 @@ impl<EF: ExecutorFactory> StarknetApi<EF> {
                     let traces = pending_block.transactions().iter().filter_map(|(t, r)| {
                         if let Some(trace) = r.trace() {
                             let transaction_hash = t.hash;
-                            let trace_root = to_rpc_trace(trace.clone());
+                            let trace_root = to_rpc_trace(trace.clone(), None);
                             Some(TransactionTraceWithHash { transaction_hash, trace_root })
                         } else {
                             None
@@ -148,7 +154,7 @@ impl<EF: ExecutorFactory> StarknetApi<EF> {
         let traces = provider.transaction_executions_by_block(block_id)?.ok_or(BlockNotFound)?;
 
         // convert to rpc types
-        let traces = traces.into_iter().map(to_rpc_trace);
+        let traces = traces.into_iter().map(|trace| to_rpc_trace(trace, None));
         let result = hashes
             .into_iter()
             .zip(traces)
@@ -167,7 +173,7 @@ impl<EF: ExecutorFactory> StarknetApi<EF> {
             let tx = pending_block.transactions().iter().find(|(t, _)| t.hash == tx_hash);
 
             if let Some(trace) = tx.and_then(|(_, res)| res.trace()) {
-                return Ok(to_rpc_trace(trace.clone()));
+                return Ok(to_rpc_trace(trace.clone(), None));
             }
         }
 
@@ -175,7 +181,7 @@ impl<EF: ExecutorFactory> StarknetApi<EF> {
         let provider = self.inner.backend.blockchain.provider();
         let trace = provider.transaction_execution(tx_hash)?.ok_or(TxnHashNotFound)?;
 
-        Ok(to_rpc_trace(trace))
+        Ok(to_rpc_trace(trace, None))
     }
 }
 
@@ -207,14 +213,17 @@ impl<EF: ExecutorFactory> StarknetTraceApiServer for StarknetApi<EF> {
 
 // TODO: move this conversion to katana_rpc_types
 
-fn to_rpc_trace(trace: TxExecInfo) -> TransactionTrace {
+/// Converts an execution trace into its RPC representation.
+///
+/// The state diff is only available for simulated transactions, as the per-transaction state
+/// changes of mined transactions are not stored.
+fn to_rpc_trace(trace: TxExecInfo, state_diff: Option<StateUpdates>) -> TransactionTrace {
     let fee_transfer_invocation =
         trace.fee_transfer_call_info.map(|f| FunctionInvocation::from(f).0);
     let validate_invocation = trace.validate_call_info.map(|f| FunctionInvocation::from(f).0);
     let execute_invocation = trace.execute_call_info.map(|f| FunctionInvocation::from(f).0);
     let revert_reason = trace.revert_error;
-    // TODO: compute the state diff
-    let state_diff = None;
+    let state_diff = state_diff.map(|diff| StateDiff::from(diff).0);
 
     let execution_resources = to_rpc_resources(trace.actual_resources.vm_resources);
 
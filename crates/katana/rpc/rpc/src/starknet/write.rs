@@ -1,6 +1,7 @@
 use jsonrpsee::core::{async_trait, RpcResult};
 use katana_executor::ExecutorFactory;
 use katana_pool::TransactionPool;
+use katana_primitives::class::SierraVersion;
 use katana_primitives::transaction::{ExecutableTx, ExecutableTxWithHash};
 use katana_rpc_api::starknet::StarknetWriteApiServer;
 use katana_rpc_types::error::starknet::StarknetApiError;
@@ -43,6 +44,14 @@ impl<EF: ExecutorFactory> StarknetApi<EF> {
                 .try_into_tx_with_chain_id(this.inner.backend.chain_spec.id())
                 .map_err(|_| StarknetApiError::InvalidContractClass)?;
 
+            // reject classes that can't be compiled by the node before they reach the pool
+            if let Some(class) = tx.class.as_sierra() {
+                let version = SierraVersion::from_class(class)?;
+                if !version.is_supported() {
+                    return Err(StarknetApiError::UnsupportedContractClassVersion);
+                }
+            }
+
             let class_hash = tx.class_hash();
             let tx = ExecutableTxWithHash::new(ExecutableTx::Declare(tx));
             let hash = this.inner.pool.add_transaction(tx)?;
@@ -0,0 +1,24 @@
+use hyper::body::{Bytes, HttpBody};
+use hyper::{Body, Response, StatusCode};
+
+/// Reads the whole body, failing if it's larger than `limit` bytes.
+pub(crate) async fn read_body(mut body: Body, limit: usize) -> Result<Bytes, StatusCode> {
+    let mut buffer = Vec::new();
+
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|_| StatusCode::BAD_REQUEST)?;
+        if buffer.len() + chunk.len() > limit {
+            return Err(StatusCode::PAYLOAD_TOO_LARGE);
+        }
+        buffer.extend_from_slice(&chunk);
+    }
+
+    Ok(Bytes::from(buffer))
+}
+
+/// A response with `status` and its reason as body.
+pub(crate) fn rejection(status: StatusCode) -> Response<Body> {
+    let mut res = Response::new(Body::from(status.canonical_reason().unwrap_or_default()));
+    *res.status_mut() = status;
+    res
+}
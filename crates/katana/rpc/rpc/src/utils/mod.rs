@@ -1 +1,2 @@
 pub mod events;
+pub(crate) mod http;
@@ -0,0 +1,136 @@
+//! Versioned RPC endpoints.
+//!
+//! Apart from the root path, the RPC server also serves the Starknet JSON-RPC API on a path that
+//! is specific to a spec version, eg `/rpc/v0_7`. This allows clients to pin the spec version
+//! they were built against, and to get an explicit error - instead of responses that they may not
+//! be able to deserialize - once that version is no longer served by the node.
+//!
+//! Requests to a supported versioned path are forwarded to the root path, while requests to an
+//! unsupported version are rejected with a `404 Not Found`.
+
+use std::task::{Context, Poll};
+
+use futures::future::{self, Either, Ready};
+use http::uri::{PathAndQuery, Uri};
+use hyper::{Body, Request, Response, StatusCode};
+use katana_rpc_api::starknet::RPC_SPEC_VERSION;
+use tower::{Layer, Service};
+
+/// The path prefix of the versioned RPC endpoints.
+const VERSIONED_PATH_PREFIX: &str = "/rpc/";
+
+/// Returns the spec versions served by the node, formatted as in the versioned paths (ie `v0_7`).
+pub fn supported_versions() -> Vec<String> {
+    vec![path_version(RPC_SPEC_VERSION)]
+}
+
+/// Converts a spec version (eg `0.7.1`) into its path representation (eg `v0_7`). Only the major
+/// and minor versions are used, as patch versions are expected to be compatible with each other.
+fn path_version(spec_version: &str) -> String {
+    let mut parts = spec_version.split('.');
+    let major = parts.next().unwrap_or("0");
+    let minor = parts.next().unwrap_or("0");
+    format!("v{major}_{minor}")
+}
+
+/// Layer that routes requests to the versioned RPC endpoints. See the [module](self) level docs
+/// for more details.
+#[derive(Debug, Clone)]
+pub struct RpcVersionLayer {
+    versions: Vec<String>,
+}
+
+impl RpcVersionLayer {
+    pub fn new() -> Self {
+        Self { versions: supported_versions() }
+    }
+}
+
+impl Default for RpcVersionLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for RpcVersionLayer {
+    type Service = RpcVersionService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcVersionService { inner, versions: self.versions.clone() }
+    }
+}
+
+/// Service created by [`RpcVersionLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcVersionService<S> {
+    inner: S,
+    versions: Vec<String>,
+}
+
+impl<S> Service<Request<Body>> for RpcVersionService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Either<S::Future, Ready<Result<Self::Response, Self::Error>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
+        let Some(version) = req.uri().path().strip_prefix(VERSIONED_PATH_PREFIX) else {
+            return Either::Left(self.inner.call(req));
+        };
+
+        let version = version.trim_end_matches('/');
+
+        if !self.versions.iter().any(|v| v == version) {
+            let message = format!(
+                "Unsupported JSON-RPC spec version: {version}. Supported versions are: {}",
+                self.versions.join(", ")
+            );
+
+            let mut res = Response::new(Body::from(message));
+            *res.status_mut() = StatusCode::NOT_FOUND;
+            return Either::Right(future::ready(Ok(res)));
+        }
+
+        *req.uri_mut() = to_root_path(req.uri());
+        Either::Left(self.inner.call(req))
+    }
+}
+
+/// Replaces the path of the `uri` with the root path, keeping its query (if any).
+fn to_root_path(uri: &Uri) -> Uri {
+    let path_and_query = match uri.query() {
+        Some(query) => format!("/?{query}"),
+        None => String::from("/"),
+    };
+
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(PathAndQuery::try_from(path_and_query).expect("valid path"));
+    Uri::from_parts(parts).expect("valid uri")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spec_version_to_path() {
+        assert_eq!(path_version("0.7.1"), "v0_7");
+        assert_eq!(path_version("0.8.0"), "v0_8");
+        assert_eq!(path_version("1.0"), "v1_0");
+    }
+
+    #[test]
+    fn versioned_path_to_root() {
+        let uri = Uri::from_static("http://localhost:5050/rpc/v0_7");
+        assert_eq!(to_root_path(&uri), Uri::from_static("http://localhost:5050/"));
+
+        let uri = Uri::from_static("/rpc/v0_7?foo=bar");
+        assert_eq!(to_root_path(&uri), Uri::from_static("/?foo=bar"));
+    }
+}
@@ -1,8 +1,22 @@
 use dojo_test_utils::sequencer::{get_default_test_config, TestSequencer};
 use katana_node::config::sequencing::SequencingConfig;
+use katana_primitives::contract::ContractAddress;
+use katana_primitives::genesis::constant::DEFAULT_ETH_FEE_TOKEN_ADDRESS;
+use katana_primitives::{Felt, U256};
 use katana_provider::traits::block::{BlockNumberProvider, BlockProvider};
 use katana_provider::traits::env::BlockEnvProvider;
+use katana_provider::traits::state_update::StateUpdateProvider;
 use katana_rpc_api::dev::DevApiClient;
+use katana_rpc_types::receipt::ReceiptBlock;
+use katana_rpc_types::transaction::{BroadcastedInvokeTx, BroadcastedTx};
+use katana_rpc_types::FeeToken;
+use starknet::accounts::Account;
+use starknet::core::types::{
+    BlockId, BlockTag, BroadcastedInvokeTransaction, BroadcastedInvokeTransactionV1, Call,
+    FunctionCall,
+};
+use starknet::macros::{felt, selector};
+use starknet::providers::Provider;
 
 async fn create_test_sequencer() -> TestSequencer {
     TestSequencer::start(get_default_test_config(SequencingConfig::default())).await
@@ -99,6 +113,62 @@ async fn test_increase_next_block_timestamp() {
     );
 }
 
+#[tokio::test]
+async fn test_increase_time_after_set_next_block_timestamp() {
+    let sequencer = create_test_sequencer().await;
+    let backend = sequencer.backend();
+    let provider = backend.blockchain.provider();
+
+    let client = HttpClientBuilder::default().build(sequencer.url()).unwrap();
+
+    let block_num = provider.latest_number().unwrap();
+    let mut block_env = provider.block_env_at(block_num.into()).unwrap().unwrap();
+    backend.update_block_env(&mut block_env);
+    let block1 = backend.mine_empty_block(&block_env).unwrap().block_number;
+
+    let block1_timestamp = provider.block(block1.into()).unwrap().unwrap().header.timestamp;
+
+    // the increase must be applied on top of the explicitly set timestamp
+    client.set_next_block_timestamp(block1_timestamp + 1000).await.unwrap();
+    client.increase_time(500).await.unwrap();
+
+    let block_num = provider.latest_number().unwrap();
+    let mut block_env = provider.block_env_at(block_num.into()).unwrap().unwrap();
+    backend.update_block_env(&mut block_env);
+    let block2 = backend.mine_empty_block(&block_env).unwrap().block_number;
+
+    let block2_timestamp = provider.block(block2.into()).unwrap().unwrap().header.timestamp;
+    assert_eq!(block2_timestamp, block1_timestamp + 1500, "timestamp should be updated");
+}
+
+#[tokio::test]
+async fn test_pending_call_does_not_consume_next_block_timestamp() {
+    let sequencer = create_test_sequencer().await;
+    let backend = sequencer.backend();
+    let provider = backend.blockchain.provider();
+
+    let client = HttpClientBuilder::default().build(sequencer.url()).unwrap();
+
+    let block_num = provider.latest_number().unwrap();
+    let timestamp = provider.block(block_num.into()).unwrap().unwrap().header.timestamp + 1000;
+    client.set_next_block_timestamp(timestamp).await.unwrap();
+
+    // executing against the pending block must not affect the timestamp of the next block
+    let call = FunctionCall {
+        contract_address: DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(),
+        entry_point_selector: selector!("balanceOf"),
+        calldata: vec![felt!("0x1")],
+    };
+    sequencer.provider().call(call, BlockId::Tag(BlockTag::Pending)).await.unwrap();
+
+    let mut block_env = provider.block_env_at(block_num.into()).unwrap().unwrap();
+    backend.update_block_env(&mut block_env);
+    let block = backend.mine_empty_block(&block_env).unwrap().block_number;
+
+    let block_timestamp = provider.block(block.into()).unwrap().unwrap().header.timestamp;
+    assert_eq!(block_timestamp, timestamp, "timestamp should be updated");
+}
+
 #[tokio::test]
 async fn test_dev_api_enabled() {
     let sequencer = create_test_sequencer().await;
@@ -109,26 +179,286 @@ async fn test_dev_api_enabled() {
     assert!(!accounts.is_empty(), "predeployed accounts should not be empty");
 }
 
-// #[tokio::test]
-// async fn test_set_storage_at_on_instant_mode() {
-//     let sequencer = create_test_sequencer().await;
-//     sequencer.backend().mine_empty_block();
+#[tokio::test]
+async fn test_mine_on_demand() {
+    let config =
+        get_default_test_config(SequencingConfig { no_mining: true, ..Default::default() });
+    let sequencer = TestSequencer::start(config).await;
+    let provider = sequencer.backend().blockchain.provider();
+
+    let client = HttpClientBuilder::default().build(sequencer.url()).unwrap();
+
+    let latest = provider.latest_number().unwrap();
+    let mined = client.mine().await.unwrap();
+
+    assert_eq!(mined, latest + 1);
+    assert_eq!(provider.latest_number().unwrap(), mined);
+}
+
+async fn assert_mint(sequencer: &TestSequencer) {
+    let client = HttpClientBuilder::default().build(sequencer.url()).unwrap();
+
+    let address = felt!("0x1337");
+    let balance_of = FunctionCall {
+        contract_address: DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(),
+        entry_point_selector: selector!("balanceOf"),
+        calldata: vec![address],
+    };
+
+    let balance = sequencer.provider().call(&balance_of, BlockId::Tag(BlockTag::Pending)).await;
+    assert_eq!(balance.unwrap(), vec![Felt::ZERO, Felt::ZERO]);
+
+    let amount = U256::from(u128::MAX) + U256::from(1000);
+    client.mint(address.into(), amount, None).await.unwrap();
+    client.mint(address.into(), U256::from(1), Some(FeeToken::Eth)).await.unwrap();
+
+    // the amount overflows the low part of the balance
+    let balance = sequencer.provider().call(&balance_of, BlockId::Tag(BlockTag::Pending)).await;
+    assert_eq!(balance.unwrap(), vec![felt!("1000"), Felt::ONE]);
+}
+
+#[tokio::test]
+async fn test_mint_on_instant_mode() {
+    let sequencer = create_test_sequencer().await;
+    let provider = sequencer.backend().blockchain.provider();
+
+    let latest = provider.latest_number().unwrap();
+    assert_mint(&sequencer).await;
+
+    // the balance and the total supply are updated in the same block
+    assert_eq!(provider.latest_number().unwrap(), latest + 2);
+}
+
+#[tokio::test]
+async fn test_mint_on_demand() {
+    let config =
+        get_default_test_config(SequencingConfig { no_mining: true, ..Default::default() });
+    let sequencer = TestSequencer::start(config).await;
+    assert_mint(&sequencer).await;
 
-//     let contract_address = ContractAddress(patricia_key!("0x1337"));
-//     let key = StorageKey(patricia_key!("0x20"));
-//     let val = stark_felt!("0xABC");
+    // the minted balance must be persisted once the pending block is mined
+    let client = HttpClientBuilder::default().build(sequencer.url()).unwrap();
+    client.mine().await.unwrap();
+
+    let balance_of = FunctionCall {
+        contract_address: DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(),
+        entry_point_selector: selector!("balanceOf"),
+        calldata: vec![felt!("0x1337")],
+    };
+    let balance = sequencer.provider().call(balance_of, BlockId::Tag(BlockTag::Latest)).await;
+    assert_eq!(balance.unwrap(), vec![felt!("1000"), Felt::ONE]);
+}
+
+#[tokio::test]
+async fn test_revert_to_block() {
+    let sequencer = create_test_sequencer().await;
+    let provider = sequencer.provider();
+    let account = sequencer.account();
+
+    let client = HttpClientBuilder::default().build(sequencer.url()).unwrap();
+
+    let block_number = provider.block_number().await.unwrap();
+    let nonce =
+        provider.get_nonce(BlockId::Tag(BlockTag::Latest), account.address()).await.unwrap();
+
+    let recipient = felt!("0x1337");
+    let transfer = Call {
+        to: DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(),
+        selector: selector!("transfer"),
+        calldata: vec![recipient, felt!("0x100"), Felt::ZERO],
+    };
+    let balance_of = FunctionCall {
+        contract_address: DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(),
+        entry_point_selector: selector!("balanceOf"),
+        calldata: vec![recipient],
+    };
+
+    let res = account.execute_v1(vec![transfer.clone()]).send().await.unwrap();
+    dojo_utils::TransactionWaiter::new(res.transaction_hash, &provider).await.unwrap();
+    client.mine().await.unwrap();
+
+    assert_eq!(provider.block_number().await.unwrap(), block_number + 2);
+
+    client.revert_to_block(block_number).await.unwrap();
+
+    // the reverted blocks and their state changes must be discarded
+    assert_eq!(provider.block_number().await.unwrap(), block_number);
+    assert!(provider.get_transaction_by_hash(res.transaction_hash).await.is_err());
+
+    let latest = BlockId::Tag(BlockTag::Latest);
+    assert_eq!(provider.get_nonce(latest, account.address()).await.unwrap(), nonce);
+    let balance = provider.call(&balance_of, latest).await.unwrap();
+    assert_eq!(balance, vec![Felt::ZERO, Felt::ZERO]);
+
+    // reverting to a block ahead of the latest block must fail
+    assert!(client.revert_to_block(block_number + 1).await.is_err());
+
+    // new blocks are built on top of the reverted chain
+    let res = account.execute_v1(vec![transfer]).nonce(nonce).send().await.unwrap();
+    dojo_utils::TransactionWaiter::new(res.transaction_hash, &provider).await.unwrap();
+
+    assert_eq!(provider.block_number().await.unwrap(), block_number + 1);
+    let balance = provider.call(&balance_of, latest).await.unwrap();
+    assert_eq!(balance, vec![felt!("0x100"), Felt::ZERO]);
+}
+
+/// Overrides the storage, nonce and class hash of contracts, and checks that the changes are
+/// visible in the pending state.
+async fn assert_set_state(sequencer: &TestSequencer) {
+    let provider = sequencer.provider();
+    let account = sequencer.account().address();
+    let client = HttpClientBuilder::default().build(sequencer.url()).unwrap();
+
+    let pending = BlockId::Tag(BlockTag::Pending);
+    let token: Felt = DEFAULT_ETH_FEE_TOKEN_ADDRESS.into();
+
+    let key = felt!("0x20");
+    client.set_storage_at(DEFAULT_ETH_FEE_TOKEN_ADDRESS, key, felt!("0xabc")).await.unwrap();
+    assert_eq!(provider.get_storage_at(token, key, pending).await.unwrap(), felt!("0xabc"));
+
+    client.set_nonce(account.into(), felt!("0x42")).await.unwrap();
+    assert_eq!(provider.get_nonce(pending, account).await.unwrap(), felt!("0x42"));
+
+    let class_hash = provider.get_class_hash_at(pending, token).await.unwrap();
+    client.set_class_hash_at(felt!("0x1337").into(), class_hash).await.unwrap();
+    let actual = provider.get_class_hash_at(pending, felt!("0x1337")).await.unwrap();
+    assert_eq!(actual, class_hash);
+
+    // the class must be declared
+    assert!(client.set_class_hash_at(felt!("0x1337").into(), felt!("0xdead")).await.is_err());
+}
+
+#[tokio::test]
+async fn test_set_state_on_instant_mode() {
+    let sequencer = create_test_sequencer().await;
+    let provider = sequencer.backend().blockchain.provider();
 
-//     {
-//         let mut state = sequencer.backend().state.write().await;
-//         let read_val = state.get_storage_at(contract_address, key).unwrap();
-//         assert_eq!(stark_felt!("0x0"), read_val, "latest storage value should be 0");
-//     }
+    let latest = provider.latest_number().unwrap();
+    assert_set_state(&sequencer).await;
 
-//     sequencer.set_storage_at(contract_address, key, val).await.unwrap();
+    // each override is mined in a block of its own, with the change as its state update
+    assert_eq!(provider.latest_number().unwrap(), latest + 3);
 
-//     {
-//         let mut state = sequencer.backend().state.write().await;
-//         let read_val = state.get_storage_at(contract_address, key).unwrap();
-//         assert_eq!(val, read_val, "latest storage value incorrect after generate");
-//     }
-// }
+    let state_update = provider.state_update((latest + 1).into()).unwrap().unwrap();
+    let storage = &state_update.storage_updates[&DEFAULT_ETH_FEE_TOKEN_ADDRESS];
+    assert_eq!(storage.get(&felt!("0x20")), Some(&felt!("0xabc")));
+
+    let state_update = provider.state_update((latest + 2).into()).unwrap().unwrap();
+    let account = ContractAddress::from(sequencer.account().address());
+    assert_eq!(state_update.nonce_updates.get(&account), Some(&felt!("0x42")));
+
+    let state_update = provider.state_update((latest + 3).into()).unwrap().unwrap();
+    assert!(state_update.replaced_classes.contains_key(&ContractAddress::from(felt!("0x1337"))));
+}
+
+#[tokio::test]
+async fn test_set_state_on_demand() {
+    let config =
+        get_default_test_config(SequencingConfig { no_mining: true, ..Default::default() });
+    let sequencer = TestSequencer::start(config).await;
+    assert_set_state(&sequencer).await;
+
+    // the changes must be persisted once the pending block is mined
+    let client = HttpClientBuilder::default().build(sequencer.url()).unwrap();
+    client.mine().await.unwrap();
+
+    let provider = sequencer.provider();
+    let latest = BlockId::Tag(BlockTag::Latest);
+    let token: Felt = DEFAULT_ETH_FEE_TOKEN_ADDRESS.into();
+
+    let value = provider.get_storage_at(token, felt!("0x20"), latest).await.unwrap();
+    assert_eq!(value, felt!("0xabc"));
+    let nonce = provider.get_nonce(latest, sequencer.account().address()).await.unwrap();
+    assert_eq!(nonce, felt!("0x42"));
+}
+
+#[tokio::test]
+async fn test_toggle_fee_at_runtime() {
+    let mut config = get_default_test_config(SequencingConfig::default());
+    config.dev.fee = true;
+    let sequencer = TestSequencer::start(config).await;
+    let provider = sequencer.provider();
+    let account = sequencer.account();
+
+    let client = HttpClientBuilder::default().build(sequencer.url()).unwrap();
+
+    let transfer = Call {
+        to: DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(),
+        selector: selector!("transfer"),
+        calldata: vec![felt!("0x1"), Felt::ONE, Felt::ZERO],
+    };
+
+    // an underpriced transaction is rejected while fee charging is enabled
+    let res = account.execute_v1(vec![transfer.clone()]).max_fee(Felt::TWO).send().await;
+    assert!(res.is_err());
+
+    // but is executed once it's disabled, as the max fee is ignored
+    client.set_fee_enabled(false).await.unwrap();
+    let res = account.execute_v1(vec![transfer.clone()]).max_fee(Felt::TWO).send().await.unwrap();
+    dojo_utils::TransactionWaiter::new(res.transaction_hash, &provider).await.unwrap();
+
+    client.set_fee_enabled(true).await.unwrap();
+    let res = account.execute_v1(vec![transfer]).max_fee(Felt::TWO).send().await;
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn test_execute_batch() {
+    let sequencer = create_test_sequencer().await;
+    let provider = sequencer.provider();
+    let account = sequencer.account();
+
+    let client = HttpClientBuilder::default().build(sequencer.url()).unwrap();
+
+    // the transactions of the batch are sent without a signature
+    client.impersonate_account(account.address().into()).await.unwrap();
+
+    let transfer = |nonce: Felt| {
+        let calldata = vec![
+            felt!("0x1"),
+            DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(),
+            selector!("transfer"),
+            felt!("0x3"),
+            felt!("0x1"),
+            felt!("0x1"),
+            Felt::ZERO,
+        ];
+
+        let tx = BroadcastedInvokeTransactionV1 {
+            nonce,
+            calldata,
+            is_query: false,
+            signature: vec![],
+            max_fee: Felt::from(u128::MAX),
+            sender_address: account.address(),
+        };
+
+        BroadcastedTx::Invoke(BroadcastedInvokeTx(BroadcastedInvokeTransaction::V1(tx)))
+    };
+
+    let block_id = BlockId::Tag(BlockTag::Pending);
+    let nonce = provider.get_nonce(block_id, account.address()).await.unwrap();
+    let latest = provider.block_number().await.unwrap();
+
+    let batch = vec![transfer(nonce), transfer(nonce + Felt::ONE)];
+    let receipts = client.execute_batch(batch).await.unwrap();
+
+    // all the transactions are mined in the same block
+    assert_eq!(receipts.len(), 2);
+    for receipt in receipts {
+        let ReceiptBlock::Block { block_number, .. } = receipt.0.block else {
+            panic!("transaction must be mined");
+        };
+        assert_eq!(block_number, latest + 1);
+    }
+
+    assert_eq!(provider.block_number().await.unwrap(), latest + 1);
+
+    // the second transaction reuses the nonce of the first one, so the whole batch is discarded
+    let nonce = nonce + Felt::TWO;
+    let res = client.execute_batch(vec![transfer(nonce), transfer(nonce)]).await;
+    assert!(res.is_err());
+
+    assert_eq!(provider.block_number().await.unwrap(), latest + 1);
+    assert_eq!(provider.get_nonce(block_id, account.address()).await.unwrap(), nonce);
+}
@@ -681,3 +681,30 @@ async fn get_events_with_invalid_block_hash(#[case] hash: BlockHash) -> Result<(
 
     Ok(())
 }
+
+#[tokio::test]
+async fn get_storage_proof_is_not_supported() -> Result<()> {
+    use jsonrpsee::core::Error;
+    use jsonrpsee::http_client::HttpClientBuilder;
+    use jsonrpsee::types::error::CallError;
+    use katana_rpc_api::starknet::StarknetApiClient;
+
+    let (sequencer, provider, _) = setup_test().await;
+    let client = HttpClientBuilder::default().build(sequencer.url())?;
+
+    // the proofs can't be generated from the state of the forked chain
+    let block_ids =
+        [BlockIdOrTag::Number(FORK_BLOCK_NUMBER + 1), BlockIdOrTag::Tag(BlockTag::Latest)];
+    for block_id in block_ids {
+        let result =
+            client.get_storage_proof(block_id, None, Some(vec![Felt::ONE.into()]), None).await;
+        assert_matches!(result, Err(Error::Call(CallError::Custom(e))) => {
+            assert_eq!(e.code(), 63);
+        });
+    }
+
+    // the node is still up
+    assert_eq!(provider.block_number().await?, FORK_BLOCK_NUMBER + 10);
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use dojo_test_utils::sequencer::{get_default_test_config, TestSequencer};
+use dojo_utils::TransactionWaiter;
+use jsonrpsee::http_client::HttpClientBuilder;
+use katana_node::config::sequencing::SequencingConfig;
+use katana_primitives::block::{BlockIdOrTag, BlockTag};
+use katana_primitives::genesis::constant::DEFAULT_ETH_FEE_TOKEN_ADDRESS;
+use katana_primitives::Felt;
+use katana_rpc_api::katana::KatanaApiClient;
+use starknet::accounts::Account;
+use starknet::core::types::Call;
+use starknet::macros::{felt, selector};
+use starknet::providers::Provider;
+
+#[tokio::test]
+async fn block_execution_report() {
+    let sequencer =
+        TestSequencer::start(get_default_test_config(SequencingConfig::default())).await;
+    let provider = sequencer.provider();
+    let account = sequencer.account();
+
+    let client = HttpClientBuilder::default().build(sequencer.url()).unwrap();
+
+    let transfer = Call {
+        to: DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(),
+        selector: selector!("transfer"),
+        calldata: vec![felt!("0x1"), Felt::ONE, Felt::ZERO],
+    };
+
+    let res = account.execute_v1(vec![transfer]).send().await.unwrap();
+    TransactionWaiter::new(res.transaction_hash, &provider).await.unwrap();
+
+    let block_number = provider.block_number().await.unwrap();
+    let report =
+        client.get_block_execution_report(BlockIdOrTag::Tag(BlockTag::Latest)).await.unwrap();
+
+    assert_eq!(report.block_number, block_number);
+    assert!(report.block_hash.is_some());
+    assert_eq!(report.transactions.len(), 1);
+
+    let tx = &report.transactions[0];
+    assert_eq!(tx.transaction_hash, res.transaction_hash);
+    assert!(!tx.reverted);
+    assert!(tx.resources.steps > 0);
+    // the transfer is made through the account's `__execute__` entrypoint
+    assert!(tx.resources.syscalls.call_contract >= 1);
+    assert_eq!(report.total, tx.resources);
+
+    let report = client.get_block_execution_report(BlockIdOrTag::Number(block_number)).await;
+    assert_eq!(report.unwrap().transactions.len(), 1);
+}
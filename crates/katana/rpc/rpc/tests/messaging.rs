@@ -13,14 +13,15 @@ use katana_messaging::MessagingConfig;
 use katana_node::config::sequencing::SequencingConfig;
 use katana_primitives::felt;
 use katana_primitives::utils::transaction::{
-    compute_l1_handler_tx_hash, compute_l1_to_l2_message_hash,
+    compute_l1_handler_tx_hash, compute_l1_to_l2_message_hash, compute_l2_to_l1_message_hash,
 };
 use katana_rpc_types::receipt::ReceiptBlock;
 use rand::Rng;
 use starknet::accounts::{Account, ConnectedAccount};
 use starknet::contract::ContractFactory;
 use starknet::core::types::{
-    BlockId, BlockTag, ContractClass, Felt, Hash256, MsgFromL1, Transaction, TransactionReceipt,
+    BlockId, BlockTag, Call, ContractClass, Felt, Hash256, MsgFromL1, Transaction,
+    TransactionReceipt,
 };
 use starknet::core::utils::get_contract_address;
 use starknet::macros::selector;
@@ -68,6 +69,7 @@ async fn test_messaging() {
         contract_address: core_contract.address().to_string(),
         interval: 2,
         from_block: 0,
+        ..Default::default()
     };
 
     let mut config = get_default_test_config(SequencingConfig::default());
@@ -229,8 +231,50 @@ async fn test_messaging() {
         }
     }
 
-    // Send message from L2 to L1 testing must be done using Saya or part of
-    // it to ensure the settlement contract is test on piltover and its `update_state` method.
+    // Send message from L2 to L1
+    {
+        // The L1 contract address to send the message to
+        let recipient = Felt::from_bytes_be_slice(l1_test_contract.address().as_slice());
+        let value = felt!("0x1337");
+
+        let call = Call {
+            to: l2_test_contract,
+            selector: selector!("send_message_value"),
+            calldata: vec![recipient, value],
+        };
+
+        let res = katana_account.execute_v1(vec![call]).send().await.unwrap();
+        TransactionWaiter::new(res.transaction_hash, katana_account.provider())
+            .await
+            .expect("send message tx failed");
+
+        // Wait for the message to be registered on L1
+        tokio::time::sleep(Duration::from_secs(5)).await;
+
+        let msg_hash = compute_l2_to_l1_message_hash(l2_test_contract, recipient, &[value]);
+        let msg_count = core_contract
+            .l2ToL1Messages(msg_hash)
+            .call()
+            .await
+            .expect("failed to get msg count")
+            ._0;
+
+        assert_eq!(msg_count, U256::from(1), "message must be consumable on L1");
+
+        // Consume the message on L1
+        let from_address = U256::from_be_slice(&l2_test_contract.to_bytes_be());
+        let receipt = l1_test_contract
+            .consumeMessage(from_address, vec![U256::from_be_slice(&value.to_bytes_be())])
+            .gas(12000000)
+            .send()
+            .await
+            .expect("failed to send tx")
+            .get_receipt()
+            .await
+            .expect("error getting transaction receipt");
+
+        assert!(receipt.status(), "failed to consume L2 -> L1 message");
+    }
 }
 
 #[tokio::test]
@@ -8,6 +8,7 @@ use dojo_utils::TransactionWaiter;
 use jsonrpsee::http_client::HttpClientBuilder;
 use katana_node::config::sequencing::SequencingConfig;
 use katana_primitives::block::{BlockIdOrTag, BlockTag};
+use katana_primitives::contract::ContractAddress;
 use katana_rpc_api::dev::DevApiClient;
 use katana_rpc_api::saya::SayaApiClient;
 use starknet::accounts::{Account, ConnectedAccount};
@@ -154,3 +155,43 @@ async fn fetch_traces_from_pending_block() {
 
     sequencer.stop().expect("failed to stop sequencer");
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn fetch_snos_inputs_in_provable_mode() {
+    let sequencer = TestSequencer::start(get_default_test_config(SequencingConfig {
+        provable: true,
+        ..Default::default()
+    }))
+    .await;
+
+    let client = HttpClientBuilder::default().build(sequencer.url()).unwrap();
+
+    let account = sequencer.account();
+
+    let path: PathBuf = PathBuf::from("tests/test_data/cairo1_contract.json");
+    let (contract, compiled_class_hash) =
+        common::prepare_contract_declaration_params(&path).unwrap();
+
+    let res = account.declare_v2(Arc::new(contract), compiled_class_hash).send().await.unwrap();
+    TransactionWaiter::new(res.transaction_hash, account.provider())
+        .with_interval(200)
+        .await
+        .expect("tx failed");
+
+    let artifacts = client
+        .block_execution_artifacts(BlockIdOrTag::Tag(BlockTag::Latest))
+        .await
+        .expect("failed to get the SNOS inputs of the latest block");
+
+    // the account is validating the declaration with its public key
+    let account_address = ContractAddress::from(account.address());
+    assert!(artifacts.initial_contracts.contains_key(&account_address));
+    assert!(artifacts.initial_storage.contains_key(&account_address));
+    assert!(!artifacts.executed_classes.is_empty());
+
+    // the genesis block is not produced by the block producer
+    let genesis = client.block_execution_artifacts(BlockIdOrTag::Number(0)).await;
+    assert!(genesis.is_err());
+
+    sequencer.stop().expect("failed to stop sequencer");
+}
@@ -12,18 +12,26 @@ use jsonrpsee::http_client::HttpClientBuilder;
 use katana_node::config::sequencing::SequencingConfig;
 use katana_primitives::event::ContinuationToken;
 use katana_primitives::genesis::constant::{
-    DEFAULT_ACCOUNT_CLASS_HASH, DEFAULT_ETH_FEE_TOKEN_ADDRESS, DEFAULT_PREFUNDED_ACCOUNT_BALANCE,
+    get_fee_token_balance_base_storage_address, DEFAULT_ACCOUNT_CLASS_HASH,
+    DEFAULT_ETH_FEE_TOKEN_ADDRESS, DEFAULT_PREFUNDED_ACCOUNT_BALANCE,
     DEFAULT_STRK_FEE_TOKEN_ADDRESS, DEFAULT_UDC_ADDRESS,
 };
+use katana_primitives::ContractAddress;
+use katana_provider::traits::block::BlockNumberProvider;
+use katana_provider::traits::transaction::TransactionProvider;
 use katana_rpc_api::dev::DevApiClient;
+use katana_rpc_api::starknet::{StarknetApiClient, StarknetTraceApiClient, RPC_SPEC_VERSION};
+use katana_rpc_types::transaction::{BroadcastedInvokeTx, BroadcastedTx};
+use katana_rpc_types::{SimulationFlag, SimulationFlagForEstimateFee};
 use starknet::accounts::{
     Account, AccountError, AccountFactory, ConnectedAccount, ExecutionEncoding,
     OpenZeppelinAccountFactory, SingleOwnerAccount,
 };
 use starknet::core::types::contract::legacy::LegacyContractClass;
 use starknet::core::types::{
-    BlockId, BlockTag, Call, DeclareTransactionReceipt, DeployAccountTransactionReceipt,
-    EventFilter, EventsPage, ExecutionResult, Felt, MaybePendingBlockWithReceipts,
+    BlockId, BlockTag, BroadcastedInvokeTransaction, BroadcastedInvokeTransactionV1, Call,
+    ContractClass, DeclareTransactionReceipt, DeployAccountTransactionReceipt, EventFilter,
+    EventsPage, ExecutionResult, Felt, FunctionCall, MaybePendingBlockWithReceipts,
     MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs, MaybePendingStateUpdate,
     StarknetError, TransactionExecutionStatus, TransactionFinalityStatus, TransactionReceipt,
     TransactionTrace,
@@ -201,6 +209,28 @@ async fn declaring_already_existing_class() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn declaring_unsupported_sierra_version() -> Result<()> {
+    let sequencer =
+        TestSequencer::start(get_default_test_config(SequencingConfig::default())).await;
+    let account = sequencer.account();
+
+    let path = PathBuf::from("tests/test_data/cairo1_contract.json");
+    let (mut contract, compiled_hash) = common::prepare_contract_declaration_params(&path)?;
+
+    // bump the minor version of the program above the one supported by the bundled compiler
+    contract.sierra_program[1] = felt!("0x63");
+
+    // set the max fee manually to skip the fee estimation, which would execute the declaration
+    let result = account.declare_v2(contract.into(), compiled_hash).max_fee(Felt::ONE).send().await;
+    assert_account_starknet_err!(
+        result.unwrap_err(),
+        StarknetError::UnsupportedContractClassVersion
+    );
+
+    Ok(())
+}
+
 #[rstest::rstest]
 #[tokio::test]
 async fn deploy_account(
@@ -333,6 +363,59 @@ async fn estimate_fee() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn estimate_fee_with_simulation_flags() -> Result<()> {
+    let sequencer =
+        TestSequencer::start(get_default_test_config(SequencingConfig::default())).await;
+
+    let provider = sequencer.provider();
+    let account = sequencer.account();
+    let client = HttpClientBuilder::default().build(sequencer.url())?;
+
+    // an unsigned transfer whose max fee exceeds the account balance
+    let nonce = provider.get_nonce(BlockId::Tag(BlockTag::Pending), account.address()).await?;
+    let calldata = vec![
+        felt!("0x1"),
+        DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(),
+        selector!("transfer"),
+        felt!("0x3"),
+        felt!("0x1"),
+        felt!("0x1"),
+        Felt::ZERO,
+    ];
+
+    let tx = BroadcastedInvokeTransactionV1 {
+        nonce,
+        calldata,
+        is_query: true,
+        signature: vec![],
+        max_fee: Felt::from(u128::MAX),
+        sender_address: account.address(),
+    };
+    let tx = BroadcastedTx::Invoke(BroadcastedInvokeTx(BroadcastedInvokeTransaction::V1(tx)));
+
+    let block_id = BlockId::Tag(BlockTag::Pending);
+    let estimate = |flags: Vec<SimulationFlagForEstimateFee>| {
+        StarknetApiClient::estimate_fee(&client, vec![tx.clone()], flags, block_id)
+    };
+
+    let result = estimate(vec![]).await;
+    assert!(result.is_err(), "estimate should fail without a valid signature");
+
+    let result = estimate(vec![SimulationFlagForEstimateFee::SkipValidate]).await;
+    assert!(result.is_err(), "estimate should fail if max fee exceeds the balance");
+
+    let flags = vec![
+        SimulationFlagForEstimateFee::SkipValidate,
+        SimulationFlagForEstimateFee::SkipFeeCharge,
+    ];
+    let fees = estimate(flags).await?;
+    assert_eq!(fees.len(), 1);
+    assert!(fees[0].overall_fee != Felt::ZERO, "fee should still be estimated");
+
+    Ok(())
+}
+
 #[rstest::rstest]
 #[tokio::test(flavor = "multi_thread")]
 async fn concurrent_transactions_submissions(
@@ -803,6 +886,65 @@ async fn get_events_with_pending() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn simulate_transactions() -> Result<()> {
+    let sequencer =
+        TestSequencer::start(get_default_test_config(SequencingConfig::default())).await;
+
+    let provider = sequencer.provider();
+    let account = sequencer.account();
+    let client = HttpClientBuilder::default().build(sequencer.url())?;
+
+    let block_id = BlockId::Tag(BlockTag::Pending);
+    let nonce = provider.get_nonce(block_id, account.address()).await?;
+
+    // unsigned transfers of 1 wei to the address 0x1
+    let transfer = |nonce: Felt| {
+        let calldata = vec![
+            felt!("0x1"),
+            DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(),
+            selector!("transfer"),
+            felt!("0x3"),
+            felt!("0x1"),
+            felt!("0x1"),
+            Felt::ZERO,
+        ];
+
+        let tx = BroadcastedInvokeTransactionV1 {
+            nonce,
+            calldata,
+            is_query: true,
+            signature: vec![],
+            max_fee: felt!("0x1111111111111"),
+            sender_address: account.address(),
+        };
+        BroadcastedTx::Invoke(BroadcastedInvokeTx(BroadcastedInvokeTransaction::V1(tx)))
+    };
+
+    // the second transaction relies on the nonce update of the first one
+    let txs = vec![transfer(nonce), transfer(nonce + Felt::ONE)];
+    let flags = vec![SimulationFlag::SkipValidate];
+    let simulated = client.simulate_transactions(block_id, txs.clone(), flags).await?;
+    assert_eq!(simulated.len(), 2);
+
+    for tx in simulated {
+        assert!(tx.fee_estimation.overall_fee != Felt::ZERO, "fee should be estimated");
+        assert_matches!(tx.transaction_trace, TransactionTrace::Invoke(trace) => {
+            assert!(trace.state_diff.is_some(), "simulated txs should have a state diff");
+        });
+    }
+
+    // simulating must not commit any of the state changes
+    let current_nonce = provider.get_nonce(block_id, account.address()).await?;
+    assert_eq!(current_nonce, nonce);
+
+    // the signatures are checked unless validation is skipped
+    let result = client.simulate_transactions(block_id, txs, vec![]).await;
+    assert!(result.is_err(), "simulation should fail without valid signatures");
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn trace() -> Result<()> {
     let config =
@@ -973,6 +1115,76 @@ async fn v3_transactions() {
     assert_eq!(status, TransactionExecutionStatus::Succeeded);
 }
 
+#[tokio::test]
+async fn versioned_rpc_paths() -> Result<()> {
+    let sequencer =
+        TestSequencer::start(get_default_test_config(SequencingConfig::default())).await;
+
+    let path = format!("rpc/{}", katana_rpc::version::supported_versions()[0]);
+    let client = HttpClientBuilder::default().build(sequencer.url().join(&path)?)?;
+    let version = StarknetApiClient::spec_version(&client).await?;
+    assert_eq!(version, RPC_SPEC_VERSION);
+
+    let client = HttpClientBuilder::default().build(sequencer.url().join("rpc/v0_1")?)?;
+    let result = StarknetApiClient::spec_version(&client).await;
+    assert!(result.is_err(), "unsupported spec versions should be rejected");
+
+    Ok(())
+}
+
+// Same as `v3_transactions`, but for the declare and deploy account v3 transactions.
+#[tokio::test]
+async fn v3_declare_and_deploy_account() -> Result<()> {
+    let sequencer =
+        TestSequencer::start(get_default_test_config(SequencingConfig::default())).await;
+
+    let provider = sequencer.provider();
+    let account = sequencer.account();
+    let chain_id = provider.chain_id().await?;
+
+    // -----------------------------------------------------------------------
+    // Declare V3
+
+    let path: PathBuf = PathBuf::from("tests/test_data/cairo1_contract.json");
+    let (contract, compiled_class_hash) = common::prepare_contract_declaration_params(&path)?;
+
+    let class_hash = contract.class_hash();
+    let res = account.declare_v3(contract.into(), compiled_class_hash).send().await?;
+
+    let receipt = dojo_utils::TransactionWaiter::new(res.transaction_hash, &provider).await?;
+    assert_matches!(receipt.receipt, TransactionReceipt::Declare(_));
+    assert_eq!(receipt.receipt.execution_result().status(), TransactionExecutionStatus::Succeeded);
+    assert!(provider.get_class(BlockId::Tag(BlockTag::Pending), class_hash).await.is_ok());
+
+    // -----------------------------------------------------------------------
+    // Deploy Account V3
+
+    let signer = LocalWallet::from(SigningKey::from_random());
+    let class_hash = DEFAULT_ACCOUNT_CLASS_HASH;
+    let salt = felt!("0x123");
+    let ctor_args = [signer.get_public_key().await?.scalar()];
+    let computed_address = get_contract_address(salt, class_hash, &ctor_args, Felt::ZERO);
+
+    // v3 transactions pay their fee in STRK, so fund the new account with STRK
+    let contract = Erc20Contract::new(DEFAULT_STRK_FEE_TOKEN_ADDRESS.into(), &account);
+    let amount = Uint256 { low: felt!("0x56bc75e2d63100000"), high: Felt::ZERO };
+    let res = contract.transfer(&computed_address, &amount).send().await?;
+    dojo_utils::TransactionWaiter::new(res.transaction_hash, &provider).await?;
+
+    let factory = OpenZeppelinAccountFactory::new(class_hash, chain_id, &signer, &provider).await?;
+    let res = factory.deploy_v3(salt).send().await?;
+    assert_eq!(res.contract_address, computed_address);
+
+    let receipt = dojo_utils::TransactionWaiter::new(res.transaction_hash, &provider).await?;
+    assert_matches!(receipt.receipt, TransactionReceipt::DeployAccount(_));
+    assert_eq!(receipt.receipt.execution_result().status(), TransactionExecutionStatus::Succeeded);
+
+    let res = provider.get_class_hash_at(BlockId::Tag(BlockTag::Pending), computed_address).await?;
+    assert_eq!(res, class_hash);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn fetch_pending_blocks() {
     let config =
@@ -1167,6 +1379,117 @@ async fn fetch_pending_blocks_in_instant_mode() {
     }
 }
 
+#[tokio::test]
+async fn historical_state_access() -> Result<()> {
+    let config = get_default_test_config(SequencingConfig::default());
+    let sequencer = TestSequencer::start(config).await;
+
+    let provider = sequencer.provider();
+    let account = sequencer.account();
+
+    let fee_token: Felt = DEFAULT_ETH_FEE_TOKEN_ADDRESS.into();
+    let recipient = felt!("0x1337");
+    let balance_key = get_fee_token_balance_base_storage_address(recipient.into());
+
+    let balance_call = FunctionCall {
+        contract_address: fee_token,
+        entry_point_selector: selector!("balanceOf"),
+        calldata: vec![recipient],
+    };
+
+    let before = provider.block_number().await?;
+    let nonce_before = provider.get_nonce(BlockId::Number(before), account.address()).await?;
+
+    // transfer some tokens to the recipient in a new block
+    let contract = Erc20Contract::new(fee_token, &account);
+    let amount = Uint256 { low: felt!("0x100"), high: Felt::ZERO };
+    let res = contract.transfer(&recipient, &amount).send().await?;
+    dojo_utils::TransactionWaiter::new(res.transaction_hash, &provider).await?;
+
+    let after = provider.block_number().await?;
+    assert!(after > before);
+
+    // the state at the block before the transfer must not include its changes
+    let block_id = BlockId::Number(before);
+    let nonce = provider.get_nonce(block_id, account.address()).await?;
+    let balance = provider.get_storage_at(fee_token, balance_key, block_id).await?;
+    let call_result = provider.call(balance_call.clone(), block_id).await?;
+
+    assert_eq!(nonce, nonce_before);
+    assert_eq!(balance, Felt::ZERO);
+    assert_eq!(call_result[0], Felt::ZERO);
+
+    // while the state at the block after the transfer must
+    for block_id in [BlockId::Number(after), BlockId::Tag(BlockTag::Latest)] {
+        let nonce = provider.get_nonce(block_id, account.address()).await?;
+        let balance = provider.get_storage_at(fee_token, balance_key, block_id).await?;
+        let call_result = provider.call(balance_call.clone(), block_id).await?;
+
+        assert_eq!(nonce, nonce_before + Felt::ONE);
+        assert_eq!(balance, amount.low);
+        assert_eq!(call_result[0], amount.low);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn historical_class_access() -> Result<()> {
+    let config = get_default_test_config(SequencingConfig::default());
+    let sequencer = TestSequencer::start(config).await;
+
+    let provider = sequencer.provider();
+    let account = sequencer.account();
+    let address = account.address();
+
+    let before_declare = provider.block_number().await?;
+    let old_class_hash =
+        provider.get_class_hash_at(BlockId::Number(before_declare), address).await?;
+
+    let path = PathBuf::from("tests/test_data/cairo1_contract.json");
+    let (contract, compiled_class_hash) = common::prepare_contract_declaration_params(&path)?;
+    let new_class_hash = contract.class_hash();
+
+    let res = account.declare_v2(contract.into(), compiled_class_hash).send().await?;
+    dojo_utils::TransactionWaiter::new(res.transaction_hash, &provider).await?;
+    let before_upgrade = provider.block_number().await?;
+
+    // upgrade the account to the newly declared class
+    let call = Call { to: address, selector: selector!("upgrade"), calldata: vec![new_class_hash] };
+    let res = account.execute_v1(vec![call]).send().await?;
+    dojo_utils::TransactionWaiter::new(res.transaction_hash, &provider).await?;
+    let after_upgrade = provider.block_number().await?;
+
+    // the class must not be available before the block it's declared in
+    let err =
+        provider.get_class(BlockId::Number(before_declare), new_class_hash).await.unwrap_err();
+    assert_matches!(err, ProviderError::StarknetError(StarknetError::ClassHashNotFound));
+    assert!(provider.get_class(BlockId::Number(before_upgrade), new_class_hash).await.is_ok());
+
+    // the contract must keep its old class until the block it's upgraded in
+    for block_id in [BlockId::Number(before_declare), BlockId::Number(before_upgrade)] {
+        let class_hash = provider.get_class_hash_at(block_id, address).await?;
+        assert_eq!(class_hash, old_class_hash);
+
+        let ContractClass::Sierra(class) = provider.get_class_at(block_id, address).await? else {
+            panic!("Invalid class type")
+        };
+        assert_eq!(class.class_hash(), old_class_hash);
+    }
+
+    for block_id in [BlockId::Number(after_upgrade), BlockId::Tag(BlockTag::Latest)] {
+        let class_hash = provider.get_class_hash_at(block_id, address).await?;
+        assert_eq!(class_hash, new_class_hash);
+
+        let ContractClass::Sierra(class) = provider.get_class_at(block_id, address).await? else {
+            panic!("Invalid class type")
+        };
+        assert_eq!(class.class_hash(), new_class_hash);
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn call_contract() {
     let config = get_default_test_config(SequencingConfig::default());
@@ -1195,3 +1518,113 @@ async fn call_contract() {
     let contract = AccountContractReader::new(account, &provider);
     let _ = contract.get_public_key().call().await.unwrap();
 }
+
+#[tokio::test]
+async fn subscribe_executed_transactions() -> Result<()> {
+    let sequencer =
+        TestSequencer::start(get_default_test_config(SequencingConfig::default())).await;
+
+    // subscribe before sending any transaction, as only new transactions are received
+    let mut executed = sequencer.backend().subscribe_executed_txs();
+
+    let provider = sequencer.provider();
+    let account = sequencer.account();
+    let contract = Erc20Contract::new(DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(), &account);
+
+    let recipient = felt!("0x1337");
+    let amount = Uint256 { low: felt!("0x100"), high: Felt::ZERO };
+    let res = contract.transfer(&recipient, &amount).send().await?;
+    dojo_utils::TransactionWaiter::new(res.transaction_hash, &provider).await?;
+
+    let tx = executed.recv().await?;
+    assert_eq!(tx.tx.hash, res.transaction_hash);
+    assert!(!tx.receipt.is_reverted());
+
+    // the state changes include the sender's nonce and the recipient's balance
+    let nonce = provider.get_nonce(BlockId::Tag(BlockTag::Latest), account.address()).await?;
+    let sender = ContractAddress::from(account.address());
+    assert_eq!(tx.states.nonce_updates.get(&sender), Some(&nonce));
+
+    let balance_key = get_fee_token_balance_base_storage_address(recipient.into());
+    let storage = &tx.states.storage_updates[&DEFAULT_ETH_FEE_TOKEN_ADDRESS];
+    assert_eq!(storage.get(&balance_key), Some(&felt!("0x100")));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn stopping_node_seals_pending_block() -> Result<()> {
+    let config =
+        get_default_test_config(SequencingConfig { no_mining: true, ..Default::default() });
+    let sequencer = TestSequencer::start(config).await;
+
+    let provider = sequencer.provider();
+    let account = sequencer.account();
+
+    let contract = Erc20Contract::new(DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(), &account);
+    let amount = Uint256 { low: felt!("0x1"), high: Felt::ZERO };
+    let res = contract.transfer(&felt!("0x1"), &amount).send().await?;
+    dojo_utils::TransactionWaiter::new(res.transaction_hash, &provider).await?;
+
+    // the transaction is only in the pending block
+    let latest = provider.block_number().await?;
+
+    sequencer.handle().stop().await?;
+
+    let db = sequencer.backend().blockchain.provider();
+    assert_eq!(db.latest_number()?, latest + 1);
+    assert!(db.transaction_by_hash(res.transaction_hash)?.is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn pending_state_update() -> Result<()> {
+    let config =
+        get_default_test_config(SequencingConfig { no_mining: true, ..Default::default() });
+    let sequencer = TestSequencer::start(config).await;
+
+    let dev_client = HttpClientBuilder::default().build(sequencer.url())?;
+    let provider = sequencer.provider();
+    let account = sequencer.account();
+
+    let contract = Erc20Contract::new(DEFAULT_ETH_FEE_TOKEN_ADDRESS.into(), &account);
+    let amount = Uint256 { low: felt!("0x1"), high: Felt::ZERO };
+    let res = contract.transfer(&felt!("0x1"), &amount).send().await?;
+    dojo_utils::TransactionWaiter::new(res.transaction_hash, &provider).await?;
+
+    let MaybePendingStateUpdate::Update(latest) =
+        provider.get_state_update(BlockId::Tag(BlockTag::Latest)).await?
+    else {
+        panic!("expected the latest state update")
+    };
+
+    let MaybePendingStateUpdate::PendingUpdate(pending) =
+        provider.get_state_update(BlockId::Tag(BlockTag::Pending)).await?
+    else {
+        panic!("expected a pending state update")
+    };
+
+    assert_eq!(pending.old_root, latest.new_root);
+
+    let nonce = pending.state_diff.nonces.iter().find(|n| n.contract_address == account.address());
+    assert_eq!(nonce.map(|n| n.nonce), Some(Felt::ONE));
+
+    let fee_token = Felt::from(DEFAULT_ETH_FEE_TOKEN_ADDRESS);
+    assert!(pending.state_diff.storage_diffs.iter().any(|diff| diff.address == fee_token));
+
+    // the state diff of the block is the one of its pending block
+    dev_client.generate_block().await?;
+
+    let MaybePendingStateUpdate::Update(mined) =
+        provider.get_state_update(BlockId::Tag(BlockTag::Latest)).await?
+    else {
+        panic!("expected the latest state update")
+    };
+
+    assert_eq!(mined.old_root, pending.old_root);
+    assert_eq!(mined.state_diff.nonces, pending.state_diff.nonces);
+    assert_eq!(mined.state_diff.storage_diffs, pending.state_diff.storage_diffs);
+
+    Ok(())
+}
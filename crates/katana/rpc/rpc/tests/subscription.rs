@@ -0,0 +1,45 @@
+use dojo_test_utils::sequencer::{get_default_test_config, TestSequencer};
+use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
+use katana_node::config::sequencing::SequencingConfig;
+use katana_rpc_api::dev::DevApiClient;
+use katana_rpc_api::starknet::StarknetSubscriptionApiClient;
+
+async fn ws_client(sequencer: &TestSequencer) -> WsClient {
+    let mut url = sequencer.url();
+    url.set_scheme("ws").unwrap();
+    WsClientBuilder::default().build(url.as_str()).await.unwrap()
+}
+
+#[tokio::test]
+async fn subscribe_new_heads() {
+    let config =
+        get_default_test_config(SequencingConfig { no_mining: true, ..Default::default() });
+    let sequencer = TestSequencer::start(config).await;
+    let client = ws_client(&sequencer).await;
+
+    let mut subscription = client.subscribe_new_heads().await.unwrap();
+
+    for _ in 0..2 {
+        let mined = client.mine().await.unwrap();
+        let header = subscription.next().await.expect("subscription closed").unwrap();
+        assert_eq!(header.block_number, mined);
+    }
+}
+
+#[tokio::test]
+async fn subscribe_events_without_activity() {
+    let config =
+        get_default_test_config(SequencingConfig { no_mining: true, ..Default::default() });
+    let sequencer = TestSequencer::start(config).await;
+    let client = ws_client(&sequencer).await;
+
+    let mut events = client.subscribe_events(None, None).await.unwrap();
+    let mut heads = client.subscribe_new_heads().await.unwrap();
+
+    // empty blocks don't emit any events
+    client.mine().await.unwrap();
+    heads.next().await.expect("subscription closed").unwrap();
+
+    let next = tokio::time::timeout(std::time::Duration::from_millis(200), events.next()).await;
+    assert!(next.is_err(), "no events should be received");
+}
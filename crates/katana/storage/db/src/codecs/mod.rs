@@ -5,6 +5,8 @@ use katana_primitives::block::FinalityStatus;
 use katana_primitives::class::ContractClass;
 use katana_primitives::contract::ContractAddress;
 use katana_primitives::Felt;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 use crate::error::CodecError;
 
@@ -88,16 +90,31 @@ impl Decode for String {
 impl Compress for ContractClass {
     type Compressed = Vec<u8>;
     fn compress(self) -> Self::Compressed {
-        serde_json::to_vec(&self).unwrap()
+        compress_class(&self)
     }
 }
 
 impl Decompress for ContractClass {
     fn decompress<B: AsRef<[u8]>>(bytes: B) -> Result<Self, CodecError> {
-        serde_json::from_slice(bytes.as_ref()).map_err(|e| CodecError::Decode(e.to_string()))
+        decompress_class(bytes.as_ref())
     }
 }
 
+/// Encodes a class artifact as zstd-compressed JSON.
+///
+/// Class artifacts are large JSON documents with a lot of repetition, so compressing them shrinks
+/// them several times over.
+pub(crate) fn compress_class<T: Serialize>(class: &T) -> Vec<u8> {
+    let json = serde_json::to_vec(class).unwrap();
+    zstd::encode_all(json.as_slice(), zstd::DEFAULT_COMPRESSION_LEVEL).unwrap()
+}
+
+/// Decodes a class artifact encoded with [`compress_class`].
+pub(crate) fn decompress_class<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, CodecError> {
+    let json = zstd::decode_all(bytes).map_err(|e| CodecError::Decompress(e.to_string()))?;
+    serde_json::from_slice(&json).map_err(|e| CodecError::Decode(e.to_string()))
+}
+
 impl Compress for FinalityStatus {
     type Compressed = [u8; 1];
     fn compress(self) -> Self::Compressed {
@@ -114,3 +131,25 @@ impl Decompress for FinalityStatus {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use katana_primitives::genesis::constant::DEFAULT_ACCOUNT_CLASS;
+
+    use super::*;
+
+    #[test]
+    fn compress_and_decompress_class() {
+        let class = DEFAULT_ACCOUNT_CLASS.clone();
+        let json = serde_json::to_vec(&class).unwrap();
+
+        let compressed = class.clone().compress();
+        assert!(compressed.len() < json.len());
+
+        let decompressed = ContractClass::decompress(compressed).unwrap();
+        assert_eq!(decompressed, class);
+
+        // uncompressed values are rejected
+        assert!(ContractClass::decompress(json).is_err());
+    }
+}
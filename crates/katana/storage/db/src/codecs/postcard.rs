@@ -1,7 +1,7 @@
 use katana_primitives::block::Header;
 use katana_primitives::contract::{ContractAddress, GenericContractInfo};
 use katana_primitives::receipt::Receipt;
-use katana_primitives::trace::TxExecInfo;
+use katana_primitives::trace::{BlockExecutionArtifacts, TxExecInfo};
 use katana_primitives::transaction::Tx;
 use katana_primitives::Felt;
 use postcard;
@@ -37,6 +37,7 @@ impl_compress_and_decompress_for_table_values!(
     u64,
     Tx,
     TxExecInfo,
+    BlockExecutionArtifacts,
     Header,
     Receipt,
     Felt,
@@ -41,6 +41,9 @@ pub enum DatabaseError {
 
     #[error("failed to get db stats: {0}")]
     GetStats(libmdbx::Error),
+
+    #[error("failed to sync db to disk: {0}")]
+    Sync(libmdbx::Error),
 }
 
 #[derive(Debug, PartialEq, Eq, thiserror::Error)]
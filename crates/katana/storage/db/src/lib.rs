@@ -11,6 +11,7 @@ pub mod abstraction;
 pub mod codecs;
 pub mod error;
 pub mod mdbx;
+pub mod memory;
 pub mod models;
 pub mod tables;
 pub mod trie;
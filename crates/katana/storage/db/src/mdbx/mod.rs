@@ -149,6 +149,12 @@ impl DbEnv {
         &self.inner.dir
     }
 
+    /// Flushes the data buffers of the environment to disk.
+    pub fn sync(&self) -> Result<(), DatabaseError> {
+        self.inner.env.sync(true).map_err(DatabaseError::Sync)?;
+        Ok(())
+    }
+
     fn with_metrics(self) -> Self {
         describe_gauge!("db.table_size", metrics::Unit::Bytes, "Total size of the table");
         describe_gauge!("db.table_pages", metrics::Unit::Count, "Number of pages in the table");
@@ -0,0 +1,292 @@
+//! Cursor of the in-memory database.
+
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::ops::Bound;
+use std::sync::Arc;
+
+use libmdbx::Error as MdbxError;
+use parking_lot::RwLock;
+
+use super::tx::{remove_key, RW};
+use super::{is_dupsort, key_entries, lower_bound, Entries, Entry, Snapshot};
+use crate::abstraction::{
+    DbCursor, DbCursorMut, DbDupSortCursor, DbDupSortCursorMut, DupWalker, Walker,
+};
+use crate::codecs::{Compress, Encode};
+use crate::error::DatabaseError;
+use crate::tables::{DupSort, Table};
+use crate::utils::{decode_one, decoder, KeyValue};
+
+/// Cursor for navigating the items within a table.
+///
+/// The cursor is positioned on an entry, which it keeps track of even after the entry has been
+/// deleted, so that moving the cursor continues from where the entry was.
+#[derive(Debug)]
+pub struct Cursor<K, T: Table> {
+    /// The tables of the transaction that created the cursor.
+    tables: Arc<RwLock<Snapshot>>,
+    /// The entry the cursor is positioned on.
+    position: Option<Entry>,
+    _phantom: PhantomData<(K, T)>,
+}
+
+impl<K, T: Table> Cursor<K, T> {
+    pub(super) fn new(tables: Arc<RwLock<Snapshot>>) -> Self {
+        Self { tables, position: None, _phantom: PhantomData }
+    }
+
+    /// Positions the cursor on the entry returned by `find`, if any, and decodes it.
+    fn move_to<F>(&mut self, find: F) -> Result<Option<KeyValue<T>>, DatabaseError>
+    where
+        F: FnOnce(&Entries, Option<&Entry>) -> Option<Entry>,
+    {
+        let entry = find(self.tables.read().table::<T>(), self.position.as_ref());
+        let Some(entry) = entry else {
+            // like MDBX, the cursor is left unpositioned when moving past the last entry
+            self.position = None;
+            return Ok(None);
+        };
+
+        let kv = decoder::<T>((Cow::Borrowed(&*entry.0), Cow::Borrowed(&*entry.1)))?;
+        self.position = Some(entry);
+        Ok(Some(kv))
+    }
+
+    fn write_error(error: MdbxError, key: &[u8]) -> DatabaseError {
+        DatabaseError::Write { error, table: T::NAME, key: Box::from(key) }
+    }
+}
+
+impl<K, T: Table> DbCursor<T> for Cursor<K, T> {
+    fn first(&mut self) -> Result<Option<KeyValue<T>>, DatabaseError> {
+        self.move_to(|entries, _| entries.first().cloned())
+    }
+
+    fn current(&mut self) -> Result<Option<KeyValue<T>>, DatabaseError> {
+        self.move_to(|entries, position| position.filter(|e| entries.contains(*e)).cloned())
+    }
+
+    fn next(&mut self) -> Result<Option<KeyValue<T>>, DatabaseError> {
+        self.move_to(|entries, position| match position {
+            Some(position) => {
+                entries.range((Bound::Excluded(position), Bound::Unbounded)).next().cloned()
+            }
+            None => entries.first().cloned(),
+        })
+    }
+
+    fn prev(&mut self) -> Result<Option<KeyValue<T>>, DatabaseError> {
+        self.move_to(|entries, position| match position {
+            Some(position) => entries.range(..position).next_back().cloned(),
+            None => entries.last().cloned(),
+        })
+    }
+
+    fn last(&mut self) -> Result<Option<KeyValue<T>>, DatabaseError> {
+        self.move_to(|entries, _| entries.last().cloned())
+    }
+
+    fn set(&mut self, key: <T as Table>::Key) -> Result<Option<KeyValue<T>>, DatabaseError> {
+        let key = Box::from(key.encode().as_ref());
+        self.move_to(|entries, _| key_entries(entries, key).next().cloned())
+    }
+
+    fn seek(&mut self, key: <T as Table>::Key) -> Result<Option<KeyValue<T>>, DatabaseError> {
+        let lower = lower_bound(key.encode().as_ref());
+        self.move_to(|entries, _| entries.range(lower..).next().cloned())
+    }
+
+    fn walk(&mut self, start_key: Option<T::Key>) -> Result<Walker<'_, T, Self>, DatabaseError> {
+        let start = match start_key {
+            Some(key) => self.seek(key).transpose(),
+            None => self.first().transpose(),
+        };
+
+        Ok(Walker::new(self, start))
+    }
+}
+
+impl<K, T: DupSort> DbDupSortCursor<T> for Cursor<K, T> {
+    fn next_dup(&mut self) -> Result<Option<KeyValue<T>>, DatabaseError> {
+        let Some(position) = self.position.clone() else { return Ok(None) };
+
+        let entry = {
+            let tables = self.tables.read();
+            let mut range =
+                tables.table::<T>().range((Bound::Excluded(&position), Bound::Unbounded));
+            range.next().filter(|(key, _)| *key == position.0).cloned()
+        };
+
+        // unlike moving to the next entry, the cursor stays on the last duplicate of the key
+        match entry {
+            Some(entry) => self.move_to(|_, _| Some(entry)),
+            None => Ok(None),
+        }
+    }
+
+    fn next_dup_val(&mut self) -> Result<Option<<T as Table>::Value>, DatabaseError> {
+        Ok(self.next_dup()?.map(|(_, value)| value))
+    }
+
+    fn next_no_dup(&mut self) -> Result<Option<KeyValue<T>>, DatabaseError> {
+        self.move_to(|entries, position| match position {
+            Some((key, _)) => entries
+                .range((Bound::Excluded(lower_bound(key)), Bound::Unbounded))
+                .find(|(k, _)| k != key)
+                .cloned(),
+            None => entries.first().cloned(),
+        })
+    }
+
+    fn seek_by_key_subkey(
+        &mut self,
+        key: <T as Table>::Key,
+        subkey: <T as DupSort>::SubKey,
+    ) -> Result<Option<<T as Table>::Value>, DatabaseError> {
+        let key: Box<[u8]> = Box::from(key.encode().as_ref());
+        let subkey: Arc<[u8]> = Arc::from(subkey.encode().as_ref());
+
+        let entry = {
+            let tables = self.tables.read();
+            let mut range = tables.table::<T>().range((key.clone(), subkey)..);
+            range.next().filter(|(k, _)| *k == key).cloned()
+        };
+
+        let Some(entry) = entry else { return Ok(None) };
+        let value = decode_one::<T>(Cow::Borrowed(&*entry.1))?;
+        self.position = Some(entry);
+        Ok(Some(value))
+    }
+
+    fn walk_dup(
+        &mut self,
+        key: Option<T::Key>,
+        subkey: Option<<T as DupSort>::SubKey>,
+    ) -> Result<Option<DupWalker<'_, T, Self>>, DatabaseError> {
+        let start = match (key, subkey) {
+            (Some(key), Some(subkey)) => {
+                let key = key.encode().as_ref().to_vec();
+                let subkey = subkey.encode().as_ref().to_vec();
+                self.move_to(|entries, _| {
+                    let mut range = entries.range((Box::from(&key[..]), Arc::from(subkey))..);
+                    range.next().filter(|(k, _)| **k == key[..]).cloned()
+                })
+                .transpose()
+            }
+
+            (Some(key), None) => {
+                let Some(start) = self.set(key).transpose() else { return Ok(None) };
+                Some(start)
+            }
+
+            (None, Some(subkey)) => {
+                if let Some((key, _)) = self.first()? {
+                    let value = self.seek_by_key_subkey(key.clone(), subkey).transpose();
+                    value.map(|value| value.map(|value| (key, value)))
+                } else {
+                    Some(Err(DatabaseError::Read(MdbxError::NotFound)))
+                }
+            }
+
+            (None, None) => self.first().transpose(),
+        };
+
+        Ok(Some(DupWalker::new(self, start)))
+    }
+}
+
+impl<T: Table> DbCursorMut<T> for Cursor<RW, T> {
+    fn upsert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key: Box<[u8]> = Box::from(key.encode().as_ref());
+        let value: Arc<[u8]> = Arc::from(value.compress().as_ref());
+
+        let mut tables = self.tables.write();
+        if !is_dupsort::<T>() {
+            remove_key::<T>(&mut tables, key.clone());
+        }
+        tables.table_mut::<T>().insert((key.clone(), value.clone()));
+
+        self.position = Some((key, value));
+        Ok(())
+    }
+
+    fn insert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key: Box<[u8]> = Box::from(key.encode().as_ref());
+        let value: Arc<[u8]> = Arc::from(value.compress().as_ref());
+
+        let mut tables = self.tables.write();
+        if key_entries(tables.table::<T>(), key.clone()).next().is_some() {
+            return Err(Self::write_error(MdbxError::KeyExist, &key));
+        }
+        tables.table_mut::<T>().insert((key.clone(), value.clone()));
+
+        self.position = Some((key, value));
+        Ok(())
+    }
+
+    fn append(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key: Box<[u8]> = Box::from(key.encode().as_ref());
+        let value: Arc<[u8]> = Arc::from(value.compress().as_ref());
+
+        let mut tables = self.tables.write();
+        let entry = (key, value);
+
+        // the entry must be after the last entry of the table
+        if tables.table::<T>().last().is_some_and(|last| {
+            if is_dupsort::<T>() {
+                *last >= entry
+            } else {
+                last.0 >= entry.0
+            }
+        }) {
+            return Err(Self::write_error(MdbxError::KeyMismatch, &entry.0));
+        }
+        tables.table_mut::<T>().insert(entry.clone());
+
+        self.position = Some(entry);
+        Ok(())
+    }
+
+    fn delete_current(&mut self) -> Result<(), DatabaseError> {
+        let Some(position) = &self.position else {
+            return Err(DatabaseError::Delete(MdbxError::NotFound));
+        };
+
+        let mut tables = self.tables.write();
+        if tables.table::<T>().contains(position) {
+            tables.table_mut::<T>().remove(position);
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: DupSort> DbDupSortCursorMut<T> for Cursor<RW, T> {
+    fn delete_current_duplicates(&mut self) -> Result<(), DatabaseError> {
+        let Some((key, _)) = &self.position else {
+            return Err(DatabaseError::Delete(MdbxError::NotFound));
+        };
+
+        remove_key::<T>(&mut self.tables.write(), key.clone());
+        Ok(())
+    }
+
+    fn append_dup(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key: Box<[u8]> = Box::from(key.encode().as_ref());
+        let value: Arc<[u8]> = Arc::from(value.compress().as_ref());
+
+        let mut tables = self.tables.write();
+        let entry = (key, value);
+
+        // the value must be after the last duplicate of the key
+        let last = key_entries(tables.table::<T>(), entry.0.clone()).last();
+        if last.is_some_and(|last| *last >= entry) {
+            return Err(Self::write_error(MdbxError::KeyMismatch, &entry.0));
+        }
+        tables.table_mut::<T>().insert(entry.clone());
+
+        self.position = Some(entry);
+        Ok(())
+    }
+}
@@ -0,0 +1,274 @@
+//! In-memory backend for the database.
+//!
+//! The tables are kept in memory as ordered sets of encoded key/value pairs, mirroring the layout
+//! of the MDBX tables, so that the same providers can be used on top of both backends.
+//!
+//! The tables are shared copy-on-write between the database, its transactions and the databases
+//! created with [`MemoryDb::fork`]: a table is only copied when it's written to while being shared,
+//! eg on the first write to it of a read-write transaction. The values are reference counted, so
+//! copying a table only copies its index. This makes forking a database cheap, which is useful for
+//! exploring many branches from a common state, eg in tests.
+
+pub mod cursor;
+pub mod tx;
+
+use std::collections::BTreeSet;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use parking_lot::{Condvar, Mutex, RwLock};
+
+use self::tx::{Tx, RO, RW};
+use crate::abstraction::Database;
+use crate::error::DatabaseError;
+use crate::tables::{Table, TableType, Tables, NUM_TABLES};
+
+/// An encoded key/value pair.
+pub(crate) type Entry = (Box<[u8]>, Arc<[u8]>);
+
+/// The entries of a table, ordered by key and then by value. A table that isn't `DUPSORT` has at
+/// most one entry per key.
+pub(crate) type Entries = BTreeSet<Entry>;
+
+/// Returns the entry preceding all the entries of `key`, for looking them up.
+pub(crate) fn lower_bound(key: &[u8]) -> Entry {
+    (Box::from(key), Arc::from(Vec::new()))
+}
+
+/// Returns the entries of `key`, ordered by value.
+pub(crate) fn key_entries(entries: &Entries, key: Box<[u8]>) -> impl Iterator<Item = &Entry> {
+    entries.range(lower_bound(&key)..).take_while(move |(k, _)| *k == key)
+}
+
+/// The state of all the tables at a point in time.
+#[derive(Debug, Clone)]
+pub(crate) struct Snapshot([Arc<Entries>; NUM_TABLES]);
+
+impl Snapshot {
+    fn new() -> Self {
+        Self(std::array::from_fn(|_| Arc::default()))
+    }
+
+    pub(crate) fn table<T: Table>(&self) -> &Entries {
+        &self.0[table_index::<T>()]
+    }
+
+    /// Returns the entries of a table for writing, copying them if they are shared.
+    pub(crate) fn table_mut<T: Table>(&mut self) -> &mut Entries {
+        Arc::make_mut(&mut self.0[table_index::<T>()])
+    }
+}
+
+pub(crate) fn table_index<T: Table>() -> usize {
+    Tables::from_str(T::NAME).expect("requested table should be part of `Tables`.") as usize
+}
+
+pub(crate) fn is_dupsort<T: Table>() -> bool {
+    Tables::ALL[table_index::<T>()].table_type() == TableType::DupSort
+}
+
+/// In-memory database. See the [module](self) level docs for more details.
+///
+/// Cloning the database returns a handle to the same database, use [`MemoryDb::fork`] for an
+/// independent copy.
+#[derive(Debug, Clone)]
+pub struct MemoryDb {
+    inner: Arc<MemoryDbInner>,
+}
+
+#[derive(Debug)]
+pub(crate) struct MemoryDbInner {
+    /// The committed state of the tables.
+    tables: RwLock<Snapshot>,
+    /// Whether a read-write transaction is in progress. Like MDBX, only one read-write
+    /// transaction can be in progress at a time.
+    writing: Mutex<bool>,
+    writer_released: Condvar,
+}
+
+impl MemoryDb {
+    /// Creates an empty database, with all the tables created.
+    pub fn new() -> Self {
+        Self::from_snapshot(Snapshot::new())
+    }
+
+    /// Creates a new database with the same content as this one. Both databases are independent,
+    /// and share their tables until either of them writes to it.
+    ///
+    /// The changes of the read-write transaction in progress, if any, are not included.
+    pub fn fork(&self) -> Self {
+        Self::from_snapshot(self.inner.tables.read().clone())
+    }
+
+    fn from_snapshot(snapshot: Snapshot) -> Self {
+        let inner = MemoryDbInner {
+            tables: RwLock::new(snapshot),
+            writing: Mutex::new(false),
+            writer_released: Condvar::new(),
+        };
+        Self { inner: Arc::new(inner) }
+    }
+}
+
+impl MemoryDbInner {
+    /// Replaces the committed state of the tables with the state of a read-write transaction.
+    pub(crate) fn commit(&self, snapshot: Snapshot) {
+        *self.tables.write() = snapshot;
+    }
+
+    /// Marks the read-write transaction in progress as ended.
+    pub(crate) fn release_writer(&self) {
+        *self.writing.lock() = false;
+        self.writer_released.notify_one();
+    }
+}
+
+impl Default for MemoryDb {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Database for MemoryDb {
+    type Tx = Tx<RO>;
+    type TxMut = Tx<RW>;
+    type Stats = Stats;
+
+    fn tx(&self) -> Result<Self::Tx, DatabaseError> {
+        Ok(Tx::new(self.inner.tables.read().clone(), None))
+    }
+
+    fn tx_mut(&self) -> Result<Self::TxMut, DatabaseError> {
+        let mut writing = self.inner.writing.lock();
+        while *writing {
+            self.inner.writer_released.wait(&mut writing);
+        }
+        *writing = true;
+
+        Ok(Tx::new(self.inner.tables.read().clone(), Some(Arc::clone(&self.inner))))
+    }
+
+    fn stats(&self) -> Result<Self::Stats, DatabaseError> {
+        let tables = self.inner.tables.read();
+        Ok(Stats(std::array::from_fn(|i| tables.0[i].len())))
+    }
+}
+
+/// Statistics of the in-memory database.
+#[derive(Debug)]
+pub struct Stats([usize; NUM_TABLES]);
+
+impl Stats {
+    /// Returns the number of entries of the given table.
+    pub fn entries(&self, table: Tables) -> usize {
+        self.0[table as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use katana_primitives::block::Header;
+    use katana_primitives::contract::{ContractAddress, GenericContractInfo};
+    use katana_primitives::Felt;
+
+    use super::*;
+    use crate::abstraction::{DbCursor, DbCursorMut, DbDupSortCursor, DbTx, DbTxMut};
+    use crate::models::storage::StorageEntry;
+    use crate::tables;
+
+    fn storage_entry(key: u8, value: u8) -> StorageEntry {
+        StorageEntry { key: Felt::from(key), value: Felt::from(value) }
+    }
+
+    #[test]
+    fn commit_and_abort() {
+        let db = MemoryDb::new();
+
+        let tx = db.tx_mut().unwrap();
+        tx.put::<tables::Headers>(1, Header { number: 1, ..Default::default() }).unwrap();
+        assert!(tx.get::<tables::Headers>(1).unwrap().is_some());
+
+        // uncommitted changes aren't visible outside of the transaction
+        assert_eq!(db.tx().unwrap().get::<tables::Headers>(1).unwrap(), None);
+        tx.commit().unwrap();
+        assert!(db.tx().unwrap().get::<tables::Headers>(1).unwrap().is_some());
+
+        let tx = db.tx_mut().unwrap();
+        tx.delete::<tables::Headers>(1, None).unwrap();
+        tx.abort();
+        assert!(db.tx().unwrap().get::<tables::Headers>(1).unwrap().is_some());
+        assert_eq!(db.stats().unwrap().entries(Tables::Headers), 1);
+    }
+
+    #[test]
+    fn cursor_navigation() {
+        let db = MemoryDb::new();
+        let tx = db.tx_mut().unwrap();
+
+        let mut cursor = tx.cursor_mut::<tables::BlockHashes>().unwrap();
+        for number in [1u64, 3, 5] {
+            cursor.append(number, Felt::from(number)).unwrap();
+        }
+        assert!(cursor.append(2, Felt::TWO).is_err());
+        assert!(cursor.insert(3, Felt::THREE).is_err());
+
+        assert_eq!(cursor.first().unwrap(), Some((1, Felt::ONE)));
+        assert_eq!(cursor.next().unwrap(), Some((3, Felt::THREE)));
+        assert_eq!(cursor.prev().unwrap(), Some((1, Felt::ONE)));
+        assert_eq!(cursor.seek(4).unwrap(), Some((5, Felt::from(5u8))));
+        assert_eq!(cursor.next().unwrap(), None);
+        assert_eq!(cursor.set(4).unwrap(), None);
+
+        cursor.set(3).unwrap();
+        cursor.delete_current().unwrap();
+        assert_eq!(cursor.next().unwrap(), Some((5, Felt::from(5u8))));
+
+        let keys = cursor.walk(None).unwrap().map(|res| res.unwrap().0).collect::<Vec<_>>();
+        assert_eq!(keys, vec![1, 5]);
+    }
+
+    #[test]
+    fn dupsort_table() {
+        let db = MemoryDb::new();
+        let tx = db.tx_mut().unwrap();
+
+        let address = ContractAddress::from(Felt::ONE);
+        tx.put::<tables::ContractStorage>(address, storage_entry(2, 20)).unwrap();
+        tx.put::<tables::ContractStorage>(address, storage_entry(1, 10)).unwrap();
+        tx.put::<tables::ContractStorage>(ContractAddress::from(Felt::TWO), storage_entry(1, 1))
+            .unwrap();
+
+        let mut cursor = tx.cursor_dup::<tables::ContractStorage>().unwrap();
+        let entry = cursor.seek_by_key_subkey(address, Felt::TWO).unwrap();
+        assert_eq!(entry, Some(storage_entry(2, 20)));
+
+        let entries = cursor
+            .walk_dup(Some(address), None)
+            .unwrap()
+            .unwrap()
+            .map(|res| res.unwrap().1)
+            .collect::<Vec<_>>();
+        assert_eq!(entries, vec![storage_entry(1, 10), storage_entry(2, 20)]);
+
+        tx.delete::<tables::ContractStorage>(address, Some(storage_entry(1, 10))).unwrap();
+        assert_eq!(tx.entries::<tables::ContractStorage>().unwrap(), 2);
+    }
+
+    #[test]
+    fn forks_are_independent() {
+        let db = MemoryDb::new();
+        let address = ContractAddress::from(Felt::ONE);
+        let info = |nonce: u8| GenericContractInfo { nonce: nonce.into(), ..Default::default() };
+
+        db.update(|tx| tx.put::<tables::ContractInfo>(address, info(1))).unwrap().unwrap();
+
+        let fork = db.fork();
+        fork.update(|tx| tx.put::<tables::ContractInfo>(address, info(2))).unwrap().unwrap();
+        db.update(|tx| tx.put::<tables::ContractInfo>(address, info(3))).unwrap().unwrap();
+
+        let nonce = |db: &MemoryDb| db.tx().unwrap().get::<tables::ContractInfo>(address).unwrap();
+        assert_eq!(nonce(&db), Some(info(3)));
+        assert_eq!(nonce(&fork), Some(info(2)));
+        assert_eq!(nonce(&fork.fork()), Some(info(2)));
+    }
+}
@@ -0,0 +1,160 @@
+//! Transaction of the in-memory database.
+
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use super::cursor::Cursor;
+use super::{is_dupsort, key_entries, MemoryDbInner, Snapshot};
+use crate::abstraction::{DbTx, DbTxMut};
+use crate::codecs::{Compress, Encode};
+use crate::error::DatabaseError;
+use crate::tables::{DupSort, Table};
+use crate::utils::decode_one;
+
+/// Marker for read-only transactions.
+#[derive(Debug)]
+pub struct RO;
+
+/// Marker for read-write transactions.
+#[derive(Debug)]
+pub struct RW;
+
+/// Alias for read-only transaction.
+pub type TxRO = Tx<RO>;
+/// Alias for read-write transaction.
+pub type TxRW = Tx<RW>;
+
+/// Database transaction.
+///
+/// Operates on its own snapshot of the tables, so it doesn't see the changes committed after it
+/// has been created. The changes of a read-write transaction replace the tables of the database
+/// when it's committed.
+#[derive(Debug)]
+pub struct Tx<K> {
+    /// The tables as seen by the transaction, shared with its cursors.
+    pub(super) tables: Arc<RwLock<Snapshot>>,
+    /// The database to commit to, only set for read-write transactions.
+    db: Option<Arc<MemoryDbInner>>,
+    _kind: PhantomData<K>,
+}
+
+impl<K> Tx<K> {
+    pub(super) fn new(snapshot: Snapshot, db: Option<Arc<MemoryDbInner>>) -> Self {
+        Self { tables: Arc::new(RwLock::new(snapshot)), db, _kind: PhantomData }
+    }
+}
+
+impl<K> Drop for Tx<K> {
+    fn drop(&mut self) {
+        // the transaction is aborted if it's dropped without being committed
+        if let Some(db) = self.db.take() {
+            db.release_writer();
+        }
+    }
+}
+
+impl<K: Send + Sync> DbTx for Tx<K> {
+    type Cursor<T: Table> = Cursor<K, T>;
+    type DupCursor<T: DupSort> = Self::Cursor<T>;
+
+    fn cursor<T: Table>(&self) -> Result<Cursor<K, T>, DatabaseError> {
+        Ok(Cursor::new(Arc::clone(&self.tables)))
+    }
+
+    fn cursor_dup<T: DupSort>(&self) -> Result<Cursor<K, T>, DatabaseError> {
+        Ok(Cursor::new(Arc::clone(&self.tables)))
+    }
+
+    fn get<T: Table>(&self, key: T::Key) -> Result<Option<<T as Table>::Value>, DatabaseError> {
+        let key = Box::from(key.encode().as_ref());
+        let tables = self.tables.read();
+        let entry = key_entries(tables.table::<T>(), key).next();
+        entry.map(|(_, value)| decode_one::<T>(Cow::Borrowed(&**value))).transpose()
+    }
+
+    fn entries<T: Table>(&self) -> Result<usize, DatabaseError> {
+        Ok(self.tables.read().table::<T>().len())
+    }
+
+    fn commit(mut self) -> Result<bool, DatabaseError> {
+        if let Some(db) = self.db.take() {
+            db.commit(self.tables.read().clone());
+            db.release_writer();
+        }
+        Ok(true)
+    }
+
+    fn abort(self) {
+        drop(self)
+    }
+}
+
+impl DbTxMut for Tx<RW> {
+    type Cursor<T: Table> = Cursor<RW, T>;
+    type DupCursor<T: DupSort> = <Self as DbTxMut>::Cursor<T>;
+
+    fn cursor_mut<T: Table>(&self) -> Result<<Self as DbTxMut>::Cursor<T>, DatabaseError> {
+        DbTx::cursor(self)
+    }
+
+    fn cursor_dup_mut<T: DupSort>(&self) -> Result<<Self as DbTxMut>::DupCursor<T>, DatabaseError> {
+        DbTx::cursor_dup(self)
+    }
+
+    fn put<T: Table>(&self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key: Box<[u8]> = Box::from(key.encode().as_ref());
+        let value: Arc<[u8]> = Arc::from(value.compress().as_ref());
+
+        let mut tables = self.tables.write();
+        if !is_dupsort::<T>() {
+            remove_key::<T>(&mut tables, key.clone());
+        }
+        tables.table_mut::<T>().insert((key, value));
+
+        Ok(())
+    }
+
+    fn delete<T: Table>(
+        &self,
+        key: T::Key,
+        value: Option<T::Value>,
+    ) -> Result<bool, DatabaseError> {
+        let key: Box<[u8]> = Box::from(key.encode().as_ref());
+        let mut tables = self.tables.write();
+
+        match value {
+            Some(value) => {
+                let entry = (key, Arc::from(value.compress().as_ref()));
+                // the table isn't copied if there's nothing to delete
+                if !tables.table::<T>().contains(&entry) {
+                    return Ok(false);
+                }
+                Ok(tables.table_mut::<T>().remove(&entry))
+            }
+            None => Ok(remove_key::<T>(&mut tables, key)),
+        }
+    }
+
+    fn clear<T: Table>(&self) -> Result<(), DatabaseError> {
+        self.tables.write().table_mut::<T>().clear();
+        Ok(())
+    }
+}
+
+/// Removes all the entries of `key`, returning whether there was any.
+pub(super) fn remove_key<T: Table>(tables: &mut Snapshot, key: Box<[u8]>) -> bool {
+    let removed = key_entries(tables.table::<T>(), key).cloned().collect::<Vec<_>>();
+    if removed.is_empty() {
+        return false;
+    }
+
+    let entries = tables.table_mut::<T>();
+    for entry in removed {
+        entries.remove(&entry);
+    }
+
+    true
+}
@@ -1,17 +1,17 @@
 use katana_primitives::class::CompiledClass;
 
-use crate::codecs::{Compress, Decompress};
+use crate::codecs::{compress_class, decompress_class, Compress, Decompress};
 use crate::error::CodecError;
 
 impl Compress for CompiledClass {
     type Compressed = Vec<u8>;
     fn compress(self) -> Self::Compressed {
-        serde_json::to_vec(&self).unwrap()
+        compress_class(&self)
     }
 }
 
 impl Decompress for CompiledClass {
     fn decompress<B: AsRef<[u8]>>(bytes: B) -> Result<Self, CodecError> {
-        serde_json::from_slice(bytes.as_ref()).map_err(|e| CodecError::Decode(e.to_string()))
+        decompress_class(bytes.as_ref())
     }
 }
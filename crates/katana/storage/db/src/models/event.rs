@@ -0,0 +1,33 @@
+use katana_primitives::transaction::TxNumber;
+
+use crate::codecs::{Compress, Decode, Decompress, Encode};
+use crate::error::CodecError;
+
+/// An entry of the event indices, ie the event keys and event emitters indices.
+///
+/// `tx_number` is the subkey for the dupsort tables. It is stored in big-endian so that the entries
+/// of a key are sorted by their transaction number.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(test, derive(::arbitrary::Arbitrary))]
+pub struct EventKeyEntry {
+    /// The number of the transaction that emitted the event.
+    pub tx_number: TxNumber,
+}
+
+impl Compress for EventKeyEntry {
+    type Compressed = [u8; 8];
+    fn compress(self) -> Self::Compressed {
+        self.tx_number.encode()
+    }
+}
+
+impl Decompress for EventKeyEntry {
+    fn decompress<B: AsRef<[u8]>>(bytes: B) -> Result<Self, CodecError> {
+        let bytes = bytes.as_ref();
+        if bytes.len() != 8 {
+            return Err(CodecError::Decompress("Invalid event key entry".into()));
+        }
+
+        Ok(Self { tx_number: TxNumber::decode(bytes)? })
+    }
+}
@@ -24,6 +24,11 @@ impl IntegerSet {
         self.0.insert(num);
     }
 
+    /// Removes a number from the set.
+    pub fn remove(&mut self, num: u64) {
+        self.0.remove(num);
+    }
+
     /// Checks if the set contains the given number.
     pub fn contains(&self, num: u64) -> bool {
         self.0.contains(num)
@@ -38,6 +43,23 @@ impl IntegerSet {
     pub fn select(&self, n: u64) -> Option<u64> {
         self.0.select(n)
     }
+
+    /// Returns the largest integer in the set or `None` if the set is empty.
+    pub fn max(&self) -> Option<u64> {
+        self.0.max()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Removes all the integers in the set that are greater than `num`.
+    pub fn truncate(&mut self, num: u64) {
+        if let Some(start) = num.checked_add(1) {
+            self.0.remove_range(start..);
+        }
+    }
 }
 
 impl<const N: usize> From<[u64; N]> for IntegerSet {
@@ -1,6 +1,7 @@
 pub mod block;
 pub mod class;
 pub mod contract;
+pub mod event;
 pub mod list;
 pub mod stage;
 pub mod storage;
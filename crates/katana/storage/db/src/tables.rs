@@ -2,12 +2,14 @@ use katana_primitives::block::{BlockHash, BlockNumber, FinalityStatus, Header};
 use katana_primitives::class::{ClassHash, CompiledClass, CompiledClassHash, ContractClass};
 use katana_primitives::contract::{ContractAddress, GenericContractInfo, StorageKey};
 use katana_primitives::receipt::Receipt;
-use katana_primitives::trace::TxExecInfo;
+use katana_primitives::trace::{BlockExecutionArtifacts, TxExecInfo};
 use katana_primitives::transaction::{Tx, TxHash, TxNumber};
+use katana_primitives::Felt;
 
 use crate::codecs::{Compress, Decode, Decompress, Encode};
 use crate::models::block::StoredBlockBodyIndices;
 use crate::models::contract::{ContractClassChange, ContractInfoChangeList, ContractNonceChange};
+use crate::models::event::EventKeyEntry;
 use crate::models::list::BlockList;
 use crate::models::stage::{StageCheckpoint, StageId};
 use crate::models::storage::{ContractStorageEntry, ContractStorageKey, StorageEntry};
@@ -53,7 +55,7 @@ pub enum TableType {
     DupSort,
 }
 
-pub const NUM_TABLES: usize = 33;
+pub const NUM_TABLES: usize = 36;
 
 /// Macro to declare `libmdbx` tables.
 #[macro_export]
@@ -186,7 +188,10 @@ define_tables_enum! {[
     (StoragesTrieHistory, TableType::DupSort),
     (ClassesTrieChangeSet, TableType::Table),
     (ContractsTrieChangeSet, TableType::Table),
-    (StoragesTrieChangeSet, TableType::Table)
+    (StoragesTrieChangeSet, TableType::Table),
+    (EventEmitters, TableType::DupSort),
+    (EventKeys, TableType::DupSort),
+    (BlockArtifacts, TableType::Table)
 ]}
 
 tables! {
@@ -265,7 +270,18 @@ tables! {
     /// contract trie change set
     ContractsTrieChangeSet: (TrieDatabaseKey) => BlockList,
     /// contract storage trie change set
-    StoragesTrieChangeSet: (TrieDatabaseKey) => BlockList
+    StoragesTrieChangeSet: (TrieDatabaseKey) => BlockList,
+
+    /// Stores the number of the transactions that emitted at least one event from the given
+    /// contract.
+    EventEmitters: (ContractAddress, TxNumber) => EventKeyEntry,
+
+    /// Stores the number of the transactions that emitted at least one event with the given first
+    /// key.
+    EventKeys: (Felt, TxNumber) => EventKeyEntry,
+
+    /// Stores the SNOS inputs, ie the accessed state, of the blocks produced in provable mode.
+    BlockArtifacts: (BlockNumber) => BlockExecutionArtifacts
 }
 
 impl Trie for ClassesTrie {
@@ -324,6 +340,9 @@ mod tests {
         assert_eq!(Tables::ALL[30].name(), ClassesTrieChangeSet::NAME);
         assert_eq!(Tables::ALL[31].name(), ContractsTrieChangeSet::NAME);
         assert_eq!(Tables::ALL[32].name(), StoragesTrieChangeSet::NAME);
+        assert_eq!(Tables::ALL[33].name(), EventEmitters::NAME);
+        assert_eq!(Tables::ALL[34].name(), EventKeys::NAME);
+        assert_eq!(Tables::ALL[35].name(), BlockArtifacts::NAME);
 
         assert_eq!(Tables::Headers.table_type(), TableType::Table);
         assert_eq!(Tables::BlockHashes.table_type(), TableType::Table);
@@ -358,6 +377,9 @@ mod tests {
         assert_eq!(Tables::ClassesTrieChangeSet.table_type(), TableType::Table);
         assert_eq!(Tables::ContractsTrieChangeSet.table_type(), TableType::Table);
         assert_eq!(Tables::StoragesTrieChangeSet.table_type(), TableType::Table);
+        assert_eq!(Tables::EventEmitters.table_type(), TableType::DupSort);
+        assert_eq!(Tables::EventKeys.table_type(), TableType::DupSort);
+        assert_eq!(Tables::BlockArtifacts.table_type(), TableType::Table);
     }
 
     use katana_primitives::address;
@@ -366,7 +388,7 @@ mod tests {
     use katana_primitives::contract::{ContractAddress, GenericContractInfo};
     use katana_primitives::fee::{PriceUnit, TxFeeInfo};
     use katana_primitives::receipt::{InvokeTxReceipt, Receipt};
-    use katana_primitives::trace::TxExecInfo;
+    use katana_primitives::trace::{BlockExecutionArtifacts, TxExecInfo};
     use katana_primitives::transaction::{InvokeTx, Tx, TxHash, TxNumber};
     use starknet::macros::felt;
 
@@ -375,6 +397,7 @@ mod tests {
     use crate::models::contract::{
         ContractClassChange, ContractInfoChangeList, ContractNonceChange,
     };
+    use crate::models::event::EventKeyEntry;
     use crate::models::list::BlockList;
     use crate::models::storage::{ContractStorageEntry, ContractStorageKey, StorageEntry};
     use crate::models::trie::{
@@ -437,6 +460,7 @@ mod tests {
             (Tx, Tx::Invoke(InvokeTx::V1(Default::default()))),
             (BlockNumber, 99),
             (TxExecInfo, TxExecInfo::default()),
+            (BlockExecutionArtifacts, BlockExecutionArtifacts::default()),
             (CompiledClassHash, felt!("211")),
             (CompiledClass, CompiledClass::Legacy(Default::default())),
             (GenericContractInfo, GenericContractInfo::default()),
@@ -446,6 +470,7 @@ mod tests {
             (ContractClassChange, ContractClassChange::default()),
             (BlockList, BlockList::default()),
             (ContractStorageEntry, ContractStorageEntry::default()),
+            (EventKeyEntry, EventKeyEntry { tx_number: 77 }),
             (Receipt, Receipt::Invoke(InvokeTxReceipt {
                 revert_error: None,
                 events: Vec::new(),
@@ -213,7 +213,7 @@ where
 {
     tx: Tx,
     /// List of key-value pairs that has been added throughout the duration of the trie
-    /// transaction.
+    /// transaction. The removed keys are recorded with an empty value.
     ///
     /// This will be used to create the trie snapshot.
     write_cache: HashMap<TrieDatabaseKey, ByteVec>,
@@ -264,7 +264,8 @@ where
         }
 
         for key in keys_to_remove {
-            let _ = self.tx.delete::<Tb>(key, None)?;
+            let _ = self.tx.delete::<Tb>(key.clone(), None)?;
+            self.write_cache.insert(key, ByteVec::new());
         }
 
         Ok(())
@@ -309,8 +310,11 @@ where
         let key = to_db_key(key);
 
         let old_value = self.tx.get::<Tb>(key.clone())?;
-        self.tx.delete::<Tb>(key, None)?;
+        self.tx.delete::<Tb>(key.clone(), None)?;
 
+        // the removal is part of the snapshot, so that the key can be removed again when unwinding
+        // the trie to a block after it
+        self.write_cache.insert(key, ByteVec::new());
         Ok(old_value)
     }
 
@@ -81,7 +81,8 @@ where
                 .seek_by_key_subkey(num, key.clone())?
                 .expect("entry should exist if in change set");
 
-            if entry.key == key {
+            // an empty value means the key was removed at that block
+            if entry.key == key && !entry.value.is_empty() {
                 return Ok(Some(entry.value));
             }
         }
@@ -99,6 +99,27 @@ pub enum ProviderError {
     #[error("State proof not supported")]
     StateProofNotSupported,
 
+    /// Error when trying to unwind to a block that is ahead of the latest block.
+    #[error("Unable to unwind to block {target} as it's ahead of the latest block {latest}")]
+    UnwindTargetAhead {
+        /// The block number to unwind to.
+        target: BlockNumber,
+        /// The latest block number.
+        latest: BlockNumber,
+    },
+
+    /// Error when trying to unwind to a block whose state diffs have been pruned.
+    #[error("Unable to unwind to block {target} as the blocks up to {pruned} are pruned")]
+    UnwindTargetPruned {
+        /// The block number to unwind to.
+        target: BlockNumber,
+        /// The last block whose state diffs are pruned.
+        pruned: BlockNumber,
+    },
+
+    #[error("Unwinding not supported")]
+    UnwindNotSupported,
+
     #[error("State root not found")]
     StateRootNotFound,
 
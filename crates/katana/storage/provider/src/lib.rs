@@ -10,14 +10,20 @@ use katana_primitives::block::{
 use katana_primitives::class::{ClassHash, CompiledClass, CompiledClassHash, ContractClass};
 use katana_primitives::contract::{ContractAddress, StorageKey, StorageValue};
 use katana_primitives::env::BlockEnv;
+use katana_primitives::event::{ContinuationToken, EventFilter};
 use katana_primitives::receipt::Receipt;
 use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
-use katana_primitives::trace::TxExecInfo;
+use katana_primitives::trace::{BlockExecutionArtifacts, TxExecInfo};
 use katana_primitives::transaction::{TxHash, TxNumber, TxWithHash};
 use katana_primitives::Felt;
-use traits::block::{BlockIdReader, BlockStatusProvider, BlockWriter};
+use traits::block::{
+    BlockArtifactsProvider, BlockArtifactsWriter, BlockIdReader, BlockStatusProvider,
+    BlockStatusWriter, BlockWriter,
+};
 use traits::contract::{ContractClassWriter, ContractClassWriterExt};
 use traits::env::BlockEnvProvider;
+use traits::event::{EventProvider, EventsPage};
+use traits::prune::PruneWriter;
 use traits::stage::StageCheckpointProvider;
 use traits::state::StateWriter;
 use traits::transaction::{TransactionStatusProvider, TransactionTraceProvider};
@@ -132,6 +138,19 @@ where
     }
 }
 
+impl<Db> BlockStatusWriter for BlockchainProvider<Db>
+where
+    Db: BlockStatusWriter,
+{
+    fn update_block_status(
+        &self,
+        block_number: BlockNumber,
+        status: FinalityStatus,
+    ) -> ProviderResult<()> {
+        self.provider.update_block_status(block_number, status)
+    }
+}
+
 impl<Db> BlockWriter for BlockchainProvider<Db>
 where
     Db: BlockWriter,
@@ -145,6 +164,38 @@ where
     ) -> ProviderResult<()> {
         self.provider.insert_block_with_states_and_receipts(block, states, receipts, executions)
     }
+
+    fn unwind_to(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        self.provider.unwind_to(block_number)
+    }
+}
+
+impl<Db> BlockArtifactsProvider for BlockchainProvider<Db>
+where
+    Db: BlockArtifactsProvider,
+{
+    fn block_execution_artifacts(
+        &self,
+        id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<BlockExecutionArtifacts>> {
+        self.provider.block_execution_artifacts(id)
+    }
+}
+
+impl<Db> BlockArtifactsWriter for BlockchainProvider<Db>
+where
+    Db: BlockArtifactsWriter,
+{
+    fn insert_block_with_artifacts(
+        &self,
+        block: SealedBlockWithStatus,
+        states: StateUpdatesWithClasses,
+        receipts: Vec<Receipt>,
+        executions: Vec<TxExecInfo>,
+        artifacts: BlockExecutionArtifacts,
+    ) -> ProviderResult<()> {
+        self.provider.insert_block_with_artifacts(block, states, receipts, executions, artifacts)
+    }
 }
 
 impl<Db> TransactionProvider for BlockchainProvider<Db>
@@ -246,6 +297,38 @@ where
     }
 }
 
+impl<Db> EventProvider for BlockchainProvider<Db>
+where
+    Db: EventProvider,
+{
+    fn events_in_range(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+        filter: &EventFilter,
+        continuation_token: Option<ContinuationToken>,
+        chunk_size: usize,
+    ) -> ProviderResult<EventsPage> {
+        self.provider.events_in_range(block_range, filter, continuation_token, chunk_size)
+    }
+}
+
+impl<Db> PruneWriter for BlockchainProvider<Db>
+where
+    Db: PruneWriter,
+{
+    fn prune_receipts(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        self.provider.prune_receipts(block_number)
+    }
+
+    fn prune_traces(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        self.provider.prune_traces(block_number)
+    }
+
+    fn prune_state_diffs(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        self.provider.prune_state_diffs(block_number)
+    }
+}
+
 impl<Db> StateFactoryProvider for BlockchainProvider<Db>
 where
     Db: StateFactoryProvider,
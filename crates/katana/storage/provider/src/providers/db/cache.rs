@@ -0,0 +1,59 @@
+use std::fmt;
+use std::sync::Arc;
+
+use hashlink::LruCache;
+use katana_primitives::class::{ClassHash, ContractClass};
+use parking_lot::Mutex;
+
+/// The default number of classes kept in a [`ClassCache`].
+pub const DEFAULT_CLASS_CACHE_SIZE: usize = 256;
+
+/// A cache of the most recently used contract classes.
+///
+/// Classes are stored compressed in the database, so decompressing and deserializing them on every
+/// access is expensive. The cache is shared by all the state providers created from the same
+/// [`DbProvider`](super::DbProvider).
+#[derive(Clone)]
+pub struct ClassCache {
+    classes: Arc<Mutex<LruCache<ClassHash, ContractClass>>>,
+}
+
+impl ClassCache {
+    /// Creates a new cache that holds up to `capacity` classes.
+    pub fn new(capacity: usize) -> Self {
+        Self { classes: Arc::new(Mutex::new(LruCache::new(capacity))) }
+    }
+
+    /// Returns the maximum number of classes held by the cache.
+    pub fn capacity(&self) -> usize {
+        self.classes.lock().capacity()
+    }
+
+    pub fn get(&self, hash: &ClassHash) -> Option<ContractClass> {
+        self.classes.lock().get(hash).cloned()
+    }
+
+    pub fn insert(&self, hash: ClassHash, class: ContractClass) {
+        self.classes.lock().insert(hash, class);
+    }
+
+    pub fn remove(&self, hash: &ClassHash) {
+        self.classes.lock().remove(hash);
+    }
+}
+
+impl Default for ClassCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CLASS_CACHE_SIZE)
+    }
+}
+
+impl fmt::Debug for ClassCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let classes = self.classes.lock();
+        f.debug_struct("ClassCache")
+            .field("len", &classes.len())
+            .field("capacity", &classes.capacity())
+            .finish()
+    }
+}
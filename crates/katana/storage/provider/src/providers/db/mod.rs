@@ -1,7 +1,8 @@
+pub mod cache;
 pub mod state;
 pub mod trie;
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fmt::Debug;
 use std::ops::{Range, RangeInclusive};
 
@@ -9,14 +10,16 @@ use katana_db::abstraction::{Database, DbCursor, DbCursorMut, DbDupSortCursor, D
 use katana_db::error::DatabaseError;
 use katana_db::init_ephemeral_db;
 use katana_db::mdbx::DbEnv;
+use katana_db::memory::MemoryDb;
 use katana_db::models::block::StoredBlockBodyIndices;
 use katana_db::models::contract::{
     ContractClassChange, ContractInfoChangeList, ContractNonceChange,
 };
+use katana_db::models::event::EventKeyEntry;
 use katana_db::models::list::BlockList;
 use katana_db::models::stage::StageCheckpoint;
 use katana_db::models::storage::{ContractStorageEntry, ContractStorageKey, StorageEntry};
-use katana_db::tables::{self, DupSort, Table};
+use katana_db::tables::{self, DupSort, Table, Trie};
 use katana_db::utils::KeyValue;
 use katana_primitives::block::{
     Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithTxHashes, FinalityStatus, Header,
@@ -27,17 +30,22 @@ use katana_primitives::contract::{
     ContractAddress, GenericContractInfo, Nonce, StorageKey, StorageValue,
 };
 use katana_primitives::env::BlockEnv;
+use katana_primitives::event::{ContinuationToken, EventFilter};
 use katana_primitives::receipt::Receipt;
 use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
-use katana_primitives::trace::TxExecInfo;
+use katana_primitives::trace::{BlockExecutionArtifacts, TxExecInfo};
 use katana_primitives::transaction::{TxHash, TxNumber, TxWithHash};
+use katana_primitives::Felt;
 
+use self::cache::ClassCache;
 use crate::error::ProviderError;
 use crate::traits::block::{
-    BlockHashProvider, BlockNumberProvider, BlockProvider, BlockStatusProvider, BlockWriter,
-    HeaderProvider,
+    BlockArtifactsProvider, BlockArtifactsWriter, BlockHashProvider, BlockNumberProvider,
+    BlockProvider, BlockStatusProvider, BlockStatusWriter, BlockWriter, HeaderProvider,
 };
 use crate::traits::env::BlockEnvProvider;
+use crate::traits::event::{EmittedEvent, EventProvider, EventsPage};
+use crate::traits::prune::PruneWriter;
 use crate::traits::stage::StageCheckpointProvider;
 use crate::traits::state::{StateFactoryProvider, StateProvider};
 use crate::traits::state_update::StateUpdateProvider;
@@ -50,12 +58,19 @@ use crate::ProviderResult;
 /// A provider implementation that uses a persistent database as the backend.
 // TODO: remove the default generic type
 #[derive(Debug, Clone)]
-pub struct DbProvider<Db: Database = DbEnv>(Db);
+pub struct DbProvider<Db: Database = DbEnv>(Db, ClassCache);
 
 impl<Db: Database> DbProvider<Db> {
     /// Creates a new [`DbProvider`] from the given [`DbEnv`].
     pub fn new(db: Db) -> Self {
-        Self(db)
+        Self(db, ClassCache::default())
+    }
+
+    /// Sets the maximum number of decompressed classes kept in memory. Default is
+    /// [`DEFAULT_CLASS_CACHE_SIZE`](cache::DEFAULT_CLASS_CACHE_SIZE).
+    pub fn with_class_cache_size(mut self, capacity: usize) -> Self {
+        self.1 = ClassCache::new(capacity);
+        self
     }
 }
 
@@ -63,13 +78,30 @@ impl DbProvider<DbEnv> {
     /// Creates a new [`DbProvider`] using an ephemeral database.
     pub fn new_ephemeral() -> Self {
         let db = init_ephemeral_db().expect("Failed to initialize ephemeral database");
-        Self(db)
+        Self::new(db)
+    }
+}
+
+impl DbProvider<MemoryDb> {
+    /// Creates a new [`DbProvider`] using an in-memory database, which can be forked.
+    pub fn new_in_memory() -> Self {
+        Self::new(MemoryDb::new())
+    }
+
+    /// Creates a new provider with the same content as this one, without copying its database.
+    /// Both providers are independent of each other once forked.
+    ///
+    /// See [`MemoryDb::fork`] for how the content is shared.
+    pub fn fork(&self) -> Self {
+        // the classes are cached by hash, so a class only declared by one of the providers must
+        // not be cached for the other
+        Self(self.0.fork(), ClassCache::new(self.1.capacity()))
     }
 }
 
 impl<Db: Database> StateFactoryProvider for DbProvider<Db> {
     fn latest(&self) -> ProviderResult<Box<dyn StateProvider>> {
-        Ok(Box::new(self::state::LatestStateProvider::new(self.0.tx()?)))
+        Ok(Box::new(self::state::LatestStateProvider::new(self.0.tx()?, self.1.clone())))
     }
 
     fn historical(
@@ -92,7 +124,9 @@ impl<Db: Database> StateFactoryProvider for DbProvider<Db> {
 
         let Some(num) = block_number else { return Ok(None) };
 
-        Ok(Some(Box::new(self::state::HistoricalStateProvider::new(self.0.tx()?, num))))
+        let tx = self.0.tx()?;
+        let provider = self::state::HistoricalStateProvider::new(tx, num, self.1.clone());
+        Ok(Some(Box::new(provider)))
     }
 }
 
@@ -255,6 +289,61 @@ impl<Db: Database> BlockStatusProvider for DbProvider<Db> {
     }
 }
 
+impl<Db: Database> BlockStatusWriter for DbProvider<Db> {
+    fn update_block_status(
+        &self,
+        block_number: BlockNumber,
+        status: FinalityStatus,
+    ) -> ProviderResult<()> {
+        self.0.update(move |db_tx| -> ProviderResult<()> {
+            if db_tx.get::<tables::Headers>(block_number)?.is_none() {
+                return Err(ProviderError::MissingBlockHeader(block_number));
+            }
+
+            db_tx.put::<tables::BlockStatusses>(block_number, status)?;
+            Ok(())
+        })?
+    }
+}
+
+impl<Db: Database> BlockArtifactsProvider for DbProvider<Db> {
+    fn block_execution_artifacts(
+        &self,
+        id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<BlockExecutionArtifacts>> {
+        let db_tx = self.0.tx()?;
+
+        let block_num = match id {
+            BlockHashOrNumber::Num(num) => Some(num),
+            BlockHashOrNumber::Hash(hash) => db_tx.get::<tables::BlockNumbers>(hash)?,
+        };
+
+        let Some(block_num) = block_num else { return Ok(None) };
+
+        let artifacts = db_tx.get::<tables::BlockArtifacts>(block_num)?;
+        db_tx.commit()?;
+        Ok(artifacts)
+    }
+}
+
+impl<Db: Database> BlockArtifactsWriter for DbProvider<Db> {
+    fn insert_block_with_artifacts(
+        &self,
+        block: SealedBlockWithStatus,
+        states: StateUpdatesWithClasses,
+        receipts: Vec<Receipt>,
+        executions: Vec<TxExecInfo>,
+        artifacts: BlockExecutionArtifacts,
+    ) -> ProviderResult<()> {
+        self.0.update(move |db_tx| -> ProviderResult<()> {
+            let block_number = block.block.header.number;
+            insert_block::<Db>(db_tx, block, states, receipts, executions)?;
+            db_tx.put::<tables::BlockArtifacts>(block_number, artifacts)?;
+            Ok(())
+        })?
+    }
+}
+
 // A helper function that iterates over all entries in a dupsort table and collects the
 // results into `V`. If `key` is not found, `V::default()` is returned.
 fn dup_entries<Db, Tb, V, T>(
@@ -275,6 +364,104 @@ where
         .unwrap_or_default())
 }
 
+/// Returns the contracts deployed at `block_num` and the contracts whose class was replaced at
+/// `block_num`, in that order.
+///
+/// Both are recorded as class changes, so a change is a class replacement if the class of the
+/// contract had already been set in an earlier block.
+fn class_changes<Db: Database>(
+    db_tx: &<Db as Database>::Tx,
+    block_num: BlockNumber,
+) -> ProviderResult<(BTreeMap<ContractAddress, ClassHash>, BTreeMap<ContractAddress, ClassHash>)> {
+    let changes = dup_entries::<Db, tables::ClassChangeHistory, Vec<ContractClassChange>, _>(
+        db_tx,
+        block_num,
+        |entry| Ok(Some(entry?.1)),
+    )?;
+
+    let mut deployed_contracts = BTreeMap::new();
+    let mut replaced_classes = BTreeMap::new();
+
+    for ContractClassChange { contract_address, class_hash } in changes {
+        let is_replaced = block_num > 0
+            && db_tx
+                .get::<tables::ContractInfoChangeSet>(contract_address)?
+                .is_some_and(|set| set.class_change_list.rank(block_num - 1) > 0);
+
+        if is_replaced {
+            replaced_classes.insert(contract_address, class_hash);
+        } else {
+            deployed_contracts.insert(contract_address, class_hash);
+        }
+    }
+
+    Ok((deployed_contracts, replaced_classes))
+}
+
+/// Reverts the latest entries of the trie table `Tb` to their values at `block_number`, and
+/// removes the changes of the blocks after it, up to `latest`, from the trie history and change
+/// set.
+///
+/// Every version of the trie entries is kept in the trie history - removals included - so only the
+/// entries written by the unwound blocks have to be reverted, each to its most recent version prior
+/// to `block_number`. Unwinding isn't constant-time: for the `k` entries written by the unwound
+/// blocks, it costs `O(k log n)` table operations on tables of `n` entries, plus truncating the
+/// change list of each of these entries. It doesn't depend on the number of entries of the trie
+/// that weren't changed.
+fn unwind_trie<Db, Tb>(
+    db_tx: &<Db as Database>::TxMut,
+    block_number: BlockNumber,
+    latest: BlockNumber,
+) -> ProviderResult<()>
+where
+    Db: Database,
+    Tb: Trie,
+{
+    // the entries written by the unwound blocks
+    let mut keys = HashSet::new();
+
+    for num in (block_number + 1)..=latest {
+        if let Some(walker) = db_tx.cursor_dup::<Tb::History>()?.walk_dup(Some(num), None)? {
+            for entry in walker {
+                keys.insert(entry?.1.key);
+            }
+        }
+
+        db_tx.delete::<Tb::History>(num, None)?;
+    }
+
+    for key in keys {
+        let mut list = db_tx.get::<Tb::Changeset>(key.clone())?.unwrap_or_default();
+        list.truncate(block_number);
+
+        let Some(num) = list.max() else {
+            // the entry didn't exist yet at `block_number`
+            db_tx.delete::<Tb>(key.clone(), None)?;
+            db_tx.delete::<Tb::Changeset>(key, None)?;
+            continue;
+        };
+
+        let mut history_cursor = db_tx.cursor_dup::<Tb::History>()?;
+        let entry = history_cursor
+            .seek_by_key_subkey(num, key.clone())?
+            .filter(|entry| entry.key == key)
+            .ok_or_else(|| {
+                ProviderError::Other(format!("missing trie history entry at block {num}"))
+            })?;
+
+        // an empty value means the entry was removed at that block
+        if entry.value.is_empty() {
+            db_tx.delete::<Tb>(key.clone(), None)?;
+        } else {
+            db_tx.put::<Tb>(key.clone(), entry.value)?;
+        }
+
+        db_tx.put::<Tb::Changeset>(key, list)?;
+    }
+
+    Ok(())
+}
+
 impl<Db: Database> StateUpdateProvider for DbProvider<Db> {
     fn state_update(&self, block_id: BlockHashOrNumber) -> ProviderResult<Option<StateUpdates>> {
         let db_tx = self.0.tx()?;
@@ -291,15 +478,7 @@ impl<Db: Database> StateUpdateProvider for DbProvider<Db> {
                 Ok(Some((contract_address, nonce)))
             })?;
 
-            let deployed_contracts = dup_entries::<
-                Db,
-                tables::ClassChangeHistory,
-                BTreeMap<ContractAddress, ClassHash>,
-                _,
-            >(&db_tx, block_num, |entry| {
-                let (_, ContractClassChange { contract_address, class_hash }) = entry?;
-                Ok(Some((contract_address, class_hash)))
-            })?;
+            let (deployed_contracts, replaced_classes) = class_changes::<Db>(&db_tx, block_num)?;
 
             let mut declared_classes = BTreeMap::new();
             let mut deprecated_declared_classes = BTreeSet::new();
@@ -348,7 +527,7 @@ impl<Db: Database> StateUpdateProvider for DbProvider<Db> {
                 deployed_contracts,
                 declared_classes,
                 deprecated_declared_classes,
-                replaced_classes: BTreeMap::default(),
+                replaced_classes,
             }))
         } else {
             Ok(None)
@@ -393,16 +572,7 @@ impl<Db: Database> StateUpdateProvider for DbProvider<Db> {
         let block_num = self.block_number_by_id(block_id)?;
 
         if let Some(block_num) = block_num {
-            let deployed_contracts = dup_entries::<
-                Db,
-                tables::ClassChangeHistory,
-                BTreeMap<ContractAddress, ClassHash>,
-                _,
-            >(&db_tx, block_num, |entry| {
-                let (_, ContractClassChange { contract_address, class_hash }) = entry?;
-                Ok(Some((contract_address, class_hash)))
-            })?;
-
+            let (deployed_contracts, _) = class_changes::<Db>(&db_tx, block_num)?;
             db_tx.commit()?;
             Ok(Some(deployed_contracts))
         } else {
@@ -603,11 +773,10 @@ impl<Db: Database> ReceiptProvider for DbProvider<Db> {
     fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Receipt>> {
         let db_tx = self.0.tx()?;
         if let Some(num) = db_tx.get::<tables::TxNumbers>(hash)? {
-            let receipt =
-                db_tx.get::<tables::Receipts>(num)?.ok_or(ProviderError::MissingTxReceipt(num))?;
-
+            // the receipt may have been pruned
+            let receipt = db_tx.get::<tables::Receipts>(num)?;
             db_tx.commit()?;
-            Ok(Some(receipt))
+            Ok(receipt)
         } else {
             Ok(None)
         }
@@ -636,6 +805,651 @@ impl<Db: Database> ReceiptProvider for DbProvider<Db> {
     }
 }
 
+impl<Db: Database> EventProvider for DbProvider<Db> {
+    fn events_in_range(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+        filter: &EventFilter,
+        continuation_token: Option<ContinuationToken>,
+        chunk_size: usize,
+    ) -> ProviderResult<EventsPage> {
+        let db_tx = self.0.tx()?;
+
+        let start = match continuation_token {
+            Some(token) if token.block_n >= *block_range.start() => token,
+            _ => ContinuationToken { block_n: *block_range.start(), ..Default::default() },
+        };
+
+        // if the filter constrains the first key or the emitter of the events, only the
+        // transactions that are indexed under one of those keys, or under that emitter, need to be
+        // examined.
+        let first_keys = filter.keys.as_ref().and_then(|keys| keys.first());
+        let index = match (first_keys.filter(|keys| !keys.is_empty()), filter.address) {
+            (Some(keys), _) => Some(EventIndex::Keys(keys)),
+            (None, Some(address)) => Some(EventIndex::Emitter(address)),
+            (None, None) => None,
+        };
+
+        let page = match index {
+            Some(idx) => {
+                indexed_events_in_range::<Db>(&db_tx, block_range, start, idx, filter, chunk_size)?
+            }
+            None => scan_events_in_range::<Db>(&db_tx, block_range, start, filter, chunk_size)?,
+        };
+
+        db_tx.commit()?;
+        Ok(page)
+    }
+}
+
+/// Examines the events of every transaction in `block_range`, starting from the event pointed by
+/// `start`.
+fn scan_events_in_range<Db: Database>(
+    db_tx: &<Db as Database>::Tx,
+    block_range: RangeInclusive<BlockNumber>,
+    start: ContinuationToken,
+    filter: &EventFilter,
+    chunk_size: usize,
+) -> ProviderResult<EventsPage> {
+    let mut events = Vec::new();
+
+    for block_number in start.block_n..=*block_range.end() {
+        // the range is allowed to go past the latest block
+        let Some(block_hash) = db_tx.get::<tables::BlockHashes>(block_number)? else { break };
+        let indices = db_tx
+            .get::<tables::BlockBodyIndices>(block_number)?
+            .ok_or(ProviderError::MissingBlockBodyIndices(block_number))?;
+
+        let first_tx = if block_number == start.block_n { start.txn_n } else { 0 };
+
+        for tx_idx in first_tx..indices.tx_count {
+            let event_n = if block_number == start.block_n && tx_idx == start.txn_n {
+                start.event_n
+            } else {
+                0
+            };
+
+            let position = ContinuationToken { block_n: block_number, txn_n: tx_idx, event_n };
+            let tx_number = indices.tx_offset + tx_idx;
+
+            let token = fetch_tx_events::<Db>(
+                db_tx,
+                position,
+                block_hash,
+                tx_number,
+                filter,
+                chunk_size,
+                &mut events,
+            )?;
+
+            if token.is_some() {
+                return Ok(EventsPage { events, continuation_token: token });
+            }
+        }
+    }
+
+    Ok(EventsPage { events, continuation_token: None })
+}
+
+/// The index used to find the transactions whose events may match a filter.
+#[derive(Debug, Clone, Copy)]
+enum EventIndex<'a> {
+    /// The transactions that emitted at least one event whose first key is one of these.
+    Keys(&'a [Felt]),
+    /// The transactions that emitted at least one event from this contract.
+    Emitter(ContractAddress),
+}
+
+/// Examines the events of the transactions in `block_range` that are found in the event `index`,
+/// starting from the event pointed by `start`.
+fn indexed_events_in_range<Db: Database>(
+    db_tx: &<Db as Database>::Tx,
+    block_range: RangeInclusive<BlockNumber>,
+    start: ContinuationToken,
+    index: EventIndex<'_>,
+    filter: &EventFilter,
+    chunk_size: usize,
+) -> ProviderResult<EventsPage> {
+    // the range is allowed to go past the latest block
+    let latest = db_tx.cursor::<tables::BlockHashes>()?.last()?.map(|(num, _)| num);
+    let end_block = match latest {
+        Some(latest) => latest.min(*block_range.end()),
+        None => return Ok(EventsPage::default()),
+    };
+
+    if start.block_n > end_block {
+        return Ok(EventsPage::default());
+    }
+
+    let start_indices = db_tx
+        .get::<tables::BlockBodyIndices>(start.block_n)?
+        .ok_or(ProviderError::MissingBlockBodyIndices(start.block_n))?;
+    let end_indices = db_tx
+        .get::<tables::BlockBodyIndices>(end_block)?
+        .ok_or(ProviderError::MissingBlockBodyIndices(end_block))?;
+
+    let first_tx = start_indices.tx_offset + start.txn_n;
+    let end_tx = end_indices.tx_offset + end_indices.tx_count;
+
+    let tx_numbers = match index {
+        EventIndex::Keys(keys) => {
+            let keys = keys.iter().copied();
+            indexed_tx_numbers::<Db, tables::EventKeys>(db_tx, keys, first_tx..end_tx)?
+        }
+        EventIndex::Emitter(address) => {
+            indexed_tx_numbers::<Db, tables::EventEmitters>(db_tx, [address], first_tx..end_tx)?
+        }
+    };
+
+    let mut events = Vec::new();
+    // the block number, hash and tx offset of the block of the last examined transaction
+    let mut current_block: Option<(BlockNumber, BlockHash, TxNumber)> = None;
+
+    for tx_number in tx_numbers {
+        let block_number = db_tx
+            .get::<tables::TxBlocks>(tx_number)?
+            .ok_or(ProviderError::MissingTxBlock(tx_number))?;
+
+        let (block_hash, tx_offset) = match current_block {
+            Some((num, hash, tx_offset)) if num == block_number => (hash, tx_offset),
+            _ => {
+                let hash = db_tx
+                    .get::<tables::BlockHashes>(block_number)?
+                    .ok_or(ProviderError::MissingBlockHash(block_number))?;
+                let indices = db_tx
+                    .get::<tables::BlockBodyIndices>(block_number)?
+                    .ok_or(ProviderError::MissingBlockBodyIndices(block_number))?;
+
+                current_block = Some((block_number, hash, indices.tx_offset));
+                (hash, indices.tx_offset)
+            }
+        };
+
+        let event_n = if tx_number == first_tx { start.event_n } else { 0 };
+        let txn_n = tx_number - tx_offset;
+        let position = ContinuationToken { block_n: block_number, txn_n, event_n };
+
+        let token = fetch_tx_events::<Db>(
+            db_tx,
+            position,
+            block_hash,
+            tx_number,
+            filter,
+            chunk_size,
+            &mut events,
+        )?;
+
+        if token.is_some() {
+            return Ok(EventsPage { events, continuation_token: token });
+        }
+    }
+
+    Ok(EventsPage { events, continuation_token: None })
+}
+
+/// Returns the number of the transactions in `range` that are indexed under one of the `keys` in
+/// the event index table `Tb`.
+fn indexed_tx_numbers<Db, Tb>(
+    db_tx: &<Db as Database>::Tx,
+    keys: impl IntoIterator<Item = Tb::Key>,
+    range: Range<TxNumber>,
+) -> ProviderResult<BTreeSet<TxNumber>>
+where
+    Db: Database,
+    Tb: DupSort<SubKey = TxNumber, Value = EventKeyEntry> + Debug,
+    Tb::Key: PartialEq,
+{
+    // the entries of a key are sorted by their tx number, so that we can stop at the first entry
+    // that is out of the range.
+    let mut tx_numbers = BTreeSet::new();
+    let mut cursor = db_tx.cursor_dup::<Tb>()?;
+
+    for key in keys {
+        if let Some(walker) = cursor.walk_dup(Some(key.clone()), Some(range.start))? {
+            for entry in walker {
+                let (entry_key, EventKeyEntry { tx_number }) = entry?;
+
+                if entry_key != key || tx_number >= range.end {
+                    break;
+                }
+
+                tx_numbers.insert(tx_number);
+            }
+        }
+    }
+
+    Ok(tx_numbers)
+}
+
+/// Stores an executed block along with its execution output, in the given transaction.
+fn insert_block<Db: Database>(
+    db_tx: &<Db as Database>::TxMut,
+    block: SealedBlockWithStatus,
+    states: StateUpdatesWithClasses,
+    receipts: Vec<Receipt>,
+    executions: Vec<TxExecInfo>,
+) -> ProviderResult<()> {
+    let block_hash = block.block.hash;
+    let block_number = block.block.header.number;
+
+    let block_header = block.block.header;
+    let transactions = block.block.body;
+
+    let tx_count = transactions.len() as u64;
+    let tx_offset = db_tx.entries::<tables::Transactions>()? as u64;
+    let block_body_indices = StoredBlockBodyIndices { tx_offset, tx_count };
+
+    db_tx.put::<tables::BlockHashes>(block_number, block_hash)?;
+    db_tx.put::<tables::BlockNumbers>(block_hash, block_number)?;
+    db_tx.put::<tables::BlockStatusses>(block_number, block.status)?;
+
+    db_tx.put::<tables::Headers>(block_number, block_header)?;
+    db_tx.put::<tables::BlockBodyIndices>(block_number, block_body_indices)?;
+
+    // Store base transaction details
+    for (i, transaction) in transactions.into_iter().enumerate() {
+        let tx_number = tx_offset + i as u64;
+        let tx_hash = transaction.hash;
+
+        db_tx.put::<tables::TxHashes>(tx_number, tx_hash)?;
+        db_tx.put::<tables::TxNumbers>(tx_hash, tx_number)?;
+        db_tx.put::<tables::TxBlocks>(tx_number, block_number)?;
+        db_tx.put::<tables::Transactions>(tx_number, transaction.transaction)?;
+    }
+
+    // Store transaction receipts
+    for (i, receipt) in receipts.into_iter().enumerate() {
+        let tx_number = tx_offset + i as u64;
+
+        insert_event_indices::<Db>(db_tx, tx_number, &receipt)?;
+        db_tx.put::<tables::Receipts>(tx_number, receipt)?;
+    }
+
+    // Store execution traces
+    for (i, execution) in executions.into_iter().enumerate() {
+        let tx_number = tx_offset + i as u64;
+        db_tx.put::<tables::TxTraces>(tx_number, execution)?;
+    }
+
+    // insert classes
+
+    for (class_hash, compiled_hash) in states.state_updates.declared_classes {
+        db_tx.put::<tables::CompiledClassHashes>(class_hash, compiled_hash)?;
+
+        db_tx.put::<tables::ClassDeclarationBlock>(class_hash, block_number)?;
+        db_tx.put::<tables::ClassDeclarations>(block_number, class_hash)?
+    }
+
+    for class_hash in states.state_updates.deprecated_declared_classes {
+        db_tx.put::<tables::ClassDeclarationBlock>(class_hash, block_number)?;
+        db_tx.put::<tables::ClassDeclarations>(block_number, class_hash)?
+    }
+
+    for (class_hash, class) in states.classes {
+        db_tx.put::<tables::Classes>(class_hash, class)?;
+    }
+
+    // insert storage changes
+    {
+        let mut storage_cursor = db_tx.cursor_dup_mut::<tables::ContractStorage>()?;
+        for (addr, entries) in states.state_updates.storage_updates {
+            let entries = entries.into_iter().map(|(key, value)| StorageEntry { key, value });
+
+            for entry in entries {
+                match storage_cursor.seek_by_key_subkey(addr, entry.key)? {
+                    Some(current) if current.key == entry.key => {
+                        storage_cursor.delete_current()?;
+                    }
+
+                    _ => {}
+                }
+
+                // update block list in the change set
+                let changeset_key = ContractStorageKey { contract_address: addr, key: entry.key };
+                let list = db_tx.get::<tables::StorageChangeSet>(changeset_key.clone())?;
+
+                let updated_list = match list {
+                    Some(mut list) => {
+                        list.insert(block_number);
+                        list
+                    }
+                    // create a new block list if it doesn't yet exist, and insert the block
+                    // number
+                    None => BlockList::from([block_number]),
+                };
+
+                db_tx.put::<tables::StorageChangeSet>(changeset_key, updated_list)?;
+                storage_cursor.upsert(addr, entry)?;
+
+                let storage_change_sharded_key =
+                    ContractStorageKey { contract_address: addr, key: entry.key };
+
+                db_tx.put::<tables::StorageChangeHistory>(
+                    block_number,
+                    ContractStorageEntry { key: storage_change_sharded_key, value: entry.value },
+                )?;
+            }
+        }
+    }
+
+    // update contract info
+
+    let class_changes = states.state_updates.deployed_contracts.into_iter();
+    let class_changes = class_changes.chain(states.state_updates.replaced_classes);
+
+    for (addr, class_hash) in class_changes {
+        let value = if let Some(info) = db_tx.get::<tables::ContractInfo>(addr)? {
+            GenericContractInfo { class_hash, ..info }
+        } else {
+            GenericContractInfo { class_hash, ..Default::default() }
+        };
+
+        let new_change_set =
+            if let Some(mut change_set) = db_tx.get::<tables::ContractInfoChangeSet>(addr)? {
+                change_set.class_change_list.insert(block_number);
+                change_set
+            } else {
+                ContractInfoChangeList {
+                    class_change_list: BlockList::from([block_number]),
+                    ..Default::default()
+                }
+            };
+
+        db_tx.put::<tables::ContractInfo>(addr, value)?;
+
+        let class_change_key = ContractClassChange { contract_address: addr, class_hash };
+        db_tx.put::<tables::ClassChangeHistory>(block_number, class_change_key)?;
+        db_tx.put::<tables::ContractInfoChangeSet>(addr, new_change_set)?;
+    }
+
+    for (addr, nonce) in states.state_updates.nonce_updates {
+        let value = if let Some(info) = db_tx.get::<tables::ContractInfo>(addr)? {
+            GenericContractInfo { nonce, ..info }
+        } else {
+            GenericContractInfo { nonce, ..Default::default() }
+        };
+
+        let new_change_set =
+            if let Some(mut change_set) = db_tx.get::<tables::ContractInfoChangeSet>(addr)? {
+                change_set.nonce_change_list.insert(block_number);
+                change_set
+            } else {
+                ContractInfoChangeList {
+                    nonce_change_list: BlockList::from([block_number]),
+                    ..Default::default()
+                }
+            };
+
+        db_tx.put::<tables::ContractInfo>(addr, value)?;
+
+        let nonce_change_key = ContractNonceChange { contract_address: addr, nonce };
+        db_tx.put::<tables::NonceChangeHistory>(block_number, nonce_change_key)?;
+        db_tx.put::<tables::ContractInfoChangeSet>(addr, new_change_set)?;
+    }
+
+    Ok(())
+}
+
+/// Indexes the transaction `tx_number` under the first keys and the emitters of the events in its
+/// `receipt`.
+fn insert_event_indices<Db: Database>(
+    db_tx: &<Db as Database>::TxMut,
+    tx_number: TxNumber,
+    receipt: &Receipt,
+) -> ProviderResult<()> {
+    let entry = EventKeyEntry { tx_number };
+
+    let keys = receipt.events().iter().filter_map(|e| e.keys.first().copied());
+    for key in keys.collect::<BTreeSet<_>>() {
+        db_tx.put::<tables::EventKeys>(key, entry)?;
+    }
+
+    let emitters = receipt.events().iter().map(|e| e.from_address);
+    for address in emitters.collect::<BTreeSet<_>>() {
+        db_tx.put::<tables::EventEmitters>(address, entry)?;
+    }
+
+    Ok(())
+}
+
+/// Removes the entries added by [`insert_event_indices`] for the transaction `tx_number`.
+fn remove_event_indices<Db: Database>(
+    db_tx: &<Db as Database>::TxMut,
+    tx_number: TxNumber,
+    receipt: &Receipt,
+) -> ProviderResult<()> {
+    let entry = EventKeyEntry { tx_number };
+
+    let keys = receipt.events().iter().filter_map(|e| e.keys.first().copied());
+    for key in keys.collect::<BTreeSet<_>>() {
+        db_tx.delete::<tables::EventKeys>(key, Some(entry))?;
+    }
+
+    let emitters = receipt.events().iter().map(|e| e.from_address);
+    for address in emitters.collect::<BTreeSet<_>>() {
+        db_tx.delete::<tables::EventEmitters>(address, Some(entry))?;
+    }
+
+    Ok(())
+}
+
+/// Appends the events of the transaction `tx_number` that match the `filter` to `events`, starting
+/// from the event pointed by `position`.
+///
+/// Returns a token that points to the next event to be examined if `events` is already full.
+fn fetch_tx_events<Db: Database>(
+    db_tx: &<Db as Database>::Tx,
+    position: ContinuationToken,
+    block_hash: BlockHash,
+    tx_number: TxNumber,
+    filter: &EventFilter,
+    chunk_size: usize,
+    events: &mut Vec<EmittedEvent>,
+) -> ProviderResult<Option<ContinuationToken>> {
+    // the receipt may have been pruned
+    let Some(receipt) = db_tx.get::<tables::Receipts>(tx_number)? else { return Ok(None) };
+
+    let first_event = position.event_n as usize;
+    if receipt.events().len() <= first_event {
+        return Ok(None);
+    }
+
+    let transaction_hash =
+        db_tx.get::<tables::TxHashes>(tx_number)?.ok_or(ProviderError::MissingTxHash(tx_number))?;
+
+    for (event_idx, event) in receipt.events().iter().enumerate().skip(first_event) {
+        if events.len() >= chunk_size {
+            return Ok(Some(ContinuationToken { event_n: event_idx as u64, ..position }));
+        }
+
+        if filter.matches(event) {
+            let event = event.clone();
+            let block_number = position.block_n;
+            events.push(EmittedEvent { block_number, block_hash, transaction_hash, event });
+        }
+    }
+
+    Ok(None)
+}
+
+impl<Db: Database> PruneWriter for DbProvider<Db> {
+    fn prune_receipts(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        self.0.update(|db_tx| -> ProviderResult<()> {
+            let Some(end) = pruning_end::<Db>(db_tx, block_number)? else { return Ok(()) };
+
+            // the receipts before the first remaining one have already been pruned
+            let first = db_tx.cursor::<tables::Receipts>()?.first()?.map(|(num, _)| num);
+            let Some(first) = first else { return Ok(()) };
+
+            for tx_number in first..end {
+                let Some(receipt) = db_tx.get::<tables::Receipts>(tx_number)? else { continue };
+                remove_event_indices::<Db>(db_tx, tx_number, &receipt)?;
+                db_tx.delete::<tables::Receipts>(tx_number, None)?;
+            }
+
+            Ok(())
+        })?
+    }
+
+    fn prune_traces(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        self.0.update(|db_tx| -> ProviderResult<()> {
+            let Some(end) = pruning_end::<Db>(db_tx, block_number)? else { return Ok(()) };
+
+            // the traces before the first remaining one have already been pruned
+            let first = db_tx.cursor::<tables::TxTraces>()?.first()?.map(|(num, _)| num);
+            let Some(first) = first else { return Ok(()) };
+
+            for tx_number in first..end {
+                db_tx.delete::<tables::TxTraces>(tx_number, None)?;
+            }
+
+            Ok(())
+        })?
+    }
+
+    fn prune_state_diffs(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        self.0.update(|db_tx| -> ProviderResult<()> {
+            // the state diffs of the blocks up to the checkpoint have already been pruned
+            let id = STATE_DIFFS_PRUNING_CHECKPOINT.to_string();
+            let checkpoint = db_tx.get::<tables::StageCheckpoints>(id.clone())?;
+            let start = checkpoint.map_or(0, |checkpoint| checkpoint.block + 1);
+
+            if start > block_number {
+                return Ok(());
+            }
+
+            prune_storage_changes::<Db>(db_tx, start, block_number)?;
+            prune_contract_changes::<Db>(db_tx, start, block_number)?;
+
+            db_tx.put::<tables::StageCheckpoints>(id, StageCheckpoint { block: block_number })?;
+            Ok(())
+        })?
+    }
+}
+
+/// The id of the checkpoint that stores the last block whose state diffs have been pruned.
+const STATE_DIFFS_PRUNING_CHECKPOINT: &str = "StateDiffsPruning";
+
+/// Prunes the storage changes of the slots that were changed in the blocks `start..=end`.
+fn prune_storage_changes<Db: Database>(
+    db_tx: &<Db as Database>::TxMut,
+    start: BlockNumber,
+    end: BlockNumber,
+) -> ProviderResult<()> {
+    let mut storage_keys = BTreeSet::new();
+
+    for num in start..=end {
+        if let Some(walker) =
+            db_tx.cursor_dup::<tables::StorageChangeHistory>()?.walk_dup(Some(num), None)?
+        {
+            for entry in walker {
+                let (_, ContractStorageEntry { key, .. }) = entry?;
+                storage_keys.insert((key.contract_address, key.key));
+            }
+        }
+    }
+
+    for (contract_address, key) in storage_keys {
+        let changeset_key = ContractStorageKey { contract_address, key };
+        let Some(mut list) = db_tx.get::<tables::StorageChangeSet>(changeset_key.clone())? else {
+            continue;
+        };
+
+        let mut cursor = db_tx.cursor_dup_mut::<tables::StorageChangeHistory>()?;
+        for num in superseded_changes(&mut list, end) {
+            if let Some(entry) = cursor.seek_by_key_subkey(num, changeset_key.clone())? {
+                if entry.key == changeset_key {
+                    cursor.delete_current()?;
+                }
+            }
+        }
+
+        db_tx.put::<tables::StorageChangeSet>(changeset_key, list)?;
+    }
+
+    Ok(())
+}
+
+/// Prunes the nonce and class hash changes of the contracts that were changed in the blocks
+/// `start..=end`.
+fn prune_contract_changes<Db: Database>(
+    db_tx: &<Db as Database>::TxMut,
+    start: BlockNumber,
+    end: BlockNumber,
+) -> ProviderResult<()> {
+    let mut contracts = BTreeSet::new();
+
+    for num in start..=end {
+        if let Some(walker) =
+            db_tx.cursor_dup::<tables::NonceChangeHistory>()?.walk_dup(Some(num), None)?
+        {
+            for entry in walker {
+                contracts.insert(entry?.1.contract_address);
+            }
+        }
+
+        if let Some(walker) =
+            db_tx.cursor_dup::<tables::ClassChangeHistory>()?.walk_dup(Some(num), None)?
+        {
+            for entry in walker {
+                contracts.insert(entry?.1.contract_address);
+            }
+        }
+    }
+
+    for address in contracts {
+        let Some(mut change_set) = db_tx.get::<tables::ContractInfoChangeSet>(address)? else {
+            continue;
+        };
+
+        let mut cursor = db_tx.cursor_dup_mut::<tables::NonceChangeHistory>()?;
+        for num in superseded_changes(&mut change_set.nonce_change_list, end) {
+            if let Some(entry) = cursor.seek_by_key_subkey(num, address)? {
+                if entry.contract_address == address {
+                    cursor.delete_current()?;
+                }
+            }
+        }
+
+        let mut cursor = db_tx.cursor_dup_mut::<tables::ClassChangeHistory>()?;
+        for num in superseded_changes(&mut change_set.class_change_list, end) {
+            if let Some(entry) = cursor.seek_by_key_subkey(num, address)? {
+                if entry.contract_address == address {
+                    cursor.delete_current()?;
+                }
+            }
+        }
+
+        db_tx.put::<tables::ContractInfoChangeSet>(address, change_set)?;
+    }
+
+    Ok(())
+}
+
+/// Removes from `list` the changes that are superseded by a more recent change made at or before
+/// the block `block_number`, and returns them.
+fn superseded_changes(list: &mut BlockList, block_number: BlockNumber) -> Vec<BlockNumber> {
+    // the most recent change at or before `block_number` is the one at position `rank - 1`
+    let rank = list.rank(block_number);
+    let superseded = (0..rank.saturating_sub(1)).filter_map(|n| list.select(n)).collect::<Vec<_>>();
+
+    for num in &superseded {
+        list.remove(*num);
+    }
+
+    superseded
+}
+
+/// Returns the number of the first transaction after the block `block_number`, or `None` if the
+/// block doesn't exist.
+fn pruning_end<Db: Database>(
+    db_tx: &<Db as Database>::TxMut,
+    block_number: BlockNumber,
+) -> ProviderResult<Option<TxNumber>> {
+    let indices = db_tx.get::<tables::BlockBodyIndices>(block_number)?;
+    Ok(indices.map(|indices| indices.tx_offset + indices.tx_count))
+}
+
 impl<Db: Database> BlockEnvProvider for DbProvider<Db> {
     fn block_env_at(&self, block_id: BlockHashOrNumber) -> ProviderResult<Option<BlockEnv>> {
         let Some(header) = self.header(block_id)? else { return Ok(None) };
@@ -658,169 +1472,209 @@ impl<Db: Database> BlockWriter for DbProvider<Db> {
         receipts: Vec<Receipt>,
         executions: Vec<TxExecInfo>,
     ) -> ProviderResult<()> {
-        self.0.update(move |db_tx| -> ProviderResult<()> {
-            let block_hash = block.block.hash;
-            let block_number = block.block.header.number;
+        self.0
+            .update(move |db_tx| insert_block::<Db>(db_tx, block, states, receipts, executions))?
+    }
 
-            let block_header = block.block.header;
-            let transactions = block.block.body;
+    fn unwind_to(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        let db_tx = self.0.tx_mut()?;
 
-            let tx_count = transactions.len() as u64;
-            let tx_offset = db_tx.entries::<tables::Transactions>()? as u64;
-            let block_body_indices = StoredBlockBodyIndices { tx_offset, tx_count };
+        let latest = db_tx.cursor::<tables::BlockHashes>()?.last()?.map(|(num, _)| num);
+        let latest = latest.ok_or(ProviderError::MissingLatestBlockNumber)?;
 
-            db_tx.put::<tables::BlockHashes>(block_number, block_hash)?;
-            db_tx.put::<tables::BlockNumbers>(block_hash, block_number)?;
-            db_tx.put::<tables::BlockStatusses>(block_number, block.status)?;
+        if block_number > latest {
+            return Err(ProviderError::UnwindTargetAhead { target: block_number, latest });
+        }
 
-            db_tx.put::<tables::Headers>(block_number, block_header)?;
-            db_tx.put::<tables::BlockBodyIndices>(block_number, block_body_indices)?;
+        // the state of the pruned blocks can't be restored
+        let id = STATE_DIFFS_PRUNING_CHECKPOINT.to_string();
+        let pruned = db_tx.get::<tables::StageCheckpoints>(id)?.map(|checkpoint| checkpoint.block);
 
-            // Store base transaction details
-            for (i, transaction) in transactions.into_iter().enumerate() {
-                let tx_number = tx_offset + i as u64;
-                let tx_hash = transaction.hash;
+        if let Some(pruned) = pruned.filter(|pruned| block_number < *pruned) {
+            return Err(ProviderError::UnwindTargetPruned { target: block_number, pruned });
+        }
 
-                db_tx.put::<tables::TxHashes>(tx_number, tx_hash)?;
-                db_tx.put::<tables::TxNumbers>(tx_hash, tx_number)?;
-                db_tx.put::<tables::TxBlocks>(tx_number, block_number)?;
-                db_tx.put::<tables::Transactions>(tx_number, transaction.transaction)?;
-            }
+        // the storage slots and contracts whose values were changed by the unwound blocks
+        let mut storage_keys = BTreeSet::new();
+        let mut contracts = BTreeSet::new();
+        // the classes declared in the unwound blocks, which must be evicted from the class cache
+        let mut removed_classes = Vec::new();
+
+        for num in (block_number + 1)..=latest {
+            let hash = db_tx
+                .get::<tables::BlockHashes>(num)?
+                .ok_or(ProviderError::MissingBlockHash(num))?;
+            let indices = db_tx
+                .get::<tables::BlockBodyIndices>(num)?
+                .ok_or(ProviderError::MissingBlockBodyIndices(num))?;
+
+            for tx_number in Range::from(indices) {
+                if let Some(tx_hash) = db_tx.get::<tables::TxHashes>(tx_number)? {
+                    db_tx.delete::<tables::TxNumbers>(tx_hash, None)?;
+                }
 
-            // Store transaction receipts
-            for (i, receipt) in receipts.into_iter().enumerate() {
-                let tx_number = tx_offset + i as u64;
-                db_tx.put::<tables::Receipts>(tx_number, receipt)?;
-            }
+                db_tx.delete::<tables::TxHashes>(tx_number, None)?;
+                db_tx.delete::<tables::TxBlocks>(tx_number, None)?;
+                db_tx.delete::<tables::Transactions>(tx_number, None)?;
 
-            // Store execution traces
-            for (i, execution) in executions.into_iter().enumerate() {
-                let tx_number = tx_offset + i as u64;
-                db_tx.put::<tables::TxTraces>(tx_number, execution)?;
-            }
+                if let Some(receipt) = db_tx.get::<tables::Receipts>(tx_number)? {
+                    remove_event_indices::<Db>(db_tx, tx_number, &receipt)?;
+                }
 
-            // insert classes
+                db_tx.delete::<tables::Receipts>(tx_number, None)?;
+                db_tx.delete::<tables::TxTraces>(tx_number, None)?;
+            }
 
-            for (class_hash, compiled_hash) in states.state_updates.declared_classes {
-                db_tx.put::<tables::CompiledClassHashes>(class_hash, compiled_hash)?;
+            db_tx.delete::<tables::BlockHashes>(num, None)?;
+            db_tx.delete::<tables::BlockNumbers>(hash, None)?;
+            db_tx.delete::<tables::BlockStatusses>(num, None)?;
+            db_tx.delete::<tables::Headers>(num, None)?;
+            db_tx.delete::<tables::BlockBodyIndices>(num, None)?;
+            db_tx.delete::<tables::BlockArtifacts>(num, None)?;
+
+            // remove the classes declared in the block
+            let mut classes = Vec::new();
+            if let Some(walker) =
+                db_tx.cursor_dup::<tables::ClassDeclarations>()?.walk_dup(Some(num), None)?
+            {
+                for entry in walker {
+                    classes.push(entry?.1);
+                }
+            }
 
-                db_tx.put::<tables::ClassDeclarationBlock>(class_hash, block_number)?;
-                db_tx.put::<tables::ClassDeclarations>(block_number, class_hash)?
+            for class_hash in classes {
+                db_tx.delete::<tables::ClassDeclarationBlock>(class_hash, None)?;
+                db_tx.delete::<tables::CompiledClassHashes>(class_hash, None)?;
+                db_tx.delete::<tables::CompiledClasses>(class_hash, None)?;
+                db_tx.delete::<tables::Classes>(class_hash, None)?;
+                removed_classes.push(class_hash);
             }
 
-            for class_hash in states.state_updates.deprecated_declared_classes {
-                db_tx.put::<tables::ClassDeclarationBlock>(class_hash, block_number)?;
-                db_tx.put::<tables::ClassDeclarations>(block_number, class_hash)?
+            if let Some(walker) =
+                db_tx.cursor_dup::<tables::StorageChangeHistory>()?.walk_dup(Some(num), None)?
+            {
+                for entry in walker {
+                    let (_, ContractStorageEntry { key, .. }) = entry?;
+                    storage_keys.insert((key.contract_address, key.key));
+                }
             }
 
-            for (class_hash, class) in states.classes {
-                db_tx.put::<tables::Classes>(class_hash, class)?;
+            if let Some(walker) =
+                db_tx.cursor_dup::<tables::NonceChangeHistory>()?.walk_dup(Some(num), None)?
+            {
+                for entry in walker {
+                    contracts.insert(entry?.1.contract_address);
+                }
             }
 
-            // insert storage changes
+            if let Some(walker) =
+                db_tx.cursor_dup::<tables::ClassChangeHistory>()?.walk_dup(Some(num), None)?
             {
-                let mut storage_cursor = db_tx.cursor_dup_mut::<tables::ContractStorage>()?;
-                for (addr, entries) in states.state_updates.storage_updates {
-                    let entries =
-                        entries.into_iter().map(|(key, value)| StorageEntry { key, value });
-
-                    for entry in entries {
-                        match storage_cursor.seek_by_key_subkey(addr, entry.key)? {
-                            Some(current) if current.key == entry.key => {
-                                storage_cursor.delete_current()?;
-                            }
-
-                            _ => {}
-                        }
+                for entry in walker {
+                    contracts.insert(entry?.1.contract_address);
+                }
+            }
 
-                        // update block list in the change set
-                        let changeset_key =
-                            ContractStorageKey { contract_address: addr, key: entry.key };
-                        let list = db_tx.get::<tables::StorageChangeSet>(changeset_key.clone())?;
-
-                        let updated_list = match list {
-                            Some(mut list) => {
-                                list.insert(block_number);
-                                list
-                            }
-                            // create a new block list if it doesn't yet exist, and insert the block
-                            // number
-                            None => BlockList::from([block_number]),
-                        };
-
-                        db_tx.put::<tables::StorageChangeSet>(changeset_key, updated_list)?;
-                        storage_cursor.upsert(addr, entry)?;
-
-                        let storage_change_sharded_key =
-                            ContractStorageKey { contract_address: addr, key: entry.key };
-
-                        db_tx.put::<tables::StorageChangeHistory>(
-                            block_number,
-                            ContractStorageEntry {
-                                key: storage_change_sharded_key,
-                                value: entry.value,
-                            },
-                        )?;
-                    }
+            db_tx.delete::<tables::ClassDeclarations>(num, None)?;
+            db_tx.delete::<tables::StorageChangeHistory>(num, None)?;
+            db_tx.delete::<tables::NonceChangeHistory>(num, None)?;
+            db_tx.delete::<tables::ClassChangeHistory>(num, None)?;
+        }
+
+        // restore the storage values to what they were at `block_number`
+        for (address, key) in storage_keys {
+            let changeset_key = ContractStorageKey { contract_address: address, key };
+            let mut list =
+                db_tx.get::<tables::StorageChangeSet>(changeset_key.clone())?.unwrap_or_default();
+            list.truncate(block_number);
+
+            let mut storage_cursor = db_tx.cursor_dup_mut::<tables::ContractStorage>()?;
+            if let Some(entry) = storage_cursor.seek_by_key_subkey(address, key)? {
+                if entry.key == key {
+                    storage_cursor.delete_current()?;
                 }
             }
 
-            // update contract info
+            if let Some(num) = list.max() {
+                let mut history_cursor = db_tx.cursor_dup::<tables::StorageChangeHistory>()?;
+                let entry = history_cursor
+                    .seek_by_key_subkey(num, changeset_key.clone())?
+                    .filter(|entry| entry.key == changeset_key)
+                    .ok_or(ProviderError::MissingStorageChangeEntry {
+                        block: num,
+                        contract_address: address,
+                        storage_key: key,
+                    })?;
+
+                storage_cursor.upsert(address, StorageEntry { key, value: entry.value })?;
+                db_tx.put::<tables::StorageChangeSet>(changeset_key, list)?;
+            } else {
+                db_tx.delete::<tables::StorageChangeSet>(changeset_key, None)?;
+            }
+        }
 
-            for (addr, class_hash) in states.state_updates.deployed_contracts {
-                let value = if let Some(info) = db_tx.get::<tables::ContractInfo>(addr)? {
-                    GenericContractInfo { class_hash, ..info }
-                } else {
-                    GenericContractInfo { class_hash, ..Default::default() }
-                };
+        // restore the nonces and class hashes to what they were at `block_number`
+        for address in contracts {
+            let Some(mut change_set) = db_tx.get::<tables::ContractInfoChangeSet>(address)? else {
+                continue;
+            };
 
-                let new_change_set = if let Some(mut change_set) =
-                    db_tx.get::<tables::ContractInfoChangeSet>(addr)?
-                {
-                    change_set.class_change_list.insert(block_number);
-                    change_set
-                } else {
-                    ContractInfoChangeList {
-                        class_change_list: BlockList::from([block_number]),
-                        ..Default::default()
-                    }
-                };
+            change_set.nonce_change_list.truncate(block_number);
+            change_set.class_change_list.truncate(block_number);
+
+            let nonce = match change_set.nonce_change_list.max() {
+                Some(num) => {
+                    let mut cursor = db_tx.cursor_dup::<tables::NonceChangeHistory>()?;
+                    let entry = cursor
+                        .seek_by_key_subkey(num, address)?
+                        .filter(|entry| entry.contract_address == address)
+                        .ok_or(ProviderError::MissingContractNonceChangeEntry {
+                            block: num,
+                            contract_address: address,
+                        })?;
+                    entry.nonce
+                }
+                None => Nonce::default(),
+            };
 
-                db_tx.put::<tables::ContractInfo>(addr, value)?;
+            let class_hash = match change_set.class_change_list.max() {
+                Some(num) => {
+                    let mut cursor = db_tx.cursor_dup::<tables::ClassChangeHistory>()?;
+                    let entry = cursor
+                        .seek_by_key_subkey(num, address)?
+                        .filter(|entry| entry.contract_address == address)
+                        .ok_or(ProviderError::MissingContractClassChangeEntry {
+                            block: num,
+                            contract_address: address,
+                        })?;
+                    entry.class_hash
+                }
+                None => ClassHash::default(),
+            };
 
-                let class_change_key = ContractClassChange { contract_address: addr, class_hash };
-                db_tx.put::<tables::ClassChangeHistory>(block_number, class_change_key)?;
-                db_tx.put::<tables::ContractInfoChangeSet>(addr, new_change_set)?;
+            if change_set.nonce_change_list.is_empty() && change_set.class_change_list.is_empty() {
+                db_tx.delete::<tables::ContractInfo>(address, None)?;
+                db_tx.delete::<tables::ContractInfoChangeSet>(address, None)?;
+            } else {
+                db_tx.put::<tables::ContractInfo>(
+                    address,
+                    GenericContractInfo { nonce, class_hash },
+                )?;
+                db_tx.put::<tables::ContractInfoChangeSet>(address, change_set)?;
             }
+        }
 
-            for (addr, nonce) in states.state_updates.nonce_updates {
-                let value = if let Some(info) = db_tx.get::<tables::ContractInfo>(addr)? {
-                    GenericContractInfo { nonce, ..info }
-                } else {
-                    GenericContractInfo { nonce, ..Default::default() }
-                };
-
-                let new_change_set = if let Some(mut change_set) =
-                    db_tx.get::<tables::ContractInfoChangeSet>(addr)?
-                {
-                    change_set.nonce_change_list.insert(block_number);
-                    change_set
-                } else {
-                    ContractInfoChangeList {
-                        nonce_change_list: BlockList::from([block_number]),
-                        ..Default::default()
-                    }
-                };
+        unwind_trie::<Db, tables::ClassesTrie>(&db_tx, block_number, latest)?;
+        unwind_trie::<Db, tables::ContractsTrie>(&db_tx, block_number, latest)?;
+        unwind_trie::<Db, tables::StoragesTrie>(&db_tx, block_number, latest)?;
 
-                db_tx.put::<tables::ContractInfo>(addr, value)?;
+        db_tx.commit()?;
 
-                let nonce_change_key = ContractNonceChange { contract_address: addr, nonce };
-                db_tx.put::<tables::NonceChangeHistory>(block_number, nonce_change_key)?;
-                db_tx.put::<tables::ContractInfoChangeSet>(addr, new_change_set)?;
-            }
+        for class_hash in removed_classes {
+            self.1.remove(&class_hash);
+        }
 
-            Ok(())
-        })?
+        Ok(())
     }
 }
 
@@ -848,24 +1702,28 @@ impl<Db: Database> StageCheckpointProvider for DbProvider<Db> {
 mod tests {
     use std::collections::BTreeMap;
 
-    use katana_primitives::address;
+    use katana_db::memory::MemoryDb;
     use katana_primitives::block::{
         Block, BlockHashOrNumber, FinalityStatus, Header, SealedBlockWithStatus,
     };
     use katana_primitives::contract::ContractAddress;
     use katana_primitives::fee::{PriceUnit, TxFeeInfo};
+    use katana_primitives::genesis::constant::DEFAULT_ACCOUNT_CLASS;
     use katana_primitives::receipt::{InvokeTxReceipt, Receipt};
     use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
     use katana_primitives::trace::TxExecInfo;
     use katana_primitives::transaction::{InvokeTx, Tx, TxHash, TxWithHash};
+    use katana_primitives::{address, Felt};
     use starknet::macros::felt;
 
     use super::DbProvider;
     use crate::traits::block::{
         BlockHashProvider, BlockNumberProvider, BlockProvider, BlockStatusProvider, BlockWriter,
     };
-    use crate::traits::state::StateFactoryProvider;
+    use crate::traits::contract::ContractClassProvider;
+    use crate::traits::state::{StateFactoryProvider, StateRootProvider};
     use crate::traits::transaction::TransactionProvider;
+    use crate::traits::trie::TrieWriter;
 
     fn create_dummy_block() -> SealedBlockWithStatus {
         let header = Header { parent_hash: 199u8.into(), number: 0, ..Default::default() };
@@ -927,7 +1785,7 @@ mod tests {
     }
 
     fn create_db_provider() -> DbProvider {
-        DbProvider(katana_db::mdbx::test_utils::create_test_db())
+        DbProvider::new(katana_db::mdbx::test_utils::create_test_db())
     }
 
     #[test]
@@ -1087,4 +1945,312 @@ mod tests {
         assert_eq!(storage1, felt!("100"));
         assert_eq!(storage2, felt!("200"));
     }
+
+    #[test]
+    fn unwind_to_block() {
+        let provider = create_db_provider();
+
+        let block0 = create_dummy_block();
+        let header = Header { parent_hash: block0.block.hash, number: 1, ..Default::default() };
+        let block1 = Block {
+            header,
+            body: vec![TxWithHash {
+                hash: 25u8.into(),
+                transaction: Tx::Invoke(InvokeTx::V1(Default::default())),
+            }],
+        }
+        .seal();
+        let block1 = SealedBlockWithStatus { block: block1, status: FinalityStatus::AcceptedOnL2 };
+
+        let mut state_updates2 = create_dummy_state_updates_2();
+        state_updates2.state_updates.deployed_contracts.insert(address!("3"), felt!("55"));
+        state_updates2
+            .state_updates
+            .storage_updates
+            .insert(address!("2"), BTreeMap::from([(felt!("1"), felt!("300"))]));
+
+        let blocks = [(block0.clone(), create_dummy_state_updates()), (block1, state_updates2)];
+        for (block, state_updates) in blocks {
+            BlockWriter::insert_block_with_states_and_receipts(
+                &provider,
+                block,
+                state_updates,
+                vec![Receipt::Invoke(InvokeTxReceipt {
+                    revert_error: None,
+                    events: Vec::new(),
+                    messages_sent: Vec::new(),
+                    execution_resources: Default::default(),
+                    fee: TxFeeInfo {
+                        gas_consumed: 0,
+                        gas_price: 0,
+                        overall_fee: 0,
+                        unit: PriceUnit::Wei,
+                    },
+                })],
+                vec![TxExecInfo::default()],
+            )
+            .expect("failed to insert block");
+        }
+
+        assert_eq!(provider.latest_number().unwrap(), 1);
+
+        // unwinding to a block ahead of the latest block must fail
+        assert!(provider.unwind_to(2).is_err());
+
+        provider.unwind_to(0).expect("failed to unwind");
+
+        assert_eq!(provider.latest_number().unwrap(), 0);
+        assert_eq!(provider.latest_hash().unwrap(), block0.block.hash);
+        assert!(provider.block(BlockHashOrNumber::Num(1)).unwrap().is_none());
+        assert!(provider.transaction_by_hash(25u8.into()).unwrap().is_none());
+        assert!(provider.transaction_by_hash(24u8.into()).unwrap().is_some());
+
+        let state_prov = StateFactoryProvider::latest(&provider).unwrap();
+
+        assert_eq!(state_prov.nonce(address!("1")).unwrap(), Some(felt!("1")));
+        assert_eq!(state_prov.nonce(address!("2")).unwrap(), Some(felt!("2")));
+        assert_eq!(state_prov.nonce(address!("3")).unwrap(), None);
+
+        assert_eq!(state_prov.class_hash_of_contract(address!("1")).unwrap(), Some(felt!("3")));
+        assert_eq!(state_prov.class_hash_of_contract(address!("3")).unwrap(), None);
+
+        assert_eq!(state_prov.storage(address!("1"), felt!("1")).unwrap(), Some(felt!("1")));
+        assert_eq!(state_prov.storage(address!("1"), felt!("2")).unwrap(), Some(felt!("2")));
+        assert_eq!(state_prov.storage(address!("2"), felt!("1")).unwrap(), None);
+
+        // new blocks can be inserted on top of the unwound chain
+        let header = Header { parent_hash: block0.block.hash, number: 1, ..Default::default() };
+        let block = Block { header, body: Vec::new() }.seal();
+        let block = SealedBlockWithStatus { block, status: FinalityStatus::AcceptedOnL2 };
+        BlockWriter::insert_block_with_states_and_receipts(
+            &provider,
+            block,
+            Default::default(),
+            Vec::new(),
+            Vec::new(),
+        )
+        .expect("failed to insert block");
+
+        assert_eq!(provider.latest_number().unwrap(), 1);
+    }
+
+    #[test]
+    fn unwound_classes_are_evicted_from_cache() {
+        let provider = create_db_provider();
+
+        let class_hash = felt!("0x1234");
+        let class = DEFAULT_ACCOUNT_CLASS.clone();
+
+        let block0 = Block { header: Header::default(), body: Vec::new() }.seal();
+        let header = Header { parent_hash: block0.hash, number: 1, ..Default::default() };
+        let block1 = Block { header, body: Vec::new() }.seal();
+
+        let states = StateUpdatesWithClasses {
+            state_updates: StateUpdates {
+                declared_classes: BTreeMap::from([(class_hash, felt!("0x1"))]),
+                ..Default::default()
+            },
+            classes: BTreeMap::from([(class_hash, class.clone())]),
+        };
+
+        for (block, states) in [(block0, Default::default()), (block1, states)] {
+            let block = SealedBlockWithStatus { block, status: FinalityStatus::AcceptedOnL2 };
+            BlockWriter::insert_block_with_states_and_receipts(
+                &provider,
+                block,
+                states,
+                Vec::new(),
+                Vec::new(),
+            )
+            .expect("failed to insert block");
+        }
+
+        // the first read populates the cache and the second one is served from it
+        let state = StateFactoryProvider::latest(&provider).unwrap();
+        assert_eq!(state.class(class_hash).unwrap(), Some(class.clone()));
+        assert_eq!(state.class(class_hash).unwrap(), Some(class));
+        drop(state);
+
+        provider.unwind_to(0).expect("failed to unwind");
+
+        let state = StateFactoryProvider::latest(&provider).unwrap();
+        assert_eq!(state.class(class_hash).unwrap(), None);
+    }
+
+    #[test]
+    fn state_root_of_partial_contract_updates() {
+        let provider = create_db_provider();
+        let states = create_dummy_state_updates();
+
+        BlockWriter::insert_block_with_states_and_receipts(
+            &provider,
+            create_dummy_block(),
+            states.clone(),
+            Vec::new(),
+            Vec::new(),
+        )
+        .expect("failed to insert block");
+
+        let updates = &states.state_updates;
+        provider.trie_insert_declared_classes(0, &updates.declared_classes).unwrap();
+        provider.trie_insert_contract_updates(0, updates).unwrap();
+
+        // only the storage of the contract is updated, its nonce and class hash are those of the
+        // latest state
+        let storage = BTreeMap::from([(felt!("1"), felt!("100"))]);
+        let updates = StateUpdates {
+            storage_updates: BTreeMap::from([(address!("1"), storage)]),
+            ..Default::default()
+        };
+        let contracts_root = provider.trie_insert_contract_updates(1, &updates).unwrap();
+
+        let state = StateFactoryProvider::latest(&provider).unwrap();
+        assert_eq!(state.contracts_root().unwrap(), contracts_root);
+
+        // the tries are the same as if all the updates had been applied at once
+        let mut merged = create_dummy_state_updates().state_updates;
+        merged.storage_updates.get_mut(&address!("1")).unwrap().insert(felt!("1"), felt!("100"));
+
+        let expected = create_db_provider();
+        let classes_root =
+            expected.trie_insert_declared_classes(0, &merged.declared_classes).unwrap();
+        let expected_root = expected.trie_insert_contract_updates(0, &merged).unwrap();
+
+        assert_eq!(contracts_root, expected_root);
+        assert_eq!(state.classes_root().unwrap(), classes_root);
+        assert_eq!(
+            state.storage_root(address!("1")).unwrap(),
+            StateFactoryProvider::latest(&expected).unwrap().storage_root(address!("1")).unwrap()
+        );
+    }
+
+    #[test]
+    fn unwind_tries() {
+        let provider = create_db_provider();
+
+        let storage = |entries: &[(Felt, Felt)]| StateUpdates {
+            storage_updates: BTreeMap::from([(
+                address!("1"),
+                BTreeMap::from_iter(entries.to_vec()),
+            )]),
+            ..Default::default()
+        };
+
+        let block0 = create_dummy_state_updates().state_updates;
+        // clearing a storage slot removes its leaf from the storage trie
+        let block1 = storage(&[(felt!("2"), felt!("0")), (felt!("3"), felt!("3"))]);
+        let block2 = storage(&[(felt!("1"), felt!("10"))]);
+
+        let mut parent_hash = Felt::ZERO;
+        let mut insert_block = |number: u64, updates: &StateUpdates| {
+            provider.trie_insert_declared_classes(number, &updates.declared_classes).unwrap();
+            provider.trie_insert_contract_updates(number, updates).unwrap();
+
+            let header = Header { parent_hash, number, ..Default::default() };
+            let block = Block { header, body: Vec::new() }.seal();
+            parent_hash = block.hash;
+
+            let block = SealedBlockWithStatus { block, status: FinalityStatus::AcceptedOnL2 };
+            let states =
+                StateUpdatesWithClasses { state_updates: updates.clone(), ..Default::default() };
+            BlockWriter::insert_block_with_states_and_receipts(
+                &provider,
+                block,
+                states,
+                Vec::new(),
+                Vec::new(),
+            )
+            .expect("failed to insert block");
+        };
+
+        let roots = |provider: &DbProvider| {
+            let state = StateFactoryProvider::latest(provider).unwrap();
+            let storage_root = state.storage_root(address!("1")).unwrap();
+            (state.contracts_root().unwrap(), state.classes_root().unwrap(), storage_root)
+        };
+
+        insert_block(0, &block0);
+        let roots0 = roots(&provider);
+        insert_block(1, &block1);
+        let roots1 = roots(&provider);
+        insert_block(2, &block2);
+
+        provider.unwind_to(0).expect("failed to unwind");
+        assert_eq!(roots(&provider), roots0);
+
+        // the entries removed by the unwound blocks are restored, so the same blocks produce the
+        // same tries again
+        insert_block(1, &block1);
+        assert_eq!(roots(&provider), roots1);
+
+        let state = StateFactoryProvider::historical(&provider, BlockHashOrNumber::Num(0));
+        let state = state.unwrap().unwrap();
+        assert_eq!(state.contracts_root().unwrap(), roots0.0);
+        assert_eq!(state.storage_root(address!("1")).unwrap(), roots0.2);
+    }
+
+    #[test]
+    fn fork_in_memory_provider() {
+        let provider = DbProvider::new_in_memory();
+
+        let block0 = create_dummy_block();
+        BlockWriter::insert_block_with_states_and_receipts(
+            &provider,
+            block0.clone(),
+            create_dummy_state_updates(),
+            Vec::new(),
+            Vec::new(),
+        )
+        .expect("failed to insert block");
+
+        // each branch builds a different block on top of the common base state
+        let branch1 = provider.fork();
+        let branch2 = provider.fork();
+
+        for (branch, storage) in [(&branch1, felt!("100")), (&branch2, felt!("200"))] {
+            let header = Header { parent_hash: block0.block.hash, number: 1, ..Default::default() };
+            let block = Block { header, body: Vec::new() }.seal();
+            let block = SealedBlockWithStatus { block, status: FinalityStatus::AcceptedOnL2 };
+
+            let states = StateUpdatesWithClasses {
+                state_updates: StateUpdates {
+                    storage_updates: BTreeMap::from([(
+                        address!("1"),
+                        BTreeMap::from([(felt!("1"), storage)]),
+                    )]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            BlockWriter::insert_block_with_states_and_receipts(
+                branch,
+                block,
+                states,
+                Vec::new(),
+                Vec::new(),
+            )
+            .expect("failed to insert block");
+        }
+
+        let storage = |provider: &DbProvider<MemoryDb>| {
+            let state = StateFactoryProvider::latest(provider).unwrap();
+            state.storage(address!("1"), felt!("1")).unwrap()
+        };
+
+        // the base provider is left untouched by its branches
+        assert_eq!(provider.latest_number().unwrap(), 0);
+        assert_eq!(storage(&provider), Some(felt!("1")));
+
+        assert_eq!(branch1.latest_number().unwrap(), 1);
+        assert_eq!(storage(&branch1), Some(felt!("100")));
+        assert_eq!(branch2.latest_number().unwrap(), 1);
+        assert_eq!(storage(&branch2), Some(felt!("200")));
+
+        // the history of the base state is shared by the branches
+        let state =
+            StateFactoryProvider::historical(&branch2, BlockHashOrNumber::Num(0)).unwrap().unwrap();
+        assert_eq!(state.storage(address!("1"), felt!("1")).unwrap(), Some(felt!("1")));
+        assert_eq!(state.nonce(address!("2")).unwrap(), Some(felt!("2")));
+    }
 }
@@ -13,6 +13,7 @@ use katana_primitives::contract::{
 };
 use katana_primitives::Felt;
 
+use super::cache::ClassCache;
 use super::DbProvider;
 use crate::error::ProviderError;
 use crate::traits::contract::{ContractClassProvider, ContractClassWriter, ContractClassWriterExt};
@@ -71,7 +72,7 @@ impl<Db: Database> StateWriter for DbProvider<Db> {
     }
 }
 
-impl ContractClassWriter for DbProvider {
+impl<Db: Database> ContractClassWriter for DbProvider<Db> {
     fn set_class(&self, hash: ClassHash, class: ContractClass) -> ProviderResult<()> {
         self.0.update(move |db_tx| -> ProviderResult<()> {
             db_tx.put::<tables::Classes>(hash, class)?;
@@ -91,7 +92,7 @@ impl ContractClassWriter for DbProvider {
     }
 }
 
-impl ContractClassWriterExt for DbProvider {
+impl<Db: Database> ContractClassWriterExt for DbProvider<Db> {
     fn set_compiled_class(&self, hash: ClassHash, class: CompiledClass) -> ProviderResult<()> {
         self.0.update(move |db_tx| -> ProviderResult<()> {
             db_tx.put::<tables::CompiledClasses>(hash, class)?;
@@ -102,11 +103,11 @@ impl ContractClassWriterExt for DbProvider {
 
 /// A state provider that provides the latest states from the database.
 #[derive(Debug)]
-pub(super) struct LatestStateProvider<Tx: DbTx>(Tx);
+pub(super) struct LatestStateProvider<Tx: DbTx>(Tx, ClassCache);
 
 impl<Tx: DbTx> LatestStateProvider<Tx> {
-    pub fn new(tx: Tx) -> Self {
-        Self(tx)
+    pub fn new(tx: Tx, classes: ClassCache) -> Self {
+        Self(tx, classes)
     }
 }
 
@@ -115,7 +116,7 @@ where
     Tx: DbTx + Send + Sync,
 {
     fn class(&self, hash: ClassHash) -> ProviderResult<Option<ContractClass>> {
-        Ok(self.0.get::<tables::Classes>(hash)?)
+        cached_class(&self.0, &self.1, hash)
     }
 
     fn compiled_class_hash_of_class_hash(
@@ -215,11 +216,13 @@ pub(super) struct HistoricalStateProvider<Tx: DbTx + fmt::Debug> {
     tx: Tx,
     /// The block number of the state.
     block_number: u64,
+    /// The cache of the decompressed classes.
+    classes: ClassCache,
 }
 
 impl<Tx: DbTx + fmt::Debug> HistoricalStateProvider<Tx> {
-    pub fn new(tx: Tx, block_number: u64) -> Self {
-        Self { tx, block_number }
+    pub fn new(tx: Tx, block_number: u64, classes: ClassCache) -> Self {
+        Self { tx, block_number, classes }
     }
 
     /// Check if the class was declared before the pinned block number.
@@ -236,7 +239,7 @@ where
 {
     fn class(&self, hash: ClassHash) -> ProviderResult<Option<ContractClass>> {
         if self.is_class_declared_before_block(hash)? {
-            Ok(self.tx.get::<tables::Classes>(hash)?)
+            cached_class(&self.tx, &self.classes, hash)
         } else {
             Ok(None)
         }
@@ -428,6 +431,25 @@ fn recent_change_from_block(
     if rank == 0 { None } else { block_list.select(rank - 1) }
 }
 
+/// Returns the class with the given hash, reading it from the database only if it isn't already in
+/// the cache.
+fn cached_class<Tx: DbTx>(
+    tx: &Tx,
+    cache: &ClassCache,
+    hash: ClassHash,
+) -> ProviderResult<Option<ContractClass>> {
+    if let Some(class) = cache.get(&hash) {
+        return Ok(Some(class));
+    }
+
+    let class = tx.get::<tables::Classes>(hash)?;
+    if let Some(class) = &class {
+        cache.insert(hash, class.clone());
+    }
+
+    Ok(class)
+}
+
 #[cfg(test)]
 mod tests {
     use katana_db::models::list::BlockList;
@@ -449,3 +471,4 @@ mod tests {
         assert_eq!(actual_block_num, expected_block_num);
     }
 }
+
@@ -46,6 +46,9 @@ impl<Db: Database> TrieWriter for DbProvider<Db> {
 
             let mut contract_leafs: HashMap<ContractAddress, ContractLeaf> = HashMap::new();
 
+            // the values of the contracts not updated by this block are those of the latest state
+            let latest_state = self.latest()?;
+
             let leaf_hashes: Vec<_> = {
                 // First we insert the contract storage changes
                 for (address, storage_entries) in &state_updates.storage_updates {
@@ -85,8 +88,7 @@ impl<Db: Database> TrieWriter for DbProvider<Db> {
                         let storage_root = storage_trie.root();
                         leaf.storage_root = Some(storage_root);
 
-                        let latest_state = self.latest()?;
-                        let leaf_hash = contract_state_leaf_hash(latest_state, &address, &leaf);
+                        let leaf_hash = contract_state_leaf_hash(&latest_state, &address, &leaf)?;
 
                         Ok((address, leaf_hash))
                     })
@@ -108,15 +110,18 @@ fn contract_state_leaf_hash(
     provider: impl StateProvider,
     address: &ContractAddress,
     contract_leaf: &ContractLeaf,
-) -> Felt {
-    let nonce =
-        contract_leaf.nonce.unwrap_or(provider.nonce(*address).unwrap().unwrap_or_default());
+) -> ProviderResult<Felt> {
+    let nonce = match contract_leaf.nonce {
+        Some(nonce) => nonce,
+        None => provider.nonce(*address)?.unwrap_or_default(),
+    };
 
-    let class_hash = contract_leaf
-        .class_hash
-        .unwrap_or(provider.class_hash_of_contract(*address).unwrap().unwrap_or_default());
+    let class_hash = match contract_leaf.class_hash {
+        Some(class_hash) => class_hash,
+        None => provider.class_hash_of_contract(*address)?.unwrap_or_default(),
+    };
 
     let storage_root = contract_leaf.storage_root.expect("root need to set");
 
-    compute_contract_state_hash(&class_hash, &storage_root, &nonce)
+    Ok(compute_contract_state_hash(&class_hash, &storage_root, &nonce))
 }
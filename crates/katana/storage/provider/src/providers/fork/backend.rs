@@ -630,44 +630,8 @@ impl ContractClassProvider for SharedStateProvider {
     }
 }
 
-impl StateProofProvider for SharedStateProvider {
-    fn class_multiproof(&self, classes: Vec<ClassHash>) -> ProviderResult<katana_trie::MultiProof> {
-        let _ = classes;
-        unimplemented!("not supported in forked mode")
-    }
-
-    fn contract_multiproof(
-        &self,
-        addresses: Vec<ContractAddress>,
-    ) -> ProviderResult<katana_trie::MultiProof> {
-        let _ = addresses;
-        unimplemented!("not supported in forked mode")
-    }
-
-    fn storage_multiproof(
-        &self,
-        address: ContractAddress,
-        key: Vec<StorageKey>,
-    ) -> ProviderResult<katana_trie::MultiProof> {
-        let _ = address;
-        let _ = key;
-        unimplemented!("not supported in forked mode")
-    }
-}
-
-impl StateRootProvider for SharedStateProvider {
-    fn classes_root(&self) -> ProviderResult<Felt> {
-        unimplemented!("not supported in forked mode")
-    }
-
-    fn contracts_root(&self) -> ProviderResult<Felt> {
-        unimplemented!("not supported in forked mode")
-    }
-
-    fn storage_root(&self, _: ContractAddress) -> ProviderResult<Option<Felt>> {
-        unimplemented!("not supported in forked mode")
-    }
-}
+impl StateProofProvider for SharedStateProvider {}
+impl StateRootProvider for SharedStateProvider {}
 
 /// A helper function to convert a contract/class not found error returned by the RPC provider into
 /// a `Option::None`.
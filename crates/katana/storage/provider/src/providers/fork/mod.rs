@@ -13,9 +13,10 @@ use katana_primitives::block::{
 use katana_primitives::class::{ClassHash, CompiledClass, CompiledClassHash, ContractClass};
 use katana_primitives::contract::ContractAddress;
 use katana_primitives::env::BlockEnv;
+use katana_primitives::event::{ContinuationToken, EventFilter};
 use katana_primitives::receipt::Receipt;
 use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
-use katana_primitives::trace::TxExecInfo;
+use katana_primitives::trace::{BlockExecutionArtifacts, TxExecInfo};
 use katana_primitives::transaction::{Tx, TxHash, TxNumber, TxWithHash};
 use katana_primitives::Felt;
 use parking_lot::RwLock;
@@ -26,12 +27,14 @@ use self::backend::{Backend, BackendError, SharedStateProvider};
 use self::state::ForkedStateDb;
 use super::in_memory::cache::{CacheDb, CacheStateDb};
 use super::in_memory::state::HistoricalStates;
+use crate::error::ProviderError;
 use crate::traits::block::{
-    BlockHashProvider, BlockNumberProvider, BlockProvider, BlockStatusProvider, BlockWriter,
-    HeaderProvider,
+    BlockArtifactsProvider, BlockArtifactsWriter, BlockHashProvider, BlockNumberProvider,
+    BlockProvider, BlockStatusProvider, BlockStatusWriter, BlockWriter, HeaderProvider,
 };
 use crate::traits::contract::{ContractClassWriter, ContractClassWriterExt};
 use crate::traits::env::BlockEnvProvider;
+use crate::traits::event::{EmittedEvent, EventProvider, EventsPage};
 use crate::traits::stage::StageCheckpointProvider;
 use crate::traits::state::{StateFactoryProvider, StateProvider, StateWriter};
 use crate::traits::state_update::StateUpdateProvider;
@@ -127,6 +130,56 @@ impl BlockStatusProvider for ForkedProvider {
     }
 }
 
+impl BlockStatusWriter for ForkedProvider {
+    fn update_block_status(
+        &self,
+        block_number: BlockNumber,
+        status: FinalityStatus,
+    ) -> ProviderResult<()> {
+        let mut storage = self.storage.write();
+        if !storage.block_headers.contains_key(&block_number) {
+            return Err(ProviderError::MissingBlockHeader(block_number));
+        }
+
+        storage.block_statusses.insert(block_number, status);
+        Ok(())
+    }
+}
+
+impl BlockArtifactsProvider for ForkedProvider {
+    fn block_execution_artifacts(
+        &self,
+        id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<BlockExecutionArtifacts>> {
+        let num = match id {
+            BlockHashOrNumber::Num(num) => num,
+            BlockHashOrNumber::Hash(hash) => {
+                match self.storage.read().block_numbers.get(&hash).copied() {
+                    Some(num) => num,
+                    None => return Ok(None),
+                }
+            }
+        };
+        Ok(self.storage.read().block_artifacts.get(&num).cloned())
+    }
+}
+
+impl BlockArtifactsWriter for ForkedProvider {
+    fn insert_block_with_artifacts(
+        &self,
+        block: SealedBlockWithStatus,
+        states: StateUpdatesWithClasses,
+        receipts: Vec<Receipt>,
+        executions: Vec<TxExecInfo>,
+        artifacts: BlockExecutionArtifacts,
+    ) -> ProviderResult<()> {
+        let block_number = block.block.header.number;
+        self.insert_block_with_states_and_receipts(block, states, receipts, executions)?;
+        self.storage.write().block_artifacts.insert(block_number, artifacts);
+        Ok(())
+    }
+}
+
 impl BlockProvider for ForkedProvider {
     fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Block>> {
         let block_num = match id {
@@ -410,6 +463,83 @@ impl ReceiptProvider for ForkedProvider {
     }
 }
 
+impl EventProvider for ForkedProvider {
+    fn events_in_range(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+        filter: &EventFilter,
+        continuation_token: Option<ContinuationToken>,
+        chunk_size: usize,
+    ) -> ProviderResult<EventsPage> {
+        let storage = self.storage.read();
+
+        let start = match continuation_token {
+            Some(token) if token.block_n >= *block_range.start() => token,
+            _ => ContinuationToken { block_n: *block_range.start(), ..Default::default() },
+        };
+
+        let mut events = Vec::new();
+
+        for block_number in start.block_n..=*block_range.end() {
+            // the range is allowed to go past the latest block
+            let Some(block_hash) = storage.block_hashes.get(&block_number).copied() else { break };
+            let indices = storage
+                .block_body_indices
+                .get(&block_number)
+                .ok_or(ProviderError::MissingBlockBodyIndices(block_number))?;
+
+            let first_tx = if block_number == start.block_n { start.txn_n } else { 0 };
+
+            for tx_idx in first_tx..indices.tx_count {
+                let tx_number = indices.tx_offset + tx_idx;
+                let receipt = storage
+                    .receipts
+                    .get(tx_number as usize)
+                    .ok_or(ProviderError::MissingTxReceipt(tx_number))?;
+
+                let first_event = if block_number == start.block_n && tx_idx == start.txn_n {
+                    start.event_n as usize
+                } else {
+                    0
+                };
+
+                if receipt.events().len() <= first_event {
+                    continue;
+                }
+
+                let transaction_hash = storage
+                    .transaction_hashes
+                    .get(&tx_number)
+                    .copied()
+                    .ok_or(ProviderError::MissingTxHash(tx_number))?;
+
+                for (event_idx, event) in receipt.events().iter().enumerate().skip(first_event) {
+                    if events.len() >= chunk_size {
+                        let token = ContinuationToken {
+                            block_n: block_number,
+                            txn_n: tx_idx,
+                            event_n: event_idx as u64,
+                        };
+                        return Ok(EventsPage { events, continuation_token: Some(token) });
+                    }
+
+                    if filter.matches(event) {
+                        let event = event.clone();
+                        events.push(EmittedEvent {
+                            block_number,
+                            block_hash,
+                            transaction_hash,
+                            event,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(EventsPage { events, continuation_token: None })
+    }
+}
+
 // impl StateRootProvider for ForkedProvider {
 //     fn state_root(
 //         &self,
@@ -532,6 +662,11 @@ impl BlockWriter for ForkedProvider {
 
         Ok(())
     }
+
+    fn unwind_to(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        let _ = block_number;
+        Err(ProviderError::UnwindNotSupported)
+    }
 }
 
 impl ContractClassWriter for ForkedProvider {
@@ -7,7 +7,7 @@ use katana_primitives::class::{ClassHash, CompiledClass, CompiledClassHash, Cont
 use katana_primitives::contract::{ContractAddress, GenericContractInfo, StorageKey, StorageValue};
 use katana_primitives::receipt::Receipt;
 use katana_primitives::state::{StateUpdates, StateUpdatesWithClasses};
-use katana_primitives::trace::TxExecInfo;
+use katana_primitives::trace::{BlockExecutionArtifacts, TxExecInfo};
 use katana_primitives::transaction::{Tx, TxHash, TxNumber};
 use parking_lot::RwLock;
 
@@ -55,7 +55,10 @@ impl<Db> CacheStateDb<Db> {
             info.nonce = nonce;
         }
 
-        for (contract_address, class_hash) in updates.state_updates.deployed_contracts {
+        let class_changes = updates.state_updates.deployed_contracts.into_iter();
+        for (contract_address, class_hash) in
+            class_changes.chain(updates.state_updates.replaced_classes)
+        {
             let info = contract_state.entry(contract_address).or_default();
             info.class_hash = class_hash;
         }
@@ -92,6 +95,7 @@ pub struct CacheDb<Db> {
     pub(crate) transaction_hashes: HashMap<TxNumber, TxHash>,
     pub(crate) transaction_numbers: HashMap<TxHash, TxNumber>,
     pub(crate) transaction_block: HashMap<TxNumber, BlockNumber>,
+    pub(crate) block_artifacts: HashMap<BlockNumber, BlockExecutionArtifacts>,
 }
 
 impl<Db> CacheStateDb<Db> {
@@ -122,6 +126,7 @@ impl<Db> CacheDb<Db> {
             block_body_indices: HashMap::new(),
             transaction_numbers: HashMap::new(),
             transactions_executions: Vec::new(),
+            block_artifacts: HashMap::new(),
             latest_block_hash: Default::default(),
             latest_block_number: Default::default(),
         }
@@ -1,2 +1,13 @@
 pub mod cache;
 pub mod state;
+
+use katana_db::memory::MemoryDb;
+
+use super::db::DbProvider;
+
+/// A provider that keeps all its data in memory, and whose state can be forked cheaply into
+/// independent branches with [`DbProvider::fork`].
+///
+/// Useful for tests that explore many scenarios from a common state, eg different orderings of the
+/// same transactions, without having to rebuild the state for each of them.
+pub type ForkableInMemoryProvider = DbProvider<MemoryDb>;
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use alloy_primitives::U256;
 use katana_db::mdbx::test_utils;
 use katana_primitives::address;
-use katana_primitives::block::{Block, BlockHash, FinalityStatus};
+use katana_primitives::block::{Block, BlockHash, FinalityStatus, Header, SealedBlockWithStatus};
 use katana_primitives::contract::ContractAddress;
 use katana_primitives::genesis::allocation::{
     DevGenesisAccount, GenesisAccountAlloc, GenesisAllocation,
@@ -22,6 +22,32 @@ pub fn test_provider() -> DbProvider {
     provider
 }
 
+/// Creates an in-memory storage provider with `count` empty blocks, starting from the block 0.
+///
+/// Every block is a child of the previous one and is accepted on L2.
+pub fn provider_with_blocks(count: u64) -> DbProvider {
+    let provider = DbProvider::new_ephemeral();
+
+    let mut parent_hash = BlockHash::ZERO;
+    for number in 0..count {
+        let header = Header { number, parent_hash, ..Default::default() };
+        let block = Block { header, body: Vec::new() }.seal();
+        parent_hash = block.hash;
+
+        let block = SealedBlockWithStatus { block, status: FinalityStatus::AcceptedOnL2 };
+        provider
+            .insert_block_with_states_and_receipts(
+                block,
+                Default::default(),
+                Vec::new(),
+                Vec::new(),
+            )
+            .expect("Failed to insert block.");
+    }
+
+    provider
+}
+
 /// Initializes the provider with a genesis block and states.
 fn initialize_test_provider<P: BlockWriter>(provider: &P) {
     let chain = create_chain_for_testing();
@@ -7,7 +7,7 @@ use katana_primitives::block::{
 };
 use katana_primitives::receipt::Receipt;
 use katana_primitives::state::StateUpdatesWithClasses;
-use katana_primitives::trace::TxExecInfo;
+use katana_primitives::trace::{BlockExecutionArtifacts, TxExecInfo};
 
 use super::transaction::{TransactionProvider, TransactionsProviderExt};
 use crate::ProviderResult;
@@ -100,6 +100,16 @@ pub trait BlockStatusProvider: Send + Sync {
     fn block_status(&self, id: BlockHashOrNumber) -> ProviderResult<Option<FinalityStatus>>;
 }
 
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait BlockStatusWriter: Send + Sync {
+    /// Updates the finality status of an already stored block, eg once it has been settled.
+    fn update_block_status(
+        &self,
+        block_number: BlockNumber,
+        status: FinalityStatus,
+    ) -> ProviderResult<()>;
+}
+
 #[auto_impl::auto_impl(&, Box, Arc)]
 pub trait BlockProvider:
     BlockHashProvider
@@ -150,4 +160,35 @@ pub trait BlockWriter: Send + Sync {
         receipts: Vec<Receipt>,
         executions: Vec<TxExecInfo>,
     ) -> ProviderResult<()>;
+
+    /// Removes all the blocks after `block_number` along with their transactions, receipts and
+    /// state changes, making `block_number` the latest block.
+    fn unwind_to(&self, block_number: BlockNumber) -> ProviderResult<()>;
+}
+
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait BlockArtifactsProvider: Send + Sync {
+    /// Returns the execution artifacts of a block, ie its SNOS inputs.
+    ///
+    /// They are only recorded for the blocks produced in provable mode, `None` is returned for the
+    /// other blocks.
+    fn block_execution_artifacts(
+        &self,
+        id: BlockHashOrNumber,
+    ) -> ProviderResult<Option<BlockExecutionArtifacts>>;
+}
+
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait BlockArtifactsWriter: Send + Sync {
+    /// Stores an executed block along with its execution output - like
+    /// [`BlockWriter::insert_block_with_states_and_receipts`] - and its execution artifacts, all at
+    /// once.
+    fn insert_block_with_artifacts(
+        &self,
+        block: SealedBlockWithStatus,
+        states: StateUpdatesWithClasses,
+        receipts: Vec<Receipt>,
+        executions: Vec<TxExecInfo>,
+        artifacts: BlockExecutionArtifacts,
+    ) -> ProviderResult<()>;
 }
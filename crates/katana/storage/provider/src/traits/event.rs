@@ -0,0 +1,44 @@
+use std::ops::RangeInclusive;
+
+use katana_primitives::block::{BlockHash, BlockNumber};
+use katana_primitives::event::{ContinuationToken, EventFilter};
+use katana_primitives::receipt::Event;
+use katana_primitives::transaction::TxHash;
+
+use crate::ProviderResult;
+
+/// An event along with the block and transaction that it was emitted in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmittedEvent {
+    pub block_number: BlockNumber,
+    pub block_hash: BlockHash,
+    pub transaction_hash: TxHash,
+    pub event: Event,
+}
+
+/// A chunk of the events returned by [`EventProvider::events_in_range`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventsPage {
+    /// The events that match the filter, in the order they were emitted.
+    pub events: Vec<EmittedEvent>,
+    /// Points to the next event to be examined. `None` if all the events in the block range have
+    /// been examined.
+    pub continuation_token: Option<ContinuationToken>,
+}
+
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait EventProvider: Send + Sync {
+    /// Returns at most `chunk_size` events that match the `filter` from the blocks in
+    /// `block_range`.
+    ///
+    /// Events are examined starting from the one pointed by the `continuation_token`, or from the
+    /// first event of the range if `None`. A token that points before the start of the range is
+    /// treated as pointing to the start of the range.
+    fn events_in_range(
+        &self,
+        block_range: RangeInclusive<BlockNumber>,
+        filter: &EventFilter,
+        continuation_token: Option<ContinuationToken>,
+        chunk_size: usize,
+    ) -> ProviderResult<EventsPage>;
+}
@@ -0,0 +1,22 @@
+use katana_primitives::block::BlockNumber;
+
+use crate::ProviderResult;
+
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait PruneWriter: Send + Sync {
+    /// Removes the receipts of all the transactions in the blocks up to, and including,
+    /// `block_number`.
+    fn prune_receipts(&self, block_number: BlockNumber) -> ProviderResult<()>;
+
+    /// Removes the execution traces of all the transactions in the blocks up to, and including,
+    /// `block_number`.
+    fn prune_traces(&self, block_number: BlockNumber) -> ProviderResult<()>;
+
+    /// Removes the state changes made in the blocks up to, and including, `block_number`, except
+    /// for the most recent change of every contract and storage slot, which is needed to know the
+    /// state of the following blocks.
+    ///
+    /// The state of the pruned blocks, and their state updates, can no longer be retrieved
+    /// accurately, and the chain can no longer be unwound to any of the pruned blocks.
+    fn prune_state_diffs(&self, block_number: BlockNumber) -> ProviderResult<()>;
+}
@@ -4,11 +4,13 @@ use katana_primitives::block::{
 };
 use katana_primitives::env::BlockEnv;
 use katana_primitives::state::StateUpdatesWithClasses;
+use katana_primitives::trace::BlockExecutionArtifacts;
 use katana_primitives::transaction::TxWithHash;
 use katana_provider::providers::db::DbProvider;
 use katana_provider::providers::fork::ForkedProvider;
 use katana_provider::traits::block::{
-    BlockHashProvider, BlockNumberProvider, BlockProvider, BlockStatusProvider, BlockWriter,
+    BlockArtifactsProvider, BlockArtifactsWriter, BlockHashProvider, BlockNumberProvider,
+    BlockProvider, BlockStatusProvider, BlockWriter,
 };
 use katana_provider::traits::env::BlockEnvProvider;
 use katana_provider::traits::state::{StateFactoryProvider, StateRootProvider};
@@ -309,6 +311,34 @@ where
     Ok(())
 }
 
+#[rstest::rstest]
+fn test_block_execution_artifacts_with_db_provider(
+    #[from(db_provider)] provider: BlockchainProvider<DbProvider>,
+) -> Result<()> {
+    let mut artifacts = BlockExecutionArtifacts::default();
+    artifacts.executed_classes.insert(Felt::from(1u8), Felt::from(2u8));
+    artifacts.initial_storage.entry(Felt::THREE.into()).or_default().insert(Felt::ONE, Felt::TWO);
+
+    let mut blocks = utils::generate_dummy_blocks_and_receipts(2).into_iter();
+
+    let (block, receipts, executions) = blocks.next().unwrap();
+    let states = StateUpdatesWithClasses::default();
+    provider.insert_block_with_states_and_receipts(block, states, receipts, executions)?;
+
+    let (block, receipts, executions) = blocks.next().unwrap();
+    let hash = block.block.hash;
+    let states = StateUpdatesWithClasses::default();
+    provider.insert_block_with_artifacts(block, states, receipts, executions, artifacts.clone())?;
+
+    // the block is stored along with its artifacts
+    assert_eq!(provider.latest_hash()?, hash);
+    assert_eq!(provider.block_execution_artifacts(1.into())?, Some(artifacts.clone()));
+    assert_eq!(provider.block_execution_artifacts(hash.into())?, Some(artifacts));
+    assert_eq!(provider.block_execution_artifacts(0.into())?, None);
+
+    Ok(())
+}
+
 #[template]
 #[rstest::rstest]
 #[case::insert_1_block(1)]
@@ -0,0 +1,152 @@
+use anyhow::Result;
+use katana_primitives::event::{ContinuationToken, EventFilter};
+use katana_primitives::receipt::{Event, Receipt};
+use katana_primitives::{ContractAddress, Felt};
+use katana_provider::providers::db::DbProvider;
+use katana_provider::traits::block::BlockWriter;
+use katana_provider::traits::event::{EmittedEvent, EventProvider};
+use katana_provider::BlockchainProvider;
+
+mod fixtures;
+mod utils;
+
+const BLOCK_COUNT: u64 = 5;
+const EVENTS_PER_TX: usize = 3;
+
+/// Inserts dummy blocks where every transaction emits [`EVENTS_PER_TX`] events, and returns all
+/// the emitted events in order.
+fn insert_blocks_with_events(provider: &impl BlockWriter) -> Result<Vec<EmittedEvent>> {
+    let mut emitted = Vec::new();
+
+    for (block, mut receipts, executions) in utils::generate_dummy_blocks_and_receipts(BLOCK_COUNT)
+    {
+        for (receipt, tx) in receipts.iter_mut().zip(&block.block.body) {
+            let Receipt::Invoke(receipt) = receipt else { unreachable!("only invoke receipts") };
+
+            for i in 0..EVENTS_PER_TX {
+                let event = Event {
+                    from_address: ContractAddress::from(Felt::from(i as u64)),
+                    keys: vec![Felt::from(i as u64)],
+                    data: vec![Felt::from(rand::random::<u128>())],
+                };
+
+                receipt.events.push(event.clone());
+                emitted.push(EmittedEvent {
+                    block_number: block.block.header.number,
+                    block_hash: block.block.hash,
+                    transaction_hash: tx.hash,
+                    event,
+                });
+            }
+        }
+
+        provider.insert_block_with_states_and_receipts(
+            block,
+            Default::default(),
+            receipts,
+            executions,
+        )?;
+    }
+
+    Ok(emitted)
+}
+
+#[test]
+fn paginate_events_across_restarts() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    let provider = BlockchainProvider::new(DbProvider::new(katana_db::init_db(dir.path())?));
+    let all_events = insert_blocks_with_events(&provider)?;
+
+    let filter = EventFilter { address: Some(Felt::ONE.into()), keys: None };
+    let expected = all_events.into_iter().filter(|e| filter.matches(&e.event)).collect::<Vec<_>>();
+
+    let range = 0..=BLOCK_COUNT;
+    let chunk_size = 2;
+
+    let first_page = provider.events_in_range(range.clone(), &filter, None, chunk_size)?;
+    assert_eq!(first_page.events.len(), chunk_size.min(expected.len()));
+
+    // the token must be serializable so that it can be handed out to clients.
+    let token = first_page.continuation_token.map(|t| t.to_string());
+    let mut actual = first_page.events;
+
+    // restart the node by reopening the database.
+    drop(provider);
+    let provider = BlockchainProvider::new(DbProvider::new(katana_db::init_db(dir.path())?));
+
+    let mut token = token.map(|t| ContinuationToken::parse(&t)).transpose()?;
+    while token.is_some() {
+        let page = provider.events_in_range(range.clone(), &filter, token, chunk_size)?;
+        assert!(page.events.len() <= chunk_size);
+
+        actual.extend(page.events);
+        token = page.continuation_token;
+    }
+
+    assert_eq!(actual, expected);
+
+    // the same token must always yield the same page.
+    let token = ContinuationToken { block_n: 1, txn_n: 0, event_n: 1 };
+    let page1 =
+        provider.events_in_range(range.clone(), &filter, Some(token.clone()), chunk_size)?;
+    let page2 = provider.events_in_range(range, &filter, Some(token), chunk_size)?;
+    assert_eq!(page1, page2);
+
+    Ok(())
+}
+
+#[test]
+fn events_without_filter() -> Result<()> {
+    let provider = fixtures::db_provider();
+    let expected = insert_blocks_with_events(&provider)?;
+
+    let page =
+        provider.events_in_range(0..=BLOCK_COUNT, &EventFilter::default(), None, usize::MAX)?;
+    assert_eq!(page.events, expected);
+    assert_eq!(page.continuation_token, None);
+
+    // a zero chunk size returns a token to the first event.
+    let page = provider.events_in_range(0..=BLOCK_COUNT, &EventFilter::default(), None, 0)?;
+    assert!(page.events.is_empty());
+
+    if let Some(first) = expected.first() {
+        let token = page.continuation_token.expect("should have a token");
+        assert_eq!(token.block_n, first.block_number);
+        assert_eq!(token.event_n, 0);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn paginate_events_by_first_key() -> Result<()> {
+    let provider = fixtures::db_provider();
+    let all_events = insert_blocks_with_events(&provider)?;
+
+    let keys = vec![vec![Felt::ZERO, Felt::TWO]];
+    let filter = EventFilter { address: None, keys: Some(keys) };
+    let expected = all_events.into_iter().filter(|e| filter.matches(&e.event)).collect::<Vec<_>>();
+
+    let range = 0..=BLOCK_COUNT;
+    let chunk_size = 3;
+
+    let mut actual = Vec::new();
+    let mut token = None;
+
+    loop {
+        let page = provider.events_in_range(range.clone(), &filter, token, chunk_size)?;
+        assert!(page.events.len() <= chunk_size);
+
+        actual.extend(page.events);
+        token = page.continuation_token;
+
+        if token.is_none() {
+            break;
+        }
+    }
+
+    assert_eq!(actual, expected);
+
+    Ok(())
+}
@@ -112,7 +112,7 @@ pub fn mock_state_updates() -> [StateUpdatesWithClasses; 3] {
                 BTreeMap::from([(felt!("1"), felt!("111")), (felt!("2"), felt!("222"))]),
             )]),
             declared_classes: BTreeMap::from([(class_hash_2, compiled_class_hash_2)]),
-            deployed_contracts: BTreeMap::from([(address_2, class_hash_2)]),
+            replaced_classes: BTreeMap::from([(address_2, class_hash_2)]),
             ..Default::default()
         },
         classes: BTreeMap::from([(class_hash_2, DEFAULT_LEGACY_UDC_CLASS.clone())]),
@@ -128,7 +128,7 @@ pub fn mock_state_updates() -> [StateUpdatesWithClasses; 3] {
                     BTreeMap::from([(1u8.into(), 12u32.into()), (2u8.into(), 13u32.into())]),
                 ),
             ]),
-            deployed_contracts: BTreeMap::from([
+            replaced_classes: BTreeMap::from([
                 (address_1, class_hash_2),
                 (address_2, class_hash_3),
             ]),
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use katana_primitives::state::StateUpdatesWithClasses;
+use katana_primitives::{address, Felt};
+use katana_provider::error::ProviderError;
+use katana_provider::traits::block::{BlockNumberProvider, BlockProvider, BlockWriter};
+use katana_provider::traits::prune::PruneWriter;
+use katana_provider::traits::state::StateFactoryProvider;
+use katana_provider::traits::state_update::StateUpdateProvider;
+use katana_provider::traits::transaction::{ReceiptProvider, TransactionTraceProvider};
+
+mod fixtures;
+mod utils;
+
+use fixtures::db_provider;
+
+#[test]
+fn prune_receipts_and_traces() -> Result<()> {
+    let provider = db_provider();
+    let blocks = utils::generate_dummy_blocks_and_receipts(5);
+
+    for (block, receipts, executions) in &blocks {
+        provider.insert_block_with_states_and_receipts(
+            block.clone(),
+            Default::default(),
+            receipts.clone(),
+            executions.clone(),
+        )?;
+    }
+
+    provider.prune_receipts(1)?;
+    provider.prune_traces(2)?;
+    // pruning is idempotent
+    provider.prune_receipts(1)?;
+
+    for (block, receipts, executions) in blocks {
+        let number = block.block.header.number;
+        let id = number.into();
+
+        let actual_receipts = provider.receipts_by_block(id)?.expect("block must exist");
+        let actual_executions = provider.transaction_executions_by_block(id)?.expect("must exist");
+
+        if number <= 1 {
+            assert!(actual_receipts.is_empty());
+        } else {
+            assert_eq!(actual_receipts, receipts);
+        }
+
+        if number <= 2 {
+            assert!(actual_executions.is_empty());
+        } else {
+            assert_eq!(actual_executions, executions);
+        }
+
+        // the blocks themselves are kept
+        assert_eq!(provider.block(id)?, Some(block.block.unseal()));
+    }
+
+    assert_eq!(provider.latest_number()?, 4);
+
+    Ok(())
+}
+
+#[test]
+fn prune_state_diffs() -> Result<()> {
+    let provider = db_provider();
+
+    let contract = address!("0x1337");
+    let other_contract = address!("0x1338");
+    let key = Felt::ONE;
+
+    for block in utils::generate_dummy_blocks_empty(5) {
+        let value = Felt::from(block.block.header.number);
+
+        let mut states = StateUpdatesWithClasses::default();
+        states.state_updates.nonce_updates.insert(contract, value);
+        states.state_updates.storage_updates.insert(contract, BTreeMap::from([(key, value)]));
+
+        // the other contract is only changed in the first block
+        if block.block.header.number == 0 {
+            states.state_updates.nonce_updates.insert(other_contract, Felt::ONE);
+        }
+
+        provider.insert_block_with_states_and_receipts(block, states, vec![], vec![])?;
+    }
+
+    provider.prune_state_diffs(2)?;
+    // pruning is idempotent
+    provider.prune_state_diffs(2)?;
+
+    // the state of the remaining blocks is unchanged
+    for block in 2..5u64 {
+        let state = provider.historical(block.into())?.expect("state must exist");
+        assert_eq!(state.storage(contract, key)?, Some(Felt::from(block)));
+        assert_eq!(state.nonce(contract)?, Some(Felt::from(block)));
+        assert_eq!(state.nonce(other_contract)?, Some(Felt::ONE));
+    }
+
+    // only the most recent change at or before the last pruned block is kept
+    let state_update = provider.state_update(0.into())?.expect("block must exist");
+    assert_eq!(state_update.nonce_updates, BTreeMap::from([(other_contract, Felt::ONE)]));
+    assert!(state_update.storage_updates.is_empty());
+
+    let state_update = provider.state_update(1.into())?.expect("block must exist");
+    assert!(state_update.nonce_updates.is_empty());
+    assert!(state_update.storage_updates.is_empty());
+
+    let state_update = provider.state_update(2.into())?.expect("block must exist");
+    assert_eq!(state_update.nonce_updates, BTreeMap::from([(contract, Felt::TWO)]));
+
+    // the chain can't be unwound to a pruned block
+    let result = provider.unwind_to(1);
+    assert!(matches!(result, Err(ProviderError::UnwindTargetPruned { target: 1, pruned: 2 })));
+
+    provider.unwind_to(3)?;
+    let state = provider.latest()?;
+    assert_eq!(state.storage(contract, key)?, Some(Felt::THREE));
+    assert_eq!(state.nonce(other_contract)?, Some(Felt::ONE));
+
+    Ok(())
+}
@@ -3,14 +3,22 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use futures::future::{self, BoxFuture};
+use futures::stream::{self, BoxStream};
+use futures::StreamExt;
 use katana_core::backend::Backend;
 use katana_core::service::block_producer::{BlockProducer, BlockProductionError};
 use katana_core::service::{BlockProductionTask, TransactionMiner};
 use katana_executor::ExecutorFactory;
 use katana_messaging::{MessagingConfig, MessagingService, MessagingTask};
 use katana_pool::{TransactionPool, TxPool};
+use katana_primitives::block::{BlockHashOrNumber, BlockNumber};
+use katana_primitives::receipt::MessageToL1;
+use katana_provider::traits::block::BlockNumberProvider;
+use katana_provider::traits::transaction::ReceiptProvider;
+use katana_provider::ProviderResult;
 use katana_tasks::{TaskHandle, TaskSpawner};
-use tracing::error;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{error, warn};
 
 pub type SequencingFut = BoxFuture<'static, Result<()>>;
 
@@ -41,7 +49,9 @@ impl<EF: ExecutorFactory> Sequencing<EF> {
             let pool = self.pool.clone();
             let chain_spec = self.backend.chain_spec.clone();
 
-            let service = MessagingService::new(config, chain_spec, pool).await?;
+            let service = MessagingService::new(config, chain_spec, pool)
+                .await?
+                .with_messages_to_send(self.messages_to_l1());
             let task = MessagingTask::new(service);
 
             let handle = self.task_spawner.build_task().name("Messaging").spawn(task);
@@ -52,6 +62,61 @@ impl<EF: ExecutorFactory> Sequencing<EF> {
         }
     }
 
+    /// Returns a stream of the L2 -> L1 messages sent by every newly mined block.
+    fn messages_to_l1(&self) -> BoxStream<'static, Vec<MessageToL1>> {
+        let backend = self.backend.clone();
+        let blocks = backend.block_notifier.subscribe();
+        // the number of the next block whose messages are to be collected
+        let next = backend.blockchain.provider().latest_number().map_or(0, |n| n + 1);
+
+        let stream = stream::unfold((blocks, next), move |(mut blocks, mut next)| {
+            let backend = backend.clone();
+            async move {
+                loop {
+                    match blocks.recv().await {
+                        // the block's messages have already been read from storage after lagging
+                        Ok(block) if block.header.number < next => continue,
+                        Ok(block) => {
+                            next = block.header.number + 1;
+                            let messages = block
+                                .receipts
+                                .iter()
+                                .flat_map(|receipt| receipt.messages_sent().iter().cloned())
+                                .collect::<Vec<_>>();
+
+                            return Some((messages, (blocks, next)));
+                        }
+
+                        // the channel only buffers the most recent blocks, so the messages of the
+                        // missed ones are read from storage instead
+                        Err(RecvError::Lagged(skipped)) => {
+                            warn!(
+                                target: "sequencing",
+                                %skipped,
+                                "Missed blocks when collecting messages to L1."
+                            );
+
+                            match stored_messages(&backend, next) {
+                                Ok((messages, until)) => {
+                                    next = until;
+                                    return Some((messages, (blocks, next)));
+                                }
+                                Err(error) => {
+                                    error!(target: "sequencing", %error, "Failed to read messages to L1.");
+                                    return None;
+                                }
+                            }
+                        }
+
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            }
+        });
+
+        stream.boxed()
+    }
+
     fn run_block_production(&self) -> TaskHandle<Result<(), BlockProductionError>> {
         // Create a new transaction miner with a subscription to the pool's pending transactions.
         let miner = TransactionMiner::new(self.pool.pending_transactions());
@@ -61,6 +126,29 @@ impl<EF: ExecutorFactory> Sequencing<EF> {
     }
 }
 
+/// Reads the L2 -> L1 messages sent by the blocks from `from` up to the latest block from storage.
+///
+/// Returns the messages along with the number of the block following the last one read.
+fn stored_messages<EF: ExecutorFactory>(
+    backend: &Backend<EF>,
+    from: BlockNumber,
+) -> ProviderResult<(Vec<MessageToL1>, BlockNumber)> {
+    let provider = backend.blockchain.provider();
+    let mut messages = Vec::new();
+    let mut next = from;
+
+    while next <= provider.latest_number()? {
+        let Some(receipts) = provider.receipts_by_block(BlockHashOrNumber::Num(next))? else {
+            break;
+        };
+
+        messages.extend(receipts.iter().flat_map(|r| r.messages_sent().iter().cloned()));
+        next += 1;
+    }
+
+    Ok((messages, next))
+}
+
 impl<EF: ExecutorFactory> IntoFuture for Sequencing<EF> {
     type Output = Result<()>;
     type IntoFuture = SequencingFut;
@@ -0,0 +1,144 @@
+//! A client publishing blobs to Celestia through the RPC of a Celestia node.
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tracing::trace;
+use url::Url;
+
+use super::{DaPointer, DataAvailability};
+use crate::prover::BackendError;
+use crate::{Error, SayaResult};
+
+/// The size of the identifier of a version 0 namespace, the only one available to users.
+const NAMESPACE_ID_SIZE: usize = 10;
+/// The size of a namespace: a version byte followed by a 28 bytes identifier.
+const NAMESPACE_SIZE: usize = 29;
+
+/// A Celestia namespace, in which the blobs of an appchain are published.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Namespace([u8; NAMESPACE_SIZE]);
+
+impl Namespace {
+    /// Creates a version 0 namespace from an identifier of up to 10 bytes, left padded with zeros.
+    pub fn new_v0(id: &str) -> SayaResult<Self> {
+        let id = id.as_bytes();
+        if id.is_empty() || id.len() > NAMESPACE_ID_SIZE {
+            return Err(Error::InvalidDaNamespace(String::from_utf8_lossy(id).into_owned()));
+        }
+
+        let mut namespace = [0u8; NAMESPACE_SIZE];
+        namespace[NAMESPACE_SIZE - id.len()..].copy_from_slice(id);
+        Ok(Self(namespace))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
+
+/// Publishes the data of the blocks as blobs with the `blob.Submit` method.
+#[derive(Debug, Clone)]
+pub struct CelestiaClient {
+    client: reqwest::Client,
+    url: Url,
+    namespace: Namespace,
+    auth_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    result: Option<u64>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl CelestiaClient {
+    pub fn new(url: Url, namespace: Namespace, auth_token: Option<String>) -> Self {
+        Self { client: reqwest::Client::new(), url, namespace, auth_token }
+    }
+
+    fn submit_request(&self, block: u64, data: &[u8]) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": block,
+            "method": "blob.Submit",
+            "params": [
+                [{
+                    "namespace": BASE64.encode(self.namespace.as_bytes()),
+                    "data": BASE64.encode(data),
+                    "share_version": 0,
+                }],
+                {},
+            ],
+        })
+    }
+}
+
+#[async_trait]
+impl DataAvailability for CelestiaClient {
+    async fn publish(&self, block: u64, data: Vec<u8>) -> Result<DaPointer, BackendError> {
+        let mut request =
+            self.client.post(self.url.clone()).json(&self.submit_request(block, &data));
+        if let Some(token) = &self.auth_token {
+            request = request.bearer_auth(token);
+        }
+
+        let response: RpcResponse = request.send().await?.error_for_status()?.json().await?;
+
+        let height = match response {
+            RpcResponse { result: Some(height), .. } => height,
+            RpcResponse { error: Some(e), .. } => {
+                return Err(format!("Celestia error {}: {}", e.code, e.message).into());
+            }
+            _ => return Err("Celestia returned no height for the submitted blob".into()),
+        };
+
+        trace!(target: "saya", %block, %height, size = data.len(), "State diff published.");
+
+        Ok(DaPointer { layer: "celestia".to_string(), height, namespace: self.namespace.to_hex() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn namespace_v0() {
+        let namespace = Namespace::new_v0("saya").unwrap();
+
+        assert_eq!(namespace.as_bytes().len(), NAMESPACE_SIZE);
+        assert!(namespace.as_bytes()[..25].iter().all(|byte| *byte == 0));
+        assert_eq!(&namespace.as_bytes()[25..], b"saya");
+        assert!(namespace.to_hex().ends_with("73617961"));
+
+        assert!(Namespace::new_v0("").is_err());
+        assert!(Namespace::new_v0("namespace_too_long").is_err());
+    }
+
+    #[test]
+    fn submit_request() {
+        let url = Url::parse("http://localhost:26658").unwrap();
+        let client = CelestiaClient::new(url, Namespace::new_v0("saya").unwrap(), None);
+
+        let request = client.submit_request(7, b"state diff");
+        assert_eq!(request["method"], "blob.Submit");
+        assert_eq!(request["id"], 7);
+
+        let blob = &request["params"][0][0];
+        assert_eq!(blob["data"], BASE64.encode(b"state diff"));
+        assert_eq!(blob["namespace"], BASE64.encode(client.namespace.as_bytes()));
+    }
+}
@@ -0,0 +1,196 @@
+//! Publishes the state diffs of the proved blocks to a data availability layer.
+//!
+//! The state diffs are encoded with the Starknet data availability format, each field element
+//! being written as 32 big-endian bytes.
+
+use std::collections::BTreeSet;
+
+use async_trait::async_trait;
+use clap::{Args, ValueEnum};
+use katana_primitives::da::encoding::encode_state_updates;
+use katana_primitives::state::StateUpdates;
+use serde::{Deserialize, Serialize};
+use starknet::core::types::StateDiff;
+use url::Url;
+
+use crate::prover::BackendError;
+use crate::SayaResult;
+
+mod celestia;
+
+pub use celestia::{CelestiaClient, Namespace};
+
+/// The default URL of the RPC of a Celestia light node.
+pub const DEFAULT_CELESTIA_URL: &str = "http://localhost:26658";
+
+/// Where the data of a block is published on the data availability layer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaPointer {
+    /// The name of the data availability layer, eg `celestia`.
+    pub layer: String,
+    /// The height of the block of the data availability layer including the data.
+    pub height: u64,
+    /// The namespace of the data, in hex.
+    pub namespace: String,
+}
+
+/// A data availability layer on which the state diffs of the blocks are published.
+#[async_trait]
+pub trait DataAvailability: Send + Sync {
+    /// Publishes the encoded state diff of `block`, returning where it's published.
+    async fn publish(&self, block: u64, data: Vec<u8>) -> Result<DaPointer, BackendError>;
+}
+
+#[async_trait]
+impl<T: DataAvailability + ?Sized> DataAvailability for Box<T> {
+    async fn publish(&self, block: u64, data: Vec<u8>) -> Result<DaPointer, BackendError> {
+        (**self).publish(block, data).await
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DaLayer {
+    Celestia,
+}
+
+#[derive(Debug, Clone, Args)]
+#[command(next_help_heading = "Data availability options")]
+pub struct DaOptions {
+    /// The data availability layer on which the state diffs of the blocks are published.
+    ///
+    /// The state diffs are not published if not set.
+    #[arg(long = "da", value_name = "LAYER")]
+    pub da: Option<DaLayer>,
+
+    /// The namespace of the published blobs, up to 10 bytes.
+    #[arg(long = "da.namespace", value_name = "NAMESPACE")]
+    #[arg(required_if_eq("da", "celestia"))]
+    pub namespace: Option<String>,
+
+    /// The URL of the RPC of the data availability node.
+    #[arg(long = "da.url", value_name = "URL")]
+    #[arg(default_value = DEFAULT_CELESTIA_URL)]
+    pub url: Url,
+
+    /// The token authenticating the requests to the data availability node.
+    #[arg(long = "da.auth-token", value_name = "TOKEN", env = "SAYA_DA_AUTH_TOKEN")]
+    pub auth_token: Option<String>,
+}
+
+impl DaOptions {
+    /// Builds the client of the configured data availability layer, if any.
+    pub fn build(&self) -> SayaResult<Option<Box<dyn DataAvailability>>> {
+        let Some(layer) = self.da else { return Ok(None) };
+
+        match layer {
+            DaLayer::Celestia => {
+                let namespace = Namespace::new_v0(self.namespace.as_deref().unwrap_or_default())?;
+                let client =
+                    CelestiaClient::new(self.url.clone(), namespace, self.auth_token.clone());
+                Ok(Some(Box::new(client)))
+            }
+        }
+    }
+}
+
+/// Encodes a state diff with the Starknet data availability format.
+pub fn encode_state_diff(state_diff: &StateDiff) -> Vec<u8> {
+    encode_state_updates(state_updates(state_diff))
+        .into_iter()
+        .flat_map(|value| {
+            let bytes = value.to_bytes_be();
+            let mut word = [0u8; 32];
+            word[32 - bytes.len()..].copy_from_slice(&bytes);
+            word
+        })
+        .collect()
+}
+
+fn state_updates(state_diff: &StateDiff) -> StateUpdates {
+    let storage_updates = state_diff
+        .storage_diffs
+        .iter()
+        .map(|diff| {
+            let entries = diff.storage_entries.iter().map(|entry| (entry.key, entry.value));
+            (diff.address.into(), entries.collect())
+        })
+        .collect();
+
+    let deployed_contracts = state_diff
+        .deployed_contracts
+        .iter()
+        .map(|contract| (contract.address.into(), contract.class_hash))
+        .collect();
+
+    let declared_classes = state_diff
+        .declared_classes
+        .iter()
+        .map(|class| (class.class_hash, class.compiled_class_hash))
+        .collect();
+
+    let replaced_classes = state_diff
+        .replaced_classes
+        .iter()
+        .map(|class| (class.contract_address.into(), class.class_hash))
+        .collect();
+
+    let nonce_updates = state_diff
+        .nonces
+        .iter()
+        .map(|nonce| (nonce.contract_address.into(), nonce.nonce))
+        .collect();
+
+    StateUpdates {
+        storage_updates,
+        declared_classes,
+        replaced_classes,
+        deployed_contracts,
+        nonce_updates,
+        deprecated_declared_classes: BTreeSet::from_iter(
+            state_diff.deprecated_declared_classes.iter().copied(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use katana_primitives::da::encoding::decode_state_updates;
+    use num_bigint::BigUint;
+    use starknet::core::types::{
+        ContractStorageDiffItem, DeployedContractItem, Felt, NonceUpdate, StorageEntry,
+    };
+
+    use super::*;
+
+    #[test]
+    fn encoded_state_diff_roundtrip() {
+        let state_diff = StateDiff {
+            storage_diffs: vec![ContractStorageDiffItem {
+                address: Felt::from(0x1_u64),
+                storage_entries: vec![StorageEntry {
+                    key: Felt::from(0x10_u64),
+                    value: Felt::from(0x20_u64),
+                }],
+            }],
+            deprecated_declared_classes: vec![],
+            declared_classes: vec![],
+            deployed_contracts: vec![DeployedContractItem {
+                address: Felt::from(0x1_u64),
+                class_hash: Felt::from(0xc1a55_u64),
+            }],
+            replaced_classes: vec![],
+            nonces: vec![NonceUpdate {
+                contract_address: Felt::from(0x1_u64),
+                nonce: Felt::from(0x2_u64),
+            }],
+        };
+
+        let encoded = encode_state_diff(&state_diff);
+        assert_eq!(encoded.len() % 32, 0);
+
+        let values = encoded.chunks(32).map(BigUint::from_bytes_be).collect::<Vec<_>>();
+        let decoded = decode_state_updates(&values).unwrap();
+
+        assert_eq!(decoded, state_updates(&state_diff));
+    }
+}
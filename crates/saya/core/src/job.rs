@@ -0,0 +1,297 @@
+//! The persistent record of the proof jobs.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::da::DaPointer;
+use crate::prover::Proof;
+use crate::settlement::StateTransition;
+use crate::SayaResult;
+
+const JOBS_FILE: &str = "jobs.json";
+const PROOFS_DIR: &str = "proofs";
+
+/// The status of the proof job of a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// The state update and the traces of the block are pulled from Katana.
+    Fetching,
+    /// The proof is being generated by the backend.
+    Proving,
+    /// The proof is generated and saved in the store, the state transition is not settled yet.
+    Proved,
+    /// The state transition proved by the proof is accepted by the settlement contract.
+    Settled,
+    /// The last attempt failed, the job is retried.
+    Failed,
+}
+
+/// The proof job of a block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Job {
+    pub block: u64,
+    pub status: JobStatus,
+    /// The number of times the job has been started.
+    pub attempts: u32,
+    /// The error of the last attempt, if it failed.
+    pub error: Option<String>,
+    /// The state transition of the block, known once the block is proved.
+    #[serde(default)]
+    pub transition: Option<StateTransition>,
+    /// Where the state diff of the block is published, if it's published.
+    #[serde(default)]
+    pub da: Option<DaPointer>,
+}
+
+/// Stores the proof jobs and the generated proofs in a directory.
+///
+/// The jobs are written to disk on every update, so a restarted orchestrator resumes from the
+/// first block which is not proved yet.
+#[derive(Debug)]
+pub struct JobStore {
+    dir: PathBuf,
+    jobs: BTreeMap<u64, Job>,
+}
+
+impl JobStore {
+    /// Opens the store in `dir`, creating it if it doesn't exist.
+    pub fn open(dir: impl AsRef<Path>) -> SayaResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(dir.join(PROOFS_DIR))?;
+
+        let jobs_path = dir.join(JOBS_FILE);
+        let jobs = if jobs_path.exists() {
+            let jobs: Vec<Job> = serde_json::from_slice(&fs::read(jobs_path)?)?;
+            jobs.into_iter().map(|job| (job.block, job)).collect()
+        } else {
+            BTreeMap::new()
+        };
+
+        Ok(Self { dir, jobs })
+    }
+
+    pub fn job(&self, block: u64) -> Option<&Job> {
+        self.jobs.get(&block)
+    }
+
+    pub fn jobs(&self) -> impl Iterator<Item = &Job> {
+        self.jobs.values()
+    }
+
+    /// Returns the next block to prove, from `start`.
+    ///
+    /// Blocks are proved in order since the proof of a block depends on the state of its parent,
+    /// so it's the first block whose job is not proved, or the block after the last proved one.
+    pub fn next_block(&self, start: u64) -> u64 {
+        self.jobs
+            .range(start..)
+            .map(|(_, job)| job)
+            .find(|job| !matches!(job.status, JobStatus::Proved | JobStatus::Settled))
+            .map(|job| job.block)
+            .or_else(|| self.jobs.range(start..).next_back().map(|(block, _)| block + 1))
+            .unwrap_or(start)
+    }
+
+    /// Sets the status of the job of `block`, creating the job if needed.
+    ///
+    /// Moving a job to [`JobStatus::Fetching`] starts a new attempt.
+    pub fn update(
+        &mut self,
+        block: u64,
+        status: JobStatus,
+        error: Option<String>,
+    ) -> SayaResult<()> {
+        let job = self.jobs.entry(block).or_insert(Job {
+            block,
+            status,
+            attempts: 0,
+            error: None,
+            transition: None,
+            da: None,
+        });
+
+        if status == JobStatus::Fetching {
+            job.attempts += 1;
+        }
+
+        job.status = status;
+        job.error = error;
+
+        self.flush()
+    }
+
+    /// Returns the next job to settle from `start`, if it's proved.
+    ///
+    /// State transitions are settled in order, so it's the first job which is not settled.
+    pub fn next_settlement(&self, start: u64) -> Option<&Job> {
+        self.jobs
+            .range(start..)
+            .map(|(_, job)| job)
+            .find(|job| job.status != JobStatus::Settled)
+            .filter(|job| job.status == JobStatus::Proved)
+    }
+
+    /// Saves the proof of `block` with the state transition it proves, and marks its job as
+    /// proved.
+    pub fn save_proof(
+        &mut self,
+        block: u64,
+        proof: &Proof,
+        transition: StateTransition,
+    ) -> SayaResult<()> {
+        fs::write(self.proof_path(block), serde_json::to_vec(proof)?)?;
+        self.update(block, JobStatus::Proved, None)?;
+
+        if let Some(job) = self.jobs.get_mut(&block) {
+            job.transition = Some(transition);
+        }
+
+        self.flush()
+    }
+
+    /// Records where the state diff of `block` is published.
+    pub fn set_da_pointer(&mut self, block: u64, pointer: DaPointer) -> SayaResult<()> {
+        if let Some(job) = self.jobs.get_mut(&block) {
+            job.da = Some(pointer);
+        }
+
+        self.flush()
+    }
+
+    /// Returns the saved proof of `block`, if any.
+    pub fn proof(&self, block: u64) -> SayaResult<Option<Proof>> {
+        let path = self.proof_path(block);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+    }
+
+    fn proof_path(&self, block: u64) -> PathBuf {
+        self.dir.join(PROOFS_DIR).join(format!("{block}.json"))
+    }
+
+    /// Writes the jobs to a temporary file first, so that a crash never leaves a truncated file.
+    fn flush(&self) -> SayaResult<()> {
+        let jobs = self.jobs.values().collect::<Vec<_>>();
+        let tmp_path = self.dir.join(format!("{JOBS_FILE}.tmp"));
+
+        fs::write(&tmp_path, serde_json::to_vec_pretty(&jobs)?)?;
+        fs::rename(tmp_path, self.dir.join(JOBS_FILE))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use starknet::core::types::Felt;
+
+    use super::*;
+
+    fn transition(block: u64) -> StateTransition {
+        StateTransition {
+            block,
+            prev_state_root: Felt::from(block),
+            new_state_root: Felt::from(block + 1),
+            fact: Felt::from(0xfac7_u64),
+        }
+    }
+
+    #[test]
+    fn jobs_are_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut store = JobStore::open(dir.path()).unwrap();
+        assert_eq!(store.next_block(0), 0);
+
+        let proof = Proof {
+            backend: "mock".to_string(),
+            fact: Felt::from(0xfac7_u64),
+            data: json!({ "block": 0 }),
+        };
+        store.update(0, JobStatus::Fetching, None).unwrap();
+        store.save_proof(0, &proof, transition(0)).unwrap();
+        store.update(1, JobStatus::Fetching, None).unwrap();
+        store.update(1, JobStatus::Failed, Some("prover down".to_string())).unwrap();
+
+        let pointer = DaPointer {
+            layer: "celestia".to_string(),
+            height: 42,
+            namespace: "73617961".to_string(),
+        };
+        store.set_da_pointer(1, pointer.clone()).unwrap();
+
+        let store = JobStore::open(dir.path()).unwrap();
+        assert_eq!(store.job(0).unwrap().status, JobStatus::Proved);
+        assert_eq!(store.job(0).unwrap().transition, Some(transition(0)));
+        assert_eq!(store.proof(0).unwrap(), Some(proof));
+        assert_eq!(store.proof(1).unwrap(), None);
+
+        let failed = store.job(1).unwrap();
+        assert_eq!(failed.status, JobStatus::Failed);
+        assert_eq!(failed.attempts, 1);
+        assert_eq!(failed.error.as_deref(), Some("prover down"));
+        assert_eq!(failed.da, Some(pointer));
+    }
+
+    #[test]
+    fn next_block_resumes_after_proved_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = JobStore::open(dir.path()).unwrap();
+
+        store.update(0, JobStatus::Proved, None).unwrap();
+        store.update(1, JobStatus::Proved, None).unwrap();
+        assert_eq!(store.next_block(0), 2);
+
+        store.update(2, JobStatus::Proving, None).unwrap();
+        assert_eq!(store.next_block(0), 2);
+
+        store.update(2, JobStatus::Fetching, None).unwrap();
+        store.update(2, JobStatus::Failed, None).unwrap();
+        assert_eq!(store.next_block(0), 2);
+        assert_eq!(store.job(2).unwrap().attempts, 1);
+
+        store.update(0, JobStatus::Settled, None).unwrap();
+        assert_eq!(store.next_block(0), 2);
+    }
+
+    #[test]
+    fn next_block_from_start_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = JobStore::open(dir.path()).unwrap();
+        assert_eq!(store.next_block(5), 5);
+
+        // the jobs before the start block are ignored
+        store.update(2, JobStatus::Failed, None).unwrap();
+        assert_eq!(store.next_block(5), 5);
+
+        store.update(5, JobStatus::Proved, None).unwrap();
+        assert_eq!(store.next_block(5), 6);
+        assert_eq!(store.next_settlement(5).unwrap().block, 5);
+        assert!(store.next_settlement(0).is_none());
+    }
+
+    #[test]
+    fn settlements_are_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = JobStore::open(dir.path()).unwrap();
+        assert!(store.next_settlement(0).is_none());
+
+        store.update(0, JobStatus::Proving, None).unwrap();
+        store.update(1, JobStatus::Proved, None).unwrap();
+        assert!(store.next_settlement(0).is_none());
+
+        store.update(0, JobStatus::Proved, None).unwrap();
+        assert_eq!(store.next_settlement(0).unwrap().block, 0);
+
+        store.update(0, JobStatus::Settled, None).unwrap();
+        assert_eq!(store.next_settlement(0).unwrap().block, 1);
+    }
+}
@@ -0,0 +1,59 @@
+//! Saya proves the blocks of a Katana appchain so that its state can be settled.
+//!
+//! The [`Orchestrator`] pulls the state update and the execution traces of each block from
+//! Katana, hands them to a [`ProverBackend`] and records the status of the proof job of each block
+//! in a [`JobStore`], so that proving resumes where it stopped after a restart. The proved state
+//! transitions are then submitted to a [`Settlement`] layer, in order, once their state diffs are
+//! published to the [`DataAvailability`] layer if one is configured.
+//!
+//! Generating the proofs themselves, with SNOS and Stone or Stwo, is left to the implementations
+//! of [`ProverBackend`], which usually wrap an external prover.
+
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+pub mod da;
+pub mod job;
+pub mod orchestrator;
+pub mod prover;
+pub mod settlement;
+
+pub use da::{DaOptions, DaPointer, DataAvailability};
+pub use job::{Job, JobStatus, JobStore};
+pub use orchestrator::Orchestrator;
+pub use prover::{Proof, ProverBackend, ProvingInput};
+pub use settlement::{
+    Settlement, SettlementConfig, SettlementOutcome, StarknetSettlement, StateTransition,
+};
+
+/// The result of the Saya operations.
+pub type SayaResult<T> = Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Block {0} is pending, only accepted blocks can be proven")]
+    PendingBlock(u64),
+
+    #[error("Prover backend `{backend}` failed to prove block {block}: {message}")]
+    Prover { backend: String, block: u64, message: String },
+
+    #[error("Failed to settle block {block}: {message}")]
+    Settlement { block: u64, message: String },
+
+    #[error("Failed to publish the state diff of block {block}: {message}")]
+    DataAvailability { block: u64, message: String },
+
+    #[error("Invalid data availability namespace `{0}`, expected 1 to 10 bytes")]
+    InvalidDaNamespace(String),
+
+    #[error("Block {0} is proved but its state transition is unknown")]
+    MissingTransition(u64),
+
+    #[error(transparent)]
+    Provider(#[from] starknet::providers::ProviderError),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
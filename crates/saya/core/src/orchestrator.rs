@@ -0,0 +1,476 @@
+//! Drives the proving of the blocks of Katana, from fetching their inputs to saving their proofs.
+
+use std::time::Duration;
+
+use starknet::core::types::{BlockId, MaybePendingStateUpdate};
+use starknet::providers::Provider;
+use tracing::{error, info};
+
+use crate::da::{encode_state_diff, DataAvailability};
+use crate::job::{JobStatus, JobStore};
+use crate::prover::{ProverBackend, ProvingInput};
+use crate::settlement::{Settlement, SettlementOutcome, StateTransition};
+use crate::{Error, SayaResult};
+
+/// The interval at which Katana is polled for new blocks.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The first block proved by default. The genesis block isn't proved, its state is the initial
+/// state of the settlement contract.
+pub const DEFAULT_START_BLOCK: u64 = 1;
+
+/// Proves the blocks of a Katana node, one after the other, as they are produced, and settles
+/// them if a settlement layer is configured.
+pub struct Orchestrator<P, B> {
+    provider: P,
+    backend: B,
+    store: JobStore,
+    da: Option<Box<dyn DataAvailability>>,
+    settlement: Option<Box<dyn Settlement>>,
+    poll_interval: Duration,
+    start_block: u64,
+}
+
+impl<P, B> Orchestrator<P, B>
+where
+    P: Provider + Send + Sync,
+    B: ProverBackend,
+{
+    pub fn new(provider: P, backend: B, store: JobStore) -> Self {
+        Self {
+            provider,
+            backend,
+            store,
+            da: None,
+            settlement: None,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            start_block: DEFAULT_START_BLOCK,
+        }
+    }
+
+    /// Publishes the state diff of each block to `da` before proving it.
+    pub fn with_da(mut self, da: impl DataAvailability + 'static) -> Self {
+        self.da = Some(Box::new(da));
+        self
+    }
+
+    pub fn with_settlement(mut self, settlement: impl Settlement + 'static) -> Self {
+        self.settlement = Some(Box::new(settlement));
+        self
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Starts proving at `start_block`, the blocks before it being already settled.
+    pub fn with_start_block(mut self, start_block: u64) -> Self {
+        self.start_block = start_block;
+        self
+    }
+
+    pub fn store(&self) -> &JobStore {
+        &self.store
+    }
+
+    /// Proves the new blocks of Katana until an error occurs in the job store.
+    ///
+    /// Errors of Katana, of the backend or of the settlement layer are recorded in the failed job,
+    /// which is retried after the poll interval.
+    pub async fn run(mut self) -> SayaResult<()> {
+        loop {
+            match self.sync().await {
+                Ok(_) => {}
+                Err(e @ (Error::Io(_) | Error::Json(_))) => return Err(e),
+                Err(e) => error!(target: "saya", error = %e, "Proving blocks."),
+            }
+
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Proves all the blocks not proved yet, up to the latest block of Katana, settling each one
+    /// once proved.
+    ///
+    /// Returns the number of blocks proved.
+    pub async fn sync(&mut self) -> SayaResult<u64> {
+        self.settle_proved().await?;
+
+        let latest = self.provider.block_number().await?;
+        let mut proved = 0;
+
+        loop {
+            let block = self.store.next_block(self.start_block);
+            if block > latest {
+                return Ok(proved);
+            }
+
+            self.prove_block(block).await?;
+            self.settle_proved().await?;
+            proved += 1;
+        }
+    }
+
+    /// Settles the proved blocks which are not settled yet, in order.
+    ///
+    /// A block is settled once its settlement transaction is accepted. If the transaction
+    /// reverts, the job fails and the block is proved again.
+    async fn settle_proved(&mut self) -> SayaResult<()> {
+        let Some(settlement) = self.settlement.as_mut() else {
+            return Ok(());
+        };
+
+        while let Some(job) = self.store.next_settlement(self.start_block) {
+            let block = job.block;
+            let transition = job.transition.ok_or(Error::MissingTransition(block))?;
+
+            let result = match settlement.settle(&transition).await {
+                Ok(transaction_hash) => settlement
+                    .wait(transaction_hash)
+                    .await
+                    .map(|outcome| (transaction_hash, outcome)),
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok((transaction_hash, SettlementOutcome::Accepted)) => {
+                    self.store.update(block, JobStatus::Settled, None)?;
+                    info!(
+                        target: "saya",
+                        %block,
+                        transaction_hash = format!("{:#066x}", transaction_hash),
+                        "Block settled."
+                    );
+                }
+                Ok((transaction_hash, SettlementOutcome::Reverted(reason))) => {
+                    let message = format!(
+                        "Settlement transaction {transaction_hash:#066x} reverted: {reason}"
+                    );
+                    self.store.update(block, JobStatus::Failed, Some(message.clone()))?;
+                    return Err(Error::Settlement { block, message });
+                }
+                Err(e) => {
+                    let message = e.to_string();
+                    self.store.update(block, JobStatus::Proved, Some(message.clone()))?;
+                    return Err(Error::Settlement { block, message });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Proves `block`, recording the progress of its job in the store.
+    pub async fn prove_block(&mut self, block: u64) -> SayaResult<()> {
+        self.store.update(block, JobStatus::Fetching, None)?;
+
+        let input = match self.fetch_input(block).await {
+            Ok(input) => input,
+            Err(e) => {
+                self.store.update(block, JobStatus::Failed, Some(e.to_string()))?;
+                return Err(e);
+            }
+        };
+
+        if let Err(e) = self.publish_state_diff(block, &input).await {
+            self.store.update(block, JobStatus::Failed, Some(e.to_string()))?;
+            return Err(e);
+        }
+
+        self.store.update(block, JobStatus::Proving, None)?;
+        info!(target: "saya", %block, backend = self.backend.name(), "Proving block.");
+
+        match self.backend.prove(&input).await {
+            Ok(proof) => {
+                let transition = StateTransition {
+                    block,
+                    prev_state_root: input.state_update.old_root,
+                    new_state_root: input.state_update.new_root,
+                    fact: proof.fact,
+                };
+
+                self.store.save_proof(block, &proof, transition)?;
+                info!(target: "saya", %block, "Block proved.");
+                Ok(())
+            }
+            Err(e) => {
+                let message = e.to_string();
+                self.store.update(block, JobStatus::Failed, Some(message.clone()))?;
+                Err(Error::Prover { backend: self.backend.name().to_string(), block, message })
+            }
+        }
+    }
+
+    /// Publishes the state diff of `block` to the data availability layer, unless it's already
+    /// published by a previous attempt.
+    async fn publish_state_diff(&mut self, block: u64, input: &ProvingInput) -> SayaResult<()> {
+        let Some(da) = self.da.as_ref() else {
+            return Ok(());
+        };
+
+        if self.store.job(block).is_some_and(|job| job.da.is_some()) {
+            return Ok(());
+        }
+
+        let data = encode_state_diff(&input.state_update.state_diff);
+        let pointer = da
+            .publish(block, data)
+            .await
+            .map_err(|e| Error::DataAvailability { block, message: e.to_string() })?;
+
+        self.store.set_da_pointer(block, pointer)
+    }
+
+    /// Pulls the state update and the execution traces of `block` from Katana.
+    async fn fetch_input(&self, block: u64) -> SayaResult<ProvingInput> {
+        let block_id = BlockId::Number(block);
+
+        let state_update = match self.provider.get_state_update(block_id).await? {
+            MaybePendingStateUpdate::Update(state_update) => state_update,
+            MaybePendingStateUpdate::PendingUpdate(_) => return Err(Error::PendingBlock(block)),
+        };
+
+        let traces = self.provider.trace_block_transactions(block_id).await?;
+
+        Ok(ProvingInput {
+            block_number: block,
+            block_hash: state_update.block_hash,
+            state_update,
+            traces,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use serde::de::DeserializeOwned;
+    use serde::Serialize;
+    use serde_json::{json, Value};
+    use starknet::core::types::Felt;
+    use starknet::providers::jsonrpc::{
+        JsonRpcClient, JsonRpcMethod, JsonRpcResponse, JsonRpcTransport,
+    };
+    use starknet::providers::ProviderRequestData;
+
+    use super::*;
+    use crate::prover::{BackendError, Proof};
+
+    /// A Katana node whose latest block is `latest`, block `n` moving the state root from `n` to
+    /// `n + 1`.
+    #[derive(Debug)]
+    struct MockKatana {
+        latest: u64,
+    }
+
+    #[async_trait]
+    impl JsonRpcTransport for MockKatana {
+        type Error = serde_json::Error;
+
+        async fn send_request<P, R>(
+            &self,
+            method: JsonRpcMethod,
+            params: P,
+        ) -> Result<JsonRpcResponse<R>, Self::Error>
+        where
+            P: Serialize + Send,
+            R: DeserializeOwned,
+        {
+            let result = match method {
+                JsonRpcMethod::BlockNumber => json!(self.latest),
+                JsonRpcMethod::GetStateUpdate => {
+                    let block = block_number(&serde_json::to_value(params)?).unwrap();
+                    json!({
+                        "block_hash": Felt::from(block),
+                        "old_root": Felt::from(block),
+                        "new_root": Felt::from(block + 1),
+                        "state_diff": {
+                            "storage_diffs": [],
+                            "deprecated_declared_classes": [],
+                            "declared_classes": [],
+                            "deployed_contracts": [],
+                            "replaced_classes": [],
+                            "nonces": []
+                        }
+                    })
+                }
+                JsonRpcMethod::TraceBlockTransactions => json!([]),
+                method => panic!("Unexpected request {method:?}"),
+            };
+
+            serde_json::from_value(json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+        }
+
+        async fn send_requests<R>(&self, _: R) -> Result<Vec<JsonRpcResponse<Value>>, Self::Error>
+        where
+            R: AsRef<[ProviderRequestData]> + Send + Sync,
+        {
+            unimplemented!()
+        }
+    }
+
+    /// Finds the number of the block requested in the params of a request.
+    fn block_number(params: &Value) -> Option<u64> {
+        match params {
+            Value::Object(map) => match map.get("block_number") {
+                Some(block) => block.as_u64(),
+                None => map.values().find_map(block_number),
+            },
+            Value::Array(items) => items.iter().find_map(block_number),
+            _ => None,
+        }
+    }
+
+    /// A prover failing to prove `failing_block`, if set.
+    #[derive(Default)]
+    struct MockBackend {
+        failing_block: Arc<Mutex<Option<u64>>>,
+    }
+
+    #[async_trait]
+    impl ProverBackend for MockBackend {
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        async fn prove(&self, input: &ProvingInput) -> Result<Proof, BackendError> {
+            if *self.failing_block.lock().unwrap() == Some(input.block_number) {
+                return Err("prover down".into());
+            }
+
+            Ok(Proof {
+                backend: "mock".to_string(),
+                fact: input.block_hash,
+                data: json!({ "block": input.block_number }),
+            })
+        }
+    }
+
+    /// A settlement layer reverting the transaction settling `reverting_block`, if set.
+    #[derive(Default)]
+    struct MockSettlement {
+        reverting_block: Arc<Mutex<Option<u64>>>,
+        /// The blocks of the submitted state transitions, in order.
+        submitted: Arc<Mutex<Vec<u64>>>,
+    }
+
+    #[async_trait]
+    impl Settlement for MockSettlement {
+        async fn settle(&mut self, transition: &StateTransition) -> Result<Felt, BackendError> {
+            self.submitted.lock().unwrap().push(transition.block);
+            Ok(Felt::from(transition.block))
+        }
+
+        async fn wait(&self, transaction_hash: Felt) -> Result<SettlementOutcome, BackendError> {
+            if self.reverting_block.lock().unwrap().map(Felt::from) == Some(transaction_hash) {
+                return Ok(SettlementOutcome::Reverted("invalid fact".to_string()));
+            }
+
+            Ok(SettlementOutcome::Accepted)
+        }
+    }
+
+    fn orchestrator(
+        latest: u64,
+        backend: MockBackend,
+        dir: &tempfile::TempDir,
+    ) -> Orchestrator<JsonRpcClient<MockKatana>, MockBackend> {
+        let provider = JsonRpcClient::new(MockKatana { latest });
+        Orchestrator::new(provider, backend, JobStore::open(dir.path()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn blocks_are_proved_after_genesis() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut orchestrator = orchestrator(3, MockBackend::default(), &dir);
+
+        assert_eq!(orchestrator.sync().await.unwrap(), 3);
+        assert!(orchestrator.store().job(0).is_none());
+
+        for block in 1..=3 {
+            let job = orchestrator.store().job(block).unwrap();
+            assert_eq!(job.status, JobStatus::Proved);
+            assert_eq!(job.attempts, 1);
+            assert_eq!(
+                job.transition,
+                Some(StateTransition {
+                    block,
+                    prev_state_root: Felt::from(block),
+                    new_state_root: Felt::from(block + 1),
+                    fact: Felt::from(block),
+                })
+            );
+            assert_eq!(orchestrator.store().proof(block).unwrap().unwrap().fact, Felt::from(block));
+        }
+
+        assert_eq!(orchestrator.sync().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn proving_starts_at_the_start_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut orchestrator = orchestrator(5, MockBackend::default(), &dir).with_start_block(4);
+
+        assert_eq!(orchestrator.sync().await.unwrap(), 2);
+        assert_eq!(orchestrator.store().jobs().map(|job| job.block).collect::<Vec<_>>(), [4, 5]);
+    }
+
+    #[tokio::test]
+    async fn failed_block_is_retried() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = MockBackend::default();
+        let failing_block = backend.failing_block.clone();
+        *failing_block.lock().unwrap() = Some(2);
+
+        let mut orchestrator = orchestrator(3, backend, &dir);
+
+        let err = orchestrator.sync().await.unwrap_err();
+        assert!(matches!(err, Error::Prover { block: 2, .. }));
+
+        let failed = orchestrator.store().job(2).unwrap();
+        assert_eq!(failed.status, JobStatus::Failed);
+        assert_eq!(failed.error.as_deref(), Some("prover down"));
+        assert_eq!(orchestrator.store().job(1).unwrap().status, JobStatus::Proved);
+        assert!(orchestrator.store().job(3).is_none());
+
+        *failing_block.lock().unwrap() = None;
+        assert_eq!(orchestrator.sync().await.unwrap(), 2);
+
+        let retried = orchestrator.store().job(2).unwrap();
+        assert_eq!(retried.status, JobStatus::Proved);
+        assert_eq!(retried.attempts, 2);
+        assert_eq!(retried.error, None);
+    }
+
+    #[tokio::test]
+    async fn reverted_settlement_is_proved_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let settlement = MockSettlement::default();
+        let reverting_block = settlement.reverting_block.clone();
+        let submitted = settlement.submitted.clone();
+        *reverting_block.lock().unwrap() = Some(2);
+
+        let mut orchestrator =
+            orchestrator(3, MockBackend::default(), &dir).with_settlement(settlement);
+
+        let err = orchestrator.sync().await.unwrap_err();
+        assert!(matches!(err, Error::Settlement { block: 2, .. }));
+        assert_eq!(orchestrator.store().job(1).unwrap().status, JobStatus::Settled);
+
+        let reverted = orchestrator.store().job(2).unwrap();
+        assert_eq!(reverted.status, JobStatus::Failed);
+        assert!(reverted.error.as_deref().unwrap().ends_with("reverted: invalid fact"));
+        assert!(orchestrator.store().job(3).is_none());
+
+        *reverting_block.lock().unwrap() = None;
+        assert_eq!(orchestrator.sync().await.unwrap(), 2);
+
+        for block in 1..=3 {
+            assert_eq!(orchestrator.store().job(block).unwrap().status, JobStatus::Settled);
+        }
+        assert_eq!(*submitted.lock().unwrap(), [1, 2, 2, 3]);
+    }
+}
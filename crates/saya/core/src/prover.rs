@@ -0,0 +1,40 @@
+//! The abstraction over the provers generating the proofs of the blocks.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use starknet::core::types::{Felt, StateUpdate, TransactionTraceWithHash};
+
+/// The error returned by a prover backend.
+pub type BackendError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Everything pulled from Katana to prove a block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvingInput {
+    pub block_number: u64,
+    pub block_hash: Felt,
+    /// The state diff of the block, with the roots before and after the block.
+    pub state_update: StateUpdate,
+    /// The execution traces of the transactions of the block, in order.
+    pub traces: Vec<TransactionTraceWithHash>,
+}
+
+/// A proof of a block, in the format of the backend which generated it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Proof {
+    /// The name of the backend which generated the proof.
+    pub backend: String,
+    /// The fact registered in the verifier once the proof is verified, which the settlement
+    /// contract checks before accepting the state transition.
+    pub fact: Felt,
+    pub data: serde_json::Value,
+}
+
+/// A prover generating the proof of a block, eg SNOS followed by Stwo.
+#[async_trait]
+pub trait ProverBackend: Send + Sync {
+    /// The name of the backend, recorded in the proof jobs.
+    fn name(&self) -> &str;
+
+    /// Generates the proof of the block described by `input`.
+    async fn prove(&self, input: &ProvingInput) -> Result<Proof, BackendError>;
+}
@@ -0,0 +1,251 @@
+//! Submits the proved state transitions to the settlement layer of the appchain.
+
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use starknet::accounts::ConnectedAccount;
+use starknet::core::types::{Call, ExecutionResult, Felt, ReceiptBlock, StarknetError};
+use starknet::core::utils::get_selector_from_name;
+use starknet::providers::{Provider, ProviderError};
+use tracing::{trace, warn};
+
+use crate::prover::BackendError;
+
+/// The default entrypoint of the settlement contract.
+pub const DEFAULT_ENTRYPOINT: &str = "update_state";
+
+/// The state transition of a block, as accepted by the settlement contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateTransition {
+    pub block: u64,
+    /// The state root before the block.
+    pub prev_state_root: Felt,
+    /// The state root after the block.
+    pub new_state_root: Felt,
+    /// The fact of the verified proof of the block.
+    pub fact: Felt,
+}
+
+impl StateTransition {
+    /// The calldata of the settlement entrypoint:
+    /// `[block, prev_state_root, new_state_root, fact]`.
+    pub fn calldata(&self) -> Vec<Felt> {
+        vec![Felt::from(self.block), self.prev_state_root, self.new_state_root, self.fact]
+    }
+}
+
+/// The outcome of a settlement transaction, once included in a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettlementOutcome {
+    /// The state transition is accepted by the settlement contract.
+    Accepted,
+    /// The transaction reverted, with the revert reason.
+    Reverted(String),
+}
+
+/// A settlement layer accepting the state transitions of the appchain.
+///
+/// Only Starknet is supported with [`StarknetSettlement`], settling on Ethereum requires another
+/// implementation of this trait.
+#[async_trait]
+pub trait Settlement: Send + Sync {
+    /// Submits `transition`, returning the hash of the settlement transaction.
+    async fn settle(&mut self, transition: &StateTransition) -> Result<Felt, BackendError>;
+
+    /// Waits for the settlement transaction `transaction_hash` to be included in a block.
+    async fn wait(&self, transaction_hash: Felt) -> Result<SettlementOutcome, BackendError>;
+}
+
+#[derive(Debug, Clone)]
+pub struct SettlementConfig {
+    /// The address of the settlement contract.
+    pub contract_address: Felt,
+    /// The entrypoint of the settlement contract receiving the state transitions.
+    pub entrypoint: String,
+    /// The number of times a failed submission is retried.
+    pub max_retries: u32,
+    /// The delay before the first retry, doubled after each retry.
+    pub initial_backoff: Duration,
+    /// The maximum delay between two retries.
+    pub max_backoff: Duration,
+    /// The interval at which the receipt of a settlement transaction is polled.
+    pub receipt_poll_interval: Duration,
+    /// How long to wait for a settlement transaction to be included in a block.
+    pub receipt_timeout: Duration,
+}
+
+impl SettlementConfig {
+    pub fn new(contract_address: Felt) -> Self {
+        Self {
+            contract_address,
+            entrypoint: DEFAULT_ENTRYPOINT.to_string(),
+            max_retries: 5,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            receipt_poll_interval: Duration::from_secs(1),
+            receipt_timeout: Duration::from_secs(300),
+        }
+    }
+
+    /// Returns the delay before the retry following `attempt`, starting at 0.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt);
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+/// Settles the state transitions on a Starknet contract, with the given account.
+///
+/// The nonce of the account is tracked locally, so that several transitions can be submitted
+/// without waiting for the previous transactions to be accepted. It's fetched again from the chain
+/// after a failed submission, in case the failure comes from a nonce out of sync.
+#[derive(Debug)]
+pub struct StarknetSettlement<A> {
+    account: A,
+    config: SettlementConfig,
+    nonce: Option<Felt>,
+}
+
+impl<A> StarknetSettlement<A>
+where
+    A: ConnectedAccount + Send + Sync,
+{
+    pub fn new(account: A, config: SettlementConfig) -> Self {
+        Self { account, config, nonce: None }
+    }
+
+    async fn submit(&mut self, call: Call) -> Result<Felt, BackendError> {
+        let nonce = match self.nonce {
+            Some(nonce) => nonce,
+            None => self.account.get_nonce().await?,
+        };
+
+        let result = self
+            .account
+            .execute_v3(vec![call])
+            .nonce(nonce)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        self.nonce = Some(nonce + Felt::ONE);
+        Ok(result.transaction_hash)
+    }
+}
+
+#[async_trait]
+impl<A> Settlement for StarknetSettlement<A>
+where
+    A: ConnectedAccount + Send + Sync,
+{
+    async fn settle(&mut self, transition: &StateTransition) -> Result<Felt, BackendError> {
+        let call = Call {
+            to: self.config.contract_address,
+            selector: get_selector_from_name(&self.config.entrypoint)?,
+            calldata: transition.calldata(),
+        };
+
+        let mut attempt = 0;
+        loop {
+            match self.submit(call.clone()).await {
+                Ok(transaction_hash) => {
+                    trace!(
+                        target: "saya",
+                        block = transition.block,
+                        transaction_hash = format!("{:#066x}", transaction_hash),
+                        "State transition submitted."
+                    );
+                    return Ok(transaction_hash);
+                }
+                Err(e) if attempt < self.config.max_retries => {
+                    self.nonce = None;
+
+                    let delay = self.config.backoff(attempt);
+                    warn!(
+                        target: "saya",
+                        block = transition.block,
+                        error = %e,
+                        ?delay,
+                        "Settlement failed, retrying."
+                    );
+
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    self.nonce = None;
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    async fn wait(&self, transaction_hash: Felt) -> Result<SettlementOutcome, BackendError> {
+        let start = Instant::now();
+
+        loop {
+            match self.account.provider().get_transaction_receipt(transaction_hash).await {
+                Ok(receipt) if matches!(receipt.block, ReceiptBlock::Block { .. }) => {
+                    return Ok(match receipt.receipt.execution_result() {
+                        ExecutionResult::Succeeded => SettlementOutcome::Accepted,
+                        ExecutionResult::Reverted { reason } => {
+                            SettlementOutcome::Reverted(reason.clone())
+                        }
+                    });
+                }
+                // the transaction is still pending
+                Ok(_)
+                | Err(ProviderError::StarknetError(StarknetError::TransactionHashNotFound)) => {}
+                Err(e) => return Err(e.into()),
+            }
+
+            if start.elapsed() >= self.config.receipt_timeout {
+                return Err(format!(
+                    "Settlement transaction {transaction_hash:#066x} not included in a block \
+                     after {:?}",
+                    self.config.receipt_timeout
+                )
+                .into());
+            }
+
+            tokio::time::sleep(self.config.receipt_poll_interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_is_doubled_up_to_the_maximum() {
+        let config = SettlementConfig::new(Felt::ONE);
+
+        assert_eq!(config.backoff(0), Duration::from_secs(1));
+        assert_eq!(config.backoff(1), Duration::from_secs(2));
+        assert_eq!(config.backoff(5), Duration::from_secs(32));
+        assert_eq!(config.backoff(6), Duration::from_secs(60));
+        assert_eq!(config.backoff(100), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn transition_calldata() {
+        let transition = StateTransition {
+            block: 3,
+            prev_state_root: Felt::from(0x1_u64),
+            new_state_root: Felt::from(0x2_u64),
+            fact: Felt::from(0xfac7_u64),
+        };
+
+        assert_eq!(
+            transition.calldata(),
+            vec![
+                Felt::from(3_u64),
+                Felt::from(0x1_u64),
+                Felt::from(0x2_u64),
+                Felt::from(0xfac7_u64)
+            ]
+        );
+    }
+}
@@ -0,0 +1,255 @@
+//! Creation of the account config files, and deployment of the OpenZeppelin account contracts
+//! they describe.
+//!
+//! The config files have the same format as the ones of `starkli`, so the accounts can be used by
+//! both tools.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use dojo_utils::{FeeConfig, TransactionExt, TransactionWaiter, TxnAction, TxnConfig};
+use serde::{Deserialize, Serialize};
+use starknet::accounts::{AccountFactory, OpenZeppelinAccountFactory};
+use starknet::core::types::{BlockId, BlockTag, FunctionCall};
+use starknet::core::utils::get_contract_address;
+use starknet::macros::{felt, selector};
+use starknet::providers::Provider;
+use starknet::signers::{LocalWallet, Signer, SigningKey};
+use starknet_crypto::Felt;
+
+/// The class hash of the OpenZeppelin account contract deployed for the new accounts (v0.8.1).
+pub const OZ_ACCOUNT_CLASS_HASH: Felt =
+    felt!("0x05400e90f7e0ae78bd02c77cd75527280470e2fe19c54970dd79dc37a9d3645c");
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AccountConfig {
+    pub version: u64,
+    pub variant: AccountVariant,
+    pub deployment: DeploymentStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AccountVariant {
+    OpenZeppelin(OzAccountConfig),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OzAccountConfig {
+    pub version: u64,
+    pub public_key: Felt,
+    #[serde(default)]
+    pub legacy: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DeploymentStatus {
+    /// The account is yet to be deployed, at the address derived from its class hash and salt.
+    Undeployed {
+        class_hash: Felt,
+        salt: Felt,
+    },
+    Deployed {
+        class_hash: Felt,
+        address: Felt,
+    },
+}
+
+/// Creates the config of a new OpenZeppelin account for the public key of `signer`, without
+/// deploying it.
+pub async fn new(signer: LocalWallet, force: bool, file: PathBuf) -> Result<()> {
+    if file.exists() && !force {
+        bail!(
+            "Account config file {} already exists, use --force to overwrite it.",
+            file.display()
+        );
+    }
+
+    let public_key = signer.get_public_key().await?.scalar();
+    let salt = SigningKey::from_random().secret_scalar();
+    let address = get_contract_address(salt, OZ_ACCOUNT_CLASS_HASH, &[public_key], Felt::ZERO);
+
+    let config = AccountConfig {
+        version: 1,
+        variant: AccountVariant::OpenZeppelin(OzAccountConfig {
+            version: 1,
+            public_key,
+            legacy: false,
+        }),
+        deployment: DeploymentStatus::Undeployed { class_hash: OZ_ACCOUNT_CLASS_HASH, salt },
+    };
+    write_config(&config, &file)?;
+
+    println!("Created new account config file: {}", file.display());
+    println!(
+        "Once deployed, the account will be at: {}",
+        format!("{address:#066x}").bright_yellow()
+    );
+    println!(
+        "Deploy it with: {}",
+        format!("sozo account deploy {}", file.display()).bright_yellow()
+    );
+
+    Ok(())
+}
+
+/// Deploys the account of the config `file` with a `DeployAccount` transaction signed by
+/// `signer`, and marks it as deployed in the config.
+///
+/// As the deployment fee is paid by the account itself, the user is asked to fund it before the
+/// transaction is sent, unless `no_confirmation` is set.
+pub async fn deploy<P>(
+    provider: P,
+    signer: LocalWallet,
+    txn_action: TxnAction,
+    nonce: Option<Felt>,
+    poll_interval: u64,
+    file: PathBuf,
+    no_confirmation: bool,
+) -> Result<()>
+where
+    P: Provider + Send + Sync,
+{
+    let mut config = read_config(&file)?;
+    let DeploymentStatus::Undeployed { class_hash, salt } = config.deployment else {
+        bail!("Account of {} is already deployed.", file.display());
+    };
+
+    let AccountVariant::OpenZeppelin(variant) = &config.variant;
+    if signer.get_public_key().await?.scalar() != variant.public_key {
+        bail!("The public key of the signer doesn't match the one of the account config.");
+    }
+
+    let chain_id = provider.chain_id().await?;
+    let factory = OpenZeppelinAccountFactory::new(class_hash, chain_id, &signer, &provider).await?;
+    let address = factory.deploy_v3(salt).address();
+
+    let (receipt, fee_config) = match txn_action {
+        TxnAction::Estimate => {
+            let mut deployment = factory.deploy_v3(salt);
+            if let Some(nonce) = nonce {
+                deployment = deployment.nonce(nonce);
+            }
+
+            let estimate = deployment.estimate_fee().await?;
+            println!("Estimated fee: {} STRK (in FRI)", estimate.overall_fee);
+            return Ok(());
+        }
+        TxnAction::Simulate => {
+            let mut deployment = factory.deploy_v3(salt);
+            if let Some(nonce) = nonce {
+                deployment = deployment.nonce(nonce);
+            }
+
+            let simulation = deployment.simulate(false, false).await?;
+            println!("{}", serde_json::to_string_pretty(&simulation)?);
+            return Ok(());
+        }
+        TxnAction::Send { receipt, fee_config, .. } => (receipt, fee_config),
+    };
+
+    println!("The account will be deployed at: {}", format!("{address:#066x}").bright_yellow());
+    if !no_confirmation {
+        print!("Fund it to pay for the deployment fee, then press [ENTER] to continue.");
+        std::io::stdout().flush()?;
+        std::io::stdin().read_line(&mut String::new())?;
+    }
+
+    let txn_config = TxnConfig { fee_config, ..Default::default() };
+    let transaction_hash = match fee_config {
+        FeeConfig::Strk(_) => {
+            let mut deployment = factory.deploy_v3(salt);
+            if let Some(nonce) = nonce {
+                deployment = deployment.nonce(nonce);
+            }
+            deployment.send_with_cfg(&txn_config).await?.transaction_hash
+        }
+        FeeConfig::Eth(_) => {
+            let mut deployment = factory.deploy_v1(salt);
+            if let Some(nonce) = nonce {
+                deployment = deployment.nonce(nonce);
+            }
+            deployment.send_with_cfg(&txn_config).await?.transaction_hash
+        }
+    };
+
+    println!("Transaction hash: {transaction_hash:#066x}");
+    let result = TransactionWaiter::new(transaction_hash, &provider)
+        .with_interval(poll_interval)
+        .await
+        .context("Failed to deploy the account.")?;
+    if receipt {
+        println!("Receipt: {}", serde_json::to_string_pretty(&result)?);
+    }
+
+    config.deployment = DeploymentStatus::Deployed { class_hash, address };
+    write_config(&config, &file)?;
+
+    println!("Account deployed at: {}", format!("{address:#066x}").bright_green());
+    Ok(())
+}
+
+/// Creates the config of an OpenZeppelin account already deployed at `address`.
+pub async fn fetch<P>(provider: P, force: bool, output: PathBuf, address: Felt) -> Result<()>
+where
+    P: Provider + Send + Sync,
+{
+    if output.exists() && !force {
+        bail!(
+            "Account config file {} already exists, use --force to overwrite it.",
+            output.display()
+        );
+    }
+
+    let block_id = BlockId::Tag(BlockTag::Pending);
+    let class_hash = provider.get_class_hash_at(block_id, address).await?;
+
+    let public_key = provider
+        .call(
+            FunctionCall {
+                contract_address: address,
+                entry_point_selector: selector!("get_public_key"),
+                calldata: vec![],
+            },
+            block_id,
+        )
+        .await
+        .context("Failed to fetch the public key, the account must be an OpenZeppelin account.")?;
+    let public_key = *public_key.first().context("Unexpected empty public key.")?;
+
+    let config = AccountConfig {
+        version: 1,
+        variant: AccountVariant::OpenZeppelin(OzAccountConfig {
+            version: 1,
+            public_key,
+            legacy: false,
+        }),
+        deployment: DeploymentStatus::Deployed { class_hash, address },
+    };
+    write_config(&config, &output)?;
+
+    println!("Account config saved to: {}", output.display());
+    Ok(())
+}
+
+fn read_config(file: &Path) -> Result<AccountConfig> {
+    let config = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read the account config {}.", file.display()))?;
+    serde_json::from_str(&config)
+        .with_context(|| format!("Invalid account config {}.", file.display()))
+}
+
+fn write_config(config: &AccountConfig, file: &Path) -> Result<()> {
+    if let Some(parent) = file.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut content = serde_json::to_string_pretty(config)?;
+    content.push('\n');
+    std::fs::write(file, content)?;
+
+    Ok(())
+}
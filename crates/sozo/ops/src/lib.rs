@@ -1,5 +1,7 @@
 // #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+pub mod account;
+pub mod keystore;
 pub mod migrate;
 pub mod migration_ui;
 pub mod model;
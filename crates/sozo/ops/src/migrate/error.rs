@@ -36,4 +36,6 @@ where
     TransactionError(#[from] TransactionError<S>),
     #[error("Declaration of class failed: {0}")]
     DeclareClassError(String),
+    #[error("The upgrade of `{tag}` is not compatible with the stored data: {reason}.")]
+    IncompatibleResourceUpgrade { tag: String, reason: String },
 }
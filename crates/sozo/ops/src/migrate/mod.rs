@@ -9,7 +9,8 @@
 //! 2. Then, all the resources (Contract, Models, Events) are synced, which can consist of:
 //!    - Declaring the classes.
 //!    - Registering the resources.
-//!    - Upgrading the resources.
+//!    - Upgrading the resources. Before sending the upgrades, each model and event upgrade is
+//!      simulated to detect a new layout or schema which is not compatible with the stored data.
 //! 3. Once resources are synced, the permissions are synced. Permissions can be in different
 //!    states:
 //!    - For newly registered resources, the permissions are applied.
@@ -19,10 +20,13 @@
 //!    initialization of contracts can mutate resources.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use anyhow::anyhow;
 use cainome::cairo_serde::{ByteArray, ClassHash, ContractAddress};
-use dojo_utils::{Declarer, Deployer, Invoker, LabeledClass, TransactionResult, TxnConfig};
+use dojo_utils::{
+    Declarer, Deployer, FeeConfig, Invoker, LabeledClass, TransactionResult, TxnConfig,
+};
 use dojo_world::config::calldata_decoder::decode_calldata;
 use dojo_world::config::{metadata_config, ProfileConfig, ResourceConfig, WorldMetadata};
 use dojo_world::constants::WORLD;
@@ -36,7 +40,7 @@ use dojo_world::metadata::MetadataStorage;
 use dojo_world::remote::ResourceRemote;
 use dojo_world::services::UploadService;
 use dojo_world::{utils, ResourceType};
-use starknet::accounts::{ConnectedAccount, SingleOwnerAccount};
+use starknet::accounts::{Account, ConnectedAccount, SingleOwnerAccount};
 use starknet::core::types::Call;
 use starknet::providers::{AnyProvider, Provider};
 use starknet::signers::LocalWallet;
@@ -48,6 +52,11 @@ use crate::migration_ui::MigrationUi;
 pub mod error;
 pub use error::MigrationError;
 
+pub mod plan;
+pub use plan::{
+    MigrationPlan, PlannedClass, PlannedPermission, PlannedResource, PlannedWorldChange,
+};
+
 #[derive(Debug)]
 pub struct Migration<A>
 where
@@ -115,6 +124,149 @@ where
         })
     }
 
+    /// Computes the changes the migration would apply to the world, and estimates its fee,
+    /// without sending any transaction.
+    pub async fn plan(&self) -> Result<MigrationPlan, MigrationError<A::SignError>> {
+        let world = match (&self.diff.world_info.status, self.guest) {
+            (_, true) | (WorldStatus::Synced, _) => None,
+            (WorldStatus::NotDeployed, _) => Some(PlannedWorldChange::Deploy),
+            (WorldStatus::NewVersion, _) => Some(PlannedWorldChange::Upgrade),
+        };
+
+        let (mut calls, mut classes) = if !self.diff.is_synced() {
+            let (calls, classes, _) = self.resources_calls_classes().await?;
+            (calls, classes)
+        } else {
+            (vec![], HashMap::new())
+        };
+
+        // The world is upgraded before the resources are synced.
+        if world == Some(PlannedWorldChange::Upgrade) {
+            calls
+                .insert(0, self.world.upgrade_getcall(&ClassHash(self.diff.world_info.class_hash)));
+        }
+
+        let resources = self
+            .diff
+            .resources
+            .values()
+            .filter(|resource| !self.profile_config.is_skipped(&resource.tag()))
+            .filter_map(|resource| {
+                let upgrade = match resource {
+                    ResourceDiff::Created(_) => false,
+                    ResourceDiff::Updated(_, _) => true,
+                    ResourceDiff::Synced(_, _) => return None,
+                };
+
+                Some(PlannedResource {
+                    tag: resource.tag(),
+                    resource_type: format!("{:?}", resource.resource_type()),
+                    upgrade,
+                })
+            })
+            .collect();
+
+        let (permissions, permissions_calls): (Vec<_>, Vec<_>) =
+            self.permissions_calls().into_iter().unzip();
+        calls.extend(permissions_calls);
+
+        let (initializations, init_calls): (Vec<_>, Vec<_>) =
+            self.init_calls()?.into_iter().unzip();
+        calls.extend(init_calls);
+
+        let (external_contract_calls, external_contract_classes) =
+            self.external_contracts_calls_classes().await?;
+        let (external_contracts, external_contract_calls): (Vec<_>, Vec<_>) =
+            external_contract_calls.into_iter().unzip();
+        calls.extend(external_contract_calls);
+        classes.extend(external_contract_classes);
+
+        let declaration_fee = self.estimate_declaration_fee(&classes).await;
+
+        // The calls can only be simulated once the world and the classes they depend on exist.
+        let invoke_fee = if world.is_none() && classes.is_empty() {
+            self.estimate_invoke_fee(&calls).await
+        } else {
+            None
+        };
+
+        let classes = classes
+            .into_values()
+            .map(|c| PlannedClass { class_hash: c.class.class_hash(), label: c.label })
+            .collect();
+
+        Ok(MigrationPlan {
+            world,
+            classes,
+            resources,
+            permissions,
+            initializations,
+            external_contracts,
+            calls,
+            declaration_fee,
+            invoke_fee,
+        })
+    }
+
+    /// Estimates the fee of declaring the classes, with the fee token of the transaction config.
+    ///
+    /// Returns `None` if any of the estimations fails.
+    async fn estimate_declaration_fee(
+        &self,
+        classes: &HashMap<Felt, LabeledClass>,
+    ) -> Option<Felt> {
+        let account = &self.world.account;
+        let mut fee = Felt::ZERO;
+
+        for labeled_class in classes.values() {
+            let class = Arc::new(labeled_class.class.clone());
+            let casm_class_hash = labeled_class.casm_class_hash;
+
+            let estimate = match self.txn_config.fee_config {
+                FeeConfig::Strk(_) => {
+                    account.declare_v3(class, casm_class_hash).estimate_fee().await
+                }
+                FeeConfig::Eth(_) => {
+                    account.declare_v2(class, casm_class_hash).estimate_fee().await
+                }
+            };
+
+            match estimate {
+                Ok(estimate) => fee += estimate.overall_fee,
+                Err(e) => {
+                    trace!(label = labeled_class.label, error = ?e, "Failed to estimate the fee.");
+                    return None;
+                }
+            }
+        }
+
+        Some(fee)
+    }
+
+    /// Estimates the fee of invoking the calls in a single transaction, with the fee token of the
+    /// transaction config.
+    ///
+    /// Returns `None` if the estimation fails.
+    async fn estimate_invoke_fee(&self, calls: &[Call]) -> Option<Felt> {
+        if calls.is_empty() {
+            return Some(Felt::ZERO);
+        }
+
+        let account = &self.world.account;
+        let estimate = match self.txn_config.fee_config {
+            FeeConfig::Strk(_) => account.execute_v3(calls.to_vec()).estimate_fee().await,
+            FeeConfig::Eth(_) => account.execute_v1(calls.to_vec()).estimate_fee().await,
+        };
+
+        match estimate {
+            Ok(estimate) => Some(estimate.overall_fee),
+            Err(e) => {
+                trace!(error = ?e, "Failed to estimate the fee.");
+                None
+            }
+        }
+    }
+
     /// Upload resources metadata to IPFS and update the ResourceMetadata Dojo model.
     ///
     /// # Arguments
@@ -227,6 +379,27 @@ where
             .map_or(true, |m| !m.disable_multicall.unwrap_or(false))
     }
 
+    /// Invokes the calls of the invoker as multicalls, split in batches if a maximum number of
+    /// calls per multicall is configured.
+    async fn multicall(&self, invoker: &Invoker<&A>) -> Result<(), MigrationError<A::SignError>> {
+        let max_calls =
+            self.profile_config.migration.as_ref().and_then(|m| m.max_calls_per_multicall);
+
+        if let Some(max_calls) = max_calls {
+            trace!(
+                max_calls,
+                n_transactions = invoker.batches_count(max_calls),
+                "Invoking batched multicalls."
+            );
+
+            invoker.multicall_batched(max_calls).await?;
+        } else {
+            invoker.multicall().await?;
+        }
+
+        Ok(())
+    }
+
     /// For all contracts that are not initialized, initialize them by using the init call arguments
     /// found in the [`ProfileConfig`].
     ///
@@ -238,7 +411,32 @@ where
         ui.update_text("Initializing contracts...");
 
         let mut invoker = Invoker::new(&self.world.account, self.txn_config);
+        invoker.extend_calls(self.init_calls()?.into_iter().map(|(_, call)| call).collect());
+
+        let has_changed = !invoker.calls.is_empty();
+
+        if !invoker.calls.is_empty() {
+            if self.do_multicall() {
+                let ui_text = format!("Initializing {} contracts...", invoker.calls.len());
+                ui.update_text_boxed(ui_text);
+
+                self.multicall(&invoker).await?;
+            } else {
+                let ui_text =
+                    format!("Initializing {} contracts (sequentially)...", invoker.calls.len());
+                ui.update_text_boxed(ui_text);
+
+                invoker.invoke_all_sequentially().await?;
+            }
+        }
+
+        Ok(has_changed)
+    }
 
+    /// Returns the calls initializing the contracts that are not initialized yet, along with the
+    /// tags of the contracts. The contracts listed in the `order_inits` of the migration config are
+    /// initialized first, in that order.
+    fn init_calls(&self) -> Result<Vec<(String, Call)>, MigrationError<A::SignError>> {
         let init_call_args = if let Some(init_call_args) = &self.profile_config.init_call_args {
             init_call_args.clone()
         } else {
@@ -255,6 +453,7 @@ where
 
         // Keeps map between the order index and the call to initialize.
         let mut ordered_init_calls = HashMap::new();
+        let mut calls = vec![];
 
         for (selector, resource) in &self.diff.resources {
             if resource.resource_type() == ResourceType::Contract {
@@ -291,46 +490,21 @@ where
 
                     trace!(tag, ?args, "Initializing contract.");
 
+                    let call = self.world.init_contract_getcall(selector, &args);
+
                     if let Some(order_index) = ordered_init_tags.iter().position(|t| *t == tag) {
-                        ordered_init_calls
-                            .insert(order_index, self.world.init_contract_getcall(selector, &args));
+                        ordered_init_calls.insert(order_index, (tag, call));
                     } else {
-                        invoker.add_call(self.world.init_contract_getcall(selector, &args));
+                        calls.push((tag, call));
                     }
                 }
             }
         }
 
-        if !ordered_init_calls.is_empty() {
-            let mut ordered_keys: Vec<_> = ordered_init_calls.keys().cloned().collect();
-            ordered_keys.sort();
-
-            let ordered_calls: Vec<_> = ordered_keys
-                .into_iter()
-                .map(|k| ordered_init_calls.get(&k).expect("Ordered call must exist.").clone())
-                .collect();
-
-            invoker.extends_ordered(ordered_calls);
-        }
-
-        let has_changed = !invoker.calls.is_empty();
-
-        if !invoker.calls.is_empty() {
-            if self.do_multicall() {
-                let ui_text = format!("Initializing {} contracts...", invoker.calls.len());
-                ui.update_text_boxed(ui_text);
+        let mut ordered_calls: Vec<_> = ordered_init_calls.into_iter().collect();
+        ordered_calls.sort_by_key(|(order_index, _)| *order_index);
 
-                invoker.multicall().await?;
-            } else {
-                let ui_text =
-                    format!("Initializing {} contracts (sequentially)...", invoker.calls.len());
-                ui.update_text_boxed(ui_text);
-
-                invoker.invoke_all_sequentially().await?;
-            }
-        }
-
-        Ok(has_changed)
+        Ok(ordered_calls.into_iter().map(|(_, call)| call).chain(calls).collect())
     }
 
     /// Syncs the permissions.
@@ -353,8 +527,30 @@ where
         ui.update_text("Syncing permissions...");
 
         let mut invoker = Invoker::new(&self.world.account, self.txn_config);
+        invoker.extend_calls(self.permissions_calls().into_iter().map(|(_, call)| call).collect());
+
+        let has_changed = !invoker.calls.is_empty();
+
+        if self.do_multicall() {
+            let ui_text = format!("Syncing {} permissions...", invoker.calls.len());
+            ui.update_text_boxed(ui_text);
+
+            self.multicall(&invoker).await?;
+        } else {
+            let ui_text = format!("Syncing {} permissions (sequentially)...", invoker.calls.len());
+            ui.update_text_boxed(ui_text);
+
+            invoker.invoke_all_sequentially().await?;
+        }
+
+        Ok(has_changed)
+    }
+
+    /// Returns the calls granting the local permissions that are not already set onchain, along
+    /// with the permissions they grant.
+    fn permissions_calls(&self) -> Vec<(PlannedPermission, Call)> {
+        let mut calls = vec![];
 
-        // Only takes the local permissions that are not already set onchain to apply them.
         for (selector, resource) in &self.diff.resources {
             if self.profile_config.is_skipped(&resource.tag()) {
                 trace!(tag = resource.tag(), "Sync permissions skipping resource.");
@@ -364,45 +560,33 @@ where
             for pdiff in self.diff.get_writers(*selector).only_local() {
                 trace!(
                     target = resource.tag(),
-                    grantee_tag = pdiff.tag.unwrap_or_default(),
+                    grantee_tag = pdiff.tag.clone().unwrap_or_default(),
                     grantee_address = format!("{:#066x}", pdiff.address),
                     "Granting writer permission."
                 );
 
-                invoker.add_call(
+                calls.push((
+                    PlannedPermission::new(resource.tag(), pdiff.tag, pdiff.address, false),
                     self.world.grant_writer_getcall(selector, &ContractAddress(pdiff.address)),
-                );
+                ));
             }
 
             for pdiff in self.diff.get_owners(*selector).only_local() {
                 trace!(
                     target = resource.tag(),
-                    grantee_tag = pdiff.tag.unwrap_or_default(),
+                    grantee_tag = pdiff.tag.clone().unwrap_or_default(),
                     grantee_address = format!("{:#066x}", pdiff.address),
                     "Granting owner permission."
                 );
 
-                invoker.add_call(
+                calls.push((
+                    PlannedPermission::new(resource.tag(), pdiff.tag, pdiff.address, true),
                     self.world.grant_owner_getcall(selector, &ContractAddress(pdiff.address)),
-                );
+                ));
             }
         }
 
-        let has_changed = !invoker.calls.is_empty();
-
-        if self.do_multicall() {
-            let ui_text = format!("Syncing {} permissions...", invoker.calls.len());
-            ui.update_text_boxed(ui_text);
-
-            invoker.multicall().await?;
-        } else {
-            let ui_text = format!("Syncing {} permissions (sequentially)...", invoker.calls.len());
-            ui.update_text_boxed(ui_text);
-
-            invoker.invoke_all_sequentially().await?;
-        }
-
-        Ok(has_changed)
+        calls
     }
 
     /// Declare classes.
@@ -416,6 +600,12 @@ where
         // we can group it with the predeployed accounts which are concrete types.
         let accounts = self.get_accounts().await;
         let n_classes = classes.len();
+        let max_concurrency = self
+            .profile_config
+            .migration
+            .as_ref()
+            .and_then(|m| m.max_concurrent_declarations)
+            .unwrap_or(1);
 
         if accounts.is_empty() {
             trace!("Declaring classes with migrator account.");
@@ -425,7 +615,7 @@ where
             let ui_text = format!("Declaring {} classes...", n_classes);
             ui.update_text_boxed(ui_text);
 
-            declarer.declare_all().await?;
+            declarer.declare_all_concurrently(max_concurrency).await?;
         } else {
             trace!("Declaring classes with {} accounts.", accounts.len());
             let mut declarers = vec![];
@@ -433,7 +623,7 @@ where
                 declarers.push(Declarer::new(account, self.txn_config));
             }
 
-            for (idx, (_, labeled_class)) in classes.into_iter().enumerate() {
+            for (idx, labeled_class) in classes.values().enumerate() {
                 let declarer_idx = idx % declarers.len();
                 declarers[declarer_idx].add_class(labeled_class.clone());
             }
@@ -442,8 +632,12 @@ where
                 format!("Declaring {} classes with {} accounts...", n_classes, declarers.len());
             ui.update_text_boxed(ui_text);
 
-            let declarers_futures =
-                futures::future::join_all(declarers.into_iter().map(|d| d.declare_all())).await;
+            let declarers_futures = futures::future::join_all(
+                declarers.into_iter().map(|d| d.declare_all_concurrently(max_concurrency)),
+            )
+            .await;
+
+            let mut interrupted = false;
 
             for declarer_results in declarers_futures {
                 if let Err(e) = declarer_results {
@@ -453,12 +647,22 @@ where
                     if e.to_string().contains("Class already declared") {
                         // If the class is already declared, it might be because it was already
                         // declared in a previous run or an other declarer.
+                        interrupted = true;
                         continue;
                     }
 
                     return Err(MigrationError::DeclareClassError(e.to_string()));
                 }
             }
+
+            // A declarer stops at its first error, the classes it didn't declare are declared
+            // with the migrator account, skipping the ones already declared.
+            if interrupted {
+                trace!("Declaring the remaining classes with migrator account.");
+                let mut declarer = Declarer::new(&self.world.account, self.txn_config);
+                declarer.extend_classes(classes.into_values().collect());
+                declarer.declare_all_concurrently(max_concurrency).await?;
+            }
         }
 
         Ok(())
@@ -473,6 +677,107 @@ where
     ) -> Result<bool, MigrationError<A::SignError>> {
         ui.update_text("Syncing resources...");
 
+        let (calls, classes, n_resources) = self.resources_calls_classes().await?;
+
+        let mut invoker = Invoker::new(&self.world.account, self.txn_config);
+        invoker.extend_calls(calls);
+
+        let has_classes = !classes.is_empty();
+        let has_calls = !invoker.calls.is_empty();
+        let has_changed = has_classes || has_calls;
+
+        self.declare_classes(ui, classes).await?;
+        self.check_resource_upgrades(ui).await?;
+
+        if self.do_multicall() {
+            let ui_text = format!("Registering {} resources...", n_resources);
+            ui.update_text_boxed(ui_text);
+
+            self.multicall(&invoker).await?;
+        } else {
+            let ui_text = format!("Registering {} resources (sequentially)...", n_resources);
+            ui.update_text_boxed(ui_text);
+
+            invoker.invoke_all_sequentially().await?;
+        }
+
+        Ok(has_changed)
+    }
+
+    /// Simulates the upgrade of each model and event, once their new classes are declared, to
+    /// detect upgrades rejected by the world because the new layout or schema is not backward
+    /// compatible with the stored data.
+    ///
+    /// Only the members appended at the end of a model or an event are compatible, which the world
+    /// enforces when upgrading. Checking the upgrades one by one reports the faulty resource
+    /// instead of the whole multicall failing.
+    async fn check_resource_upgrades(
+        &self,
+        ui: &mut MigrationUi,
+    ) -> Result<(), MigrationError<A::SignError>> {
+        let mut upgrades = vec![];
+
+        for resource in self.diff.resources.values() {
+            if self.profile_config.is_skipped(&resource.tag()) {
+                continue;
+            }
+
+            let ns_bytearray = ByteArray::from_string(&resource.namespace())?;
+
+            let call = match resource {
+                ResourceDiff::Updated(ResourceLocal::Model(model), _) => self
+                    .world
+                    .upgrade_model_getcall(&ns_bytearray, &ClassHash(model.common.class_hash)),
+                ResourceDiff::Updated(ResourceLocal::Event(event), _) => self
+                    .world
+                    .upgrade_event_getcall(&ns_bytearray, &ClassHash(event.common.class_hash)),
+                _ => continue,
+            };
+
+            upgrades.push((resource.tag(), call));
+        }
+
+        if upgrades.is_empty() {
+            return Ok(());
+        }
+
+        ui.update_text_boxed(format!("Checking {} resource upgrades...", upgrades.len()));
+
+        let account = &self.world.account;
+
+        for (tag, call) in upgrades {
+            let estimate = match self.txn_config.fee_config {
+                FeeConfig::Strk(_) => account.execute_v3(vec![call]).estimate_fee().await,
+                FeeConfig::Eth(_) => account.execute_v1(vec![call]).estimate_fee().await,
+            };
+
+            let Err(e) = estimate else {
+                continue;
+            };
+
+            let error = format!("{:?}", e);
+            if let Some(reason) = incompatible_upgrade_reason(&error) {
+                return Err(MigrationError::IncompatibleResourceUpgrade {
+                    tag,
+                    reason: reason.to_string(),
+                });
+            }
+
+            // Any other failure is reported when the upgrades are actually sent.
+            trace!(tag, error, "Failed to simulate the resource upgrade.");
+        }
+
+        Ok(())
+    }
+
+    /// Gathers the calls required to sync the namespaces and the resources, and the classes to be
+    /// declared.
+    ///
+    /// Returns a tuple of calls, (casm_class_hash, class) to be declared and the number of
+    /// resources to register or upgrade.
+    async fn resources_calls_classes(
+        &self,
+    ) -> Result<(Vec<Call>, HashMap<Felt, LabeledClass>, usize), MigrationError<A::SignError>> {
         let mut invoker = Invoker::new(&self.world.account, self.txn_config);
 
         // Namespaces must be synced first, since contracts, models and events are namespaced.
@@ -535,25 +840,7 @@ where
             }
         }
 
-        let has_classes = !classes.is_empty();
-        let has_calls = !invoker.calls.is_empty();
-        let has_changed = has_classes || has_calls;
-
-        self.declare_classes(ui, classes).await?;
-
-        if self.do_multicall() {
-            let ui_text = format!("Registering {} resources...", n_resources);
-            ui.update_text_boxed(ui_text);
-
-            invoker.multicall().await?;
-        } else {
-            let ui_text = format!("Registering {} resources (sequentially)...", n_resources);
-            ui.update_text_boxed(ui_text);
-
-            invoker.invoke_all_sequentially().await?;
-        }
-
-        Ok(has_changed)
+        Ok((invoker.calls, classes, n_resources))
     }
 
     /// Syncs the external contracts by declaring their classes and deploying them with
@@ -568,9 +855,41 @@ where
             format!("Syncing {} external contracts...", self.diff.external_contracts.len());
         ui.update_text_boxed(ui_text);
 
+        let (calls, classes) = self.external_contracts_calls_classes().await?;
+
+        let ui_text = format!("Declaring {} external contract classes...", classes.len());
+        ui.update_text_boxed(ui_text);
+
+        self.declare_classes(ui, classes).await?;
+
+        // then deploying new external contracts
         let mut invoker = Invoker::new(&self.world.account, self.txn_config);
+        invoker.extend_calls(calls.into_iter().map(|(_, call)| call).collect());
+
+        let has_changed = !invoker.calls.is_empty();
+
+        if self.do_multicall() {
+            let ui_text = format!("Deploying {} external contracts...", invoker.calls.len());
+            ui.update_text_boxed(ui_text);
+
+            self.multicall(&invoker).await?;
+        } else {
+            let ui_text =
+                format!("Deploying {} external contracts (sequentially)...", invoker.calls.len());
+            ui.update_text_boxed(ui_text);
+
+            invoker.invoke_all_sequentially().await?;
+        }
+
+        Ok(has_changed)
+    }
 
-        // declaring external contract classes
+    /// Gathers the classes of the external contracts to be declared, and the calls deploying the
+    /// new external contracts along with their instance names.
+    async fn external_contracts_calls_classes(
+        &self,
+    ) -> Result<(Vec<(String, Call)>, HashMap<Felt, LabeledClass>), MigrationError<A::SignError>>
+    {
         let classes: HashMap<_, _> = self
             .diff
             .external_contract_classes
@@ -578,13 +897,8 @@ where
             .filter_map(|(_, c)| self.external_contract_classes(c))
             .collect();
 
-        let ui_text = format!("Declaring {} external contract classes...", classes.len());
-        ui.update_text_boxed(ui_text);
-
-        self.declare_classes(ui, classes).await?;
-
-        // then deploying new external contracts
         let deployer = Deployer::new(&self.world.account, self.txn_config);
+        let mut calls = vec![];
 
         for contract in self.diff.external_contracts.values() {
             if let ExternalContractDiff::Created(contract) = contract {
@@ -597,27 +911,12 @@ where
                     )
                     .await?
                 {
-                    invoker.add_call(call);
+                    calls.push((contract.instance_name.clone(), call));
                 }
             }
         }
 
-        let has_changed = !invoker.calls.is_empty();
-
-        if self.do_multicall() {
-            let ui_text = format!("Deploying {} external contracts...", invoker.calls.len());
-            ui.update_text_boxed(ui_text);
-
-            invoker.multicall().await?;
-        } else {
-            let ui_text =
-                format!("Deploying {} external contracts (sequentially)...", invoker.calls.len());
-            ui.update_text_boxed(ui_text);
-
-            invoker.invoke_all_sequentially().await?;
-        }
-
-        Ok(has_changed)
+        Ok((calls, classes))
     }
 
     /// Returns the calls required to sync the namespaces.
@@ -983,6 +1282,11 @@ where
 
                         ui.restart("World deployed, continuing...");
                     }
+                    // The world has been deployed since the diff was computed, by an interrupted
+                    // migration whose transaction was still pending.
+                    TransactionResult::Noop => {
+                        ui.update_text("World already deployed, resuming the migration...");
+                    }
                     _ => unreachable!(),
                 }
             }
@@ -1027,3 +1331,37 @@ where
             .unwrap_or_default()
     }
 }
+
+/// Returns the reason why the world rejected a resource upgrade, if the error of the upgrade
+/// contains one of the world errors about an incompatible layout or schema.
+fn incompatible_upgrade_reason(error: &str) -> Option<&'static str> {
+    const REASONS: [(&str, &str); 3] = [
+        ("Invalid new layout", "the new layout is not of the same type as the current one"),
+        (
+            "Invalid new schema",
+            "the new schema is not backward compatible, only new members appended at the end are \
+             allowed and existing members can't be removed, moved or changed",
+        ),
+        (
+            "Packed layout cannot be upgraded",
+            "a model or an event with a packed layout can't be upgraded",
+        ),
+    ];
+
+    REASONS.iter().find(|(message, _)| error.contains(message)).map(|(_, reason)| *reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_incompatible_upgrade_reason() {
+        let error = "TransactionExecutionError { execution_error: \"Invalid new schema to upgrade \
+                     the resource `ns-Position`\" }";
+        assert!(incompatible_upgrade_reason(error).unwrap().contains("backward compatible"));
+
+        assert!(incompatible_upgrade_reason("Packed layout cannot be upgraded `ns-M`").is_some());
+        assert!(incompatible_upgrade_reason("Insufficient max fee").is_none());
+    }
+}
@@ -0,0 +1,89 @@
+//! The plan of a migration, computed without sending any transaction.
+
+use starknet::core::types::Call;
+use starknet_crypto::Felt;
+
+/// The changes a migration would apply to the world, and the calls it would send.
+#[derive(Debug)]
+pub struct MigrationPlan {
+    /// The change applied to the world contract itself, if any.
+    pub world: Option<PlannedWorldChange>,
+    /// The classes to be declared, with their labels.
+    pub classes: Vec<PlannedClass>,
+    /// The resources to be registered or upgraded.
+    pub resources: Vec<PlannedResource>,
+    /// The permissions to be granted.
+    pub permissions: Vec<PlannedPermission>,
+    /// The tags of the contracts to be initialized, in the order they are initialized.
+    pub initializations: Vec<String>,
+    /// The instance names of the external contracts to be deployed.
+    pub external_contracts: Vec<String>,
+    /// All the calls to be invoked once the classes are declared, in the order they are sent,
+    /// starting with the upgrade of the world if any.
+    pub calls: Vec<Call>,
+    /// The estimated fee of declaring the classes, in the fee token of the transactions.
+    ///
+    /// `None` if the fee can't be estimated.
+    pub declaration_fee: Option<Felt>,
+    /// The estimated fee of invoking the calls, in the fee token of the transactions.
+    ///
+    /// `None` if the fee can't be estimated, which is always the case when the calls depend on
+    /// classes or on a world that are not declared yet, since they can't be simulated.
+    pub invoke_fee: Option<Felt>,
+}
+
+impl MigrationPlan {
+    /// Returns true if the migration would not change anything.
+    pub fn is_empty(&self) -> bool {
+        self.world.is_none() && self.classes.is_empty() && self.calls.is_empty()
+    }
+}
+
+/// The change applied to the world contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlannedWorldChange {
+    /// The world is declared and deployed.
+    Deploy,
+    /// The world is upgraded to a new class.
+    Upgrade,
+}
+
+/// A class to be declared.
+#[derive(Debug, Clone)]
+pub struct PlannedClass {
+    pub label: String,
+    pub class_hash: Felt,
+}
+
+/// A resource to be registered or upgraded.
+#[derive(Debug, Clone)]
+pub struct PlannedResource {
+    pub tag: String,
+    /// The type of the resource, eg `Contract` or `Model`.
+    pub resource_type: String,
+    /// Whether the resource is already registered and is upgraded.
+    pub upgrade: bool,
+}
+
+/// A permission to be granted on a resource.
+#[derive(Debug, Clone)]
+pub struct PlannedPermission {
+    /// The tag of the resource the permission is granted on.
+    pub resource: String,
+    /// The tag of the grantee, or its address if it's not managed by the local project.
+    pub grantee: String,
+    /// Whether the owner permission is granted, the writer permission otherwise.
+    pub owner: bool,
+}
+
+impl PlannedPermission {
+    pub(crate) fn new(
+        resource: String,
+        grantee_tag: Option<String>,
+        grantee: Felt,
+        owner: bool,
+    ) -> Self {
+        let grantee = grantee_tag.unwrap_or_else(|| format!("{:#066x}", grantee));
+        Self { resource, grantee, owner }
+    }
+}
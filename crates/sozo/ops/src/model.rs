@@ -512,8 +512,8 @@ fn format_record_value(
     }
 }
 
-// print the structured record values
-fn format_deep_record(schema: &Ty, keys: &[Felt], values: &[Felt]) -> String {
+/// Formats the values of a record, keys included, following its schema.
+pub fn format_deep_record(schema: &Ty, keys: &[Felt], values: &[Felt]) -> String {
     let mut model_values = vec![];
     model_values.extend(keys);
     model_values.extend(values);
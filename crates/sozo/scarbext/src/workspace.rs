@@ -111,10 +111,9 @@ impl WorkspaceExt for Workspace<'_> {
         }
 
         // If the profile file is not found, default to `dev.toml` file that must exist.
-        let config_path = if !config_path.exists() { dev_config_path } else { config_path };
+        let profile = if !config_path.exists() { "dev" } else { profile_str.as_str() };
 
-        let content = fs::read_to_string(&config_path)?;
-        let config: ProfileConfig = toml::from_str(&content)?;
+        let config = ProfileConfig::from_profile(&manifest_dir, profile)?;
 
         config.validate()?;
 
@@ -67,6 +67,10 @@ pub struct ToriiArgs {
     #[command(flatten)]
     pub server: ServerOptions,
 
+    #[cfg(feature = "server")]
+    #[command(flatten)]
+    pub graphql: GraphqlOptions,
+
     #[cfg(feature = "server")]
     #[command(flatten)]
     pub relay: RelayOptions,
@@ -90,6 +94,8 @@ impl Default for ToriiArgs {
             #[cfg(feature = "server")]
             server: ServerOptions::default(),
             #[cfg(feature = "server")]
+            graphql: GraphqlOptions::default(),
+            #[cfg(feature = "server")]
             relay: RelayOptions::default(),
         }
     }
@@ -219,6 +225,7 @@ mod test {
         assert_eq!(torii_args.sql, SqlOptions::default());
         assert_eq!(torii_args.runner, RunnerOptions::default());
         assert_eq!(torii_args.server, ServerOptions::default());
+        assert_eq!(torii_args.graphql, GraphqlOptions::default());
         assert_eq!(torii_args.relay, RelayOptions::default());
         assert_eq!(torii_args.metrics, MetricsOptions::default());
 
@@ -227,6 +234,7 @@ mod test {
         assert!(torii_args.indexing.pending);
         assert_eq!(torii_args.indexing.polling_interval, DEFAULT_POLLING_INTERVAL);
         assert_eq!(torii_args.indexing.max_concurrent_tasks, DEFAULT_MAX_CONCURRENT_TASKS);
+        assert_eq!(torii_args.indexing.prefetch_ranges, DEFAULT_PREFETCH_RANGES);
 
         assert!(!torii_args.events.raw);
 
@@ -269,6 +277,10 @@ mod test {
         http_port = 7777
         http_cors_origins = ["*"]
 
+        [graphql]
+        max_depth = 10
+        rate_limit = 60
+
         [indexing]
         events_chunk_size = 9999
         pending = true
@@ -333,5 +345,62 @@ mod test {
         assert_eq!(torii_args.server.http_addr, IpAddr::V4(Ipv4Addr::LOCALHOST));
         assert_eq!(torii_args.server.http_port, 7777);
         assert_eq!(torii_args.server.http_cors_origins, Some(vec!["*".to_string()]));
+        assert_eq!(torii_args.graphql.max_depth, 10);
+        assert_eq!(torii_args.graphql.max_complexity, DEFAULT_GRAPHQL_MAX_COMPLEXITY);
+        assert_eq!(torii_args.graphql.rate_limit, Some(60));
+    }
+
+    #[test]
+    fn test_erc_contracts_alias() {
+        let args = vec!["torii", "--erc.contracts", "erc20:0x1234,erc721:0x5678"];
+        let torii_args = ToriiArgs::parse_from(args);
+
+        assert_eq!(
+            torii_args.indexing.contracts,
+            vec![
+                Contract {
+                    address: Felt::from_str("0x1234").unwrap(),
+                    r#type: ContractType::ERC20
+                },
+                Contract {
+                    address: Felt::from_str("0x5678").unwrap(),
+                    r#type: ContractType::ERC721
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_abi_contracts() {
+        let args =
+            vec!["torii", "--indexing.abi_contracts", "0x1234:abis/game.json,0x5678:C:\\abi.json"];
+        let torii_args = ToriiArgs::parse_from(args);
+
+        assert_eq!(
+            torii_args.indexing.abi_contracts,
+            vec![
+                AbiContract {
+                    address: Felt::from_str("0x1234").unwrap(),
+                    abi: "abis/game.json".into()
+                },
+                AbiContract {
+                    address: Felt::from_str("0x5678").unwrap(),
+                    abi: "C:\\abi.json".into()
+                }
+            ]
+        );
+
+        let args = vec!["torii", "--indexing.contracts", "abi:0x1234"];
+        assert!(ToriiArgs::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn test_graphql_rate_limit() {
+        let args = vec!["torii", "--graphql.rate_limit", "100"];
+        let torii_args = ToriiArgs::parse_from(args);
+        assert_eq!(torii_args.graphql.rate_limit, Some(100));
+
+        let args = vec!["torii", "--graphql.rate_limit", "0"];
+        assert!(ToriiArgs::try_parse_from(args).is_err());
     }
 }
@@ -17,6 +17,9 @@ pub const DEFAULT_EVENTS_CHUNK_SIZE: u64 = 1024;
 pub const DEFAULT_BLOCKS_CHUNK_SIZE: u64 = 10240;
 pub const DEFAULT_POLLING_INTERVAL: u64 = 500;
 pub const DEFAULT_MAX_CONCURRENT_TASKS: usize = 100;
+pub const DEFAULT_PREFETCH_RANGES: usize = 2;
+pub const DEFAULT_GRAPHQL_MAX_DEPTH: usize = 32;
+pub const DEFAULT_GRAPHQL_MAX_COMPLEXITY: usize = 5000;
 pub const DEFAULT_RELAY_PORT: u16 = 9090;
 pub const DEFAULT_RELAY_WEBRTC_PORT: u16 = 9091;
 pub const DEFAULT_RELAY_WEBSOCKET_PORT: u16 = 9092;
@@ -132,6 +135,15 @@ pub struct IndexingOptions {
     )]
     pub max_concurrent_tasks: usize,
 
+    /// Number of ranges of blocks fetched ahead of their processing while syncing.
+    #[arg(
+        long = "indexing.prefetch_ranges",
+        default_value_t = DEFAULT_PREFETCH_RANGES,
+        help = "Number of ranges of blocks to fetch ahead of their processing while syncing. \
+                Prefetching is disabled if 0."
+    )]
+    pub prefetch_ranges: usize,
+
     /// Whether or not to index world transactions
     #[arg(
         long = "indexing.transactions",
@@ -143,6 +155,7 @@ pub struct IndexingOptions {
     /// ERC contract addresses to index
     #[arg(
         long = "indexing.contracts",
+        alias = "erc.contracts",
         value_delimiter = ',',
         value_parser = parse_erc_contract,
         help = "ERC contract addresses to index. You may only specify ERC20 or ERC721 contracts."
@@ -151,6 +164,19 @@ pub struct IndexingOptions {
     #[serde(serialize_with = "serialize_contracts")]
     pub contracts: Vec<Contract>,
 
+    /// Contracts to index using their ABI
+    #[arg(
+        long = "indexing.abi_contracts",
+        value_delimiter = ',',
+        value_parser = parse_abi_contract,
+        help = "Contracts to index using their ABI, as <address>:<path to the ABI>. The ABI is \
+                either a JSON array or a contract class. The events of the contracts are decoded \
+                and stored in the contract_events table."
+    )]
+    #[serde(deserialize_with = "deserialize_abi_contracts")]
+    #[serde(serialize_with = "serialize_abi_contracts")]
+    pub abi_contracts: Vec<AbiContract>,
+
     /// Namespaces to index
     #[arg(
         long = "indexing.namespaces",
@@ -198,8 +224,10 @@ impl Default for IndexingOptions {
             pending: true,
             polling_interval: DEFAULT_POLLING_INTERVAL,
             max_concurrent_tasks: DEFAULT_MAX_CONCURRENT_TASKS,
+            prefetch_ranges: DEFAULT_PREFETCH_RANGES,
             transactions: false,
             contracts: vec![],
+            abi_contracts: vec![],
             namespaces: vec![],
             world_block: 0,
             controllers: false,
@@ -247,6 +275,36 @@ impl Default for ServerOptions {
     }
 }
 
+#[derive(Debug, clap::Args, Clone, Serialize, Deserialize, PartialEq, MergeOptions)]
+#[serde(default)]
+#[command(next_help_heading = "GraphQL options")]
+pub struct GraphqlOptions {
+    /// The maximum depth of the selections of a GraphQL query.
+    #[arg(long = "graphql.max_depth", value_name = "DEPTH")]
+    #[arg(default_value_t = DEFAULT_GRAPHQL_MAX_DEPTH)]
+    pub max_depth: usize,
+
+    /// The maximum complexity of a GraphQL query, ie the number of fields it selects.
+    #[arg(long = "graphql.max_complexity", value_name = "COMPLEXITY")]
+    #[arg(default_value_t = DEFAULT_GRAPHQL_MAX_COMPLEXITY)]
+    pub max_complexity: usize,
+
+    /// The maximum number of GraphQL requests per minute of each client. Unlimited if not set.
+    #[arg(long = "graphql.rate_limit", value_name = "REQUESTS")]
+    #[arg(value_parser = clap::value_parser!(u32).range(1..))]
+    pub rate_limit: Option<u32>,
+}
+
+impl Default for GraphqlOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_GRAPHQL_MAX_DEPTH,
+            max_complexity: DEFAULT_GRAPHQL_MAX_COMPLEXITY,
+            rate_limit: None,
+        }
+    }
+}
+
 #[derive(Debug, clap::Args, Clone, Serialize, Deserialize, PartialEq, MergeOptions)]
 #[serde(default)]
 #[command(next_help_heading = "Metrics options")]
@@ -428,6 +486,12 @@ fn parse_erc_contract(part: &str) -> anyhow::Result<Contract> {
                     "World address cannot be specified as an ERC contract"
                 ));
             }
+            if r#type == ContractType::ABI {
+                return Err(anyhow::anyhow!(
+                    "Contracts indexed using their ABI must be specified with \
+                     --indexing.abi_contracts"
+                ));
+            }
 
             let address = Felt::from_str(address)
                 .with_context(|| format!("Expected address, found {}", address))?;
@@ -458,3 +522,47 @@ where
 
     seq.end()
 }
+
+/// A contract indexed using its ABI.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AbiContract {
+    pub address: Felt,
+    /// The path of the file containing the ABI of the contract.
+    pub abi: Utf8PathBuf,
+}
+
+impl std::fmt::Display for AbiContract {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:#x}:{}", self.address, self.abi)
+    }
+}
+
+fn parse_abi_contract(part: &str) -> anyhow::Result<AbiContract> {
+    // only split at the first colon, the path may contain colons
+    let (address, abi) = part.split_once(':').context("Invalid ABI contract format")?;
+    let address =
+        Felt::from_str(address).with_context(|| format!("Expected address, found {}", address))?;
+
+    Ok(AbiContract { address, abi: Utf8PathBuf::from(abi) })
+}
+
+fn deserialize_abi_contracts<'de, D>(deserializer: D) -> Result<Vec<AbiContract>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let contracts: Vec<String> = Vec::deserialize(deserializer)?;
+    contracts.iter().map(|s| parse_abi_contract(s).map_err(serde::de::Error::custom)).collect()
+}
+
+fn serialize_abi_contracts<S>(contracts: &[AbiContract], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let mut seq = serializer.serialize_seq(Some(contracts.len()))?;
+
+    for contract in contracts {
+        seq.serialize_element(&contract.to_string())?;
+    }
+
+    seq.end()
+}
@@ -17,7 +17,7 @@ use torii_grpc::proto::world::{
 };
 use torii_grpc::types::schema::Entity;
 use torii_grpc::types::{
-    Controller, EntityKeysClause, Event, EventQuery, Query, Token, TokenBalance,
+    Controller, EntityKeysClause, Event, EventFilter, EventQuery, Query, Token, TokenBalance,
 };
 use torii_relay::client::EventLoop;
 use torii_relay::types::Message;
@@ -185,9 +185,10 @@ impl Client {
     pub async fn on_starknet_event(
         &self,
         keys: Vec<EntityKeysClause>,
+        filter: EventFilter,
     ) -> Result<EventUpdateStreaming, Error> {
         let mut grpc_client = self.inner.write().await;
-        let stream = grpc_client.subscribe_events(keys).await?;
+        let stream = grpc_client.subscribe_events(keys, filter).await?;
         Ok(stream)
     }
 
@@ -4,6 +4,7 @@ pub const DEFAULT_LIMIT: u64 = 10;
 pub const BOOLEAN_TRUE: i64 = 1;
 
 pub const ENTITY_TABLE: &str = "entities";
+pub const ENTITY_HISTORICAL_TABLE: &str = "entities_historical";
 pub const EVENT_TABLE: &str = "events";
 pub const EVENT_MESSAGE_TABLE: &str = "event_messages";
 pub const MODEL_TABLE: &str = "models";
@@ -12,6 +13,7 @@ pub const TRANSACTION_CALLS_TABLE: &str = "transaction_calls";
 pub const TOKEN_TRANSFER_TABLE: &str = "token_transfers";
 pub const METADATA_TABLE: &str = "metadata";
 pub const CONTROLLER_TABLE: &str = "controllers";
+pub const OFFCHAIN_MESSAGE_TABLE: &str = "offchain_messages";
 
 pub const ID_COLUMN: &str = "id";
 pub const EVENT_ID_COLUMN: &str = "event_id";
@@ -24,9 +26,11 @@ pub const INTERNAL_ENTITY_ID_KEY: &str = "$entity_id$";
 
 // objects namespaced to avoid conflicts with user models
 pub const ENTITY_TYPE_NAME: &str = "World__Entity";
+pub const ENTITY_VERSION_TYPE_NAME: &str = "World__EntityVersion";
 pub const EVENT_MESSAGE_TYPE_NAME: &str = "World__EventMessage";
 pub const MODEL_TYPE_NAME: &str = "World__Model";
 pub const EVENT_TYPE_NAME: &str = "World__Event";
+pub const OFFCHAIN_MESSAGE_TYPE_NAME: &str = "World__OffchainMessage";
 pub const SOCIAL_TYPE_NAME: &str = "World__Social";
 pub const CONTENT_TYPE_NAME: &str = "World__Content";
 pub const METADATA_TYPE_NAME: &str = "World__Metadata";
@@ -48,9 +52,11 @@ pub const ERC1155_TYPE_NAME: &str = "ERC1155__Token";
 
 // objects' single and plural names
 pub const ENTITY_NAMES: (&str, &str) = ("entity", "entities");
+pub const ENTITY_VERSION_NAMES: (&str, &str) = ("entityAtBlock", "entityHistory");
 pub const EVENT_MESSAGE_NAMES: (&str, &str) = ("eventMessage", "eventMessages");
 pub const MODEL_NAMES: (&str, &str) = ("model", "models");
 pub const EVENT_NAMES: (&str, &str) = ("event", "events");
+pub const OFFCHAIN_MESSAGE_NAMES: (&str, &str) = ("offchainMessage", "offchainMessages");
 pub const SOCIAL_NAMES: (&str, &str) = ("social", "socials");
 pub const CONTENT_NAMES: (&str, &str) = ("content", "contents");
 pub const METADATA_NAMES: (&str, &str) = ("metadata", "metadatas");
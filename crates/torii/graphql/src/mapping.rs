@@ -25,6 +25,18 @@ lazy_static! {
             TypeData::Simple(TypeRef::named(GraphqlType::DateTime.to_string())),
         ),
     ]);
+    pub static ref ENTITY_VERSION_TYPE_MAPPING: TypeMapping = IndexMap::from([
+        (Name::new("entityId"), TypeData::Simple(TypeRef::named(TypeRef::ID))),
+        (Name::new("namespace"), TypeData::Simple(TypeRef::named(TypeRef::STRING))),
+        (Name::new("model"), TypeData::Simple(TypeRef::named(TypeRef::STRING))),
+        (Name::new("eventId"), TypeData::Simple(TypeRef::named(TypeRef::STRING))),
+        (Name::new("blockNumber"), TypeData::Simple(TypeRef::named(TypeRef::INT))),
+        (
+            Name::new("executedAt"),
+            TypeData::Simple(TypeRef::named(GraphqlType::DateTime.to_string())),
+        ),
+        (Name::new("data"), TypeData::Simple(TypeRef::named(TypeRef::STRING))),
+    ]);
     pub static ref EVENT_TYPE_MAPPING: TypeMapping = IndexMap::from([
         (Name::new("id"), TypeData::Simple(TypeRef::named(TypeRef::ID))),
         (Name::new("keys"), TypeData::Simple(TypeRef::named_list(TypeRef::STRING))),
@@ -39,6 +51,22 @@ lazy_static! {
         ),
         (Name::new("transactionHash"), TypeData::Simple(TypeRef::named(TypeRef::STRING))),
     ]);
+    pub static ref OFFCHAIN_MESSAGE_TYPE_MAPPING: TypeMapping = IndexMap::from([
+        (Name::new("id"), TypeData::Simple(TypeRef::named(TypeRef::ID))),
+        (Name::new("identity"), TypeData::Simple(TypeRef::named(TypeRef::STRING))),
+        (Name::new("entityId"), TypeData::Simple(TypeRef::named(TypeRef::STRING))),
+        (Name::new("model"), TypeData::Simple(TypeRef::named(TypeRef::STRING))),
+        (Name::new("message"), TypeData::Simple(TypeRef::named(TypeRef::STRING))),
+        (Name::new("signature"), TypeData::Simple(TypeRef::named_list(TypeRef::STRING))),
+        (
+            Name::new("receivedAt"),
+            TypeData::Simple(TypeRef::named(GraphqlType::DateTime.to_string())),
+        ),
+        (
+            Name::new("createdAt"),
+            TypeData::Simple(TypeRef::named(GraphqlType::DateTime.to_string())),
+        ),
+    ]);
     pub static ref MODEL_TYPE_MAPPING: TypeMapping = IndexMap::from([
         (Name::new("id"), TypeData::Simple(TypeRef::named(TypeRef::ID))),
         (Name::new("name"), TypeData::Simple(TypeRef::named(TypeRef::STRING))),
@@ -10,7 +10,8 @@ pub fn encode(primary: &str, secondary: &str) -> String {
 pub fn decode(cursor: &str) -> Result<(String, String), Error> {
     let bytes = general_purpose::STANDARD.decode(cursor)?;
     let cursor = String::from_utf8(bytes)?;
-    let parts: Vec<&str> = cursor.split('/').collect();
+    // the secondary value is the last part, as it may contain `/` (eg keys)
+    let parts: Vec<&str> = cursor.splitn(3, '/').collect();
 
     if parts.len() != 3 || parts[0] != "cursor" {
         return Err("Invalid cursor format".into());
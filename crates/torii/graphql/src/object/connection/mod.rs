@@ -27,6 +27,14 @@ pub struct ConnectionArguments {
     pub limit: Option<u64>,
 }
 
+impl ConnectionArguments {
+    /// Whether the connection is paginated with cursors (`first`/`last`/`after`/`before`), rather
+    /// than with `offset`/`limit`.
+    pub fn is_cursor_based(&self) -> bool {
+        self.first.or(self.last).is_some() || self.after.is_some() || self.before.is_some()
+    }
+}
+
 #[derive(Debug)]
 pub struct ConnectionObject {
     pub name: String,
@@ -101,14 +109,27 @@ pub fn parse_connection_arguments(ctx: &ResolverContext<'_>) -> Result<Connectio
     Ok(ConnectionArguments { first, last, after, before, offset, limit })
 }
 
+/// Whether the total count of the connection has to be computed, ie it is selected or the
+/// connection is paginated with `offset`/`limit`, whose page info depends on it. Counting scans all
+/// the matching rows, so it is skipped when paging through large collections with cursors.
+pub fn total_count_required(ctx: &ResolverContext<'_>, connection: &ConnectionArguments) -> bool {
+    !connection.is_cursor_based() || ctx.look_ahead().field("totalCount").exists()
+}
+
 pub fn connection_arguments(field: Field) -> Field {
     field
         .argument(InputValue::new("first", TypeRef::named(TypeRef::INT)))
         .argument(InputValue::new("last", TypeRef::named(TypeRef::INT)))
         .argument(InputValue::new("before", TypeRef::named(GraphqlType::Cursor.to_string())))
         .argument(InputValue::new("after", TypeRef::named(GraphqlType::Cursor.to_string())))
-        .argument(InputValue::new("offset", TypeRef::named(TypeRef::INT)))
-        .argument(InputValue::new("limit", TypeRef::named(TypeRef::INT)))
+        .argument(
+            InputValue::new("offset", TypeRef::named(TypeRef::INT))
+                .description("Prefer the cursors, the deeper the offset the slower the query."),
+        )
+        .argument(
+            InputValue::new("limit", TypeRef::named(TypeRef::INT))
+                .description("Prefer the cursors, the deeper the offset the slower the query."),
+        )
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -1,16 +1,17 @@
 use async_graphql::dynamic::indexmap::IndexMap;
 use async_graphql::dynamic::{
-    Field, FieldFuture, FieldValue, InputValue, SubscriptionField, SubscriptionFieldFuture, TypeRef,
+    Field, FieldFuture, FieldValue, InputValue, ResolverContext, SubscriptionField,
+    SubscriptionFieldFuture, TypeRef,
 };
-use async_graphql::{Name, Value};
-use dojo_types::naming::get_tag;
+use async_graphql::{Name, Result, Value};
+use dojo_types::naming::{get_tag, split_tag};
 use dojo_types::schema::Ty;
 use sqlx::{Pool, Sqlite};
 use tokio_stream::StreamExt;
 use torii_sqlite::simple_broker::SimpleBroker;
 use torii_sqlite::types::Entity;
 
-use super::inputs::keys_input::keys_argument;
+use super::inputs::keys_input::{keys_argument, match_keys, parse_keys_argument};
 use super::{BasicObject, ResolvableObject, TypeMapping, ValueMapping};
 use crate::constants::{
     DATETIME_FORMAT, ENTITY_NAMES, ENTITY_TABLE, ENTITY_TYPE_NAME, EVENT_ID_COLUMN, ID_COLUMN,
@@ -66,27 +67,75 @@ impl ResolvableObject for EntityObject {
         Some(vec![
             SubscriptionField::new("entityUpdated", TypeRef::named_nn(self.type_name()), |ctx| {
                 SubscriptionFieldFuture::new(async move {
-                    let id = match ctx.args.get("id") {
-                        Some(id) => Some(id.string()?.to_string()),
-                        None => None,
+                    // the entities are only sent if they match all the provided arguments, so
+                    // subscribe to all entities if none are provided
+                    let filter = EntityUpdatedFilter {
+                        id: optional_string(&ctx, "id")?,
+                        namespace: optional_string(&ctx, "namespace")?,
+                        model: optional_string(&ctx, "model")?,
+                        keys: parse_keys_argument(&ctx)?,
                     };
-                    // if id is None, then subscribe to all entities
-                    // if id is Some, then subscribe to only the entity with that id
+
                     Ok(SimpleBroker::<Entity>::subscribe().filter_map(move |entity: Entity| {
-                        if id.is_none() || id == Some(entity.id.clone()) {
+                        if filter.matches(&entity) {
                             Some(Ok(Value::Object(EntityObject::value_mapping(entity))))
                         } else {
-                            // id != entity.id , then don't send anything, still listening
+                            // no match, then don't send anything, still listening
                             None
                         }
                     }))
                 })
             })
-            .argument(InputValue::new("id", TypeRef::named(TypeRef::ID))),
+            .argument(InputValue::new("id", TypeRef::named(TypeRef::ID)))
+            .argument(InputValue::new("namespace", TypeRef::named(TypeRef::STRING)))
+            .argument(InputValue::new("model", TypeRef::named(TypeRef::STRING)))
+            .argument(InputValue::new("keys", TypeRef::named_list(TypeRef::STRING))),
         ])
     }
 }
 
+/// Filter of the `entityUpdated` subscription.
+#[derive(Debug)]
+struct EntityUpdatedFilter {
+    id: Option<String>,
+    namespace: Option<String>,
+    model: Option<String>,
+    /// Prefix of the entity keys, where `*` matches any key part.
+    keys: Option<Vec<String>>,
+}
+
+impl EntityUpdatedFilter {
+    fn matches(&self, entity: &Entity) -> bool {
+        if self.id.as_ref().is_some_and(|id| id != &entity.id) {
+            return false;
+        }
+
+        if self.namespace.is_some() || self.model.is_some() {
+            // the updated model is named after its tag
+            let Some(Ok((namespace, model))) =
+                entity.updated_model.as_ref().map(|ty| split_tag(&ty.name()))
+            else {
+                return false;
+            };
+
+            if self.namespace.as_ref().is_some_and(|ns| ns != &namespace)
+                || self.model.as_ref().is_some_and(|name| name != &model)
+            {
+                return false;
+            }
+        }
+
+        self.keys.as_ref().is_none_or(|keys| match_keys(keys, &entity.keys))
+    }
+}
+
+fn optional_string(ctx: &ResolverContext<'_>, name: &str) -> Result<Option<String>> {
+    match ctx.args.get(name) {
+        Some(value) => Ok(Some(value.string()?.to_string())),
+        None => Ok(None),
+    }
+}
+
 impl EntityObject {
     pub fn value_mapping(entity: Entity) -> ValueMapping {
         let keys: Vec<&str> = entity.keys.split('/').filter(|&k| !k.is_empty()).collect();
@@ -0,0 +1,186 @@
+use async_graphql::dynamic::{Field, FieldFuture, InputValue, ResolverContext, TypeRef};
+use async_graphql::{Name, Result, Value};
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, Pool, Sqlite};
+
+use super::{BasicObject, ResolvableObject, TypeMapping, ValueMapping};
+use crate::constants::{
+    DATETIME_FORMAT, ENTITY_HISTORICAL_TABLE, ENTITY_VERSION_NAMES, ENTITY_VERSION_TYPE_NAME,
+    MODEL_TABLE,
+};
+use crate::mapping::ENTITY_VERSION_TYPE_MAPPING;
+
+/// The versions of the entities, ie the values of their models as they were set at a given block.
+///
+/// Versions are only stored for the historical models (ie the `--sql.historical` option).
+#[derive(Debug)]
+pub struct EntityVersionObject;
+
+impl BasicObject for EntityVersionObject {
+    fn name(&self) -> (&str, &str) {
+        ENTITY_VERSION_NAMES
+    }
+
+    fn type_name(&self) -> &str {
+        ENTITY_VERSION_TYPE_NAME
+    }
+
+    fn type_mapping(&self) -> &TypeMapping {
+        &ENTITY_VERSION_TYPE_MAPPING
+    }
+}
+
+impl ResolvableObject for EntityVersionObject {
+    fn resolvers(&self) -> Vec<Field> {
+        // the latest version of each model of the entity at the given block
+        let entity_at_block =
+            Field::new(self.name().0, TypeRef::named_nn_list_nn(self.type_name()), |ctx| {
+                FieldFuture::new(async move {
+                    let filter = VersionFilter::from_args(&ctx)?;
+                    let block_number = ctx.args.try_get("blockNumber")?.u64()? as i64;
+
+                    let (conditions, values) = filter.conditions();
+                    let query = format!(
+                        "SELECT * FROM (SELECT {VERSION_COLUMNS}, ROW_NUMBER() OVER (PARTITION BY \
+                         h.model_id ORDER BY h.block_number DESC, h.rowid DESC) AS version FROM \
+                         {ENTITY_HISTORICAL_TABLE} h JOIN {MODEL_TABLE} m ON m.id = h.model_id \
+                         WHERE {conditions} AND h.block_number <= ?) WHERE version = 1",
+                    );
+
+                    let mut query = sqlx::query_as(&query);
+                    for value in values {
+                        query = query.bind(value);
+                    }
+
+                    let pool = ctx.data::<Pool<Sqlite>>()?;
+                    let versions: Vec<EntityVersion> =
+                        query.bind(block_number).fetch_all(pool).await?;
+
+                    Ok(Some(versions_value(versions)))
+                })
+            })
+            .argument(InputValue::new("blockNumber", TypeRef::named_nn(TypeRef::INT)));
+
+        // all the versions of the entity, ordered by block
+        let entity_history =
+            Field::new(self.name().1, TypeRef::named_nn_list_nn(self.type_name()), |ctx| {
+                FieldFuture::new(async move {
+                    let filter = VersionFilter::from_args(&ctx)?;
+                    let from_block = ctx.args.get("fromBlock").map(|v| v.u64()).transpose()?;
+                    let to_block = ctx.args.get("toBlock").map(|v| v.u64()).transpose()?;
+
+                    let (mut conditions, values) = filter.conditions();
+                    if from_block.is_some() {
+                        conditions.push_str(" AND h.block_number >= ?");
+                    }
+                    if to_block.is_some() {
+                        conditions.push_str(" AND h.block_number <= ?");
+                    }
+
+                    let query = format!(
+                        "SELECT {VERSION_COLUMNS} FROM {ENTITY_HISTORICAL_TABLE} h JOIN \
+                         {MODEL_TABLE} m ON m.id = h.model_id WHERE {conditions} ORDER BY \
+                         h.block_number ASC, h.rowid ASC",
+                    );
+
+                    let mut query = sqlx::query_as(&query);
+                    for value in values {
+                        query = query.bind(value);
+                    }
+                    for block in from_block.into_iter().chain(to_block) {
+                        query = query.bind(block as i64);
+                    }
+
+                    let pool = ctx.data::<Pool<Sqlite>>()?;
+                    let versions: Vec<EntityVersion> = query.fetch_all(pool).await?;
+
+                    Ok(Some(versions_value(versions)))
+                })
+            })
+            .argument(InputValue::new("fromBlock", TypeRef::named(TypeRef::INT)))
+            .argument(InputValue::new("toBlock", TypeRef::named(TypeRef::INT)));
+
+        vec![filter_arguments(entity_at_block), filter_arguments(entity_history)]
+    }
+}
+
+// the columns of `EntityVersion`, from the historical entities (h) joined with their model (m)
+const VERSION_COLUMNS: &str =
+    "h.id, m.namespace, m.name, h.event_id, h.block_number, h.executed_at, h.data";
+
+#[derive(FromRow, Debug)]
+struct EntityVersion {
+    id: String,
+    namespace: String,
+    name: String,
+    event_id: String,
+    block_number: Option<i64>,
+    executed_at: DateTime<Utc>,
+    data: String,
+}
+
+impl EntityVersion {
+    fn value_mapping(self) -> ValueMapping {
+        ValueMapping::from([
+            (Name::new("entityId"), Value::from(self.id)),
+            (Name::new("namespace"), Value::from(self.namespace)),
+            (Name::new("model"), Value::from(self.name)),
+            (Name::new("eventId"), Value::from(self.event_id)),
+            (Name::new("blockNumber"), Value::from(self.block_number)),
+            (
+                Name::new("executedAt"),
+                Value::from(self.executed_at.format(DATETIME_FORMAT).to_string()),
+            ),
+            (Name::new("data"), Value::from(self.data)),
+        ])
+    }
+}
+
+/// The entity, and optionally the model, the versions are queried for.
+struct VersionFilter {
+    entity_id: String,
+    namespace: Option<String>,
+    model: Option<String>,
+}
+
+impl VersionFilter {
+    fn from_args(ctx: &ResolverContext<'_>) -> Result<Self> {
+        let optional_string = |name: &str| -> Result<Option<String>> {
+            ctx.args.get(name).map(|v| v.string().map(ToString::to_string)).transpose()
+        };
+
+        Ok(Self {
+            entity_id: ctx.args.try_get("entityId")?.string()?.to_string(),
+            namespace: optional_string("namespace")?,
+            model: optional_string("model")?,
+        })
+    }
+
+    /// Returns the SQL conditions of the filter, along with the values to bind in order.
+    fn conditions(&self) -> (String, Vec<String>) {
+        let mut conditions = String::from("h.id = ?");
+        let mut values = vec![self.entity_id.clone()];
+
+        if let Some(namespace) = &self.namespace {
+            conditions.push_str(" AND m.namespace = ?");
+            values.push(namespace.clone());
+        }
+        if let Some(model) = &self.model {
+            conditions.push_str(" AND m.name = ?");
+            values.push(model.clone());
+        }
+
+        (conditions, values)
+    }
+}
+
+fn filter_arguments(field: Field) -> Field {
+    field
+        .argument(InputValue::new("entityId", TypeRef::named_nn(TypeRef::ID)))
+        .argument(InputValue::new("namespace", TypeRef::named(TypeRef::STRING)))
+        .argument(InputValue::new("model", TypeRef::named(TypeRef::STRING)))
+}
+
+fn versions_value(versions: Vec<EntityVersion>) -> Value {
+    Value::List(versions.into_iter().map(|v| Value::Object(v.value_mapping())).collect())
+}
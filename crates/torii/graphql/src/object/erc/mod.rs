@@ -1,6 +1,7 @@
 use async_graphql::Value;
 
 use super::connection::cursor;
+use crate::query::data::escape_string;
 use crate::query::order::CursorDirection;
 
 pub mod erc_token;
@@ -13,7 +14,9 @@ fn handle_cursor(
     id_column: &str,
 ) -> sqlx::Result<String> {
     match cursor::decode(cursor) {
-        Ok((event_id, _)) => Ok(format!("{} {} '{}'", id_column, direction.as_ref(), event_id)),
+        Ok((event_id, _)) => {
+            Ok(format!("{} {} '{}'", id_column, direction.as_ref(), escape_string(&event_id)))
+        }
         Err(_) => Err(sqlx::Error::Decode("Invalid cursor format".into())),
     }
 }
@@ -25,7 +25,7 @@ use crate::object::connection::{
 use crate::object::erc::erc_token::{Erc1155Token, Erc721Token};
 use crate::object::{BasicObject, ResolvableObject};
 use crate::query::data::count_rows;
-use crate::query::filter::{Comparator, Filter, FilterValue};
+use crate::query::filter::{Comparator, Condition, Filter, FilterValue};
 use crate::query::order::{CursorDirection, Direction};
 use crate::types::TypeMapping;
 use crate::utils::extract;
@@ -67,11 +67,11 @@ impl ResolvableObject for ErcBalanceObject {
                         &account_address.to_case(Case::Camel),
                     )?;
 
-                    let filter = vec![Filter {
+                    let filter = vec![Condition::from(Filter {
                         field: "account_address".to_string(),
                         comparator: Comparator::Eq,
                         value: FilterValue::String(felt_to_sql_string(&address)),
-                    }];
+                    })];
 
                     let total_count =
                         count_rows(&mut conn, TOKEN_BALANCE_TABLE, &None, &Some(filter)).await?;
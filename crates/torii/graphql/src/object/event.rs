@@ -3,11 +3,10 @@ use async_graphql::dynamic::{
 };
 use async_graphql::{Name, Result, Value};
 use tokio_stream::{Stream, StreamExt};
-use torii_sqlite::constants::SQL_FELT_DELIMITER;
 use torii_sqlite::simple_broker::SimpleBroker;
 use torii_sqlite::types::Event;
 
-use super::inputs::keys_input::{keys_argument, parse_keys_argument};
+use super::inputs::keys_input::{keys_argument, match_keys, parse_keys_argument};
 use super::{resolve_many, BasicObject, ResolvableObject, TypeMapping};
 use crate::constants::{DATETIME_FORMAT, EVENT_NAMES, EVENT_TABLE, EVENT_TYPE_NAME, ID_COLUMN};
 use crate::mapping::EVENT_TYPE_MAPPING;
@@ -101,19 +100,6 @@ impl EventObject {
     // Checks if the provided keys match the event's keys, allowing '*' as a wildcard. Returns true
     // if all keys match or if a wildcard is present at the respective position.
     pub fn match_keys(input_keys: &[String], event: &Event) -> bool {
-        let event_keys: Vec<&str> =
-            event.keys.split(SQL_FELT_DELIMITER).filter(|s| !s.is_empty()).collect();
-
-        if input_keys.len() > event_keys.len() {
-            return false;
-        }
-
-        for (input_key, event_key) in input_keys.iter().zip(event_keys.iter()) {
-            if input_key != "*" && input_key != event_key {
-                return false;
-            }
-        }
-
-        true
+        match_keys(input_keys, &event.keys)
     }
 }
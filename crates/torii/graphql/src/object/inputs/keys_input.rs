@@ -1,5 +1,6 @@
 use async_graphql::dynamic::{Field, InputValue, ResolverContext, TypeRef};
 use async_graphql::Error;
+use torii_sqlite::constants::SQL_FELT_DELIMITER;
 
 use crate::utils::extract;
 
@@ -21,6 +22,18 @@ pub fn parse_keys_argument(ctx: &ResolverContext<'_>) -> Result<Option<Vec<Strin
     Ok(None)
 }
 
+// Checks if the provided keys match the stored keys, allowing '*' as a wildcard. The input keys
+// are matched as a prefix, ie the stored keys can have extra key parts.
+pub fn match_keys(input_keys: &[String], keys: &str) -> bool {
+    let keys: Vec<&str> = keys.split(SQL_FELT_DELIMITER).filter(|s| !s.is_empty()).collect();
+
+    if input_keys.len() > keys.len() {
+        return false;
+    }
+
+    input_keys.iter().zip(keys.iter()).all(|(input_key, key)| input_key == "*" || input_key == key)
+}
+
 fn is_hex_or_star(s: &str) -> bool {
     if s == "*" {
         return true;
@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use async_graphql::dynamic::{
-    Field, InputObject, InputValue, ResolverContext, TypeRef, ValueAccessor,
+    Field, InputObject, InputValue, ObjectAccessor, ResolverContext, TypeRef, ValueAccessor,
 };
 use async_graphql::{Error as GqlError, Name, Result};
 use dojo_types::primitive::{Primitive, SqlType};
@@ -9,14 +9,21 @@ use strum::IntoEnumIterator;
 
 use super::InputObjectTrait;
 use crate::object::TypeMapping;
-use crate::query::filter::{parse_filter, Comparator, Filter, FilterValue};
+use crate::query::filter::{parse_filter, Comparator, Condition, FilterValue};
 use crate::types::TypeData;
 
+/// The fields of a where input combining other where inputs, ie the conditions they contain, with a
+/// logical operator.
+const AND_FIELD: &str = "and";
+const OR_FIELD: &str = "or";
+
 #[derive(Debug)]
 pub struct WhereInputObject {
     pub type_name: String,
     pub type_mapping: TypeMapping,
     pub nested_inputs: Vec<WhereInputObject>,
+    /// Whether the input has the `and` and `or` fields, only the case of the top level input.
+    logical_operators: bool,
 }
 
 impl WhereInputObject {
@@ -27,6 +34,7 @@ impl WhereInputObject {
             return vec![(Name::new(type_name), type_data.clone())];
         }
 
+        let is_byte_array = type_data.type_ref() == TypeRef::named("ByteArray");
         Comparator::iter().fold(
             vec![(Name::new(type_name), type_data.clone())],
             |mut acc, comparator| {
@@ -35,6 +43,8 @@ impl WhereInputObject {
                     Comparator::In | Comparator::NotIn => {
                         acc.push((Name::new(name), TypeData::List(Box::new(type_data.clone()))))
                     }
+                    // searching for a substring only makes sense for strings
+                    Comparator::Contains if !is_byte_array => {}
                     _ => {
                         acc.push((Name::new(name), type_data.clone()));
                     }
@@ -45,6 +55,10 @@ impl WhereInputObject {
     }
 
     pub fn new(type_name: &str, object_types: &TypeMapping) -> Self {
+        Self { logical_operators: true, ..Self::nested(type_name, object_types) }
+    }
+
+    fn nested(type_name: &str, object_types: &TypeMapping) -> Self {
         let mut nested_inputs = Vec::new();
         let mut where_mapping = TypeMapping::new();
 
@@ -53,7 +67,7 @@ impl WhereInputObject {
                 match type_data {
                     TypeData::Nested((_, nested_types)) => {
                         // Create nested input object
-                        let nested_input = WhereInputObject::new(
+                        let nested_input = WhereInputObject::nested(
                             &format!("{}_{}", type_name, field_name),
                             nested_types,
                         );
@@ -83,6 +97,7 @@ impl WhereInputObject {
             type_name: format!("{}WhereInput", type_name),
             type_mapping: where_mapping,
             nested_inputs,
+            logical_operators: false,
         }
     }
 }
@@ -107,8 +122,19 @@ impl InputObjectTrait for WhereInputObject {
     }
 
     fn input_object(&self) -> InputObject {
-        self.type_mapping.iter().fold(InputObject::new(self.type_name()), |acc, (ty_name, ty)| {
-            acc.field(InputValue::new(ty_name.to_string(), ty.type_ref()))
+        let object = self
+            .type_mapping
+            .iter()
+            .fold(InputObject::new(self.type_name()), |acc, (ty_name, ty)| {
+                acc.field(InputValue::new(ty_name.to_string(), ty.type_ref()))
+            });
+
+        if !self.logical_operators {
+            return object;
+        }
+
+        [AND_FIELD, OR_FIELD].into_iter().fold(object, |acc, name| {
+            acc.field(InputValue::new(name, TypeRef::named_nn_list(self.type_name())))
         })
     }
 }
@@ -121,7 +147,7 @@ fn parse_nested_where(
     input_object: &ValueAccessor<'_>,
     type_name: &str,
     type_data: &TypeData,
-) -> Result<Vec<Filter>> {
+) -> Result<Vec<Condition>> {
     match type_data {
         TypeData::Nested((_, nested_mapping)) => {
             let nested_input = input_object.object()?;
@@ -148,7 +174,7 @@ fn parse_where_value(
     input: ValueAccessor<'_>,
     field_path: &str,
     type_data: &TypeData,
-) -> Result<Vec<Filter>> {
+) -> Result<Vec<Condition>> {
     match type_data {
         TypeData::Simple(_) => {
             if type_data.type_ref() == TypeRef::named("Enum") {
@@ -158,7 +184,19 @@ fn parse_where_value(
                 // complex enums have a nested option field for their variant name.
                 // we trim the .option suffix to get the actual db field name
                 filter.field = filter.field.trim_end_matches(".option").to_string();
-                return Ok(vec![filter]);
+                return Ok(vec![filter.into()]);
+            }
+
+            if type_data.type_ref() == TypeRef::named("ByteArray") {
+                let value = input.string().map_err(|_| {
+                    GqlError::new(format!("Expected string on field {}", field_path))
+                })?;
+                let mut filter =
+                    parse_filter(&Name::new(field_path), FilterValue::String(value.to_string()));
+                if filter.comparator == Comparator::Contains {
+                    filter.value = FilterValue::String(format!("%{}%", value));
+                }
+                return Ok(vec![filter.into()]);
             }
 
             let primitive = Primitive::from_str(&type_data.type_ref().to_string())?;
@@ -167,13 +205,17 @@ fn parse_where_value(
                 SqlType::Text => parse_string(input, field_path, primitive)?,
             };
 
-            Ok(vec![parse_filter(&Name::new(field_path), filter_value)])
+            Ok(vec![parse_filter(&Name::new(field_path), filter_value).into()])
         }
         TypeData::List(inner) => {
             let list = input.list()?;
             let values = list
                 .iter()
                 .map(|value| {
+                    if inner.type_ref() == TypeRef::named("ByteArray") {
+                        return Ok(FilterValue::String(value.string()?.to_string()));
+                    }
+
                     let primitive = Primitive::from_str(&inner.type_ref().to_string())?;
                     match primitive.to_sql_type() {
                         SqlType::Integer => parse_integer(value, field_path, primitive),
@@ -182,27 +224,58 @@ fn parse_where_value(
                 })
                 .collect::<Result<Vec<_>>>()?;
 
-            Ok(vec![parse_filter(&Name::new(field_path), FilterValue::List(values))])
+            Ok(vec![parse_filter(&Name::new(field_path), FilterValue::List(values)).into()])
         }
         TypeData::Nested(_) => parse_nested_where(&input, field_path, type_data),
     }
 }
 
+/// Parses the conditions of a where input object. The conditions of its fields are combined with
+/// `AND`, along with the ones of its `and` and `or` fields.
+fn parse_where_object(
+    input_object: &ObjectAccessor<'_>,
+    where_mapping: &TypeMapping,
+) -> Result<Vec<Condition>> {
+    let mut conditions = where_mapping
+        .iter()
+        .filter_map(|(field_name, type_data)| {
+            input_object
+                .get(field_name)
+                .map(|input| parse_where_value(input, field_name, type_data))
+        })
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    for field in [AND_FIELD, OR_FIELD] {
+        let Some(inputs) = input_object.get(field) else {
+            continue;
+        };
+
+        // each input of the list is itself a where input, whose conditions are combined with `AND`
+        let operands = inputs
+            .list()?
+            .iter()
+            .map(|input| parse_where_object(&input.object()?, where_mapping).map(Condition::And))
+            .collect::<Result<Vec<_>>>()?;
+
+        conditions.push(if field == AND_FIELD {
+            Condition::And(operands)
+        } else {
+            Condition::Or(operands)
+        });
+    }
+
+    Ok(conditions)
+}
+
 pub fn parse_where_argument(
     ctx: &ResolverContext<'_>,
     where_mapping: &TypeMapping,
-) -> Result<Option<Vec<Filter>>> {
+) -> Result<Option<Vec<Condition>>> {
     ctx.args.get("where").map_or(Ok(None), |where_input| {
-        let input_object = where_input.object()?;
-        where_mapping
-            .iter()
-            .filter_map(|(field_name, type_data)| {
-                input_object
-                    .get(field_name)
-                    .map(|input| parse_where_value(input, field_name, type_data))
-            })
-            .collect::<Result<Vec<_>>>()
-            .map(|filters| Some(filters.into_iter().flatten().collect()))
+        parse_where_object(&where_input.object()?, where_mapping).map(Some)
     })
 }
 
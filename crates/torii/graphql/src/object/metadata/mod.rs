@@ -6,7 +6,9 @@ use sqlx::sqlite::SqliteRow;
 use sqlx::{Pool, Row, Sqlite};
 
 use super::connection::page_info::PageInfoObject;
-use super::connection::{connection_arguments, cursor, parse_connection_arguments};
+use super::connection::{
+    connection_arguments, cursor, parse_connection_arguments, total_count_required,
+};
 use super::{BasicObject, ResolvableObject};
 use crate::constants::{
     ID_COLUMN, JSON_COLUMN, METADATA_NAMES, METADATA_TABLE, METADATA_TYPE_NAME,
@@ -57,7 +59,11 @@ impl ResolvableObject for MetadataObject {
                 FieldFuture::new(async move {
                     let mut conn = ctx.data::<Pool<Sqlite>>()?.acquire().await?;
                     let connection = parse_connection_arguments(&ctx)?;
-                    let total_count = count_rows(&mut conn, METADATA_TABLE, &None, &None).await?;
+                    let total_count = if total_count_required(&ctx, &connection) {
+                        count_rows(&mut conn, METADATA_TABLE, &None, &None).await?
+                    } else {
+                        0
+                    };
                     let (data, page_info) = fetch_multiple_rows(
                         &mut conn,
                         METADATA_TABLE,
@@ -2,6 +2,7 @@ pub mod connection;
 pub mod controller;
 pub mod empty;
 pub mod entity;
+pub mod entity_version;
 pub mod erc;
 pub mod event;
 pub mod event_message;
@@ -9,6 +10,7 @@ pub mod inputs;
 pub mod metadata;
 pub mod model;
 pub mod model_data;
+pub mod offchain_message;
 pub mod transaction;
 
 use async_graphql::dynamic::{
@@ -24,7 +26,8 @@ use sqlx::{Pool, Sqlite};
 
 use self::connection::edge::EdgeObject;
 use self::connection::{
-    connection_arguments, connection_output, parse_connection_arguments, ConnectionObject,
+    connection_arguments, connection_output, parse_connection_arguments, total_count_required,
+    ConnectionObject,
 };
 use self::inputs::keys_input::parse_keys_argument;
 use self::inputs::order_input::parse_order_argument;
@@ -340,7 +343,11 @@ pub fn resolve_many(
                 let connection = parse_connection_arguments(&ctx)?;
                 let keys = parse_keys_argument(&ctx)?;
                 let order = parse_order_argument(&ctx);
-                let total_count = count_rows(&mut conn, &table_name, &keys, &None).await?;
+                let total_count = if total_count_required(&ctx, &connection) {
+                    count_rows(&mut conn, &table_name, &keys, &None).await?
+                } else {
+                    0
+                };
 
                 let (data, page_info) = fetch_multiple_rows(
                     &mut conn,
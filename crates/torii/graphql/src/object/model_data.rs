@@ -4,7 +4,9 @@ use dojo_types::naming::get_tag;
 use dojo_types::schema::Ty;
 use sqlx::{Pool, Sqlite};
 
-use super::connection::{connection_arguments, connection_output, parse_connection_arguments};
+use super::connection::{
+    connection_arguments, connection_output, parse_connection_arguments, total_count_required,
+};
 use super::inputs::order_input::{order_argument, parse_order_argument, OrderInputObject};
 use super::inputs::where_input::{parse_where_argument, where_argument, WhereInputObject};
 use super::inputs::InputObjectTrait;
@@ -104,7 +106,11 @@ impl ResolvableObject for ModelDataObject {
                 let filters = parse_where_argument(&ctx, &where_mapping)?;
                 let connection = parse_connection_arguments(&ctx)?;
 
-                let total_count = count_rows(&mut conn, &table_name, &None, &filters).await?;
+                let total_count = if total_count_required(&ctx, &connection) {
+                    count_rows(&mut conn, &table_name, &None, &filters).await?
+                } else {
+                    0
+                };
                 let (data, page_info) = fetch_multiple_rows(
                     &mut conn,
                     &table_name,
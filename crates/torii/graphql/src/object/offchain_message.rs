@@ -0,0 +1,118 @@
+use async_graphql::dynamic::{
+    Field, InputValue, ResolverContext, SubscriptionField, SubscriptionFieldFuture, TypeRef,
+};
+use async_graphql::{Name, Result, Value};
+use tokio_stream::{Stream, StreamExt};
+use torii_sqlite::constants::SQL_FELT_DELIMITER;
+use torii_sqlite::simple_broker::SimpleBroker;
+use torii_sqlite::types::OffchainMessage;
+
+use super::{resolve_many, BasicObject, ResolvableObject, TypeMapping};
+use crate::constants::{
+    DATETIME_FORMAT, ID_COLUMN, OFFCHAIN_MESSAGE_NAMES, OFFCHAIN_MESSAGE_TABLE,
+    OFFCHAIN_MESSAGE_TYPE_NAME,
+};
+use crate::mapping::OFFCHAIN_MESSAGE_TYPE_MAPPING;
+use crate::types::ValueMapping;
+
+/// The off-chain messages received by the relay, once their signature has been verified against
+/// their identity.
+#[derive(Debug)]
+pub struct OffchainMessageObject;
+
+impl BasicObject for OffchainMessageObject {
+    fn name(&self) -> (&str, &str) {
+        OFFCHAIN_MESSAGE_NAMES
+    }
+
+    fn type_name(&self) -> &str {
+        OFFCHAIN_MESSAGE_TYPE_NAME
+    }
+
+    fn type_mapping(&self) -> &TypeMapping {
+        &OFFCHAIN_MESSAGE_TYPE_MAPPING
+    }
+}
+
+impl ResolvableObject for OffchainMessageObject {
+    fn resolvers(&self) -> Vec<Field> {
+        let resolve_many = resolve_many(
+            OFFCHAIN_MESSAGE_TABLE,
+            ID_COLUMN,
+            self.name().1,
+            self.type_name(),
+            self.type_mapping(),
+        );
+
+        vec![resolve_many]
+    }
+
+    fn subscriptions(&self) -> Option<Vec<SubscriptionField>> {
+        Some(vec![
+            SubscriptionField::new(
+                "offchainMessageReceived",
+                TypeRef::named_nn(self.type_name()),
+                |ctx| {
+                    SubscriptionFieldFuture::new(async move {
+                        let filter = MessageFilter::from_args(&ctx)?;
+                        Ok(OffchainMessageObject::subscription_stream(filter))
+                    })
+                },
+            )
+            .argument(InputValue::new("identity", TypeRef::named(TypeRef::STRING)))
+            .argument(InputValue::new("model", TypeRef::named(TypeRef::STRING))),
+        ])
+    }
+}
+
+impl OffchainMessageObject {
+    fn value_mapping(message: OffchainMessage) -> ValueMapping {
+        let signature: Vec<&str> =
+            message.signature.split(SQL_FELT_DELIMITER).filter(|s| !s.is_empty()).collect();
+        ValueMapping::from([
+            (Name::new("id"), Value::from(message.id)),
+            (Name::new("identity"), Value::from(message.identity)),
+            (Name::new("entityId"), Value::from(message.entity_id)),
+            (Name::new("model"), Value::from(message.model)),
+            (Name::new("message"), Value::from(message.message)),
+            (Name::new("signature"), Value::from(signature)),
+            (
+                Name::new("receivedAt"),
+                Value::from(message.received_at.format(DATETIME_FORMAT).to_string()),
+            ),
+            (
+                Name::new("createdAt"),
+                Value::from(message.created_at.format(DATETIME_FORMAT).to_string()),
+            ),
+        ])
+    }
+
+    fn subscription_stream(filter: MessageFilter) -> impl Stream<Item = Result<Value>> {
+        SimpleBroker::<OffchainMessage>::subscribe().filter_map(move |message| {
+            filter
+                .matches(&message)
+                .then(|| Ok(Value::Object(OffchainMessageObject::value_mapping(message))))
+        })
+    }
+}
+
+/// The identity and the model tag the subscribed messages must have, if any.
+struct MessageFilter {
+    identity: Option<String>,
+    model: Option<String>,
+}
+
+impl MessageFilter {
+    fn from_args(ctx: &ResolverContext<'_>) -> Result<Self> {
+        let optional_string = |name: &str| -> Result<Option<String>> {
+            ctx.args.get(name).map(|v| v.string().map(ToString::to_string)).transpose()
+        };
+
+        Ok(Self { identity: optional_string("identity")?, model: optional_string("model")? })
+    }
+
+    fn matches(&self, message: &OffchainMessage) -> bool {
+        self.identity.as_ref().is_none_or(|identity| *identity == message.identity)
+            && self.model.as_ref().is_none_or(|model| *model == message.model)
+    }
+}
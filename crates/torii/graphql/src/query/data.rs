@@ -3,7 +3,7 @@ use sqlx::sqlite::SqliteRow;
 use sqlx::{Result, Row, SqliteConnection};
 use torii_sqlite::constants::WORLD_CONTRACT_TYPE;
 
-use super::filter::{Filter, FilterValue};
+use super::filter::{Condition, Filter, FilterValue};
 use super::order::{CursorDirection, Direction, Order};
 use crate::constants::DEFAULT_LIMIT;
 use crate::object::connection::{cursor, ConnectionArguments};
@@ -12,7 +12,7 @@ pub async fn count_rows(
     conn: &mut SqliteConnection,
     table_name: &str,
     keys: &Option<Vec<String>>,
-    filters: &Option<Vec<Filter>>,
+    filters: &Option<Vec<Condition>>,
 ) -> Result<i64> {
     let mut query = format!("SELECT COUNT(*) FROM [{}]", table_name);
     let conditions = build_conditions(keys, filters);
@@ -112,7 +112,7 @@ pub async fn fetch_multiple_rows(
     id_column: &str,
     keys: &Option<Vec<String>>,
     order: &Option<Order>,
-    filters: &Option<Vec<Filter>>,
+    filters: &Option<Vec<Condition>>,
     connection: &ConnectionArguments,
     total_count: i64,
 ) -> Result<(Vec<SqliteRow>, PageInfo)> {
@@ -133,7 +133,7 @@ pub async fn fetch_multiple_rows(
         query.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
     }
 
-    let is_cursor_based = connection.first.or(connection.last).is_some() || cursor_param.is_some();
+    let is_cursor_based = connection.is_cursor_based();
 
     let data_limit =
         connection.first.or(connection.last).or(connection.limit).unwrap_or(DEFAULT_LIMIT);
@@ -249,26 +249,28 @@ fn handle_cursor(
     direction: CursorDirection,
     id_column: &str,
 ) -> Result<String> {
-    match cursor::decode(cursor) {
-        Ok((event_id, field_value)) => match order {
-            Some(order) => Ok(format!(
-                "(({} {} '{}' AND {} = '{}') OR {} {} '{}')",
-                id_column,
-                direction.as_ref(),
-                event_id,
-                order.field,
-                field_value,
-                order.field,
-                direction.as_ref(),
-                field_value
-            )),
-            None => Ok(format!("{} {} '{}'", id_column, direction.as_ref(), event_id)),
-        },
-        Err(_) => Err(sqlx::Error::Decode("Invalid cursor format".into())),
+    let (event_id, field_value) =
+        cursor::decode(cursor).map_err(|_| sqlx::Error::Decode("Invalid cursor format".into()))?;
+    // the cursors are provided by the clients, so their values are escaped
+    let (event_id, field_value) = (escape_string(&event_id), escape_string(&field_value));
+
+    match order {
+        Some(order) => Ok(format!(
+            "(({} {} '{}' AND {} = '{}') OR {} {} '{}')",
+            id_column,
+            direction.as_ref(),
+            event_id,
+            order.field,
+            field_value,
+            order.field,
+            direction.as_ref(),
+            field_value
+        )),
+        None => Ok(format!("{} {} '{}'", id_column, direction.as_ref(), event_id)),
     }
 }
 
-fn build_conditions(keys: &Option<Vec<String>>, filters: &Option<Vec<Filter>>) -> Vec<String> {
+fn build_conditions(keys: &Option<Vec<String>>, filters: &Option<Vec<Condition>>) -> Vec<String> {
     let mut conditions = Vec::new();
 
     if let Some(keys) = keys {
@@ -284,27 +286,54 @@ fn build_conditions(keys: &Option<Vec<String>>, filters: &Option<Vec<Filter>>) -
     }
 
     if let Some(filters) = filters {
-        conditions.extend(filters.iter().map(|filter| match &filter.value {
-            FilterValue::Int(i) => format!("[{}] {} {}", filter.field, filter.comparator, i),
-            FilterValue::String(s) => format!("[{}] {} '{}'", filter.field, filter.comparator, s),
-            FilterValue::List(list) => {
-                let values = list
-                    .iter()
-                    .map(|value| match value {
-                        FilterValue::Int(i) => i.to_string(),
-                        FilterValue::String(s) => format!("'{}'", s),
-                        FilterValue::List(_) => unreachable!(),
-                    })
-                    .collect::<Vec<_>>()
-                    .join(", ");
-                format!("[{}] {} ({})", filter.field, filter.comparator, values)
-            }
-        }));
+        conditions.extend(filters.iter().map(condition_to_sql));
     }
 
     conditions
 }
 
+fn condition_to_sql(condition: &Condition) -> String {
+    let (conditions, operator) = match condition {
+        Condition::Filter(filter) => return filter_to_sql(filter),
+        Condition::And(conditions) => (conditions, " AND "),
+        Condition::Or(conditions) => (conditions, " OR "),
+    };
+
+    // an empty list of conditions doesn't filter anything
+    if conditions.is_empty() {
+        return "1 = 1".to_string();
+    }
+
+    let conditions = conditions.iter().map(condition_to_sql).collect::<Vec<_>>();
+    format!("({})", conditions.join(operator))
+}
+
+fn filter_to_sql(filter: &Filter) -> String {
+    match &filter.value {
+        FilterValue::Int(i) => format!("[{}] {} {}", filter.field, filter.comparator, i),
+        FilterValue::String(s) => {
+            format!("[{}] {} '{}'", filter.field, filter.comparator, escape_string(s))
+        }
+        FilterValue::List(list) => {
+            let values = list
+                .iter()
+                .map(|value| match value {
+                    FilterValue::Int(i) => i.to_string(),
+                    FilterValue::String(s) => format!("'{}'", escape_string(s)),
+                    FilterValue::List(_) => unreachable!(),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("[{}] {} ({})", filter.field, filter.comparator, values)
+        }
+    }
+}
+
+// escapes the quotes of a string literal, eg of a `ByteArray` value
+pub(crate) fn escape_string(s: &str) -> String {
+    s.replace('\'', "''")
+}
+
 fn keys_to_pattern(keys: &[String], use_regex: bool) -> String {
     let pattern = keys
         .iter()
@@ -20,6 +20,7 @@ pub enum Comparator {
     In,
     NotLike,
     Like,
+    Contains,
 }
 
 impl fmt::Display for Comparator {
@@ -35,6 +36,8 @@ impl fmt::Display for Comparator {
             Comparator::NotIn => write!(f, "NOT IN"),
             Comparator::Like => write!(f, "LIKE"),
             Comparator::NotLike => write!(f, "NOT LIKE"),
+            // the value is wrapped in `%` when parsed
+            Comparator::Contains => write!(f, "LIKE"),
         }
     }
 }
@@ -53,6 +56,21 @@ pub struct Filter {
     pub value: FilterValue,
 }
 
+/// A condition of a where clause, either a filter on a field or conditions combined with a logical
+/// operator.
+#[derive(Debug)]
+pub enum Condition {
+    Filter(Filter),
+    And(Vec<Condition>),
+    Or(Vec<Condition>),
+}
+
+impl From<Filter> for Condition {
+    fn from(filter: Filter) -> Self {
+        Condition::Filter(filter)
+    }
+}
+
 pub fn parse_filter(input: &Name, value: FilterValue) -> Filter {
     for comparator in Comparator::iter() {
         if let Some(field) = input.strip_suffix(comparator.as_ref()) {
@@ -16,6 +16,7 @@ use crate::constants::{
 };
 use crate::object::controller::ControllerObject;
 use crate::object::empty::EmptyObject;
+use crate::object::entity_version::EntityVersionObject;
 use crate::object::erc::erc_token::{
     Erc1155TokenObject, Erc20TokenObject, Erc721TokenObject, TokenObject,
 };
@@ -26,6 +27,7 @@ use crate::object::metadata::content::ContentObject;
 use crate::object::metadata::social::SocialObject;
 use crate::object::metadata::MetadataObject;
 use crate::object::model::ModelObject;
+use crate::object::offchain_message::OffchainMessageObject;
 use crate::object::transaction::{CallObject, TransactionObject};
 use crate::object::ObjectVariant;
 use crate::query::build_type_mapping;
@@ -35,10 +37,30 @@ use crate::query::build_type_mapping;
 // events, their schema is known but we generate them dynamically as well because async-graphql
 // does not allow mixing of static and dynamic schemas.
 pub async fn build_schema(pool: &SqlitePool) -> Result<Schema> {
+    build_schema_with_limits(pool, QueryLimits::default()).await
+}
+
+/// The limits of the queries executed against the schema. The queries exceeding them, eg
+/// pathological nested queries, are rejected before being executed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QueryLimits {
+    /// The maximum depth of the selections of a query.
+    pub max_depth: Option<usize>,
+    /// The maximum complexity of a query, ie the number of fields it selects, fragments included.
+    pub max_complexity: Option<usize>,
+}
+
+pub async fn build_schema_with_limits(pool: &SqlitePool, limits: QueryLimits) -> Result<Schema> {
     // build world gql objects
     let (objects, unions) = build_objects(pool).await?;
 
     let mut schema_builder = Schema::build(QUERY_TYPE_NAME, None, Some(SUBSCRIPTION_TYPE_NAME));
+    if let Some(max_depth) = limits.max_depth {
+        schema_builder = schema_builder.limit_depth(max_depth);
+    }
+    if let Some(max_complexity) = limits.max_complexity {
+        schema_builder = schema_builder.limit_complexity(max_complexity);
+    }
     //? why we need to provide QUERY_TYPE_NAME object here when its already passed to Schema?
     let mut query_root = Object::new(QUERY_TYPE_NAME);
     let mut subscription_root = Subscription::new(SUBSCRIPTION_TYPE_NAME);
@@ -119,8 +141,10 @@ async fn build_objects(pool: &SqlitePool) -> Result<(Vec<ObjectVariant>, Vec<Uni
     // predefined objects
     let mut objects: Vec<ObjectVariant> = vec![
         ObjectVariant::Resolvable(Box::new(EntityObject)),
+        ObjectVariant::Resolvable(Box::new(EntityVersionObject)),
         ObjectVariant::Resolvable(Box::new(EventMessageObject)),
         ObjectVariant::Resolvable(Box::new(EventObject)),
+        ObjectVariant::Resolvable(Box::new(OffchainMessageObject)),
         ObjectVariant::Resolvable(Box::new(MetadataObject)),
         ObjectVariant::Resolvable(Box::new(ModelObject)),
         ObjectVariant::Resolvable(Box::new(TransactionObject)),
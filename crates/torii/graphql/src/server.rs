@@ -9,13 +9,14 @@ use sqlx::{Pool, Sqlite};
 use tokio::sync::broadcast::Receiver;
 use warp::{Filter, Rejection, Reply};
 
-use super::schema::build_schema;
+use super::schema::{build_schema_with_limits, QueryLimits};
 
 pub async fn new(
     mut shutdown_rx: Receiver<()>,
     pool: &Pool<Sqlite>,
+    limits: QueryLimits,
 ) -> (SocketAddr, impl Future<Output = ()> + 'static) {
-    let schema = build_schema(pool).await.unwrap();
+    let schema = build_schema_with_limits(pool, limits).await.unwrap();
     let routes = graphql_filter(schema);
     warp::serve(routes).bind_with_graceful_shutdown(([127, 0, 0, 1], 0), async move {
         shutdown_rx.recv().await.ok();
@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    use dojo_types::naming::get_tag;
+    use dojo_types::primitive::Primitive;
+    use dojo_types::schema::{Member, Struct, Ty};
+    use dojo_world::contracts::naming::compute_selector_from_tag;
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use starknet::core::types::Felt;
+    use starknet::providers::jsonrpc::HttpTransport;
+    use starknet::providers::JsonRpcClient;
+    use starknet_crypto::poseidon_hash_many;
+    use tokio::sync::broadcast;
+    use torii_sqlite::cache::ModelCache;
+    use torii_sqlite::executor::Executor;
+    use torii_sqlite::types::{Contract, ContractType};
+    use torii_sqlite::utils::felts_to_sql_string;
+    use torii_sqlite::{Sql, SqlConfig};
+    use url::Url;
+
+    use crate::schema::build_schema;
+    use crate::tests::{model_fixtures, run_graphql_query};
+
+    const BLOCK_TIMESTAMP: u64 = 1710754478;
+
+    fn record(record_id: u32) -> Ty {
+        Ty::Struct(Struct {
+            name: get_tag("types_test", "Record"),
+            children: vec![
+                Member {
+                    name: "record_id".to_string(),
+                    key: false,
+                    ty: Ty::Primitive(Primitive::U32(Some(record_id))),
+                },
+                Member {
+                    name: "typeContractAddress".to_string(),
+                    key: true,
+                    ty: Ty::Primitive(Primitive::ContractAddress(Some(Felt::ONE))),
+                },
+            ],
+        })
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_entity_versions(pool: SqlitePool) {
+        let (shutdown_tx, _) = broadcast::channel(1);
+        // dummy provider since its required to query data for erc721 tokens
+        let url: Url = "https://www.example.com".parse().unwrap();
+        let provider = Arc::new(JsonRpcClient::new(HttpTransport::new(url)));
+        let (mut executor, sender) =
+            Executor::new(pool.clone(), shutdown_tx.clone(), provider, 100).await.unwrap();
+        tokio::spawn(async move {
+            executor.run().await.unwrap();
+        });
+
+        let model_cache = Arc::new(ModelCache::new(pool.clone()));
+        let config = SqlConfig {
+            historical_models: HashSet::from([get_tag("types_test", "Record")]),
+            ..Default::default()
+        };
+        let mut db = Sql::new_with_config(
+            pool.clone(),
+            sender.clone(),
+            &[Contract { address: Felt::ZERO, r#type: ContractType::WORLD }],
+            model_cache.clone(),
+            config,
+        )
+        .await
+        .unwrap();
+
+        model_fixtures(&mut db).await;
+
+        let keys = vec![Felt::ONE];
+        let entity_id = poseidon_hash_many(&keys);
+        let model_id = compute_selector_from_tag(&get_tag("types_test", "Record"));
+
+        let event_id =
+            |block_number: u64| format!("{block_number:#064x}:{:#x}:{:#04x}", Felt::ZERO, 0);
+
+        // the record is set at blocks 1, 2 and 4
+        for (block_number, record_id) in [(1u64, 10u32), (2, 20), (4, 40)] {
+            db.set_entity(
+                record(record_id),
+                &event_id(block_number),
+                BLOCK_TIMESTAMP,
+                entity_id,
+                model_id,
+                Some(&felts_to_sql_string(&keys)),
+            )
+            .await
+            .unwrap();
+        }
+        db.execute().await.unwrap();
+
+        let schema = build_schema(&pool).await.unwrap();
+        let data = |record_id: u32| {
+            serde_json::to_string(&record(record_id).to_json_value().unwrap()).unwrap()
+        };
+
+        let query = format!(
+            r#"{{
+                entityAtBlock(entityId: "{entity_id:#x}", blockNumber: 3) {{
+                    model
+                    blockNumber
+                    data
+                }}
+            }}"#
+        );
+        let result = run_graphql_query(&schema, &query).await;
+        assert_eq!(
+            result["entityAtBlock"],
+            json!([{ "model": "Record", "blockNumber": 2, "data": data(20) }])
+        );
+
+        let query = format!(
+            r#"{{
+                entityHistory(entityId: "{entity_id:#x}", namespace: "types_test", fromBlock: 2) {{
+                    blockNumber
+                    data
+                }}
+            }}"#
+        );
+        let result = run_graphql_query(&schema, &query).await;
+        assert_eq!(
+            result["entityHistory"],
+            json!([{ "blockNumber": 2, "data": data(20) }, { "blockNumber": 4, "data": data(40) }])
+        );
+
+        // no version is stored before the first block the entity was set at
+        let query = format!(
+            r#"{{
+                entityAtBlock(entityId: "{entity_id:#x}", blockNumber: 0) {{
+                    blockNumber
+                }}
+            }}"#
+        );
+        let result = run_graphql_query(&schema, &query).await;
+        assert_eq!(result["entityAtBlock"], json!([]));
+
+        // the versions set in the blocks removed by a chain reorganization are rolled back
+        db.rollback_to_block(Some(2)).await.unwrap();
+        db.execute().await.unwrap();
+        assert_eq!(db.cursors().await.unwrap().head, Some(2));
+
+        let query = format!(
+            r#"{{
+                entityHistory(entityId: "{entity_id:#x}") {{
+                    blockNumber
+                }}
+            }}"#
+        );
+        let result = run_graphql_query(&schema, &query).await;
+        assert_eq!(result["entityHistory"], json!([{ "blockNumber": 1 }, { "blockNumber": 2 }]));
+
+        // the record is restored to its version of block 2
+        let record_event_id = move |pool: SqlitePool| async move {
+            sqlx::query_scalar::<_, String>(
+                "SELECT internal_event_id FROM [types_test-Record] WHERE internal_id = ?",
+            )
+            .bind(format!("{entity_id:#x}"))
+            .fetch_optional(&pool)
+            .await
+            .unwrap()
+        };
+        assert_eq!(record_event_id(pool.clone()).await, Some(event_id(2)));
+
+        // without the versions of a model, the indexed data is removed to index the chain again
+        let mut db = Sql::new_with_config(
+            pool.clone(),
+            sender,
+            &[Contract { address: Felt::ZERO, r#type: ContractType::WORLD }],
+            model_cache,
+            SqlConfig::default(),
+        )
+        .await
+        .unwrap();
+        db.set_entity(
+            record(30),
+            &event_id(3),
+            BLOCK_TIMESTAMP,
+            entity_id,
+            model_id,
+            Some(&felts_to_sql_string(&keys)),
+        )
+        .await
+        .unwrap();
+        db.execute().await.unwrap();
+
+        db.rollback_to_block(Some(2)).await.unwrap();
+        db.execute().await.unwrap();
+        assert_eq!(db.cursors().await.unwrap().head, None);
+        assert_eq!(record_event_id(pool.clone()).await, None);
+    }
+}
@@ -74,4 +74,37 @@ mod tests {
 
         Ok(())
     }
+
+    // the total count isn't selected, so the events aren't counted
+    async fn events_page(schema: &Schema, args: &str) -> Value {
+        let query = format!(
+            "{{ events {args} {{ edges {{ node {{ id }} }} pageInfo {{ hasNextPage endCursor }} \
+             }} }}"
+        );
+
+        let result = run_graphql_query(schema, &query).await;
+        result.get("events").ok_or("events not found").unwrap().clone()
+    }
+
+    #[sqlx::test(migrations = "../migrations", fixtures("./fixtures/events.sql"))]
+    async fn test_events_cursor_pagination(
+        options: SqlitePoolOptions,
+        connect_options: SqliteConnectOptions,
+    ) -> Result<()> {
+        let pool = options.connect_with(connect_options).await?;
+        let schema = build_schema(&pool).await?;
+
+        let first = events_page(&schema, "(first: 2)").await;
+        assert_eq!(first["edges"][0]["node"]["id"], "0x3");
+        assert_eq!(first["edges"][1]["node"]["id"], "0x2");
+        assert_eq!(first["pageInfo"]["hasNextPage"], true);
+
+        let cursor = first["pageInfo"]["endCursor"].as_str().unwrap();
+        let second = events_page(&schema, &format!("(first: 2, after: \"{cursor}\")")).await;
+        assert_eq!(second["edges"].as_array().unwrap().len(), 1);
+        assert_eq!(second["edges"][0]["node"]["id"], "0x1");
+        assert_eq!(second["pageInfo"]["hasNextPage"], false);
+
+        Ok(())
+    }
 }
@@ -34,10 +34,13 @@ use torii_sqlite::types::{Contract, ContractType};
 use torii_sqlite::Sql;
 
 mod entities_test;
+mod entity_version_test;
 mod events_test;
 mod metadata_test;
 mod models_ordering_test;
 mod models_test;
+mod query_limits_test;
+mod rate_limit_test;
 mod subscription_test;
 
 use crate::schema::build_schema;
@@ -345,6 +345,26 @@ mod tests {
         let connection: Connection<Record> = serde_json::from_value(records).unwrap();
         assert_eq!(connection.total_count, 7);
 
+        // where filter OR
+        let records = records_model_query(
+            &schema,
+            "(where: { or: [{ type_u8: 1 }, { type_u8GTE: 8 }] }, order: { direction: DESC, \
+             field: RECORD_ID })",
+        )
+        .await;
+        let connection: Connection<Record> = serde_json::from_value(records).unwrap();
+        assert_eq!(connection.total_count, 3);
+
+        // where filter AND nested in OR, combined with a field filter
+        let records = records_model_query(
+            &schema,
+            "(where: { type_u8LT: 8, or: [{ type_u8: 1 }, { and: [{ type_u8GTE: 4 }, { \
+             type_u8LTE: 5 }] }] }, order: { direction: DESC, field: RECORD_ID })",
+        )
+        .await;
+        let connection: Connection<Record> = serde_json::from_value(records).unwrap();
+        assert_eq!(connection.total_count, 3);
+
         // *** ORDER TESTING ***
 
         // order on random u8 DESC (number)
@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use sqlx::SqlitePool;
+
+    use crate::schema::{build_schema_with_limits, QueryLimits};
+    use crate::tests::run_graphql_query;
+
+    const QUERY: &str = "{ events { edges { node { id keys } } pageInfo { hasNextPage } } }";
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_query_depth_limit(pool: SqlitePool) -> Result<()> {
+        // the query is 4 levels deep: events > edges > node > id
+        let limits = QueryLimits { max_depth: Some(4), ..Default::default() };
+        let schema = build_schema_with_limits(&pool, limits).await?;
+        run_graphql_query(&schema, QUERY).await;
+
+        let limits = QueryLimits { max_depth: Some(3), ..Default::default() };
+        let schema = build_schema_with_limits(&pool, limits).await?;
+        let response = schema.execute(QUERY).await;
+        assert_eq!(response.errors.len(), 1);
+        assert_eq!(response.errors[0].message, "Query is nested too deep.");
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "../migrations")]
+    async fn test_query_complexity_limit(pool: SqlitePool) -> Result<()> {
+        // the query selects 7 fields
+        let limits = QueryLimits { max_complexity: Some(7), ..Default::default() };
+        let schema = build_schema_with_limits(&pool, limits).await?;
+        run_graphql_query(&schema, QUERY).await;
+
+        let limits = QueryLimits { max_complexity: Some(6), ..Default::default() };
+        let schema = build_schema_with_limits(&pool, limits).await?;
+        let response = schema.execute(QUERY).await;
+        assert_eq!(response.errors.len(), 1);
+        assert_eq!(response.errors[0].message, "Query is too complex.");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+    use std::time::{Duration, Instant};
+
+    use torii_server::{RateLimiter, RATE_LIMIT_MAX_CLIENTS, RATE_LIMIT_WINDOW};
+
+    fn client(n: u32) -> IpAddr {
+        IpAddr::from(n.to_be_bytes())
+    }
+
+    #[test]
+    fn test_rate_limit_per_client() {
+        let limiter = RateLimiter::new(2);
+        let now = Instant::now();
+
+        assert!(limiter.check(client(1), now));
+        assert!(limiter.check(client(1), now));
+        assert!(!limiter.check(client(1), now));
+
+        // other clients have their own count
+        assert!(limiter.check(client(2), now));
+        assert!(!limiter.check(client(1), now + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_rate_limit_window_reset() {
+        let limiter = RateLimiter::new(1);
+        let now = Instant::now();
+
+        assert!(limiter.check(client(1), now));
+        assert!(!limiter.check(client(1), now + RATE_LIMIT_WINDOW - Duration::from_millis(1)));
+
+        // the count restarts once the window of the client is over
+        let later = now + RATE_LIMIT_WINDOW;
+        assert!(limiter.check(client(1), later));
+        assert!(!limiter.check(client(1), later));
+    }
+
+    #[test]
+    fn test_rate_limit_clients_eviction() {
+        let limiter = RateLimiter::new(1);
+        let now = Instant::now();
+
+        for n in 0..RATE_LIMIT_MAX_CLIENTS as u32 {
+            assert!(limiter.check(client(n), now));
+        }
+        assert_eq!(limiter.num_clients(), RATE_LIMIT_MAX_CLIENTS);
+
+        // the clients are kept while their window is not over
+        let max = RATE_LIMIT_MAX_CLIENTS as u32;
+        assert!(limiter.check(client(max), now + Duration::from_secs(1)));
+        assert_eq!(limiter.num_clients(), RATE_LIMIT_MAX_CLIENTS + 1);
+
+        // the clients whose window is over are forgotten once the limit is reached
+        assert!(limiter.check(client(max + 1), now + RATE_LIMIT_WINDOW));
+        assert_eq!(limiter.num_clients(), 2);
+        assert!(!limiter.check(client(max), now + RATE_LIMIT_WINDOW));
+    }
+}
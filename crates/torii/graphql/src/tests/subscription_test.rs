@@ -315,6 +315,122 @@ mod tests {
         rx.recv().await.unwrap();
     }
 
+    #[sqlx::test(migrations = "../migrations")]
+    #[serial]
+    async fn test_entity_subscription_with_model_and_keys(pool: SqlitePool) {
+        let (shutdown_tx, _) = broadcast::channel(1);
+
+        // dummy provider since its required to query data for erc721 tokens
+        let url: Url = "https://www.example.com".parse().unwrap();
+        let provider = Arc::new(JsonRpcClient::new(HttpTransport::new(url)));
+
+        let (mut executor, sender) =
+            Executor::new(pool.clone(), shutdown_tx.clone(), provider, 100).await.unwrap();
+        tokio::spawn(async move {
+            executor.run().await.unwrap();
+        });
+
+        let model_cache = Arc::new(ModelCache::new(pool.clone()));
+        let mut db = Sql::new(
+            pool.clone(),
+            sender,
+            &[Contract { address: Felt::ZERO, r#type: ContractType::WORLD }],
+            model_cache,
+        )
+        .await
+        .unwrap();
+
+        model_fixtures(&mut db).await;
+        // 0. Preprocess expected entity value
+        let namespace = "types_test".to_string();
+        let model_name = "Record".to_string();
+        let key = vec![Felt::ONE];
+        let entity_id = format!("{:#x}", poseidon_hash_many(&key));
+        let block_timestamp = 1710754478_u64;
+        let keys_str = key.iter().map(|k| format!("{:#x}", k)).collect::<Vec<String>>().join(",");
+        let type_name = utils::type_name_from_names(&namespace, &model_name);
+
+        let expected_value: async_graphql::Value = value!({
+            "entityUpdated": {
+                "id": entity_id,
+                "keys":vec![keys_str],
+                "models" : [{
+                    "__typename": type_name,
+                        "record_id": 0,
+                        "typeContractAddress": format!("{:#x}", Felt::ONE)
+                }]
+            }
+        });
+        let (tx, mut rx) = mpsc::channel(10);
+
+        tokio::spawn(async move {
+            // 1. Open process and sleep.Go to execute subscription
+            tokio::time::sleep(Duration::from_secs(1)).await;
+
+            // The first entity doesn't match the keys of the subscription, so only the second one
+            // is received.
+            for key in [Felt::TWO, Felt::ONE] {
+                let ty = Ty::Struct(Struct {
+                    name: get_tag(&namespace, &model_name),
+                    children: vec![
+                        Member {
+                            name: "record_id".to_string(),
+                            key: false,
+                            ty: Ty::Primitive(Primitive::U32(Some(0))),
+                        },
+                        Member {
+                            name: "typeContractAddress".to_string(),
+                            key: true,
+                            ty: Ty::Primitive(Primitive::ContractAddress(Some(key))),
+                        },
+                    ],
+                });
+
+                let keys = keys_from_ty(&ty).unwrap();
+                let keys_str = felts_to_sql_string(&keys);
+                let entity_id = poseidon_hash_many(&keys);
+                let model_id = model_id_from_ty(&ty);
+
+                db.set_entity(
+                    ty,
+                    &format!("0x{:064x}:0x{:04x}:0x{:04x}", 0, 0, 0),
+                    block_timestamp,
+                    entity_id,
+                    model_id,
+                    Some(&keys_str),
+                )
+                .await
+                .unwrap();
+                db.execute().await.unwrap();
+            }
+
+            tx.send(()).await.unwrap();
+        });
+
+        // 2. The subscription is executed and it is listening, waiting for publish() to be executed
+        let response_value = run_graphql_subscription(
+            &pool,
+            r#"subscription {
+                entityUpdated(namespace: "types_test", model: "Record", keys: ["0x1"]) {
+                    id
+                    keys
+                    models {
+                        __typename
+                        ... on types_test_Record {
+                            record_id
+                            typeContractAddress
+                        }
+                    }
+                }
+            }"#,
+        )
+        .await;
+        // 4. The subscription has received the message from publish()
+        // 5. Compare values
+        assert_eq!(expected_value, response_value);
+        rx.recv().await.unwrap();
+    }
+
     #[sqlx::test(migrations = "../migrations")]
     #[serial]
     async fn test_model_subscription(pool: SqlitePool) {
@@ -564,6 +680,84 @@ mod tests {
         rx.recv().await.unwrap();
     }
 
+    #[sqlx::test(migrations = "../migrations")]
+    #[serial]
+    async fn test_offchain_message_received(pool: SqlitePool) {
+        let (shutdown_tx, _) = broadcast::channel(1);
+
+        let url: Url = "https://www.example.com".parse().unwrap();
+        let provider = Arc::new(JsonRpcClient::new(HttpTransport::new(url)));
+        let (mut executor, sender) =
+            Executor::new(pool.clone(), shutdown_tx.clone(), provider, 100).await.unwrap();
+        tokio::spawn(async move {
+            executor.run().await.unwrap();
+        });
+
+        let model_cache = Arc::new(ModelCache::new(pool.clone()));
+        let mut db = Sql::new(
+            pool.clone(),
+            sender,
+            &[Contract { address: Felt::ZERO, r#type: ContractType::WORLD }],
+            model_cache,
+        )
+        .await
+        .unwrap();
+        let (tx, mut rx) = mpsc::channel(7);
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+
+            // a message of another model, filtered out by the subscription
+            db.store_offchain_message(
+                "0x1",
+                Felt::from_str("0xface").unwrap(),
+                Felt::ONE,
+                "ns-Position",
+                "{}",
+                &[Felt::ONE],
+                1710754478,
+            )
+            .unwrap();
+            db.store_offchain_message(
+                "0x2",
+                Felt::from_str("0xface").unwrap(),
+                Felt::TWO,
+                "ns-Message",
+                "{}",
+                &[Felt::ONE, Felt::TWO],
+                1710754478,
+            )
+            .unwrap();
+            db.execute().await.unwrap();
+
+            tx.send(()).await.unwrap();
+        });
+
+        let response_value = run_graphql_subscription(
+            &pool,
+            r#"
+                subscription {
+                    offchainMessageReceived (identity: "0xface", model: "ns-Message") {
+                        id
+                        entityId
+                        signature
+                    }
+                }
+            "#,
+        )
+        .await;
+
+        let expected_value: async_graphql::Value = value!({
+            "offchainMessageReceived": {
+                "id": "0x2",
+                "entityId": "0x2",
+                "signature": ["0x1", "0x2"]
+            }
+        });
+
+        assert_eq!(response_value, expected_value);
+        rx.recv().await.unwrap();
+    }
+
     fn keys_from_ty(ty: &Ty) -> anyhow::Result<Vec<Felt>> {
         if let Ty::Struct(s) = &ty {
             let mut keys = Vec::new();
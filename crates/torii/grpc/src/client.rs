@@ -25,7 +25,8 @@ use crate::proto::world::{
 };
 use crate::types::schema::{Entity, SchemaError};
 use crate::types::{
-    EntityKeysClause, Event, EventQuery, IndexerUpdate, ModelKeysClause, Query, Token, TokenBalance,
+    EntityKeysClause, Event, EventFilter, EventQuery, IndexerUpdate, ModelKeysClause, Query, Token,
+    TokenBalance,
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -338,12 +339,19 @@ impl WorldClient {
     pub async fn subscribe_events(
         &mut self,
         keys: Vec<EntityKeysClause>,
+        filter: EventFilter,
     ) -> Result<EventUpdateStreaming, Error> {
         let keys = keys.into_iter().map(|c| c.into()).collect();
+        let request = SubscribeEventsRequest {
+            keys,
+            selectors: filter.selectors.iter().map(|s| s.to_bytes_be().to_vec()).collect(),
+            systems: filter.systems.iter().map(|s| s.to_bytes_be().to_vec()).collect(),
+            namespaces: filter.namespaces,
+        };
 
         let stream = self
             .inner
-            .subscribe_events(SubscribeEventsRequest { keys })
+            .subscribe_events(request)
             .await
             .map_err(Error::Grpc)
             .map(|res| res.into_inner())?;
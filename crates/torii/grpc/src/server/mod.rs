@@ -70,7 +70,7 @@ use crate::proto::world::{
 };
 use crate::proto::{self};
 use crate::types::schema::SchemaError;
-use crate::types::ComparisonOperator;
+use crate::types::{ComparisonOperator, EventFilter};
 
 pub(crate) static ENTITIES_TABLE: &str = "entities";
 pub(crate) static ENTITIES_MODEL_RELATION_TABLE: &str = "entity_model";
@@ -185,7 +185,10 @@ impl DojoWorld {
             &event_message_manager,
         )));
 
-        tokio::task::spawn(subscriptions::event::Service::new(Arc::clone(&event_manager)));
+        tokio::task::spawn(subscriptions::event::Service::new(
+            Arc::clone(&event_manager),
+            Arc::clone(&model_cache),
+        ));
 
         tokio::task::spawn(subscriptions::indexer::Service::new(Arc::clone(&indexer_manager)));
 
@@ -1694,10 +1697,16 @@ impl proto::world::world_server::World for DojoWorld {
         &self,
         request: Request<proto::world::SubscribeEventsRequest>,
     ) -> ServiceResult<Self::SubscribeEventsStream> {
-        let keys = request.into_inner().keys;
+        let request = request.into_inner();
+        let filter = EventFilter {
+            selectors: request.selectors.iter().map(|s| Felt::from_bytes_be_slice(s)).collect(),
+            systems: request.systems.iter().map(|s| Felt::from_bytes_be_slice(s)).collect(),
+            namespaces: request.namespaces,
+        };
+
         let rx = self
             .event_manager
-            .add_subscriber(keys.into_iter().map(|keys| keys.into()).collect())
+            .add_subscriber(request.keys.into_iter().map(|keys| keys.into()).collect(), filter)
             .await
             .map_err(|e| Status::internal(e.to_string()))?;
 
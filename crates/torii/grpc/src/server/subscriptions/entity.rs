@@ -19,7 +19,7 @@ use torii_sqlite::simple_broker::SimpleBroker;
 use torii_sqlite::types::OptimisticEntity;
 use tracing::{error, trace};
 
-use super::match_entity_keys;
+use super::{match_entity_keys, report_subscribers};
 use crate::proto;
 use crate::proto::world::SubscribeEntityResponse;
 use crate::types::EntityKeysClause;
@@ -51,10 +51,9 @@ impl EntityManager {
         // initial subscribe call
         let _ = sender.send(Ok(SubscribeEntityResponse { entity: None, subscription_id })).await;
 
-        self.subscribers
-            .write()
-            .await
-            .insert(subscription_id, EntitiesSubscriber { clauses, sender });
+        let mut subscribers = self.subscribers.write().await;
+        subscribers.insert(subscription_id, EntitiesSubscriber { clauses, sender });
+        report_subscribers("entity", subscribers.len());
 
         Ok(receiver)
     }
@@ -73,7 +72,9 @@ impl EntityManager {
     }
 
     pub(super) async fn remove_subscriber(&self, id: u64) {
-        self.subscribers.write().await.remove(&id);
+        let mut subscribers = self.subscribers.write().await;
+        subscribers.remove(&id);
+        report_subscribers("entity", subscribers.len());
     }
 }
 
@@ -9,20 +9,22 @@ use futures::Stream;
 use futures_util::StreamExt;
 use rand::Rng;
 use starknet::core::types::Felt;
+use starknet::core::utils::get_selector_from_name;
 use tokio::sync::mpsc::{
     channel, unbounded_channel, Receiver, Sender, UnboundedReceiver, UnboundedSender,
 };
 use tokio::sync::RwLock;
+use torii_sqlite::cache::ModelCache;
 use torii_sqlite::constants::SQL_FELT_DELIMITER;
 use torii_sqlite::error::{Error, ParseError};
 use torii_sqlite::simple_broker::SimpleBroker;
 use torii_sqlite::types::Event;
 use tracing::{error, trace};
 
-use super::match_keys;
+use super::{match_keys, report_subscribers};
 use crate::proto;
 use crate::proto::world::SubscribeEventsResponse;
-use crate::types::EntityKeysClause;
+use crate::types::{EntityKeysClause, EventFilter};
 
 pub(crate) const LOG_TARGET: &str = "torii::grpc::server::subscriptions::event";
 
@@ -30,6 +32,8 @@ pub(crate) const LOG_TARGET: &str = "torii::grpc::server::subscriptions::event";
 pub struct EventSubscriber {
     /// Event keys that the subscriber is interested in
     keys: Vec<EntityKeysClause>,
+    /// The filters of the events, on top of their keys
+    filter: EventFilter,
     /// The channel to send the response back to the subscriber.
     sender: Sender<Result<proto::world::SubscribeEventsResponse, tonic::Status>>,
 }
@@ -43,6 +47,7 @@ impl EventManager {
     pub async fn add_subscriber(
         &self,
         keys: Vec<EntityKeysClause>,
+        filter: EventFilter,
     ) -> Result<Receiver<Result<proto::world::SubscribeEventsResponse, tonic::Status>>, Error> {
         let id = rand::thread_rng().gen::<usize>();
         let (sender, receiver) = channel(1);
@@ -52,13 +57,17 @@ impl EventManager {
         // initial subscribe call
         let _ = sender.send(Ok(SubscribeEventsResponse { event: None })).await;
 
-        self.subscribers.write().await.insert(id, EventSubscriber { keys, sender });
+        let mut subscribers = self.subscribers.write().await;
+        subscribers.insert(id, EventSubscriber { keys, filter, sender });
+        report_subscribers("event", subscribers.len());
 
         Ok(receiver)
     }
 
     pub(super) async fn remove_subscriber(&self, id: usize) {
-        self.subscribers.write().await.remove(&id);
+        let mut subscribers = self.subscribers.write().await;
+        subscribers.remove(&id);
+        report_subscribers("event", subscribers.len());
     }
 }
 
@@ -70,28 +79,33 @@ pub struct Service {
 }
 
 impl Service {
-    pub fn new(subs_manager: Arc<EventManager>) -> Self {
+    pub fn new(subs_manager: Arc<EventManager>, model_cache: Arc<ModelCache>) -> Self {
         let (event_sender, event_receiver) = unbounded_channel();
         let service =
             Self { simple_broker: Box::pin(SimpleBroker::<Event>::subscribe()), event_sender };
 
-        tokio::spawn(Self::publish_updates(subs_manager, event_receiver));
+        tokio::spawn(Self::publish_updates(subs_manager, model_cache, event_receiver));
 
         service
     }
 
     async fn publish_updates(
         subs: Arc<EventManager>,
+        model_cache: Arc<ModelCache>,
         mut event_receiver: UnboundedReceiver<Event>,
     ) {
         while let Some(event) = event_receiver.recv().await {
-            if let Err(e) = Self::process_event(&subs, &event).await {
+            if let Err(e) = Self::process_event(&subs, &model_cache, &event).await {
                 error!(target = LOG_TARGET, error = %e, "Processing event update.");
             }
         }
     }
 
-    async fn process_event(subs: &Arc<EventManager>, event: &Event) -> Result<(), Error> {
+    async fn process_event(
+        subs: &Arc<EventManager>,
+        model_cache: &ModelCache,
+        event: &Event,
+    ) -> Result<(), Error> {
         let mut closed_stream = Vec::new();
         let keys = event
             .keys
@@ -110,11 +124,26 @@ impl Service {
             .collect::<Result<Vec<_>, _>>()
             .map_err(ParseError::from)?;
 
+        // the namespace of the model or event the world event relates to, only looked up once a
+        // subscriber filters by namespace
+        let mut namespace = None;
+
         for (idx, sub) in subs.subscribers.read().await.iter() {
-            if !match_keys(&keys, &sub.keys) {
+            if !match_keys(&keys, &sub.keys) || !match_selector_and_system(&keys, &sub.filter) {
                 continue;
             }
 
+            if !sub.filter.namespaces.is_empty() {
+                if namespace.is_none() {
+                    namespace = Some(resource_namespace(model_cache, &keys).await);
+                }
+
+                let namespace = namespace.as_ref().and_then(Option::as_ref);
+                if !namespace.is_some_and(|ns| sub.filter.namespaces.contains(ns)) {
+                    continue;
+                }
+            }
+
             let resp = proto::world::SubscribeEventsResponse {
                 event: Some(proto::types::Event {
                     keys: keys.iter().map(|k| k.to_bytes_be().to_vec()).collect(),
@@ -140,6 +169,31 @@ impl Service {
     }
 }
 
+/// Whether the event matches the selectors and the systems of the filter.
+fn match_selector_and_system(keys: &[Felt], filter: &EventFilter) -> bool {
+    let selector = keys.first();
+    if !filter.selectors.is_empty() && !selector.is_some_and(|s| filter.selectors.contains(s)) {
+        return false;
+    }
+
+    if !filter.systems.is_empty() {
+        // the keys of an `EventEmitted` event are its selector, the selector of the Dojo event and
+        // the address of the system that emitted it
+        let event_emitted = get_selector_from_name("EventEmitted").expect("valid selector");
+        let system = keys.get(2).filter(|_| selector == Some(&event_emitted));
+        return system.is_some_and(|s| filter.systems.contains(s));
+    }
+
+    true
+}
+
+/// Returns the namespace of the model or event a world event relates to, ie the resource whose
+/// selector is the second key of the event.
+async fn resource_namespace(model_cache: &ModelCache, keys: &[Felt]) -> Option<String> {
+    let selector = keys.get(1)?;
+    model_cache.model(selector).await.ok().map(|model| model.namespace)
+}
+
 impl Future for Service {
     type Output = ();
 
@@ -18,6 +18,7 @@ use torii_sqlite::simple_broker::SimpleBroker;
 use torii_sqlite::types::ContractCursor as ContractUpdated;
 use tracing::{error, trace};
 
+use super::report_subscribers;
 use crate::proto;
 use crate::proto::world::SubscribeIndexerResponse;
 
@@ -69,13 +70,17 @@ impl IndexerManager {
                 }))
                 .await;
         }
-        self.subscribers.write().await.insert(id, IndexerSubscriber { contract_address, sender });
+        let mut subscribers = self.subscribers.write().await;
+        subscribers.insert(id, IndexerSubscriber { contract_address, sender });
+        report_subscribers("indexer", subscribers.len());
 
         Ok(receiver)
     }
 
     pub(super) async fn remove_subscriber(&self, id: usize) {
-        self.subscribers.write().await.remove(&id);
+        let mut subscribers = self.subscribers.write().await;
+        subscribers.remove(&id);
+        report_subscribers("indexer", subscribers.len());
     }
 }
 
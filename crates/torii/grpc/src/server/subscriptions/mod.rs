@@ -1,4 +1,5 @@
 use dojo_types::schema::Ty;
+use metrics::gauge;
 use starknet_crypto::{poseidon_hash_many, Felt};
 
 use crate::types::{EntityKeysClause, PatternMatching};
@@ -12,6 +13,11 @@ pub mod model_diff;
 pub mod token;
 pub mod token_balance;
 
+/// Reports the number of subscribers of a type of subscription, eg `entity`.
+pub(crate) fn report_subscribers(subscription: &'static str, count: usize) {
+    gauge!("grpc_subscribers", "subscription" => subscription).set(count as f64);
+}
+
 pub(crate) fn match_entity_keys(
     id: Felt,
     keys: &[Felt],
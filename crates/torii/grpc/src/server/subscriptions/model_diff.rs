@@ -20,6 +20,7 @@ use torii_sqlite::error::Error;
 use tracing::{debug, error, trace};
 
 use super::error::SubscriptionError;
+use super::report_subscribers;
 use crate::proto;
 use crate::proto::world::SubscribeModelsResponse;
 use crate::types::ModelKeysClause;
@@ -91,16 +92,17 @@ impl StateDiffManager {
         // initial subscribe call
         let _ = sender.send(Ok(SubscribeModelsResponse { model_update: None })).await;
 
-        self.subscribers
-            .write()
-            .await
-            .insert(id, ModelDiffSubscriber { storage_addresses, sender });
+        let mut subscribers = self.subscribers.write().await;
+        subscribers.insert(id, ModelDiffSubscriber { storage_addresses, sender });
+        report_subscribers("model_diff", subscribers.len());
 
         Ok(receiver)
     }
 
     pub(super) async fn remove_subscriber(&self, id: usize) {
-        self.subscribers.write().await.remove(&id);
+        let mut subscribers = self.subscribers.write().await;
+        subscribers.remove(&id);
+        report_subscribers("model_diff", subscribers.len());
     }
 }
 
@@ -18,6 +18,7 @@ use torii_sqlite::simple_broker::SimpleBroker;
 use torii_sqlite::types::OptimisticToken;
 use tracing::{error, trace};
 
+use super::report_subscribers;
 use crate::proto;
 use crate::proto::world::SubscribeTokensResponse;
 
@@ -52,7 +53,8 @@ impl TokenManager {
         // Send initial empty response
         let _ = sender.send(Ok(SubscribeTokensResponse { subscription_id, token: None })).await;
 
-        self.subscribers.write().await.insert(
+        let mut subscribers = self.subscribers.write().await;
+        subscribers.insert(
             subscription_id,
             TokenSubscriber {
                 contract_addresses: contract_addresses.into_iter().collect(),
@@ -60,6 +62,7 @@ impl TokenManager {
                 sender,
             },
         );
+        report_subscribers("token", subscribers.len());
 
         Ok(receiver)
     }
@@ -90,7 +93,9 @@ impl TokenManager {
     }
 
     pub(super) async fn remove_subscriber(&self, id: u64) {
-        self.subscribers.write().await.remove(&id);
+        let mut subscribers = self.subscribers.write().await;
+        subscribers.remove(&id);
+        report_subscribers("token", subscribers.len());
     }
 }
 
@@ -18,6 +18,7 @@ use torii_sqlite::simple_broker::SimpleBroker;
 use torii_sqlite::types::OptimisticTokenBalance;
 use tracing::{error, trace};
 
+use super::report_subscribers;
 use crate::proto;
 use crate::proto::world::SubscribeTokenBalancesResponse;
 
@@ -58,7 +59,8 @@ impl TokenBalanceManager {
             .send(Ok(SubscribeTokenBalancesResponse { subscription_id, balance: None }))
             .await;
 
-        self.subscribers.write().await.insert(
+        let mut subscribers = self.subscribers.write().await;
+        subscribers.insert(
             subscription_id,
             TokenBalanceSubscriber {
                 contract_addresses: contract_addresses.into_iter().collect(),
@@ -67,6 +69,7 @@ impl TokenBalanceManager {
                 sender,
             },
         );
+        report_subscribers("token_balance", subscribers.len());
 
         Ok(receiver)
     }
@@ -99,7 +102,9 @@ impl TokenBalanceManager {
     }
 
     pub(super) async fn remove_subscriber(&self, id: u64) {
-        self.subscribers.write().await.remove(&id);
+        let mut subscribers = self.subscribers.write().await;
+        subscribers.remove(&id);
+        report_subscribers("token_balance", subscribers.len());
     }
 }
 
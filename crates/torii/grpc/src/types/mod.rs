@@ -509,6 +509,19 @@ pub struct Event {
     pub transaction_hash: Felt,
 }
 
+/// The filters of an events subscription, on top of its keys. Empty filters match all the events.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Hash, Eq, Clone, Default)]
+pub struct EventFilter {
+    /// The selectors of the events, ie their first key.
+    pub selectors: Vec<Felt>,
+    /// The systems that emitted the Dojo events (`EventEmitted`). The other world events don't
+    /// match if set.
+    pub systems: Vec<Felt>,
+    /// The namespaces of the models or events the world events relate to (eg the model of a
+    /// `StoreSetRecord` event).
+    pub namespaces: Vec<String>,
+}
+
 impl From<proto::types::Event> for Event {
     fn from(value: proto::types::Event) -> Self {
         let keys = value.keys.into_iter().map(|k| Felt::from_bytes_be_slice(&k)).collect();
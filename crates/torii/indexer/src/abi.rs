@@ -0,0 +1,334 @@
+//! Decoding of the events of arbitrary contracts, using their Cairo 1 ABI.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use cainome::cairo_serde::{ByteArray, CairoSerde};
+use dojo_types::type_path::{generic_argument, split_tuple};
+use serde_json::{Map, Value};
+use starknet::core::types::contract::{
+    AbiEntry, AbiEvent, AbiNamedMember, EventField, EventFieldKind, TypedAbiEvent,
+};
+use starknet::core::types::{Event, Felt};
+use starknet::core::utils::get_selector_from_name;
+
+/// An event decoded from the ABI of the contract that emitted it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedEvent {
+    /// The name of the event, ie the name of the variant of the event enum of the contract.
+    pub name: String,
+    /// The members of the event, as a JSON object.
+    pub data: Value,
+}
+
+/// The types of the ABI of a contract needed to decode its events.
+#[derive(Debug, Clone)]
+pub struct ContractAbi {
+    /// The name of the event enum of the contract, the events emitted are its variants.
+    event: String,
+    event_structs: HashMap<String, Vec<EventField>>,
+    event_enums: HashMap<String, Vec<EventField>>,
+    structs: HashMap<String, Vec<AbiNamedMember>>,
+    enums: HashMap<String, Vec<AbiNamedMember>>,
+}
+
+impl ContractAbi {
+    /// Parses the ABI from JSON, either the ABI itself or a contract class containing it.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let abi = match serde_json::from_str::<Value>(json)? {
+            Value::Object(mut class) => match class.remove("abi") {
+                // the ABI of the flattened classes, eg as returned by the provider, is a string
+                Some(Value::String(abi)) => serde_json::from_str(&abi)?,
+                Some(abi) => serde_json::from_value(abi)?,
+                None => return Err(anyhow!("Contract class has no ABI")),
+            },
+            abi => serde_json::from_value(abi)?,
+        };
+
+        Self::new(abi)
+    }
+
+    pub fn new(abi: Vec<AbiEntry>) -> Result<Self> {
+        let mut event = None;
+        let mut event_structs = HashMap::new();
+        let mut event_enums = HashMap::new();
+        let mut structs = HashMap::new();
+        let mut enums = HashMap::new();
+
+        for entry in abi {
+            match entry {
+                AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Struct(e))) => {
+                    event_structs.insert(e.name, e.members);
+                }
+                AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Enum(e))) => {
+                    // the event enum of the contract is the last one, after the ones of its
+                    // components
+                    event = Some(e.name.clone());
+                    event_enums.insert(e.name, e.variants);
+                }
+                AbiEntry::Struct(s) => {
+                    structs.insert(s.name, s.members);
+                }
+                AbiEntry::Enum(e) => {
+                    enums.insert(e.name, e.variants);
+                }
+                _ => {}
+            }
+        }
+
+        let event = event.ok_or_else(|| anyhow!("ABI has no event enum"))?;
+        Ok(Self { event, event_structs, event_enums, structs, enums })
+    }
+
+    /// Decodes the event, `None` if it is not an event of the ABI or if its keys and data don't
+    /// match the types of its members.
+    pub fn decode(&self, event: &Event) -> Option<DecodedEvent> {
+        let mut keys = event.keys.as_slice();
+        let mut data = event.data.as_slice();
+        let decoded = self.decode_event_enum(&self.event, &mut keys, &mut data)?;
+
+        // all the keys and data must have been consumed by the members of the event
+        (keys.is_empty() && data.is_empty()).then_some(decoded)
+    }
+
+    fn decode_event_enum(
+        &self,
+        name: &str,
+        keys: &mut &[Felt],
+        data: &mut &[Felt],
+    ) -> Option<DecodedEvent> {
+        let variants = self.event_enums.get(name)?;
+
+        // the variants of the flattened enums are emitted as if they were variants of this enum
+        for variant in variants.iter().filter(|v| matches!(v.kind, EventFieldKind::Flat)) {
+            let (mut flat_keys, mut flat_data) = (*keys, *data);
+            let decoded = self.decode_event_enum(&variant.r#type, &mut flat_keys, &mut flat_data);
+            if decoded.is_some() {
+                *keys = flat_keys;
+                *data = flat_data;
+                return decoded;
+            }
+        }
+
+        let (selector, rest) = keys.split_first()?;
+        let variant = variants.iter().find(|v| {
+            !matches!(v.kind, EventFieldKind::Flat)
+                && get_selector_from_name(&v.name).is_ok_and(|s| s == *selector)
+        })?;
+        *keys = rest;
+
+        if self.event_enums.contains_key(&variant.r#type) {
+            return self.decode_event_enum(&variant.r#type, keys, data);
+        }
+
+        let members = self.event_structs.get(&variant.r#type)?;
+        let mut object = Map::new();
+        for member in members {
+            let felts = match member.kind {
+                EventFieldKind::Key => &mut *keys,
+                _ => &mut *data,
+            };
+            object.insert(member.name.clone(), self.decode_type(&member.r#type, felts)?);
+        }
+
+        Some(DecodedEvent { name: variant.name.clone(), data: Value::Object(object) })
+    }
+
+    fn decode_type(&self, r#type: &str, felts: &mut &[Felt]) -> Option<Value> {
+        if let Some(inner) = generic_argument(r#type, "core::array::Array")
+            .or_else(|| generic_argument(r#type, "core::array::Span"))
+        {
+            let len = u64::try_from(next(felts)?).ok()?;
+            let items = (0..len).map(|_| self.decode_type(inner, felts)).collect::<Option<_>>()?;
+            return Some(Value::Array(items));
+        }
+
+        if let Some(inner) = generic_argument(r#type, "core::option::Option") {
+            return match u64::try_from(next(felts)?).ok()? {
+                0 => self.decode_type(inner, felts),
+                1 => Some(Value::Null),
+                _ => None,
+            };
+        }
+
+        if let Some(members) = r#type.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+            let items = split_tuple(members)
+                .into_iter()
+                .map(|member| self.decode_type(member, felts))
+                .collect::<Option<_>>()?;
+            return Some(Value::Array(items));
+        }
+
+        if let Some(members) = self.structs.get(r#type) {
+            // u256 is a struct of the corelib, but is decoded as a single value
+            if r#type == "core::integer::u256" {
+                let (low, high) = (next(felts)?, next(felts)?);
+                let (low, high) = (u128::try_from(low).ok()?, u128::try_from(high).ok()?);
+                return Some(Value::String(match high {
+                    0 => format!("{low:#x}"),
+                    _ => format!("{high:#x}{low:032x}"),
+                }));
+            }
+
+            let mut object = Map::new();
+            for member in members {
+                object.insert(member.name.clone(), self.decode_type(&member.r#type, felts)?);
+            }
+            return Some(Value::Object(object));
+        }
+
+        if let Some(variants) = self.enums.get(r#type) {
+            let index = u64::try_from(next(felts)?).ok()?;
+            let variant = variants.get(index as usize)?;
+            if variant.r#type == "()" {
+                return Some(Value::String(variant.name.clone()));
+            }
+
+            let value = self.decode_type(&variant.r#type, felts)?;
+            return Some(Value::Object(Map::from_iter([(variant.name.clone(), value)])));
+        }
+
+        decode_primitive(r#type, felts)
+    }
+}
+
+fn decode_primitive(r#type: &str, felts: &mut &[Felt]) -> Option<Value> {
+    if r#type == "core::byte_array::ByteArray" {
+        let byte_array = ByteArray::cairo_deserialize(felts, 0).ok()?;
+        *felts = felts.get(ByteArray::cairo_serialized_size(&byte_array)..)?;
+        return byte_array.to_string().ok().map(Value::String);
+    }
+
+    let felt = next(felts)?;
+    let value = match r#type.rsplit("::").next()? {
+        "felt252" | "ContractAddress" | "ClassHash" | "EthAddress" | "bytes31" | "u128" => {
+            Value::String(format!("{felt:#x}"))
+        }
+        "bool" => Value::Bool(felt != Felt::ZERO),
+        "u8" | "u16" | "u32" | "u64" | "usize" => Value::from(u64::try_from(felt).ok()?),
+        // the negative integers are represented as `P - |value|`
+        "i8" | "i16" | "i32" | "i64" => match u64::try_from(felt) {
+            Ok(value) => Value::from(value as i64),
+            Err(_) => Value::from(-(u64::try_from(-felt).ok()? as i64)),
+        },
+        "i128" => match u128::try_from(felt) {
+            Ok(value) => Value::String(value.to_string()),
+            Err(_) => Value::String(format!("-{}", u128::try_from(-felt).ok()?)),
+        },
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+fn next(felts: &mut &[Felt]) -> Option<Felt> {
+    let (first, rest) = felts.split_first()?;
+    *felts = rest;
+    Some(*first)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use starknet::macros::felt;
+
+    use super::*;
+
+    const ABI: &str = r#"[
+        {
+            "type": "struct",
+            "name": "core::integer::u256",
+            "members": [
+                { "name": "low", "type": "core::integer::u128" },
+                { "name": "high", "type": "core::integer::u128" }
+            ]
+        },
+        {
+            "type": "event",
+            "name": "game::Moved",
+            "kind": "struct",
+            "members": [
+                {
+                    "name": "player",
+                    "type": "core::starknet::contract_address::ContractAddress",
+                    "kind": "key"
+                },
+                {
+                    "name": "steps",
+                    "type": "core::array::Span::<core::integer::u8>",
+                    "kind": "data"
+                },
+                { "name": "score", "type": "core::integer::u256", "kind": "data" }
+            ]
+        },
+        {
+            "type": "event",
+            "name": "token::Component::Transfer",
+            "kind": "struct",
+            "members": [
+                { "name": "to", "type": "core::felt252", "kind": "key" },
+                { "name": "positive", "type": "core::bool", "kind": "data" }
+            ]
+        },
+        {
+            "type": "event",
+            "name": "token::Component::Event",
+            "kind": "enum",
+            "variants": [
+                {
+                    "name": "Transfer",
+                    "type": "token::Component::Transfer",
+                    "kind": "nested"
+                }
+            ]
+        },
+        {
+            "type": "event",
+            "name": "game::Event",
+            "kind": "enum",
+            "variants": [
+                { "name": "Moved", "type": "game::Moved", "kind": "nested" },
+                {
+                    "name": "TokenEvent",
+                    "type": "token::Component::Event",
+                    "kind": "flat"
+                }
+            ]
+        }
+    ]"#;
+
+    #[test]
+    fn decode_events() {
+        let abi = ContractAbi::from_json(ABI).unwrap();
+
+        let moved = Event {
+            from_address: felt!("0x1"),
+            keys: vec![selector("Moved"), felt!("0x123")],
+            data: vec![felt!("0x2"), felt!("0x3"), felt!("0x4"), felt!("0x1"), felt!("0x1")],
+        };
+        let decoded = abi.decode(&moved).unwrap();
+        assert_eq!(decoded.name, "Moved");
+        assert_eq!(
+            decoded.data,
+            json!({ "player": "0x123", "steps": [3, 4], "score": format!("0x1{:032x}", 1) })
+        );
+
+        // the events of the flattened components have the selector of their own variant
+        let transfer = Event {
+            from_address: felt!("0x1"),
+            keys: vec![selector("Transfer"), felt!("0x5")],
+            data: vec![felt!("0x1")],
+        };
+        let decoded = abi.decode(&transfer).unwrap();
+        assert_eq!(decoded.name, "Transfer");
+        assert_eq!(decoded.data, json!({ "to": "0x5", "positive": true }));
+
+        // missing data
+        let invalid = Event { data: vec![], ..transfer };
+        assert_eq!(abi.decode(&invalid), None);
+    }
+
+    fn selector(name: &str) -> Felt {
+        get_selector_from_name(name).unwrap()
+    }
+}
@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -5,29 +6,31 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Result;
-use async_recursion::async_recursion;
 use bitflags::bitflags;
 use dojo_utils::provider as provider_utils;
 use dojo_world::contracts::world::WorldContractReader;
 use hashlink::LinkedHashMap;
-use starknet::core::types::requests::{GetBlockWithTxHashesRequest, GetEventsRequest};
 use starknet::core::types::{
-    BlockHashAndNumber, BlockId, BlockTag, EmittedEvent, Event, EventFilter, EventFilterWithPage,
-    MaybePendingBlockWithReceipts, MaybePendingBlockWithTxHashes, PendingBlockWithReceipts,
-    ResultPageRequest, Transaction, TransactionReceipt, TransactionWithReceipt,
+    BlockHashAndNumber, BlockId, BlockTag, Event, MaybePendingBlockWithReceipts,
+    MaybePendingBlockWithTxHashes, PendingBlockWithReceipts, Transaction, TransactionReceipt,
+    TransactionWithReceipt,
 };
 use starknet::core::utils::get_selector_from_name;
-use starknet::providers::{Provider, ProviderRequestData, ProviderResponseData};
+use starknet::providers::Provider;
 use starknet_crypto::Felt;
 use tokio::sync::broadcast::Sender;
 use tokio::sync::mpsc::Sender as BoundedSender;
+use tokio::sync::watch;
 use tokio::time::{sleep, Instant};
 use torii_sqlite::cache::ContractClassCache;
-use torii_sqlite::types::{Contract, ContractType};
+use torii_sqlite::types::{Contract, ContractType, SyncStatus};
 use torii_sqlite::{Cursors, Sql};
 use tracing::{debug, error, info, trace, warn};
 
 use crate::constants::LOG_TARGET;
+use crate::fetcher::{self, RangeDecoder, RangeFetcher, RangePipeline};
+use crate::metrics::EngineMetrics;
+use crate::processors::abi_event::AbiEventProcessor;
 use crate::processors::controller::ControllerProcessor;
 use crate::processors::erc1155_transfer_batch::Erc1155TransferBatchProcessor;
 use crate::processors::erc1155_transfer_single::Erc1155TransferSingleProcessor;
@@ -61,6 +64,8 @@ pub struct Processors<P: Provider + Send + Sync + std::fmt::Debug + 'static> {
     pub block: Vec<Box<dyn BlockProcessor<P>>>,
     pub transaction: Vec<Box<dyn TransactionProcessor<P>>>,
     pub catch_all_event: Box<dyn EventProcessor<P>>,
+    /// The processor of all the events of the contracts indexed using their ABI.
+    pub abi_event: Box<dyn EventProcessor<P>>,
     pub event_processors: HashMap<ContractType, EventProcessorMap<P>>,
 }
 
@@ -72,6 +77,7 @@ impl<P: Provider + Send + Sync + std::fmt::Debug + 'static> Default for Processo
             // We shouldn't have a catch all for now since the world doesn't forward raw events
             // anymore.
             catch_all_event: Box::new(RawEventProcessor) as Box<dyn EventProcessor<P>>,
+            abi_event: Box::new(AbiEventProcessor) as Box<dyn EventProcessor<P>>,
             event_processors: Self::initialize_event_processors(),
         }
     }
@@ -168,6 +174,9 @@ pub struct EngineConfig {
     pub flags: IndexingFlags,
     pub event_processor_config: EventProcessorConfig,
     pub world_block: u64,
+    /// The number of ranges of blocks fetched and decoded ahead of their processing while syncing.
+    /// The ranges are fetched one at a time, after their processing, if `0`.
+    pub prefetch_ranges: usize,
 }
 
 impl Default for EngineConfig {
@@ -180,6 +189,7 @@ impl Default for EngineConfig {
             flags: IndexingFlags::empty(),
             event_processor_config: EventProcessorConfig::default(),
             world_block: 0,
+            prefetch_ranges: 2,
         }
     }
 }
@@ -188,6 +198,9 @@ impl Default for EngineConfig {
 pub enum FetchDataResult {
     Range(FetchRangeResult),
     Pending(FetchPendingResult),
+    /// The chain has been reorganized, the indexed data must be rolled back to the last block that
+    /// is still part of the chain. `None` if none of the indexed blocks are.
+    Reorg(Option<u64>),
     None,
 }
 
@@ -196,6 +209,7 @@ impl FetchDataResult {
         match self {
             FetchDataResult::Range(range) => Some(BlockId::Number(range.latest_block_number)),
             FetchDataResult::Pending(_pending) => Some(BlockId::Tag(BlockTag::Pending)),
+            FetchDataResult::Reorg(block) => Some(BlockId::Number(block.unwrap_or_default())),
             // we dont require block_id when result is none, we return None
             FetchDataResult::None => None,
         }
@@ -204,13 +218,30 @@ impl FetchDataResult {
 
 #[derive(Debug)]
 pub struct FetchRangeResult {
-    // (block_number, transaction_hash) -> events
+    // (block_number, transaction_hash) -> transaction
     // NOTE: LinkedList might contains blocks in different order
-    pub transactions: BTreeMap<u64, LinkedHashMap<Felt, Vec<EmittedEvent>>>,
+    pub transactions: BTreeMap<u64, LinkedHashMap<Felt, RangeTransaction>>,
     pub blocks: BTreeMap<u64, u64>,
+    // block_number -> block_hash, only for the blocks that are not pending
+    pub block_hashes: BTreeMap<u64, Felt>,
     pub latest_block_number: u64,
 }
 
+/// A transaction of a fetched range, with its events emitted by the indexed contracts.
+#[derive(Debug)]
+pub struct RangeTransaction {
+    pub events: Vec<RangeEvent>,
+    /// The transaction itself, only fetched if the transactions are indexed.
+    pub transaction: Option<Transaction>,
+}
+
+#[derive(Debug)]
+pub struct RangeEvent {
+    pub event_id: String,
+    pub event: Event,
+    pub contract_type: ContractType,
+}
+
 #[derive(Debug)]
 pub struct FetchPendingResult {
     pub pending_block: Box<PendingBlockWithReceipts>,
@@ -230,6 +261,11 @@ pub struct Engine<P: Provider + Send + Sync + std::fmt::Debug + 'static> {
     task_manager: TaskManager<P>,
     contracts: Arc<HashMap<Felt, ContractType>>,
     contract_class_cache: Arc<ContractClassCache<P>>,
+    fetcher: RangeFetcher<P>,
+    decoder: RangeDecoder<P>,
+    pipeline: Option<RangePipeline>,
+    sync_status: watch::Sender<SyncStatus>,
+    metrics: EngineMetrics,
 }
 
 struct UnprocessedEvent {
@@ -257,6 +293,14 @@ impl<P: Provider + Send + Sync + std::fmt::Debug + 'static> Engine<P> {
         let max_concurrent_tasks = config.max_concurrent_tasks;
         let event_processor_config = config.event_processor_config.clone();
         let provider = Arc::new(provider);
+        let fetcher =
+            RangeFetcher::new(provider.clone(), contracts.clone(), config.events_chunk_size);
+        let decoder = RangeDecoder::new(
+            provider.clone(),
+            contracts.clone(),
+            config.flags.contains(IndexingFlags::TRANSACTIONS),
+            max_concurrent_tasks,
+        );
 
         Self {
             world: world.clone(),
@@ -275,9 +319,20 @@ impl<P: Provider + Send + Sync + std::fmt::Debug + 'static> Engine<P> {
                 event_processor_config,
             ),
             contract_class_cache: Arc::new(ContractClassCache::new(provider)),
+            fetcher,
+            decoder,
+            pipeline: None,
+            sync_status: watch::Sender::new(SyncStatus::default()),
+            metrics: EngineMetrics::default(),
         }
     }
 
+    /// Returns a receiver of the status of the synchronization with the chain, updated every time
+    /// the chain is polled.
+    pub fn sync_status(&self) -> watch::Receiver<SyncStatus> {
+        self.sync_status.subscribe()
+    }
+
     pub async fn start(&mut self) -> Result<()> {
         if let Err(e) = provider_utils::health_check_provider(self.provider.clone()).await {
             error!(target: LOG_TARGET,"Provider health check failed during engine start");
@@ -309,6 +364,8 @@ impl<P: Provider + Send + Sync + std::fmt::Debug + 'static> Engine<P> {
                             let block_id = fetch_result.block_id();
                             match self.process(fetch_result).await {
                                 Ok(_) => {
+                                    let elapsed = instant.elapsed().as_secs_f64();
+                                    self.metrics.process_seconds.record(elapsed);
                                     // Its only `None` when `FetchDataResult::None` in which case
                                     // we don't need to flush or apply cache diff
                                     if let Some(block_id) = block_id {
@@ -349,17 +406,39 @@ impl<P: Provider + Send + Sync + std::fmt::Debug + 'static> Engine<P> {
     // TODO: since we now process blocks in chunks we can parallelize the fetching of data
     pub async fn fetch_data(&mut self, cursors: &Cursors) -> Result<FetchDataResult> {
         let latest_block = self.provider.block_hash_and_number().await?;
+        self.report_sync_status(cursors.head, latest_block.block_number);
+
+        if let Some(head) = cursors.head {
+            if let Some(block) = self.find_reorg(head, &latest_block).await? {
+                self.pipeline = None;
+                warn!(target: LOG_TARGET, head = %head, block = ?block, "Chain reorganization detected, rolling back.");
+                return Ok(FetchDataResult::Reorg(block));
+            }
+        }
 
         let from = cursors.head.unwrap_or(self.config.world_block);
-        let total_remaining_blocks = latest_block.block_number - from;
+        let total_remaining_blocks = latest_block.block_number.saturating_sub(from);
         let blocks_to_process = total_remaining_blocks.min(self.config.blocks_chunk_size);
         let to = (from + blocks_to_process).min(latest_block.block_number);
 
         let instant = Instant::now();
         let result = if from < latest_block.block_number {
             let from = if from == 0 { from } else { from + 1 };
-            let data = self.fetch_range(from, to, &cursors.cursor_map).await?;
+            let data = match fetcher::pipelined_range(&mut self.pipeline, from).await {
+                Some(data) => data?,
+                None => self.fetch_range(from, to, &cursors.cursor_map).await?,
+            };
+            let to = data.latest_block_number;
             debug!(target: LOG_TARGET, duration = ?instant.elapsed(), from = %from, to = %to, "Fetched data for range.");
+
+            // keep fetching and decoding the next ranges while this one is processed
+            if self.pipeline.is_none()
+                && self.config.prefetch_ranges > 0
+                && to < latest_block.block_number
+            {
+                self.pipeline = Some(self.spawn_pipeline(to, latest_block.block_number));
+            }
+
             FetchDataResult::Range(data)
         } else if self.config.flags.contains(IndexingFlags::PENDING_BLOCKS) {
             let data =
@@ -377,168 +456,103 @@ impl<P: Provider + Send + Sync + std::fmt::Debug + 'static> Engine<P> {
         Ok(result)
     }
 
-    pub async fn fetch_range(
-        &mut self,
-        from: u64,
-        to: u64,
-        cursor_map: &HashMap<Felt, Felt>,
-    ) -> Result<FetchRangeResult> {
-        let mut events = vec![];
-
-        // Create initial batch requests for all contracts
-        let mut event_requests = Vec::new();
-        for (contract_address, _) in self.contracts.iter() {
-            let events_filter = EventFilter {
-                from_block: Some(BlockId::Number(from)),
-                to_block: Some(BlockId::Number(to)),
-                address: Some(*contract_address),
-                keys: None,
-            };
-
-            event_requests.push((
-                *contract_address,
-                ProviderRequestData::GetEvents(GetEventsRequest {
-                    filter: EventFilterWithPage {
-                        event_filter: events_filter,
-                        result_page_request: ResultPageRequest {
-                            continuation_token: None,
-                            chunk_size: self.config.events_chunk_size,
-                        },
-                    },
-                }),
-            ));
-        }
-
-        // Recursively fetch all events using batch requests
-        events.extend(self.fetch_events_recursive(event_requests, cursor_map).await?);
-
-        // Process events to get unique blocks and transactions
-        let mut blocks = BTreeMap::new();
-        let mut transactions = BTreeMap::new();
-        let mut block_numbers = HashSet::new();
-
-        for event in events {
-            let block_number = match event.block_number {
-                Some(block_number) => block_number,
-                None => unreachable!("In fetch range all events should have block number"),
-            };
-
-            block_numbers.insert(block_number);
+    fn report_sync_status(&self, head: Option<u64>, latest_block: u64) {
+        let status = SyncStatus {
+            head,
+            latest_block: Some(latest_block),
+            updated_at: Some(std::time::Instant::now()),
+        };
 
-            transactions
-                .entry(block_number)
-                .or_insert(LinkedHashMap::new())
-                .entry(event.transaction_hash)
-                .or_insert(vec![])
-                .push(event);
-        }
+        self.metrics.head.set(head.unwrap_or_default() as f64);
+        self.metrics.latest_block.set(latest_block as f64);
+        self.metrics.head_lag.set(status.head_lag().unwrap_or_default() as f64);
+        self.sync_status.send_replace(status);
+    }
 
-        // Always ensure the latest block number is included
-        block_numbers.insert(to);
+    /// Checks whether the indexed blocks up to `head` are still part of the chain. If not, returns
+    /// the block to roll back to, ie the most recent indexed block that is still part of the chain,
+    /// or `None` if none of them are.
+    async fn find_reorg(
+        &self,
+        head: u64,
+        latest_block: &BlockHashAndNumber,
+    ) -> Result<Option<Option<u64>>> {
+        // the chain is unchanged if the most recent indexed block is still part of it
+        let Some((block_number, block_hash)) = self.db.block_hashes(head, Some(1)).await?.pop()
+        else {
+            // without any tracked block (ie indexed before the hashes were stored), only a chain
+            // shorter than the indexed one can be detected
+            return Ok((head > latest_block.block_number).then_some(None));
+        };
 
-        // Batch request block timestamps
-        let mut timestamp_requests = Vec::new();
-        for block_number in &block_numbers {
-            timestamp_requests.push(ProviderRequestData::GetBlockWithTxHashes(
-                GetBlockWithTxHashesRequest { block_id: BlockId::Number(*block_number) },
-            ));
+        if self.is_canonical(block_number, block_hash, latest_block).await? {
+            return Ok(None);
         }
 
-        // Execute timestamp requests in batch
-        if !timestamp_requests.is_empty() {
-            let timestamp_results = self.provider.batch_requests(timestamp_requests).await?;
-
-            // Process timestamp results
-            for (block_number, result) in block_numbers.iter().zip(timestamp_results) {
-                match result {
-                    ProviderResponseData::GetBlockWithTxHashes(block) => {
-                        let timestamp = match block {
-                            MaybePendingBlockWithTxHashes::Block(block) => block.timestamp,
-                            MaybePendingBlockWithTxHashes::PendingBlock(block) => block.timestamp,
-                        };
-                        blocks.insert(*block_number, timestamp);
-                    }
-                    _ => {
-                        error!(target: LOG_TARGET, "Unexpected response type from batch timestamp request");
-                        return Err(anyhow::anyhow!(
-                            "Unexpected response type from batch timestamp request"
-                        ));
-                    }
-                }
+        let indexed_blocks = self.db.block_hashes(head, None).await?;
+        for (block_number, block_hash) in indexed_blocks.into_iter().skip(1) {
+            if self.is_canonical(block_number, block_hash, latest_block).await? {
+                return Ok(Some(Some(block_number)));
             }
         }
 
-        debug!("Transactions: {}", &transactions.len());
-        debug!("Blocks: {}", &blocks.len());
-
-        Ok(FetchRangeResult { transactions, blocks, latest_block_number: to })
+        Ok(Some(None))
     }
 
-    #[async_recursion]
-    async fn fetch_events_recursive(
+    /// Whether the block `block_number` with the hash `block_hash` is part of the chain ending at
+    /// `latest_block`.
+    async fn is_canonical(
         &self,
-        requests: Vec<(Felt, ProviderRequestData)>,
-        cursor_map: &HashMap<Felt, Felt>,
-    ) -> Result<Vec<EmittedEvent>> {
-        if requests.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        let mut events = Vec::new();
-        let mut next_requests = Vec::new();
-
-        // Extract just the requests without the contract addresses
-        let batch_requests: Vec<ProviderRequestData> =
-            requests.iter().map(|(_, req)| req.clone()).collect();
-        let batch_results = self.provider.batch_requests(batch_requests).await?;
-
-        // Process results and prepare next batch of requests if needed
-        for ((contract_address, original_request), result) in
-            requests.into_iter().zip(batch_results)
-        {
-            match result {
-                ProviderResponseData::GetEvents(events_page) => {
-                    // Process events for this page
-                    for event in events_page.events {
-                        let last_contract_tx = cursor_map.get(&contract_address).cloned();
-
-                        // Skip if we haven't reached the last processed transaction
-                        if let Some(last_tx) = last_contract_tx {
-                            if event.transaction_hash == last_tx {
-                                continue;
-                            }
-                        }
+        block_number: u64,
+        block_hash: Felt,
+        latest_block: &BlockHashAndNumber,
+    ) -> Result<bool> {
+        Ok(match block_number.cmp(&latest_block.block_number) {
+            Ordering::Equal => latest_block.block_hash == block_hash,
+            Ordering::Less => self.block_hash(block_number).await? == Some(block_hash),
+            Ordering::Greater => false,
+        })
+    }
 
-                        events.push(event);
-                    }
-
-                    // If there's a continuation token, prepare next request
-                    if let Some(continuation_token) = events_page.continuation_token {
-                        if let ProviderRequestData::GetEvents(mut next_request) = original_request {
-                            next_request.filter.result_page_request.continuation_token =
-                                Some(continuation_token);
-                            next_requests.push((
-                                contract_address,
-                                ProviderRequestData::GetEvents(next_request),
-                            ));
-                        }
-                    }
-                }
-                _ => {
-                    error!(target: LOG_TARGET, "Unexpected response type from batch events request");
-                    return Err(anyhow::anyhow!(
-                        "Unexpected response type from batch events request"
-                    ));
-                }
-            }
+    async fn block_hash(&self, block_number: u64) -> Result<Option<Felt>> {
+        let block = self.provider.get_block_with_tx_hashes(BlockId::Number(block_number)).await?;
+        match block {
+            MaybePendingBlockWithTxHashes::Block(block) => Ok(Some(block.block_hash)),
+            MaybePendingBlockWithTxHashes::PendingBlock(_) => Ok(None),
         }
+    }
 
-        // Recursively fetch next batch if there are any continuation tokens
-        if !next_requests.is_empty() {
-            events.extend(self.fetch_events_recursive(next_requests, cursor_map).await?);
-        }
+    /// Spawns the fetching and the decoding of the ranges following `head`, up to `latest_block`.
+    fn spawn_pipeline(&self, head: u64, latest_block: u64) -> RangePipeline {
+        let fetcher = self.fetcher.clone();
+        let decoder = self.decoder.clone();
+
+        RangePipeline::spawn(
+            move |from, to| {
+                let fetcher = fetcher.clone();
+                // the cursors of the pending block are reset once a range is processed, so none of
+                // the transactions of the following ranges have been processed yet
+                async move { fetcher.fetch_range(from, to, &HashMap::new()).await }
+            },
+            move |range| {
+                let decoder = decoder.clone();
+                async move { decoder.decode(range).await }
+            },
+            head,
+            latest_block,
+            self.config.blocks_chunk_size,
+            self.config.prefetch_ranges,
+        )
+    }
 
-        Ok(events)
+    pub async fn fetch_range(
+        &mut self,
+        from: u64,
+        to: u64,
+        cursor_map: &HashMap<Felt, Felt>,
+    ) -> Result<FetchRangeResult> {
+        let range = self.fetcher.fetch_range(from, to, cursor_map).await?;
+        self.decoder.decode(range).await
     }
 
     async fn fetch_pending(
@@ -574,6 +588,7 @@ impl<P: Provider + Send + Sync + std::fmt::Debug + 'static> Engine<P> {
         match fetch_result {
             FetchDataResult::Range(data) => self.process_range(data).await?,
             FetchDataResult::Pending(data) => self.process_pending(data).await?,
+            FetchDataResult::Reorg(block) => self.db.rollback_to_block(block).await?,
             FetchDataResult::None => {}
         };
 
@@ -631,21 +646,13 @@ impl<P: Provider + Send + Sync + std::fmt::Debug + 'static> Engine<P> {
         let mut processed_blocks = HashSet::new();
         let mut cursor_map = HashMap::new();
         for (block_number, transactions) in data.transactions {
-            for (transaction_hash, events) in transactions {
+            for (transaction_hash, transaction) in transactions {
                 debug!("Processing transaction hash: {:#x}", transaction_hash);
-                // Process transaction
-                let transaction = if self.config.flags.contains(IndexingFlags::TRANSACTIONS) {
-                    Some(self.provider.get_transaction_by_hash(transaction_hash).await?)
-                } else {
-                    None
-                };
-
                 self.process_transaction_with_events(
                     transaction_hash,
-                    events.as_slice(),
+                    transaction,
                     block_number,
                     data.blocks[&block_number],
-                    transaction,
                     &mut cursor_map,
                 )
                 .await?;
@@ -665,6 +672,10 @@ impl<P: Provider + Send + Sync + std::fmt::Debug + 'static> Engine<P> {
         // Process parallelized events
         self.task_manager.process_tasks().await?;
 
+        for (block_number, block_hash) in data.block_hashes {
+            self.db.set_block_hash(block_number, block_hash)?;
+        }
+
         let last_block_timestamp = data.blocks[&data.latest_block_number];
         self.db.update_cursors(data.latest_block_number, last_block_timestamp, None, cursor_map)?;
 
@@ -674,39 +685,23 @@ impl<P: Provider + Send + Sync + std::fmt::Debug + 'static> Engine<P> {
     async fn process_transaction_with_events(
         &mut self,
         transaction_hash: Felt,
-        events: &[EmittedEvent],
+        transaction: RangeTransaction,
         block_number: u64,
         block_timestamp: u64,
-        transaction: Option<Transaction>,
         cursor_map: &mut HashMap<Felt, (Felt, u64)>,
     ) -> Result<()> {
         let mut unique_contracts = HashSet::new();
         // Contract -> Cursor
-        for (event_idx, event) in events.iter().enumerate() {
-            // NOTE: erc* processors expect the event_id to be in this format to get
-            // transaction_hash:
-            let event_id =
-                format!("{:#064x}:{:#x}:{:#04x}", block_number, transaction_hash, event_idx);
-
-            let event = Event {
-                from_address: event.from_address,
-                keys: event.keys.clone(),
-                data: event.data.clone(),
-            };
-
-            let Some(&contract_type) = self.contracts.get(&event.from_address) else {
-                continue;
-            };
-
+        for RangeEvent { event_id, event, contract_type } in &transaction.events {
             unique_contracts.insert(event.from_address);
 
             self.process_event(
                 block_number,
                 block_timestamp,
-                &event_id,
-                &event,
+                event_id,
+                event,
                 transaction_hash,
-                contract_type,
+                *contract_type,
             )
             .await?;
         }
@@ -717,7 +712,7 @@ impl<P: Provider + Send + Sync + std::fmt::Debug + 'static> Engine<P> {
             entry.1 += 1;
         }
 
-        if let Some(ref transaction) = transaction {
+        if let Some(ref transaction) = transaction.transaction {
             Self::process_transaction(
                 self,
                 block_number,
@@ -844,10 +839,35 @@ impl<P: Provider + Send + Sync + std::fmt::Debug + 'static> Engine<P> {
         transaction_hash: Felt,
         contract_type: ContractType,
     ) -> Result<()> {
+        self.metrics.events_processed_total.increment(1);
+
         if self.config.flags.contains(IndexingFlags::RAW_EVENTS) {
             self.db.store_event(event_id, event, transaction_hash, block_timestamp)?;
         }
 
+        // the events of the contracts indexed using their ABI don't have a processor per event,
+        // they are all decoded from the ABI
+        if contract_type == ContractType::ABI {
+            if let Err(e) = self
+                .processors
+                .abi_event
+                .process(
+                    &self.world,
+                    &mut self.db,
+                    block_number,
+                    block_timestamp,
+                    event_id,
+                    event,
+                    &self.config.event_processor_config,
+                )
+                .await
+            {
+                error!(target: LOG_TARGET, error = %e, "Processing ABI event.");
+            }
+
+            return Ok(());
+        }
+
         let event_key = event.keys[0];
 
         let processors = self.processors.get_event_processor(contract_type);
@@ -0,0 +1,565 @@
+//! Fetching and decoding of the ranges of blocks to index.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_recursion::async_recursion;
+use futures_util::{stream, StreamExt, TryStreamExt};
+use hashlink::LinkedHashMap;
+use starknet::core::types::requests::{GetBlockWithTxHashesRequest, GetEventsRequest};
+use starknet::core::types::{
+    BlockId, EmittedEvent, Event, EventFilter, EventFilterWithPage, MaybePendingBlockWithTxHashes,
+    ResultPageRequest,
+};
+use starknet::providers::{Provider, ProviderRequestData, ProviderResponseData};
+use starknet_crypto::Felt;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use torii_sqlite::types::ContractType;
+use tracing::{debug, error};
+
+use crate::constants::LOG_TARGET;
+use crate::engine::{FetchRangeResult, RangeEvent, RangeTransaction};
+
+/// The events of a range of blocks, and the blocks they were emitted in, as fetched from the
+/// provider.
+#[derive(Debug)]
+pub(crate) struct FetchedRange {
+    // block_number -> transaction_hash -> events
+    pub(crate) transactions: BTreeMap<u64, LinkedHashMap<Felt, Vec<EmittedEvent>>>,
+    pub(crate) blocks: BTreeMap<u64, u64>,
+    // block_number -> block_hash, only for the blocks that are not pending
+    pub(crate) block_hashes: BTreeMap<u64, Felt>,
+    pub(crate) latest_block_number: u64,
+}
+
+/// Fetches the events of the indexed contracts, and the blocks they were emitted in, for a range of
+/// blocks.
+#[derive(Debug)]
+pub(crate) struct RangeFetcher<P: Provider + Send + Sync + std::fmt::Debug + 'static> {
+    provider: Arc<P>,
+    contracts: Arc<HashMap<Felt, ContractType>>,
+    events_chunk_size: u64,
+}
+
+impl<P: Provider + Send + Sync + std::fmt::Debug + 'static> Clone for RangeFetcher<P> {
+    fn clone(&self) -> Self {
+        Self {
+            provider: Arc::clone(&self.provider),
+            contracts: Arc::clone(&self.contracts),
+            events_chunk_size: self.events_chunk_size,
+        }
+    }
+}
+
+impl<P: Provider + Send + Sync + std::fmt::Debug + 'static> RangeFetcher<P> {
+    pub(crate) fn new(
+        provider: Arc<P>,
+        contracts: Arc<HashMap<Felt, ContractType>>,
+        events_chunk_size: u64,
+    ) -> Self {
+        Self { provider, contracts, events_chunk_size }
+    }
+
+    pub(crate) async fn fetch_range(
+        &self,
+        from: u64,
+        to: u64,
+        cursor_map: &HashMap<Felt, Felt>,
+    ) -> Result<FetchedRange> {
+        let mut events = vec![];
+
+        // Create initial batch requests for all contracts
+        let mut event_requests = Vec::new();
+        for (contract_address, _) in self.contracts.iter() {
+            let events_filter = EventFilter {
+                from_block: Some(BlockId::Number(from)),
+                to_block: Some(BlockId::Number(to)),
+                address: Some(*contract_address),
+                keys: None,
+            };
+
+            event_requests.push((
+                *contract_address,
+                ProviderRequestData::GetEvents(GetEventsRequest {
+                    filter: EventFilterWithPage {
+                        event_filter: events_filter,
+                        result_page_request: ResultPageRequest {
+                            continuation_token: None,
+                            chunk_size: self.events_chunk_size,
+                        },
+                    },
+                }),
+            ));
+        }
+
+        // Recursively fetch all events using batch requests
+        events.extend(self.fetch_events_recursive(event_requests, cursor_map).await?);
+
+        // Process events to get unique blocks and transactions
+        let mut blocks = BTreeMap::new();
+        let mut block_hashes = BTreeMap::new();
+        let mut transactions = BTreeMap::new();
+        let mut block_numbers = HashSet::new();
+
+        for event in events {
+            let block_number = match event.block_number {
+                Some(block_number) => block_number,
+                None => unreachable!("In fetch range all events should have block number"),
+            };
+
+            block_numbers.insert(block_number);
+
+            transactions
+                .entry(block_number)
+                .or_insert(LinkedHashMap::new())
+                .entry(event.transaction_hash)
+                .or_insert(vec![])
+                .push(event);
+        }
+
+        // Always ensure the latest block number is included
+        block_numbers.insert(to);
+
+        // Batch request block timestamps
+        let mut timestamp_requests = Vec::new();
+        for block_number in &block_numbers {
+            timestamp_requests.push(ProviderRequestData::GetBlockWithTxHashes(
+                GetBlockWithTxHashesRequest { block_id: BlockId::Number(*block_number) },
+            ));
+        }
+
+        // Execute timestamp requests in batch
+        if !timestamp_requests.is_empty() {
+            let timestamp_results = self.provider.batch_requests(timestamp_requests).await?;
+
+            // Process timestamp results
+            for (block_number, result) in block_numbers.iter().zip(timestamp_results) {
+                match result {
+                    ProviderResponseData::GetBlockWithTxHashes(block) => {
+                        let timestamp = match block {
+                            MaybePendingBlockWithTxHashes::Block(block) => {
+                                block_hashes.insert(*block_number, block.block_hash);
+                                block.timestamp
+                            }
+                            MaybePendingBlockWithTxHashes::PendingBlock(block) => block.timestamp,
+                        };
+                        blocks.insert(*block_number, timestamp);
+                    }
+                    _ => {
+                        error!(target: LOG_TARGET, "Unexpected response type from batch timestamp request");
+                        return Err(anyhow::anyhow!(
+                            "Unexpected response type from batch timestamp request"
+                        ));
+                    }
+                }
+            }
+        }
+
+        debug!("Transactions: {}", &transactions.len());
+        debug!("Blocks: {}", &blocks.len());
+
+        Ok(FetchedRange { transactions, blocks, block_hashes, latest_block_number: to })
+    }
+
+    #[async_recursion]
+    async fn fetch_events_recursive(
+        &self,
+        requests: Vec<(Felt, ProviderRequestData)>,
+        cursor_map: &HashMap<Felt, Felt>,
+    ) -> Result<Vec<EmittedEvent>> {
+        if requests.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut events = Vec::new();
+        let mut next_requests = Vec::new();
+
+        // Extract just the requests without the contract addresses
+        let batch_requests: Vec<ProviderRequestData> =
+            requests.iter().map(|(_, req)| req.clone()).collect();
+        let batch_results = self.provider.batch_requests(batch_requests).await?;
+
+        // Process results and prepare next batch of requests if needed
+        for ((contract_address, original_request), result) in
+            requests.into_iter().zip(batch_results)
+        {
+            match result {
+                ProviderResponseData::GetEvents(events_page) => {
+                    // Process events for this page
+                    for event in events_page.events {
+                        let last_contract_tx = cursor_map.get(&contract_address).cloned();
+
+                        // Skip if we haven't reached the last processed transaction
+                        if let Some(last_tx) = last_contract_tx {
+                            if event.transaction_hash == last_tx {
+                                continue;
+                            }
+                        }
+
+                        events.push(event);
+                    }
+
+                    // If there's a continuation token, prepare next request
+                    if let Some(continuation_token) = events_page.continuation_token {
+                        if let ProviderRequestData::GetEvents(mut next_request) = original_request {
+                            next_request.filter.result_page_request.continuation_token =
+                                Some(continuation_token);
+                            next_requests.push((
+                                contract_address,
+                                ProviderRequestData::GetEvents(next_request),
+                            ));
+                        }
+                    }
+                }
+                _ => {
+                    error!(target: LOG_TARGET, "Unexpected response type from batch events request");
+                    return Err(anyhow::anyhow!(
+                        "Unexpected response type from batch events request"
+                    ));
+                }
+            }
+        }
+
+        // Recursively fetch next batch if there are any continuation tokens
+        if !next_requests.is_empty() {
+            events.extend(self.fetch_events_recursive(next_requests, cursor_map).await?);
+        }
+
+        Ok(events)
+    }
+}
+
+/// Decodes the fetched events into the events to process, and fetches the transactions they were
+/// emitted in if the transactions are indexed.
+#[derive(Debug)]
+pub(crate) struct RangeDecoder<P: Provider + Send + Sync + std::fmt::Debug + 'static> {
+    provider: Arc<P>,
+    contracts: Arc<HashMap<Felt, ContractType>>,
+    fetch_transactions: bool,
+    max_concurrent_requests: usize,
+}
+
+impl<P: Provider + Send + Sync + std::fmt::Debug + 'static> Clone for RangeDecoder<P> {
+    fn clone(&self) -> Self {
+        Self {
+            provider: Arc::clone(&self.provider),
+            contracts: Arc::clone(&self.contracts),
+            fetch_transactions: self.fetch_transactions,
+            max_concurrent_requests: self.max_concurrent_requests,
+        }
+    }
+}
+
+impl<P: Provider + Send + Sync + std::fmt::Debug + 'static> RangeDecoder<P> {
+    pub(crate) fn new(
+        provider: Arc<P>,
+        contracts: Arc<HashMap<Felt, ContractType>>,
+        fetch_transactions: bool,
+        max_concurrent_requests: usize,
+    ) -> Self {
+        Self { provider, contracts, fetch_transactions, max_concurrent_requests }
+    }
+
+    pub(crate) async fn decode(&self, range: FetchedRange) -> Result<FetchRangeResult> {
+        let FetchedRange { transactions, blocks, block_hashes, latest_block_number } = range;
+
+        let mut decoded = BTreeMap::new();
+        for (block_number, block_transactions) in transactions {
+            let mut decoded_transactions = LinkedHashMap::new();
+            for (transaction_hash, events) in block_transactions {
+                let events = events
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(event_idx, event)| {
+                        let contract_type = *self.contracts.get(&event.from_address)?;
+                        Some(RangeEvent {
+                            // NOTE: erc* processors expect the event_id to be in this format to
+                            // get transaction_hash:
+                            event_id: format!(
+                                "{:#064x}:{:#x}:{:#04x}",
+                                block_number, transaction_hash, event_idx
+                            ),
+                            event: Event {
+                                from_address: event.from_address,
+                                keys: event.keys,
+                                data: event.data,
+                            },
+                            contract_type,
+                        })
+                    })
+                    .collect();
+
+                decoded_transactions
+                    .insert(transaction_hash, RangeTransaction { events, transaction: None });
+            }
+
+            decoded.insert(block_number, decoded_transactions);
+        }
+
+        if self.fetch_transactions {
+            let transaction_hashes = decoded
+                .values()
+                .flat_map(|transactions| transactions.keys().copied())
+                .collect::<Vec<_>>();
+            let fetched = stream::iter(transaction_hashes)
+                .map(|transaction_hash| self.provider.get_transaction_by_hash(transaction_hash))
+                .buffered(self.max_concurrent_requests)
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            let transactions =
+                decoded.values_mut().flat_map(|transactions| transactions.values_mut());
+            for (transaction, fetched) in transactions.zip(fetched) {
+                transaction.transaction = Some(fetched);
+            }
+        }
+
+        Ok(FetchRangeResult { transactions: decoded, blocks, block_hashes, latest_block_number })
+    }
+}
+
+/// Fetches, decodes and hands over the ranges of blocks in a pipeline of background tasks, so that
+/// the requests to the provider and the decoding overlap with the processing of the previous
+/// ranges.
+///
+/// Each stage buffers its ranges up to the capacity of the pipeline, after which it waits for them
+/// to be received by the next one. A stage stops after its first error, which is handed over in
+/// place of the range.
+#[derive(Debug)]
+pub(crate) struct RangePipeline {
+    /// The first block of the next range to receive.
+    from: u64,
+    receiver: mpsc::Receiver<Result<FetchRangeResult>>,
+    handles: [JoinHandle<()>; 2],
+}
+
+impl RangePipeline {
+    /// Spawns the fetching of the ranges of `chunk_size` blocks following `head`, up to
+    /// `latest_block`, with `fetch`, and their decoding with `decode`. `capacity` must be greater
+    /// than zero.
+    pub(crate) fn spawn<F, FetchFut, D, DecodeFut>(
+        fetch: F,
+        decode: D,
+        head: u64,
+        latest_block: u64,
+        chunk_size: u64,
+        capacity: usize,
+    ) -> Self
+    where
+        F: Fn(u64, u64) -> FetchFut + Send + 'static,
+        FetchFut: Future<Output = Result<FetchedRange>> + Send,
+        D: Fn(FetchedRange) -> DecodeFut + Send + 'static,
+        DecodeFut: Future<Output = Result<FetchRangeResult>> + Send,
+    {
+        let (fetched_sender, mut fetched_receiver) = mpsc::channel(capacity);
+        let (sender, receiver) = mpsc::channel(capacity);
+
+        let fetching = tokio::spawn(async move {
+            let mut head = head;
+            while head < latest_block {
+                let to = (head + chunk_size).min(latest_block);
+                let result = fetch(head + 1, to).await;
+
+                let failed = result.is_err();
+                if fetched_sender.send(result).await.is_err() || failed {
+                    break;
+                }
+
+                head = to;
+            }
+        });
+
+        let decoding = tokio::spawn(async move {
+            while let Some(fetched) = fetched_receiver.recv().await {
+                let result = match fetched {
+                    Ok(range) => decode(range).await,
+                    Err(e) => Err(e),
+                };
+
+                let failed = result.is_err();
+                if sender.send(result).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        Self { from: head + 1, receiver, handles: [fetching, decoding] }
+    }
+
+    /// Receives the next decoded range, if it starts at `from`. Returns `None` once all the ranges
+    /// have been received, or if `from` doesn't follow the last received range.
+    pub(crate) async fn recv(&mut self, from: u64) -> Option<Result<FetchRangeResult>> {
+        if from != self.from {
+            return None;
+        }
+
+        let result = self.receiver.recv().await?;
+        if let Ok(data) = &result {
+            self.from = data.latest_block_number + 1;
+        }
+
+        Some(result)
+    }
+}
+
+impl Drop for RangePipeline {
+    fn drop(&mut self) {
+        for handle in &self.handles {
+            handle.abort();
+        }
+    }
+}
+
+/// Receives the range starting at `from` from `pipeline`, if any. The pipeline is dropped once
+/// done, or once out of sync with the indexed head, eg after a processing error.
+pub(crate) async fn pipelined_range(
+    pipeline: &mut Option<RangePipeline>,
+    from: u64,
+) -> Option<Result<FetchRangeResult>> {
+    let result = pipeline.as_mut()?.recv(from).await;
+    if !matches!(result, Some(Ok(_))) {
+        *pipeline = None;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use starknet::macros::felt;
+    use starknet::providers::jsonrpc::HttpTransport;
+    use starknet::providers::{JsonRpcClient, Url};
+
+    use super::*;
+
+    fn fetched(from: u64, to: u64) -> FetchedRange {
+        FetchedRange {
+            transactions: BTreeMap::new(),
+            blocks: (from..=to).map(|block_number| (block_number, block_number * 10)).collect(),
+            block_hashes: BTreeMap::new(),
+            latest_block_number: to,
+        }
+    }
+
+    fn decoded(range: FetchedRange) -> FetchRangeResult {
+        FetchRangeResult {
+            transactions: BTreeMap::new(),
+            blocks: range.blocks,
+            block_hashes: range.block_hashes,
+            latest_block_number: range.latest_block_number,
+        }
+    }
+
+    fn spawn(head: u64, latest_block: u64, failing_block: Option<u64>) -> RangePipeline {
+        RangePipeline::spawn(
+            move |from, to| async move {
+                match failing_block {
+                    Some(block) if (from..=to).contains(&block) => Err(anyhow!("fetch failed")),
+                    _ => Ok(fetched(from, to)),
+                }
+            },
+            |range| async move { Ok(decoded(range)) },
+            head,
+            latest_block,
+            10,
+            1,
+        )
+    }
+
+    async fn recv_blocks(pipeline: &mut RangePipeline, from: u64) -> (u64, u64) {
+        let range = pipeline.recv(from).await.unwrap().unwrap();
+        (*range.blocks.keys().next().unwrap(), range.latest_block_number)
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_ranges() {
+        let mut pipeline = spawn(0, 25, None);
+
+        assert_eq!(recv_blocks(&mut pipeline, 1).await, (1, 10));
+        assert_eq!(recv_blocks(&mut pipeline, 11).await, (11, 20));
+        assert_eq!(recv_blocks(&mut pipeline, 21).await, (21, 25));
+        assert!(pipeline.recv(26).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_reset_after_reorg() {
+        let mut pipeline = Some(spawn(0, 30, None));
+        let range = pipelined_range(&mut pipeline, 1).await.unwrap().unwrap();
+        assert_eq!(range.latest_block_number, 10);
+
+        // the rollback moves the head back to the block 5, the ranges fetched ahead are stale
+        assert!(pipelined_range(&mut pipeline, 6).await.is_none());
+        assert!(pipeline.is_none());
+
+        let mut pipeline = spawn(5, 30, None);
+        assert_eq!(recv_blocks(&mut pipeline, 6).await, (6, 15));
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_fetch_error() {
+        let mut pipeline = Some(spawn(0, 30, Some(15)));
+        assert!(pipelined_range(&mut pipeline, 1).await.unwrap().is_ok());
+
+        // the failed range is fetched again outside of the pipeline
+        assert!(pipelined_range(&mut pipeline, 11).await.unwrap().is_err());
+        assert!(pipeline.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_decode_error() {
+        let mut pipeline = RangePipeline::spawn(
+            |from, to| async move { Ok(fetched(from, to)) },
+            |_| async move { Err::<FetchRangeResult, _>(anyhow!("decode failed")) },
+            0,
+            30,
+            10,
+            1,
+        );
+
+        assert!(pipeline.recv(1).await.unwrap().is_err());
+        // the stages stop after the error
+        assert!(pipeline.recv(1).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_decode_range() {
+        let world = felt!("0x1");
+        let transaction_hash = felt!("0xabc");
+        let event = |from_address| EmittedEvent {
+            from_address,
+            keys: vec![felt!("0x2")],
+            data: vec![felt!("0x3")],
+            block_hash: None,
+            block_number: Some(7),
+            transaction_hash,
+        };
+
+        let provider =
+            JsonRpcClient::new(HttpTransport::new(Url::parse("http://localhost:5050").unwrap()));
+        let contracts = HashMap::from([(world, ContractType::WORLD)]);
+        let decoder = RangeDecoder::new(Arc::new(provider), Arc::new(contracts), false, 1);
+
+        let mut range = fetched(7, 7);
+        range.transactions.insert(
+            7,
+            LinkedHashMap::from_iter([(transaction_hash, vec![event(felt!("0x9")), event(world)])]),
+        );
+
+        let decoded = decoder.decode(range).await.unwrap();
+        let transaction = &decoded.transactions[&7][&transaction_hash];
+        assert!(transaction.transaction.is_none());
+
+        // the events of the contracts that aren't indexed are skipped, but keep their index
+        assert_eq!(transaction.events.len(), 1);
+        assert_eq!(
+            transaction.events[0].event_id,
+            format!("{:#064x}:{:#x}:{:#04x}", 7, transaction_hash, 1)
+        );
+        assert_eq!(transaction.events[0].contract_type, ContractType::WORLD);
+        assert_eq!(transaction.events[0].event.keys, vec![felt!("0x2")]);
+    }
+}
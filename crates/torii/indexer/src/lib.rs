@@ -4,7 +4,10 @@ mod constants;
 #[path = "test.rs"]
 mod test;
 
+pub mod abi;
 pub mod engine;
+mod fetcher;
+mod metrics;
 pub mod processors;
 mod task_manager;
 
@@ -0,0 +1,17 @@
+use dojo_metrics::Metrics;
+use metrics::{Counter, Gauge, Histogram};
+
+#[derive(Metrics, Clone)]
+#[metrics(scope = "indexer")]
+pub(crate) struct EngineMetrics {
+    /// The number of events processed.
+    pub(crate) events_processed_total: Counter,
+    /// The last block processed.
+    pub(crate) head: Gauge,
+    /// The latest block of the chain.
+    pub(crate) latest_block: Gauge,
+    /// The number of blocks of the chain that are not processed yet.
+    pub(crate) head_lag: Gauge,
+    /// The time it took to process the data fetched, in seconds.
+    pub(crate) process_seconds: Histogram,
+}
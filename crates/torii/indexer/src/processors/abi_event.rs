@@ -0,0 +1,84 @@
+use anyhow::{Error, Result};
+use async_trait::async_trait;
+use dojo_world::contracts::world::WorldContractReader;
+use starknet::core::types::{Event, Felt};
+use starknet::providers::Provider;
+use torii_sqlite::Sql;
+use tracing::{debug, trace};
+
+use super::{EventProcessor, EventProcessorConfig};
+use crate::engine::get_transaction_hash_from_event_id;
+use crate::task_manager::{self, TaskId, TaskPriority};
+
+pub(crate) const LOG_TARGET: &str = "torii_indexer::processors::abi_event";
+
+/// Decodes and stores the events of the contracts indexed using their ABI, ie the
+/// [`EventProcessorConfig::contract_abis`].
+#[derive(Default, Debug)]
+pub struct AbiEventProcessor;
+
+#[async_trait]
+impl<P> EventProcessor<P> for AbiEventProcessor
+where
+    P: Provider + Send + Sync + std::fmt::Debug,
+{
+    fn event_key(&self) -> String {
+        "".to_string()
+    }
+
+    fn validate(&self, _event: &Event) -> bool {
+        true
+    }
+
+    fn task_priority(&self) -> TaskPriority {
+        1
+    }
+
+    fn task_identifier(&self, _event: &Event) -> TaskId {
+        task_manager::TASK_ID_SEQUENTIAL
+    }
+
+    async fn process(
+        &self,
+        _world: &WorldContractReader<P>,
+        db: &mut Sql,
+        _block_number: u64,
+        block_timestamp: u64,
+        event_id: &str,
+        event: &Event,
+        config: &EventProcessorConfig,
+    ) -> Result<(), Error> {
+        let Some(abi) = config.contract_abis.get(&event.from_address) else {
+            return Ok(());
+        };
+
+        let Some(decoded) = abi.decode(event) else {
+            trace!(
+                target: LOG_TARGET,
+                contract_address = %format!("{:#x}", event.from_address),
+                keys = ?event.keys,
+                "Event not found in ABI."
+            );
+            return Ok(());
+        };
+
+        let transaction_hash = Felt::from_hex(&get_transaction_hash_from_event_id(event_id))?;
+        db.store_contract_event(
+            event_id,
+            event.from_address,
+            &decoded.name,
+            &decoded.data,
+            transaction_hash,
+            block_timestamp,
+        )?;
+
+        debug!(
+            target: LOG_TARGET,
+            contract_address = %format!("{:#x}", event.from_address),
+            name = %decoded.name,
+            "Stored contract event."
+        );
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 use anyhow::{Error, Result};
 use async_trait::async_trait;
@@ -8,8 +9,10 @@ use starknet::providers::Provider;
 use torii_sqlite::cache::ContractClassCache;
 use torii_sqlite::Sql;
 
+use crate::abi::ContractAbi;
 use crate::task_manager::{TaskId, TaskPriority};
 
+pub mod abi_event;
 pub mod controller;
 pub mod erc1155_transfer_batch;
 pub mod erc1155_transfer_single;
@@ -36,6 +39,8 @@ pub mod upgrade_model;
 pub struct EventProcessorConfig {
     pub namespaces: HashSet<String>,
     pub strict_model_reader: bool,
+    /// The ABIs of the contracts indexed using their ABI, by contract address.
+    pub contract_abis: HashMap<Felt, Arc<ContractAbi>>,
 }
 
 impl EventProcessorConfig {
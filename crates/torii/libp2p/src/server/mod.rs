@@ -371,11 +371,30 @@ impl<P: Provider + Sync> Relay<P> {
                                 continue;
                             }
 
+                            let received_at = Utc::now().timestamp() as u64;
+                            if let Err(e) = self.db.store_offchain_message(
+                                &message_id.to_string(),
+                                entity_identity,
+                                entity_id,
+                                &ty.name(),
+                                &serde_json::to_string(&data.message).unwrap(),
+                                &data.signature,
+                                received_at,
+                            ) {
+                                warn!(
+                                    target: LOG_TARGET,
+                                    error = %e,
+                                    "Storing message."
+                                );
+                                continue;
+                            }
+
+                            // the message is committed along with its entity
                             if let Err(e) = set_entity(
                                 &mut self.db,
                                 ty.clone(),
                                 &message_id.to_string(),
-                                Utc::now().timestamp() as u64,
+                                received_at,
                                 entity_id,
                                 model_id,
                                 &keys_str,
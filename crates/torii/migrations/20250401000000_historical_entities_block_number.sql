@@ -0,0 +1,5 @@
+-- Stores the block number at which each version of a historical entity was set, to query the state
+-- of an entity at a given block. The versions stored before this migration have no block number.
+ALTER TABLE entities_historical ADD COLUMN block_number BIGINT;
+
+CREATE INDEX idx_entities_historical_id_block_number ON entities_historical (id, model_id, block_number);
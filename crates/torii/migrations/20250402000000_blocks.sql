@@ -0,0 +1,6 @@
+-- The hashes of the indexed blocks, to detect the reorganizations of the chain. Only the blocks
+-- with events and the last block of each indexed range are stored.
+CREATE TABLE blocks (
+    number INTEGER NOT NULL PRIMARY KEY,
+    hash TEXT NOT NULL
+);
@@ -0,0 +1,13 @@
+-- The events of the contracts indexed using their ABI, decoded as JSON.
+CREATE TABLE contract_events (
+    -- the id of the event, as the ids of the raw events
+    id TEXT NOT NULL PRIMARY KEY,
+    contract_address TEXT NOT NULL,
+    name TEXT NOT NULL,
+    data TEXT NOT NULL,
+    transaction_hash TEXT NOT NULL,
+    executed_at DATETIME NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_contract_events_contract_address_name ON contract_events (contract_address, name);
@@ -0,0 +1,18 @@
+-- The off-chain messages received by the relay, stored once their signature has been verified.
+CREATE TABLE offchain_messages (
+    -- the id of the gossipsub message
+    id TEXT NOT NULL PRIMARY KEY,
+    -- the account that signed the message
+    identity TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    -- the tag of the model of the message
+    model TEXT NOT NULL,
+    -- the typed data of the message, as JSON
+    message TEXT NOT NULL,
+    signature TEXT NOT NULL,
+    received_at DATETIME NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_offchain_messages_identity ON offchain_messages (identity);
+CREATE INDEX idx_offchain_messages_model ON offchain_messages (model);
@@ -0,0 +1,11 @@
+-- Records the deletions of the models of the entities, to restore the entities deleted in the
+-- blocks removed by a chain reorganization.
+CREATE TABLE entities_deleted (
+    id TEXT NOT NULL,
+    model_id TEXT NOT NULL,
+    event_id TEXT NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_entities_deleted_id ON entities_deleted (id, model_id);
+CREATE INDEX idx_entities_deleted_event_id ON entities_deleted (event_id);
@@ -11,11 +11,13 @@
 //!   for more info.
 
 use std::cmp;
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
+use anyhow::Context;
 use camino::Utf8PathBuf;
 use constants::UDC_ADDRESS;
 use dojo_metrics::exporters::prometheus::PrometheusRecorder;
@@ -33,12 +35,15 @@ use tokio::sync::broadcast;
 use tokio::sync::broadcast::Sender;
 use tokio_stream::StreamExt;
 use torii_cli::ToriiArgs;
+use torii_graphql::schema::QueryLimits;
+use torii_indexer::abi::ContractAbi;
 use torii_indexer::engine::{Engine, EngineConfig, IndexingFlags, Processors};
 use torii_indexer::processors::EventProcessorConfig;
 use torii_server::proxy::Proxy;
 use torii_sqlite::cache::ModelCache;
 use torii_sqlite::executor::Executor;
 use torii_sqlite::simple_broker::SimpleBroker;
+use torii_sqlite::snapshot::DATABASE_FILE_NAME;
 use torii_sqlite::types::{Contract, ContractType, Model};
 use torii_sqlite::{Sql, SqlConfig};
 use tracing::{error, info, warn};
@@ -88,6 +93,20 @@ impl Runner {
                 .push(Contract { address: UDC_ADDRESS, r#type: ContractType::UDC });
         }
 
+        let mut contract_abis = HashMap::new();
+        for contract in &self.args.indexing.abi_contracts {
+            let abi = std::fs::read_to_string(&contract.abi)
+                .with_context(|| format!("Failed to read ABI file {}", contract.abi))?;
+            let abi = ContractAbi::from_json(&abi)
+                .with_context(|| format!("Invalid ABI file {}", contract.abi))?;
+
+            contract_abis.insert(contract.address, Arc::new(abi));
+            self.args
+                .indexing
+                .contracts
+                .push(Contract { address: contract.address, r#type: ContractType::ABI });
+        }
+
         // Setup cancellation for graceful shutdown
         let (shutdown_tx, _) = broadcast::channel(1);
 
@@ -116,7 +135,7 @@ impl Runner {
             // Create the directory if it doesn't exist
             std::fs::create_dir_all(&db_dir)?;
             // Set the database file path inside the directory
-            db_dir.join("torii.db")
+            db_dir.join(DATABASE_FILE_NAME)
         } else {
             tempfile.path().to_path_buf()
         };
@@ -217,8 +236,10 @@ impl Runner {
                 event_processor_config: EventProcessorConfig {
                     strict_model_reader: self.args.indexing.strict_model_reader,
                     namespaces: self.args.indexing.namespaces.into_iter().collect(),
+                    contract_abis,
                 },
                 world_block: self.args.indexing.world_block,
+                prefetch_ranges: self.args.indexing.prefetch_ranges,
             },
             shutdown_tx.clone(),
             Some(block_tx),
@@ -272,14 +293,21 @@ impl Runner {
             self.args.server.http_cors_origins.filter(|cors_origins| !cors_origins.is_empty()),
             Some(grpc_addr),
             None,
+            self.args.graphql.rate_limit,
             Some(artifacts_addr),
             Arc::new(readonly_pool.clone()),
+            engine.sync_status(),
         ));
 
+        let graphql_limits = QueryLimits {
+            max_depth: Some(self.args.graphql.max_depth),
+            max_complexity: Some(self.args.graphql.max_complexity),
+        };
         let graphql_server = spawn_rebuilding_graphql_server(
             shutdown_tx.clone(),
             readonly_pool.into(),
             proxy_server.clone(),
+            graphql_limits,
         );
 
         let gql_endpoint = format!("{addr}/graphql");
@@ -336,12 +364,13 @@ async fn spawn_rebuilding_graphql_server(
     shutdown_tx: Sender<()>,
     pool: Arc<SqlitePool>,
     proxy_server: Arc<Proxy>,
+    limits: QueryLimits,
 ) {
     let mut broker = SimpleBroker::<Model>::subscribe();
 
     loop {
         let shutdown_rx = shutdown_tx.subscribe();
-        let (new_addr, new_server) = torii_graphql::server::new(shutdown_rx, &pool).await;
+        let (new_addr, new_server) = torii_graphql::server::new(shutdown_rx, &pool, limits).await;
 
         tokio::spawn(new_server);
 
@@ -1,31 +1,55 @@
+use std::collections::HashMap;
 use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use http::StatusCode;
 use hyper::{Body, Request, Response};
+use parking_lot::Mutex;
 use tracing::error;
 
 use super::Handler;
 
 pub(crate) const LOG_TARGET: &str = "torii::server::handlers::graphql";
 
+/// The window over which the requests of a client are rate limited.
+pub const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+/// The number of clients above which the clients whose window is over are forgotten.
+pub const RATE_LIMIT_MAX_CLIENTS: usize = 10_000;
+
 #[derive(Debug)]
 pub struct GraphQLHandler {
     pub(crate) graphql_addr: Option<SocketAddr>,
+    rate_limiter: Option<Arc<RateLimiter>>,
 }
 
 impl GraphQLHandler {
-    pub fn new(graphql_addr: Option<SocketAddr>) -> Self {
-        Self { graphql_addr }
+    pub fn new(graphql_addr: Option<SocketAddr>, rate_limiter: Option<Arc<RateLimiter>>) -> Self {
+        Self { graphql_addr, rate_limiter }
     }
 }
 
 #[async_trait::async_trait]
 impl Handler for GraphQLHandler {
+    fn name(&self) -> &'static str {
+        "graphql"
+    }
+
     fn should_handle(&self, req: &Request<Body>) -> bool {
         req.uri().path().starts_with("/graphql")
     }
 
     async fn handle(&self, req: Request<Body>, client_addr: IpAddr) -> Response<Body> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            if !rate_limiter.check(client_addr, Instant::now()) {
+                return Response::builder()
+                    .status(StatusCode::TOO_MANY_REQUESTS)
+                    .header(http::header::RETRY_AFTER, RATE_LIMIT_WINDOW.as_secs())
+                    .body(Body::empty())
+                    .unwrap();
+            }
+        }
+
         if let Some(addr) = self.graphql_addr {
             let graphql_addr = format!("http://{}", addr);
             match crate::proxy::GRAPHQL_PROXY_CLIENT.call(client_addr, &graphql_addr, req).await {
@@ -43,3 +67,47 @@ impl Handler for GraphQLHandler {
         }
     }
 }
+
+/// Limits the number of requests of each client, identified by its address, per minute.
+///
+/// A subscription is counted as a single request, as it is made over a single websocket
+/// connection.
+#[derive(Debug)]
+pub struct RateLimiter {
+    requests_per_minute: u32,
+    // the start of the current window of each client, and the number of requests in it
+    clients: Mutex<HashMap<IpAddr, (Instant, u32)>>,
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_minute: u32) -> Self {
+        Self { requests_per_minute, clients: Mutex::new(HashMap::new()) }
+    }
+
+    /// Records a request of the client at `now`, and returns whether it is within the limit.
+    pub fn check(&self, client: IpAddr, now: Instant) -> bool {
+        let mut clients = self.clients.lock();
+
+        if clients.len() >= RATE_LIMIT_MAX_CLIENTS {
+            clients.retain(|_, (start, _)| now.duration_since(*start) < RATE_LIMIT_WINDOW);
+        }
+
+        let (start, count) = clients.entry(client).or_insert((now, 0));
+        if now.duration_since(*start) >= RATE_LIMIT_WINDOW {
+            *start = now;
+            *count = 0;
+        }
+
+        if *count >= self.requests_per_minute {
+            return false;
+        }
+
+        *count += 1;
+        true
+    }
+
+    /// Returns the number of clients whose requests are being counted.
+    pub fn num_clients(&self) -> usize {
+        self.clients.lock().len()
+    }
+}
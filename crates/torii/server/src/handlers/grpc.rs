@@ -21,6 +21,10 @@ impl GrpcHandler {
 
 #[async_trait::async_trait]
 impl Handler for GrpcHandler {
+    fn name(&self) -> &'static str {
+        "grpc"
+    }
+
     fn should_handle(&self, req: &Request<Body>) -> bool {
         req.headers()
             .get(CONTENT_TYPE)
@@ -0,0 +1,68 @@
+use std::net::IpAddr;
+use std::time::Duration;
+
+use http::header::CONTENT_TYPE;
+use hyper::{Body, Request, Response, StatusCode};
+use serde_json::json;
+use tokio::sync::watch;
+use torii_sqlite::types::SyncStatus;
+
+use super::Handler;
+
+/// The maximum number of blocks the indexer can be behind the chain to be considered synced.
+const MAX_HEAD_LAG: u64 = 10;
+/// The maximum time since the indexer last polled the chain for it to be considered running.
+const MAX_POLL_AGE: Duration = Duration::from_secs(120);
+
+/// Readiness endpoint, `GET /health`, reporting the synchronization of the indexer with the chain.
+///
+/// Responds with `200 OK` if the indexer is synced, `503 Service Unavailable` if it is still
+/// syncing or has stopped polling the chain.
+#[derive(Debug)]
+pub struct HealthHandler {
+    sync_status: watch::Receiver<SyncStatus>,
+}
+
+impl HealthHandler {
+    pub fn new(sync_status: watch::Receiver<SyncStatus>) -> Self {
+        Self { sync_status }
+    }
+}
+
+#[async_trait::async_trait]
+impl Handler for HealthHandler {
+    fn name(&self) -> &'static str {
+        "health"
+    }
+
+    fn should_handle(&self, req: &Request<Body>) -> bool {
+        req.uri().path().trim_end_matches('/') == "/health"
+    }
+
+    async fn handle(&self, _req: Request<Body>, _client_addr: IpAddr) -> Response<Body> {
+        let status = *self.sync_status.borrow();
+        let last_poll = status.updated_at.map(|updated_at| updated_at.elapsed());
+
+        let (code, state) = match (last_poll, status.head_lag()) {
+            (Some(last_poll), _) if last_poll > MAX_POLL_AGE => {
+                (StatusCode::SERVICE_UNAVAILABLE, "stalled")
+            }
+            (Some(_), Some(lag)) if lag <= MAX_HEAD_LAG => (StatusCode::OK, "synced"),
+            _ => (StatusCode::SERVICE_UNAVAILABLE, "syncing"),
+        };
+
+        let body = json!({
+            "status": state,
+            "head": status.head,
+            "latest_block": status.latest_block,
+            "head_lag": status.head_lag(),
+            "last_poll_seconds": last_poll.map(|last_poll| last_poll.as_secs()),
+        });
+
+        Response::builder()
+            .status(code)
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+}
@@ -301,6 +301,10 @@ impl McpHandler {
 
 #[async_trait::async_trait]
 impl Handler for McpHandler {
+    fn name(&self) -> &'static str {
+        "mcp"
+    }
+
     fn should_handle(&self, req: &Request<Body>) -> bool {
         req.uri().path().starts_with("/mcp")
     }
@@ -1,6 +1,8 @@
 pub mod graphql;
 pub mod grpc;
+pub mod health;
 pub mod mcp;
+pub mod rest;
 pub mod sql;
 pub mod static_files;
 
@@ -10,6 +12,9 @@ use hyper::{Body, Request, Response};
 
 #[async_trait::async_trait]
 pub trait Handler: Send + Sync + std::fmt::Debug {
+    // The name of the handler, eg to label its metrics
+    fn name(&self) -> &'static str;
+
     // Check if this handler should handle the given request
     fn should_handle(&self, req: &Request<Body>) -> bool;
 
@@ -0,0 +1,367 @@
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use http::header::CONTENT_TYPE;
+use hyper::{Body, Method, Request, Response, StatusCode};
+use serde_json::json;
+use sqlx::SqlitePool;
+use starknet::core::types::Felt;
+use torii_sqlite::constants::SQL_FELT_DELIMITER;
+use torii_sqlite::utils::{felts_to_sql_string, map_row_to_json};
+
+use super::Handler;
+
+const DEFAULT_LIMIT: u64 = 100;
+const MAX_LIMIT: u64 = 1000;
+
+/// Read-only JSON endpoints for the most common queries, for the clients that can't depend on
+/// GraphQL or gRPC:
+///
+/// - `GET /models`: the registered models.
+/// - `GET /entities/{model}`: the entities of a model, eg `/entities/ns-Position`.
+/// - `GET /entities/{model}/{keys}`: the entity of a model with the given comma separated keys, eg
+///   `/entities/ns-Position/0x1`.
+/// - `GET /events`: the raw events, optionally filtered by `keys`, a comma separated list of keys
+///   where `*` matches any key.
+///
+/// The lists are paginated with the `limit` and `offset` query parameters.
+#[derive(Debug)]
+pub struct RestHandler {
+    pool: Arc<SqlitePool>,
+}
+
+impl RestHandler {
+    pub fn new(pool: Arc<SqlitePool>) -> Self {
+        Self { pool }
+    }
+
+    async fn models(&self) -> Response<Body> {
+        let query = "SELECT id, namespace, name, class_hash, contract_address, packed_size, \
+                     unpacked_size, executed_at FROM models ORDER BY namespace, name";
+
+        self.fetch_all(sqlx::query(query)).await
+    }
+
+    async fn entities(&self, tag: &str, query: &Query) -> Response<Body> {
+        let table = match self.model_table(tag).await {
+            Ok(table) => table,
+            Err(response) => return response,
+        };
+
+        let sql =
+            format!("SELECT * FROM [{table}] ORDER BY internal_event_id DESC LIMIT ? OFFSET ?");
+        self.fetch_all(sqlx::query(&sql).bind(query.limit as i64).bind(query.offset as i64)).await
+    }
+
+    async fn entity(&self, tag: &str, keys: &str) -> Response<Body> {
+        let table = match self.model_table(tag).await {
+            Ok(table) => table,
+            Err(response) => return response,
+        };
+
+        let keys = match keys.split(',').map(Felt::from_str).collect::<Result<Vec<_>, _>>() {
+            Ok(keys) => felts_to_sql_string(&keys),
+            Err(_) => return error(StatusCode::BAD_REQUEST, "Keys must be comma separated felts."),
+        };
+
+        let sql = format!(
+            "SELECT [{table}].* FROM [{table}] JOIN entities ON entities.id = \
+             [{table}].internal_entity_id WHERE entities.keys = ?"
+        );
+
+        match sqlx::query(&sql).bind(keys).fetch_optional(&*self.pool).await {
+            Ok(Some(row)) => json_response(StatusCode::OK, &map_row_to_json(&row)),
+            Ok(None) => error(StatusCode::NOT_FOUND, "Entity not found."),
+            Err(e) => error(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+        }
+    }
+
+    async fn events(&self, query: &Query) -> Response<Body> {
+        let mut sql =
+            String::from("SELECT id, keys, data, transaction_hash, executed_at FROM events");
+
+        let pattern = match query.keys.as_deref().map(keys_pattern) {
+            Some(None) => {
+                return error(
+                    StatusCode::BAD_REQUEST,
+                    "Keys must be comma separated felts or `*`.",
+                );
+            }
+            pattern => pattern.flatten(),
+        };
+        if pattern.is_some() {
+            sql.push_str(" WHERE keys LIKE ?");
+        }
+        sql.push_str(" ORDER BY id DESC LIMIT ? OFFSET ?");
+
+        let mut sql = sqlx::query(&sql);
+        if let Some(pattern) = pattern {
+            sql = sql.bind(pattern);
+        }
+
+        self.fetch_all(sql.bind(query.limit as i64).bind(query.offset as i64)).await
+    }
+
+    /// Returns the name of the table of the model with the given tag, if the model is registered.
+    async fn model_table(&self, tag: &str) -> Result<String, Response<Body>> {
+        let not_found = || error(StatusCode::NOT_FOUND, &format!("Model {tag} not found."));
+        let (namespace, name) = tag.split_once('-').ok_or_else(not_found)?;
+
+        let exists = sqlx::query("SELECT id FROM models WHERE namespace = ? AND name = ?")
+            .bind(namespace)
+            .bind(name)
+            .fetch_optional(&*self.pool)
+            .await
+            .map_err(|e| error(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()))?;
+
+        match exists {
+            Some(_) => Ok(format!("{namespace}-{name}")),
+            None => Err(not_found()),
+        }
+    }
+
+    async fn fetch_all<'q>(
+        &self,
+        query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+    ) -> Response<Body> {
+        match query.fetch_all(&*self.pool).await {
+            Ok(rows) => {
+                let rows: Vec<_> = rows.iter().map(map_row_to_json).collect();
+                json_response(StatusCode::OK, &serde_json::Value::from(rows))
+            }
+            Err(e) => error(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Handler for RestHandler {
+    fn name(&self) -> &'static str {
+        "rest"
+    }
+
+    fn should_handle(&self, req: &Request<Body>) -> bool {
+        let path = req.uri().path().trim_end_matches('/');
+        path == "/models" || path == "/events" || path.starts_with("/entities/")
+    }
+
+    async fn handle(&self, req: Request<Body>, _client_addr: IpAddr) -> Response<Body> {
+        if req.method() != Method::GET {
+            return error(StatusCode::METHOD_NOT_ALLOWED, "Only GET method is allowed.");
+        }
+
+        let query = match Query::parse(req.uri().query().unwrap_or_default()) {
+            Ok(query) => query,
+            Err(message) => return error(StatusCode::BAD_REQUEST, &message),
+        };
+
+        let path = req.uri().path().trim_end_matches('/');
+        let segments: Vec<&str> = path.split('/').skip(1).collect();
+
+        match segments.as_slice() {
+            ["models"] => self.models().await,
+            ["events"] => self.events(&query).await,
+            ["entities", tag] => self.entities(tag, &query).await,
+            ["entities", tag, keys] => self.entity(tag, keys).await,
+            _ => error(StatusCode::NOT_FOUND, "Not found."),
+        }
+    }
+}
+
+/// The query parameters of the endpoints.
+#[derive(Debug)]
+struct Query {
+    limit: u64,
+    offset: u64,
+    keys: Option<String>,
+}
+
+impl Query {
+    fn parse(query: &str) -> Result<Self, String> {
+        let mut parsed = Self { limit: DEFAULT_LIMIT, offset: 0, keys: None };
+
+        for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+            let number = || value.parse::<u64>().map_err(|_| format!("Invalid `{key}` parameter."));
+            match key.as_ref() {
+                "limit" => parsed.limit = number()?.min(MAX_LIMIT),
+                "offset" => parsed.offset = number()?,
+                "keys" => parsed.keys = Some(value.to_string()),
+                _ => {}
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Converts comma separated keys, where `*` matches any key, to a `LIKE` pattern of the keys as
+/// stored in the database. The keys are matched as a prefix.
+fn keys_pattern(keys: &str) -> Option<String> {
+    let keys = keys
+        .split(',')
+        .map(|key| match key {
+            "*" => Some("%".to_string()),
+            _ => Felt::from_str(key).ok().map(|felt| format!("{felt:#x}")),
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(format!("{}{SQL_FELT_DELIMITER}%", keys.join(SQL_FELT_DELIMITER)))
+}
+
+fn json_response(status: StatusCode, value: &serde_json::Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/json")
+        .body(Body::from(value.to_string()))
+        .unwrap()
+}
+
+fn error(status: StatusCode, message: &str) -> Response<Body> {
+    json_response(status, &json!({ "error": message }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use serde_json::Value;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+
+    async fn handler() -> RestHandler {
+        // a single connection, for all the queries to use the same in-memory database
+        let pool =
+            SqlitePoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO models (id, namespace, name, layout, class_hash, packed_size, \
+             unpacked_size, executed_at, schema) VALUES ('0x1', 'ns', 'Position', '', '0x2', 1, \
+             1, CURRENT_TIMESTAMP, '')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "CREATE TABLE [ns-Position] (internal_id TEXT NOT NULL PRIMARY KEY, internal_event_id \
+             TEXT NOT NULL, internal_entity_id TEXT, x INTEGER)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO entities (id, keys, event_id, executed_at) VALUES ('0xe', '0x1/', '0x1', \
+             CURRENT_TIMESTAMP)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO [ns-Position] (internal_id, internal_event_id, internal_entity_id, x) \
+             VALUES ('0xe', '0x1', '0xe', 7)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        for (id, keys) in [("0x1", "0x1/0x2/"), ("0x2", "0x3/0x2/"), ("0x3", "0x1/0x4/")] {
+            sqlx::query(
+                "INSERT INTO events (id, keys, data, executed_at) VALUES (?, ?, '', \
+                 CURRENT_TIMESTAMP)",
+            )
+            .bind(id)
+            .bind(keys)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        RestHandler::new(Arc::new(pool))
+    }
+
+    async fn get(handler: &RestHandler, uri: &str) -> (StatusCode, Value) {
+        let req = Request::get(uri).body(Body::empty()).unwrap();
+        let response = handler.handle(req, IpAddr::V4(Ipv4Addr::LOCALHOST)).await;
+
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    fn event_ids(events: &Value) -> Vec<&str> {
+        events.as_array().unwrap().iter().map(|event| event["id"].as_str().unwrap()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_limit() {
+        let handler = handler().await;
+        sqlx::query(
+            "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 1200) \
+             INSERT INTO events (id, keys, data, executed_at) SELECT 'e' || i, '0x5/', '', \
+             CURRENT_TIMESTAMP FROM n",
+        )
+        .execute(&*handler.pool)
+        .await
+        .unwrap();
+
+        let (status, events) = get(&handler, "/events").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(events.as_array().unwrap().len(), DEFAULT_LIMIT as usize);
+
+        let (_, events) = get(&handler, "/events?limit=2").await;
+        assert_eq!(events.as_array().unwrap().len(), 2);
+
+        let (_, events) = get(&handler, "/events?limit=5000").await;
+        assert_eq!(events.as_array().unwrap().len(), MAX_LIMIT as usize);
+
+        let (status, _) = get(&handler, "/events?limit=many").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_keys() {
+        let handler = handler().await;
+
+        let (status, _) = get(&handler, "/events?keys=0x1,key").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = get(&handler, "/entities/ns-Position/0x1,key").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_model() {
+        let handler = handler().await;
+
+        for uri in ["/entities/ns-Missing", "/entities/Position", "/entities/ns-Missing/0x1"] {
+            let (status, _) = get(&handler, uri).await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+        }
+
+        let (status, _) = get(&handler, "/entities/ns-Position/0x2").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, entity) = get(&handler, "/entities/ns-Position/0x1").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(entity["x"], 7);
+    }
+
+    #[tokio::test]
+    async fn test_keys_pattern() {
+        let handler = handler().await;
+
+        let (_, events) = get(&handler, "/events?keys=*,0x2").await;
+        assert_eq!(event_ids(&events), ["0x2", "0x1"]);
+
+        let (_, events) = get(&handler, "/events?keys=0x1").await;
+        assert_eq!(event_ids(&events), ["0x3", "0x1"]);
+
+        let (_, events) = get(&handler, "/events?keys=0x1,*").await;
+        assert_eq!(event_ids(&events), ["0x3", "0x1"]);
+
+        let (_, events) = get(&handler, "/events?keys=*,0x4").await;
+        assert_eq!(event_ids(&events), ["0x3"]);
+    }
+}
@@ -108,6 +108,10 @@ impl SqlHandler {
 
 #[async_trait::async_trait]
 impl Handler for SqlHandler {
+    fn name(&self) -> &'static str {
+        "sql"
+    }
+
     fn should_handle(&self, req: &Request<Body>) -> bool {
         req.uri().path().starts_with("/sql")
     }
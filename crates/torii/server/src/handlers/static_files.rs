@@ -20,6 +20,10 @@ impl StaticHandler {
 
 #[async_trait::async_trait]
 impl Handler for StaticHandler {
+    fn name(&self) -> &'static str {
+        "static"
+    }
+
     fn should_handle(&self, req: &Request<Body>) -> bool {
         req.uri().path().starts_with("/static")
     }
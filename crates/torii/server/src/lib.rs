@@ -1,3 +1,5 @@
 pub mod artifacts;
 pub(crate) mod handlers;
 pub mod proxy;
+
+pub use handlers::graphql::{RateLimiter, RATE_LIMIT_MAX_CLIENTS, RATE_LIMIT_WINDOW};
@@ -1,7 +1,7 @@
 use std::convert::Infallible;
 use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use http::header::CONTENT_TYPE;
 use http::{HeaderName, Method};
@@ -11,15 +11,19 @@ use hyper::server::conn::AddrStream;
 use hyper::service::make_service_fn;
 use hyper::{Body, Client, Request, Response, Server, StatusCode};
 use hyper_reverse_proxy::ReverseProxy;
+use metrics::histogram;
 use serde_json::json;
 use sqlx::SqlitePool;
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 use tower::ServiceBuilder;
+use torii_sqlite::types::SyncStatus;
 use tower_http::cors::{AllowOrigin, CorsLayer};
 
-use crate::handlers::graphql::GraphQLHandler;
+use crate::handlers::graphql::{GraphQLHandler, RateLimiter};
 use crate::handlers::grpc::GrpcHandler;
+use crate::handlers::health::HealthHandler;
 use crate::handlers::mcp::McpHandler;
+use crate::handlers::rest::RestHandler;
 use crate::handlers::sql::SqlHandler;
 use crate::handlers::static_files::StaticHandler;
 use crate::handlers::Handler;
@@ -65,6 +69,8 @@ pub struct Proxy {
     addr: SocketAddr,
     allowed_origins: Option<Vec<String>>,
     handlers: Arc<RwLock<Vec<Box<dyn Handler>>>>,
+    // shared by the graphql handlers, so that the limits outlive the rebuilds of the schema
+    graphql_rate_limiter: Option<Arc<RateLimiter>>,
 }
 
 impl Proxy {
@@ -73,23 +79,29 @@ impl Proxy {
         allowed_origins: Option<Vec<String>>,
         grpc_addr: Option<SocketAddr>,
         graphql_addr: Option<SocketAddr>,
+        graphql_rate_limit: Option<u32>,
         artifacts_addr: Option<SocketAddr>,
         pool: Arc<SqlitePool>,
+        sync_status: watch::Receiver<SyncStatus>,
     ) -> Self {
+        let graphql_rate_limiter =
+            graphql_rate_limit.map(|limit| Arc::new(RateLimiter::new(limit)));
         let handlers: Arc<RwLock<Vec<Box<dyn Handler>>>> = Arc::new(RwLock::new(vec![
-            Box::new(GraphQLHandler::new(graphql_addr)),
+            Box::new(GraphQLHandler::new(graphql_addr, graphql_rate_limiter.clone())),
             Box::new(GrpcHandler::new(grpc_addr)),
             Box::new(McpHandler::new(pool.clone())),
             Box::new(SqlHandler::new(pool.clone())),
+            Box::new(RestHandler::new(pool.clone())),
+            Box::new(HealthHandler::new(sync_status)),
             Box::new(StaticHandler::new(artifacts_addr)),
         ]));
 
-        Self { addr, allowed_origins, handlers }
+        Self { addr, allowed_origins, handlers, graphql_rate_limiter }
     }
 
     pub async fn set_graphql_addr(&self, addr: SocketAddr) {
         let mut handlers = self.handlers.write().await;
-        handlers[0] = Box::new(GraphQLHandler::new(Some(addr)));
+        handlers[0] = Box::new(GraphQLHandler::new(Some(addr), self.graphql_rate_limiter.clone()));
     }
 
     pub async fn start(
@@ -164,7 +176,13 @@ async fn handle(
 ) -> Result<Response<Body>, Infallible> {
     for handler in handlers.iter() {
         if handler.should_handle(&req) {
-            return Ok(handler.handle(req, client_ip).await);
+            let instant = Instant::now();
+            let response = handler.handle(req, client_ip).await;
+
+            let elapsed = instant.elapsed().as_secs_f64();
+            histogram!("server_request_seconds", "handler" => handler.name()).record(elapsed);
+
+            return Ok(response);
         }
     }
 
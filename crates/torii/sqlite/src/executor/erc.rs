@@ -64,6 +64,7 @@ impl<'c, P: Provider + Sync + Send + 'static> Executor<'c, P> {
             match contract_type {
                 ContractType::WORLD => unreachable!(),
                 ContractType::UDC => unreachable!(),
+                ContractType::ABI => unreachable!(),
                 ContractType::ERC721 => {
                     // account_address/contract_address:id => ERC721
                     assert!(id.len() == 2);
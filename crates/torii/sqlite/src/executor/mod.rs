@@ -24,10 +24,10 @@ use crate::constants::TOKENS_TABLE;
 use crate::simple_broker::SimpleBroker;
 use crate::types::{
     ContractCursor, ContractType, Entity as EntityUpdated, Event as EventEmitted,
-    EventMessage as EventMessageUpdated, Model as ModelRegistered, OptimisticEntity,
-    OptimisticEventMessage, ParsedCall, Token, TokenBalance, Transaction,
+    EventMessage as EventMessageUpdated, Model as ModelRegistered, OffchainMessage,
+    OptimisticEntity, OptimisticEventMessage, ParsedCall, Token, TokenBalance, Transaction,
 };
-use crate::utils::{felt_to_sql_string, felts_to_sql_string, I256};
+use crate::utils::{block_number_from_event_id, felt_to_sql_string, felts_to_sql_string, I256};
 
 pub mod erc;
 pub use erc::{RegisterErc20TokenQuery, RegisterNftTokenQuery};
@@ -53,6 +53,7 @@ pub enum BrokerMessage {
     TokenRegistered(Token),
     TokenBalanceUpdated(TokenBalance),
     Transaction(Transaction),
+    OffchainMessage(OffchainMessage),
 }
 
 #[derive(Debug, Clone)]
@@ -128,6 +129,7 @@ pub enum QueryType {
     TokenTransfer,
     RegisterModel,
     StoreEvent,
+    StoreOffchainMessage,
     UpdateNftMetadata(UpdateNftMetadataQuery),
     Flush,
     Execute,
@@ -473,10 +475,12 @@ impl<'c, P: Provider + Sync + Send + 'static> Executor<'c, P> {
                     entity_counter += 1;
 
                     let data = serde_json::to_string(&entity.ty.to_json_value()?)?;
+                    let block_number =
+                        block_number_from_event_id(&entity.event_id).map(|number| number as i64);
                     if let Some(keys) = entity.keys_str {
                         sqlx::query(
                             "INSERT INTO entities_historical (id, keys, event_id, data, model_id, \
-                             executed_at) VALUES (?, ?, ?, ?, ?, ?) RETURNING *",
+                             executed_at, block_number) VALUES (?, ?, ?, ?, ?, ?, ?) RETURNING *",
                         )
                         .bind(entity.entity_id.clone())
                         .bind(keys)
@@ -484,18 +488,20 @@ impl<'c, P: Provider + Sync + Send + 'static> Executor<'c, P> {
                         .bind(data)
                         .bind(entity.model_id.clone())
                         .bind(entity.block_timestamp.clone())
+                        .bind(block_number)
                         .fetch_one(&mut **tx)
                         .await?;
                     } else {
                         sqlx::query(
                             "INSERT INTO entities_historical (id, event_id, data, model_id, \
-                             executed_at) VALUES (?, ?, ?, ?, ?) RETURNING *",
+                             executed_at, block_number) VALUES (?, ?, ?, ?, ?, ?) RETURNING *",
                         )
                         .bind(entity.entity_id.clone())
                         .bind(entity.event_id.clone())
                         .bind(data)
                         .bind(entity.model_id.clone())
                         .bind(entity.block_timestamp.clone())
+                        .bind(block_number)
                         .fetch_one(&mut **tx)
                         .await?;
                     }
@@ -533,10 +539,19 @@ impl<'c, P: Provider + Sync + Send + 'static> Executor<'c, P> {
 
                 sqlx::query("DELETE FROM entity_model WHERE entity_id = ? AND model_id = ?")
                     .bind(entity.entity_id.clone())
-                    .bind(entity.model_id)
+                    .bind(entity.model_id.clone())
                     .execute(&mut **tx)
                     .await?;
 
+                sqlx::query(
+                    "INSERT INTO entities_deleted (id, model_id, event_id) VALUES (?, ?, ?)",
+                )
+                .bind(entity.entity_id.clone())
+                .bind(entity.model_id)
+                .bind(entity.event_id.clone())
+                .execute(&mut **tx)
+                .await?;
+
                 let row = sqlx::query(
                     "UPDATE entities SET updated_at=CURRENT_TIMESTAMP, executed_at=?, event_id=? \
                      WHERE id = ? RETURNING *",
@@ -649,6 +664,19 @@ impl<'c, P: Provider + Sync + Send + 'static> Executor<'c, P> {
                 let event = EventEmitted::from_row(&row)?;
                 self.publish_queue.push(BrokerMessage::EventEmitted(event));
             }
+            QueryType::StoreOffchainMessage => {
+                // a message already stored is ignored, and not published again
+                let row = query.fetch_optional(&mut **tx).await.with_context(|| {
+                    format!(
+                        "Failed to execute query: {:?}, args: {:?}",
+                        query_message.statement, query_message.arguments
+                    )
+                })?;
+                if let Some(row) = row {
+                    let message = OffchainMessage::from_row(&row)?;
+                    self.publish_queue.push(BrokerMessage::OffchainMessage(message));
+                }
+            }
             QueryType::ApplyBalanceDiff(apply_balance_diff) => {
                 debug!(target: LOG_TARGET, "Applying balance diff.");
                 let instant = Instant::now();
@@ -894,5 +922,6 @@ fn send_broker_message(message: BrokerMessage) {
         BrokerMessage::TokenRegistered(token) => SimpleBroker::publish(token),
         BrokerMessage::TokenBalanceUpdated(token_balance) => SimpleBroker::publish(token_balance),
         BrokerMessage::Transaction(transaction) => SimpleBroker::publish(transaction),
+        BrokerMessage::OffchainMessage(message) => SimpleBroker::publish(message),
     }
 }
@@ -32,7 +32,9 @@ pub mod erc;
 pub mod error;
 pub mod executor;
 pub mod model;
+mod reorg;
 pub mod simple_broker;
+pub mod snapshot;
 pub mod types;
 pub mod utils;
 
@@ -247,6 +249,112 @@ impl Sql {
         Ok(())
     }
 
+    /// Stores the hash of an indexed block, to detect the reorganizations of the chain.
+    pub fn set_block_hash(&mut self, block_number: u64, block_hash: Felt) -> Result<()> {
+        self.executor.send(QueryMessage::other(
+            "INSERT INTO blocks (number, hash) VALUES (?, ?) ON CONFLICT(number) DO UPDATE SET \
+             hash=EXCLUDED.hash"
+                .to_string(),
+            vec![Argument::Int(block_number as i64), Argument::FieldElement(block_hash)],
+        ))?;
+
+        Ok(())
+    }
+
+    /// Returns the hashes of the indexed blocks up to `block_number`, from the most recent one. At
+    /// most `limit` blocks are returned if set.
+    pub async fn block_hashes(
+        &self,
+        block_number: u64,
+        limit: Option<u64>,
+    ) -> Result<Vec<(u64, Felt)>> {
+        // a negative limit means no limit
+        let blocks = sqlx::query_as::<_, (i64, String)>(
+            "SELECT number, hash FROM blocks WHERE number <= ? ORDER BY number DESC LIMIT ?",
+        )
+        .bind(block_number as i64)
+        .bind(limit.map_or(-1, |limit| limit as i64))
+        .fetch_all(&self.pool)
+        .await?;
+
+        blocks
+            .into_iter()
+            .map(|(number, hash)| Ok((number.try_into()?, Felt::from_str(&hash)?)))
+            .collect()
+    }
+
+    /// Removes the data indexed after `block_number`, and moves the head back to it. All the
+    /// indexed data is removed if `block_number` is `None`.
+    ///
+    /// The entities and the event messages are restored to their last version before the removed
+    /// blocks, and the token transfers of these blocks are reverted from the balances. The versions
+    /// are only stored for the historical models: if any other model was set or deleted in the
+    /// removed blocks, all the indexed data is removed instead, to index the chain again from the
+    /// start.
+    pub async fn rollback_to_block(&mut self, block_number: Option<u64>) -> Result<()> {
+        let mut block_number = block_number;
+        if let Some(number) = block_number {
+            if !self.restore_to_event(&format!("{:#064x}", number + 1)).await? {
+                block_number = None;
+            }
+        }
+        if block_number.is_none() {
+            self.clear_state().await?;
+        }
+
+        // the event ids are prefixed by their zero-padded block number, so the ids of the events of
+        // the removed blocks are all greater than the prefix of the next block.
+        let (event_id, number) = match block_number {
+            Some(number) => (format!("{:#064x}", number + 1), number as i64),
+            None => (String::new(), -1),
+        };
+
+        let event_id_tables = [
+            "events",
+            "contract_events",
+            "entities_historical",
+            "event_messages_historical",
+            "entities_deleted",
+            "token_transfers",
+        ];
+        for table in event_id_tables {
+            let column = if table.ends_with("events") { "id" } else { "event_id" };
+            self.executor.send(QueryMessage::other(
+                format!("DELETE FROM {table} WHERE {column} >= ?"),
+                vec![Argument::String(event_id.clone())],
+            ))?;
+        }
+
+        for table in ["transaction_calls", "transaction_contract"] {
+            self.executor.send(QueryMessage::other(
+                format!(
+                    "DELETE FROM {table} WHERE transaction_hash IN (SELECT id FROM transactions \
+                     WHERE block_number > ?)"
+                ),
+                vec![Argument::Int(number)],
+            ))?;
+        }
+
+        self.executor.send(QueryMessage::other(
+            "DELETE FROM transactions WHERE block_number > ?".to_string(),
+            vec![Argument::Int(number)],
+        ))?;
+        self.executor.send(QueryMessage::other(
+            "DELETE FROM blocks WHERE number > ?".to_string(),
+            vec![Argument::Int(number)],
+        ))?;
+
+        let head = block_number.map_or(Argument::Null, |number| Argument::Int(number as i64));
+        self.executor.send(QueryMessage::other(
+            "UPDATE contracts SET head = ?, last_pending_block_tx = NULL, \
+             last_pending_block_contract_tx = NULL WHERE 1=1"
+                .to_string(),
+            vec![head],
+        ))?;
+
+        Ok(())
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn register_model(
         &mut self,
@@ -581,6 +689,69 @@ impl Sql {
         Ok(())
     }
 
+    /// Stores an event decoded from the ABI of the contract that emitted it.
+    pub fn store_contract_event(
+        &mut self,
+        event_id: &str,
+        contract_address: Felt,
+        name: &str,
+        data: &serde_json::Value,
+        transaction_hash: Felt,
+        block_timestamp: u64,
+    ) -> Result<()> {
+        let arguments = vec![
+            Argument::String(event_id.to_string()),
+            Argument::FieldElement(contract_address),
+            Argument::String(name.to_string()),
+            Argument::String(data.to_string()),
+            Argument::FieldElement(transaction_hash),
+            Argument::String(utc_dt_string_from_timestamp(block_timestamp)),
+        ];
+
+        self.executor.send(QueryMessage::other(
+            "INSERT OR IGNORE INTO contract_events (id, contract_address, name, data, \
+             transaction_hash, executed_at) VALUES (?, ?, ?, ?, ?, ?)"
+                .to_string(),
+            arguments,
+        ))?;
+
+        Ok(())
+    }
+
+    /// Stores an off-chain message received by the relay. The signature of the message must have
+    /// been verified against its identity beforehand.
+    #[allow(clippy::too_many_arguments)]
+    pub fn store_offchain_message(
+        &mut self,
+        message_id: &str,
+        identity: Felt,
+        entity_id: Felt,
+        model_tag: &str,
+        message: &str,
+        signature: &[Felt],
+        received_at: u64,
+    ) -> Result<()> {
+        let arguments = vec![
+            Argument::String(message_id.to_string()),
+            Argument::FieldElement(identity),
+            Argument::FieldElement(entity_id),
+            Argument::String(model_tag.to_string()),
+            Argument::String(message.to_string()),
+            Argument::String(felts_to_sql_string(signature)),
+            Argument::String(utc_dt_string_from_timestamp(received_at)),
+        ];
+
+        self.executor.send(QueryMessage::new(
+            "INSERT OR IGNORE INTO offchain_messages (id, identity, entity_id, model, message, \
+             signature, received_at) VALUES (?, ?, ?, ?, ?, ?, ?) RETURNING *"
+                .to_string(),
+            arguments,
+            QueryType::StoreOffchainMessage,
+        ))?;
+
+        Ok(())
+    }
+
     fn set_entity_model(
         &mut self,
         model_name: &str,
@@ -0,0 +1,310 @@
+//! Rollback of the entities, the event messages and the token balances to their state at a block,
+//! when the blocks after it are removed by a chain reorganization.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use anyhow::Result;
+use chrono::DateTime;
+use dojo_types::naming::get_tag;
+use dojo_types::schema::Ty;
+use starknet_crypto::Felt;
+use tracing::warn;
+
+use crate::constants::{SQL_FELT_DELIMITER, TOKEN_BALANCE_TABLE, TOKEN_TRANSFER_TABLE};
+use crate::executor::{Argument, QueryMessage};
+use crate::types::ContractType;
+use crate::utils::{sql_string_to_u256, I256};
+use crate::Sql;
+
+pub(crate) const LOG_TARGET: &str = "torii::sqlite::reorg";
+
+/// The state of a model of an entity or of an event message at the block rolled back to.
+#[derive(Debug)]
+struct ModelRestore {
+    table: String,
+    model_id: String,
+    /// The id of the entity, or the id of the event message prefixed by `event:`.
+    internal_id: String,
+    /// The last version of the model, `None` if the model wasn't set or was deleted.
+    version: Option<ModelVersion>,
+}
+
+#[derive(Debug)]
+struct ModelVersion {
+    ty: Ty,
+    event_id: String,
+    executed_at: String,
+    keys: Option<String>,
+}
+
+impl Sql {
+    /// Restores the state of the models set or deleted from `event_id`, and reverts the token
+    /// transfers from it from the balances.
+    ///
+    /// Returns `false` without restoring anything if one of the models isn't historical: without
+    /// their versions, the state of its entities can't be restored.
+    pub(crate) async fn restore_to_event(&mut self, event_id: &str) -> Result<bool> {
+        let Some(restores) = self.model_restores(event_id).await? else {
+            return Ok(false);
+        };
+
+        for restore in restores {
+            self.restore_model(restore, event_id)?;
+        }
+
+        self.revert_token_transfers(event_id).await?;
+        Ok(true)
+    }
+
+    /// Removes the entities, the event messages and the token balances, to index the chain again
+    /// from the start.
+    pub(crate) async fn clear_state(&mut self) -> Result<()> {
+        let models = sqlx::query_as::<_, (String, String)>("SELECT namespace, name FROM models")
+            .fetch_all(&self.pool)
+            .await?;
+        for (namespace, name) in models {
+            self.executor.send(QueryMessage::other(
+                format!("DELETE FROM [{}]", get_tag(&namespace, &name)),
+                vec![],
+            ))?;
+        }
+
+        for table in [
+            "entity_model",
+            "entities",
+            "event_model",
+            "event_messages",
+            TOKEN_BALANCE_TABLE,
+            TOKEN_TRANSFER_TABLE,
+        ] {
+            self.executor.send(QueryMessage::other(format!("DELETE FROM {table}"), vec![]))?;
+        }
+
+        self.local_cache.erc_cache.write().await.clear();
+        Ok(())
+    }
+
+    /// Returns the state before `event_id` of the models set or deleted from it, or `None` if one
+    /// of them isn't historical.
+    async fn model_restores(&self, event_id: &str) -> Result<Option<Vec<ModelRestore>>> {
+        let models =
+            sqlx::query_as::<_, (String, String, String)>("SELECT id, namespace, name FROM models")
+                .fetch_all(&self.pool)
+                .await?;
+
+        let mut restores = Vec::new();
+        for (model_id, namespace, name) in models {
+            let table = get_tag(&namespace, &name);
+
+            let mut internal_ids = sqlx::query_scalar::<_, String>(&format!(
+                "SELECT internal_id FROM [{table}] WHERE internal_event_id >= ?"
+            ))
+            .bind(event_id)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .collect::<HashSet<_>>();
+            internal_ids.extend(
+                sqlx::query_scalar::<_, String>(
+                    "SELECT id FROM entities_deleted WHERE model_id = ? AND event_id >= ?",
+                )
+                .bind(&model_id)
+                .bind(event_id)
+                .fetch_all(&self.pool)
+                .await?,
+            );
+
+            if internal_ids.is_empty() {
+                continue;
+            }
+            if !self.config.historical_models.contains(&table) {
+                warn!(target: LOG_TARGET, model = %table, "Model without history updated in the removed blocks, indexing the chain again from the start.");
+                return Ok(None);
+            }
+
+            let schema = self.model(Felt::from_str(&model_id)?).await?.schema;
+            for internal_id in internal_ids {
+                // the versions before the block, since the entity was last deleted
+                let versions = match internal_id.strip_prefix("event:") {
+                    Some(id) => {
+                        sqlx::query_as::<_, (String, String, String, Option<String>)>(
+                            "SELECT data, event_id, executed_at, keys FROM \
+                             event_messages_historical WHERE id = ? AND model_id = ? AND event_id \
+                             < ? ORDER BY event_id",
+                        )
+                        .bind(id)
+                        .bind(&model_id)
+                        .bind(event_id)
+                        .fetch_all(&self.pool)
+                        .await?
+                    }
+                    None => {
+                        sqlx::query_as::<_, (String, String, String, Option<String>)>(
+                            "SELECT data, event_id, executed_at, keys FROM entities_historical \
+                             WHERE id = ?1 AND model_id = ?2 AND event_id < ?3 AND event_id > \
+                             COALESCE((SELECT MAX(event_id) FROM entities_deleted WHERE id = ?1 \
+                             AND model_id = ?2 AND event_id < ?3), '') ORDER BY event_id",
+                        )
+                        .bind(&internal_id)
+                        .bind(&model_id)
+                        .bind(event_id)
+                        .fetch_all(&self.pool)
+                        .await?
+                    }
+                };
+
+                restores.push(ModelRestore {
+                    table: table.clone(),
+                    model_id: model_id.clone(),
+                    internal_id,
+                    version: merge_versions(&schema, versions)?,
+                });
+            }
+        }
+
+        Ok(Some(restores))
+    }
+
+    fn restore_model(&mut self, restore: ModelRestore, event_id: &str) -> Result<()> {
+        let ModelRestore { table, model_id, internal_id, version } = restore;
+        let (id, entities_table, models_table, historical_table) =
+            match internal_id.strip_prefix("event:") {
+                Some(id) => (id, "event_messages", "event_model", "event_messages_historical"),
+                None => (internal_id.as_str(), "entities", "entity_model", "entities_historical"),
+            };
+
+        // the members set in the removed blocks are unset, if not set by an earlier version
+        self.executor.send(QueryMessage::other(
+            format!("DELETE FROM [{table}] WHERE internal_id = ?"),
+            vec![Argument::String(internal_id.clone())],
+        ))?;
+
+        let Some(version) = version else {
+            self.executor.send(QueryMessage::other(
+                format!("DELETE FROM {models_table} WHERE entity_id = ? AND model_id = ?"),
+                vec![Argument::String(id.to_string()), Argument::String(model_id)],
+            ))?;
+            // the entity is deleted with its last model
+            self.executor.send(QueryMessage::other(
+                format!(
+                    "DELETE FROM {entities_table} WHERE id = ?1 AND NOT EXISTS (SELECT 1 FROM \
+                     {models_table} WHERE entity_id = ?1)"
+                ),
+                vec![Argument::String(id.to_string())],
+            ))?;
+            return Ok(());
+        };
+
+        self.executor.send(QueryMessage::other(
+            format!(
+                "INSERT INTO {entities_table} (id, keys, event_id, executed_at) VALUES (?, ?, ?, \
+                 ?) ON CONFLICT(id) DO UPDATE SET updated_at=CURRENT_TIMESTAMP, \
+                 executed_at=EXCLUDED.executed_at, event_id=EXCLUDED.event_id, \
+                 keys=COALESCE(EXCLUDED.keys, {entities_table}.keys)"
+            ),
+            vec![
+                Argument::String(id.to_string()),
+                version.keys.map_or(Argument::Null, Argument::String),
+                Argument::String(version.event_id.clone()),
+                Argument::String(version.executed_at.clone()),
+            ],
+        ))?;
+
+        // the versions from `event_id` are removed with the rest of the data of the removed blocks
+        self.executor.send(QueryMessage::other(
+            format!(
+                "INSERT INTO {models_table} (entity_id, model_id, historical_counter) VALUES (?1, \
+                 ?2, (SELECT COUNT(*) FROM {historical_table} WHERE id = ?1 AND model_id = ?2 AND \
+                 event_id < ?3)) ON CONFLICT(entity_id, model_id) DO UPDATE SET \
+                 historical_counter=EXCLUDED.historical_counter"
+            ),
+            vec![
+                Argument::String(id.to_string()),
+                Argument::String(model_id),
+                Argument::String(event_id.to_string()),
+            ],
+        ))?;
+
+        let block_timestamp = DateTime::parse_from_rfc3339(&version.executed_at)?.timestamp();
+        self.set_entity_model(
+            &table,
+            &version.event_id,
+            &internal_id,
+            &version.ty,
+            block_timestamp as u64,
+        )
+    }
+
+    /// Reverts the token transfers from `event_id`, by adding their opposites to the balance diffs
+    /// applied by [`Sql::apply_cache_diff`].
+    async fn revert_token_transfers(&self, event_id: &str) -> Result<()> {
+        let transfers = sqlx::query_as::<_, (String, String, String, String, String)>(&format!(
+            "SELECT contract_address, from_address, to_address, amount, token_id FROM \
+             {TOKEN_TRANSFER_TABLE} WHERE event_id >= ?"
+        ))
+        .bind(event_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut erc_cache = self.local_cache.erc_cache.write().await;
+        for (contract_address, from_address, to_address, amount, token_id) in transfers {
+            let amount = I256::from(sql_string_to_u256(&amount));
+
+            // the balances of the ERC20 tokens are identified by `account/contract/`, and the
+            // ones of the NFTs by `account/contract:id`
+            let (contract_type, balance_id) = if token_id.contains(':') {
+                (ContractType::ERC721, format!("{SQL_FELT_DELIMITER}{token_id}"))
+            } else {
+                (
+                    ContractType::ERC20,
+                    format!("{SQL_FELT_DELIMITER}{contract_address}{SQL_FELT_DELIMITER}"),
+                )
+            };
+
+            if Felt::from_str(&from_address)? != Felt::ZERO {
+                *erc_cache
+                    .entry((contract_type, format!("{from_address}{balance_id}")))
+                    .or_default() += amount;
+            }
+            if Felt::from_str(&to_address)? != Felt::ZERO {
+                *erc_cache
+                    .entry((contract_type, format!("{to_address}{balance_id}")))
+                    .or_default() -= amount;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Merges the versions of a model, from the oldest one. The versions set by `StoreUpdateMember`
+/// and `StoreUpdateRecord` only hold the members they update.
+fn merge_versions(
+    schema: &Ty,
+    versions: Vec<(String, String, String, Option<String>)>,
+) -> Result<Option<ModelVersion>> {
+    let mut members = serde_json::Map::new();
+    let mut last_version = None;
+    let mut keys = None;
+    for (data, event_id, executed_at, version_keys) in versions {
+        if let serde_json::Value::Object(version_members) = serde_json::from_str(&data)? {
+            members.extend(version_members);
+        }
+        keys = version_keys.or(keys);
+        last_version = Some((event_id, executed_at));
+    }
+
+    let Some((event_id, executed_at)) = last_version else {
+        return Ok(None);
+    };
+
+    // the members never set are left unset
+    let mut ty = schema.clone();
+    if let Ty::Struct(s) = &mut ty {
+        s.children.retain(|member| members.contains_key(&member.name));
+    }
+    ty.from_json_value(serde_json::Value::Object(members))?;
+
+    Ok(Some(ModelVersion { ty, event_id, executed_at, keys }))
+}
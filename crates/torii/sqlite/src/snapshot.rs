@@ -0,0 +1,131 @@
+//! Snapshots of the indexed database, to bootstrap new indexers without replaying the chain.
+//!
+//! A snapshot is a copy of the database, which records the head of each indexed contract. An
+//! indexer started from an imported snapshot resumes from those heads, and only indexes the blocks
+//! produced since the snapshot was exported.
+
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{Connection, SqliteConnection};
+
+/// The name of the database file in the database directory of the indexer.
+pub const DATABASE_FILE_NAME: &str = "torii.db";
+
+/// The state of the indexer recorded in a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotHead {
+    /// The number of indexed contracts.
+    pub contracts: u64,
+    /// The lowest head of the indexed contracts, ie the block up to which all the contracts are
+    /// indexed.
+    pub head: u64,
+}
+
+/// Exports the database at `database` into a new snapshot file at `output`.
+///
+/// The snapshot is consistent even if an indexer is writing to the database while it is exported.
+pub async fn export(database: &Path, output: &Path) -> Result<SnapshotHead> {
+    if !database.exists() {
+        bail!("Database not found at {}", database.display());
+    }
+    if output.exists() {
+        bail!("Snapshot file {} already exists", output.display());
+    }
+
+    let mut conn = connect(database).await?;
+    let head = snapshot_head(&mut conn).await?;
+
+    sqlx::query("VACUUM INTO ?")
+        .bind(output.to_string_lossy().to_string())
+        .execute(&mut conn)
+        .await
+        .context("Failed to export the database")?;
+
+    Ok(head)
+}
+
+/// Imports the snapshot at `input` as the database at `database`, which must not exist yet.
+///
+/// The snapshot is migrated when the indexer starts, so snapshots exported by an older version can
+/// be imported.
+pub async fn import(input: &Path, database: &Path) -> Result<SnapshotHead> {
+    if !input.exists() {
+        bail!("Snapshot file not found at {}", input.display());
+    }
+    if database.exists() {
+        bail!(
+            "Database {} already exists, the snapshot must be imported into an empty directory",
+            database.display()
+        );
+    }
+
+    let head = snapshot_head(&mut connect(input).await?).await?;
+
+    if let Some(parent) = database.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::copy(input, database).context("Failed to copy the snapshot")?;
+
+    Ok(head)
+}
+
+async fn connect(path: &Path) -> Result<SqliteConnection> {
+    let options = SqliteConnectOptions::from_str(&path.to_string_lossy())?.read_only(true);
+    Ok(SqliteConnection::connect_with(&options).await?)
+}
+
+async fn snapshot_head(conn: &mut SqliteConnection) -> Result<SnapshotHead> {
+    let (contracts, head): (i64, Option<i64>) =
+        sqlx::query_as("SELECT COUNT(*), MIN(head) FROM contracts")
+            .fetch_one(conn)
+            .await
+            .context("Not a Torii database")?;
+
+    Ok(SnapshotHead { contracts: contracts as u64, head: head.unwrap_or_default() as u64 })
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::sqlite::SqlitePoolOptions;
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn export_and_import() {
+        let dir = tempdir().unwrap();
+        let database = dir.path().join("indexer").join(DATABASE_FILE_NAME);
+        std::fs::create_dir_all(database.parent().unwrap()).unwrap();
+
+        let options = SqliteConnectOptions::from_str(&database.to_string_lossy())
+            .unwrap()
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new().connect_with(options).await.unwrap();
+        sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+        sqlx::query(
+            "INSERT INTO contracts (id, contract_address, contract_type, head) VALUES ('0x1', \
+             '0x1', 'WORLD', 10), ('0x2', '0x2', 'ERC20', 7)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool.close().await;
+
+        let snapshot = dir.path().join("snapshot.db");
+        let exported = export(&database, &snapshot).await.unwrap();
+        assert_eq!(exported, SnapshotHead { contracts: 2, head: 7 });
+
+        // the snapshot already exists
+        assert!(export(&database, &snapshot).await.is_err());
+        // the database already exists
+        assert!(import(&snapshot, &database).await.is_err());
+
+        let replica = dir.path().join("replica").join(DATABASE_FILE_NAME);
+        let imported = import(&snapshot, &replica).await.unwrap();
+        assert_eq!(imported, exported);
+        assert!(replica.exists());
+    }
+}
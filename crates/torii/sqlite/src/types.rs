@@ -1,6 +1,7 @@
 use core::fmt;
 use std::collections::HashSet;
 use std::str::FromStr;
+use std::time::Instant;
 
 use chrono::{DateTime, Utc};
 use dojo_types::schema::Ty;
@@ -121,6 +122,20 @@ pub struct Event {
     pub created_at: DateTime<Utc>,
 }
 
+/// An off-chain message received by the relay, whose signature has been verified.
+#[derive(FromRow, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OffchainMessage {
+    pub id: String,
+    pub identity: String,
+    pub entity_id: String,
+    pub model: String,
+    pub message: String,
+    pub signature: String,
+    pub received_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(FromRow, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OptimisticToken {
@@ -178,6 +193,8 @@ pub enum ContractType {
     ERC721,
     ERC1155,
     UDC,
+    /// A contract whose events are decoded using its ABI.
+    ABI,
 }
 
 impl std::fmt::Display for Contract {
@@ -196,6 +213,7 @@ impl FromStr for ContractType {
             "erc721" => Ok(ContractType::ERC721),
             "erc1155" => Ok(ContractType::ERC1155),
             "udc" => Ok(ContractType::UDC),
+            "abi" => Ok(ContractType::ABI),
             _ => Err(anyhow::anyhow!("Invalid ERC type: {}", input)),
         }
     }
@@ -209,10 +227,29 @@ impl std::fmt::Display for ContractType {
             ContractType::ERC721 => write!(f, "ERC721"),
             ContractType::ERC1155 => write!(f, "ERC1155"),
             ContractType::UDC => write!(f, "UDC"),
+            ContractType::ABI => write!(f, "ABI"),
         }
     }
 }
 
+/// The status of the synchronization of the indexer with the chain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SyncStatus {
+    /// The last block processed, `None` until a block is processed.
+    pub head: Option<u64>,
+    /// The latest block of the chain, `None` until the chain is polled.
+    pub latest_block: Option<u64>,
+    /// The last time the chain was polled.
+    pub updated_at: Option<Instant>,
+}
+
+impl SyncStatus {
+    /// The number of blocks of the chain that are not processed yet.
+    pub fn head_lag(&self) -> Option<u64> {
+        self.latest_block.map(|latest| latest.saturating_sub(self.head.unwrap_or_default()))
+    }
+}
+
 #[derive(FromRow, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ContractCursor {
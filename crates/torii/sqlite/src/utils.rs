@@ -32,6 +32,13 @@ pub fn utc_dt_string_from_timestamp(timestamp: u64) -> String {
     must_utc_datetime_from_timestamp(timestamp).to_rfc3339()
 }
 
+/// Returns the block number of an event id, which are formatted as
+/// `<block_number>:<transaction_hash>:<event_index>`.
+pub fn block_number_from_event_id(event_id: &str) -> Option<u64> {
+    let block_number = event_id.split(':').next()?;
+    u64::from_str_radix(block_number.strip_prefix("0x").unwrap_or(block_number), 16).ok()
+}
+
 pub fn felts_to_sql_string(felts: &[Felt]) -> String {
     felts.iter().map(|k| format!("{:#x}", k)).collect::<Vec<String>>().join(SQL_FELT_DELIMITER)
         + SQL_FELT_DELIMITER
@@ -352,6 +359,14 @@ mod tests {
         assert_eq!(out, expected, "Failed to convert timestamp to String");
     }
 
+    #[test]
+    fn test_block_number_from_event_id() {
+        let event_id = format!("{:#064x}:{:#x}:{:#04x}", 42, Felt::ONE, 3);
+        assert_eq!(block_number_from_event_id(&event_id), Some(42));
+        assert_eq!(block_number_from_event_id("0x1a:0x2:0x0003"), Some(26));
+        assert_eq!(block_number_from_event_id("not-an-event-id"), None);
+    }
+
     #[test]
     fn test_add_zero_false_and_zero_false() {
         // 0,false + 0,false == 0,false